@@ -203,7 +203,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpsertPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("UpsertPoints.points", ""),
             ("UpsertPoints.update_filter", ""),
+            ("UpsertPoints.if_payload_matches", ""),
             ("DeletePoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("DeletePoints.if_payload_matches", ""),
             ("UpdatePointVectors.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("UpdatePointVectors.points", ""),
             ("UpdatePointVectors.update_filter", ""),
@@ -214,6 +216,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("GetPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("SetPayloadPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("SetPayloadPoints.points_selector", ""),
+            ("SetPayloadPoints.if_payload_matches", ""),
             ("DeletePayloadPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("DeletePayloadPoints.points_selector", ""),
             ("ClearPayloadPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
@@ -231,6 +234,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPoints.limit", "range(min = 1)"),
             ("SearchPoints.params", ""),
             ("SearchPoints.timeout", "range(min = 1)"),
+            ("SearchPoints.mmr", ""),
             ("SearchBatchPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("SearchBatchPoints.search_points", ""),
             ("SearchBatchPoints.timeout", "range(min = 1)"),
@@ -354,6 +358,7 @@ fn configure_validation(builder: Builder) -> Builder {
         .validates(&[
             ("UpsertPointsInternal.upsert_points", ""),
             ("DeletePointsInternal.delete_points", ""),
+            ("RestorePointsInternal.restore_points", ""),
             ("UpdateVectorsInternal.update_vectors", ""),
             ("DeleteVectorsInternal.delete_vectors", ""),
             ("SetPayloadPointsInternal.set_payload_points", ""),
@@ -385,6 +390,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CountPointsInternal.count_points", ""),
             ("SyncPointsInternal.sync_points", ""),
             ("SyncPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("RestorePoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("QueryBatchPointsInternal.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("QueryBatchPointsInternal.timeout", "range(min = 1)"),
             ("FacetCountsInternal.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
@@ -28,13 +28,15 @@ use uuid::Uuid;
 
 use super::qdrant::{
     BinaryQuantization, BoolIndexParams, CompressionRatio, DatetimeIndexParams, DatetimeRange,
-    Direction, FacetHit, FacetHitInternal, FacetValue, FacetValueInternal, FieldType,
+    DimensionContribution, Direction, ExplanationSpace, ExplanationUnavailableReason,
+    ExplanationVectorSource, FacetHit, FacetHitInternal, FacetValue, FacetValueInternal, FieldType,
     FloatIndexParams, GeoIndexParams, GeoLineString, GroupId, HardwareUsage, HasVectorCondition,
     KeywordIndexParams, LookupLocation, MaxOptimizationThreads, MultiVectorComparator,
     MultiVectorConfig, OrderBy, OrderValue, Range, RawVector, RecommendStrategy, RetrievedPoint,
-    SearchMatrixPair, SearchPointGroups, SearchPoints, ShardKeySelector, StartFrom,
-    StrictModeMultivector, StrictModeMultivectorConfig, StrictModeSparse, StrictModeSparseConfig,
-    UuidIndexParams, VectorsOutput, WithLookup, raw_query, start_from,
+    RocchioParams, ScoreExplanation, SearchMatrixPair, SearchPointGroups, SearchPoints,
+    ShardKeySelector, StartFrom, StrictModeMultivector, StrictModeMultivectorConfig,
+    StrictModeSparse, StrictModeSparseConfig, UuidIndexParams, VectorsOutput, WithLookup,
+    raw_query, start_from,
 };
 use super::stemming_algorithm::StemmingParams;
 use super::{Expression, Formula, RecoQuery, SnowballParams, StemmingAlgorithm, Usage};
@@ -50,13 +52,13 @@ use crate::grpc::qdrant::{
     HealthCheckReply, HnswConfigDiff, IntegerIndexParams, IsEmptyCondition, IsNullCondition,
     ListCollectionsResponse, ListShardKeysResponse, Match, MinShould, NamedVectors,
     NestedCondition, PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams,
-    PayloadSchemaInfo, PayloadSchemaType, PointId, PointStruct, PointsOperationResponse,
-    PointsOperationResponseInternal, ProductQuantization, QuantizationConfig,
-    QuantizationSearchParams, QuantizationType, RepeatedIntegers, RepeatedStrings,
-    ScalarQuantization, ScoredPoint, SearchParams, ShardKey, ShardKeyDescription, StopwordsSet,
-    StrictModeConfig, TextIndexParams, TokenizerType, UpdateResult, UpdateResultInternal,
-    ValuesCount, VectorsSelector, WithPayloadSelector, WithVectorsSelector, shard_key,
-    with_vectors_selector,
+    PayloadSchemaInfo, PayloadSchemaType, PointId, PointIdGeneration, PointStruct,
+    PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
+    QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
+    RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, ShardKeyDescription,
+    StopwordsSet, StrictModeConfig, TextIndexParams, TokenizerType, UpdateResult,
+    UpdateResultInternal, ValuesCount, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    shard_key, with_vectors_selector,
 };
 use crate::grpc::{
     self, BinaryQuantizationEncoding, BinaryQuantizationQueryEncoding, DecayParamsExpression,
@@ -793,6 +795,12 @@ impl From<segment::types::WithPayloadInterface> for WithPayloadSelector {
                         fields: s.exclude.iter().map(|f| f.to_string()).collect(),
                     })
                 }
+                // Transformations are not supported in gRPC, select their source fields instead
+                segment::types::PayloadSelector::Transform(s) => {
+                    SelectorOptions::Include(PayloadIncludeSelector {
+                        fields: s.source_keys().iter().map(|f| f.to_string()).collect(),
+                    })
+                }
             },
         };
         WithPayloadSelector {
@@ -812,6 +820,7 @@ impl From<QuantizationSearchParams> for segment::types::QuantizationSearchParams
             ignore: ignore.unwrap_or(default_quantization_ignore_value()),
             rescore,
             oversampling,
+            compare_explanations: false,
         }
     }
 }
@@ -822,6 +831,7 @@ impl From<segment::types::QuantizationSearchParams> for QuantizationSearchParams
             ignore,
             rescore,
             oversampling,
+            compare_explanations: _, // not sent to remote shards
         } = params;
         Self {
             ignore: Some(ignore),
@@ -872,6 +882,7 @@ impl From<SearchParams> for segment::types::SearchParams {
             quantization: quantization.map(|q| q.into()),
             indexed_only: indexed_only.unwrap_or(false),
             acorn: acorn.map(segment::types::AcornSearchParams::from),
+            explain_search_path: false,
         }
     }
 }
@@ -884,6 +895,7 @@ impl From<segment::types::SearchParams> for SearchParams {
             quantization,
             indexed_only,
             acorn,
+            explain_search_path: _, // not sent to remote shards
         } = params;
         Self {
             hnsw_ef: hnsw_ef.map(|x| x as u64),
@@ -938,6 +950,50 @@ impl TryFrom<PointStruct> for rest::PointStruct {
     }
 }
 
+impl TryFrom<PointStruct> for rest::InsertPointStruct {
+    type Error = Status;
+
+    fn try_from(value: PointStruct) -> Result<Self, Self::Error> {
+        let PointStruct {
+            id,
+            vectors,
+            payload,
+        } = value;
+
+        if id.is_some() {
+            return Err(Status::invalid_argument(
+                "Points must not have ids if ids are assigned by the server",
+            ));
+        }
+
+        // empty payload means None in PointStruct
+        let converted_payload = if payload.is_empty() {
+            None
+        } else {
+            Some(json::proto_to_payloads(payload)?)
+        };
+
+        let vector_struct = match vectors {
+            None => return Err(Status::invalid_argument("Expected some vectors")),
+            Some(vectors) => rest::VectorStruct::try_from(vectors)?,
+        };
+
+        Ok(Self {
+            vector: vector_struct,
+            payload: converted_payload,
+        })
+    }
+}
+
+impl From<PointIdGeneration> for rest::PointIdGeneration {
+    fn from(value: PointIdGeneration) -> Self {
+        match value {
+            PointIdGeneration::Uuid => rest::PointIdGeneration::Uuid,
+            PointIdGeneration::Sequential => rest::PointIdGeneration::Sequential,
+        }
+    }
+}
+
 impl TryFrom<rest::Record> for RetrievedPoint {
     type Error = OperationError;
     fn try_from(record: rest::Record) -> Result<Self, Self::Error> {
@@ -1009,7 +1065,7 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
             vector,
             shard_key,
             order_value,
-            score_explanation: _, // Note: gRPC doesn't support score_explanation yet
+            score_explanation,
         } = point;
         Self {
             id: Some(PointId::from(id)),
@@ -1019,6 +1075,7 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
             vectors: vector.map(VectorsOutput::from),
             shard_key: shard_key.map(convert_shard_key_to_grpc),
             order_value: order_value.map(OrderValue::from),
+            score_explanation: score_explanation.map(ScoreExplanation::from),
         }
     }
 }
@@ -1034,7 +1091,7 @@ impl TryFrom<rest::ScoredPoint> for ScoredPoint {
             vector,
             shard_key,
             order_value,
-            score_explanation: _, // no support for gRPC
+            score_explanation: _, // REST output drops the explanation status, convert from segment instead
         } = point;
         Ok(Self {
             id: Some(PointId::from(id)),
@@ -1044,6 +1101,164 @@ impl TryFrom<rest::ScoredPoint> for ScoredPoint {
             vectors: vector.map(VectorsOutput::try_from).transpose()?,
             shard_key: shard_key.map(convert_shard_key_to_grpc),
             order_value: order_value.map(OrderValue::from),
+            score_explanation: None,
+        })
+    }
+}
+
+impl From<common::types::ScoreExplanation> for ScoreExplanation {
+    fn from(explanation: common::types::ScoreExplanation) -> Self {
+        use common::types as common_types;
+
+        let common_types::ScoreExplanation {
+            top_dimensions,
+            total_score,
+            approximate_search,
+            space,
+            vector_source,
+            status,
+            bundle: _, // not exposed over gRPC
+            verbosity: _,
+            query_norm: _,
+            stored_norm: _,
+            baseline_explanation: _,
+            vector_name,
+            dominant_examples: _,  // not exposed over gRPC
+            quantization_error: _, // not exposed over gRPC
+            fusion: _,             // not exposed over gRPC
+        } = explanation;
+
+        let space = match space {
+            common_types::ExplanationSpace::Original => ExplanationSpace::OriginalSpace,
+            common_types::ExplanationSpace::Processed => ExplanationSpace::ProcessedSpace,
+        };
+        let vector_source = match vector_source {
+            common_types::VectorSource::Original => ExplanationVectorSource::OriginalVectors,
+            common_types::VectorSource::Quantized => ExplanationVectorSource::QuantizedVectors,
+        };
+        let unavailable_reason = match status {
+            common_types::ExplanationStatus::Available => None,
+            common_types::ExplanationStatus::Unavailable(reason) => Some(match reason {
+                common_types::UnavailableReason::OrderByQuery => {
+                    ExplanationUnavailableReason::OrderByQuery
+                }
+                common_types::UnavailableReason::UnsupportedQuery => {
+                    ExplanationUnavailableReason::UnsupportedQuery
+                }
+                common_types::UnavailableReason::VectorMissing => {
+                    ExplanationUnavailableReason::VectorMissing
+                }
+                common_types::UnavailableReason::Timeout => ExplanationUnavailableReason::Timeout,
+            }),
+        };
+
+        Self {
+            top_dimensions: top_dimensions
+                .into_iter()
+                .map(|dim| DimensionContribution {
+                    dimension: dim.dimension as u64,
+                    contribution: dim.contribution,
+                    label: dim.label,
+                })
+                .collect(),
+            total_score,
+            approximate_search,
+            space: space as i32,
+            vector_source: vector_source as i32,
+            unavailable_reason: unavailable_reason.map(|reason| reason as i32),
+            vector_name,
+        }
+    }
+}
+
+impl TryFrom<ScoreExplanation> for common::types::ScoreExplanation {
+    type Error = Status;
+
+    fn try_from(explanation: ScoreExplanation) -> Result<Self, Self::Error> {
+        use common::types as common_types;
+
+        let ScoreExplanation {
+            top_dimensions,
+            total_score,
+            approximate_search,
+            space,
+            vector_source,
+            unavailable_reason,
+            vector_name,
+        } = explanation;
+
+        let space = match ExplanationSpace::try_from(space) {
+            Ok(ExplanationSpace::OriginalSpace) => common_types::ExplanationSpace::Original,
+            Ok(ExplanationSpace::ProcessedSpace) => common_types::ExplanationSpace::Processed,
+            Err(_) => {
+                return Err(Status::invalid_argument(format!(
+                    "Unknown explanation space: {space}"
+                )));
+            }
+        };
+        let vector_source = match ExplanationVectorSource::try_from(vector_source) {
+            Ok(ExplanationVectorSource::OriginalVectors) => common_types::VectorSource::Original,
+            Ok(ExplanationVectorSource::QuantizedVectors) => common_types::VectorSource::Quantized,
+            Err(_) => {
+                return Err(Status::invalid_argument(format!(
+                    "Unknown explanation vector source: {vector_source}"
+                )));
+            }
+        };
+        let status = match unavailable_reason.map(ExplanationUnavailableReason::try_from) {
+            None => common_types::ExplanationStatus::Available,
+            Some(Ok(reason)) => {
+                let reason = match reason {
+                    ExplanationUnavailableReason::OrderByQuery => {
+                        common_types::UnavailableReason::OrderByQuery
+                    }
+                    ExplanationUnavailableReason::UnsupportedQuery => {
+                        common_types::UnavailableReason::UnsupportedQuery
+                    }
+                    ExplanationUnavailableReason::VectorMissing => {
+                        common_types::UnavailableReason::VectorMissing
+                    }
+                    ExplanationUnavailableReason::Timeout => {
+                        common_types::UnavailableReason::Timeout
+                    }
+                    ExplanationUnavailableReason::UnknownUnavailableReason => {
+                        return Err(Status::invalid_argument(
+                            "Unknown explanation unavailable reason",
+                        ));
+                    }
+                };
+                common_types::ExplanationStatus::Unavailable(reason)
+            }
+            Some(Err(_)) => {
+                return Err(Status::invalid_argument(
+                    "Unknown explanation unavailable reason",
+                ));
+            }
+        };
+
+        Ok(Self {
+            top_dimensions: top_dimensions
+                .into_iter()
+                .map(|dim| common_types::DimensionContribution {
+                    dimension: dim.dimension as usize,
+                    contribution: dim.contribution,
+                    label: dim.label,
+                })
+                .collect(),
+            total_score,
+            approximate_search,
+            space,
+            vector_source,
+            status,
+            bundle: None,
+            verbosity: Default::default(),
+            query_norm: None,
+            stored_norm: None,
+            baseline_explanation: None,
+            vector_name,
+            dominant_examples: Vec::new(),
+            quantization_error: Vec::new(),
+            fusion: None,
         })
     }
 }
@@ -2103,6 +2318,7 @@ impl TryFrom<OrderBy> for segment::data_types::order_by::OrderBy {
             key: json::json_path_from_proto(&key)?,
             direction,
             start_from,
+            then_by: vec![],
         })
     }
 }
@@ -2113,6 +2329,8 @@ impl From<segment::data_types::order_by::OrderBy> for OrderBy {
             key,
             direction,
             start_from,
+            // Ordering by multiple keys is not supported in gRPC
+            then_by: _,
         } = value;
         Self {
             key: key.to_string(),
@@ -2660,10 +2878,12 @@ impl From<UpdateResultInternal> for UpdateResult {
             operation_id,
             status,
             clock_tag: _,
+            assigned_ids,
         } = res;
         Self {
             operation_id,
             status,
+            assigned_ids,
         }
     }
 }
@@ -2674,11 +2894,13 @@ impl From<UpdateResult> for UpdateResultInternal {
         let UpdateResult {
             operation_id,
             status,
+            assigned_ids,
         } = res;
         Self {
             operation_id,
             status,
             clock_tag: None,
+            assigned_ids,
         }
     }
 }
@@ -2689,10 +2911,18 @@ impl From<RecommendStrategy> for crate::rest::RecommendStrategy {
             RecommendStrategy::AverageVector => crate::rest::RecommendStrategy::AverageVector,
             RecommendStrategy::BestScore => crate::rest::RecommendStrategy::BestScore,
             RecommendStrategy::SumScores => crate::rest::RecommendStrategy::SumScores,
+            RecommendStrategy::Rocchio => crate::rest::RecommendStrategy::Rocchio,
         }
     }
 }
 
+impl From<RocchioParams> for rest::RocchioParams {
+    fn from(value: RocchioParams) -> Self {
+        let RocchioParams { alpha, beta } = value;
+        Self { alpha, beta }
+    }
+}
+
 impl TryFrom<i32> for rest::RecommendStrategy {
     type Error = Status;
 
@@ -2920,6 +3150,8 @@ impl TryFrom<SearchPoints> for rest::SearchRequestInternal {
             timeout: _,
             shard_key_selector: _,
             sparse_indices,
+            with_explanation,
+            mmr,
         } = value;
 
         let vector_internal =
@@ -2949,7 +3181,16 @@ impl TryFrom<SearchPoints> for rest::SearchRequestInternal {
                     .unwrap_or_default(),
             ),
             score_threshold,
-            with_explanation: Some(false),
+            with_explanation,
+            with_explanation_bundle: Some(false),
+            explanation_verbosity: None,
+            explanation_params: None,
+            explanation_mode: None,
+            mmr: mmr.map(|mmr| rest::Mmr {
+                diversity: mmr.diversity,
+                candidates_limit: mmr.candidates_limit.map(|limit| limit as usize),
+            }),
+            distinct_by: None,
         })
     }
 }
@@ -2991,6 +3232,8 @@ impl TryFrom<SearchPointGroups> for rest::SearchGroupsRequestInternal {
             timeout,
             shard_key_selector,
             sparse_indices,
+            with_explanation: None,
+            mmr: None,
         };
 
         if let Some(sparse_indices) = &search_points.sparse_indices {
@@ -3013,6 +3256,12 @@ impl TryFrom<SearchPointGroups> for rest::SearchGroupsRequestInternal {
             with_vector,
             score_threshold,
             with_explanation: _,
+            with_explanation_bundle: _,
+            explanation_verbosity: _,
+            explanation_params: _,
+            explanation_mode: _,
+            mmr: _,
+            distinct_by: _,
         } = rest::SearchRequestInternal::try_from(search_points)?;
 
         Ok(Self {
@@ -3029,7 +3278,9 @@ impl TryFrom<SearchPointGroups> for rest::SearchGroupsRequestInternal {
                 with_lookup: with_lookup
                     .map(rest::WithLookupInterface::try_from)
                     .transpose()?,
+                aggregations: None, // no gRPC
             },
+            with_explanation: None, // no gRPC
         })
     }
 }
@@ -3175,7 +3426,12 @@ impl From<segment_facets::FacetValue> for FacetValue {
 
 impl From<rest::SearchMatrixPair> for SearchMatrixPair {
     fn from(pair: rest::SearchMatrixPair) -> Self {
-        let rest::SearchMatrixPair { a, b, score } = pair;
+        let rest::SearchMatrixPair {
+            a,
+            b,
+            score,
+            top_dimensions: _, // not exposed over gRPC
+        } = pair;
         Self {
             a: Some(a.into()),
             b: Some(b.into()),
@@ -3387,3 +3643,101 @@ impl From<Modifier> for grpc::Modifier {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use common::types as common_types;
+
+    use super::*;
+
+    #[test]
+    fn test_score_explanation_round_trip() {
+        let explanation = common_types::ScoreExplanation {
+            top_dimensions: vec![
+                common_types::DimensionContribution {
+                    dimension: 3,
+                    contribution: 0.5,
+                    label: None,
+                },
+                common_types::DimensionContribution {
+                    dimension: 0,
+                    contribution: -0.25,
+                    label: None,
+                },
+            ],
+            total_score: 0.3,
+            approximate_search: true,
+            space: common_types::ExplanationSpace::Processed,
+            vector_source: common_types::VectorSource::Quantized,
+            status: common_types::ExplanationStatus::Available,
+            bundle: None,
+            verbosity: Default::default(),
+            query_norm: None,
+            stored_norm: None,
+            baseline_explanation: None,
+            vector_name: Some("image".to_string()),
+            dominant_examples: Vec::new(),
+            quantization_error: Vec::new(),
+            fusion: None,
+        };
+
+        let grpc_explanation = ScoreExplanation::from(explanation.clone());
+        assert_eq!(grpc_explanation.unavailable_reason, None);
+        let converted = common_types::ScoreExplanation::try_from(grpc_explanation).unwrap();
+
+        assert_eq!(converted.top_dimensions, explanation.top_dimensions);
+        assert_eq!(converted.total_score, explanation.total_score);
+        assert_eq!(converted.approximate_search, explanation.approximate_search);
+        assert_eq!(converted.space, explanation.space);
+        assert_eq!(converted.vector_source, explanation.vector_source);
+        assert_eq!(converted.status, explanation.status);
+        assert_eq!(converted.vector_name, explanation.vector_name);
+
+        let unavailable = common_types::ScoreExplanation {
+            status: common_types::ExplanationStatus::Unavailable(
+                common_types::UnavailableReason::VectorMissing,
+            ),
+            ..Default::default()
+        };
+        let converted =
+            common_types::ScoreExplanation::try_from(ScoreExplanation::from(unavailable)).unwrap();
+        assert_eq!(
+            converted.status,
+            common_types::ExplanationStatus::Unavailable(
+                common_types::UnavailableReason::VectorMissing,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_scored_point_without_explanation() {
+        let point = segment::types::ScoredPoint {
+            id: 1.into(),
+            version: 0,
+            score: 1.0,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: None,
+        };
+        assert_eq!(ScoredPoint::from(point).score_explanation, None);
+    }
+
+    #[test]
+    fn test_search_points_with_explanation() {
+        let search_points = |with_explanation| SearchPoints {
+            collection_name: "test".into(),
+            vector: vec![1.0, 2.0],
+            limit: 3,
+            with_explanation,
+            ..Default::default()
+        };
+
+        let request = rest::SearchRequestInternal::try_from(search_points(Some(true))).unwrap();
+        assert_eq!(request.with_explanation, Some(true));
+
+        let request = rest::SearchRequestInternal::try_from(search_points(None)).unwrap();
+        assert_eq!(request.with_explanation, None);
+    }
+}
@@ -1610,7 +1610,7 @@ pub struct ChangeAliases {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AliasOperations {
-    #[prost(oneof = "alias_operations::Action", tags = "1, 2, 3")]
+    #[prost(oneof = "alias_operations::Action", tags = "1, 2, 3, 4")]
     pub action: ::core::option::Option<alias_operations::Action>,
 }
 /// Nested message and enum types in `AliasOperations`.
@@ -1625,6 +1625,8 @@ pub mod alias_operations {
         RenameAlias(super::RenameAlias),
         #[prost(message, tag = "3")]
         DeleteAlias(super::DeleteAlias),
+        #[prost(message, tag = "4")]
+        SwapAliases(super::SwapAliases),
     }
 }
 #[derive(serde::Serialize)]
@@ -1657,6 +1659,17 @@ pub struct DeleteAlias {
     #[prost(string, tag = "1")]
     pub alias_name: ::prost::alloc::string::String,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SwapAliases {
+    /// Name of the first alias
+    #[prost(string, tag = "1")]
+    pub first_alias_name: ::prost::alloc::string::String,
+    /// Name of the second alias
+    #[prost(string, tag = "2")]
+    pub second_alias_name: ::prost::alloc::string::String,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4898,6 +4911,23 @@ pub struct UpsertPoints {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "7")]
     pub timeout: ::core::option::Option<u64>,
+    /// If specified, all points must exist with this version on the shard's leader replica, otherwise the request fails with a conflict
+    #[prost(uint64, optional, tag = "8")]
+    pub if_version: ::core::option::Option<u64>,
+    /// If specified, all points must exist and match this filter, otherwise the request fails with a conflict
+    #[prost(message, optional, tag = "9")]
+    #[validate(nested)]
+    pub if_payload_matches: ::core::option::Option<Filter>,
+    /// If specified, ids are assigned to the points by the server, points must not have ids.
+    /// Points are only inserted, the request fails with a conflict if an assigned id is taken.
+    #[prost(enumeration = "PointIdGeneration", optional, tag = "10")]
+    pub id_generation: ::core::option::Option<i32>,
+    /// If true, none of the points may exist yet, otherwise the request fails with a conflict
+    #[prost(bool, optional, tag = "11")]
+    pub if_not_exists: ::core::option::Option<bool>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "12")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4926,6 +4956,16 @@ pub struct DeletePoints {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "6")]
     pub timeout: ::core::option::Option<u64>,
+    /// If specified, all points must exist with this version on the shard's leader replica, otherwise the request fails with a conflict
+    #[prost(uint64, optional, tag = "7")]
+    pub if_version: ::core::option::Option<u64>,
+    /// If specified, all points must exist and match this filter, otherwise the request fails with a conflict
+    #[prost(message, optional, tag = "8")]
+    #[validate(nested)]
+    pub if_payload_matches: ::core::option::Option<Filter>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "9")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4990,6 +5030,9 @@ pub struct UpdatePointVectors {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "7")]
     pub timeout: ::core::option::Option<u64>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "8")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5035,6 +5078,9 @@ pub struct DeletePointVectors {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "7")]
     pub timeout: ::core::option::Option<u64>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "8")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5070,6 +5116,16 @@ pub struct SetPayloadPoints {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "9")]
     pub timeout: ::core::option::Option<u64>,
+    /// If specified, all points must exist with this version on the shard's leader replica, otherwise the request fails with a conflict
+    #[prost(uint64, optional, tag = "10")]
+    pub if_version: ::core::option::Option<u64>,
+    /// If specified, all points must exist and match this filter, otherwise the request fails with a conflict
+    #[prost(message, optional, tag = "11")]
+    #[validate(nested)]
+    pub if_payload_matches: ::core::option::Option<Filter>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "12")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5102,6 +5158,9 @@ pub struct DeletePayloadPoints {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "8")]
     pub timeout: ::core::option::Option<u64>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "9")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5131,6 +5190,9 @@ pub struct ClearPayloadPoints {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "6")]
     pub timeout: ::core::option::Option<u64>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "7")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5164,6 +5226,9 @@ pub struct CreateFieldIndexCollection {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "7")]
     pub timeout: ::core::option::Option<u64>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "8")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5190,6 +5255,9 @@ pub struct DeleteFieldIndexCollection {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "5")]
     pub timeout: ::core::option::Option<u64>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "6")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5444,6 +5512,13 @@ pub struct SearchPoints {
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
     #[prost(message, optional, tag = "15")]
     pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// If true, return an explanation of which dimensions contributed most to each result's score.
+    #[prost(bool, optional, tag = "16")]
+    pub with_explanation: ::core::option::Option<bool>,
+    /// Rerank the results with MMR (Maximal Marginal Relevance), trading relevance for diversity
+    #[prost(message, optional, tag = "17")]
+    #[validate(nested)]
+    pub mmr: ::core::option::Option<Mmr>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5625,6 +5700,18 @@ pub struct ScrollPoints {
     #[prost(uint64, optional, tag = "11")]
     pub timeout: ::core::option::Option<u64>,
 }
+/// Weights of the `Rocchio` recommend strategy
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RocchioParams {
+    /// Weight of the average of positive vectors. Default: 1.0
+    #[prost(float, optional, tag = "1")]
+    pub alpha: ::core::option::Option<f32>,
+    /// Weight of the average of negative vectors. Default: 0.2
+    #[prost(float, optional, tag = "2")]
+    pub beta: ::core::option::Option<f32>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -5706,6 +5793,9 @@ pub struct RecommendPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "20")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Weights of the `Rocchio` strategy
+    #[prost(message, optional, tag = "21")]
+    pub rocchio: ::core::option::Option<RocchioParams>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5809,6 +5899,9 @@ pub struct RecommendPointGroups {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "21")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Weights of the `Rocchio` strategy
+    #[prost(message, optional, tag = "22")]
+    pub rocchio: ::core::option::Option<RocchioParams>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5933,6 +6026,9 @@ pub struct DiscoverBatchPoints {
     #[prost(uint64, optional, tag = "4")]
     #[validate(range(min = 1))]
     pub timeout: ::core::option::Option<u64>,
+    /// Context pairs shared by all searches, added to the context of each of them
+    #[prost(message, repeated, tag = "5")]
+    pub context: ::prost::alloc::vec::Vec<ContextExamplePair>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5979,6 +6075,9 @@ pub struct RecommendInput {
     /// How to use the provided vectors to find the results
     #[prost(enumeration = "RecommendStrategy", optional, tag = "3")]
     pub strategy: ::core::option::Option<i32>,
+    /// Weights of the `Rocchio` strategy
+    #[prost(message, optional, tag = "4")]
+    pub rocchio: ::core::option::Option<RocchioParams>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -6301,7 +6400,7 @@ pub struct PrefetchQuery {
     #[prost(message, optional, tag = "4")]
     #[validate(nested)]
     pub filter: ::core::option::Option<Filter>,
-    /// Search params for when there is no prefetch.
+    /// Search params of this prefetch, used by its own search, or to rescore its nested prefetches.
     #[prost(message, optional, tag = "5")]
     #[validate(nested)]
     pub params: ::core::option::Option<SearchParams>,
@@ -6381,6 +6480,9 @@ pub struct QueryPoints {
     #[prost(uint64, optional, tag = "15")]
     #[validate(range(min = 1))]
     pub timeout: ::core::option::Option<u64>,
+    /// If true, return an explanation of which dimensions contributed most to each result's score.
+    #[prost(bool, optional, tag = "16")]
+    pub with_explanation: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -6804,6 +6906,9 @@ pub struct UpdateBatchPoints {
     /// Timeout for the operation in seconds
     #[prost(uint64, optional, tag = "5")]
     pub timeout: ::core::option::Option<u64>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "6")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -6827,6 +6932,9 @@ pub struct UpdateResult {
     /// Operation status
     #[prost(enumeration = "UpdateStatus", tag = "2")]
     pub status: i32,
+    /// Ids assigned by the server, in the order of the points in the request
+    #[prost(message, repeated, tag = "4")]
+    pub assigned_ids: ::prost::alloc::vec::Vec<PointId>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -6872,6 +6980,50 @@ pub struct ScoredPoint {
     /// Order by value
     #[prost(message, optional, tag = "8")]
     pub order_value: ::core::option::Option<OrderValue>,
+    /// Which dimensions contributed most to the score, if requested
+    #[prost(message, optional, tag = "9")]
+    pub score_explanation: ::core::option::Option<ScoreExplanation>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DimensionContribution {
+    /// Index of the dimension
+    #[prost(uint64, tag = "1")]
+    pub dimension: u64,
+    /// Part of the score coming from this dimension
+    #[prost(float, tag = "2")]
+    pub contribution: f32,
+    /// Label of the dimension, if the collection has dimension labels
+    #[prost(string, optional, tag = "3")]
+    pub label: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScoreExplanation {
+    /// Dimensions which contributed most to the score, largest contribution first
+    #[prost(message, repeated, tag = "1")]
+    pub top_dimensions: ::prost::alloc::vec::Vec<DimensionContribution>,
+    /// Score reconstructed from the contributions of all dimensions
+    #[prost(float, tag = "2")]
+    pub total_score: f32,
+    /// True if the point was found by an approximate search, e.g. by traversing an HNSW graph
+    /// rather than scanning all vectors of its segment
+    #[prost(bool, tag = "3")]
+    pub approximate_search: bool,
+    /// Space of the vectors the contributions were computed on
+    #[prost(enumeration = "ExplanationSpace", tag = "4")]
+    pub space: i32,
+    /// Storage the vector of the point was read from
+    #[prost(enumeration = "ExplanationVectorSource", tag = "5")]
+    pub vector_source: i32,
+    /// Set if no contributions could be computed for this point
+    #[prost(enumeration = "ExplanationUnavailableReason", optional, tag = "6")]
+    pub unavailable_reason: ::core::option::Option<i32>,
+    /// Name of the vector the contributions were computed on
+    #[prost(string, optional, tag = "7")]
+    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -7295,6 +7447,44 @@ impl WriteOrderingType {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum UpdateAck {
+    /// Once the operation is accepted, it may still fail without the client noticing
+    None = 0,
+    /// Once the operation is written into the WAL and queued to be applied, same as `wait=false`.
+    /// The WAL is not flushed to disk before responding, so the operation may be lost on a crash
+    Queued = 1,
+    /// Once the operation is applied and visible to search, same as `wait=true`
+    Applied = 2,
+    /// Once the operation is applied on the majority of replicas of the affected shards
+    Replicated = 3,
+}
+impl UpdateAck {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            UpdateAck::None => "UpdateAckNone",
+            UpdateAck::Queued => "UpdateAckQueued",
+            UpdateAck::Applied => "UpdateAckApplied",
+            UpdateAck::Replicated => "UpdateAckReplicated",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UpdateAckNone" => Some(Self::None),
+            "UpdateAckQueued" => Some(Self::Queued),
+            "UpdateAckApplied" => Some(Self::Applied),
+            "UpdateAckReplicated" => Some(Self::Replicated),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ReadConsistencyType {
     /// Send request to all nodes and return points which are present on all of them
     All = 0,
@@ -7328,6 +7518,35 @@ impl ReadConsistencyType {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum PointIdGeneration {
+    /// Random UUIDs
+    Uuid = 0,
+    /// Increasing unsigned integers, composed of the current time and the id of the peer
+    Sequential = 1,
+}
+impl PointIdGeneration {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PointIdGeneration::Uuid => "Uuid",
+            PointIdGeneration::Sequential => "Sequential",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Uuid" => Some(Self::Uuid),
+            "Sequential" => Some(Self::Sequential),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum FieldType {
     Keyword = 0,
     Integer = 1,
@@ -7412,6 +7631,9 @@ pub enum RecommendStrategy {
     /// Uses custom search objective. Compares against all inputs, sums all the scores.
     /// Scores against positive vectors are added, against negatives are subtracted.
     SumScores = 2,
+    /// Create a single query with the Rocchio formula
+    /// `query = alpha * avg_pos - beta * avg_neg`. Then performs normal search.
+    Rocchio = 3,
 }
 impl RecommendStrategy {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -7423,6 +7645,7 @@ impl RecommendStrategy {
             RecommendStrategy::AverageVector => "AverageVector",
             RecommendStrategy::BestScore => "BestScore",
             RecommendStrategy::SumScores => "SumScores",
+            RecommendStrategy::Rocchio => "Rocchio",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -7431,6 +7654,7 @@ impl RecommendStrategy {
             "AverageVector" => Some(Self::AverageVector),
             "BestScore" => Some(Self::BestScore),
             "SumScores" => Some(Self::SumScores),
+            "Rocchio" => Some(Self::Rocchio),
             _ => None,
         }
     }
@@ -7529,6 +7753,104 @@ impl UpdateStatus {
         }
     }
 }
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ExplanationSpace {
+    /// Vectors as they were inserted
+    OriginalSpace = 0,
+    /// Vectors as they are stored, after ingest preprocessing
+    ProcessedSpace = 1,
+}
+impl ExplanationSpace {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ExplanationSpace::OriginalSpace => "OriginalSpace",
+            ExplanationSpace::ProcessedSpace => "ProcessedSpace",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "OriginalSpace" => Some(Self::OriginalSpace),
+            "ProcessedSpace" => Some(Self::ProcessedSpace),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ExplanationVectorSource {
+    /// Original vector storage
+    OriginalVectors = 0,
+    /// Dequantized from the quantized storage, contributions are approximate
+    QuantizedVectors = 1,
+}
+impl ExplanationVectorSource {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ExplanationVectorSource::OriginalVectors => "OriginalVectors",
+            ExplanationVectorSource::QuantizedVectors => "QuantizedVectors",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "OriginalVectors" => Some(Self::OriginalVectors),
+            "QuantizedVectors" => Some(Self::QuantizedVectors),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ExplanationUnavailableReason {
+    UnknownUnavailableReason = 0,
+    /// Points are ordered by a payload field, not by a vector score
+    OrderByQuery = 1,
+    /// The kind of query doesn't support explanations
+    UnsupportedQuery = 2,
+    /// The vector of the point couldn't be retrieved
+    VectorMissing = 3,
+    /// Explanations were skipped to finish the request within its timeout
+    Timeout = 4,
+}
+impl ExplanationUnavailableReason {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ExplanationUnavailableReason::UnknownUnavailableReason => "UnknownUnavailableReason",
+            ExplanationUnavailableReason::OrderByQuery => "OrderByQuery",
+            ExplanationUnavailableReason::UnsupportedQuery => "UnsupportedQuery",
+            ExplanationUnavailableReason::VectorMissing => "VectorMissing",
+            ExplanationUnavailableReason::Timeout => "Timeout",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownUnavailableReason" => Some(Self::UnknownUnavailableReason),
+            "OrderByQuery" => Some(Self::OrderByQuery),
+            "UnsupportedQuery" => Some(Self::UnsupportedQuery),
+            "VectorMissing" => Some(Self::VectorMissing),
+            "Timeout" => Some(Self::Timeout),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod points_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -9969,6 +10291,9 @@ pub struct SyncPoints {
     /// Timeout for the request in seconds
     #[prost(uint64, optional, tag = "7")]
     pub timeout: ::core::option::Option<u64>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "8")]
+    pub ack: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -9987,6 +10312,46 @@ pub struct SyncPointsInternal {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestorePoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name_legacy")
+    )]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Soft deleted points to restore
+    #[prost(message, repeated, tag = "3")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Timeout for the request in seconds
+    #[prost(uint64, optional, tag = "5")]
+    pub timeout: ::core::option::Option<u64>,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[prost(enumeration = "UpdateAck", optional, tag = "6")]
+    pub ack: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestorePointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate(nested)]
+    pub restore_points: ::core::option::Option<RestorePoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "3")]
+    pub clock_tag: ::core::option::Option<ClockTag>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpsertPointsInternal {
     #[prost(message, optional, tag = "1")]
     #[validate(nested)]
@@ -10008,6 +10373,9 @@ pub struct DeletePointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Time the points are soft deleted at, set by the leader
+    #[prost(message, optional, tag = "4")]
+    pub deleted_at: ::core::option::Option<::prost_wkt_types::Timestamp>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -10111,7 +10479,7 @@ pub struct DeleteFieldIndexCollectionInternal {
 pub struct UpdateOperation {
     #[prost(
         oneof = "update_operation::Update",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11"
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12"
     )]
     #[validate(nested)]
     pub update: ::core::option::Option<update_operation::Update>,
@@ -10144,6 +10512,8 @@ pub mod update_operation {
         CreateFieldIndex(super::CreateFieldIndexCollectionInternal),
         #[prost(message, tag = "11")]
         DeleteFieldIndex(super::DeleteFieldIndexCollectionInternal),
+        #[prost(message, tag = "12")]
+        Restore(super::RestorePointsInternal),
     }
 }
 #[derive(serde::Serialize)]
@@ -10183,6 +10553,8 @@ pub struct UpdateResultInternal {
     pub status: i32,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    #[prost(message, repeated, tag = "4")]
+    pub assigned_ids: ::prost::alloc::vec::Vec<PointId>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -10918,6 +11290,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "Delete"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn restore(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RestorePointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Restore",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Restore"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn update_vectors(
             &mut self,
             request: impl tonic::IntoRequest<super::UpdateVectorsInternal>,
@@ -11338,6 +11735,13 @@ pub mod points_internal_server {
             tonic::Response<super::PointsOperationResponseInternal>,
             tonic::Status,
         >;
+        async fn restore(
+            &self,
+            request: tonic::Request<super::RestorePointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        >;
         async fn update_vectors(
             &self,
             request: tonic::Request<super::UpdateVectorsInternal>,
@@ -11659,6 +12063,52 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/Restore" => {
+                    #[allow(non_camel_case_types)]
+                    struct RestoreSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::RestorePointsInternal>
+                    for RestoreSvc<T> {
+                        type Response = super::PointsOperationResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RestorePointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::restore(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RestoreSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/UpdateVectors" => {
                     #[allow(non_camel_case_types)]
                     struct UpdateVectorsSvc<T: PointsInternal>(pub Arc<T>);
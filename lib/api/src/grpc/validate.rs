@@ -224,6 +224,7 @@ impl Validate for grpc::update_operation::Update {
             Update::Sync(op) => op.validate(),
             Update::Upsert(op) => op.validate(),
             Update::Delete(op) => op.validate(),
+            Update::Restore(op) => op.validate(),
             Update::UpdateVectors(op) => op.validate(),
             Update::DeleteVectors(op) => op.validate(),
             Update::SetPayload(op) => op.validate(),
@@ -6,8 +6,8 @@ use uuid::Uuid;
 
 use super::schema::{ScoredPoint, Vector};
 use super::{
-    FacetRequestInternal, FacetResponse, FacetValue, FacetValueHit, NearestQuery, OrderByInterface,
-    Query, QueryInterface, VectorOutput, VectorStructOutput,
+    FacetRequestInternal, FacetResponse, FacetValue, FacetValueHit, HistogramRequestInternal,
+    NearestQuery, OrderByInterface, Query, QueryInterface, VectorOutput, VectorStructOutput,
 };
 use crate::grpc;
 use crate::rest::models::InferenceUsage;
@@ -136,6 +136,7 @@ impl From<OrderByInterface> for OrderBy {
                 key,
                 direction: None,
                 start_from: None,
+                then_by: vec![],
             },
             OrderByInterface::Struct(order_by) => order_by,
         }
@@ -186,6 +187,29 @@ impl From<segment::data_types::facets::FacetResponse> for FacetResponse {
     }
 }
 
+impl From<HistogramRequestInternal> for segment::data_types::histogram::HistogramParams {
+    fn from(value: HistogramRequestInternal) -> Self {
+        let HistogramRequestInternal {
+            key,
+            buckets,
+            filter,
+            min,
+            max,
+            percentiles,
+            exact,
+        } = value;
+        Self {
+            key,
+            buckets: buckets.unwrap_or(Self::DEFAULT_BUCKETS),
+            filter,
+            min,
+            max,
+            percentiles: percentiles.unwrap_or_default(),
+            exact: exact.unwrap_or(Self::DEFAULT_EXACT),
+        }
+    }
+}
+
 impl From<FacetRequestInternal> for segment::data_types::facets::FacetParams {
     fn from(value: FacetRequestInternal) -> Self {
         let FacetRequestInternal {
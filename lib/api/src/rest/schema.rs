@@ -2,7 +2,11 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
-use common::types::{DimensionContribution, ScoreExplanation, ScoreType};
+use common::types::{
+    DimensionContribution, DominantExample, ExampleKind, ExplanationBundle, ExplanationSpace,
+    ExplanationStatus, FusionExplanation, FusionMethod, FusionSource, ScoreExplanation, ScoreType,
+    UnavailableReason, VectorSource,
+};
 use common::validation::validate_multi_vector;
 use ordered_float::NotNan;
 use schemars::JsonSchema;
@@ -293,6 +297,195 @@ mod tests {
         // Bm25 option is used only for schema, actual deserialization will happen in specialized code
         assert!(matches!(options, DocumentOptions::Common(_)));
     }
+
+    #[test]
+    fn test_minimal_explanation_omits_contributions() {
+        let contributions = vec![
+            DimensionContribution {
+                dimension: 0,
+                contribution: 0.5,
+                label: None,
+            },
+            DimensionContribution {
+                dimension: 1,
+                contribution: 2.0,
+                label: None,
+            },
+            DimensionContribution {
+                dimension: 2,
+                contribution: -1.0,
+                label: None,
+            },
+        ];
+        let explanation = ScoreExplanation::new(contributions, 2);
+
+        let full = serde_json::to_value(ScoreExplanationOutput::from(explanation.clone())).unwrap();
+        assert_eq!(
+            full["top_dimensions"],
+            serde_json::json!([
+                {"dimension": 1, "contribution": 2.0},
+                {"dimension": 2, "contribution": -1.0},
+            ]),
+        );
+
+        let minimal = ScoreExplanation {
+            verbosity: common::types::ExplanationVerbosity::Minimal,
+            ..explanation
+        };
+        let minimal = serde_json::to_value(ScoreExplanationOutput::from(minimal)).unwrap();
+        assert_eq!(
+            minimal,
+            serde_json::json!({
+                "total_score": 1.5,
+                "top_dimensions": [1, 2],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_explanation_json_round_trip() {
+        let contributions = vec![
+            DimensionContribution {
+                dimension: 0,
+                contribution: 0.1,
+                label: None,
+            },
+            DimensionContribution {
+                dimension: 1,
+                contribution: 1.0 / 3.0,
+                label: Some("term".to_string()),
+            },
+            DimensionContribution {
+                dimension: 2,
+                contribution: -0.7,
+                label: None,
+            },
+        ];
+        let explanation = ScoreExplanation {
+            approximate_search: true,
+            space: ExplanationSpace::Processed,
+            vector_source: VectorSource::Quantized,
+            bundle: Some(ExplanationBundle {
+                query: vec![0.1, 1.0 / 3.0, -0.7],
+                stored: vec![1.0, 1.0, 1.0],
+            }),
+            query_norm: Some(0.77),
+            stored_norm: Some(3.0_f32.sqrt()),
+            baseline_explanation: Some(Box::new(ScoreExplanation::new(contributions.clone(), 1))),
+            vector_name: Some("text".to_string()),
+            dominant_examples: vec![DominantExample {
+                dimension: 1,
+                kind: ExampleKind::Negative,
+                index: 2,
+                contribution: -0.25,
+            }],
+            quantization_error: vec![DimensionContribution {
+                dimension: 2,
+                contribution: 0.01,
+                label: None,
+            }],
+            fusion: Some(FusionExplanation {
+                method: FusionMethod::Rrf { k: 60 },
+                sources: vec![FusionSource {
+                    rank: Some(0),
+                    score: None,
+                    contribution: 1.0 / 61.0,
+                }],
+            }),
+            ..ScoreExplanation::new(contributions, 3)
+        };
+
+        let json =
+            serde_json::to_string(&ScoreExplanationOutput::from(explanation.clone())).unwrap();
+        let ScoreExplanationOutput::Full(output) = serde_json::from_str(&json).unwrap() else {
+            panic!("full explanation deserialized as minimal: {json}");
+        };
+        let deserialized = ScoreExplanation::from(output);
+
+        assert!(
+            deserialized.approx_eq(&explanation, 1e-6),
+            "{deserialized:?} != {explanation:?}",
+        );
+        assert_eq!(deserialized.total_score, explanation.total_score);
+        assert_eq!(deserialized.top_dimensions, explanation.top_dimensions);
+        assert_eq!(deserialized.vector_source, VectorSource::Quantized);
+
+        let unavailable = ScoreExplanation::unavailable(UnavailableReason::Timeout);
+        let json =
+            serde_json::to_string(&ScoreExplanationOutput::from(unavailable.clone())).unwrap();
+        let ScoreExplanationOutput::Full(output) = serde_json::from_str(&json).unwrap() else {
+            panic!("full explanation deserialized as minimal: {json}");
+        };
+        assert!(ScoreExplanation::from(output).approx_eq(&unavailable, 0.0));
+    }
+
+    #[test]
+    fn test_explanation_approx_eq_compares_optional_fields() {
+        let contributions = vec![DimensionContribution {
+            dimension: 0,
+            contribution: 1.0,
+            label: None,
+        }];
+        let explanation = ScoreExplanation::new(contributions.clone(), 1);
+
+        let differing = [
+            ScoreExplanation {
+                top_dimensions: vec![DimensionContribution {
+                    label: Some("term".to_string()),
+                    ..contributions[0].clone()
+                }],
+                ..explanation.clone()
+            },
+            ScoreExplanation {
+                query_norm: Some(1.0),
+                ..explanation.clone()
+            },
+            ScoreExplanation {
+                stored_norm: Some(1.0),
+                ..explanation.clone()
+            },
+            ScoreExplanation {
+                baseline_explanation: Some(Box::new(explanation.clone())),
+                ..explanation.clone()
+            },
+            ScoreExplanation {
+                vector_name: Some("text".to_string()),
+                ..explanation.clone()
+            },
+            ScoreExplanation {
+                dominant_examples: vec![DominantExample {
+                    dimension: 0,
+                    kind: ExampleKind::Positive,
+                    index: 0,
+                    contribution: 1.0,
+                }],
+                ..explanation.clone()
+            },
+            ScoreExplanation {
+                quantization_error: contributions.clone(),
+                ..explanation.clone()
+            },
+            ScoreExplanation {
+                fusion: Some(FusionExplanation {
+                    method: FusionMethod::Dbsf,
+                    sources: vec![],
+                }),
+                ..explanation.clone()
+            },
+        ];
+        for other in &differing {
+            assert!(!explanation.approx_eq(other, 1e-6), "{other:?}");
+            assert!(!other.approx_eq(&explanation, 1e-6), "{other:?}");
+        }
+
+        // Floats of optional fields are compared with tolerance
+        let with_norm = |norm| ScoreExplanation {
+            query_norm: Some(norm),
+            ..explanation.clone()
+        };
+        assert!(with_norm(1.0).approx_eq(&with_norm(1.0 + 1e-7), 1e-6));
+        assert!(!with_norm(1.0).approx_eq(&with_norm(1.1), 1e-6));
+    }
 }
 
 impl DocumentOptions {
@@ -440,10 +633,13 @@ fn image_model_example() -> String {
 }
 
 /// the contribution of a single dimension to the similarity score
-#[derive(Clone, Debug, PartialEq, Serialize, JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
 pub struct DimensionContributionOutput {
     pub dimension: usize,
     pub contribution: ScoreType,
+    /// Label of the dimension, if the collection has dimension labels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
 impl From<DimensionContribution> for DimensionContributionOutput {
@@ -451,23 +647,562 @@ impl From<DimensionContribution> for DimensionContributionOutput {
         Self {
             dimension: value.dimension,
             contribution: value.contribution,
+            label: value.label,
         }
     }
 }
 
-#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
-pub struct ScoreExplanationOutput {
-    pub top_dimensions: Vec<DimensionContributionOutput>,
+impl From<DimensionContributionOutput> for DimensionContribution {
+    fn from(value: DimensionContributionOutput) -> Self {
+        Self {
+            dimension: value.dimension,
+            contribution: value.contribution,
+            label: value.label,
+        }
+    }
+}
+
+/// Kind of an example of a recommend query
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExampleKindOutput {
+    Positive,
+    Negative,
+}
+
+impl From<ExampleKind> for ExampleKindOutput {
+    fn from(value: ExampleKind) -> Self {
+        match value {
+            ExampleKind::Positive => Self::Positive,
+            ExampleKind::Negative => Self::Negative,
+        }
+    }
+}
+
+impl From<ExampleKindOutput> for ExampleKind {
+    fn from(value: ExampleKindOutput) -> Self {
+        match value {
+            ExampleKindOutput::Positive => Self::Positive,
+            ExampleKindOutput::Negative => Self::Negative,
+        }
+    }
+}
+
+/// Example of a recommend query which weighed the most on a dimension of the query vector
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct DominantExampleOutput {
+    pub dimension: usize,
+    pub kind: ExampleKindOutput,
+    /// Position of the example among the examples of its kind
+    pub index: usize,
+    /// Value the example adds to this dimension of the query vector
+    pub contribution: ScoreType,
+}
+
+impl From<DominantExample> for DominantExampleOutput {
+    fn from(value: DominantExample) -> Self {
+        let DominantExample {
+            dimension,
+            kind,
+            index,
+            contribution,
+        } = value;
+        Self {
+            dimension,
+            kind: kind.into(),
+            index,
+            contribution,
+        }
+    }
+}
+
+impl From<DominantExampleOutput> for DominantExample {
+    fn from(value: DominantExampleOutput) -> Self {
+        let DominantExampleOutput {
+            dimension,
+            kind,
+            index,
+            contribution,
+        } = value;
+        Self {
+            dimension,
+            kind: kind.into(),
+            index,
+            contribution,
+        }
+    }
+}
+
+/// Method a fusion query combined the results of its prefetches with
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FusionMethodOutput {
+    Rrf { k: usize },
+    Dbsf,
+}
+
+impl From<FusionMethod> for FusionMethodOutput {
+    fn from(value: FusionMethod) -> Self {
+        match value {
+            FusionMethod::Rrf { k } => Self::Rrf { k },
+            FusionMethod::Dbsf => Self::Dbsf,
+        }
+    }
+}
+
+impl From<FusionMethodOutput> for FusionMethod {
+    fn from(value: FusionMethodOutput) -> Self {
+        match value {
+            FusionMethodOutput::Rrf { k } => Self::Rrf { k },
+            FusionMethodOutput::Dbsf => Self::Dbsf,
+        }
+    }
+}
+
+/// Part of the fused score of a point coming from one prefetch of a fusion query
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct FusionSourceOutput {
+    /// Position of the point in the results of the prefetch, absent if it wasn't part of them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<usize>,
+    /// Score of the point in the results of the prefetch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<ScoreType>,
+    /// Value the prefetch adds to the fused score
+    pub contribution: ScoreType,
+}
+
+impl From<FusionSource> for FusionSourceOutput {
+    fn from(value: FusionSource) -> Self {
+        let FusionSource {
+            rank,
+            score,
+            contribution,
+        } = value;
+        Self {
+            rank,
+            score,
+            contribution,
+        }
+    }
+}
+
+impl From<FusionSourceOutput> for FusionSource {
+    fn from(value: FusionSourceOutput) -> Self {
+        let FusionSourceOutput {
+            rank,
+            score,
+            contribution,
+        } = value;
+        Self {
+            rank,
+            score,
+            contribution,
+        }
+    }
+}
+
+/// How a fusion query combined the results of its prefetches into the score of a point
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct FusionExplanationOutput {
+    pub method: FusionMethodOutput,
+    /// One source per prefetch of the query, in the order of the prefetches
+    pub sources: Vec<FusionSourceOutput>,
+}
+
+impl From<FusionExplanation> for FusionExplanationOutput {
+    fn from(value: FusionExplanation) -> Self {
+        let FusionExplanation { method, sources } = value;
+        Self {
+            method: method.into(),
+            sources: sources.into_iter().map(|s| s.into()).collect(),
+        }
+    }
+}
+
+impl From<FusionExplanationOutput> for FusionExplanation {
+    fn from(value: FusionExplanationOutput) -> Self {
+        let FusionExplanationOutput { method, sources } = value;
+        Self {
+            method: method.into(),
+            sources: sources.into_iter().map(|s| s.into()).collect(),
+        }
+    }
+}
+
+/// Space in which the contributions of an explanation are expressed
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplanationSpaceOutput {
+    /// Vectors as they were inserted
+    Original,
+    /// Vectors as they are stored, after ingest preprocessing (e.g. L2 normalization for cosine)
+    Processed,
+}
+
+impl From<ExplanationSpace> for ExplanationSpaceOutput {
+    fn from(value: ExplanationSpace) -> Self {
+        match value {
+            ExplanationSpace::Original => Self::Original,
+            ExplanationSpace::Processed => Self::Processed,
+        }
+    }
+}
+
+impl From<ExplanationSpaceOutput> for ExplanationSpace {
+    fn from(value: ExplanationSpaceOutput) -> Self {
+        match value {
+            ExplanationSpaceOutput::Original => Self::Original,
+            ExplanationSpaceOutput::Processed => Self::Processed,
+        }
+    }
+}
+
+/// Storage the explained vector of a point was read from
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorSourceOutput {
+    /// Original vector storage
+    Original,
+    /// Dequantized from the quantized storage, contributions are approximate
+    Quantized,
+}
+
+impl From<VectorSource> for VectorSourceOutput {
+    fn from(value: VectorSource) -> Self {
+        match value {
+            VectorSource::Original => Self::Original,
+            VectorSource::Quantized => Self::Quantized,
+        }
+    }
+}
+
+impl From<VectorSourceOutput> for VectorSource {
+    fn from(value: VectorSourceOutput) -> Self {
+        match value {
+            VectorSourceOutput::Original => Self::Original,
+            VectorSourceOutput::Quantized => Self::Quantized,
+        }
+    }
+}
+
+/// Why no explanation could be computed for a point
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnavailableReasonOutput {
+    /// Points are ordered by a payload field, not by a vector score
+    OrderByQuery,
+    /// The kind of query doesn't support explanations
+    UnsupportedQuery,
+    /// The vector of the point couldn't be retrieved
+    VectorMissing,
+    /// Explanations were skipped to finish the request within its timeout
+    Timeout,
+}
+
+impl From<UnavailableReason> for UnavailableReasonOutput {
+    fn from(value: UnavailableReason) -> Self {
+        match value {
+            UnavailableReason::OrderByQuery => Self::OrderByQuery,
+            UnavailableReason::UnsupportedQuery => Self::UnsupportedQuery,
+            UnavailableReason::VectorMissing => Self::VectorMissing,
+            UnavailableReason::Timeout => Self::Timeout,
+        }
+    }
+}
+
+impl From<UnavailableReasonOutput> for UnavailableReason {
+    fn from(value: UnavailableReasonOutput) -> Self {
+        match value {
+            UnavailableReasonOutput::OrderByQuery => Self::OrderByQuery,
+            UnavailableReasonOutput::UnsupportedQuery => Self::UnsupportedQuery,
+            UnavailableReasonOutput::VectorMissing => Self::VectorMissing,
+            UnavailableReasonOutput::Timeout => Self::Timeout,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplanationStatusOutput {
+    Available,
+    Unavailable(UnavailableReasonOutput),
+}
+
+impl From<ExplanationStatus> for ExplanationStatusOutput {
+    fn from(value: ExplanationStatus) -> Self {
+        match value {
+            ExplanationStatus::Available => Self::Available,
+            ExplanationStatus::Unavailable(reason) => Self::Unavailable(reason.into()),
+        }
+    }
+}
+
+impl From<ExplanationStatusOutput> for ExplanationStatus {
+    fn from(value: ExplanationStatusOutput) -> Self {
+        match value {
+            ExplanationStatusOutput::Available => Self::Available,
+            ExplanationStatusOutput::Unavailable(reason) => Self::Unavailable(reason.into()),
+        }
+    }
+}
+
+/// Explanation of a score, in the verbosity requested by the client
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ScoreExplanationOutput {
+    Full(FullScoreExplanationOutput),
+    Minimal(MinimalScoreExplanationOutput),
 }
 
 impl From<ScoreExplanation> for ScoreExplanationOutput {
     fn from(value: ScoreExplanation) -> Self {
+        match value.verbosity {
+            common::types::ExplanationVerbosity::Full => Self::Full(value.into()),
+            common::types::ExplanationVerbosity::Minimal => Self::Minimal(value.into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct FullScoreExplanationOutput {
+    pub top_dimensions: Vec<DimensionContributionOutput>,
+    /// Score reconstructed from the contributions of all dimensions
+    pub total_score: ScoreType,
+    /// True if the point was found by an approximate search, e.g. by traversing an HNSW graph
+    /// rather than scanning all vectors of its segment.
+    /// The explanation itself is exact for the returned pair, but the result set may differ
+    /// from an exact search.
+    pub approximate_search: bool,
+    /// Space of the vectors the contributions were computed on
+    pub space: ExplanationSpaceOutput,
+    /// Storage the vector of the point was read from
+    pub vector_source: VectorSourceOutput,
+    /// Whether contributions could be computed for this point
+    pub status: ExplanationStatusOutput,
+    /// Vectors the contributions were computed from, if `with_explanation_bundle` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<ExplanationBundleOutput>,
+    /// L2 norm of the query vector.
+    /// Compared with `stored_norm`, tells whether a low score comes from direction or magnitude.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_norm: Option<ScoreType>,
+    /// L2 norm of the stored vector of the point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored_norm: Option<ScoreType>,
+    /// Explanation of the point against the collection centroid,
+    /// showing the dimensions where it deviates the most from a typical point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline_explanation: Option<Box<FullScoreExplanationOutput>>,
+    /// Name of the vector the contributions were computed on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_name: Option<String>,
+    /// For recommend queries, the example which weighed the most on each of the top dimensions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dominant_examples: Vec<DominantExampleOutput>,
+    /// Contribution of each top dimension minus its contribution with the quantized vector,
+    /// if `compare_quantized` is set and the point was found by a quantized search
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quantization_error: Vec<DimensionContributionOutput>,
+    /// For fusion queries, the contribution of each prefetch to the fused score
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fusion: Option<FusionExplanationOutput>,
+}
+
+impl From<ScoreExplanation> for FullScoreExplanationOutput {
+    fn from(value: ScoreExplanation) -> Self {
+        let ScoreExplanation {
+            top_dimensions,
+            total_score,
+            approximate_search,
+            space,
+            vector_source,
+            status,
+            bundle,
+            verbosity: _,
+            query_norm,
+            stored_norm,
+            baseline_explanation,
+            vector_name,
+            dominant_examples,
+            quantization_error,
+            fusion,
+        } = value;
         Self {
-            top_dimensions: value.top_dimensions.into_iter().map(|d| d.into()).collect(),
+            top_dimensions: top_dimensions.into_iter().map(|d| d.into()).collect(),
+            total_score,
+            approximate_search,
+            space: space.into(),
+            vector_source: vector_source.into(),
+            status: status.into(),
+            bundle: bundle.map(|b| b.into()),
+            query_norm,
+            stored_norm,
+            baseline_explanation: baseline_explanation.map(|b| Box::new((*b).into())),
+            vector_name,
+            dominant_examples: dominant_examples.into_iter().map(|d| d.into()).collect(),
+            quantization_error: quantization_error.into_iter().map(|d| d.into()).collect(),
+            fusion: fusion.map(|f| f.into()),
         }
     }
 }
 
+impl From<FullScoreExplanationOutput> for ScoreExplanation {
+    fn from(value: FullScoreExplanationOutput) -> Self {
+        let FullScoreExplanationOutput {
+            top_dimensions,
+            total_score,
+            approximate_search,
+            space,
+            vector_source,
+            status,
+            bundle,
+            query_norm,
+            stored_norm,
+            baseline_explanation,
+            vector_name,
+            dominant_examples,
+            quantization_error,
+            fusion,
+        } = value;
+        Self {
+            top_dimensions: top_dimensions.into_iter().map(|d| d.into()).collect(),
+            total_score,
+            approximate_search,
+            space: space.into(),
+            vector_source: vector_source.into(),
+            status: status.into(),
+            bundle: bundle.map(|b| b.into()),
+            verbosity: common::types::ExplanationVerbosity::Full,
+            query_norm,
+            stored_norm,
+            baseline_explanation: baseline_explanation.map(|b| Box::new((*b).into())),
+            vector_name,
+            dominant_examples: dominant_examples.into_iter().map(|d| d.into()).collect(),
+            quantization_error: quantization_error.into_iter().map(|d| d.into()).collect(),
+            fusion: fusion.map(|f| f.into()),
+        }
+    }
+}
+
+/// Compact explanation, without the contribution values
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct MinimalScoreExplanationOutput {
+    /// Score reconstructed from the contributions of all dimensions
+    pub total_score: ScoreType,
+    /// Indices of the dimensions which contributed most to the score, largest contribution first
+    pub top_dimensions: Vec<usize>,
+}
+
+impl From<ScoreExplanation> for MinimalScoreExplanationOutput {
+    fn from(value: ScoreExplanation) -> Self {
+        Self {
+            total_score: value.total_score,
+            top_dimensions: value
+                .top_dimensions
+                .into_iter()
+                .map(|dim| dim.dimension)
+                .collect(),
+        }
+    }
+}
+
+/// Amount of detail returned in score explanations
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplanationVerbosity {
+    /// Contributions of the top dimensions, and how they were computed
+    #[default]
+    Full,
+    /// Only the total score and the indices of the top dimensions
+    Minimal,
+}
+
+impl From<ExplanationVerbosity> for common::types::ExplanationVerbosity {
+    fn from(value: ExplanationVerbosity) -> Self {
+        match value {
+            ExplanationVerbosity::Full => Self::Full,
+            ExplanationVerbosity::Minimal => Self::Minimal,
+        }
+    }
+}
+
+/// Number of dimensions returned in score explanations
+#[derive(
+    Copy, Clone, Debug, Default, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash,
+)]
+pub struct ExplanationParams {
+    /// Number of top contributing dimensions to return. Default is 10.
+    #[validate(range(min = 1, max = 1024))]
+    pub top_n: Option<usize>,
+    /// If true, return the contributions of all dimensions, up to 1024. Default is false.
+    pub include_all: Option<bool>,
+    /// If true, also return how much the contribution of each top dimension differs with
+    /// the quantized vector of the point, for points found by a quantized search.
+    /// Contributions are always computed with the original vectors. Default is false.
+    pub compare_quantized: Option<bool>,
+}
+
+impl From<ExplanationParams> for common::types::ExplanationParams {
+    fn from(value: ExplanationParams) -> Self {
+        let ExplanationParams {
+            top_n,
+            include_all,
+            compare_quantized,
+        } = value;
+        Self {
+            top_n,
+            include_all: include_all.unwrap_or(false),
+            mode: Default::default(), // set by `explanation_mode` of the request
+            compare_quantized: compare_quantized.unwrap_or(false),
+        }
+    }
+}
+
+/// What the contributions of score explanations stand for
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplanationMode {
+    /// Share of the score computed from each dimension
+    #[default]
+    Contribution,
+    /// How much the score would drop if each dimension was removed from both vectors.
+    /// Re-normalized for cosine similarity
+    Counterfactual,
+}
+
+impl From<ExplanationMode> for common::types::ExplanationMode {
+    fn from(value: ExplanationMode) -> Self {
+        match value {
+            ExplanationMode::Contribution => Self::Contribution,
+            ExplanationMode::Counterfactual => Self::Counterfactual,
+        }
+    }
+}
+
+/// Exact vectors an explanation was computed from.
+/// Feeding them to the same distance reproduces the explanation.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ExplanationBundleOutput {
+    pub query: Vec<ScoreType>,
+    /// Vector of the point as stored, i.e. after ingest preprocessing
+    pub stored: Vec<ScoreType>,
+}
+
+impl From<ExplanationBundle> for ExplanationBundleOutput {
+    fn from(value: ExplanationBundle) -> Self {
+        let ExplanationBundle { query, stored } = value;
+        Self { query, stored }
+    }
+}
+
+impl From<ExplanationBundleOutput> for ExplanationBundle {
+    fn from(value: ExplanationBundleOutput) -> Self {
+        let ExplanationBundleOutput { query, stored } = value;
+        Self { query, stored }
+    }
+}
+
 /// Search result
 #[derive(Serialize, JsonSchema, Clone, Debug)]
 pub struct ScoredPoint {
@@ -627,6 +1362,28 @@ pub struct QueryRequestInternal {
     /// Note: the other collection vectors should have the same vector size as the 'using' vector in the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// If true, return an explanation of which dimensions contributed most to each result's score.
+    /// Default is false.
+    #[serde(default)]
+    pub with_explanation: Option<bool>,
+
+    /// Number of dimensions returned in explanations. Requires `with_explanation`.
+    #[serde(default)]
+    #[validate(nested)]
+    pub explanation_params: Option<ExplanationParams>,
+
+    /// What the contributions of explanations stand for. Requires `with_explanation`.
+    /// Default is `contribution`.
+    #[serde(default)]
+    pub explanation_mode: Option<ExplanationMode>,
+
+    /// Payload field to deduplicate results by, must be a string or number field.
+    /// Only the best scored point of each value is returned, as with `group_by` and a `group_size` of 1.
+    /// Points without a value for this field are left out.
+    #[serde(default)]
+    #[schemars(length(min = 1))]
+    pub distinct_by: Option<JsonPath>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -648,6 +1405,20 @@ pub struct QueryResponse {
     pub points: Vec<ScoredPoint>,
 }
 
+/// Explanations of the results of a query as a dense matrix, e.g. to render a heatmap
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ExplanationMatrixResponse {
+    /// Ids of the results, one per row
+    pub ids: Vec<PointIdType>,
+    /// Scores of the results, one per row
+    pub scores: Vec<ScoreType>,
+    /// Dimensions which contributed most over all results, one per column, most important first
+    pub dimensions: Vec<usize>,
+    /// Contribution of each dimension to the score of each result, one row per result.
+    /// Rows of results without an explanation are all zeros.
+    pub contributions: Vec<Vec<ScoreType>>,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 #[serde(expecting = "Expected some form of vector, id, or a type of query")]
@@ -671,6 +1442,9 @@ pub enum Query {
     /// Return points that live in positive areas.
     Context(ContextQuery),
 
+    /// Find the nearest neighbors to a weighted sum of vectors.
+    Arithmetic(ArithmeticQuery),
+
     /// Order the points by a payload field.
     OrderBy(OrderByQuery),
 
@@ -721,6 +1495,13 @@ pub struct ContextQuery {
     pub context: ContextInput,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct ArithmeticQuery {
+    #[validate(nested)]
+    pub arithmetic: ArithmeticInput,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct OrderByQuery {
@@ -758,7 +1539,7 @@ pub struct SampleQuery {
 }
 
 /// Maximal Marginal Relevance (MMR) algorithm for re-ranking the points.
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct Mmr {
     /// Tunable parameter for the MMR algorithm.
@@ -798,7 +1579,7 @@ pub struct Prefetch {
     #[validate(nested)]
     pub filter: Option<Filter>,
 
-    /// Search params for when there is no prefetch
+    /// Search params of this prefetch, used by its own search, or to rescore its nested prefetches
     #[validate(nested)]
     pub params: Option<SearchParams>,
 
@@ -827,6 +1608,10 @@ pub struct Prefetch {
 ///
 /// * `sum_scores` - Uses custom search objective. Compares against all inputs, sums all the scores.
 ///   Scores against positive vectors are added, against negatives are subtracted.
+///
+/// * `rocchio` - Create a single query with the Rocchio formula
+///   `query = alpha * avg_pos - beta * avg_neg`, weights are set with `rocchio` parameters.
+///   Then performs normal search.
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Default, PartialEq, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum RecommendStrategy {
@@ -834,6 +1619,30 @@ pub enum RecommendStrategy {
     AverageVector,
     BestScore,
     SumScores,
+    Rocchio,
+}
+
+pub const DEFAULT_ROCCHIO_ALPHA: f32 = 1.0;
+pub const DEFAULT_ROCCHIO_BETA: f32 = 0.2;
+
+/// Weights of the `rocchio` recommend strategy
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Default, PartialEq, Clone, Copy)]
+pub struct RocchioParams {
+    /// Weight of the average of positive vectors. Default: 1.0
+    pub alpha: Option<f32>,
+
+    /// Weight of the average of negative vectors. Default: 0.2
+    pub beta: Option<f32>,
+}
+
+impl RocchioParams {
+    pub fn alpha(&self) -> f32 {
+        self.alpha.unwrap_or(DEFAULT_ROCCHIO_ALPHA)
+    }
+
+    pub fn beta(&self) -> f32 {
+        self.beta.unwrap_or(DEFAULT_ROCCHIO_BETA)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -846,6 +1655,9 @@ pub struct RecommendInput {
 
     /// How to use the provided vectors to find the results
     pub strategy: Option<RecommendStrategy>,
+
+    /// Weights of the `rocchio` strategy
+    pub rocchio: Option<RocchioParams>,
 }
 
 impl RecommendInput {
@@ -870,6 +1682,28 @@ pub struct DiscoverInput {
     pub context: Option<Vec<ContextPair>>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ArithmeticInput {
+    /// Vectors to add to the query vector
+    pub add: Option<Vec<VectorInput>>,
+
+    /// Vectors to subtract from the query vector
+    pub subtract: Option<Vec<VectorInput>>,
+
+    /// Weight of each vector, in the order of `add` followed by `subtract`.
+    /// If not set, every vector has a weight of 1.
+    pub weights: Option<Vec<f32>>,
+}
+
+impl ArithmeticInput {
+    pub fn iter(&self) -> impl Iterator<Item = &VectorInput> {
+        self.add
+            .iter()
+            .flatten()
+            .chain(self.subtract.iter().flatten())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ContextInput(
     /// Search space will be constrained by these pairs of vectors
@@ -1104,6 +1938,7 @@ pub struct LookupLocation {
 #[derive(Validate, Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 pub struct BaseGroupRequest {
     /// Payload field to group by, must be a string or number field.
+    /// Nested fields can be used with a path, e.g. `metadata.author.id` or `authors[].id`.
     /// If the field contains more than 1 value, all values will be used for grouping.
     /// One point can be in multiple groups.
     #[schemars(length(min = 1))]
@@ -1119,6 +1954,25 @@ pub struct BaseGroupRequest {
 
     /// Look for points in another collection using the group ids
     pub with_lookup: Option<WithLookupInterface>,
+
+    /// Aggregations to compute for each group
+    #[serde(default)]
+    pub aggregations: Option<GroupAggregations>,
+}
+
+/// Aggregations computed for each returned group
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Default)]
+pub struct GroupAggregations {
+    /// Count all points of the group which match the request filter, not only the returned hits.
+    /// Requires an additional count request per group. Default: false
+    #[serde(default)]
+    pub count: bool,
+    /// Compute min, max and average score of the returned hits. Default: false
+    #[serde(default)]
+    pub score: bool,
+    /// Numeric payload fields to compute sum and average of, over the returned hits
+    #[serde(default)]
+    pub payload: Vec<JsonPath>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -1151,6 +2005,11 @@ pub struct SearchGroupsRequestInternal {
     #[serde(flatten)]
     #[validate(nested)]
     pub group_request: BaseGroupRequest,
+
+    /// If true, return an explanation of which dimensions contributed most to the score
+    /// of every point of every group. Default is false.
+    #[serde(default)]
+    pub with_explanation: Option<bool>,
 }
 
 /// Search request.
@@ -1190,11 +2049,42 @@ pub struct SearchRequestInternal {
     /// Default is false.
     #[serde(default)]
     pub with_explanation: Option<bool>,
+    /// Debug option: if true, explanations also contain the exact query and stored vectors
+    /// they were computed from. Requires `with_explanation`. Default is false.
+    #[serde(default)]
+    pub with_explanation_bundle: Option<bool>,
+    /// Amount of detail in explanations. `minimal` only returns the total score and
+    /// the indices of the top dimensions. Requires `with_explanation`. Default is `full`.
+    #[serde(default)]
+    pub explanation_verbosity: Option<ExplanationVerbosity>,
+    /// Number of dimensions returned in explanations. Requires `with_explanation`.
+    #[serde(default)]
+    #[validate(nested)]
+    pub explanation_params: Option<ExplanationParams>,
+
+    /// What the contributions of explanations stand for. Requires `with_explanation`.
+    /// Default is `contribution`.
+    #[serde(default)]
+    pub explanation_mode: Option<ExplanationMode>,
+
+    /// Rerank the results with MMR (Maximal Marginal Relevance), trading relevance for diversity.
+    /// Results are reranked over `candidates_limit` nearest points, down to `limit`.
+    #[serde(default)]
+    #[validate(nested)]
+    pub mmr: Option<Mmr>,
+
+    /// Payload field to deduplicate results by, must be a string or number field.
+    /// Only the best scored point of each value is returned, as with `group_by` and a `group_size` of 1.
+    /// Points without a value for this field are left out.
+    #[serde(default)]
+    #[schemars(length(min = 1))]
+    pub distinct_by: Option<JsonPath>,
 }
 
 #[derive(Validate, Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 pub struct QueryBaseGroupRequest {
     /// Payload field to group by, must be a string or number field.
+    /// Nested fields can be used with a path, e.g. `metadata.author.id` or `authors[].id`.
     /// If the field contains more than 1 value, all values will be used for grouping.
     /// One point can be in multiple groups.
     #[schemars(length(min = 1))]
@@ -1210,6 +2100,10 @@ pub struct QueryBaseGroupRequest {
 
     /// Look for points in another collection using the group ids
     pub with_lookup: Option<WithLookupInterface>,
+
+    /// Aggregations to compute for each group
+    #[serde(default)]
+    pub aggregations: Option<GroupAggregations>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -1252,6 +2146,21 @@ pub struct QueryGroupsRequestInternal {
     #[serde(flatten)]
     #[validate(nested)]
     pub group_request: QueryBaseGroupRequest,
+
+    /// If true, return an explanation of which dimensions contributed most to the score
+    /// of every point of every group. Default is false.
+    #[serde(default)]
+    pub with_explanation: Option<bool>,
+
+    /// Number of dimensions returned in explanations. Requires `with_explanation`.
+    #[serde(default)]
+    #[validate(nested)]
+    pub explanation_params: Option<ExplanationParams>,
+
+    /// What the contributions of explanations stand for. Requires `with_explanation`.
+    /// Default is `contribution`.
+    #[serde(default)]
+    pub explanation_mode: Option<ExplanationMode>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -1277,6 +2186,9 @@ pub struct SearchMatrixRequestInternal {
     pub limit: Option<usize>,
     /// Define which vector name to use for querying. If missing, the default vector is used.
     pub using: Option<VectorNameBuf>,
+    /// If true, return the dimensions which contributed most to the score of each pair.
+    /// Only the 256 highest-scoring pairs are explained. Default is false.
+    pub with_explanation: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -1301,6 +2213,10 @@ pub struct SearchMatrixOffsetsResponse {
     pub scores: Vec<ScoreType>,
     /// Ids of the points in order
     pub ids: Vec<PointIdType>,
+    /// Dimensions which contributed most to each score, if `with_explanation` is set.
+    /// Empty for the pairs which were not explained
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_dimensions: Option<Vec<Vec<DimensionContributionOutput>>>,
 }
 
 #[derive(Debug, Serialize, JsonSchema, PartialEq)]
@@ -1310,6 +2226,9 @@ pub struct SearchMatrixPair {
     pub a: PointIdType,
     pub b: PointIdType,
     pub score: ScoreType,
+    /// Dimensions which contributed most to the score, if the pair was explained
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_dimensions: Option<Vec<DimensionContributionOutput>>,
 }
 
 impl SearchMatrixPair {
@@ -1318,6 +2237,7 @@ impl SearchMatrixPair {
             a: a.into(),
             b: b.into(),
             score,
+            top_dimensions: None,
         }
     }
 }
@@ -1355,6 +2275,50 @@ pub struct FacetRequest {
     pub shard_key: Option<ShardKeySelector>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct HistogramRequestInternal {
+    /// Numeric payload key to compute the histogram of.
+    /// Must have an integer or float index with range support, unless both `min` and `max` are set.
+    pub key: JsonPath,
+
+    /// Number of buckets of equal width. Default is 10.
+    #[validate(range(min = 1, max = 1000))]
+    pub buckets: Option<usize>,
+
+    /// Filter conditions - only consider points that satisfy these conditions.
+    #[validate(nested)]
+    pub filter: Option<Filter>,
+
+    /// Lower bound of the histogram. Default is the lowest value of the field.
+    pub min: Option<f64>,
+
+    /// Upper bound of the histogram. Default is the highest value of the field.
+    pub max: Option<f64>,
+
+    /// Percentiles to estimate from the histogram, between 0 and 100, e.g. `[50, 90, 99]`
+    pub percentiles: Option<Vec<f64>>,
+
+    /// Whether to do a more expensive exact count for each bucket.
+    /// Otherwise counts are estimated from the payload index. Default is false.
+    pub exact: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct HistogramRequest {
+    #[validate(nested)]
+    #[serde(flatten)]
+    pub histogram_request: HistogramRequestInternal,
+
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct FacetRequestBatch {
+    /// Facets to compute, e.g. one per payload key
+    #[validate(nested, length(min = 1))]
+    pub facets: Vec<FacetRequest>,
+}
+
 #[derive(Debug, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum FacetValue {
@@ -1398,6 +2362,17 @@ pub struct PointsBatch {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate(nested)]
     pub update_filter: Option<Filter>,
+
+    /// If specified, all points must already exist with this version on the shard's leader replica, otherwise the request fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<segment::types::SeqNumberType>,
+    /// If specified, all points must already exist and match this filter, otherwise the request fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub if_payload_matches: Option<Filter>,
+    /// If true, none of the points may exist yet, otherwise the request fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_not_exists: Option<bool>,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
@@ -1432,6 +2407,54 @@ pub struct PointsList {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate(nested)]
     pub update_filter: Option<Filter>,
+
+    /// If specified, all points must already exist with this version on the shard's leader replica, otherwise the request fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<segment::types::SeqNumberType>,
+    /// If specified, all points must already exist and match this filter, otherwise the request fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub if_payload_matches: Option<Filter>,
+    /// If true, none of the points may exist yet, otherwise the request fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_not_exists: Option<bool>,
+}
+
+/// Type of ids assigned by the server to inserted points
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PointIdGeneration {
+    /// Random UUIDs
+    #[default]
+    Uuid,
+    /// Increasing unsigned integers, composed of the current time and the id of the peer
+    Sequential,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct InsertPointStruct {
+    /// Vectors
+    #[serde(alias = "vectors")]
+    #[validate(nested)]
+    pub vector: VectorStruct,
+    /// Payload values (optional)
+    pub payload: Option<Payload>,
+}
+
+/// Points to insert with ids assigned by the server
+///
+/// Points are only inserted, the request fails with a conflict if an assigned id is taken.
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, Validate)]
+pub struct InsertPoints {
+    /// Assigned ids are returned in the same order as the points
+    #[validate(nested, length(min = 1))]
+    pub points: Vec<InsertPointStruct>,
+    /// Type of ids to assign. Default: uuid
+    #[serde(default)]
+    pub id_generation: PointIdGeneration,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
 }
 
 impl<'de> serde::Deserialize<'de> for PointInsertOperations {
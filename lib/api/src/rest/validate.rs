@@ -5,8 +5,9 @@ use segment::index::query_optimization::rescore_formula::parsed_formula::Variabl
 use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::{
-    Batch, BatchVectorStruct, ContextInput, Expression, FormulaQuery, Fusion, NamedVectorStruct,
-    OrderByInterface, PointVectors, Query, QueryInterface, RecommendInput, Sample, VectorInput,
+    ArithmeticInput, Batch, BatchVectorStruct, ContextInput, Expression, FormulaQuery, Fusion,
+    NamedVectorStruct, OrderByInterface, PointVectors, Query, QueryInterface, RecommendInput,
+    Sample, VectorInput,
 };
 
 impl Validate for NamedVectorStruct {
@@ -35,6 +36,7 @@ impl Validate for Query {
             Query::Recommend(recommend) => recommend.validate(),
             Query::Discover(discover) => discover.validate(),
             Query::Context(context) => context.validate(),
+            Query::Arithmetic(arithmetic) => arithmetic.validate(),
             Query::Fusion(fusion) => fusion.validate(),
             Query::Rrf(rrf) => rrf.validate(),
             Query::Formula(formula) => formula.validate(),
@@ -82,6 +84,38 @@ impl Validate for RecommendInput {
     }
 }
 
+impl Validate for ArithmeticInput {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        let vectors_count = self.iter().count();
+
+        if vectors_count == 0 {
+            let mut errors = validator::ValidationErrors::new();
+            errors.add(
+                "add, subtract",
+                ValidationError::new("At least one vector/id to add or subtract must be provided"),
+            );
+            return Err(errors);
+        }
+
+        if let Some(weights) = &self.weights
+            && weights.len() != vectors_count
+        {
+            let mut errors = validator::ValidationErrors::new();
+            errors.add(
+                "weights",
+                ValidationError::new("There must be exactly one weight per vector/id"),
+            );
+            return Err(errors);
+        }
+
+        for item in self.iter() {
+            item.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Validate for ContextInput {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         for item in self.0.iter().flatten().flat_map(|item| item.iter()) {
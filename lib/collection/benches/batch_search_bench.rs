@@ -62,6 +62,10 @@ fn batch_search_bench(c: &mut Criterion) {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        point_history_versions: None,
+        soft_delete_retention_sec: None,
+        write_coalescing_ms: None,
+        write_coalescing_max_points: None,
     };
 
     let collection_params = CollectionParams {
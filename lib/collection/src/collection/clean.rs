@@ -330,7 +330,11 @@ async fn clean_task(
         // Delete points from local shard
         let delete_operation =
             OperationWithClockTag::from(CollectionUpdateOperations::PointOperation(
-                crate::operations::point_ops::PointOperations::DeletePoints { ids },
+                crate::operations::point_ops::PointOperations::DeletePoints {
+                    ids,
+                    precondition: None,
+                    deleted_at: None,
+                },
             ));
         if let Err(err) = shard
             .update_local(
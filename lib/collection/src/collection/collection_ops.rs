@@ -1,10 +1,12 @@
 use std::cmp::{self, Reverse};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use futures::{TryStreamExt as _, future};
 use lazy_static::lazy_static;
-use segment::types::{Payload, QuantizationConfig, StrictModeConfig};
+use segment::explain::dimension_labels_from_metadata;
+use segment::types::{Distance, Payload, QuantizationConfig, StrictModeConfig, VectorName};
 use semver::Version;
 
 use super::Collection;
@@ -315,6 +317,25 @@ impl Collection {
             .clone()
     }
 
+    pub async fn vector_distance(&self, vector_name: &VectorName) -> CollectionResult<Distance> {
+        self.collection_config
+            .read()
+            .await
+            .params
+            .get_distance(vector_name)
+    }
+
+    /// Labels of the dimensions of a vector, stored in the collection metadata
+    pub async fn dimension_labels(&self, vector_name: &VectorName) -> HashMap<usize, String> {
+        self.collection_config
+            .read()
+            .await
+            .metadata
+            .as_ref()
+            .map(|metadata| dimension_labels_from_metadata(metadata, vector_name))
+            .unwrap_or_default()
+    }
+
     pub async fn info(
         &self,
         shard_selection: &ShardSelectorInternal,
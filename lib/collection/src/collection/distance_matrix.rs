@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
 use ahash::AHashSet;
@@ -6,10 +7,14 @@ use api::rest::{
     SearchMatrixRequestInternal,
 };
 use common::counter::hardware_accumulator::HwMeasurementAcc;
-use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, VectorInternal};
+use segment::explain::{
+    ExplanationConfig, ScoreExplanation, UnavailableReason, compute_sparse_explanation,
+    label_dimensions, try_compute_explanation,
+};
 use segment::types::{
-    Condition, Filter, HasIdCondition, HasVectorCondition, PointIdType, ScoredPoint, VectorNameBuf,
-    WithPayloadInterface, WithVector,
+    Condition, Distance, Filter, HasIdCondition, HasVectorCondition, PointIdType, ScoredPoint,
+    VectorNameBuf, WithPayloadInterface, WithVector,
 };
 
 use crate::collection::Collection;
@@ -35,11 +40,82 @@ pub struct CollectionSearchMatrixRequest {
     pub limit_per_sample: usize,
     pub filter: Option<Filter>,
     pub using: VectorNameBuf,
+    /// Explain the score of the highest-scoring pairs, see [`Self::MAX_EXPLAINED_CELLS`]
+    pub with_explanation: bool,
 }
 
 impl CollectionSearchMatrixRequest {
     pub const DEFAULT_LIMIT_PER_SAMPLE: usize = 3;
     pub const DEFAULT_SAMPLE: usize = 10;
+    /// Most cells of a matrix explained, see [`CollectionSearchMatrixResponse::cells_to_explain`]
+    pub const MAX_EXPLAINED_CELLS: usize = 256;
+}
+
+impl CollectionSearchMatrixResponse {
+    /// Cells to explain when at most `max_cells` of the matrix can be,
+    /// as `(sample, nearest)` positions in `nearests`.
+    ///
+    /// Highest-scoring cells come first, equal scores are ordered by position,
+    /// so that the same matrix always explains the same cells.
+    pub fn cells_to_explain(&self, max_cells: usize) -> Vec<(usize, usize)> {
+        let mut cells: Vec<_> = self
+            .nearests
+            .iter()
+            .enumerate()
+            .flat_map(|(row, nearests)| {
+                nearests
+                    .iter()
+                    .enumerate()
+                    .map(move |(col, point)| (row, col, point.score))
+            })
+            .collect();
+        cells.sort_by(|(row_a, col_a, score_a), (row_b, col_b, score_b)| {
+            score_b
+                .total_cmp(score_a)
+                .then_with(|| (row_a, col_a).cmp(&(row_b, col_b)))
+        });
+        cells
+            .into_iter()
+            .take(max_cells)
+            .map(|(row, col, _score)| (row, col))
+            .collect()
+    }
+
+    /// Explain the scores of the cells of [`Self::cells_to_explain`].
+    ///
+    /// `sample_vectors` are the vectors of the sampled points, in the order of `sample_ids`,
+    /// as both points of each cell are sampled points.
+    fn explain_cells(
+        &mut self,
+        sample_vectors: &[VectorInternal],
+        distance: Distance,
+        labels: &HashMap<usize, String>,
+        max_cells: usize,
+    ) {
+        for (row, col) in self.cells_to_explain(max_cells) {
+            let nearest = &mut self.nearests[row][col];
+            let stored = self
+                .sample_ids
+                .binary_search(&nearest.id)
+                .ok()
+                .map(|pos| &sample_vectors[pos]);
+            let mut explanation = match (&sample_vectors[row], stored) {
+                (VectorInternal::Dense(query), Some(VectorInternal::Dense(stored))) => {
+                    try_compute_explanation(distance, query, stored, &ExplanationConfig::default())
+                        .unwrap_or_else(|_| {
+                            ScoreExplanation::unavailable(UnavailableReason::UnsupportedQuery)
+                        })
+                }
+                (VectorInternal::Sparse(query), Some(VectorInternal::Sparse(stored))) => {
+                    compute_sparse_explanation(query, stored, None)
+                }
+                (_, None) => ScoreExplanation::unavailable(UnavailableReason::VectorMissing),
+                _ => ScoreExplanation::unavailable(UnavailableReason::UnsupportedQuery),
+            };
+            label_dimensions(&mut explanation, labels);
+            nearest.score_explanation = Some(explanation);
+        }
+    }
 }
 
 impl From<SearchMatrixRequestInternal> for CollectionSearchMatrixRequest {
@@ -49,6 +125,7 @@ impl From<SearchMatrixRequestInternal> for CollectionSearchMatrixRequest {
             limit,
             filter,
             using,
+            with_explanation,
         } = request;
         Self {
             sample_size: sample.unwrap_or(CollectionSearchMatrixRequest::DEFAULT_SAMPLE),
@@ -56,6 +133,7 @@ impl From<SearchMatrixRequestInternal> for CollectionSearchMatrixRequest {
                 .unwrap_or(CollectionSearchMatrixRequest::DEFAULT_LIMIT_PER_SAMPLE),
             filter,
             using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned()),
+            with_explanation: with_explanation.unwrap_or(false),
         }
     }
 }
@@ -80,14 +158,36 @@ impl From<CollectionSearchMatrixResponse> for SearchMatrixOffsetsResponse {
             }
         }
         let scores = nearests
-            .into_iter()
-            .flat_map(|row| row.into_iter().map(|p| p.score))
+            .iter()
+            .flat_map(|row| row.iter().map(|p| p.score))
             .collect();
+        let explained = nearests
+            .iter()
+            .flatten()
+            .any(|p| p.score_explanation.is_some());
+        let top_dimensions = explained.then(|| {
+            nearests
+                .into_iter()
+                .flatten()
+                .map(|p| {
+                    p.score_explanation
+                        .map(|explanation| {
+                            explanation
+                                .top_dimensions
+                                .into_iter()
+                                .map(From::from)
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                })
+                .collect()
+        });
         Self {
             offsets_row,
             offsets_col,
             scores,
             ids: sample_ids,
+            top_dimensions,
         }
     }
 }
@@ -108,6 +208,13 @@ impl From<CollectionSearchMatrixResponse> for SearchMatrixPairsResponse {
                     a,
                     b: scored_point.id,
                     score: scored_point.score,
+                    top_dimensions: scored_point.score_explanation.map(|explanation| {
+                        explanation
+                            .top_dimensions
+                            .into_iter()
+                            .map(From::from)
+                            .collect()
+                    }),
                 });
             }
         }
@@ -151,6 +258,7 @@ impl Collection {
             limit_per_sample,
             filter,
             using,
+            with_explanation,
         } = request;
         if limit_per_sample == 0 || sample_size == 0 {
             return Ok(Default::default());
@@ -211,6 +319,8 @@ impl Collection {
 
         // Perform nearest neighbor search for each sampled point
         let mut queries = Vec::with_capacity(sampled_points.len());
+        // keep the sampled vectors to explain the pairs afterward
+        let mut sample_vectors = Vec::new();
 
         for point in sampled_points {
             let vector = point
@@ -219,6 +329,9 @@ impl Collection {
                 .and_then(|v| v.get(&using))
                 .map(|v| v.to_owned())
                 .expect("Vector not found in the point");
+            if with_explanation {
+                sample_vectors.push(vector.clone());
+            }
 
             // nearest query on the sample vector
             let query = Query::Vector(VectorQuery::Nearest(VectorInputInternal::Vector(vector)));
@@ -235,6 +348,9 @@ impl Collection {
                 with_vector: WithVector::Bool(false),
                 with_payload: WithPayloadInterface::Bool(false),
                 lookup_from: None,
+                with_explanation: false,
+                explanation_params: Default::default(),
+                distinct_by: None,
             };
 
             queries.push((query_request, shard_selection.clone()));
@@ -272,10 +388,23 @@ impl Collection {
             }
         }
 
-        Ok(CollectionSearchMatrixResponse {
+        let mut response = CollectionSearchMatrixResponse {
             sample_ids: sampled_point_ids,
             nearests: nearest,
-        })
+        };
+
+        if with_explanation {
+            let distance = self.vector_distance(&using).await?;
+            let labels = self.dimension_labels(&using).await;
+            response.explain_cells(
+                &sample_vectors,
+                distance,
+                &labels,
+                CollectionSearchMatrixRequest::MAX_EXPLAINED_CELLS,
+            );
+        }
+
+        Ok(response)
     }
 }
 
@@ -336,9 +465,31 @@ mod tests {
             offsets_col: vec![0, 1, 1, 2, 0, 2],
             scores: vec![0.2, 0.1, 0.4, 0.3, 0.6, 0.5],
             ids: vec![1.into(), 2.into(), 3.into()],
+            top_dimensions: None,
         };
 
         let actual = SearchMatrixOffsetsResponse::from(response);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_matrix_cells_to_explain() {
+        let response_with_tie = || {
+            let mut response = fixture_response();
+            // Ties with the highest-scoring cell, at (2, 0)
+            response.nearests[0][1].score = 0.6;
+            response
+        };
+
+        let cells = response_with_tie().cells_to_explain(3);
+        assert_eq!(cells, vec![(0, 1), (2, 0), (2, 1)]);
+
+        // Same matrix, same cells
+        for _ in 0..10 {
+            assert_eq!(response_with_tie().cells_to_explain(3), cells);
+        }
+
+        assert_eq!(response_with_tie().cells_to_explain(100).len(), 6);
+        assert!(response_with_tie().cells_to_explain(0).is_empty());
+    }
 }
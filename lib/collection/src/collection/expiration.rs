@@ -0,0 +1,124 @@
+//! Removal of expired points.
+//!
+//! A point expires once the datetime stored in its [`EXPIRES_AT_KEY`] payload field is in the past.
+//! Expiration is enabled for a collection by creating a `datetime` payload index on this field,
+//! so expired points can be found without scanning all payloads.
+//!
+//! Expired points are deleted through the regular update path of each shard, by the peer leading
+//! its updates, so that the deletion gets a clock tag and is applied by every replica.
+
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use cancel::{CancellationToken, DropGuard};
+use chrono::Utc;
+use common::save_on_disk::SaveOnDisk;
+use segment::json_path::JsonPath;
+use segment::types::{
+    Condition, DateTimeWrapper, FieldCondition, Filter, PayloadSchemaType, Range,
+};
+use tokio::runtime::Handle;
+
+use super::Collection;
+use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::shards::shard_holder::LockedShardHolder;
+
+/// Reserved payload field with the expiration datetime of a point
+pub const EXPIRES_AT_KEY: &str = "_expires_at";
+
+/// Interval between two checks for expired points
+const EXPIRATION_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Filter matching expired points, `None` if expiration is not enabled for the collection
+pub(crate) fn expired_points_filter(
+    payload_index_schema: &SaveOnDisk<PayloadIndexSchema>,
+) -> Option<Filter> {
+    let expires_at_key = JsonPath::new(EXPIRES_AT_KEY);
+
+    let is_enabled = payload_index_schema
+        .read()
+        .schema
+        .get(&expires_at_key)
+        .is_some_and(|schema| schema.kind() == PayloadSchemaType::Datetime);
+
+    if !is_enabled {
+        return None;
+    }
+
+    Some(Filter::new_must(Condition::Field(
+        FieldCondition::new_datetime_range(
+            expires_at_key,
+            Range {
+                lt: Some(DateTimeWrapper(Utc::now())),
+                ..Default::default()
+            },
+        ),
+    )))
+}
+
+/// Background task periodically deleting expired points of the collection
+///
+/// The task is cancelled when this is dropped.
+pub(super) struct ExpirationTask {
+    _cancel: DropGuard,
+}
+
+impl ExpirationTask {
+    pub fn new(shards_holder: &Arc<LockedShardHolder>, runtime: &Handle) -> Self {
+        let shards_holder = Arc::downgrade(shards_holder);
+        let cancel = CancellationToken::default();
+
+        runtime.spawn(Self::task(shards_holder, cancel.clone()));
+
+        Self {
+            _cancel: cancel.drop_guard(),
+        }
+    }
+
+    async fn task(shards_holder: Weak<LockedShardHolder>, cancel: CancellationToken) {
+        loop {
+            // Only cancel in between deletions, as updates are not cancel safe
+            let interval = tokio::time::sleep(EXPIRATION_CHECK_INTERVAL);
+            if cancel.run_until_cancelled(interval).await.is_none() {
+                return;
+            }
+
+            let Some(shards_holder) = shards_holder.upgrade() else {
+                return;
+            };
+
+            expire_points(&shards_holder).await;
+        }
+    }
+}
+
+/// Delete expired points of all shards, returns the number of expired points found
+async fn expire_points(shards_holder: &LockedShardHolder) -> usize {
+    let shards_holder = shards_holder.read().await;
+
+    let mut expired_points = 0;
+    for shard in shards_holder.all_shards() {
+        match shard.expire_points().await {
+            Ok(0) => {}
+            Ok(count) => {
+                log::debug!("Deleted {count} expired points of shard {}", shard.shard_id);
+                expired_points += count;
+            }
+            Err(err) => log::error!(
+                "Failed to delete expired points of shard {}: {err}",
+                shard.shard_id,
+            ),
+        }
+    }
+
+    expired_points
+}
+
+impl Collection {
+    /// Delete expired points now, without waiting for the background task
+    ///
+    /// Returns the number of expired points found in shards whose updates are led by this peer.
+    pub async fn expire_points(&self) -> usize {
+        expire_points(&self.shards_holder).await
+    }
+}
@@ -7,6 +7,7 @@ use shard::query::mmr::mmr_from_points_with_vector as mmr_from_points_with_vecto
 use tokio::runtime::Handle;
 use tokio_util::task::AbortOnDropHandle;
 
+use crate::collection::Collection;
 use crate::config::CollectionParams;
 use crate::operations::types::{CollectionError, CollectionResult};
 
@@ -43,3 +44,31 @@ pub async fn mmr_from_points_with_vector(
 
     Ok(result)
 }
+
+impl Collection {
+    /// Rerank already scored points with MMR, keeping at most `limit` of them.
+    ///
+    /// Points must carry the vector named in `mmr.using`.
+    pub async fn mmr_rerank(
+        &self,
+        points_with_vector: Vec<ScoredPoint>,
+        mmr: MmrInternal,
+        limit: usize,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let collection_params = self.collection_config.read().await.params.clone();
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        mmr_from_points_with_vector(
+            &collection_params,
+            points_with_vector,
+            mmr,
+            limit,
+            &self.search_runtime,
+            timeout,
+            hw_measurement_acc,
+        )
+        .await
+    }
+}
@@ -1,11 +1,14 @@
 mod clean;
 mod collection_ops;
 pub mod distance_matrix;
+pub mod expiration;
 mod facet;
 pub mod mmr;
 pub mod payload_index_schema;
+mod point_history;
 mod point_ops;
 pub mod query;
+mod query_plan;
 mod resharding;
 mod search;
 mod shard_transfer;
@@ -13,6 +16,7 @@ mod sharding_keys;
 mod snapshots;
 mod state_management;
 mod telemetry;
+mod write_coalescing;
 
 use std::collections::HashMap;
 use std::ops::Deref;
@@ -23,11 +27,13 @@ use std::time::Duration;
 use clean::ShardCleanTasks;
 use common::budget::ResourceBudget;
 use common::save_on_disk::SaveOnDisk;
+use expiration::ExpirationTask;
 use io::storage_version::StorageVersion;
 use segment::types::ShardKey;
 use semver::Version;
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
+use write_coalescing::WriteCoalescingTask;
 
 use crate::collection::collection_ops::ABORT_TRANSFERS_ON_SHARD_DROP_FIX_FROM_VERSION;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
@@ -91,6 +97,10 @@ pub struct Collection {
     collection_stats_cache: CollectionSizeStatsCache,
     // Background tasks to clean shards
     shard_clean_tasks: ShardCleanTasks,
+    // Background task to delete expired points
+    _expiration_task: ExpirationTask,
+    // Background task to apply coalesced upserts abandoned by cancelled requests
+    _write_coalescing_task: WriteCoalescingTask,
 }
 
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
@@ -172,6 +182,10 @@ impl Collection {
             Self::estimate_collection_size_stats(&locked_shard_holder).await,
         );
 
+        let update_runtime = update_runtime.unwrap_or_else(Handle::current);
+        let expiration_task = ExpirationTask::new(&locked_shard_holder, &update_runtime);
+        let write_coalescing_task = WriteCoalescingTask::new(&locked_shard_holder, &update_runtime);
+
         // Once the config is persisted - the collection is considered to be successfully created.
         CollectionVersion::save(path)?;
         collection_config.save(path)?;
@@ -194,11 +208,13 @@ impl Collection {
             init_time: start_time.elapsed(),
             is_initialized: Default::default(),
             updates_lock: Default::default(),
-            update_runtime: update_runtime.unwrap_or_else(Handle::current),
+            update_runtime,
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_resource_budget,
             collection_stats_cache,
             shard_clean_tasks: Default::default(),
+            _expiration_task: expiration_task,
+            _write_coalescing_task: write_coalescing_task,
         })
     }
 
@@ -292,6 +308,10 @@ impl Collection {
             Self::estimate_collection_size_stats(&locked_shard_holder).await,
         );
 
+        let update_runtime = update_runtime.unwrap_or_else(Handle::current);
+        let expiration_task = ExpirationTask::new(&locked_shard_holder, &update_runtime);
+        let write_coalescing_task = WriteCoalescingTask::new(&locked_shard_holder, &update_runtime);
+
         Self {
             id: collection_id.clone(),
             shards_holder: locked_shard_holder,
@@ -310,11 +330,13 @@ impl Collection {
             init_time: start_time.elapsed(),
             is_initialized: Default::default(),
             updates_lock: Default::default(),
-            update_runtime: update_runtime.unwrap_or_else(Handle::current),
+            update_runtime,
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_resource_budget,
             collection_stats_cache,
             shard_clean_tasks: Default::default(),
+            _expiration_task: expiration_task,
+            _write_coalescing_task: write_coalescing_task,
         }
     }
 
@@ -0,0 +1,47 @@
+use futures::future;
+use segment::types::PointIdType;
+
+use super::Collection;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::local_shard::point_history::PointVersion;
+
+impl Collection {
+    /// Previous versions of the point, latest first.
+    ///
+    /// History is kept by every replica independently, only local replicas of the selected shards are read.
+    /// Fails if any of the selected shards has no replica on this peer.
+    pub async fn point_history(
+        &self,
+        point_id: PointIdType,
+        shard_selection: ShardSelectorInternal,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(&shard_selection)?;
+
+        let histories = target_shards.iter().map(|(replica_set, _)| async move {
+            replica_set
+                .point_history_local(point_id)
+                .await?
+                .ok_or_else(|| {
+                    CollectionError::bad_request(format!(
+                        "Point history of shard {} is not available on this peer, \
+                         request it from a peer with a replica of the shard",
+                        replica_set.shard_id,
+                    ))
+                })
+        });
+
+        let mut versions: Vec<_> = future::try_join_all(histories)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        // A point might be present in several shards while resharding
+        versions.sort_unstable_by_key(|version| std::cmp::Reverse(version.replaced_by));
+        versions.dedup_by_key(|version| (version.version, version.replaced_by));
+
+        Ok(versions)
+    }
+}
@@ -6,13 +6,13 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use futures::stream::FuturesUnordered;
 use futures::{StreamExt as _, TryFutureExt, TryStreamExt as _, future};
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::OrderBy;
 use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
 use shard::retrieve::record_internal::RecordInternal;
 
 use super::Collection;
 use crate::operations::consistency_params::ReadConsistency;
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::point_ops::{UpdateAck, WriteOrdering};
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
 use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
@@ -92,7 +92,7 @@ impl Collection {
         &self,
         operation: OperationWithClockTag,
         shard_selection: ShardId,
-        wait: bool,
+        ack: UpdateAck,
         ordering: WriteOrdering,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
@@ -107,7 +107,11 @@ impl Collection {
             };
 
             match ordering {
-                WriteOrdering::Weak => shard.update_local(operation, wait, hw_measurement_acc.clone(), false).await,
+                WriteOrdering::Weak => {
+                    shard
+                        .update_local(operation, ack.wait(), hw_measurement_acc, false)
+                        .await
+                }
                 WriteOrdering::Medium | WriteOrdering::Strong => {
                     if let Some(clock_tag) = operation.clock_tag {
                         log::warn!(
@@ -118,7 +122,13 @@ impl Collection {
                     }
 
                     shard
-                        .update_with_consistency(operation.operation, wait, ordering, false, hw_measurement_acc)
+                        .update_with_consistency(
+                            operation.operation,
+                            ack,
+                            ordering,
+                            false,
+                            hw_measurement_acc,
+                        )
                         .await
                         .map(Some)
                 }
@@ -137,80 +147,101 @@ impl Collection {
         }
     }
 
+    /// With [`UpdateAck::None`], returns as soon as the update is scheduled,
+    /// unless it has a precondition, which is checked before returning.
+    ///
     /// # Cancel safety
     ///
     /// This method is cancel safe.
     pub async fn update_from_client(
         &self,
         operation: CollectionUpdateOperations,
-        wait: bool,
+        ack: UpdateAck,
         ordering: WriteOrdering,
         shard_keys_selection: Option<ShardKey>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
+        // Rejection of an update by its precondition must be reported, so it is always awaited
+        let detach = ack == UpdateAck::None && operation.precondition().is_none();
+
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
 
-        let mut results = self
-            .update_runtime
-            .spawn(async move {
-                let _update_lock = update_lock;
+        let update_task = self.update_runtime.spawn(async move {
+            let _update_lock = update_lock;
 
-                let updates = FuturesUnordered::new();
-                let operations = shard_holder.split_by_shard(operation, &shard_keys_selection)?;
-
-                for (shard, operation) in operations {
-                    let operation = shard_holder.split_by_mode(shard.shard_id, operation);
-
-                    let hw_acc = hw_measurement_acc.clone();
-                    updates.push(async move {
-                        let mut result = UpdateResult {
-                            operation_id: None,
-                            status: UpdateStatus::Acknowledged,
-                            clock_tag: None,
-                        };
-
-                        for operation in operation.update_all {
-                            result = shard
-                                .update_with_consistency(
-                                    operation,
-                                    wait,
-                                    ordering,
-                                    false,
-                                    hw_acc.clone(),
-                                )
-                                .await?;
-                        }
+            let updates = FuturesUnordered::new();
+            let operations = shard_holder.split_by_shard(operation, &shard_keys_selection)?;
+
+            for (shard, operation) in operations {
+                let operation = shard_holder.split_by_mode(shard.shard_id, operation);
 
-                        for operation in operation.update_only_existing {
-                            let res = shard
-                                .update_with_consistency(
-                                    operation,
-                                    wait,
-                                    ordering,
-                                    true,
-                                    hw_acc.clone(),
-                                )
-                                .await;
-
-                            if let Err(err) = &res
-                                && err.is_missing_point()
-                            {
-                                continue;
-                            }
-
-                            result = res?;
+                let hw_acc = hw_measurement_acc.clone();
+                updates.push(async move {
+                    let mut result = UpdateResult {
+                        operation_id: None,
+                        status: UpdateStatus::Acknowledged,
+                        clock_tag: None,
+                    };
+
+                    for operation in operation.update_all {
+                        result = shard
+                            .update_with_consistency(
+                                operation,
+                                ack,
+                                ordering,
+                                false,
+                                hw_acc.clone(),
+                            )
+                            .await?;
+                    }
+
+                    for operation in operation.update_only_existing {
+                        let res = shard
+                            .update_with_consistency(operation, ack, ordering, true, hw_acc.clone())
+                            .await;
+
+                        if let Err(err) = &res
+                            && err.is_missing_point()
+                        {
+                            continue;
                         }
 
-                        CollectionResult::Ok(result)
-                    });
+                        result = res?;
+                    }
+
+                    CollectionResult::Ok(result)
+                });
+            }
+
+            let results: Vec<_> = updates.collect().await;
+
+            CollectionResult::Ok(results)
+        });
+
+        if detach {
+            // Nobody waits for the result, so at least report failures
+            let collection_id = self.id.clone();
+            self.update_runtime.spawn(async move {
+                let results = match update_task.await {
+                    Ok(Ok(results)) => results,
+                    Ok(Err(err)) => vec![Err(err)],
+                    Err(err) => vec![Err(CollectionError::from(err))],
+                };
+
+                for err in results.into_iter().filter_map(Result::err) {
+                    log::warn!("Unacknowledged update of collection {collection_id} failed: {err}");
                 }
+            });
 
-                let results: Vec<_> = updates.collect().await;
+            return Ok(UpdateResult {
+                operation_id: None,
+                status: UpdateStatus::Acknowledged,
+                clock_tag: None,
+            });
+        }
 
-                CollectionResult::Ok(results)
-            })
-            .await??;
+        let mut results = update_task.await??;
 
         if results.is_empty() {
             return Err(CollectionError::bad_request(
@@ -256,8 +287,14 @@ impl Collection {
         ordering: WriteOrdering,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
-        self.update_from_client(operation, wait, ordering, None, hw_measurement_acc)
-            .await
+        self.update_from_client(
+            operation,
+            UpdateAck::from_wait(wait),
+            ordering,
+            None,
+            hw_measurement_acc,
+        )
+        .await
     }
 
     pub async fn scroll_by(
@@ -334,9 +371,13 @@ impl Collection {
             Some(order_by) => {
                 retrieved_iter
                     // Get top results
-                    .kmerge_by(|a, b| match order_by.direction() {
-                        Direction::Asc => (a.order_value, a.id) < (b.order_value, b.id),
-                        Direction::Desc => (a.order_value, a.id) > (b.order_value, b.id),
+                    .kmerge_by(|a, b| {
+                        order_by
+                            .compare_values(
+                                (a.order_value, &a.then_by_values, a.id),
+                                (b.order_value, &b.then_by_values, b.id),
+                            )
+                            .is_lt()
                     })
                     .dedup_by(|record_a, record_b| {
                         (record_a.order_value, record_a.id) == (record_b.order_value, record_b.id)
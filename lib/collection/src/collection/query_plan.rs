@@ -0,0 +1,85 @@
+use std::future::Future;
+use std::time::Duration;
+
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use futures::future;
+use tokio::sync::RwLockReadGuard;
+
+use super::Collection;
+use crate::common::fetch_vectors::{
+    build_vector_resolver_queries, resolve_referenced_vectors_batch,
+};
+use crate::common::retrieve_request_trait::RetrieveRequest;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::universal_query::collection_query::CollectionQueryRequest;
+use crate::operations::universal_query::query_plan::{QueryPlan, ShardQueryPlan};
+
+impl Collection {
+    /// Describe how the query would be executed on each targeted shard, without running it.
+    ///
+    /// Referenced point ids are still resolved into vectors, as the shard requests are built from them.
+    pub async fn query_plan<'a, F, Fut>(
+        &self,
+        request: CollectionQueryRequest,
+        shard_selection: ShardSelectorInternal,
+        collection_by_name: F,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<QueryPlan>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+    {
+        let requests_batch = vec![(request, shard_selection)];
+        let resolver_requests = build_vector_resolver_queries(&requests_batch);
+
+        let ids_to_vectors = resolve_referenced_vectors_batch(
+            &resolver_requests,
+            self,
+            collection_by_name,
+            read_consistency,
+            timeout,
+            hw_measurement_acc.clone(),
+        )
+        .await?;
+
+        for (resolver_req, _) in &resolver_requests {
+            for point_id in resolver_req.get_referenced_point_ids() {
+                let lookup_collection = resolver_req.get_lookup_collection();
+                if ids_to_vectors.get(lookup_collection, point_id).is_none() {
+                    return Err(CollectionError::PointNotFound {
+                        missed_point_id: point_id,
+                    });
+                }
+            }
+        }
+
+        let (request, shard_selection) = requests_batch.into_iter().next().unwrap();
+        let shard_request = request.try_into_shard_request(&self.id, &ids_to_vectors)?;
+
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(&shard_selection)?;
+
+        let shard_plans = target_shards.iter().map(|(replica_set, shard_key)| {
+            let shard_request = shard_request.clone();
+            let hw_measurement_acc = &hw_measurement_acc;
+            async move {
+                let stages = replica_set
+                    .query_plan_local(shard_request, hw_measurement_acc)
+                    .await?;
+                CollectionResult::Ok(ShardQueryPlan {
+                    shard_id: replica_set.shard_id,
+                    shard_key: shard_key.cloned(),
+                    local: stages.is_some(),
+                    stages: stages.unwrap_or_default(),
+                })
+            }
+        });
+
+        let shards = future::try_join_all(shard_plans).await?;
+        Ok(QueryPlan { shards })
+    }
+}
@@ -43,9 +43,10 @@ impl Collection {
                 shard_selection,
                 timeout,
                 hw_measurement_acc,
+                false,
             )
             .await?;
-        Ok(results.into_iter().next().unwrap())
+        Ok(results.results.into_iter().next().unwrap())
     }
 
     pub async fn core_search_batch(
@@ -56,10 +57,53 @@ impl Collection {
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let results = self
+            .core_search_batch_impl(
+                request,
+                read_consistency,
+                shard_selection,
+                timeout,
+                hw_measurement_acc,
+                false,
+            )
+            .await?;
+        Ok(results.results)
+    }
+
+    /// Same as [`Self::core_search_batch`], but shards which don't answer within the timeout
+    /// are left out of the results instead of failing the whole batch.
+    pub async fn core_search_batch_partial(
+        &self,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<PartialSearchResults> {
+        self.core_search_batch_impl(
+            request,
+            read_consistency,
+            shard_selection,
+            timeout,
+            hw_measurement_acc,
+            true,
+        )
+        .await
+    }
+
+    async fn core_search_batch_impl(
+        &self,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+        partial_ok: bool,
+    ) -> CollectionResult<PartialSearchResults> {
         let start = Instant::now();
         // shortcuts batch if all requests with limit=0
         if request.searches.iter().all(|s| s.limit == 0) {
-            return Ok(vec![]);
+            return Ok(PartialSearchResults::default());
         }
 
         let is_payload_required = request
@@ -84,7 +128,9 @@ impl Collection {
         let is_required_transfer_large_enough = require_transfers
             > used_transfers.saturating_mul(super::query::PAYLOAD_TRANSFERS_FACTOR_THRESHOLD);
 
-        if metadata_required && is_required_transfer_large_enough {
+        // Payloads are fetched in a second step with the remaining timeout,
+        // which can't be partial, so partial searches fetch them along with the points
+        if metadata_required && is_required_transfer_large_enough && !partial_ok {
             // If there is a significant offset, we need to retrieve the whole result
             // set without payload first and then retrieve the payload.
             // It is required to do this because the payload might be too large to send over the
@@ -110,8 +156,10 @@ impl Collection {
                     &shard_selection,
                     timeout,
                     hw_measurement_acc.clone(),
+                    false,
                 )
-                .await?;
+                .await?
+                .results;
             // update timeout
             let timeout = timeout.map(|t| t.saturating_sub(start.elapsed()));
             let filled_results = without_payload_results
@@ -128,18 +176,18 @@ impl Collection {
                         hw_measurement_acc.clone(),
                     )
                 });
-            future::try_join_all(filled_results).await
+            let results = future::try_join_all(filled_results).await?;
+            Ok(PartialSearchResults::complete(results))
         } else {
-            let result = self
-                .do_core_search_batch(
-                    request,
-                    read_consistency,
-                    &shard_selection,
-                    timeout,
-                    hw_measurement_acc,
-                )
-                .await?;
-            Ok(result)
+            self.do_core_search_batch(
+                request,
+                read_consistency,
+                &shard_selection,
+                timeout,
+                hw_measurement_acc,
+                partial_ok,
+            )
+            .await
         }
     }
 
@@ -150,18 +198,20 @@ impl Collection {
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        partial_ok: bool,
+    ) -> CollectionResult<PartialSearchResults> {
         let request = Arc::new(request);
 
         let instant = Instant::now();
 
         // query all shards concurrently
-        let all_searches_res = {
+        let (all_searches_res, timed_out_shards) = {
             let shard_holder = self.shards_holder.read().await;
             let target_shards = shard_holder.select_shards(shard_selection)?;
             let all_searches = target_shards.into_iter().map(|(shard, shard_key)| {
+                let shard_id = shard.shard_id;
                 let shard_key = shard_key.cloned();
-                shard
+                let search = shard
                     .core_search(
                         request.clone(),
                         read_consistency,
@@ -179,9 +229,32 @@ impl Collection {
                             }
                         }
                         Ok(records)
-                    })
+                    });
+                (shard_id, search)
             });
-            future::try_join_all(all_searches).await?
+
+            if partial_ok {
+                let (shard_ids, searches): (Vec<_>, Vec<_>) = all_searches.unzip();
+                let searches = searches
+                    .into_iter()
+                    .map(|search| with_search_deadline(search, timeout));
+
+                let mut all_searches_res = Vec::with_capacity(shard_ids.len());
+                let mut timed_out_shards = Vec::new();
+                for (shard_id, result) in
+                    shard_ids.into_iter().zip(future::join_all(searches).await)
+                {
+                    match result {
+                        Ok(records) => all_searches_res.push(records),
+                        Err(CollectionError::Timeout { .. }) => timed_out_shards.push(shard_id),
+                        Err(err) => return Err(err),
+                    }
+                }
+                (all_searches_res, timed_out_shards)
+            } else {
+                let searches = all_searches.map(|(_shard_id, search)| search);
+                (future::try_join_all(searches).await?, Vec::new())
+            }
         };
 
         let result = self
@@ -196,7 +269,10 @@ impl Collection {
 
         self.post_process_if_slow_request(instant.elapsed(), filters_refs);
 
-        result
+        Ok(PartialSearchResults {
+            results: result?,
+            timed_out_shards,
+        })
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -331,3 +407,17 @@ impl Collection {
         }
     }
 }
+
+/// Fail the search with a timeout error if it didn't finish within `timeout`,
+/// even if the shard itself doesn't enforce it in time.
+async fn with_search_deadline<T>(
+    search: impl Future<Output = CollectionResult<T>>,
+    timeout: Option<Duration>,
+) -> CollectionResult<T> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, search)
+            .await
+            .unwrap_or_else(|_| Err(CollectionError::timeout(timeout, "search"))),
+        None => search.await,
+    }
+}
@@ -0,0 +1,55 @@
+//! Flushing of coalesced upserts abandoned by cancelled requests.
+//!
+//! A batch of coalesced upserts is applied by the request which started it. If that request is
+//! cancelled, the batch would stay buffered until enough points are upserted to fill it, so it is
+//! applied by this task instead.
+
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use cancel::{CancellationToken, DropGuard};
+use tokio::runtime::Handle;
+
+use crate::shards::shard_holder::LockedShardHolder;
+
+/// Interval between two checks for abandoned coalesced upserts
+const FLUSH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Background task periodically applying abandoned coalesced upserts of the collection
+///
+/// The task is cancelled when this is dropped.
+pub(super) struct WriteCoalescingTask {
+    _cancel: DropGuard,
+}
+
+impl WriteCoalescingTask {
+    pub fn new(shards_holder: &Arc<LockedShardHolder>, runtime: &Handle) -> Self {
+        let shards_holder = Arc::downgrade(shards_holder);
+        let cancel = CancellationToken::default();
+
+        runtime.spawn(Self::task(shards_holder, cancel.clone()));
+
+        Self {
+            _cancel: cancel.drop_guard(),
+        }
+    }
+
+    async fn task(shards_holder: Weak<LockedShardHolder>, cancel: CancellationToken) {
+        loop {
+            // Only cancel in between flushes, as updates are not cancel safe
+            let interval = tokio::time::sleep(FLUSH_CHECK_INTERVAL);
+            if cancel.run_until_cancelled(interval).await.is_none() {
+                return;
+            }
+
+            let Some(shards_holder) = shards_holder.upgrade() else {
+                return;
+            };
+
+            let shards_holder = shards_holder.read().await;
+            for shard in shards_holder.all_shards() {
+                shard.flush_abandoned_writes().await;
+            }
+        }
+    }
+}
@@ -85,14 +85,18 @@ mod tests {
     use common::counter::hardware_accumulator::HwMeasurementAcc;
     use itertools::Itertools;
     use parking_lot::RwLockUpgradableReadGuard;
+    use segment::data_types::named_vectors::NamedVectors;
     use segment::data_types::vectors::{
         DEFAULT_VECTOR_NAME, VectorStructInternal, only_default_vector,
     };
     use segment::entry::entry_point::SegmentEntry;
     use segment::json_path::JsonPath;
     use segment::payload_json;
+    use segment::segment_constructor::simple_segment_constructor::{
+        VECTOR1_NAME, VECTOR2_NAME, build_multivec_segment,
+    };
     use segment::types::PayloadSchemaType::Keyword;
-    use segment::types::{Payload, PayloadContainer, PayloadFieldSchema, WithPayload};
+    use segment::types::{Distance, Payload, PayloadContainer, PayloadFieldSchema, WithPayload};
     use serde_json::json;
     use shard::retrieve::retrieve_blocking::retrieve_blocking;
     use shard::update::upsert_points;
@@ -105,8 +109,9 @@ mod tests {
     use crate::collection_manager::holders::segment_holder::LockedSegment::Original;
     use crate::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
     use crate::operations::point_ops::{
-        PointOperations, PointStructPersisted, VectorStructPersisted,
+        PointOperations, PointStructPersisted, VectorPersisted, VectorStructPersisted,
     };
+    use crate::operations::vector_ops::{PointVectorsPersisted, UpdateVectorsOp, VectorOperations};
 
     #[test]
     fn test_sync_ops() {
@@ -222,6 +227,8 @@ mod tests {
             101,
             PointOperations::DeletePoints {
                 ids: vec![500.into()],
+                precondition: None,
+                deleted_at: None,
             },
             &hw_counter,
         )
@@ -265,6 +272,7 @@ mod tests {
                 points: Some(points.clone()),
                 filter: None,
                 key: None,
+                precondition: None,
             }),
             &hw_counter,
         )
@@ -420,6 +428,7 @@ mod tests {
                 points: Some(points.clone()),
                 filter: None,
                 key: Some(meta_key_path.clone()),
+                precondition: None,
             }),
             &hw_counter,
         )
@@ -484,6 +493,7 @@ mod tests {
                 points: Some(points.clone()),
                 filter: None,
                 key: Some(meta_key_path.clone()),
+                precondition: None,
             }),
             &hw_counter,
         )
@@ -518,4 +528,81 @@ mod tests {
             },
         };
     }
+
+    #[test]
+    fn test_update_vectors_keeps_other_vectors_and_payload() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let is_stopped = AtomicBool::new(false);
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut segment = build_multivec_segment(dir.path(), 4, 2, Distance::Dot).unwrap();
+        let mut vectors = NamedVectors::default();
+        vectors.insert(VECTOR1_NAME.to_owned(), vec![1.0, 1.0, 1.0, 1.0].into());
+        vectors.insert(VECTOR2_NAME.to_owned(), vec![2.0, 2.0].into());
+        segment
+            .upsert_point(100, 1.into(), vectors, &hw_counter)
+            .unwrap();
+        segment
+            .set_payload(
+                100,
+                1.into(),
+                &payload_json! { "color": "red" },
+                &None,
+                &hw_counter,
+            )
+            .unwrap();
+
+        // Point is moved into the appendable segment on update
+        segment.appendable_flag = false;
+        let appendable_segment = build_multivec_segment(dir.path(), 4, 2, Distance::Dot).unwrap();
+
+        let mut holder = SegmentHolder::default();
+        holder.add_new(segment);
+        holder.add_new(appendable_segment);
+        let segments = Arc::new(RwLock::new(holder));
+
+        let updated = process_vector_operation(
+            &segments,
+            101,
+            VectorOperations::UpdateVectors(UpdateVectorsOp {
+                points: vec![PointVectorsPersisted {
+                    id: 1.into(),
+                    vector: VectorStructPersisted::Named(
+                        [(
+                            VECTOR1_NAME.to_owned(),
+                            VectorPersisted::Dense(vec![3.0, 3.0, 3.0, 3.0]),
+                        )]
+                        .into(),
+                    ),
+                }],
+                update_filter: None,
+            }),
+            &hw_counter,
+        )
+        .unwrap();
+        assert_eq!(updated, 1);
+
+        let records = retrieve_blocking(
+            segments,
+            &[1.into()],
+            &WithPayload::from(true),
+            &true.into(),
+            TEST_TIMEOUT,
+            &is_stopped,
+            HwMeasurementAcc::new(),
+        )
+        .unwrap();
+        assert_eq!(records.len(), 1);
+
+        let record = records.into_values().next().unwrap();
+        let vectors = record.vector.as_ref().unwrap();
+        let updated_vector = vec![3.0, 3.0, 3.0, 3.0];
+        let kept_vector = vec![2.0, 2.0];
+        assert_eq!(vectors.get(VECTOR1_NAME), Some((&updated_vector).into()));
+        assert_eq!(vectors.get(VECTOR2_NAME), Some((&kept_vector).into()));
+        assert_eq!(
+            record.payload.as_ref().unwrap(),
+            &payload_json! { "color": "red" },
+        );
+    }
 }
@@ -576,8 +576,8 @@ pub trait SegmentOptimizer {
         }
 
         for (point_id, versions) in deleted_points_snapshot {
-            optimized_segment
-                .delete_point(versions.operation_version, point_id, hw_counter)
+            versions
+                .delete_from(&mut optimized_segment, point_id, hw_counter)
                 .unwrap();
         }
 
@@ -805,6 +805,10 @@ pub trait SegmentOptimizer {
                     let proxy_read = proxy.read();
                     for (point_id, versions) in proxy_read.get_deleted_points() {
                         let entry = deleted_points.entry(*point_id).or_insert(*versions);
+                        // Soft or hard, the newest delete wins
+                        if versions.operation_version > entry.operation_version {
+                            entry.deleted_at = versions.deleted_at;
+                        }
                         entry.operation_version =
                             entry.operation_version.max(versions.operation_version);
                         entry.local_version = entry.local_version.max(versions.local_version);
@@ -942,8 +946,8 @@ pub trait SegmentOptimizer {
                     >= optimized_segment.point_version(point_id).unwrap_or(0),
                 "proxied point deletes should have newer version than point in segment",
             );
-            optimized_segment
-                .delete_point(versions.operation_version, point_id, hw_counter)
+            versions
+                .delete_from(&mut optimized_segment, point_id, hw_counter)
                 .unwrap();
         }
 
@@ -97,8 +97,11 @@ impl VacuumOptimizer {
         };
         let read_segment = segment_entry.read();
 
-        let littered_ratio =
-            read_segment.deleted_point_count() as f64 / read_segment.total_point_count() as f64;
+        // Soft deleted points are carried over on optimization, they are not litter
+        let littered_point_count = read_segment
+            .deleted_point_count()
+            .saturating_sub(read_segment.tombstone_count());
+        let littered_ratio = littered_point_count as f64 / read_segment.total_point_count() as f64;
         let is_big = read_segment.total_point_count() >= self.min_vectors_number;
         let is_littered = littered_ratio > self.deleted_threshold;
 
@@ -307,6 +307,7 @@ fn test_delete_all_point_versions() {
                 payload: None,
                 shard_key: None,
                 order_value: None,
+                then_by_values: vec![],
             }
         )])
     );
@@ -318,7 +319,7 @@ fn test_delete_all_point_versions() {
         assert!(holder.get(sid2).unwrap().get().read().has_point(point_id));
 
         // Delete point 123
-        delete_points(&holder, 102, &[123.into()], &hw_counter).unwrap();
+        delete_points(&holder, 102, &[123.into()], None, &hw_counter).unwrap();
 
         // Assert that point 123 is deleted from both segments
         // Note: before the bug fix the point was only deleted from segment 2
@@ -42,6 +42,25 @@ pub struct WalConfig {
     #[validate(range(min = 1))]
     #[serde(default = "default_wal_retain_closed")]
     pub wal_retain_closed: usize,
+    /// Number of previous versions to keep for each point, to be retrieved with the point history API.
+    /// History is not kept if not set. Changes only apply once the collection is loaded again.
+    /// If set, every update of points by ID first reads the parts of the points it changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub point_history_versions: Option<usize>,
+    /// If set, deleted points are kept for this number of seconds, and can be restored in the meantime.
+    /// Points are deleted permanently if not set. Changes only apply once the collection is loaded again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_delete_retention_sec: Option<u64>,
+    /// If set, small upserts into a shard are buffered for up to this number of milliseconds,
+    /// and applied together as a single operation. Only applies to upserts with `weak` ordering.
+    /// Buffered upserts respond once they are applied, so they take up to this long, even with `wait=false`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_coalescing_ms: Option<u64>,
+    /// Number of buffered points at which coalesced upserts are applied without waiting any longer.
+    /// Default: 1000
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub write_coalescing_max_points: Option<usize>,
 }
 
 fn default_wal_retain_closed() -> usize {
@@ -54,6 +73,10 @@ impl From<&WalConfig> for WalOptions {
             wal_capacity_mb,
             wal_segments_ahead,
             wal_retain_closed,
+            point_history_versions: _,
+            soft_delete_retention_sec: _,
+            write_coalescing_ms: _,
+            write_coalescing_max_points: _,
         } = config;
         WalOptions {
             segment_capacity: wal_capacity_mb * 1024 * 1024,
@@ -69,6 +92,10 @@ impl Default for WalConfig {
             wal_capacity_mb: 32,
             wal_segments_ahead: 0,
             wal_retain_closed: default_wal_retain_closed(),
+            point_history_versions: None,
+            soft_delete_retention_sec: None,
+            write_coalescing_ms: None,
+            write_coalescing_max_points: None,
         }
     }
 }
@@ -120,6 +120,11 @@ fn discovery_into_core_search(
         with_vector: request.with_vector,
         score_threshold: None,
         with_explanation: false,
+        with_explanation_bundle: false,
+        explanation_verbosity: Default::default(),
+        explanation_params: Default::default(),
+        mmr: None,
+        distinct_by: None,
     };
 
     Ok(core_search)
@@ -258,6 +258,42 @@ mod unit_tests {
         assert_eq!(result[1].hits[1].id, 3.into());
     }
 
+    #[test]
+    fn test_group_by_nested_path() {
+        let nested_point = |idx: u64, score: ScoreType, authors: Value| ScoredPoint {
+            payload: Some(payload_json! { "metadata": { "authors": authors } }),
+            ..empty_point(idx, score)
+        };
+        let scored_points = vec![
+            nested_point(1, 0.99, json!([{ "id": "a" }, { "id": "b" }])),
+            nested_point(2, 0.995, json!({ "id": "b" })),
+            nested_point(3, 0.75, json!([{ "id": "c" }, { "name": "no id" }])),
+        ];
+
+        let mut aggregator = GroupsAggregator::new(
+            3,
+            2,
+            "metadata.authors[].id".parse().unwrap(),
+            Some(Order::LargeBetter),
+        );
+        aggregator.add_points(&scored_points);
+
+        let groups: Vec<_> = aggregator
+            .distill()
+            .into_iter()
+            .map(|group| (group.key, group.hits.iter().map(|hit| hit.id).collect_vec()))
+            .collect();
+
+        assert_eq!(
+            groups,
+            vec![
+                (GroupId::from("b"), vec![2.into(), 1.into()]),
+                (GroupId::from("a"), vec![1.into()]),
+                (GroupId::from("c"), vec![3.into()]),
+            ],
+        );
+    }
+
     struct Case {
         point: ScoredPoint,
         key: Value,
@@ -1,11 +1,15 @@
 use std::future::Future;
+use std::iter;
 use std::time::Duration;
 
 use ahash::AHashMap;
-use api::rest::{BaseGroupRequest, SearchGroupsRequestInternal, SearchRequestInternal};
+use api::rest::{
+    BaseGroupRequest, GroupAggregations, SearchGroupsRequestInternal, SearchRequestInternal,
+};
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
+use segment::data_types::groups::GroupId;
 use segment::json_path::JsonPath;
 use segment::types::{
     AnyVariants, Condition, FieldCondition, Filter, Match, ScoredPoint, WithPayloadInterface,
@@ -23,7 +27,8 @@ use crate::lookup::WithLookup;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    CollectionResult, PointGroup, RecommendGroupsRequestInternal, RecommendRequestInternal,
+    CollectionResult, CountRequestInternal, PointGroup, RecommendGroupsRequestInternal,
+    RecommendRequestInternal,
 };
 use crate::operations::universal_query::collection_query::{
     CollectionQueryGroupsRequest, CollectionQueryRequest,
@@ -57,6 +62,9 @@ pub struct GroupRequest {
 
     /// Options for specifying how to use the group id to lookup points in another collection
     pub with_lookup: Option<WithLookup>,
+
+    /// Aggregations to compute for each group
+    pub aggregations: Option<GroupAggregations>,
 }
 
 impl GroupRequest {
@@ -76,6 +84,7 @@ impl GroupRequest {
             group_size,
             limit,
             with_lookup: None,
+            aggregations: None,
         }
     }
 
@@ -131,14 +140,61 @@ impl GroupRequest {
             group_by: self.group_by,
             group_size: self.group_size,
             groups: self.limit,
+            aggregations: self.aggregations,
         })
     }
 }
 
 impl QueryGroupRequest {
-    /// Make `group_by` field selector work with as `with_payload`.
-    fn group_by_to_payload_selector(group_by: &JsonPath) -> WithPayloadInterface {
-        WithPayloadInterface::Fields(vec![group_by.strip_wildcard_suffix()])
+    /// Make `group_by` and aggregated fields selector work with as `with_payload`.
+    fn group_by_to_payload_selector(&self) -> WithPayloadInterface {
+        let aggregated_fields = self
+            .aggregations
+            .iter()
+            .flat_map(|aggregations| &aggregations.payload);
+        WithPayloadInterface::Fields(
+            iter::once(&self.group_by)
+                .chain(aggregated_fields)
+                .map(JsonPath::strip_wildcard_suffix)
+                .collect(),
+        )
+    }
+
+    /// Count points of the group which match the request filter
+    async fn count_group(
+        &self,
+        group_key: &GroupId,
+        collection: &Collection,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<usize> {
+        let in_group = Filter {
+            must: Some(match_on(&self.group_by, &[Value::from(group_key.clone())])),
+            ..Default::default()
+        };
+        let filter = self
+            .source
+            .filter
+            .as_ref()
+            .map(|filter| filter.merge(&in_group))
+            .unwrap_or(in_group);
+
+        let request = CountRequestInternal {
+            filter: Some(filter),
+            exact: true,
+        };
+        let result = collection
+            .count(
+                request,
+                read_consistency,
+                shard_selection,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await?;
+        Ok(result.count)
     }
 
     async fn r#do(
@@ -160,7 +216,7 @@ impl QueryGroupRequest {
         let key_not_empty = Filter::new_must_not(Condition::IsEmpty(self.group_by.clone().into()));
         request.filter = Some(request.filter.unwrap_or_default().merge(&key_not_empty));
 
-        let with_group_by_payload = Self::group_by_to_payload_selector(&self.group_by);
+        let with_group_by_payload = self.group_by_to_payload_selector();
 
         // We're enriching the final results at the end, so we'll keep this minimal
         request.with_payload = with_group_by_payload;
@@ -193,7 +249,9 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
                     group_size,
                     limit,
                     with_lookup: with_lookup_interface,
+                    aggregations,
                 },
+            with_explanation: _, // explained by the caller, after grouping
         } = request;
 
         let search = SearchRequestInternal {
@@ -206,6 +264,12 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
             with_vector,
             score_threshold,
             with_explanation: None,
+            with_explanation_bundle: None,
+            explanation_verbosity: None,
+            explanation_params: None,
+            explanation_mode: None,
+            mmr: None,
+            distinct_by: None,
         };
 
         GroupRequest {
@@ -214,6 +278,7 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
             group_size: group_size as usize,
             limit: limit as usize,
             with_lookup: with_lookup_interface.map(Into::into),
+            aggregations,
         }
     }
 }
@@ -224,6 +289,7 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             positive,
             negative,
             strategy,
+            rocchio,
             filter,
             params,
             with_payload,
@@ -237,6 +303,7 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
                     group_size,
                     limit,
                     with_lookup: with_lookup_interface,
+                    aggregations,
                 },
         } = request;
 
@@ -244,6 +311,7 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             positive,
             negative,
             strategy,
+            rocchio,
             filter,
             params,
             limit: 0,
@@ -261,6 +329,7 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             group_size: group_size as usize,
             limit: limit as usize,
             with_lookup: with_lookup_interface.map(Into::into),
+            aggregations,
         }
     }
 }
@@ -281,6 +350,9 @@ impl From<CollectionQueryGroupsRequest> for GroupRequest {
             group_size,
             limit,
             with_lookup: with_lookup_interface,
+            aggregations,
+            with_explanation: _,   // explained by the caller, after grouping
+            explanation_params: _, // explained by the caller, after grouping
         } = request;
 
         let collection_query_request = CollectionQueryRequest {
@@ -295,6 +367,9 @@ impl From<CollectionQueryGroupsRequest> for GroupRequest {
             with_vector,
             with_payload,
             lookup_from,
+            with_explanation: false,
+            explanation_params: Default::default(),
+            distinct_by: None,
         };
 
         GroupRequest {
@@ -303,6 +378,7 @@ impl From<CollectionQueryGroupsRequest> for GroupRequest {
             group_size,
             limit,
             with_lookup: with_lookup_interface,
+            aggregations,
         }
     }
 }
@@ -458,6 +534,29 @@ pub async fn group_by(
     // extract best results
     let mut groups = aggregator.distill();
 
+    // aggregate while hits still carry the grouping payload
+    let mut groups_aggregations = Vec::new();
+    if let Some(aggregations) = &request.aggregations {
+        for group in &groups {
+            let mut group_aggregations = group.aggregate(aggregations);
+            if aggregations.count {
+                let timeout = timeout.map(|t| t.saturating_sub(start.elapsed()));
+                let count = request
+                    .count_group(
+                        &group.key,
+                        collection,
+                        read_consistency,
+                        &shard_selection,
+                        timeout,
+                        hw_measurement_acc.clone(),
+                    )
+                    .await?;
+                group_aggregations.count = Some(count);
+            }
+            groups_aggregations.push(group_aggregations);
+        }
+    }
+
     // flatten results
     let bare_points = groups
         .iter()
@@ -490,7 +589,10 @@ pub async fn group_by(
         .for_each(|group| group.hydrate_from(&enriched_points));
 
     // turn into output form
-    let groups = groups.into_iter().map(PointGroup::from).collect();
+    let mut groups: Vec<_> = groups.into_iter().map(PointGroup::from).collect();
+    for (group, aggregations) in groups.iter_mut().zip(groups_aggregations) {
+        group.aggregations = Some(aggregations);
+    }
 
     Ok(groups)
 }
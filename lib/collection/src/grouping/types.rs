@@ -1,9 +1,13 @@
 use ahash::AHashMap;
+use api::rest::GroupAggregations;
 use segment::data_types::groups::GroupId;
 use segment::json_path::JsonPath;
-use segment::types::{PointIdType, ScoredPoint};
+use segment::types::{PayloadContainer, PointIdType, ScoredPoint};
+use serde_json::Value;
 
-use crate::operations::types::PointGroup;
+use crate::operations::types::{
+    GroupAggregationsResult, GroupPayloadStats, GroupScoreStats, PointGroup,
+};
 use crate::operations::universal_query::shard_query::ShardQueryRequest;
 
 #[derive(PartialEq, Debug)]
@@ -26,6 +30,49 @@ impl Group {
             }
         });
     }
+
+    /// Compute aggregations over the hits of the group, except for the count of all group points.
+    /// Hits must still carry the aggregated payload fields.
+    pub(super) fn aggregate(&self, aggregations: &GroupAggregations) -> GroupAggregationsResult {
+        let score = (aggregations.score && !self.hits.is_empty()).then(|| {
+            let scores = self.hits.iter().map(|hit| hit.score);
+            GroupScoreStats {
+                min: scores.clone().fold(f32::INFINITY, f32::min),
+                max: scores.clone().fold(f32::NEG_INFINITY, f32::max),
+                avg: scores.sum::<f32>() / self.hits.len() as f32,
+            }
+        });
+
+        let payload = aggregations
+            .payload
+            .iter()
+            .map(|path| {
+                let mut stats = GroupPayloadStats::default();
+                for payload in self.hits.iter().filter_map(|hit| hit.payload.as_ref()) {
+                    let numbers = payload
+                        .get_value(path)
+                        .into_iter()
+                        .flat_map(|v| match v {
+                            Value::Array(arr) => arr.iter().collect(),
+                            _ => vec![v],
+                        })
+                        .filter_map(Value::as_f64);
+                    for number in numbers {
+                        stats.count += 1;
+                        stats.sum += number;
+                    }
+                }
+                stats.avg = (stats.count > 0).then(|| stats.sum / stats.count as f64);
+                (path.to_string(), stats)
+            })
+            .collect();
+
+        GroupAggregationsResult {
+            count: None,
+            score,
+            payload,
+        }
+    }
 }
 
 impl From<Group> for PointGroup {
@@ -38,6 +85,7 @@ impl From<Group> for PointGroup {
                 .collect(),
             id: group.key,
             lookup: None,
+            aggregations: None,
         }
     }
 }
@@ -55,11 +103,67 @@ pub struct QueryGroupRequest {
 
     /// Limit of groups to return
     pub groups: usize,
+
+    /// Aggregations to compute for each group
+    pub aggregations: Option<GroupAggregations>,
 }
 
 #[cfg(test)]
 mod test {
+    use api::rest::GroupAggregations;
     use segment::data_types::groups::GroupId;
+    use segment::payload_json;
+    use segment::types::ScoredPoint;
+
+    use super::Group;
+    use crate::operations::types::{GroupPayloadStats, GroupScoreStats};
+
+    #[test]
+    fn test_group_aggregate() {
+        let hit = |id: u64, score: f32, price: serde_json::Value| ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: Some(payload_json! { "price": price }),
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: None,
+        };
+        let group = Group {
+            hits: vec![
+                hit(1, 0.9, serde_json::json!(10)),
+                hit(2, 0.6, serde_json::json!([2.5, 7.5])),
+                hit(3, 0.3, serde_json::json!("not a number")),
+            ],
+            key: GroupId::from("group"),
+        };
+
+        let aggregations = GroupAggregations {
+            count: true,
+            score: true,
+            payload: vec!["price".parse().unwrap(), "missing".parse().unwrap()],
+        };
+        let result = group.aggregate(&aggregations);
+
+        assert_eq!(result.count, None);
+        let score = result.score.unwrap();
+        assert_eq!((score.min, score.max), (0.3, 0.9));
+        assert!((score.avg - 0.6).abs() < 1e-6);
+        assert_eq!(
+            result.payload["price"],
+            GroupPayloadStats {
+                count: 3,
+                sum: 20.0,
+                avg: Some(20.0 / 3.0),
+            },
+        );
+        assert_eq!(result.payload["missing"], GroupPayloadStats::default());
+
+        let no_score = group.aggregate(&GroupAggregations::default());
+        assert_eq!(no_score.score, None::<GroupScoreStats>);
+        assert!(no_score.payload.is_empty());
+    }
 
     #[test]
     fn group_key_from_values() {
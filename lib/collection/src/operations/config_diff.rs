@@ -84,6 +84,19 @@ pub struct WalConfigDiff {
     pub wal_segments_ahead: Option<usize>,
     /// Number of closed WAL segments to retain
     pub wal_retain_closed: Option<usize>,
+    /// Number of previous versions to keep for each point
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub point_history_versions: Option<usize>,
+    /// Number of seconds deleted points are kept to be restored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_delete_retention_sec: Option<u64>,
+    /// Number of milliseconds small upserts are buffered to be applied together
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_coalescing_ms: Option<u64>,
+    /// Number of buffered points at which coalesced upserts are applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub write_coalescing_max_points: Option<usize>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
@@ -285,12 +298,21 @@ impl DiffConfig<WalConfigDiff> for WalConfig {
             wal_capacity_mb,
             wal_segments_ahead,
             wal_retain_closed,
+            point_history_versions,
+            soft_delete_retention_sec,
+            write_coalescing_ms,
+            write_coalescing_max_points,
         } = diff;
 
         WalConfig {
             wal_capacity_mb: wal_capacity_mb.unwrap_or(self.wal_capacity_mb),
             wal_segments_ahead: wal_segments_ahead.unwrap_or(self.wal_segments_ahead),
             wal_retain_closed: wal_retain_closed.unwrap_or(self.wal_retain_closed),
+            point_history_versions: point_history_versions.or(self.point_history_versions),
+            soft_delete_retention_sec: soft_delete_retention_sec.or(self.soft_delete_retention_sec),
+            write_coalescing_ms: write_coalescing_ms.or(self.write_coalescing_ms),
+            write_coalescing_max_points: write_coalescing_max_points
+                .or(self.write_coalescing_max_points),
         }
     }
 }
@@ -406,12 +428,20 @@ impl From<WalConfig> for WalConfigDiff {
             wal_capacity_mb,
             wal_segments_ahead,
             wal_retain_closed,
+            point_history_versions,
+            soft_delete_retention_sec,
+            write_coalescing_ms,
+            write_coalescing_max_points,
         } = config;
 
         WalConfigDiff {
             wal_capacity_mb: Some(wal_capacity_mb),
             wal_segments_ahead: Some(wal_segments_ahead),
             wal_retain_closed: Some(wal_retain_closed),
+            point_history_versions,
+            soft_delete_retention_sec,
+            write_coalescing_ms,
+            write_coalescing_max_points,
         }
     }
 }
@@ -12,7 +12,9 @@ use api::grpc::qdrant::update_collection_cluster_setup_request::{
     Operation as ClusterOperationsPb, Operation,
 };
 use api::rest::schema::ShardKeySelector;
-use api::rest::{BaseGroupRequest, LookupLocation, MaxOptimizationThreads, ShardKeyWithFallback};
+use api::rest::{
+    BaseGroupRequest, LookupLocation, MaxOptimizationThreads, RocchioParams, ShardKeyWithFallback,
+};
 use itertools::Itertools;
 use segment::common::operation_error::OperationError;
 use segment::data_types::modifier::Modifier;
@@ -49,7 +51,9 @@ use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
-use crate::operations::point_ops::{FilterSelector, PointIdsList, PointsSelector, WriteOrdering};
+use crate::operations::point_ops::{
+    FilterSelector, PointIdsList, PointsSelector, UpdateAck, WriteOrdering,
+};
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CollectionWarning,
@@ -115,6 +119,31 @@ pub fn write_ordering_from_proto(
     })
 }
 
+pub fn update_ack_to_proto(ack: UpdateAck) -> api::grpc::qdrant::UpdateAck {
+    match ack {
+        UpdateAck::None => api::grpc::qdrant::UpdateAck::None,
+        UpdateAck::Queued => api::grpc::qdrant::UpdateAck::Queued,
+        UpdateAck::Applied => api::grpc::qdrant::UpdateAck::Applied,
+        UpdateAck::Replicated => api::grpc::qdrant::UpdateAck::Replicated,
+    }
+}
+
+pub fn update_ack_from_proto(ack: Option<i32>) -> Result<Option<UpdateAck>, Status> {
+    let Some(ack) = ack else {
+        return Ok(None);
+    };
+
+    let ack_parsed = api::grpc::qdrant::UpdateAck::try_from(ack)
+        .map_err(|_| Status::invalid_argument(format!("cannot convert ack: {ack}")))?;
+
+    Ok(Some(match ack_parsed {
+        api::grpc::qdrant::UpdateAck::None => UpdateAck::None,
+        api::grpc::qdrant::UpdateAck::Queued => UpdateAck::Queued,
+        api::grpc::qdrant::UpdateAck::Applied => UpdateAck::Applied,
+        api::grpc::qdrant::UpdateAck::Replicated => UpdateAck::Replicated,
+    }))
+}
+
 pub fn try_record_from_grpc(
     point: api::grpc::qdrant::RetrievedPoint,
     with_payload: bool,
@@ -150,9 +179,28 @@ pub fn try_record_from_grpc(
         vector,
         shard_key: convert_shard_key_from_grpc_opt(shard_key),
         order_value,
+        then_by_values: vec![],
     })
 }
 
+impl TryFrom<api::grpc::qdrant::ContextExamplePair> for ContextExamplePair {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::ContextExamplePair) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::ContextExamplePair { positive, negative } = value;
+        match (
+            positive.map(|p| p.try_into()),
+            negative.map(|n| n.try_into()),
+        ) {
+            (Some(Ok(positive)), Some(Ok(negative))) => Ok(Self { positive, negative }),
+            (Some(Err(e)), _) | (_, Some(Err(e))) => Err(e),
+            (None, _) | (_, None) => Err(Status::invalid_argument(
+                "Both positive and negative are required in a context pair",
+            )),
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub fn try_discover_request_from_grpc(
     value: api::grpc::qdrant::DiscoverPoints,
@@ -187,20 +235,7 @@ pub fn try_discover_request_from_grpc(
 
     let context = context
         .into_iter()
-        .map(|pair| {
-            match (
-                pair.positive.map(|p| p.try_into()),
-                pair.negative.map(|n| n.try_into()),
-            ) {
-                (Some(Ok(positive)), Some(Ok(negative))) => {
-                    Ok(ContextExamplePair { positive, negative })
-                }
-                (Some(Err(e)), _) | (_, Some(Err(e))) => Err(e),
-                (None, _) | (_, None) => Err(Status::invalid_argument(
-                    "Both positive and negative are required in a context pair",
-                )),
-            }
-        })
+        .map(ContextExamplePair::try_from)
         .try_collect()?;
 
     let request = DiscoverRequestInternal {
@@ -290,6 +325,11 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
             wal_capacity_mb: wal_capacity_mb.map(|v| v as usize),
             wal_segments_ahead: wal_segments_ahead.map(|v| v as usize),
             wal_retain_closed: wal_retain_closed.map(|v| v as usize),
+            // Point history, soft delete and write coalescing are not configurable through gRPC
+            point_history_versions: None,
+            soft_delete_retention_sec: None,
+            write_coalescing_ms: None,
+            write_coalescing_max_points: None,
         }
     }
 }
@@ -519,6 +559,10 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                         wal_capacity_mb,
                         wal_segments_ahead,
                         wal_retain_closed,
+                        point_history_versions: _,
+                        soft_delete_retention_sec: _,
+                        write_coalescing_ms: _,
+                        write_coalescing_max_points: _,
                     } = wal_config;
 
                     api::grpc::qdrant::WalConfigDiff {
@@ -632,6 +676,10 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfig {
             wal_capacity_mb: wal_capacity_mb.unwrap_or_default() as usize,
             wal_segments_ahead: wal_segments_ahead.unwrap_or_default() as usize,
             wal_retain_closed: wal_retain_closed.unwrap_or_default() as usize,
+            point_history_versions: None,
+            soft_delete_retention_sec: None,
+            write_coalescing_ms: None,
+            write_coalescing_max_points: None,
         }
     }
 }
@@ -886,6 +934,8 @@ pub fn try_points_selector_from_grpc(
                 shard_key: shard_key_selector
                     .map(ShardKeySelector::try_from)
                     .transpose()?,
+                if_version: None,
+                if_payload_matches: None,
             }))
         }
         Some(api::grpc::qdrant::points_selector::PointsSelectorOneOf::Filter(f)) => {
@@ -894,6 +944,8 @@ pub fn try_points_selector_from_grpc(
                 shard_key: shard_key_selector
                     .map(ShardKeySelector::try_from)
                     .transpose()?,
+                if_version: None,
+                if_payload_matches: None,
             }))
         }
         _ => Err(Status::invalid_argument("Malformed PointsSelector type")),
@@ -911,6 +963,7 @@ impl From<UpdateResult> for api::grpc::qdrant::UpdateResultInternal {
             operation_id,
             status: status.into(),
             clock_tag: clock_tag.map(Into::into),
+            assigned_ids: Vec::new(),
         }
     }
 }
@@ -929,6 +982,7 @@ impl TryFrom<api::grpc::qdrant::UpdateResultInternal> for UpdateResult {
             operation_id,
             status,
             clock_tag,
+            assigned_ids: _,
         } = res;
         let res = Self {
             operation_id,
@@ -1012,6 +1066,11 @@ impl<'a> From<CollectionCoreSearchRequest<'a>> for api::grpc::qdrant::CoreSearch
             score_threshold,
             offset,
             with_explanation: _, // gRPC doesn't support explanation yet
+            with_explanation_bundle: _,
+            explanation_verbosity: _,
+            explanation_params: _,
+            mmr: _, // reranked by the caller
+            distinct_by: _,
         } = request;
         Self {
             collection_name: collection_id,
@@ -1076,7 +1135,12 @@ impl TryFrom<api::grpc::qdrant::TargetVector> for RecommendExample {
 impl TryFrom<PointGroup> for api::grpc::qdrant::PointGroup {
     type Error = OperationError;
     fn try_from(group: PointGroup) -> Result<Self, Self::Error> {
-        let PointGroup { hits, id, lookup } = group;
+        let PointGroup {
+            hits,
+            id,
+            lookup,
+            aggregations: _, // no gRPC
+        } = group;
         let hits: Result<_, _> = hits
             .into_iter()
             .map(api::grpc::qdrant::ScoredPoint::try_from)
@@ -1189,6 +1253,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequestInternal {
             positive,
             negative,
             strategy,
+            rocchio,
             using,
             lookup_from,
             filter,
@@ -1231,6 +1296,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequestInternal {
             positive,
             negative,
             strategy: strategy.map(|s| s.try_into()).transpose()?,
+            rocchio: rocchio.map(RocchioParams::from),
             filter: filter.map(|f| f.try_into()).transpose()?,
             params: params.map(|p| p.into()),
             limit: limit as usize,
@@ -1256,6 +1322,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             positive: value.positive,
             negative: value.negative,
             strategy: value.strategy,
+            rocchio: value.rocchio,
             using: value.using,
             lookup_from: value.lookup_from,
             filter: value.filter,
@@ -1277,6 +1344,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             positive,
             negative,
             strategy,
+            rocchio,
             using,
             lookup_from,
             filter,
@@ -1292,6 +1360,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             positive,
             negative,
             strategy,
+            rocchio,
             using,
             lookup_from,
             filter,
@@ -1304,6 +1373,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
                 limit: value.limit,
                 group_size: value.group_size,
                 with_lookup: value.with_lookup.map(|l| l.try_into()).transpose()?,
+                aggregations: None, // no gRPC
             },
         })
     }
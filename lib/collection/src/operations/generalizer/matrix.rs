@@ -10,6 +10,7 @@ impl Generalizer for SearchMatrixRequestInternal {
             sample,
             limit,
             using,
+            with_explanation,
         } = self;
 
         Self {
@@ -17,6 +18,7 @@ impl Generalizer for SearchMatrixRequestInternal {
             sample: *sample,
             limit: *limit,
             using: using.clone(),
+            with_explanation: *with_explanation,
         }
     }
 }
@@ -28,6 +30,7 @@ impl Generalizer for CollectionSearchMatrixRequest {
             limit_per_sample,
             filter,
             using,
+            with_explanation,
         } = self;
 
         Self {
@@ -35,6 +38,7 @@ impl Generalizer for CollectionSearchMatrixRequest {
             limit_per_sample: *limit_per_sample,
             filter: filter.clone(),
             using: using.clone(),
+            with_explanation: *with_explanation,
         }
     }
 }
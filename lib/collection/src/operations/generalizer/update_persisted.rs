@@ -55,15 +55,24 @@ impl Generalizer for PointOperations {
                     upsert_conditional_operation.remove_details(),
                 )
             }
-            PointOperations::DeletePoints { ids } => {
-                PointOperations::DeletePoints { ids: ids.clone() }
-            }
-            PointOperations::DeletePointsByFilter(filter) => {
-                PointOperations::DeletePointsByFilter(filter.clone())
+            PointOperations::DeletePoints {
+                ids,
+                precondition,
+                deleted_at,
+            } => PointOperations::DeletePoints {
+                ids: ids.clone(),
+                precondition: precondition.clone(),
+                deleted_at: *deleted_at,
+            },
+            PointOperations::DeletePointsByFilter(delete) => {
+                PointOperations::DeletePointsByFilter(delete.clone())
             }
             PointOperations::SyncPoints(sync_operation) => {
                 PointOperations::SyncPoints(sync_operation.remove_details())
             }
+            PointOperations::RestorePoints { ids } => {
+                PointOperations::RestorePoints { ids: ids.clone() }
+            }
             #[cfg(feature = "staging")]
             PointOperations::TestDelay(op) => PointOperations::TestDelay(op.clone()),
         }
@@ -107,11 +116,13 @@ impl Generalizer for ConditionalInsertOperationInternal {
         let Self {
             points_op,
             condition,
+            precondition,
         } = self;
 
         Self {
             condition: condition.clone(),
             points_op: points_op.remove_details(),
+            precondition: precondition.clone(),
         }
     }
 }
@@ -282,6 +293,7 @@ impl Generalizer for SetPayloadOp {
             points,
             filter,
             key,
+            precondition,
         } = self;
 
         Self {
@@ -289,6 +301,7 @@ impl Generalizer for SetPayloadOp {
             points: points.clone(),
             filter: filter.clone(),
             key: key.clone(),
+            precondition: precondition.clone(),
         }
     }
 }
@@ -53,11 +53,11 @@ impl EstimateOperationEffectArea for point_ops::PointOperations {
             point_ops::PointOperations::UpsertPointsConditional(conditional_upsert) => {
                 conditional_upsert.points_op.estimate_effect_area()
             }
-            point_ops::PointOperations::DeletePoints { ids } => {
+            point_ops::PointOperations::DeletePoints { ids, .. } => {
                 OperationEffectArea::Points(Cow::Borrowed(ids))
             }
-            point_ops::PointOperations::DeletePointsByFilter(filter) => {
-                OperationEffectArea::Filter(filter)
+            point_ops::PointOperations::DeletePointsByFilter(delete) => {
+                OperationEffectArea::Filter(&delete.filter)
             }
             point_ops::PointOperations::SyncPoints(sync_op) => {
                 debug_assert!(
@@ -68,6 +68,9 @@ impl EstimateOperationEffectArea for point_ops::PointOperations {
                     sync_op.points.iter().map(|x| x.id).collect(),
                 ))
             }
+            point_ops::PointOperations::RestorePoints { ids } => {
+                OperationEffectArea::Points(Cow::Borrowed(ids))
+            }
             #[cfg(feature = "staging")]
             point_ops::PointOperations::TestDelay(_) => OperationEffectArea::Empty,
         }
@@ -50,6 +50,7 @@ impl SplitByShard for SetPayloadOp {
                         payload: self.payload.clone(),
                         filter: self.filter.clone(),
                         key: self.key.clone(),
+                        precondition: self.precondition.clone(),
                     }
                 })
             }
@@ -7,7 +7,7 @@ use schemars::JsonSchema;
 use segment::common::utils::transpose_map_into_named_vector;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::VectorInternal;
-use segment::types::Filter;
+use segment::types::{Filter, SeqNumberType};
 use serde::{Deserialize, Serialize};
 pub use shard::operations::point_ops::*;
 use validator::{Validate, ValidationErrors};
@@ -40,6 +40,12 @@ pub struct FilterSelector {
     pub filter: Filter,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Not supported when selecting points by filter, the request is rejected if specified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<SeqNumberType>,
+    /// Not supported when selecting points by filter, the request is rejected if specified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_payload_matches: Option<Filter>,
 }
 
 /// Defines write ordering guarantees for collection operations
@@ -59,6 +65,42 @@ pub enum WriteOrdering {
     Strong,
 }
 
+/// Defines when an update operation is acknowledged
+///
+/// * `none` - once the operation is accepted, it may still fail without the client noticing
+///
+/// * `queued` - once the operation is written into the WAL and queued to be applied, same as `wait=false`. The WAL is not flushed to disk before responding, so the operation may be lost on a crash
+///
+/// * `applied` - once the operation is applied and visible to search, same as `wait=true`
+///
+/// * `replicated` - once the operation is applied on the majority of replicas of the affected shards
+///
+/// Levels are ordered from the earliest to the latest acknowledgement.
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateAck {
+    None,
+    Queued,
+    Applied,
+    Replicated,
+}
+
+impl UpdateAck {
+    pub fn from_wait(wait: bool) -> Self {
+        if wait { Self::Applied } else { Self::Queued }
+    }
+
+    /// Whether replicas have to apply the operation before responding
+    pub fn wait(self) -> bool {
+        match self {
+            Self::None | Self::Queued => false,
+            Self::Applied | Self::Replicated => true,
+        }
+    }
+}
+
 impl SplitByShard for PointOperations {
     fn split_by_shard(self, ring: &HashRingRouter) -> OperationToShard<Self> {
         match self {
@@ -68,8 +110,17 @@ impl SplitByShard for PointOperations {
             PointOperations::UpsertPointsConditional(conditional_upsert) => conditional_upsert
                 .split_by_shard(ring)
                 .map(PointOperations::UpsertPointsConditional),
-            PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
-                .map(|ids| PointOperations::DeletePoints { ids }),
+            PointOperations::DeletePoints {
+                ids,
+                precondition,
+                deleted_at,
+            } => {
+                split_iter_by_shard(ids, |id| *id, ring).map(|ids| PointOperations::DeletePoints {
+                    ids,
+                    precondition: precondition.clone(),
+                    deleted_at,
+                })
+            }
             by_filter @ PointOperations::DeletePointsByFilter(_) => {
                 OperationToShard::to_all(by_filter)
             }
@@ -79,6 +130,8 @@ impl SplitByShard for PointOperations {
                 #[cfg(not(debug_assertions))]
                 OperationToShard::by_shard(vec![])
             }
+            PointOperations::RestorePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
+                .map(|ids| PointOperations::RestorePoints { ids }),
             #[cfg(feature = "staging")]
             test_delay @ PointOperations::TestDelay(_) => OperationToShard::to_all(test_delay),
         }
@@ -103,6 +156,7 @@ impl SplitByShard for ConditionalInsertOperationInternal {
         let ConditionalInsertOperationInternal {
             points_op,
             condition,
+            precondition,
         } = self;
 
         let points_op = points_op.split_by_shard(ring);
@@ -116,6 +170,7 @@ impl SplitByShard for ConditionalInsertOperationInternal {
                             ConditionalInsertOperationInternal {
                                 points_op: upsert_operation,
                                 condition: condition.clone(),
+                                precondition: precondition.clone(),
                             },
                         )
                     })
@@ -124,6 +179,7 @@ impl SplitByShard for ConditionalInsertOperationInternal {
             OperationToShard::ToAll(upsert_operation) => OperationToShard::ToAll(Self {
                 points_op: upsert_operation,
                 condition,
+                precondition,
             }),
         }
     }
@@ -397,6 +453,9 @@ mod tests {
             },
             shard_key: None,
             update_filter: None,
+            if_version: None,
+            if_payload_matches: None,
+            if_not_exists: None,
         });
         assert!(batch.validate().is_err());
 
@@ -408,6 +467,9 @@ mod tests {
             },
             shard_key: None,
             update_filter: None,
+            if_version: None,
+            if_payload_matches: None,
+            if_not_exists: None,
         });
         assert!(batch.validate().is_ok());
 
@@ -419,6 +481,9 @@ mod tests {
             },
             shard_key: None,
             update_filter: None,
+            if_version: None,
+            if_payload_matches: None,
+            if_not_exists: None,
         });
         assert!(batch.validate().is_err());
     }
@@ -8,7 +8,7 @@ use std::time::{Duration, SystemTimeError};
 
 use api::grpc::transport_channel_pool::RequestError;
 use api::rest::{
-    BaseGroupRequest, LookupLocation, OrderByInterface, RecommendStrategy,
+    BaseGroupRequest, LookupLocation, OrderByInterface, RecommendStrategy, RocchioParams,
     SearchGroupsRequestInternal, SearchRequestInternal, ShardKeySelector, VectorStructOutput,
 };
 use common::ext::OptionExt;
@@ -27,7 +27,7 @@ use segment::data_types::modifier::Modifier;
 use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, DenseVector};
 use segment::types::{
     Distance, Filter, HnswConfig, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType,
-    PointIdType, QuantizationConfig, SearchParams, SeqNumberType, ShardKey,
+    PointIdType, QuantizationConfig, ScoredPoint, SearchParams, SeqNumberType, ShardKey,
     SparseVectorStorageType, StrictModeConfigOutput, VectorName, VectorNameBuf,
     VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
@@ -403,6 +403,16 @@ pub struct UpdateResult {
     pub clock_tag: Option<ClockTag>,
 }
 
+/// Result of an upsert with ids assigned by the server
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct InsertPointsResult {
+    #[serde(flatten)]
+    pub result: UpdateResult,
+    /// Assigned ids, in the order of the points in the request
+    pub assigned_ids: Vec<PointIdType>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct ScrollRequest {
@@ -521,6 +531,10 @@ pub struct SearchRequest {
 pub struct SearchRequestBatch {
     #[validate(nested)]
     pub searches: Vec<SearchRequest>,
+    /// If true, shards which don't answer within the timeout are left out instead of failing the request.
+    /// The response then reports whether results are partial, and which shards timed out.
+    #[serde(default)]
+    pub partial_ok: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -642,6 +656,10 @@ pub struct RecommendRequestInternal {
     /// How to use positive and negative examples to find the results
     pub strategy: Option<api::rest::RecommendStrategy>,
 
+    /// Weights of the `rocchio` strategy
+    #[serde(default)]
+    pub rocchio: Option<RocchioParams>,
+
     /// Look only for points which satisfies this conditions
     #[validate(nested)]
     pub filter: Option<Filter>,
@@ -715,6 +733,10 @@ pub struct RecommendGroupsRequestInternal {
     #[serde(default)]
     pub strategy: Option<RecommendStrategy>,
 
+    /// Weights of the `rocchio` strategy
+    #[serde(default)]
+    pub rocchio: Option<RocchioParams>,
+
     /// Look only for points which satisfies this conditions
     #[validate(nested)]
     pub filter: Option<Filter>,
@@ -834,10 +856,28 @@ pub struct DiscoverRequestInternal {
     pub lookup_from: Option<LookupLocation>,
 }
 
+impl DiscoverRequestInternal {
+    /// Add context pairs shared by a batch of requests to the own context of this request
+    pub fn extend_context(&mut self, shared_context: &[ContextExamplePair]) {
+        if shared_context.is_empty() {
+            return;
+        }
+        self.context
+            .get_or_insert_with(Vec::new)
+            .extend_from_slice(shared_context);
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 pub struct DiscoverRequestBatch {
     #[validate(nested)]
     pub searches: Vec<DiscoverRequest>,
+
+    /// Pairs of { positive, negative } examples shared by all searches of the batch.
+    /// They are added to the context of each search, so large contexts need to be sent only once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub context: Option<Vec<ContextExamplePair>>,
 }
 
 #[derive(Debug, Serialize, JsonSchema, Clone)]
@@ -849,6 +889,39 @@ pub struct PointGroup {
     /// Record that has been looked up using the group id
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lookup: Option<api::rest::Record>,
+    /// Aggregations requested for the group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregations: Option<GroupAggregationsResult>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct GroupAggregationsResult {
+    /// Number of points of the group which match the request filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+    /// Statistics of the scores of the returned hits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<GroupScoreStats>,
+    /// Statistics of numeric payload values of the returned hits, by field
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub payload: HashMap<String, GroupPayloadStats>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+pub struct GroupScoreStats {
+    pub min: ScoreType,
+    pub max: ScoreType,
+    pub avg: ScoreType,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, Default, PartialEq)]
+pub struct GroupPayloadStats {
+    /// Number of numeric values found in the field
+    pub count: usize,
+    pub sum: f64,
+    /// Average of the values, absent if no numeric value was found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg: Option<f64>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -893,6 +966,27 @@ pub struct CountResult {
     pub count: usize,
 }
 
+/// Results of a search batch, which only contain the points of shards answering within the timeout
+#[derive(Debug, Default, Clone)]
+pub struct PartialSearchResults {
+    pub results: Vec<Vec<ScoredPoint>>,
+    /// Shards which did not answer within the timeout, their points are missing from the results
+    pub timed_out_shards: Vec<ShardId>,
+}
+
+impl PartialSearchResults {
+    pub fn complete(results: Vec<Vec<ScoredPoint>>) -> Self {
+        Self {
+            results,
+            timed_out_shards: Vec::new(),
+        }
+    }
+
+    pub fn is_partial(&self) -> bool {
+        !self.timed_out_shards.is_empty()
+    }
+}
+
 #[derive(Error, Debug, Clone, PartialEq)]
 #[error("{0}")]
 pub enum CollectionError {
@@ -942,6 +1036,8 @@ pub enum CollectionError {
     },
     #[error("Shard temporarily unavailable: {description}")]
     ShardUnavailable { description: String },
+    #[error("Conflict: {description}")]
+    Conflict { description: String },
 }
 
 impl CollectionError {
@@ -1080,6 +1176,7 @@ impl CollectionError {
             Self::PointNotFound { .. } => false,
             Self::BadRequest { .. } => false,
             Self::BadShardSelection { .. } => false,
+            Self::Conflict { .. } => false,
             Self::InconsistentShardFailure { .. } => false,
             Self::ForwardProxyError { .. } => false,
             Self::ObjectStoreError { .. } => false,
@@ -1167,6 +1264,9 @@ impl From<OperationError> for CollectionError {
             OperationError::MissingMapIndexForFacet { .. } => Self::bad_input(format!("{err}")),
             OperationError::VariableTypeError { .. } => Self::bad_input(format!("{err}")),
             OperationError::NonFiniteNumber { .. } => Self::bad_input(format!("{err}")),
+            OperationError::PreconditionConflict { .. } => Self::Conflict {
+                description: format!("{err}"),
+            },
             OperationError::RocksDbColumnFamilyNotFound { .. } => Self::ServiceError {
                 error: format!("{err}"),
                 backtrace: None,
@@ -1278,6 +1378,9 @@ impl From<tonic::Status> for CollectionError {
             tonic::Code::FailedPrecondition => CollectionError::PreConditionFailed {
                 description: format!("{err}"),
             },
+            tonic::Code::Aborted => CollectionError::Conflict {
+                description: format!("{err}"),
+            },
             tonic::Code::ResourceExhausted => {
                 // extract retry-after from metadata
                 // the value is passed as a String containing an integer number of seconds
@@ -1302,7 +1405,6 @@ impl From<tonic::Status> for CollectionError {
             tonic::Code::Ok
             | tonic::Code::Unknown
             | tonic::Code::PermissionDenied
-            | tonic::Code::Aborted
             | tonic::Code::OutOfRange
             | tonic::Code::Unimplemented
             | tonic::Code::Unavailable
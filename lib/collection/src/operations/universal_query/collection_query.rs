@@ -1,6 +1,6 @@
 use ahash::AHashSet;
-use api::rest::LookupLocation;
-use common::types::ScoreType;
+use api::rest::{GroupAggregations, LookupLocation, RocchioParams};
+use common::types::{ExplanationParams, ScoreType};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use segment::data_types::order_by::OrderBy;
@@ -15,6 +15,7 @@ use segment::vector_storage::query::{
     ContextPair, ContextQuery, DiscoveryQuery, FeedbackItem, NaiveFeedbackCoefficients, RecoQuery,
 };
 use serde::Serialize;
+use shard::query::mmr::DEFAULT_MMR_LAMBDA;
 use shard::query::query_enum::QueryEnum;
 
 use super::formula::FormulaInternal;
@@ -25,9 +26,9 @@ use crate::common::fetch_vectors::ReferencedVectors;
 use crate::lookup::WithLookup;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::universal_query::shard_query::MmrInternal;
-use crate::recommendations::avg_vector_for_recommendation;
-
-const DEFAULT_MMR_LAMBDA: f32 = 0.5;
+use crate::recommendations::{
+    avg_vector_for_recommendation, rocchio_weights, weighted_sum_vectors,
+};
 
 /// Internal representation of a query request, used to converge from REST and gRPC. This can have IDs referencing vectors.
 #[derive(Clone, Debug, PartialEq)]
@@ -44,6 +45,12 @@ pub struct CollectionQueryRequest {
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
     pub lookup_from: Option<LookupLocation>,
+    /// If true, explain which dimensions contributed most to each result's score
+    pub with_explanation: bool,
+    /// Number of dimensions returned in explanations
+    pub explanation_params: ExplanationParams,
+    /// Keep only the best scored point of each value of this payload field
+    pub distinct_by: Option<JsonPath>,
 }
 
 impl CollectionQueryRequest {
@@ -84,6 +91,12 @@ pub struct CollectionQueryGroupsRequest {
     pub group_size: usize,
     pub limit: usize,
     pub with_lookup: Option<WithLookup>,
+    /// Aggregations to compute for each group
+    pub aggregations: Option<GroupAggregations>,
+    /// If true, explain which dimensions contributed most to the score of each group hit
+    pub with_explanation: bool,
+    /// Number of dimensions returned in explanations
+    pub explanation_params: ExplanationParams,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -168,6 +181,7 @@ pub enum VectorQuery<T> {
     Discover(DiscoveryQuery<T>),
     Context(ContextQuery<T>),
     Feedback(FeedbackQuery<T>),
+    Arithmetic(ArithmeticQuery<T>),
 }
 
 impl<T> VectorQuery<T> {
@@ -182,6 +196,7 @@ impl<T> VectorQuery<T> {
             VectorQuery::Discover(query) => Box::new(query.flat_iter()),
             VectorQuery::Context(query) => Box::new(query.flat_iter()),
             VectorQuery::Feedback(query) => Box::new(query.flat_iter()),
+            VectorQuery::Arithmetic(query) => Box::new(query.flat_iter()),
         }
     }
 }
@@ -219,6 +234,72 @@ pub enum FeedbackStrategy {
     Naive { a: f32, b: f32, c: f32 },
 }
 
+/// Weighted sum of vectors, used as a nearest neighbors query
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArithmeticQuery<T> {
+    pub add: Vec<T>,
+    pub subtract: Vec<T>,
+    /// Weight of each vector, in the order of `add` followed by `subtract`
+    pub weights: Option<Vec<f32>>,
+}
+
+impl<T> ArithmeticQuery<T> {
+    /// Query of the Rocchio recommend strategy, `alpha * avg(positives) - beta * avg(negatives)`
+    pub fn rocchio(reco: RecoQuery<T>, params: RocchioParams) -> Self {
+        let RecoQuery {
+            positives,
+            negatives,
+        } = reco;
+        let (positive_weight, negative_weight) =
+            rocchio_weights(params, positives.len(), negatives.len());
+        let weights = std::iter::repeat_n(positive_weight, positives.len())
+            .chain(std::iter::repeat_n(negative_weight, negatives.len()))
+            .collect();
+
+        Self {
+            add: positives,
+            subtract: negatives,
+            weights: Some(weights),
+        }
+    }
+
+    fn flat_iter(&self) -> impl Iterator<Item = &T> {
+        self.add.iter().chain(&self.subtract)
+    }
+}
+
+impl ArithmeticQuery<VectorInternal> {
+    /// Adds up all vectors, each multiplied by its weight, negated for subtracted ones
+    fn into_vector(self) -> CollectionResult<VectorInternal> {
+        let Self {
+            add,
+            subtract,
+            weights,
+        } = self;
+
+        let vectors_count = add.len() + subtract.len();
+        let weights = match weights {
+            Some(weights) if weights.len() != vectors_count => {
+                return Err(CollectionError::bad_input(format!(
+                    "Expected {vectors_count} weights, one per vector, got {}",
+                    weights.len(),
+                )));
+            }
+            Some(weights) => weights,
+            None => vec![1.0; vectors_count],
+        };
+
+        let terms = add
+            .iter()
+            .map(|vector| (vector, 1.0))
+            .chain(subtract.iter().map(|vector| (vector, -1.0)))
+            .zip(weights)
+            .map(|((vector, sign), weight)| (VectorRef::from(vector), sign * weight));
+
+        weighted_sum_vectors(terms)
+    }
+}
+
 impl VectorQuery<VectorInputInternal> {
     /// Turns all [VectorInputInternal]s into [VectorInternal]s, using the provided [ReferencedVectors] to look up the vectors.
     ///
@@ -359,6 +440,28 @@ impl VectorQuery<VectorInputInternal> {
                     strategy,
                 }))
             }
+            VectorQuery::Arithmetic(ArithmeticQuery {
+                add,
+                subtract,
+                weights,
+            }) => {
+                let resolve = |vectors: Vec<VectorInputInternal>| {
+                    vectors
+                        .into_iter()
+                        .map(|vector| {
+                            ids_to_vectors
+                                .resolve_reference(lookup_collection, lookup_vector_name, vector)
+                                .ok_or_else(|| vector_not_found_error(lookup_vector_name))
+                        })
+                        .collect::<CollectionResult<Vec<_>>>()
+                };
+
+                Ok(VectorQuery::Arithmetic(ArithmeticQuery {
+                    add: resolve(add)?,
+                    subtract: resolve(subtract)?,
+                    weights,
+                }))
+            }
         }
     }
 
@@ -443,6 +546,14 @@ impl VectorQuery<VectorInternal> {
                     .iter_mut()
                     .for_each(|item| item.vector.preprocess());
             }
+            VectorQuery::Arithmetic(ArithmeticQuery {
+                add,
+                subtract,
+                weights: _,
+            }) => {
+                add.iter_mut().for_each(|v| v.preprocess());
+                subtract.iter_mut().for_each(|v| v.preprocess());
+            }
         }
         self
     }
@@ -503,6 +614,9 @@ impl VectorQuery<VectorInternal> {
                     using,
                 )),
             },
+            VectorQuery::Arithmetic(arithmetic) => {
+                QueryEnum::Nearest(NamedQuery::new(arithmetic.into_vector()?, using))
+            }
         };
 
         Ok(ScoringQuery::Vector(query_enum))
@@ -517,7 +631,7 @@ pub struct CollectionPrefetch {
     pub filter: Option<Filter>,
     pub score_threshold: Option<OrderedFloat<ScoreType>>,
     pub limit: usize,
-    /// Search params for when there is no prefetch
+    /// Search params of this prefetch, used by its own search, or to rescore its nested prefetches
     pub params: Option<SearchParams>,
     pub lookup_from: Option<LookupLocation>,
 }
@@ -769,6 +883,15 @@ impl CollectionQueryRequest {
             }
         }
 
+        // Results of different shards are merged by the first order key only
+        if let Some(Query::OrderBy(order_by)) = query
+            && order_by.is_compound()
+        {
+            return Err(CollectionError::bad_request(
+                "Ordering by multiple keys is only supported by the scroll API.",
+            ));
+        }
+
         // Check that fusion queries are not combined with a using vector name
         if let Some(Query::Fusion(_)) = query
             && using != DEFAULT_VECTOR_NAME
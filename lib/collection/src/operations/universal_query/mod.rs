@@ -21,6 +21,7 @@
 //! [`QueryShardPoints`]: api::grpc::qdrant::QueryShardPoints
 
 pub mod collection_query;
+pub mod query_plan;
 pub mod shard_query;
 
 pub mod planned_query {
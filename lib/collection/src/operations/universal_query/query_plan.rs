@@ -0,0 +1,66 @@
+use schemars::JsonSchema;
+use segment::data_types::query_plan::SegmentSearchPlan;
+use segment::types::{Filter, SearchParams, ShardKey, VectorNameBuf};
+use serde::Serialize;
+
+use super::planned_query::PlannedQuery;
+use crate::shards::shard::ShardId;
+
+/// Description of how a query would be executed, without running it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct QueryPlan {
+    pub shards: Vec<ShardQueryPlan>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ShardQueryPlan {
+    pub shard_id: ShardId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Whether the shard is hosted on this peer.
+    /// Stages of remote shards are not planned, as their segments can't be inspected from here.
+    pub local: bool,
+    pub stages: Vec<StagePlan>,
+}
+
+/// Plan of a single leaf search or scroll, which is executed on every segment of the shard
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StagePlan {
+    /// Searched vector, absent for scrolls
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector: Option<VectorNameBuf>,
+    /// Whether exact search was requested for this stage
+    pub exact: bool,
+    pub segments: Vec<SegmentSearchPlan>,
+}
+
+/// Leaf operation of a planned query, as executed on segments
+#[derive(Debug, Clone)]
+pub struct PlannedStage {
+    pub vector: Option<VectorNameBuf>,
+    pub filter: Option<Filter>,
+    pub params: Option<SearchParams>,
+}
+
+impl PlannedStage {
+    /// All leaf searches and scrolls of the planned query, with parent filters already merged in
+    pub fn from_planned_query(planned_query: &PlannedQuery) -> Vec<Self> {
+        let searches = planned_query.searches.iter().map(|search| Self {
+            vector: Some(search.query.get_vector_name().to_owned()),
+            filter: search.filter.clone(),
+            params: search.params,
+        });
+
+        let scrolls = planned_query.scrolls().iter().map(|scroll| Self {
+            vector: None,
+            filter: scroll.filter.clone(),
+            params: None,
+        });
+
+        searches.chain(scrolls).collect()
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.params.is_some_and(|params| params.exact)
+    }
+}
@@ -1,6 +1,7 @@
 mod count;
 mod discovery;
 mod facet;
+mod histogram;
 mod local_shard;
 mod matrix;
 mod query;
@@ -397,12 +398,16 @@ mod test {
         let restricted_request = PointsSelector::FilterSelector(FilterSelector {
             filter: filter_fixture(UNINDEXED_KEY),
             shard_key: None,
+            if_version: None,
+            if_payload_matches: None,
         });
         assert_strict_mode_error(restricted_request, collection).await;
 
         let allowed_request = PointsSelector::FilterSelector(FilterSelector {
             filter: filter_fixture(INDEXED_KEY),
             shard_key: None,
+            if_version: None,
+            if_payload_matches: None,
         });
         assert_strict_mode_success(allowed_request, collection).await;
     }
@@ -1,6 +1,6 @@
 use api::rest::{
-    BatchVectorStruct, MultiDenseVector, PointInsertOperations, PointsBatch, PointsList,
-    UpdateVectors, Vector, VectorStruct,
+    BatchVectorStruct, InsertPoints, MultiDenseVector, PointInsertOperations, PointsBatch,
+    PointsList, UpdateVectors, Vector, VectorStruct,
 };
 use segment::data_types::tiny_map::TinyMap;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
@@ -13,7 +13,7 @@ use super::{StrictModeVerification, check_limit_opt};
 use crate::collection::Collection;
 use crate::common::collection_size_stats::CollectionSizeAtomicStats;
 use crate::operations::payload_ops::{DeletePayload, SetPayload};
-use crate::operations::point_ops::PointsSelector;
+use crate::operations::point_ops::{PointsSelector, RestorePoints};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::vector_ops::DeleteVectors;
 
@@ -42,6 +42,28 @@ impl StrictModeVerification for PointsSelector {
     }
 }
 
+impl StrictModeVerification for RestorePoints {
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&segment::types::SearchParams> {
+        None
+    }
+}
+
 impl StrictModeVerification for DeleteVectors {
     fn query_limit(&self) -> Option<usize> {
         None
@@ -156,19 +178,25 @@ impl StrictModeVerification for PointInsertOperations {
     }
 
     fn indexed_filter_write(&self) -> Option<&Filter> {
-        // Update filter doesn't require strict mode checks
-        // as it is only used on a limited and small subset of points.
+        // Update filter and precondition don't require strict mode checks
+        // as they are only used on a limited and small subset of points.
         // Reading from payload storage is acceptable in this case.
         match self {
             PointInsertOperations::PointsBatch(PointsBatch {
                 batch: _,
                 shard_key: _,
                 update_filter: _,
+                if_version: _,
+                if_payload_matches: _,
+                if_not_exists: _,
             }) => None,
             PointInsertOperations::PointsList(PointsList {
                 points: _,
                 shard_key: _,
                 update_filter: _,
+                if_version: _,
+                if_payload_matches: _,
+                if_not_exists: _,
             }) => None,
         }
     }
@@ -196,12 +224,14 @@ impl StrictModeVerification for UpdateVectors {
 
         check_collection_size_limit(collection, strict_mode_config).await?;
 
+        let vectors = || self.points.iter().map(|point| &point.vector);
+
         if let Some(multivector_config) = &strict_mode_config.multivector_config {
-            check_multivectors_limits_update(self, multivector_config).await?;
+            check_multivectors_limits_vecstructs(vectors(), multivector_config).await?;
         }
 
         if let Some(sparse_config) = &strict_mode_config.sparse_config {
-            check_sparse_vector_limits_update(self, sparse_config).await?;
+            check_sparse_vector_limits_vecstructs(vectors(), sparse_config).await?;
         }
 
         Ok(())
@@ -236,6 +266,54 @@ impl StrictModeVerification for UpdateVectors {
     }
 }
 
+impl StrictModeVerification for InsertPoints {
+    async fn check_custom(
+        &self,
+        collection: &Collection,
+        strict_mode_config: &StrictModeConfig,
+    ) -> CollectionResult<()> {
+        check_limit_opt(
+            Some(self.points.len()),
+            strict_mode_config.upsert_max_batchsize,
+            "upsert limit",
+        )?;
+
+        check_collection_size_limit(collection, strict_mode_config).await?;
+
+        let vectors = || self.points.iter().map(|point| &point.vector);
+
+        if let Some(multivector_config) = &strict_mode_config.multivector_config {
+            check_multivectors_limits_vecstructs(vectors(), multivector_config).await?;
+        }
+
+        if let Some(sparse_config) = &strict_mode_config.sparse_config {
+            check_sparse_vector_limits_vecstructs(vectors(), sparse_config).await?;
+        }
+
+        Ok(())
+    }
+
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&segment::types::SearchParams> {
+        None
+    }
+}
+
 /// Checks all collection size limits that are configured in strict mode.
 async fn check_collection_size_limit(
     collection: &Collection,
@@ -348,8 +426,8 @@ async fn multivector_limits_by_name(
     }
 }
 
-async fn check_multivectors_limits_update(
-    point_insert: &UpdateVectors,
+async fn check_multivectors_limits_vecstructs(
+    vectors: impl IntoIterator<Item = &VectorStruct>,
     multivector_strict_config: &StrictModeMultivectorConfig,
 ) -> CollectionResult<()> {
     let Some(multivector_max_size_by_name) =
@@ -358,10 +436,10 @@ async fn check_multivectors_limits_update(
         return Ok(());
     };
 
-    for point in &point_insert.points {
+    for vector in vectors {
         check_named_multivectors_vecstruct_limit(
             DEFAULT_VECTOR_NAME,
-            &point.vector,
+            vector,
             &multivector_max_size_by_name,
         )?;
     }
@@ -389,16 +467,16 @@ async fn sparse_limits(
     (!sparse_max_size.is_empty()).then_some(sparse_max_size)
 }
 
-async fn check_sparse_vector_limits_update(
-    point_insert: &UpdateVectors,
+async fn check_sparse_vector_limits_vecstructs(
+    vectors: impl IntoIterator<Item = &VectorStruct>,
     sparse_config: &StrictModeSparseConfig,
 ) -> CollectionResult<()> {
     let Some(sparse_max_size_by_name) = sparse_limits(sparse_config).await else {
         return Ok(());
     };
 
-    for point in &point_insert.points {
-        check_sparse_vecstruct_limit(&point.vector, &sparse_max_size_by_name)?;
+    for vector in vectors {
+        check_sparse_vecstruct_limit(vector, &sparse_max_size_by_name)?;
     }
 
     Ok(())
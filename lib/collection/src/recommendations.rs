@@ -2,7 +2,7 @@ use std::future::Future;
 use std::iter::Peekable;
 use std::time::Duration;
 
-use api::rest::RecommendStrategy;
+use api::rest::{RecommendStrategy, RocchioParams};
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use itertools::Itertools;
 use segment::data_types::vectors::{
@@ -101,6 +101,85 @@ fn avg_vectors<'a>(
     }
 }
 
+/// Query vector of the Rocchio strategy: `alpha * avg(positive) - beta * avg(negative)`
+pub fn rocchio_vector_for_recommendation<'a>(
+    positive: impl IntoIterator<Item = VectorRef<'a>>,
+    negative: impl IntoIterator<Item = VectorRef<'a>>,
+    params: RocchioParams,
+) -> CollectionResult<VectorInternal> {
+    let positive = positive.into_iter().collect_vec();
+    let negative = negative.into_iter().collect_vec();
+    let (positive_weight, negative_weight) =
+        rocchio_weights(params, positive.len(), negative.len());
+    weighted_sum_vectors(
+        positive
+            .into_iter()
+            .map(|vector| (vector, positive_weight))
+            .chain(
+                negative
+                    .into_iter()
+                    .map(|vector| (vector, -negative_weight)),
+            ),
+    )
+}
+
+/// Weight of each positive and each negative vector in the Rocchio formula
+pub fn rocchio_weights(params: RocchioParams, positives: usize, negatives: usize) -> (f32, f32) {
+    (
+        params.alpha() / positives.max(1) as f32,
+        params.beta() / negatives.max(1) as f32,
+    )
+}
+
+/// Sum of vectors, each multiplied by its weight
+pub fn weighted_sum_vectors<'a>(
+    vectors: impl IntoIterator<Item = (VectorRef<'a>, f32)>,
+) -> CollectionResult<VectorInternal> {
+    let mut sum: Option<VectorInternal> = None;
+    for (vector, weight) in vectors {
+        sum = Some(match (sum, vector) {
+            (None, VectorRef::Dense(vector)) => {
+                VectorInternal::from(vector.iter().map(|x| x * weight).collect::<DenseVector>())
+            }
+            (None, VectorRef::Sparse(vector)) => {
+                let mut vector = vector.clone();
+                vector.values.iter_mut().for_each(|x| *x *= weight);
+                VectorInternal::from(vector)
+            }
+            (Some(VectorInternal::Dense(mut sum)), VectorRef::Dense(vector)) => {
+                if sum.len() != vector.len() {
+                    return Err(CollectionError::bad_input(format!(
+                        "Can't add up dense vectors of different dimensions: {} and {}",
+                        sum.len(),
+                        vector.len(),
+                    )));
+                }
+                sum.iter_mut()
+                    .zip(vector)
+                    .for_each(|(sum, x)| *sum += x * weight);
+                VectorInternal::Dense(sum)
+            }
+            (Some(VectorInternal::Sparse(sum)), VectorRef::Sparse(vector)) => {
+                VectorInternal::Sparse(sum.combine_aggregate(vector, |sum, x| sum + x * weight))
+            }
+            (_, VectorRef::MultiDense(_)) => {
+                return Err(CollectionError::bad_input(
+                    "Vector arithmetic is not supported for multivectors".to_owned(),
+                ));
+            }
+            (_, _) => {
+                return Err(CollectionError::bad_input(
+                    "Can't add up vectors with different types".to_owned(),
+                ));
+            }
+        });
+    }
+
+    sum.ok_or_else(|| {
+        CollectionError::bad_input("At least one vector to add or subtract is required".to_owned())
+    })
+}
+
 fn merge_positive_and_negative_avg(
     positive: VectorInternal,
     negative: VectorInternal,
@@ -206,7 +285,7 @@ pub fn recommend_into_core_search(
     };
 
     match request.strategy.unwrap_or_default() {
-        RecommendStrategy::AverageVector => recommend_by_avg_vector(
+        RecommendStrategy::AverageVector | RecommendStrategy::Rocchio => recommend_by_avg_vector(
             request,
             reference_vectors_ids_to_exclude,
             all_vectors_records_map,
@@ -264,7 +343,7 @@ where
     // Validate amount of examples
     request_batch.iter().try_for_each(|(request, _)| {
         match request.strategy.unwrap_or_default() {
-            RecommendStrategy::AverageVector => {
+            RecommendStrategy::AverageVector | RecommendStrategy::Rocchio => {
                 if request.positive.is_empty() {
                     return Err(CollectionError::BadRequest {
                         description: "At least one positive vector ID required with this strategy"
@@ -357,6 +436,8 @@ fn recommend_by_avg_vector(
         positive,
         negative,
         lookup_from,
+        strategy,
+        rocchio,
         ..
     } = request;
 
@@ -376,8 +457,14 @@ fn recommend_by_avg_vector(
         lookup_collection_name,
     );
 
-    let search_vector =
-        avg_vector_for_recommendation(positive_vectors, negative_vectors.peekable())?;
+    let search_vector = match strategy.unwrap_or_default() {
+        RecommendStrategy::Rocchio => rocchio_vector_for_recommendation(
+            positive_vectors,
+            negative_vectors,
+            rocchio.unwrap_or_default(),
+        )?,
+        _ => avg_vector_for_recommendation(positive_vectors, negative_vectors.peekable())?,
+    };
 
     Ok(CoreSearchRequest {
         query: QueryEnum::Nearest(NamedQuery {
@@ -400,6 +487,11 @@ fn recommend_by_avg_vector(
         score_threshold,
         offset: offset.unwrap_or_default(),
         with_explanation: false,
+        with_explanation_bundle: false,
+        explanation_verbosity: Default::default(),
+        explanation_params: Default::default(),
+        mmr: None,
+        distinct_by: None,
     })
 }
 
@@ -466,15 +558,21 @@ fn recommend_by_custom_score(
         with_vector,
         score_threshold,
         with_explanation: false,
+        with_explanation_bundle: false,
+        explanation_verbosity: Default::default(),
+        explanation_params: Default::default(),
+        mmr: None,
+        distinct_by: None,
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use api::rest::RocchioParams;
     use segment::data_types::vectors::{VectorInternal, VectorRef};
     use sparse::common::sparse_vector::SparseVector;
 
-    use super::avg_vectors;
+    use super::{avg_vectors, rocchio_vector_for_recommendation, weighted_sum_vectors};
 
     #[test]
     fn test_avg_vectors() {
@@ -511,4 +609,72 @@ mod tests {
         ];
         assert!(avg_vectors(vectors.iter().map(VectorRef::from)).is_err());
     }
+
+    #[test]
+    fn test_weighted_sum_vectors() {
+        let add: VectorInternal = vec![1.0, 2.0, 3.0].into();
+        let subtract: VectorInternal = vec![0.5, 0.5, 1.0].into();
+        assert_eq!(
+            weighted_sum_vectors([
+                (VectorRef::from(&add), 2.0),
+                (VectorRef::from(&subtract), -1.0)
+            ])
+            .unwrap(),
+            vec![1.5, 3.5, 5.0].into(),
+        );
+
+        let add: VectorInternal = SparseVector::new(vec![0, 1], vec![1.0, 2.0])
+            .unwrap()
+            .into();
+        let subtract: VectorInternal = SparseVector::new(vec![1, 2], vec![1.0, 4.0])
+            .unwrap()
+            .into();
+        assert_eq!(
+            weighted_sum_vectors([
+                (VectorRef::from(&add), 1.0),
+                (VectorRef::from(&subtract), -0.5)
+            ])
+            .unwrap(),
+            SparseVector::new(vec![0, 1, 2], vec![1.0, 1.5, -2.0])
+                .unwrap()
+                .into(),
+        );
+
+        let shorter: VectorInternal = vec![1.0, 2.0].into();
+        assert!(
+            weighted_sum_vectors([
+                (VectorRef::from(&add), 1.0),
+                (VectorRef::from(&shorter), 1.0)
+            ])
+            .is_err()
+        );
+        let longer: VectorInternal = vec![1.0, 2.0, 3.0, 4.0].into();
+        assert!(
+            weighted_sum_vectors([
+                (VectorRef::from(&shorter), 1.0),
+                (VectorRef::from(&longer), 1.0)
+            ])
+            .is_err()
+        );
+        assert!(weighted_sum_vectors([]).is_err());
+    }
+
+    #[test]
+    fn test_rocchio_vector() {
+        let positive: Vec<VectorInternal> = vec![vec![1.0, 2.0].into(), vec![3.0, 4.0].into()];
+        let negative: Vec<VectorInternal> = vec![vec![10.0, 0.0].into()];
+        let params = RocchioParams {
+            alpha: Some(1.0),
+            beta: Some(0.5),
+        };
+        assert_eq!(
+            rocchio_vector_for_recommendation(
+                positive.iter().map(VectorRef::from),
+                negative.iter().map(VectorRef::from),
+                params,
+            )
+            .unwrap(),
+            vec![-3.0, 3.0].into(),
+        );
+    }
 }
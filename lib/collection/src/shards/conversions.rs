@@ -1,29 +1,30 @@
 use api::conversions::json::payload_to_proto;
-use api::grpc::conversions::convert_shard_key_from_grpc_opt;
+use api::grpc::conversions::{convert_shard_key_from_grpc_opt, date_time_to_proto};
 use api::grpc::qdrant::points_selector::PointsSelectorOneOf;
 use api::grpc::qdrant::{
     ClearPayloadPoints, ClearPayloadPointsInternal, CreateFieldIndexCollection,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
     DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
-    PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
-    SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
-    UpsertPointsInternal, Vectors, VectorsSelector,
+    PointsIdsList, PointsSelector, RestorePoints, RestorePointsInternal, SetPayloadPoints,
+    SetPayloadPointsInternal, SyncPoints, SyncPointsInternal, UpdatePointVectors,
+    UpdateVectorsInternal, UpsertPoints, UpsertPointsInternal, Vectors, VectorsSelector,
 };
+use chrono::{DateTime, Utc};
 use segment::data_types::vectors::VectorStructInternal;
 use segment::json_path::JsonPath;
 use segment::types::{Filter, PayloadFieldSchema, PointIdType, ScoredPoint, VectorNameBuf};
 use tonic::Status;
 
-use crate::operations::conversions::write_ordering_to_proto;
+use crate::operations::conversions::{update_ack_to_proto, write_ordering_to_proto};
 use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
 use crate::operations::point_ops::{
     ConditionalInsertOperationInternal, PointInsertOperationsInternal, PointSyncOperation,
-    WriteOrdering,
+    UpdateAck, WriteOrdering,
 };
 use crate::operations::types::CollectionResult;
 use crate::operations::vector_ops::UpdateVectorsOp;
-use crate::operations::{ClockTag, CreateIndex};
+use crate::operations::{ClockTag, CreateIndex, UpdatePrecondition};
 use crate::shards::shard::ShardId;
 
 pub fn internal_sync_points(
@@ -31,7 +32,7 @@ pub fn internal_sync_points(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     points_sync_operation: PointSyncOperation,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> CollectionResult<SyncPointsInternal> {
     let PointSyncOperation {
@@ -44,7 +45,7 @@ pub fn internal_sync_points(
         clock_tag: clock_tag.map(Into::into),
         sync_points: Some(SyncPoints {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points: points
                 .into_iter()
                 .map(api::grpc::qdrant::PointStruct::try_from)
@@ -53,6 +54,7 @@ pub fn internal_sync_points(
             to_id: to_id.map(|x| x.into()),
             ordering: ordering.map(write_ordering_to_proto),
             timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     })
 }
@@ -62,7 +64,7 @@ pub fn internal_upsert_points(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     point_insert_operations: PointInsertOperationsInternal,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> CollectionResult<UpsertPointsInternal> {
     Ok(UpsertPointsInternal {
@@ -70,7 +72,7 @@ pub fn internal_upsert_points(
         clock_tag: clock_tag.map(Into::into),
         upsert_points: Some(UpsertPoints {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points: match point_insert_operations {
                 PointInsertOperationsInternal::PointsBatch(batch) => TryFrom::try_from(batch)?,
                 PointInsertOperationsInternal::PointsList(list) => list
@@ -82,6 +84,11 @@ pub fn internal_upsert_points(
             shard_key_selector: None,
             update_filter: None,
             timeout: None,
+            if_version: None,
+            if_payload_matches: None,
+            id_generation: None,
+            if_not_exists: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     })
 }
@@ -91,20 +98,27 @@ pub fn internal_conditional_upsert_points(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     point_condition_upsert_operations: ConditionalInsertOperationInternal,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> CollectionResult<UpsertPointsInternal> {
     let ConditionalInsertOperationInternal {
         points_op: point_insert_operations,
         condition,
+        precondition,
     } = point_condition_upsert_operations;
 
+    let UpdatePrecondition {
+        if_version,
+        if_payload_matches,
+        if_not_exists,
+    } = precondition.unwrap_or_default();
+
     Ok(UpsertPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         upsert_points: Some(UpsertPoints {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points: match point_insert_operations {
                 PointInsertOperationsInternal::PointsBatch(batch) => TryFrom::try_from(batch)?,
                 PointInsertOperationsInternal::PointsList(list) => list
@@ -116,24 +130,39 @@ pub fn internal_conditional_upsert_points(
             shard_key_selector: None,
             update_filter: Some(api::grpc::Filter::from(condition)),
             timeout: None,
+            if_version,
+            if_payload_matches: if_payload_matches.map(api::grpc::Filter::from),
+            id_generation: None,
+            if_not_exists: if_not_exists.then_some(true),
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     })
 }
 
+#[expect(clippy::too_many_arguments)]
 pub fn internal_delete_points(
     shard_id: Option<ShardId>,
     clock_tag: Option<ClockTag>,
     collection_name: String,
     ids: Vec<PointIdType>,
-    wait: bool,
+    precondition: Option<UpdatePrecondition>,
+    deleted_at: Option<DateTime<Utc>>,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> DeletePointsInternal {
+    let UpdatePrecondition {
+        if_version,
+        if_payload_matches,
+        if_not_exists,
+    } = precondition.unwrap_or_default();
+    debug_assert!(!if_not_exists, "Deleted points are supposed to exist");
+
     DeletePointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         delete_points: Some(DeletePoints {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points: Some(PointsSelector {
                 points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
                     ids: ids.into_iter().map(|id| id.into()).collect(),
@@ -142,7 +171,11 @@ pub fn internal_delete_points(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             timeout: None,
+            if_version,
+            if_payload_matches: if_payload_matches.map(api::grpc::Filter::from),
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
+        deleted_at: deleted_at.map(|deleted_at| date_time_to_proto(deleted_at.into())),
     }
 }
 
@@ -151,7 +184,8 @@ pub fn internal_delete_points_by_filter(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     filter: Filter,
-    wait: bool,
+    deleted_at: Option<DateTime<Utc>>,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> DeletePointsInternal {
     DeletePointsInternal {
@@ -159,13 +193,39 @@ pub fn internal_delete_points_by_filter(
         clock_tag: clock_tag.map(Into::into),
         delete_points: Some(DeletePoints {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points: Some(PointsSelector {
                 points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             timeout: None,
+            if_version: None,
+            if_payload_matches: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
+        }),
+        deleted_at: deleted_at.map(|deleted_at| date_time_to_proto(deleted_at.into())),
+    }
+}
+
+pub fn internal_restore_points(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    ids: Vec<PointIdType>,
+    ack: UpdateAck,
+    ordering: Option<WriteOrdering>,
+) -> RestorePointsInternal {
+    RestorePointsInternal {
+        shard_id,
+        clock_tag: clock_tag.map(Into::into),
+        restore_points: Some(RestorePoints {
+            collection_name,
+            wait: Some(ack.wait()),
+            ids: ids.into_iter().map(|id| id.into()).collect(),
+            ordering: ordering.map(write_ordering_to_proto),
+            timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     }
 }
@@ -175,7 +235,7 @@ pub fn internal_update_vectors(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     update_vectors: UpdateVectorsOp,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> CollectionResult<UpdateVectorsInternal> {
     let UpdateVectorsOp {
@@ -197,12 +257,13 @@ pub fn internal_update_vectors(
         clock_tag: clock_tag.map(Into::into),
         update_vectors: Some(UpdatePointVectors {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points: points?,
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             update_filter: update_filter.map(api::grpc::Filter::from),
             timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     })
 }
@@ -213,7 +274,7 @@ pub fn internal_delete_vectors(
     collection_name: String,
     ids: Vec<PointIdType>,
     vector_names: Vec<VectorNameBuf>,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> DeleteVectorsInternal {
     DeleteVectorsInternal {
@@ -221,7 +282,7 @@ pub fn internal_delete_vectors(
         clock_tag: clock_tag.map(Into::into),
         delete_vectors: Some(DeletePointVectors {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points_selector: Some(PointsSelector {
                 points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
                     ids: ids.into_iter().map(|id| id.into()).collect(),
@@ -233,6 +294,7 @@ pub fn internal_delete_vectors(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     }
 }
@@ -243,7 +305,7 @@ pub fn internal_delete_vectors_by_filter(
     collection_name: String,
     filter: Filter,
     vector_names: Vec<VectorNameBuf>,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> DeleteVectorsInternal {
     DeleteVectorsInternal {
@@ -251,7 +313,7 @@ pub fn internal_delete_vectors_by_filter(
         clock_tag: clock_tag.map(Into::into),
         delete_vectors: Some(DeletePointVectors {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points_selector: Some(PointsSelector {
                 points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
             }),
@@ -261,6 +323,7 @@ pub fn internal_delete_vectors_by_filter(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     }
 }
@@ -270,7 +333,7 @@ pub fn internal_set_payload(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     set_payload: SetPayloadOp,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> SetPayloadPointsInternal {
     let points_selector = if let Some(points) = set_payload.points {
@@ -285,18 +348,28 @@ pub fn internal_set_payload(
         })
     };
 
+    let UpdatePrecondition {
+        if_version,
+        if_payload_matches,
+        if_not_exists,
+    } = set_payload.precondition.unwrap_or_default();
+    debug_assert!(!if_not_exists, "Updated points are supposed to exist");
+
     SetPayloadPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         set_payload_points: Some(SetPayloadPoints {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             payload: payload_to_proto(set_payload.payload),
             points_selector,
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             key: set_payload.key.map(|key| key.to_string()),
             timeout: None,
+            if_version,
+            if_payload_matches: if_payload_matches.map(api::grpc::Filter::from),
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     }
 }
@@ -306,7 +379,7 @@ pub fn internal_delete_payload(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     delete_payload: DeletePayloadOp,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> DeletePayloadPointsInternal {
     let points_selector = if let Some(points) = delete_payload.points {
@@ -326,7 +399,7 @@ pub fn internal_delete_payload(
         clock_tag: clock_tag.map(Into::into),
         delete_payload_points: Some(DeletePayloadPoints {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             keys: delete_payload
                 .keys
                 .into_iter()
@@ -336,6 +409,7 @@ pub fn internal_delete_payload(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     }
 }
@@ -345,7 +419,7 @@ pub fn internal_clear_payload(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     points: Vec<PointIdType>,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> ClearPayloadPointsInternal {
     ClearPayloadPointsInternal {
@@ -353,7 +427,7 @@ pub fn internal_clear_payload(
         clock_tag: clock_tag.map(Into::into),
         clear_payload_points: Some(ClearPayloadPoints {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points: Some(PointsSelector {
                 points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
                     ids: points.into_iter().map(|id| id.into()).collect(),
@@ -362,6 +436,7 @@ pub fn internal_clear_payload(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     }
 }
@@ -371,7 +446,7 @@ pub fn internal_clear_payload_by_filter(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     filter: Filter,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> ClearPayloadPointsInternal {
     ClearPayloadPointsInternal {
@@ -379,13 +454,14 @@ pub fn internal_clear_payload_by_filter(
         clock_tag: clock_tag.map(Into::into),
         clear_payload_points: Some(ClearPayloadPoints {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             points: Some(PointsSelector {
                 points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     }
 }
@@ -395,7 +471,7 @@ pub fn internal_create_index(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     create_index: CreateIndex,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> CreateFieldIndexCollectionInternal {
     let (field_type, field_index_params) = create_index
@@ -417,12 +493,13 @@ pub fn internal_create_index(
         clock_tag: clock_tag.map(Into::into),
         create_field_index_collection: Some(CreateFieldIndexCollection {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             field_name: create_index.field_name.to_string(),
             field_type,
             field_index_params,
             ordering: ordering.map(write_ordering_to_proto),
             timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     }
 }
@@ -432,7 +509,7 @@ pub fn internal_delete_index(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     delete_index: JsonPath,
-    wait: bool,
+    ack: UpdateAck,
     ordering: Option<WriteOrdering>,
 ) -> DeleteFieldIndexCollectionInternal {
     DeleteFieldIndexCollectionInternal {
@@ -440,10 +517,11 @@ pub fn internal_delete_index(
         clock_tag: clock_tag.map(Into::into),
         delete_field_index_collection: Some(DeleteFieldIndexCollection {
             collection_name,
-            wait: Some(wait),
+            wait: Some(ack.wait()),
             field_name: delete_index.to_string(),
             ordering: ordering.map(write_ordering_to_proto),
             timeout: None,
+            ack: Some(update_ack_to_proto(ack) as i32),
         }),
     }
 }
@@ -460,6 +538,7 @@ pub fn try_scored_point_from_grpc(
         vectors,
         shard_key,
         order_value,
+        score_explanation,
     } = point;
     let id = id
         .ok_or_else(|| Status::invalid_argument("scored point does not have an ID"))?
@@ -485,6 +564,6 @@ pub fn try_scored_point_from_grpc(
         vector,
         shard_key: convert_shard_key_from_grpc_opt(shard_key),
         order_value: order_value.map(TryFrom::try_from).transpose()?,
-        score_explanation: None,
+        score_explanation: score_explanation.map(TryFrom::try_from).transpose()?,
     })
 }
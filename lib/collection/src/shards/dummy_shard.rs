@@ -9,7 +9,7 @@ use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::manifest::SnapshotManifest;
 use segment::index::field_index::CardinalityEstimation;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
+    ExtendedPointId, Filter, PointIdType, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
     WithPayloadInterface, WithVector,
 };
 use shard::operations::CollectionUpdateOperations;
@@ -23,7 +23,9 @@ use crate::operations::types::{
     OptimizersStatus, PointRequestInternal, ScrollRequestInternal, ShardStatus, UpdateResult,
     UpdateStatus,
 };
+use crate::operations::universal_query::query_plan::StagePlan;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use crate::shards::local_shard::point_history::PointVersion;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
 
@@ -92,6 +94,14 @@ impl DummyShard {
         self.dummy()
     }
 
+    pub fn query_plan(&self, _: ShardQueryRequest) -> CollectionResult<Vec<StagePlan>> {
+        self.dummy()
+    }
+
+    pub fn point_history(&self, _: PointIdType) -> CollectionResult<Vec<PointVersion>> {
+        self.dummy()
+    }
+
     fn dummy<T>(&self) -> CollectionResult<T> {
         Err(CollectionError::service_error(self.message.clone()))
     }
@@ -31,12 +31,14 @@ use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CountRequestInternal, CountResult,
     OptimizersStatus, PointRequestInternal, ScrollRequestInternal, UpdateResult, UpdateStatus,
 };
+use crate::operations::universal_query::query_plan::StagePlan;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::{
     CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationToShard,
     OperationWithClockTag, SplitByShard as _,
 };
 use crate::shards::local_shard::LocalShard;
+use crate::shards::local_shard::point_history::PointVersion;
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
@@ -395,6 +397,20 @@ impl ForwardProxyShard {
             .estimate_cardinality(filter, hw_measurement_acc)
             .await
     }
+
+    pub async fn query_plan(
+        &self,
+        request: ShardQueryRequest,
+        hw_measurement_acc: &HwMeasurementAcc,
+    ) -> CollectionResult<Vec<StagePlan>> {
+        self.wrapped_shard
+            .query_plan(request, hw_measurement_acc)
+            .await
+    }
+
+    pub fn point_history(&self, point_id: PointIdType) -> CollectionResult<Vec<PointVersion>> {
+        self.wrapped_shard.point_history(point_id)
+    }
 }
 
 #[async_trait]
@@ -406,7 +422,7 @@ impl ShardOperation for ForwardProxyShard {
     /// This method is *not* cancel safe.
     async fn update(
         &self,
-        operation: OperationWithClockTag,
+        mut operation: OperationWithClockTag,
         _wait: bool,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
@@ -426,6 +442,10 @@ impl ShardOperation for ForwardProxyShard {
             .update(operation.clone(), true, hw_measurement_acc.clone())
             .await?;
 
+        // The precondition was checked by the wrapped shard, the remote shard must apply the
+        // operation unconditionally
+        operation.operation.take_precondition();
+
         let points_matching_filter_before = {
             if let Some(filter) = &self.filter
                 && let Some(point_ids) = operation.operation.upsert_point_ids()
@@ -2,7 +2,9 @@ pub mod clock_map;
 pub mod disk_usage_watcher;
 pub(super) mod facet;
 pub(super) mod formula_rescore;
+pub mod point_history;
 pub(super) mod query;
+pub(super) mod query_plan;
 pub(super) mod scroll;
 pub(super) mod search;
 pub(super) mod shard_ops;
@@ -52,11 +54,12 @@ use shard::operations::point_ops::{PointInsertOperationsInternal, PointOperation
 use shard::wal::SerdeWal;
 use tokio::runtime::Handle;
 use tokio::sync::mpsc::Sender;
-use tokio::sync::{Mutex, RwLock as TokioRwLock, mpsc};
+use tokio::sync::{Mutex, RwLock as TokioRwLock, mpsc, oneshot};
 use tokio_util::task::AbortOnDropHandle;
 
 use self::clock_map::{ClockMap, RecoveryPoint};
 use self::disk_usage_watcher::DiskUsageWatcher;
+use self::point_history::{PointHistory, PointVersion};
 use super::update_tracker::UpdateTracker;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::collection_updater::CollectionUpdater;
@@ -133,11 +136,20 @@ pub struct LocalShard {
     ///
     /// Write lock must be held for updates, while read lock must be held for critical sections
     pub(super) update_operation_lock: Arc<tokio::sync::RwLock<()>>,
+
+    /// Precondition lock
+    /// Updates hold a read lock until they are written to the WAL. Updates with a precondition hold
+    /// the write lock instead, so that no other update is applied between checking the precondition
+    /// and writing the update to the WAL.
+    pub(super) precondition_lock: tokio::sync::RwLock<()>,
+
+    /// Previous versions of points, if `wal_config.point_history_versions` is set
+    pub(super) point_history: Option<Arc<PointHistory>>,
 }
 
 /// Shard holds information about segments and WAL.
 impl LocalShard {
-    /// Moves `wal`, `segments` and `clocks` data from one path to another.
+    /// Moves `wal`, `segments`, `clocks` and point history data from one path to another.
     pub async fn move_data(from: &Path, to: &Path) -> CollectionResult<()> {
         log::debug!(
             "Moving local shard from {} to {}",
@@ -154,6 +166,7 @@ impl LocalShard {
         move_dir(segments_from, segments_to).await?;
 
         LocalShardClocks::move_data(from, to).await?;
+        PointHistory::move_data(from, to).await?;
 
         Ok(())
     }
@@ -182,6 +195,7 @@ impl LocalShard {
         }
 
         LocalShardClocks::delete_data(shard_path).await?;
+        PointHistory::delete_data(shard_path).await?;
 
         Ok(())
     }
@@ -189,7 +203,7 @@ impl LocalShard {
     #[allow(clippy::too_many_arguments)]
     pub async fn new(
         collection_name: String,
-        segment_holder: SegmentHolder,
+        mut segment_holder: SegmentHolder,
         collection_config: Arc<TokioRwLock<CollectionConfigInternal>>,
         shared_storage_config: Arc<SharedStorageConfig>,
         payload_index_schema: Arc<SaveOnDisk<PayloadIndexSchema>>,
@@ -198,14 +212,23 @@ impl LocalShard {
         optimizer_resource_budget: ResourceBudget,
         shard_path: &Path,
         clocks: LocalShardClocks,
+        point_history: Option<PointHistory>,
         update_runtime: Handle,
         search_runtime: Handle,
     ) -> Self {
-        let segment_holder = Arc::new(RwLock::new(segment_holder));
         let config = collection_config.read().await;
+
+        // Deleted points are kept restorable as tombstones during the retention window
+        segment_holder.soft_delete_retention = config
+            .wal_config
+            .soft_delete_retention_sec
+            .filter(|&sec| sec > 0)
+            .map(Duration::from_secs);
+        let segment_holder = Arc::new(RwLock::new(segment_holder));
         let locked_wal = Arc::new(Mutex::new(wal));
         let optimizers_log = Arc::new(ParkingMutex::new(Default::default()));
         let total_optimized_points = Arc::new(AtomicUsize::new(0));
+        let point_history = point_history.map(Arc::new);
 
         // default to 2x the WAL capacity
         let disk_buffer_threshold_mb =
@@ -237,6 +260,7 @@ impl LocalShard {
             shard_path.into(),
             scroll_read_lock.clone(),
             update_tracker.clone(),
+            point_history.clone(),
         );
 
         let (update_sender, update_receiver) =
@@ -271,6 +295,8 @@ impl LocalShard {
             read_rate_limiter,
             is_gracefully_stopped: false,
             update_operation_lock: scroll_read_lock,
+            precondition_lock: tokio::sync::RwLock::new(()),
+            point_history,
         }
     }
 
@@ -302,6 +328,8 @@ impl LocalShard {
             SerdeWal::new(&wal_path, (&collection_config_read.wal_config).into())
                 .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?;
 
+        let point_history = PointHistory::load(shard_path, &collection_config_read.wal_config)?;
+
         // Walk over segments directory and collect all directory entries now
         // Collect now and error early to prevent errors while we've already spawned load threads
         let segment_paths = fs::read_dir(&segments_path)
@@ -455,6 +483,7 @@ impl LocalShard {
             optimizer_resource_budget,
             shard_path,
             clocks,
+            point_history,
             update_runtime,
             search_runtime,
         )
@@ -588,6 +617,8 @@ impl LocalShard {
         let wal: SerdeWal<OperationWithClockTag> =
             SerdeWal::new(&wal_path, (&config.wal_config).into())?;
 
+        let point_history = PointHistory::load(shard_path, &config.wal_config)?;
+
         let optimizers = build_optimizers(
             shard_path,
             &config.params,
@@ -610,6 +641,7 @@ impl LocalShard {
             optimizer_resource_budget,
             shard_path,
             LocalShardClocks::default(),
+            point_history,
             update_runtime,
             search_runtime,
         )
@@ -959,6 +991,56 @@ impl LocalShard {
         &self.update_tracker
     }
 
+    /// Previous versions of the point, latest first
+    pub fn point_history(&self, point_id: PointIdType) -> CollectionResult<Vec<PointVersion>> {
+        let Some(point_history) = &self.point_history else {
+            return Err(CollectionError::bad_request(
+                "Point history is disabled, set `wal_config.point_history_versions` to enable it",
+            ));
+        };
+        Ok(point_history.get(point_id))
+    }
+
+    /// Check the precondition of the operation, if it has any, and remove it from the operation.
+    ///
+    /// The precondition is checked against the shard state after all updates written to the WAL so
+    /// far are applied. The caller must hold the write `precondition_lock` until the operation is
+    /// written to the WAL. The operation is written without its precondition, so it is not checked
+    /// again when it's applied, e.g. on WAL replay or on another replica.
+    pub(super) async fn resolve_precondition(
+        &self,
+        operation: OperationWithClockTag,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<OperationWithClockTag> {
+        if operation.operation.precondition().is_none() {
+            return Ok(operation);
+        }
+
+        // Wait for all updates written to the WAL so far to be applied
+        let (tx, rx) = oneshot::channel();
+        self.update_sender
+            .load()
+            .send(UpdateSignal::Plunger(tx))
+            .await?;
+        rx.await?;
+
+        let segments = self.segments.clone();
+        let (mut operation, result) = tokio::task::spawn_blocking(move || {
+            let hw_counter = hw_measurement_acc.get_counter_cell();
+            let result = shard::update::check_operation_precondition(
+                &segments.read(),
+                &operation.operation,
+                &hw_counter,
+            );
+            (operation, result)
+        })
+        .await?;
+        result?;
+
+        operation.operation.take_precondition();
+        Ok(operation)
+    }
+
     pub fn optimizers_log(&self) -> Arc<ParkingMutex<TrackerLog>> {
         Arc::clone(&self.optimizers_log)
     }
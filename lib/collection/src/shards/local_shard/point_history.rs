@@ -0,0 +1,372 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+use api::rest::VectorStructOutput;
+use chrono::{DateTime, Utc};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use fs_err::tokio as tokio_fs;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::types::{Payload, PointIdType, SeqNumberType, WithPayload, WithVector};
+use serde::{Deserialize, Serialize};
+use shard::operations::CollectionUpdateOperations;
+use shard::retrieve::retrieve_blocking::retrieve_versioned_blocking;
+use shard::segment_holder::LockedSegmentHolder;
+use shard::wal::SerdeWal;
+
+use crate::common::file_utils::move_dir;
+use crate::config::WalConfig;
+use crate::operations::types::CollectionResult;
+
+const POINT_HISTORY_PATH: &str = "point_history";
+
+/// How long to wait for segments to read the previous versions of updated points
+const POINT_HISTORY_READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Number of records in the history WAL below which it is not compacted
+const POINT_HISTORY_COMPACTION_MIN_RECORDS: usize = 1024;
+
+/// Version of a point, which was replaced or deleted by a later operation
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PointVersion {
+    /// Number of the operation which wrote this version
+    pub version: SeqNumberType,
+    /// Number of the operation which replaced or deleted this version
+    pub replaced_by: SeqNumberType,
+    /// When this version was replaced or deleted
+    pub replaced_at: DateTime<Utc>,
+    /// Payload of this version, not recorded if the operation only updated vectors
+    pub payload: Option<Payload>,
+    /// Vectors of this version, not recorded if the operation only updated the payload
+    pub vector: Option<VectorStructOutput>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PointHistoryRecord {
+    id: PointIdType,
+    version: PointVersion,
+}
+
+/// Previous versions of the points of a local shard, kept if `wal_config.point_history_versions` is set.
+///
+/// Versions are persisted in a dedicated WAL, only their positions in it are kept in memory.
+/// Only operations listing point ids explicitly are recorded, points updated through a filter are not.
+pub struct PointHistory {
+    max_versions: usize,
+    storage: Mutex<PointHistoryStorage>,
+}
+
+struct PointHistoryStorage {
+    wal: SerdeWal<PointHistoryRecord>,
+    /// WAL indices of the retained versions of each point, oldest first
+    versions: HashMap<PointIdType, VecDeque<u64>>,
+    /// Number of retained versions of all points
+    retained: usize,
+}
+
+impl PointHistory {
+    /// Load the point history of the shard, if enabled in the WAL config
+    pub fn load(shard_path: &Path, wal_config: &WalConfig) -> CollectionResult<Option<Self>> {
+        let Some(max_versions) = wal_config.point_history_versions.filter(|&n| n > 0) else {
+            return Ok(None);
+        };
+
+        let wal: SerdeWal<PointHistoryRecord> =
+            SerdeWal::new(&Self::path(shard_path), wal_config.into())?;
+
+        let mut storage = PointHistoryStorage {
+            wal,
+            versions: HashMap::new(),
+            retained: 0,
+        };
+        let records: Vec<_> = storage
+            .wal
+            .read_all(false)
+            .map(|(index, record)| (index, record.id))
+            .collect();
+        for (index, id) in records {
+            storage.push_index(max_versions, id, index);
+        }
+        storage.compact_if_needed()?;
+
+        Ok(Some(Self {
+            max_versions,
+            storage: Mutex::new(storage),
+        }))
+    }
+
+    pub fn path(shard_path: &Path) -> PathBuf {
+        shard_path.join(POINT_HISTORY_PATH)
+    }
+
+    pub async fn move_data(from: &Path, to: &Path) -> CollectionResult<()> {
+        let history_from = Self::path(from);
+        if history_from.exists() {
+            move_dir(history_from, Self::path(to)).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn delete_data(shard_path: &Path) -> CollectionResult<()> {
+        let history_path = Self::path(shard_path);
+        if history_path.exists() {
+            tokio_fs::remove_dir_all(history_path).await?;
+        }
+        Ok(())
+    }
+
+    /// Previous versions of the point, latest first
+    pub fn get(&self, point_id: PointIdType) -> Vec<PointVersion> {
+        let storage = self.storage.lock();
+        let Some(indices) = storage.versions.get(&point_id) else {
+            return vec![];
+        };
+
+        indices
+            .iter()
+            .rev()
+            .filter_map(|&index| storage.wal.read(index).next())
+            .map(|(_, record)| record.version)
+            .collect()
+    }
+
+    /// Read the current versions of the points the operation is about to update
+    ///
+    /// Only the parts of the points the operation can change are read.
+    pub fn read_previous(
+        segments: &LockedSegmentHolder,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<Vec<(PointIdType, PointVersion)>> {
+        let (with_payload, with_vector) = match operation {
+            CollectionUpdateOperations::PointOperation(_) => (true, true),
+            CollectionUpdateOperations::VectorOperation(_) => (false, true),
+            CollectionUpdateOperations::PayloadOperation(_) => (true, false),
+            CollectionUpdateOperations::FieldIndexOperation(_) => return Ok(vec![]),
+        };
+
+        let Some(point_ids) = operation.point_ids() else {
+            return Ok(vec![]);
+        };
+
+        let records = retrieve_versioned_blocking(
+            segments.clone(),
+            &point_ids,
+            &WithPayload::from(with_payload),
+            &WithVector::Bool(with_vector),
+            POINT_HISTORY_READ_TIMEOUT,
+            &AtomicBool::new(false),
+            HwMeasurementAcc::disposable(),
+        )?;
+
+        let replaced_at = Utc::now();
+        let previous = records
+            .into_iter()
+            .map(|(id, (version, record))| {
+                let version = PointVersion {
+                    version,
+                    // Set once the operation is applied
+                    replaced_by: version,
+                    replaced_at,
+                    payload: record.payload,
+                    vector: record.vector.map(VectorStructOutput::from),
+                };
+                (id, version)
+            })
+            .collect();
+        Ok(previous)
+    }
+
+    /// Record previous versions of points, which were replaced or deleted by the applied operation
+    pub fn record(
+        &self,
+        segments: &LockedSegmentHolder,
+        previous: Vec<(PointIdType, PointVersion)>,
+        op_num: SeqNumberType,
+    ) -> CollectionResult<()> {
+        if previous.is_empty() {
+            return Ok(());
+        }
+
+        let point_ids: Vec<_> = previous.iter().map(|(id, _)| *id).collect();
+        let current = retrieve_versioned_blocking(
+            segments.clone(),
+            &point_ids,
+            &WithPayload::from(false),
+            &WithVector::Bool(false),
+            POINT_HISTORY_READ_TIMEOUT,
+            &AtomicBool::new(false),
+            HwMeasurementAcc::disposable(),
+        )?;
+
+        let mut storage = self.storage.lock();
+        for (id, mut version) in previous {
+            // Operations might leave points untouched, e.g. if a condition is not met
+            let is_replaced = current
+                .get(&id)
+                .is_none_or(|(current_version, _)| *current_version != version.version);
+            if !is_replaced {
+                continue;
+            }
+
+            version.replaced_by = op_num;
+            storage.push(self.max_versions, id, version)?;
+        }
+        storage.compact_if_needed()?;
+
+        Ok(())
+    }
+
+    pub fn flush(&self) -> CollectionResult<()> {
+        self.storage.lock().wal.flush()?;
+        Ok(())
+    }
+}
+
+impl PointHistoryStorage {
+    /// Write a version of the point, dropping the oldest one if there are too many
+    fn push(
+        &mut self,
+        max_versions: usize,
+        id: PointIdType,
+        version: PointVersion,
+    ) -> CollectionResult<()> {
+        let index = self.wal.write(&PointHistoryRecord { id, version })?;
+        self.push_index(max_versions, id, index);
+        Ok(())
+    }
+
+    fn push_index(&mut self, max_versions: usize, id: PointIdType, index: u64) {
+        let point_versions = self.versions.entry(id).or_default();
+        point_versions.push_back(index);
+        if point_versions.len() > max_versions {
+            point_versions.pop_front();
+        } else {
+            self.retained += 1;
+        }
+    }
+
+    /// Rewrite retained versions at the end of the WAL, so that older records can be dropped,
+    /// once most of the records in the WAL are not retained anymore
+    fn compact_if_needed(&mut self) -> CollectionResult<()> {
+        let records = self.wal.len(false) as usize;
+        if records < POINT_HISTORY_COMPACTION_MIN_RECORDS || records <= 2 * self.retained {
+            return Ok(());
+        }
+
+        let last_index = self.wal.last_index();
+        for (&id, indices) in self.versions.iter_mut() {
+            for index in indices.iter_mut() {
+                let Some((_, record)) = self.wal.read(*index).next() else {
+                    continue;
+                };
+                *index = self.wal.write(&PointHistoryRecord {
+                    id,
+                    version: record.version,
+                })?;
+            }
+        }
+        self.wal.flush()?;
+        self.wal.ack(last_index)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn point_version(version: SeqNumberType) -> PointVersion {
+        PointVersion {
+            version,
+            replaced_by: version + 1,
+            replaced_at: Utc::now(),
+            payload: Some(Payload::from(serde_json::json!({ "version": version }))),
+            vector: None,
+        }
+    }
+
+    #[test]
+    fn test_point_history_keeps_latest_versions() {
+        let dir = Builder::new().prefix("point_history").tempdir().unwrap();
+        let wal_config = WalConfig {
+            point_history_versions: Some(2),
+            ..WalConfig::default()
+        };
+
+        let history = PointHistory::load(dir.path(), &wal_config)
+            .unwrap()
+            .unwrap();
+        {
+            let mut storage = history.storage.lock();
+            for version in 1..=3 {
+                storage
+                    .push(history.max_versions, 1.into(), point_version(version))
+                    .unwrap();
+            }
+            storage.wal.flush().unwrap();
+        }
+
+        let get_versions = |history: &PointHistory| -> Vec<SeqNumberType> {
+            history
+                .get(1.into())
+                .into_iter()
+                .map(|version| version.version)
+                .collect()
+        };
+        assert_eq!(get_versions(&history), vec![3, 2]);
+        assert!(history.get(2.into()).is_empty());
+        drop(history);
+
+        // Versions are restored from the WAL
+        let history = PointHistory::load(dir.path(), &wal_config)
+            .unwrap()
+            .unwrap();
+        assert_eq!(get_versions(&history), vec![3, 2]);
+
+        // Disabled history is not loaded
+        assert!(
+            PointHistory::load(dir.path(), &WalConfig::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_point_history_compacts_wal() {
+        let dir = Builder::new().prefix("point_history").tempdir().unwrap();
+        let wal_config = WalConfig {
+            point_history_versions: Some(2),
+            ..WalConfig::default()
+        };
+
+        let history = PointHistory::load(dir.path(), &wal_config)
+            .unwrap()
+            .unwrap();
+        {
+            let mut storage = history.storage.lock();
+            for version in 1..=3000 {
+                let id = (version % 2).into();
+                storage
+                    .push(history.max_versions, id, point_version(version))
+                    .unwrap();
+                storage.compact_if_needed().unwrap();
+            }
+
+            // Only retained versions are left in the WAL, others are dropped
+            assert_eq!(storage.retained, 4);
+            assert!(storage.wal.len(false) < POINT_HISTORY_COMPACTION_MIN_RECORDS as u64);
+        }
+
+        let versions: Vec<_> = history
+            .get(1.into())
+            .into_iter()
+            .map(|version| version.version)
+            .collect();
+        assert_eq!(versions, vec![2999, 2997]);
+    }
+}
@@ -347,6 +347,11 @@ impl LocalShard {
                     with_vector: None,
                     score_threshold: score_threshold.map(OrderedFloat::into_inner),
                     with_explanation: false,
+                    with_explanation_bundle: false,
+                    explanation_verbosity: Default::default(),
+                    explanation_params: Default::default(),
+                    mmr: None,
+                    distinct_by: None,
                 };
                 let rescoring_core_search_request = CoreSearchRequestBatch {
                     searches: vec![search_request],
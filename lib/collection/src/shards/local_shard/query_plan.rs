@@ -0,0 +1,65 @@
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::counter::hardware_counter::HardwareCounterCell;
+use segment::common::operation_error::OperationResult;
+use segment::data_types::query_plan::SegmentSearchPlan;
+use tokio_util::task::AbortOnDropHandle;
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::operations::types::CollectionResult;
+use crate::operations::universal_query::planned_query::PlannedQuery;
+use crate::operations::universal_query::query_plan::{PlannedStage, StagePlan};
+use crate::operations::universal_query::shard_query::ShardQueryRequest;
+
+impl LocalShard {
+    /// Plan every leaf search and scroll of the request on each segment, without executing them
+    pub async fn query_plan(
+        &self,
+        request: ShardQueryRequest,
+        hw_measurement_acc: &HwMeasurementAcc,
+    ) -> CollectionResult<Vec<StagePlan>> {
+        let planned_query = PlannedQuery::try_from(vec![request])?;
+        let stages = PlannedStage::from_planned_query(&planned_query);
+
+        let segments = self.segments.clone();
+        let hw_counter = hw_measurement_acc.get_counter_cell();
+        let plans = tokio::task::spawn_blocking(move || {
+            let segments = segments.read(); // blocking sync lock
+            stages
+                .into_iter()
+                .map(|stage| {
+                    let segments = segments
+                        .iter()
+                        .map(|(_id, segment)| segment_search_plan(segment, &stage, &hw_counter))
+                        .collect::<OperationResult<Vec<_>>>()?;
+                    Ok(StagePlan {
+                        exact: stage.is_exact(),
+                        vector: stage.vector,
+                        segments,
+                    })
+                })
+                .collect::<OperationResult<Vec<_>>>()
+        });
+        let plans = AbortOnDropHandle::new(plans).await??;
+        Ok(plans)
+    }
+}
+
+/// Plan on the segment the search is executed on, proxies are searched through their wrapped segment
+fn segment_search_plan(
+    segment: &LockedSegment,
+    stage: &PlannedStage,
+    hw_counter: &HardwareCounterCell,
+) -> OperationResult<SegmentSearchPlan> {
+    match segment {
+        LockedSegment::Original(segment) => segment.read().search_plan(
+            stage.vector.as_deref(),
+            stage.filter.as_ref(),
+            stage.params.as_ref(),
+            hw_counter,
+        ),
+        LockedSegment::Proxy(proxy) => {
+            segment_search_plan(&proxy.read().wrapped_segment, stage, hw_counter)
+        }
+    }
+}
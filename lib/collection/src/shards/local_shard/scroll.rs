@@ -9,7 +9,7 @@ use itertools::Itertools as _;
 use rand::distr::weighted::WeightedIndex;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -272,15 +272,14 @@ impl LocalShard {
 
         let all_reads = all_reads.into_iter().collect::<Result<Vec<_>, _>>()?;
 
-        let (values, point_ids): (Vec<_>, Vec<_>) = all_reads
+        let ordered_points = all_reads
             .into_iter()
-            .kmerge_by(|a, b| match order_by.direction() {
-                Direction::Asc => a <= b,
-                Direction::Desc => a >= b,
-            })
+            .kmerge_by(|a, b| order_by.compare(a, b).is_le())
             .dedup()
             .take(limit)
-            .unzip();
+            .collect_vec();
+
+        let point_ids = ordered_points.iter().map(|point| point.id).collect_vec();
 
         let with_payload = WithPayload::from(with_payload_interface);
 
@@ -305,12 +304,12 @@ impl LocalShard {
 
         drop(update_operation_lock);
 
-        let ordered_records = point_ids
-            .iter()
-            .zip(values)
-            .filter_map(|(point_id, value)| {
-                let mut record = records_map.get(point_id).cloned()?;
-                record.order_value = Some(value);
+        let ordered_records = ordered_points
+            .into_iter()
+            .filter_map(|point| {
+                let mut record = records_map.get(&point.id).cloned()?;
+                record.order_value = Some(point.value);
+                record.then_by_values = point.then_by;
                 Some(record)
             })
             .collect();
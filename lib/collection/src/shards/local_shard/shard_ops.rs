@@ -45,8 +45,9 @@ impl ShardOperation for LocalShard {
         wait: bool,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
-        // `LocalShard::update` only has a single cancel safe `await`, WAL operations are blocking,
-        // and update is applied by a separate task, so, surprisingly, this method is cancel safe. :D
+        // `LocalShard::update` only has cancel safe `await`s before the operation is written to the
+        // WAL, WAL operations are blocking, and update is applied by a separate task, so,
+        // surprisingly, this method is cancel safe. :D
 
         let (callback_sender, callback_receiver) = if wait {
             let (tx, rx) = oneshot::channel();
@@ -67,6 +68,19 @@ impl ShardOperation for LocalShard {
         }
 
         let operation_id = {
+            // Must be acquired before reserving the channel permit, so that updates waiting for
+            // the write lock don't hold permits the precondition check waits for
+            let _precondition_read_guard;
+            let _precondition_write_guard;
+            if operation.operation.precondition().is_some() {
+                _precondition_write_guard = self.precondition_lock.write().await;
+                operation = self
+                    .resolve_precondition(operation, hw_measurement_acc.clone())
+                    .await?;
+            } else {
+                _precondition_read_guard = self.precondition_lock.read().await;
+            }
+
             let update_sender = self.update_sender.load();
             let channel_permit = update_sender.reserve().await?;
 
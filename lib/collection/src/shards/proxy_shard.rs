@@ -32,8 +32,10 @@ use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CountRequestInternal, CountResult,
     OptimizersStatus, PointRequestInternal, ScrollRequestInternal, UpdateResult,
 };
+use crate::operations::universal_query::query_plan::StagePlan;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::shards::local_shard::LocalShard;
+use crate::shards::local_shard::point_history::PointVersion;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
 use crate::update_handler::UpdateSignal;
@@ -181,6 +183,20 @@ impl ProxyShard {
             .estimate_cardinality(filter, hw_measurement_acc)
             .await
     }
+
+    pub async fn query_plan(
+        &self,
+        request: ShardQueryRequest,
+        hw_measurement_acc: &HwMeasurementAcc,
+    ) -> CollectionResult<Vec<StagePlan>> {
+        self.wrapped_shard
+            .query_plan(request, hw_measurement_acc)
+            .await
+    }
+
+    pub fn point_history(&self, point_id: PointIdType) -> CollectionResult<Vec<PointVersion>> {
+        self.wrapped_shard.point_history(point_id)
+    }
 }
 
 #[async_trait]
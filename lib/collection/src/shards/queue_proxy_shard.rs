@@ -12,7 +12,7 @@ use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::manifest::SnapshotManifest;
 use segment::index::field_index::CardinalityEstimation;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
+    ExtendedPointId, Filter, PointIdType, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
     WithPayloadInterface, WithVector,
 };
 use semver::Version;
@@ -27,13 +27,15 @@ use super::transfer::transfer_tasks_pool::TransferTaskProgress;
 use super::update_tracker::UpdateTracker;
 use crate::collection_manager::optimizers::TrackerLog;
 use crate::operations::OperationWithClockTag;
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::point_ops::{UpdateAck, WriteOrdering};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CountRequestInternal, CountResult,
     OptimizersStatus, PointRequestInternal, ScrollRequestInternal, UpdateResult,
 };
+use crate::operations::universal_query::query_plan::StagePlan;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::shards::local_shard::LocalShard;
+use crate::shards::local_shard::point_history::PointVersion;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
 
@@ -269,6 +271,21 @@ impl QueueProxyShard {
             .estimate_cardinality(filter, hw_measurement_acc)
             .await
     }
+
+    pub async fn query_plan(
+        &self,
+        request: ShardQueryRequest,
+        hw_measurement_acc: &HwMeasurementAcc,
+    ) -> CollectionResult<Vec<StagePlan>> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .query_plan(request, hw_measurement_acc)
+            .await
+    }
+
+    pub fn point_history(&self, point_id: PointIdType) -> CollectionResult<Vec<PointVersion>> {
+        self.inner_unchecked().wrapped_shard.point_history(point_id)
+    }
 }
 
 #[async_trait]
@@ -800,7 +817,7 @@ async fn transfer_operations_batch(
         remote_shard
             .forward_update_batch(
                 batch_upd,
-                wait,
+                UpdateAck::from_wait(wait),
                 WriteOrdering::Weak,
                 hw_measurement_acc.clone(),
             )
@@ -822,7 +839,7 @@ async fn transfer_operations_batch(
         remote_shard
             .forward_update(
                 operation,
-                wait,
+                UpdateAck::from_wait(wait),
                 WriteOrdering::Weak,
                 hw_measurement_acc.clone(),
             )
@@ -49,7 +49,9 @@ use super::conversions::{
 use super::local_shard::clock_map::RecoveryPoint;
 use crate::operations::conversions::try_record_from_grpc;
 use crate::operations::payload_ops::PayloadOps;
-use crate::operations::point_ops::{PointOperations, WriteOrdering};
+use crate::operations::point_ops::{
+    DeleteByFilterOperation, PointOperations, UpdateAck, WriteOrdering,
+};
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CountRequestInternal,
@@ -63,8 +65,8 @@ use crate::shards::channel_service::ChannelService;
 use crate::shards::conversions::{
     internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
     internal_delete_index, internal_delete_payload, internal_delete_points,
-    internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_delete_points_by_filter, internal_restore_points, internal_set_payload,
+    internal_sync_points, internal_upsert_points, try_scored_point_from_grpc,
 };
 use crate::shards::replica_set::replica_set_state::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
@@ -232,7 +234,7 @@ impl RemoteShard {
     pub async fn forward_update_batch(
         &self,
         operations: Vec<OperationWithClockTag>,
-        wait: bool,
+        ack: UpdateAck,
         ordering: WriteOrdering,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
@@ -251,7 +253,7 @@ impl RemoteShard {
                             operation.clock_tag,
                             collection_name.clone(),
                             point_insert_operations,
-                            wait,
+                            ack,
                             ordering,
                         )?;
 
@@ -263,40 +265,61 @@ impl RemoteShard {
                             operation.clock_tag,
                             collection_name.clone(),
                             conditional_upsert,
-                            wait,
+                            ack,
                             ordering,
                         )?;
                         Update::Upsert(request)
                     }
-                    PointOperations::DeletePoints { ids } => {
+                    PointOperations::DeletePoints {
+                        ids,
+                        precondition,
+                        deleted_at,
+                    } => {
                         let request = internal_delete_points(
                             shard_id,
                             operation.clock_tag,
                             collection_name.clone(),
                             ids,
-                            wait,
+                            precondition,
+                            deleted_at,
+                            ack,
                             ordering,
                         );
                         Update::Delete(request)
                     }
-                    PointOperations::DeletePointsByFilter(filter) => {
+                    PointOperations::DeletePointsByFilter(DeleteByFilterOperation {
+                        filter,
+                        deleted_at,
+                    }) => {
                         let request = internal_delete_points_by_filter(
                             shard_id,
                             operation.clock_tag,
                             collection_name.clone(),
                             filter,
-                            wait,
+                            deleted_at,
+                            ack,
                             ordering,
                         );
                         Update::Delete(request)
                     }
+                    PointOperations::RestorePoints { ids } => {
+                        let request = internal_restore_points(
+                            shard_id,
+                            operation.clock_tag,
+                            collection_name.clone(),
+                            ids,
+                            ack,
+                            ordering,
+                        );
+                        Update::Restore(request)
+                    }
                     PointOperations::SyncPoints(operation) => {
                         let request = internal_sync_points(
                             shard_id,
                             None, // TODO!?
                             collection_name.clone(),
                             operation,
-                            wait,
+                            ack,
                             ordering,
                         )?;
                         Update::Sync(request)
@@ -314,7 +337,7 @@ impl RemoteShard {
                             operation.clock_tag,
                             collection_name.clone(),
                             update_operation,
-                            wait,
+                            ack,
                             ordering,
                         )?;
                         Update::UpdateVectors(request)
@@ -326,7 +349,7 @@ impl RemoteShard {
                             collection_name.clone(),
                             ids.points,
                             vector_names.clone(),
-                            wait,
+                            ack,
                             ordering,
                         );
                         Update::DeleteVectors(request)
@@ -338,7 +361,7 @@ impl RemoteShard {
                             collection_name.clone(),
                             filter,
                             vector_names.clone(),
-                            wait,
+                            ack,
                             ordering,
                         );
                         Update::DeleteVectors(request)
@@ -351,7 +374,7 @@ impl RemoteShard {
                             operation.clock_tag,
                             collection_name.clone(),
                             set_payload,
-                            wait,
+                            ack,
                             ordering,
                         );
                         Update::SetPayload(request)
@@ -362,7 +385,7 @@ impl RemoteShard {
                             operation.clock_tag,
                             collection_name.clone(),
                             delete_payload,
-                            wait,
+                            ack,
                             ordering,
                         );
                         Update::DeletePayload(request)
@@ -373,7 +396,7 @@ impl RemoteShard {
                             operation.clock_tag,
                             collection_name.clone(),
                             points,
-                            wait,
+                            ack,
                             ordering,
                         );
                         Update::ClearPayload(request)
@@ -384,7 +407,7 @@ impl RemoteShard {
                             operation.clock_tag,
                             collection_name.clone(),
                             filter,
-                            wait,
+                            ack,
                             ordering,
                         );
                         Update::ClearPayload(request)
@@ -395,7 +418,7 @@ impl RemoteShard {
                             operation.clock_tag,
                             collection_name.clone(),
                             set_payload,
-                            wait,
+                            ack,
                             ordering,
                         );
                         Update::OverwritePayload(request)
@@ -409,7 +432,7 @@ impl RemoteShard {
                                 operation.clock_tag,
                                 collection_name.clone(),
                                 create_index,
-                                wait,
+                                ack,
                                 ordering,
                             );
                             Update::CreateFieldIndex(request)
@@ -420,7 +443,7 @@ impl RemoteShard {
                                 operation.clock_tag,
                                 collection_name.clone(),
                                 delete_index,
-                                wait,
+                                ack,
                                 ordering,
                             );
                             Update::DeleteFieldIndex(request)
@@ -464,7 +487,7 @@ impl RemoteShard {
     pub async fn forward_update(
         &self,
         operation: OperationWithClockTag,
-        wait: bool,
+        ack: UpdateAck,
         ordering: WriteOrdering,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
@@ -474,7 +497,7 @@ impl RemoteShard {
             Some(self.id),
             self.collection_id.clone(),
             operation,
-            wait,
+            ack,
             Some(ordering),
             hw_measurement_acc,
         )
@@ -489,7 +512,7 @@ impl RemoteShard {
         shard_id: Option<ShardId>,
         collection_name: String,
         operation: OperationWithClockTag,
-        wait: bool,
+        ack: UpdateAck,
         ordering: Option<WriteOrdering>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
@@ -507,7 +530,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         point_insert_operations,
-                        wait,
+                        ack,
                         ordering,
                     )?;
                     self.with_points_client(|mut client| async move {
@@ -522,7 +545,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         conditional_upsert,
-                        wait,
+                        ack,
                         ordering,
                     )?;
                     self.with_points_client(|mut client| async move {
@@ -531,13 +554,19 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
-                PointOperations::DeletePoints { ids } => {
+                PointOperations::DeletePoints {
+                    ids,
+                    precondition,
+                    deleted_at,
+                } => {
                     let request = &internal_delete_points(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
                         ids,
-                        wait,
+                        precondition,
+                        deleted_at,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -546,13 +575,17 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
-                PointOperations::DeletePointsByFilter(filter) => {
+                PointOperations::DeletePointsByFilter(DeleteByFilterOperation {
+                    filter,
+                    deleted_at,
+                }) => {
                     let request = &internal_delete_points_by_filter(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
                         filter,
-                        wait,
+                        deleted_at,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -561,13 +594,28 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
+                PointOperations::RestorePoints { ids } => {
+                    let request = &internal_restore_points(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        ids,
+                        ack,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client.restore(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
                 PointOperations::SyncPoints(operation) => {
                     let request = &internal_sync_points(
                         shard_id,
                         None, // TODO!?
                         collection_name,
                         operation,
-                        wait,
+                        ack,
                         ordering,
                     )?;
                     self.with_points_client(|mut client| async move {
@@ -600,7 +648,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         update_operation,
-                        wait,
+                        ack,
                         ordering,
                     )?;
                     self.with_points_client(|mut client| async move {
@@ -618,7 +666,7 @@ impl RemoteShard {
                         collection_name,
                         ids.points,
                         vector_names.clone(),
-                        wait,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -636,7 +684,7 @@ impl RemoteShard {
                         collection_name,
                         filter,
                         vector_names.clone(),
-                        wait,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -655,7 +703,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         set_payload,
-                        wait,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -672,7 +720,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         delete_payload,
-                        wait,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -689,7 +737,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         points,
-                        wait,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -706,7 +754,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         filter,
-                        wait,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -723,7 +771,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         set_payload,
-                        wait,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -743,7 +791,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         create_index,
-                        wait,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -760,7 +808,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         delete_index,
-                        wait,
+                        ack,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
@@ -957,7 +1005,7 @@ impl ShardOperation for RemoteShard {
             shard_id,
             self.collection_id.clone(),
             operation,
-            wait,
+            UpdateAck::from_wait(wait),
             None,
             hw_measurement_acc,
         )
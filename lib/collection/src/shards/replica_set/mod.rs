@@ -8,11 +8,13 @@ mod shard_transfer;
 pub mod snapshots;
 mod telemetry;
 mod update;
+mod write_coalescer;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref as _;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
 use std::time::Duration;
 
 use common::budget::ResourceBudget;
@@ -45,6 +47,7 @@ use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::replica_set::clock_set::ClockSet;
+use crate::shards::replica_set::write_coalescer::WriteCoalescer;
 use crate::shards::shard::{PeerId, Shard, ShardId};
 use crate::shards::shard_config::ShardConfig;
 
@@ -98,7 +101,7 @@ pub struct ShardReplicaSet {
     /// List is checked on each consensus round and submitted to the consensus.
     /// If the state of the peer is changed in the consensus, it is removed from the list.
     /// Update and read operations are not performed on the peers marked as dead.
-    locally_disabled_peers: parking_lot::RwLock<locally_disabled_peers::Registry>,
+    locally_disabled_peers: Arc<parking_lot::RwLock<locally_disabled_peers::Registry>>,
     pub(crate) shard_path: PathBuf,
     pub(crate) shard_id: ShardId,
     shard_key: Option<ShardKey>,
@@ -115,10 +118,14 @@ pub struct ShardReplicaSet {
     optimizer_resource_budget: ResourceBudget,
     /// Lock to serialized write operations on the replicaset when a write ordering is used.
     write_ordering_lock: Mutex<()>,
+    /// Upserts buffered to be applied together, if write coalescing is enabled.
+    write_coalescer: WriteCoalescer,
     /// Local clock set, used to tag new operations on this shard.
     clock_set: Mutex<ClockSet>,
     write_rate_limiter: Option<parking_lot::Mutex<RateLimiter>>,
     pub partial_snapshot_meta: PartialSnapshotMeta,
+    /// Total number of expired points deleted by this peer since the last start.
+    total_expired_points: AtomicUsize,
 }
 
 pub type AbortShardTransfer = Arc<dyn Fn(ShardTransfer, &str) + Send + Sync>;
@@ -226,9 +233,11 @@ impl ShardReplicaSet {
             search_runtime,
             optimizer_resource_budget,
             write_ordering_lock: Mutex::new(()),
+            write_coalescer: Default::default(),
             clock_set: Default::default(),
             write_rate_limiter,
             partial_snapshot_meta: PartialSnapshotMeta::default(),
+            total_expired_points: Default::default(),
         })
     }
 
@@ -368,9 +377,11 @@ impl ShardReplicaSet {
             search_runtime,
             optimizer_resource_budget,
             write_ordering_lock: Mutex::new(()),
+            write_coalescer: Default::default(),
             clock_set: Default::default(),
             write_rate_limiter,
             partial_snapshot_meta: PartialSnapshotMeta::default(),
+            total_expired_points: Default::default(),
         };
 
         // `active_remote_shards` includes `Active` and `ReshardingScaleDown` replicas!
@@ -1058,6 +1069,8 @@ impl ShardReplicaSet {
         let op =
             CollectionUpdateOperations::PointOperation(point_ops::PointOperations::DeletePoints {
                 ids,
+                precondition: None,
+                deleted_at: None,
             });
 
         // TODO(resharding): Assign clock tag to the operation!? 🤔
@@ -1172,11 +1185,28 @@ impl ShardReplicaSet {
             None => state_guard.insert(self.replica_state.read()),
         };
 
+        Self::disable_peer_locally(
+            &self.locally_disabled_peers,
+            state,
+            peer_id,
+            from_state,
+            || self.notify_peer_failure(peer_id, from_state),
+        );
+    }
+
+    /// Locally disable the peer and notify about its failure, unless it is the last active peer
+    fn disable_peer_locally(
+        locally_disabled_peers: &parking_lot::RwLock<locally_disabled_peers::Registry>,
+        state: &ReplicaSetState,
+        peer_id: PeerId,
+        from_state: Option<ReplicaState>,
+        notify_peer_failure: impl FnOnce(),
+    ) {
         let other_peers = state
             .active_or_resharding_peers()
             .filter(|id| id != &peer_id);
 
-        let mut locally_disabled_peers_guard = self.locally_disabled_peers.upgradable_read();
+        let mut locally_disabled_peers_guard = locally_disabled_peers.upgradable_read();
 
         // Prevent disabling last peer in consensus
         {
@@ -1190,7 +1220,7 @@ impl ShardReplicaSet {
 
         locally_disabled_peers_guard.with_upgraded(|locally_disabled_peers| {
             if locally_disabled_peers.disable_peer_and_notify_if_elapsed(peer_id, from_state) {
-                self.notify_peer_failure(peer_id, from_state);
+                notify_peer_failure();
             }
         });
     }
@@ -11,7 +11,9 @@ use shard::search::CoreSearchRequestBatch;
 use super::ShardReplicaSet;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::types::*;
+use crate::operations::universal_query::query_plan::StagePlan;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use crate::shards::local_shard::point_history::PointVersion;
 
 impl ShardReplicaSet {
     #[allow(clippy::too_many_arguments)]
@@ -214,6 +216,31 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Plan the query on the local replica, `None` if this peer has no local replica
+    pub async fn query_plan_local(
+        &self,
+        request: ShardQueryRequest,
+        hw_measurement_acc: &HwMeasurementAcc,
+    ) -> CollectionResult<Option<Vec<StagePlan>>> {
+        let local = self.local.read().await;
+        match &*local {
+            None => Ok(None),
+            Some(shard) => Ok(Some(shard.query_plan(request, hw_measurement_acc).await?)),
+        }
+    }
+
+    /// Previous versions of the point on the local replica, `None` if this peer has no local replica
+    pub async fn point_history_local(
+        &self,
+        point_id: PointIdType,
+    ) -> CollectionResult<Option<Vec<PointVersion>>> {
+        let local = self.local.read().await;
+        match &*local {
+            None => Ok(None),
+            Some(shard) => Ok(Some(shard.point_history(point_id)?)),
+        }
+    }
+
     pub async fn query_batch(
         &self,
         requests: Arc<Vec<ShardQueryRequest>>,
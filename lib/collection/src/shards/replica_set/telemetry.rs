@@ -1,4 +1,5 @@
 use std::ops::Deref as _;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use common::types::TelemetryDetail;
@@ -41,6 +42,7 @@ impl ShardReplicaSet {
                 is_recovering: self.partial_snapshot_meta.is_recovery_lock_taken(),
                 recovery_timestamp: self.partial_snapshot_meta.recovery_timestamp(),
             }),
+            total_expired_points: self.total_expired_points.load(Ordering::Relaxed),
         })
     }
 
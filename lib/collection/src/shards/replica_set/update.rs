@@ -1,18 +1,27 @@
 use std::ops::Deref as _;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
+use chrono::Utc;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::save_on_disk::SaveOnDisk;
 use futures::stream::FuturesUnordered;
-use futures::{FutureExt as _, StreamExt as _};
+use futures::{FutureExt, Stream, StreamExt as _};
 use itertools::Itertools as _;
 use tokio_util::task::AbortOnDropHandle;
 
-use super::{ShardReplicaSet, clock_set};
-use crate::operations::point_ops::WriteOrdering;
-use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
+use super::{ChangePeerFromState, ShardReplicaSet, clock_set, locally_disabled_peers};
+use crate::collection::expiration::expired_points_filter;
+use crate::operations::point_ops::{PointOperations, UpdateAck, WriteOrdering};
+use crate::operations::types::{
+    CollectionError, CollectionResult, CountRequestInternal, UpdateResult, UpdateStatus,
+};
 use crate::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
+use crate::shards::CollectionId;
+use crate::shards::remote_shard::RemoteShard;
 use crate::shards::replica_set::replica_set_state::{ReplicaSetState, ReplicaState};
-use crate::shards::shard::{PeerId, Shard};
+use crate::shards::shard::{PeerId, Shard, ShardId};
 use crate::shards::shard_trait::ShardOperation as _;
 
 /// Maximum number of attempts for applying an update with a new clock.
@@ -23,6 +32,9 @@ const UPDATE_MAX_CLOCK_REJECTED_RETRIES: usize = 3;
 
 const DEFAULT_SHARD_DEACTIVATION_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Outcome of an update on a single replica
+type UpdateOutcome = Result<(PeerId, UpdateResult), (PeerId, CollectionError)>;
+
 impl ShardReplicaSet {
     /// Update local shard if any without forwarding to remote shards
     ///
@@ -112,13 +124,19 @@ impl ShardReplicaSet {
     pub async fn update_with_consistency(
         &self,
         operation: CollectionUpdateOperations,
-        wait: bool,
+        ack: UpdateAck,
         ordering: WriteOrdering,
         update_only_existing: bool,
         mut hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
         // `ShardReplicaSet::update` is not cancel safe, so this method is not cancel safe.
 
+        // Preconditions are only checked by the leader, which must have an up-to-date replica
+        let ordering = match ordering {
+            WriteOrdering::Weak if operation.precondition().is_some() => WriteOrdering::Medium,
+            ordering => ordering,
+        };
+
         let Some(leader_peer) = self.leader_peer_for_update(ordering) else {
             return Err(CollectionError::service_error(format!(
                 "Cannot update shard {}:{} with {ordering:?} ordering because no leader could be selected",
@@ -142,11 +160,24 @@ impl ShardReplicaSet {
                 WriteOrdering::Weak => None,
             };
 
-            self.update(operation, wait, update_only_existing, hw_measurement_acc)
+            if let Some(config) = self.write_coalescing_config().await {
+                return self
+                    .update_coalesced(
+                        operation,
+                        ack,
+                        ordering,
+                        update_only_existing,
+                        config,
+                        hw_measurement_acc,
+                    )
+                    .await;
+            }
+
+            self.update(operation, ack, update_only_existing, hw_measurement_acc)
                 .await
         } else {
             // Forward the update to the designated leader
-            self.forward_update(leader_peer, operation, wait, ordering, hw_measurement_acc)
+            self.forward_update(leader_peer, operation, ack, ordering, hw_measurement_acc)
                 .await
                 .map_err(|err| {
                     if err.is_transient() {
@@ -166,6 +197,59 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Delete points of this shard whose expiration datetime has passed
+    ///
+    /// Only the peer leading updates of the shard deletes expired points, through the regular
+    /// update path, so that every replica applies the same deletion. Does nothing if expiration
+    /// is not enabled for the collection.
+    ///
+    /// Returns the number of expired points found.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe.
+    pub(crate) async fn expire_points(&self) -> CollectionResult<usize> {
+        let Some(filter) = expired_points_filter(&self.payload_index_schema) else {
+            return Ok(0);
+        };
+
+        if self.leader_peer_for_update(WriteOrdering::Medium) != Some(self.this_peer_id()) {
+            return Ok(0);
+        }
+
+        // Avoid writing empty deletions into the WAL of every replica
+        let request = Arc::new(CountRequestInternal {
+            filter: Some(filter.clone()),
+            exact: true,
+        });
+        let expired_points = self
+            .count_local(request, None, HwMeasurementAcc::disposable())
+            .await?
+            .map_or(0, |result| result.count);
+
+        if expired_points == 0 {
+            return Ok(0);
+        }
+
+        // Points refreshed in between are not deleted, as the filter is checked again on apply
+        let operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::DeletePointsByFilter(filter.into()),
+        );
+        self.update_with_consistency(
+            operation,
+            UpdateAck::Applied,
+            WriteOrdering::Medium,
+            false,
+            HwMeasurementAcc::disposable(), // Internal operation, no measurement needed
+        )
+        .await?;
+
+        self.total_expired_points
+            .fetch_add(expired_points, Ordering::Relaxed);
+
+        Ok(expired_points)
+    }
+
     /// Designated a leader replica for the update based on the WriteOrdering
     fn leader_peer_for_update(&self, ordering: WriteOrdering) -> Option<PeerId> {
         match ordering {
@@ -193,15 +277,19 @@ impl ShardReplicaSet {
     /// # Cancel safety
     ///
     /// This method is *not* cancel safe.
-    async fn update(
+    pub(super) async fn update(
         &self,
-        operation: CollectionUpdateOperations,
-        wait: bool,
+        mut operation: CollectionUpdateOperations,
+        ack: UpdateAck,
         update_only_existing: bool,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
         // `ShardRepilcaSet::update_impl` is not cancel safe, so this method is not cancel safe.
 
+        // Set the deletion time once on the leader, so all replicas, WAL replay and shard
+        // transfers soft delete points at the same time
+        operation.set_deleted_at(Utc::now());
+
         // TODO: Optimize `remotes`/`local`/`clock` locking for the "happy path"?
         //
         // E.g., refactor `update`/`update_impl`, so that it would be possible to:
@@ -216,7 +304,7 @@ impl ShardReplicaSet {
             let res = self
                 .update_impl(
                     operation.clone(),
-                    wait,
+                    ack,
                     &mut clock,
                     update_only_existing,
                     hw_measurement_acc.clone(),
@@ -257,7 +345,7 @@ impl ShardReplicaSet {
     async fn update_impl(
         &self,
         operation: CollectionUpdateOperations,
-        wait: bool,
+        ack: UpdateAck,
         clock: &mut clock_set::ClockGuard,
         update_only_existing: bool,
         hw_measurement_acc: HwMeasurementAcc,
@@ -266,10 +354,27 @@ impl ShardReplicaSet {
         // multiple parallel updates in a way that is *guaranteed* not to introduce inconsistencies
         // between nodes, so this method is not cancel safe.
 
+        let wait = ack.wait();
+
+        let write_consistency_factor = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .write_consistency_factor
+            .get() as usize;
+
         let remotes = self.remotes.read().await;
         let local = self.local.read().await;
         let replica_count = usize::from(local.is_some()) + remotes.len();
 
+        let minimal_success_count = match ack {
+            // Majority of replicas, unless write consistency factor requires more
+            UpdateAck::Replicated => write_consistency_factor.max(replica_count / 2 + 1),
+            UpdateAck::None | UpdateAck::Queued | UpdateAck::Applied => write_consistency_factor,
+        }
+        .min(replica_count);
+
         let this_peer_id = self.this_peer_id();
 
         // Target all remote peers that can receive updates
@@ -288,11 +393,21 @@ impl ShardReplicaSet {
             )));
         }
 
+        if operation.precondition().is_some()
+            && !(local_is_updatable && self.peer_can_be_source_of_truth(this_peer_id))
+        {
+            return Err(CollectionError::service_error(format!(
+                "Can't check update precondition on peer {this_peer_id}, \
+                 which has no active replica of shard {}",
+                self.shard_id,
+            )));
+        }
+
         let current_clock_tick = clock.tick_once();
         let clock_tag = ClockTag::new(this_peer_id, clock.id() as _, current_clock_tick);
-        let operation = OperationWithClockTag::new(operation, Some(clock_tag));
+        let mut operation = OperationWithClockTag::new(operation, Some(clock_tag));
 
-        let mut update_futures = Vec::with_capacity(updatable_remote_shards.len() + 1);
+        let mut local_update = None;
 
         if let Some(local) = local.deref()
             && self.is_peer_updatable(this_peer_id)
@@ -309,60 +424,86 @@ impl ShardReplicaSet {
                     .await?;
             }
 
+            // Check the precondition once, by applying the operation to the leader replica first.
+            // The local shard checks it before writing the operation to its WAL. Other replicas
+            // apply it unconditionally, so they can't diverge on its outcome.
+            let checked_result = if operation.operation.precondition().is_some() {
+                let result = local
+                    .get()
+                    .update(operation.clone(), true, hw_measurement_acc.clone())
+                    .await?;
+
+                if result.status == UpdateStatus::ClockRejected {
+                    if let Some(echo_tag) = result.clock_tag {
+                        clock.advance_to(echo_tag.clock_tick);
+                    }
+                    return Ok(None);
+                }
+
+                operation.operation.take_precondition();
+                Some(result)
+            } else {
+                None
+            };
+
             let operation = operation.clone();
 
             let hw_acc = hw_measurement_acc.clone();
-            let local_update = async move {
-                local
-                    .get()
-                    .update(operation, local_wait, hw_acc)
-                    .await
+            local_update = Some(async move {
+                let result = match checked_result {
+                    Some(result) => Ok(result),
+                    None => local.get().update(operation, local_wait, hw_acc).await,
+                };
+                result
                     .map(|ok| (this_peer_id, ok))
                     .map_err(|err| (this_peer_id, err))
-            };
-
-            update_futures.push(local_update.left_future());
+            });
         }
 
-        for remote in updatable_remote_shards {
-            let operation = operation.clone();
+        let all_res: Vec<Result<_, _>> = if ack == UpdateAck::Replicated {
+            self.update_until_replicated(
+                local_update,
+                updatable_remote_shards,
+                &operation,
+                minimal_success_count,
+                update_only_existing,
+                &hw_measurement_acc,
+            )
+            .await
+        } else {
+            let mut update_futures = Vec::with_capacity(updatable_remote_shards.len() + 1);
+            update_futures.extend(local_update.map(FutureExt::left_future));
+
+            for remote in updatable_remote_shards {
+                let operation = operation.clone();
+
+                let hw_acc = hw_measurement_acc.clone();
+                let remote_update = async move {
+                    remote
+                        .update(operation, wait, hw_acc)
+                        .await
+                        .map(|ok| (remote.peer_id, ok))
+                        .map_err(|err| (remote.peer_id, err))
+                };
 
-            let hw_acc = hw_measurement_acc.clone();
-            let remote_update = async move {
-                remote
-                    .update(operation, wait, hw_acc)
-                    .await
-                    .map(|ok| (remote.peer_id, ok))
-                    .map_err(|err| (remote.peer_id, err))
-            };
+                update_futures.push(remote_update.right_future());
+            }
 
-            update_futures.push(remote_update.right_future());
-        }
+            match self.shared_storage_config.update_concurrency {
+                Some(concurrency) => {
+                    futures::stream::iter(update_futures)
+                        .buffer_unordered(concurrency.get())
+                        .collect()
+                        .await
+                }
 
-        let all_res: Vec<Result<_, _>> = match self.shared_storage_config.update_concurrency {
-            Some(concurrency) => {
-                futures::stream::iter(update_futures)
-                    .buffer_unordered(concurrency.get())
-                    .collect()
-                    .await
+                None => FuturesUnordered::from_iter(update_futures).collect().await,
             }
-
-            None => FuturesUnordered::from_iter(update_futures).collect().await,
         };
 
         drop(local);
         drop(remotes);
 
-        let write_consistency_factor = self
-            .collection_config
-            .read()
-            .await
-            .params
-            .write_consistency_factor
-            .get() as usize;
-
-        let minimal_success_count = write_consistency_factor.min(replica_count);
-
         let (successes, failures): (Vec<_>, Vec<_>) = all_res.into_iter().partition_result();
 
         // Advance clock if some replica echoed *newer* tick
@@ -526,6 +667,24 @@ impl ShardReplicaSet {
             return Ok(None);
         }
 
+        if ack == UpdateAck::Replicated {
+            let applied_count = successes
+                .iter()
+                .filter(|&&(peer_id, ref res)| {
+                    res.status == UpdateStatus::Completed
+                        && self.peer_can_be_source_of_truth(peer_id)
+                })
+                .count();
+
+            if applied_count < minimal_success_count {
+                return Err(CollectionError::service_error(format!(
+                    "Update was applied by {applied_count} active replicas of shard {}, \
+                     but {minimal_success_count} are required to acknowledge it as replicated",
+                    self.shard_id,
+                )));
+            }
+        }
+
         // There are enough successes, return the first one
         let (_, res) = successes
             .into_iter()
@@ -535,6 +694,96 @@ impl ShardReplicaSet {
         Ok(Some(res))
     }
 
+    /// Apply the update to all replicas, but only wait until it is applied by
+    /// `minimal_success_count` active replicas, including the local one
+    ///
+    /// Remote replicas which did not respond by then keep applying the update in the background.
+    /// If one of them fails, it is deactivated.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe.
+    async fn update_until_replicated(
+        &self,
+        local_update: Option<impl Future<Output = UpdateOutcome>>,
+        remotes: Vec<&RemoteShard>,
+        operation: &OperationWithClockTag,
+        minimal_success_count: usize,
+        update_only_existing: bool,
+        hw_measurement_acc: &HwMeasurementAcc,
+    ) -> Vec<UpdateOutcome> {
+        let this_peer_id = self.this_peer_id();
+
+        let mut is_local_pending = local_update.is_some();
+        let local_updates: FuturesUnordered<_> = local_update.into_iter().collect();
+
+        // Remote updates run in tasks, so that they are not cancelled once we respond
+        let remote_updates: FuturesUnordered<_> = remotes
+            .into_iter()
+            .map(|remote| {
+                let remote = remote.clone();
+                let peer_id = remote.peer_id;
+                let operation = operation.clone();
+                let hw_acc = hw_measurement_acc.clone();
+
+                let task = self
+                    .update_runtime
+                    .spawn(async move { remote.update(operation, true, hw_acc).await });
+
+                task.map(move |result| {
+                    let result = result.unwrap_or_else(|err| Err(err.into()));
+                    result.map(|ok| (peer_id, ok)).map_err(|err| (peer_id, err))
+                })
+            })
+            .collect();
+
+        let mut updates = futures::stream::select(local_updates, remote_updates);
+
+        let mut results = Vec::new();
+        let mut applied_count = 0;
+
+        while let Some(result) = updates.next().await {
+            let peer_id = match &result {
+                Ok((peer_id, _)) | Err((peer_id, _)) => *peer_id,
+            };
+
+            if let Ok((_, res)) = &result
+                && res.status == UpdateStatus::Completed
+                && self.peer_can_be_source_of_truth(peer_id)
+            {
+                applied_count += 1;
+            }
+
+            if peer_id == this_peer_id {
+                is_local_pending = false;
+            }
+
+            results.push(result);
+
+            // Local update borrows the local shard, so it must complete before we respond
+            if applied_count >= minimal_success_count && !is_local_pending {
+                break;
+            }
+        }
+
+        let (_, late_updates) = updates.into_inner();
+        if !late_updates.is_empty() {
+            self.update_runtime.spawn(handle_late_updates(
+                late_updates,
+                LateUpdatesContext {
+                    replica_state: self.replica_state.clone(),
+                    locally_disabled_peers: self.locally_disabled_peers.clone(),
+                    notify_peer_failure: self.notify_peer_failure_cb.clone(),
+                    collection_id: self.collection_id.clone(),
+                    shard_id: self.shard_id,
+                    update_only_existing,
+                },
+            ));
+        }
+
+        results
+    }
+
     /// Check write rate limiter for the operation
     ///
     /// Lazily compute the cost of the operation and check against the write rate limiter
@@ -595,34 +844,7 @@ impl ShardReplicaSet {
                 continue;
             };
 
-            // Ignore errors entirely for dead and listener replicas
-            match peer_state {
-                ReplicaState::Dead | ReplicaState::Listener => continue,
-                ReplicaState::Active
-                | ReplicaState::Initializing
-                | ReplicaState::Partial
-                | ReplicaState::Recovery
-                | ReplicaState::PartialSnapshot
-                | ReplicaState::Resharding
-                | ReplicaState::ReshardingScaleDown
-                | ReplicaState::ActiveRead => (),
-            }
-
-            // Handle a special case where transfer receiver is not in the expected replica state yet.
-            // Data consistency will be handled by the shard transfer and the associated proxies.
-            if peer_state.is_partial_or_recovery() && err.is_pre_condition_failed() {
-                continue;
-            }
-
-            // Ignore missing point errors if replica is in partial or recovery state
-            // Partial or recovery state indicates that the replica is receiving a shard transfer,
-            // it might not have received all the points yet
-            // See: <https://github.com/qdrant/qdrant/pull/5991>
-            if peer_state.is_partial_or_recovery() && err.is_missing_point() {
-                continue;
-            }
-
-            if update_only_existing && err.is_missing_point() {
+            if !failure_requires_deactivation(peer_state, err, update_only_existing) {
                 continue;
             }
 
@@ -659,7 +881,7 @@ impl ShardReplicaSet {
         &self,
         leader_peer: PeerId,
         operation: CollectionUpdateOperations,
-        wait: bool,
+        ack: UpdateAck,
         ordering: WriteOrdering,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
@@ -677,7 +899,7 @@ impl ShardReplicaSet {
         remote_leader
             .forward_update(
                 OperationWithClockTag::from(operation),
-                wait,
+                ack,
                 ordering,
                 hw_measurement_acc,
             ) // `clock_tag` *has to* be `None`!
@@ -685,6 +907,109 @@ impl ShardReplicaSet {
     }
 }
 
+/// Whether a replica which failed to apply an update has to be deactivated
+fn failure_requires_deactivation(
+    peer_state: ReplicaState,
+    err: &CollectionError,
+    update_only_existing: bool,
+) -> bool {
+    // Ignore errors entirely for dead and listener replicas
+    match peer_state {
+        ReplicaState::Dead | ReplicaState::Listener => return false,
+        ReplicaState::Active
+        | ReplicaState::Initializing
+        | ReplicaState::Partial
+        | ReplicaState::Recovery
+        | ReplicaState::PartialSnapshot
+        | ReplicaState::Resharding
+        | ReplicaState::ReshardingScaleDown
+        | ReplicaState::ActiveRead => (),
+    }
+
+    // Handle a special case where transfer receiver is not in the expected replica state yet.
+    // Data consistency will be handled by the shard transfer and the associated proxies.
+    if peer_state.is_partial_or_recovery() && err.is_pre_condition_failed() {
+        return false;
+    }
+
+    // Ignore missing point errors if replica is in partial or recovery state
+    // Partial or recovery state indicates that the replica is receiving a shard transfer,
+    // it might not have received all the points yet
+    // See: <https://github.com/qdrant/qdrant/pull/5991>
+    if peer_state.is_partial_or_recovery() && err.is_missing_point() {
+        return false;
+    }
+
+    !(update_only_existing && err.is_missing_point())
+}
+
+/// State required to deactivate replicas after the update was acknowledged
+struct LateUpdatesContext {
+    replica_state: Arc<SaveOnDisk<ReplicaSetState>>,
+    locally_disabled_peers: Arc<parking_lot::RwLock<locally_disabled_peers::Registry>>,
+    notify_peer_failure: ChangePeerFromState,
+    collection_id: CollectionId,
+    shard_id: ShardId,
+    update_only_existing: bool,
+}
+
+/// Deactivate replicas which fail to apply an update after it was acknowledged
+async fn handle_late_updates(
+    mut late_updates: impl Stream<Item = UpdateOutcome> + Unpin,
+    context: LateUpdatesContext,
+) {
+    let LateUpdatesContext {
+        replica_state,
+        locally_disabled_peers,
+        notify_peer_failure,
+        collection_id,
+        shard_id,
+        update_only_existing,
+    } = context;
+
+    while let Some(result) = late_updates.next().await {
+        let (peer_id, err) = match result {
+            Ok((peer_id, res)) if res.status == UpdateStatus::ClockRejected => (
+                peer_id,
+                CollectionError::service_error("Update was rejected because of an outdated clock"),
+            ),
+            Ok(_) => continue,
+            Err(failure) => failure,
+        };
+
+        log::warn!(
+            "Failed to update shard {collection_id}:{shard_id} on peer {peer_id} \
+             after the update was acknowledged, error: {err}",
+        );
+
+        let state = replica_state.read();
+
+        let Some(peer_state) = state.get_peer_state(peer_id) else {
+            continue;
+        };
+
+        if !failure_requires_deactivation(peer_state, &err, update_only_existing) {
+            continue;
+        }
+
+        log::debug!(
+            "Deactivating peer {peer_id} because of failed update of shard {collection_id}:{shard_id}"
+        );
+
+        // Deactivate replica in consensus if it matches the state we expect
+        // Always deactivate the replica if its in a shard transfer related state
+        let from_state = Some(peer_state).filter(|state| !state.is_partial_or_recovery());
+
+        ShardReplicaSet::disable_peer_locally(
+            &locally_disabled_peers,
+            &state,
+            peer_id,
+            from_state,
+            || notify_peer_failure.deref()(peer_id, shard_id, from_state),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -700,10 +1025,13 @@ mod tests {
 
     use super::*;
     use crate::config::*;
+    use crate::operations::point_ops::{
+        PointInsertOperationsInternal, PointStructPersisted, VectorStructPersisted,
+    };
     use crate::operations::types::VectorsConfig;
     use crate::operations::vector_params_builder::VectorParamsBuilder;
     use crate::optimizers_builder::OptimizersConfig;
-    use crate::shards::replica_set::{AbortShardTransfer, ChangePeerFromState};
+    use crate::shards::replica_set::AbortShardTransfer;
 
     #[tokio::test]
     async fn test_highest_replica_peer_id() {
@@ -745,6 +1073,10 @@ mod tests {
             wal_capacity_mb: 1,
             wal_segments_ahead: 0,
             wal_retain_closed: 1,
+            point_history_versions: None,
+            soft_delete_retention_sec: None,
+            write_coalescing_ms: None,
+            write_coalescing_max_points: None,
         };
 
         let collection_params = CollectionParams {
@@ -797,6 +1129,158 @@ mod tests {
         .unwrap()
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_update_ack_levels() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let rs = new_local_shard_replica_set(&collection_dir, HashSet::new()).await;
+
+        for (id, ack, expected_status) in [
+            (1, UpdateAck::None, UpdateStatus::Acknowledged),
+            (2, UpdateAck::Queued, UpdateStatus::Acknowledged),
+            (3, UpdateAck::Applied, UpdateStatus::Completed),
+            (4, UpdateAck::Replicated, UpdateStatus::Completed),
+        ] {
+            let result = rs
+                .update_with_consistency(
+                    upsert_point(id),
+                    ack,
+                    WriteOrdering::Weak,
+                    false,
+                    HwMeasurementAcc::new(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(result.status, expected_status, "ack: {ack:?}");
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_replicated_ack_requires_majority() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        // Remote replicas are dead, so only the local replica applies updates
+        let rs = new_local_shard_replica_set(&collection_dir, HashSet::from([2, 3])).await;
+
+        let update = |id, ack| {
+            rs.update_with_consistency(
+                upsert_point(id),
+                ack,
+                WriteOrdering::Weak,
+                false,
+                HwMeasurementAcc::new(),
+            )
+        };
+
+        // Write consistency factor of 1 is satisfied by the local replica
+        let result = update(1, UpdateAck::Applied).await.unwrap();
+        assert_eq!(result.status, UpdateStatus::Completed);
+
+        // But it is not a majority of 3 replicas
+        let err = update(2, UpdateAck::Replicated).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("required to acknowledge it as replicated")
+        );
+    }
+
+    #[test]
+    fn test_failure_requires_deactivation() {
+        let missing_point = CollectionError::PointNotFound {
+            missed_point_id: 1.into(),
+        };
+        let service_error = CollectionError::service_error("failed");
+
+        assert!(failure_requires_deactivation(
+            ReplicaState::Active,
+            &service_error,
+            false
+        ));
+        assert!(failure_requires_deactivation(
+            ReplicaState::Active,
+            &missing_point,
+            false
+        ));
+        assert!(!failure_requires_deactivation(
+            ReplicaState::Active,
+            &missing_point,
+            true
+        ));
+        assert!(!failure_requires_deactivation(
+            ReplicaState::Partial,
+            &missing_point,
+            false
+        ));
+        assert!(!failure_requires_deactivation(
+            ReplicaState::Dead,
+            &service_error,
+            false
+        ));
+        assert!(!failure_requires_deactivation(
+            ReplicaState::Listener,
+            &service_error,
+            false
+        ));
+    }
+
+    fn upsert_point(id: u64) -> CollectionUpdateOperations {
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(vec![PointStructPersisted {
+                id: id.into(),
+                vector: VectorStructPersisted::Single(vec![1.0, 0.0, 1.0, 0.0]),
+                payload: None,
+            }]),
+        ))
+    }
+
+    /// Replica set with an active local replica, and the given dead remote replicas
+    async fn new_local_shard_replica_set(
+        collection_dir: &TempDir,
+        remotes: HashSet<PeerId>,
+    ) -> ShardReplicaSet {
+        let config = CollectionConfigInternal {
+            params: CollectionParams {
+                vectors: VectorsConfig::Single(VectorParamsBuilder::new(4, Distance::Dot).build()),
+                ..CollectionParams::empty()
+            },
+            optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+            wal_config: WalConfig::default(),
+            hnsw_config: Default::default(),
+            quantization_config: None,
+            strict_mode_config: None,
+            uuid: None,
+            metadata: None,
+        };
+
+        let payload_index_schema_file = collection_dir.path().join("payload-schema.json");
+        let payload_index_schema =
+            Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+        let rs = ShardReplicaSet::build(
+            1,
+            None,
+            "test_collection".to_string(),
+            1,
+            true,
+            remotes,
+            dummy_on_replica_failure(),
+            dummy_abort_shard_transfer(),
+            collection_dir.path(),
+            Arc::new(RwLock::new(config.clone())),
+            config.optimizer_config.clone(),
+            Default::default(),
+            payload_index_schema,
+            Default::default(),
+            Handle::current(),
+            Handle::current(),
+            ResourceBudget::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        rs.set_replica_state(1, ReplicaState::Active).await.unwrap();
+        rs
+    }
+
     fn dummy_on_replica_failure() -> ChangePeerFromState {
         Arc::new(move |_peer_id, _shard_id, _from_state| {})
     }
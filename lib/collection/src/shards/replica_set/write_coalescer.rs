@@ -0,0 +1,590 @@
+//! Coalescing of small upserts into a single operation.
+//!
+//! If `write_coalescing_ms` is set in the WAL config of the collection, lists of points upserted
+//! with weak ordering are buffered per shard, and applied together once the time window passes or
+//! enough points are buffered. Many small requests then produce a single WAL entry and a single
+//! round of replication, at the cost of added latency: requests only respond once their batch is
+//! applied, even with `wait=false`.
+//!
+//! Upserts are coalesced in the replica set rather than in the update queue of the local shard,
+//! so that a batch is also sent to the other replicas as a single operation. Coalescing in the
+//! local update queue would come after every request already got its own WAL entry and its own
+//! round of replication.
+//!
+//! Batches and all other operations on the shard are applied under the same lock, and other
+//! operations apply the pending batch first. So operations are applied in the order they are
+//! accepted, even if an upsert is still buffered.
+//!
+//! Points of each request are validated before they are buffered, so that an invalid request
+//! can't fail the other requests of its batch. If a batch fails anyway, its requests are applied
+//! one by one, each getting its own result.
+
+use std::time::{Duration, Instant};
+
+use common::counter::hardware_accumulator::{HwMeasurementAcc, HwSharedDrain};
+use parking_lot::Mutex;
+use segment::common::check_named_vectors;
+use tokio::sync::oneshot;
+
+use super::ShardReplicaSet;
+use crate::operations::CollectionUpdateOperations;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStructPersisted, UpdateAck, WriteOrdering,
+};
+use crate::operations::types::{CollectionError, CollectionResult, UpdateResult};
+
+/// Number of buffered points at which coalesced upserts are applied, if not configured
+const DEFAULT_WRITE_COALESCING_MAX_POINTS: usize = 1000;
+
+#[derive(Clone, Copy, Debug)]
+pub(super) struct WriteCoalescingConfig {
+    window: Duration,
+    max_points: usize,
+}
+
+/// Upserts buffered in a replica set, to be applied together
+#[derive(Default)]
+pub(super) struct WriteCoalescer {
+    state: Mutex<CoalescerState>,
+    /// Held while applying a batch or any other operation, batches may only be taken with it held
+    apply_lock: tokio::sync::Mutex<()>,
+}
+
+#[derive(Default)]
+struct CoalescerState {
+    pending: Option<PendingBatch>,
+    next_batch_id: u64,
+}
+
+struct PendingBatch {
+    id: u64,
+    created_at: Instant,
+    requests: Vec<PendingUpsert>,
+    points_count: usize,
+}
+
+/// Single upsert request buffered in a batch
+struct PendingUpsert {
+    points: Vec<PointStructPersisted>,
+    ack: UpdateAck,
+    hw_measurement_acc: HwMeasurementAcc,
+    result: oneshot::Sender<CollectionResult<UpdateResult>>,
+}
+
+impl WriteCoalescer {
+    /// Take the pending batch, if any
+    fn take_pending(&self) -> Option<PendingBatch> {
+        self.state.lock().pending.take()
+    }
+
+    /// Take the pending batch, unless it was already taken by another request
+    fn take(&self, batch_id: u64) -> Option<PendingBatch> {
+        let mut state = self.state.lock();
+        if state.pending.as_ref()?.id == batch_id {
+            state.pending.take()
+        } else {
+            None
+        }
+    }
+
+    /// Take the pending batch, if no request is going to apply it anymore
+    fn take_abandoned(&self, config: Option<WriteCoalescingConfig>) -> Option<PendingBatch> {
+        let mut state = self.state.lock();
+        let batch = state.pending.as_ref()?;
+
+        // The request which started the batch applies it right after the time window,
+        // unless it was cancelled
+        let is_abandoned = match config {
+            Some(config) => batch.created_at.elapsed() > config.window * 2,
+            None => true,
+        };
+
+        if is_abandoned {
+            state.pending.take()
+        } else {
+            None
+        }
+    }
+}
+
+fn upsert_points(points: Vec<PointStructPersisted>) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ))
+}
+
+impl ShardReplicaSet {
+    /// Write coalescing parameters from the collection config, if it is enabled
+    pub(super) async fn write_coalescing_config(&self) -> Option<WriteCoalescingConfig> {
+        let collection_config = self.collection_config.read().await;
+        let wal_config = &collection_config.wal_config;
+
+        let window_ms = wal_config.write_coalescing_ms.filter(|&ms| ms > 0)?;
+        let max_points = wal_config
+            .write_coalescing_max_points
+            .unwrap_or(DEFAULT_WRITE_COALESCING_MAX_POINTS);
+
+        Some(WriteCoalescingConfig {
+            window: Duration::from_millis(window_ms),
+            max_points,
+        })
+    }
+
+    /// Buffer the upsert to apply it together with other upserts into this shard, if it has weak
+    /// ordering. Other operations apply the pending batch first, and are then applied right away.
+    ///
+    /// The request which starts a batch applies it once the time window passes, unless another
+    /// request fills the batch before that. Every request gets the result of the batch, and a
+    /// share of its hardware usage proportional to the number of points it upserted.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe. If the request which started a batch is cancelled, the
+    /// batch is applied by [`Self::flush_abandoned_writes`], or by the next operation.
+    pub(super) async fn update_coalesced(
+        &self,
+        operation: CollectionUpdateOperations,
+        ack: UpdateAck,
+        ordering: WriteOrdering,
+        update_only_existing: bool,
+        config: WriteCoalescingConfig,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<UpdateResult> {
+        let is_coalesced = matches!(ordering, WriteOrdering::Weak)
+            && !update_only_existing
+            && ack != UpdateAck::Replicated;
+
+        let points = match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )) if is_coalesced => points,
+            operation => {
+                let _apply_guard = self.write_coalescer.apply_lock.lock().await;
+                if let Some(batch) = self.write_coalescer.take_pending() {
+                    self.apply_coalesced(batch).await;
+                }
+                return self
+                    .update(operation, ack, update_only_existing, hw_measurement_acc)
+                    .await;
+            }
+        };
+
+        // Reject invalid points before buffering, so they don't fail other requests of the batch
+        let segment_config = self
+            .collection_config
+            .read()
+            .await
+            .to_base_segment_config()?;
+        for point in &points {
+            check_named_vectors(&point.get_vectors(), &segment_config)?;
+        }
+
+        let (result_sender, result_receiver) = oneshot::channel();
+
+        let (batch_id, is_full, started_batch) = {
+            let mut state = self.write_coalescer.state.lock();
+            let state = &mut *state;
+
+            let is_new = state.pending.is_none();
+            let batch = state.pending.get_or_insert_with(|| {
+                state.next_batch_id += 1;
+                PendingBatch {
+                    id: state.next_batch_id,
+                    created_at: Instant::now(),
+                    requests: Vec::new(),
+                    points_count: 0,
+                }
+            });
+
+            batch.points_count += points.len();
+            batch.requests.push(PendingUpsert {
+                points,
+                ack,
+                hw_measurement_acc,
+                result: result_sender,
+            });
+
+            let is_full = batch.points_count >= config.max_points;
+            (batch.id, is_full, is_new)
+        };
+
+        // Batch is applied by the request which fills it, or the one which started it
+        if is_full || started_batch {
+            if !is_full {
+                tokio::time::sleep(config.window).await;
+            }
+
+            let _apply_guard = self.write_coalescer.apply_lock.lock().await;
+            if let Some(batch) = self.write_coalescer.take(batch_id) {
+                self.apply_coalesced(batch).await;
+            }
+        }
+
+        result_receiver.await.map_err(|_| {
+            CollectionError::service_error("Coalesced upsert was dropped before being applied")
+        })?
+    }
+
+    /// Apply the pending batch of coalesced upserts, if the request which started it was
+    /// cancelled, or if write coalescing got disabled in the meantime
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe.
+    pub(crate) async fn flush_abandoned_writes(&self) {
+        let config = self.write_coalescing_config().await;
+        let _apply_guard = self.write_coalescer.apply_lock.lock().await;
+        if let Some(batch) = self.write_coalescer.take_abandoned(config) {
+            log::debug!(
+                "Applying {} abandoned coalesced upserts of shard {}",
+                batch.requests.len(),
+                self.shard_id,
+            );
+            self.apply_coalesced(batch).await;
+        }
+    }
+
+    /// Apply all requests of the batch in a single operation, and send each request its result
+    ///
+    /// Must be called with the `apply_lock` held.
+    ///
+    /// If the operation fails, requests are applied one by one instead, so that the error is only
+    /// reported to the requests which cause it.
+    async fn apply_coalesced(&self, batch: PendingBatch) {
+        let PendingBatch {
+            requests,
+            points_count,
+            ..
+        } = batch;
+
+        // Acknowledge the batch once every request in it can be acknowledged
+        let ack = requests
+            .iter()
+            .map(|request| request.ack)
+            .max()
+            .expect("batch has at least one request");
+        let points = requests
+            .iter()
+            .flat_map(|request| request.points.iter().cloned())
+            .collect();
+
+        // Measure the batch separately, to split its usage among requests
+        let batch_hw_measurement_acc =
+            HwMeasurementAcc::new_with_metrics_drain(HwSharedDrain::default());
+
+        let update_result = self
+            .update(
+                upsert_points(points),
+                ack,
+                false,
+                batch_hw_measurement_acc.clone(),
+            )
+            .await;
+
+        for request in &requests {
+            request.hw_measurement_acc.accumulate_share(
+                &batch_hw_measurement_acc,
+                request.points.len(),
+                points_count,
+            );
+        }
+
+        match update_result {
+            Err(err) if requests.len() > 1 => {
+                log::debug!(
+                    "Failed to apply {} coalesced upserts of shard {} together, applying them one by one: {err}",
+                    requests.len(),
+                    self.shard_id,
+                );
+
+                for request in requests {
+                    let PendingUpsert {
+                        points,
+                        ack,
+                        hw_measurement_acc,
+                        result,
+                    } = request;
+
+                    let update_result = self
+                        .update(upsert_points(points), ack, false, hw_measurement_acc)
+                        .await;
+                    let _ = result.send(update_result);
+                }
+            }
+            update_result => {
+                for request in requests {
+                    // Request may have been cancelled, its points are upserted regardless
+                    let _ = request.result.send(update_result.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use common::budget::ResourceBudget;
+    use common::save_on_disk::SaveOnDisk;
+    use segment::types::Distance;
+    use tempfile::Builder;
+    use tokio::runtime::Handle;
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::config::{CollectionConfigInternal, CollectionParams, WalConfig};
+    use crate::operations::point_ops::VectorStructPersisted;
+    use crate::operations::types::VectorsConfig;
+    use crate::operations::vector_params_builder::VectorParamsBuilder;
+    use crate::optimizers_builder::OptimizersConfig;
+    use crate::shards::replica_set::replica_set_state::ReplicaState;
+
+    const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
+        deleted_threshold: 0.9,
+        vacuum_min_vector_number: 1000,
+        default_segment_number: 2,
+        max_segment_size: None,
+        #[expect(deprecated)]
+        memmap_threshold: None,
+        indexing_threshold: Some(50_000),
+        flush_interval_sec: 30,
+        max_optimization_threads: Some(2),
+    };
+
+    fn upsert_point(id: u64, vector: Vec<f32>) -> CollectionUpdateOperations {
+        upsert_points(vec![PointStructPersisted {
+            id: id.into(),
+            vector: VectorStructPersisted::Single(vector),
+            payload: None,
+        }])
+    }
+
+    async fn build_replica_set(
+        collection_dir: &Path,
+        payload_index_schema_dir: &Path,
+    ) -> ShardReplicaSet {
+        let config = CollectionConfigInternal {
+            params: CollectionParams {
+                vectors: VectorsConfig::Single(VectorParamsBuilder::new(4, Distance::Dot).build()),
+                ..CollectionParams::empty()
+            },
+            optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+            wal_config: WalConfig {
+                write_coalescing_ms: Some(200),
+                write_coalescing_max_points: Some(4),
+                ..WalConfig::default()
+            },
+            hnsw_config: Default::default(),
+            quantization_config: None,
+            strict_mode_config: None,
+            uuid: None,
+            metadata: None,
+        };
+
+        let payload_index_schema = Arc::new(
+            SaveOnDisk::load_or_init_default(payload_index_schema_dir.join("payload-schema.json"))
+                .unwrap(),
+        );
+
+        let replica_set = ShardReplicaSet::build(
+            1,
+            None,
+            "test_collection".to_string(),
+            1,
+            true,
+            HashSet::new(),
+            Arc::new(|_peer_id, _shard_id, _from_state| {}),
+            Arc::new(|_shard_transfer, _reason| {}),
+            collection_dir,
+            Arc::new(RwLock::new(config.clone())),
+            config.optimizer_config.clone(),
+            Default::default(),
+            payload_index_schema,
+            Default::default(),
+            Handle::current(),
+            Handle::current(),
+            ResourceBudget::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        replica_set
+            .set_replica_state(1, ReplicaState::Active)
+            .await
+            .unwrap();
+
+        replica_set
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_coalesced_upserts_share_operation() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+
+        let replica_set =
+            build_replica_set(collection_dir.path(), payload_index_schema_dir.path()).await;
+
+        let upsert = |id: u64| {
+            replica_set.update_with_consistency(
+                upsert_point(id, vec![1.0, 0.0, 1.0, 0.0]),
+                UpdateAck::Applied,
+                WriteOrdering::Weak,
+                false,
+                HwMeasurementAcc::new(),
+            )
+        };
+
+        // Concurrent upserts are applied together after the time window
+        let results = futures::future::join_all((1..=3).map(upsert)).await;
+        let operation_ids: HashSet<_> = results
+            .into_iter()
+            .map(|result| result.unwrap().operation_id)
+            .collect();
+        assert_eq!(operation_ids.len(), 1);
+
+        // Batch is applied right away once it is full
+        let started = Instant::now();
+        let results = futures::future::join_all((4..=7).map(upsert)).await;
+        assert!(started.elapsed() < Duration::from_millis(200));
+        let operation_ids: HashSet<_> = results
+            .into_iter()
+            .map(|result| result.unwrap().operation_id)
+            .collect();
+        assert_eq!(operation_ids.len(), 1);
+
+        let points_count = replica_set
+            .count_local(
+                Arc::new(crate::operations::types::CountRequestInternal {
+                    filter: None,
+                    exact: true,
+                }),
+                None,
+                HwMeasurementAcc::new(),
+            )
+            .await
+            .unwrap()
+            .unwrap()
+            .count;
+        assert_eq!(points_count, 7);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_operation_after_coalesced_upsert_keeps_order() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+
+        let replica_set =
+            build_replica_set(collection_dir.path(), payload_index_schema_dir.path()).await;
+
+        let upsert = replica_set.update_with_consistency(
+            upsert_point(1, vec![1.0, 0.0, 1.0, 0.0]),
+            UpdateAck::Applied,
+            WriteOrdering::Weak,
+            false,
+            HwMeasurementAcc::new(),
+        );
+
+        // Delete the point while its upsert is still buffered
+        let delete = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let started = Instant::now();
+            let result = replica_set
+                .update_with_consistency(
+                    CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                        ids: vec![1.into()],
+                        precondition: None,
+                        deleted_at: None,
+                    }),
+                    UpdateAck::Applied,
+                    WriteOrdering::Weak,
+                    false,
+                    HwMeasurementAcc::new(),
+                )
+                .await;
+            (result, started.elapsed())
+        };
+
+        let (upsert, (delete, delete_elapsed)) = tokio::join!(upsert, delete);
+        let upsert = upsert.unwrap();
+        let delete = delete.unwrap();
+
+        // Delete applies the buffered upsert first, without waiting for the time window
+        assert!(upsert.operation_id < delete.operation_id);
+        assert!(delete_elapsed < Duration::from_millis(150));
+
+        let points_count = replica_set
+            .count_local(
+                Arc::new(crate::operations::types::CountRequestInternal {
+                    filter: None,
+                    exact: true,
+                }),
+                None,
+                HwMeasurementAcc::new(),
+            )
+            .await
+            .unwrap()
+            .unwrap()
+            .count;
+        assert_eq!(points_count, 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_invalid_coalesced_upsert_fails_alone() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+
+        let replica_set =
+            build_replica_set(collection_dir.path(), payload_index_schema_dir.path()).await;
+
+        let upsert = |id: u64, vector: Vec<f32>, hw_measurement_acc: HwMeasurementAcc| {
+            replica_set.update_with_consistency(
+                upsert_point(id, vector),
+                UpdateAck::Applied,
+                WriteOrdering::Weak,
+                false,
+                hw_measurement_acc,
+            )
+        };
+
+        let hw_valid_1 = HwMeasurementAcc::new();
+        let hw_invalid = HwMeasurementAcc::new();
+        let hw_valid_2 = HwMeasurementAcc::new();
+
+        // Vector with a wrong dimension is rejected without failing the rest of the batch
+        let (valid_1, invalid, valid_2) = tokio::join!(
+            upsert(1, vec![1.0, 0.0, 1.0, 0.0], hw_valid_1.clone()),
+            upsert(2, vec![1.0, 0.0, 1.0], hw_invalid.clone()),
+            upsert(3, vec![0.0, 1.0, 0.0, 1.0], hw_valid_2.clone()),
+        );
+
+        assert!(invalid.is_err());
+        assert_eq!(valid_1.unwrap().operation_id, valid_2.unwrap().operation_id,);
+
+        // Usage of the batch is split among the requests in it
+        assert!(hw_valid_1.get_vector_io_write() > 0);
+        assert_eq!(
+            hw_valid_1.get_vector_io_write(),
+            hw_valid_2.get_vector_io_write(),
+        );
+        assert_eq!(hw_invalid.get_vector_io_write(), 0);
+
+        let points_count = replica_set
+            .count_local(
+                Arc::new(crate::operations::types::CountRequestInternal {
+                    filter: None,
+                    exact: true,
+                }),
+                None,
+                HwMeasurementAcc::new(),
+            )
+            .await
+            .unwrap()
+            .unwrap()
+            .count;
+        assert_eq!(points_count, 2);
+    }
+}
@@ -10,16 +10,19 @@ use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
 use segment::data_types::manifest::SnapshotManifest;
 use segment::index::field_index::CardinalityEstimation;
-use segment::types::{Filter, SizeStats, SnapshotFormat};
+use segment::types::{Filter, PointIdType, SizeStats, SnapshotFormat};
 
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
 use crate::collection_manager::optimizers::TrackerLog;
 use crate::operations::operation_effect::{EstimateOperationEffectArea, OperationEffectArea};
 use crate::operations::types::{CollectionError, CollectionResult, OptimizersStatus};
+use crate::operations::universal_query::query_plan::StagePlan;
+use crate::operations::universal_query::shard_query::ShardQueryRequest;
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::local_shard::point_history::PointVersion;
 use crate::shards::proxy_shard::ProxyShard;
 use crate::shards::queue_proxy_shard::QueueProxyShard;
 use crate::shards::shard_trait::ShardOperation;
@@ -398,6 +401,38 @@ impl Shard {
         }
     }
 
+    pub async fn query_plan(
+        &self,
+        request: ShardQueryRequest,
+        hw_measurement_acc: &HwMeasurementAcc,
+    ) -> CollectionResult<Vec<StagePlan>> {
+        match self {
+            Shard::Local(local_shard) => local_shard.query_plan(request, hw_measurement_acc).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.query_plan(request, hw_measurement_acc).await,
+            Shard::ForwardProxy(forward_proxy_shard) => {
+                forward_proxy_shard
+                    .query_plan(request, hw_measurement_acc)
+                    .await
+            }
+            Shard::QueueProxy(queue_proxy_shard) => {
+                queue_proxy_shard
+                    .query_plan(request, hw_measurement_acc)
+                    .await
+            }
+            Shard::Dummy(dummy_shard) => dummy_shard.query_plan(request),
+        }
+    }
+
+    pub fn point_history(&self, point_id: PointIdType) -> CollectionResult<Vec<PointVersion>> {
+        match self {
+            Shard::Local(local_shard) => local_shard.point_history(point_id),
+            Shard::Proxy(proxy_shard) => proxy_shard.point_history(point_id),
+            Shard::ForwardProxy(forward_proxy_shard) => forward_proxy_shard.point_history(point_id),
+            Shard::QueueProxy(queue_proxy_shard) => queue_proxy_shard.point_history(point_id),
+            Shard::Dummy(dummy_shard) => dummy_shard.point_history(point_id),
+        }
+    }
+
     pub async fn estimate_request_cardinality(
         &self,
         operation: &impl EstimateOperationEffectArea,
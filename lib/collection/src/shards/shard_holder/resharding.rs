@@ -556,6 +556,8 @@ pub struct OperationsByMode {
 
 impl OperationsByMode {
     pub fn with_update_only_existing(mut self, operation: CollectionUpdateOperations) -> Self {
+        // Preconditions are checked by the shard the operation is applied to first,
+        // points might not be present in the target shard yet
         self.update_only_existing = match operation {
             CollectionUpdateOperations::PointOperation(point_operation) => match point_operation {
                 PointOperations::UpsertPoints(operation) => operation.into_update_only(None),
@@ -563,13 +565,22 @@ impl OperationsByMode {
                     let ConditionalInsertOperationInternal {
                         points_op,
                         condition,
+                        precondition: _,
                     } = operation;
                     points_op.into_update_only(Some(condition))
                 }
 
-                PointOperations::DeletePoints { ids } => {
+                PointOperations::DeletePoints {
+                    ids,
+                    precondition: _,
+                    deleted_at,
+                } => {
                     vec![CollectionUpdateOperations::PointOperation(
-                        PointOperations::DeletePoints { ids },
+                        PointOperations::DeletePoints {
+                            ids,
+                            precondition: None,
+                            deleted_at,
+                        },
                     )]
                 }
                 PointOperations::DeletePointsByFilter(op) => {
@@ -582,6 +593,11 @@ impl OperationsByMode {
                         PointOperations::SyncPoints(op),
                     )]
                 }
+                PointOperations::RestorePoints { ids } => {
+                    vec![CollectionUpdateOperations::PointOperation(
+                        PointOperations::RestorePoints { ids },
+                    )]
+                }
                 #[cfg(feature = "staging")]
                 PointOperations::TestDelay(op) => {
                     vec![CollectionUpdateOperations::PointOperation(
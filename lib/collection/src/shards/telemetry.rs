@@ -23,6 +23,8 @@ pub struct ReplicaSetTelemetry {
     pub replicate_states: HashMap<PeerId, ReplicaState>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub partial_snapshot: Option<PartialSnapshotTelemetry>,
+    /// Total number of expired points deleted by this peer since the last start.
+    pub total_expired_points: usize,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
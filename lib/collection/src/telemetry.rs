@@ -84,6 +84,15 @@ impl CollectionTelemetry {
             .count()
     }
 
+    /// Amount of expired points deleted by this peer since the last start.
+    pub fn count_expired_points(&self) -> usize {
+        self.shards
+            .iter()
+            .flatten()
+            .map(|shard| shard.total_expired_points)
+            .sum()
+    }
+
     pub fn count_points(&self) -> usize {
         self.shards
             .iter()
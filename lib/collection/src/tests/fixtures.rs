@@ -30,6 +30,10 @@ pub fn create_collection_config_with_dim(dim: usize) -> CollectionConfigInternal
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        point_history_versions: None,
+        soft_delete_retention_sec: None,
+        write_coalescing_ms: None,
+        write_coalescing_max_points: None,
     };
 
     let collection_params = CollectionParams {
@@ -114,6 +118,8 @@ pub fn create_payload_index_operation() -> CollectionUpdateOperations {
 pub fn delete_point_operation(idx: u64) -> CollectionUpdateOperations {
     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
         ids: vec![idx.into()],
+        precondition: None,
+        deleted_at: None,
     })
 }
 
@@ -15,8 +15,8 @@ use crate::collection::{Collection, RequestShardTransfer};
 use crate::config::{CollectionConfigInternal, CollectionParams, WalConfig};
 use crate::operations::CollectionUpdateOperations;
 use crate::operations::point_ops::{
-    PointInsertOperationsInternal, PointOperations, PointStructPersisted, VectorStructPersisted,
-    WriteOrdering,
+    PointInsertOperationsInternal, PointOperations, PointStructPersisted, UpdateAck,
+    VectorStructPersisted, WriteOrdering,
 };
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::shared_storage_config::SharedStorageConfig;
@@ -43,6 +43,10 @@ async fn fixture() -> Collection {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        point_history_versions: None,
+        soft_delete_retention_sec: None,
+        write_coalescing_ms: None,
+        write_coalescing_max_points: None,
     };
 
     let collection_params = CollectionParams {
@@ -121,7 +125,7 @@ async fn fixture() -> Collection {
     collection
         .update_from_client(
             operation,
-            true,
+            UpdateAck::Applied,
             WriteOrdering::Weak,
             None,
             HwMeasurementAcc::disposable(),
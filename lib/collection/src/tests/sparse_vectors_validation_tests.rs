@@ -70,6 +70,9 @@ fn validate_error_sparse_vector_points_list() {
         points: vec![wrong_point_struct()],
         shard_key: None,
         update_filter: None,
+        if_version: None,
+        if_payload_matches: None,
+        if_not_exists: None,
     });
 }
 
@@ -101,7 +104,9 @@ fn validate_error_sparse_vector_search_groups_request_internal() {
             group_size: 5,
             limit: 5,
             with_lookup: None,
+            aggregations: None,
         },
+        with_explanation: None,
     });
 }
 
@@ -116,6 +121,7 @@ fn validate_error_sparse_vector_recommend_request_internal() {
         positive: vec![wrong_recommend_example()],
         negative: vec![wrong_recommend_example()],
         strategy: None,
+        rocchio: None,
         filter: None,
         params: None,
         limit: 5,
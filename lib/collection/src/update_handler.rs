@@ -23,6 +23,7 @@ use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::CollectionResult;
 use crate::shards::CollectionId;
 use crate::shards::local_shard::LocalShardClocks;
+use crate::shards::local_shard::point_history::PointHistory;
 use crate::shards::update_tracker::UpdateTracker;
 use crate::update_workers::UpdateWorkers;
 use crate::wal_delta::LockedWal;
@@ -121,6 +122,9 @@ pub struct UpdateHandler {
     scroll_read_lock: Arc<tokio::sync::RwLock<()>>,
 
     update_tracker: UpdateTracker,
+
+    /// Previous versions of points, if enabled
+    point_history: Option<Arc<PointHistory>>,
 }
 
 impl UpdateHandler {
@@ -142,6 +146,7 @@ impl UpdateHandler {
         shard_path: PathBuf,
         scroll_read_lock: Arc<tokio::sync::RwLock<()>>,
         update_tracker: UpdateTracker,
+        point_history: Option<Arc<PointHistory>>,
     ) -> UpdateHandler {
         UpdateHandler {
             collection_name,
@@ -167,6 +172,7 @@ impl UpdateHandler {
             has_triggered_optimizers: Default::default(),
             scroll_read_lock,
             update_tracker,
+            point_history,
         }
     }
 
@@ -197,6 +203,7 @@ impl UpdateHandler {
         let scroll_read_lock = self.scroll_read_lock.clone();
         let update_tracker = self.update_tracker.clone();
         let collection_name = self.collection_name.clone();
+        let point_history = self.point_history.clone();
         self.update_worker = Some(self.runtime_handle.spawn(UpdateWorkers::update_worker_fn(
             collection_name,
             update_receiver,
@@ -205,6 +212,7 @@ impl UpdateHandler {
             segments,
             scroll_read_lock,
             update_tracker,
+            point_history,
         )));
 
         let segments = self.segments.clone();
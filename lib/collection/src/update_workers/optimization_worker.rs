@@ -3,8 +3,9 @@ use std::panic::AssertUnwindSafe;
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use chrono::Utc;
 use common::budget::ResourceBudget;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::panic;
@@ -38,6 +39,9 @@ use crate::wal_delta::LockedWal;
 /// The longer the duration, the longer it takes for panicked tasks to be reported.
 const OPTIMIZER_CLEANUP_INTERVAL: Duration = Duration::from_secs(5);
 
+/// Minimal interval between two purges of soft deleted points past their retention window
+const TOMBSTONES_PURGE_INTERVAL: Duration = Duration::from_secs(10);
+
 impl UpdateWorkers {
     #[allow(clippy::too_many_arguments)]
     pub async fn optimization_worker_fn(
@@ -65,12 +69,37 @@ impl UpdateWorkers {
         // Asynchronous task to trigger optimizers once CPU budget is available again
         let mut resource_available_trigger: Option<JoinHandle<()>> = None;
 
+        let mut last_tombstones_purge = Instant::now();
+
         loop {
             let result = timeout(OPTIMIZER_CLEANUP_INTERVAL, receiver.recv()).await;
 
             let cleaned_any =
                 Self::cleanup_optimization_handles(optimization_handles.clone()).await;
 
+            let is_stopping = matches!(result, Ok(None | Some(OptimizerSignal::Stop)));
+            if !is_stopping && last_tombstones_purge.elapsed() >= TOMBSTONES_PURGE_INTERVAL {
+                last_tombstones_purge = Instant::now();
+
+                // Soft deleted points can't be restored after the retention window.
+                // Also purges all of them if soft delete got disabled.
+                let segments = segments.clone();
+                let purged = task::spawn_blocking(move || {
+                    let segments = segments.read();
+                    let retention = segments.soft_delete_retention.unwrap_or_default();
+                    let deleted_before = Utc::now() - retention;
+                    segments.purge_tombstones(deleted_before)
+                })
+                .await;
+
+                match purged {
+                    Ok(Ok(0)) => {}
+                    Ok(Ok(purged)) => log::debug!("Purged {purged} soft deleted points"),
+                    Ok(Err(err)) => log::error!("Failed to purge soft deleted points: {err}"),
+                    Err(err) => log::error!("Failed to purge soft deleted points: {err}"),
+                }
+            }
+
             // Either continue below here with the worker, or reloop/break
             // Decision logic doing one of three things:
             // 1. run optimizers
@@ -12,6 +12,7 @@ use crate::operations::generalizer::Generalizer;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::profiling::interface::log_request_to_collector;
 use crate::shards::CollectionId;
+use crate::shards::local_shard::point_history::PointHistory;
 use crate::shards::update_tracker::UpdateTracker;
 use crate::update_handler::{OperationData, OptimizerSignal, UpdateSignal};
 use crate::update_workers::UpdateWorkers;
@@ -27,6 +28,7 @@ impl UpdateWorkers {
         segments: LockedSegmentHolder,
         update_operation_lock: Arc<tokio::sync::RwLock<()>>,
         update_tracker: UpdateTracker,
+        point_history: Option<Arc<PointHistory>>,
     ) {
         while let Some(signal) = receiver.recv().await {
             match signal {
@@ -42,6 +44,7 @@ impl UpdateWorkers {
                     let segments_clone = segments.clone();
                     let update_operation_lock_clone = update_operation_lock.clone();
                     let update_tracker_clone = update_tracker.clone();
+                    let point_history_clone = point_history.clone();
 
                     let operation_result = tokio::task::spawn_blocking(move || {
                         Self::update_worker_internal(
@@ -53,6 +56,7 @@ impl UpdateWorkers {
                             segments_clone,
                             update_operation_lock_clone,
                             update_tracker_clone,
+                            point_history_clone,
                             hw_measurements,
                         )
                     })
@@ -113,6 +117,7 @@ impl UpdateWorkers {
         segments: LockedSegmentHolder,
         update_operation_lock: Arc<tokio::sync::RwLock<()>>,
         update_tracker: UpdateTracker,
+        point_history: Option<Arc<PointHistory>>,
         hw_measurements: HwMeasurementAcc,
     ) -> CollectionResult<usize> {
         // If wait flag is set, explicitly flush WAL first
@@ -124,6 +129,16 @@ impl UpdateWorkers {
             })?;
         }
 
+        // Versions of the points, which might be replaced by the operation
+        let previous_versions = point_history.as_ref().and_then(|point_history| {
+            PointHistory::read_previous(&segments, &operation)
+                .inspect_err(|err| {
+                    log::warn!("Can't read point history before operation {op_num}: {err}");
+                })
+                .ok()
+                .map(|previous| (point_history, previous))
+        });
+
         let start_time = Instant::now();
 
         // This represents the operation without vectors and payloads for logging purposes
@@ -143,6 +158,18 @@ impl UpdateWorkers {
 
         log_request_to_collector(&collection_name, duration, move || loggable_operation);
 
+        // History is best effort, the operation is already applied at this point
+        if result.is_ok()
+            && let Some((point_history, previous)) = previous_versions
+        {
+            let recorded = point_history
+                .record(&segments, previous, op_num)
+                .and_then(|()| if wait { point_history.flush() } else { Ok(()) });
+            if let Err(err) = recorded {
+                log::warn!("Can't record point history of operation {op_num}: {err}");
+            }
+        }
+
         result
     }
 }
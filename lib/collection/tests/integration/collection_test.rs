@@ -3,6 +3,7 @@ use std::io::{BufReader, BufWriter};
 
 use ahash::AHashSet;
 use api::rest::{OrderByInterface, SearchRequestInternal};
+use collection::collection::expiration::EXPIRES_AT_KEY;
 use collection::operations::CollectionUpdateOperations;
 use collection::operations::payload_ops::{PayloadOps, SetPayloadOp};
 use collection::operations::point_ops::{
@@ -19,7 +20,7 @@ use collection::shards::replica_set::replica_set_state::{ReplicaSetState, Replic
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use fs_err::File;
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::{Direction, OrderBy, ThenBy};
 use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{
     Condition, ExtendedPointId, FieldCondition, Filter, HasIdCondition, Payload,
@@ -249,6 +250,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
                 points: Some(vec![2.into(), 3.into()]),
                 filter: None,
                 key: None,
+                precondition: None,
             }));
 
         let hw_counter = HwMeasurementAcc::new();
@@ -600,6 +602,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        then_by: vec![],
                     })),
                 },
                 None,
@@ -632,6 +635,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        then_by: vec![],
                     })),
                 },
                 None,
@@ -673,6 +677,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        then_by: vec![],
                     })),
                 },
                 None,
@@ -713,6 +718,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        then_by: vec![],
                     })),
                 },
                 None,
@@ -777,6 +783,116 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_compound_ordered_scroll_api() {
+    test_compound_ordered_scroll_api_with_shards(1).await;
+    test_compound_ordered_scroll_api_with_shards(N_SHARDS).await;
+}
+
+async fn test_compound_ordered_scroll_api_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    const CATEGORY_KEY: &str = "category";
+    const RATING_KEY: &str = "rating";
+
+    let get_payload = |category: i64, rating: Option<f64>| -> Option<Payload> {
+        let mut payload_map = Map::new();
+        payload_map.insert(CATEGORY_KEY.to_string(), category.into());
+        if let Some(rating) = rating {
+            payload_map.insert(RATING_KEY.to_string(), rating.into());
+        }
+        Some(Payload(payload_map))
+    };
+
+    let payloads = vec![
+        get_payload(1, Some(4.5)),
+        get_payload(2, None),
+        get_payload(1, None),
+        get_payload(1, Some(3.0)),
+        get_payload(2, Some(1.0)),
+        get_payload(1, Some(3.0)),
+    ];
+
+    let batch = BatchPersisted {
+        ids: (1..=6).map(|x: u64| x.into()).collect_vec(),
+        vectors: BatchVectorStructPersisted::Single(vec![vec![1.0, 0.0, 0.0, 0.0]; 6]),
+        payloads: Some(payloads),
+    };
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::from(batch),
+    ));
+
+    let hw_counter = HwMeasurementAcc::new();
+    collection
+        .update_from_client_simple(
+            insert_points,
+            true,
+            WriteOrdering::default(),
+            hw_counter.clone(),
+        )
+        .await
+        .unwrap();
+
+    for (key, schema) in [
+        (CATEGORY_KEY, PayloadSchemaType::Integer),
+        (RATING_KEY, PayloadSchemaType::Float),
+    ] {
+        collection
+            .create_payload_index_with_wait(
+                key.parse().unwrap(),
+                PayloadFieldSchema::FieldType(schema),
+                true,
+                hw_counter.clone(),
+            )
+            .await
+            .unwrap();
+    }
+
+    let scroll_ids = |limit: usize| {
+        let collection = &collection;
+        async move {
+            collection
+                .scroll_by(
+                    ScrollRequestInternal {
+                        offset: None,
+                        limit: Some(limit),
+                        filter: None,
+                        with_payload: Some(WithPayloadInterface::Bool(false)),
+                        with_vector: false.into(),
+                        order_by: Some(OrderByInterface::Struct(OrderBy {
+                            key: CATEGORY_KEY.parse().unwrap(),
+                            direction: Some(Direction::Desc),
+                            start_from: None,
+                            then_by: vec![ThenBy {
+                                key: RATING_KEY.parse().unwrap(),
+                                direction: Some(Direction::Asc),
+                            }],
+                        })),
+                    },
+                    None,
+                    &ShardSelectorInternal::All,
+                    None,
+                    HwMeasurementAcc::new(),
+                )
+                .await
+                .unwrap()
+                .points
+                .into_iter()
+                .map(|point| point.id)
+                .collect_vec()
+        }
+    };
+
+    // Ties on the category are ordered by rating, points without rating last, then by id
+    let expected: Vec<PointIdType> = [5, 2, 6, 4, 1, 3].map(PointIdType::from).to_vec();
+    assert_eq!(scroll_ids(10).await, expected);
+
+    // The page ends within a tie on the category, still ordered by rating
+    assert_eq!(scroll_ids(3).await, expected[..3]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_delete_points_by_filter() {
     test_collection_delete_points_by_filter_with_shards(1).await;
@@ -830,7 +946,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
         segment::types::Filter::new_must(Condition::HasId(HasIdCondition::from(to_be_deleted)));
 
     let delete_points = CollectionUpdateOperations::PointOperation(
-        PointOperations::DeletePointsByFilter(delete_filter),
+        PointOperations::DeletePointsByFilter(delete_filter.into()),
     );
 
     let delete_result = collection
@@ -913,3 +1029,80 @@ async fn test_collection_local_load_initializing_not_stuck() {
         }
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_expire_points() {
+    test_expire_points_with_shards(1).await;
+    test_expire_points_with_shards(N_SHARDS).await;
+}
+
+async fn test_expire_points_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let expires_at = |datetime: &str| -> Option<Payload> {
+        let mut payload_map = Map::new();
+        payload_map.insert(EXPIRES_AT_KEY.to_string(), datetime.into());
+        Some(Payload(payload_map))
+    };
+
+    let batch = BatchPersisted {
+        ids: vec![0.into(), 1.into(), 2.into()],
+        vectors: BatchVectorStructPersisted::Single(vec![
+            vec![1.0, 0.0, 1.0, 1.0],
+            vec![1.0, 0.0, 1.0, 0.0],
+            vec![1.0, 1.0, 1.0, 1.0],
+        ]),
+        payloads: Some(vec![
+            expires_at("2000-01-01T00:00:00Z"),
+            expires_at("2999-01-01T00:00:00Z"),
+            None,
+        ]),
+    };
+
+    let hw_counter = HwMeasurementAcc::new();
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::from(batch),
+            )),
+            true,
+            WriteOrdering::default(),
+            hw_counter.clone(),
+        )
+        .await
+        .unwrap();
+
+    let count_points = || {
+        collection.count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+            HwMeasurementAcc::new(),
+        )
+    };
+
+    // Expiration is not enabled without an index
+    assert_eq!(collection.expire_points().await, 0);
+    assert_eq!(count_points().await.unwrap().count, 3);
+
+    collection
+        .create_payload_index_with_wait(
+            EXPIRES_AT_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Datetime),
+            true,
+            hw_counter,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(collection.expire_points().await, 1);
+    assert_eq!(count_points().await.unwrap().count, 2);
+
+    // Nothing left to expire
+    assert_eq!(collection.expire_points().await, 0);
+}
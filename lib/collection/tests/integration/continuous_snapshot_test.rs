@@ -114,6 +114,8 @@ async fn test_continuous_snapshot() {
                 let delete_points =
                     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
                         ids: (0..points_count).map(|i| i.into()).collect(),
+                        precondition: None,
+                        deleted_at: None,
                     });
                 let hw_counter = HwMeasurementAcc::disposable();
                 collection
@@ -179,6 +181,7 @@ async fn test_continuous_snapshot() {
                             points: Some(vec![i.into()]),
                             filter: None,
                             key: None,
+                            precondition: None,
                         }),
                     );
                     let hw_counter = HwMeasurementAcc::disposable();
@@ -29,6 +29,7 @@ async fn distance_matrix_empty() {
         limit_per_sample,
         filter: None,
         using: DEFAULT_VECTOR_NAME.to_owned(),
+        with_explanation: false,
     };
     let matrix = collection
         .search_points_matrix(request, ShardSelectorInternal::All, None, None, hw_acc)
@@ -80,6 +81,7 @@ async fn distance_matrix_anonymous_vector() {
         limit_per_sample,
         filter: None,
         using: DEFAULT_VECTOR_NAME.to_owned(),
+        with_explanation: false,
     };
     let matrix = collection
         .search_points_matrix(request, ShardSelectorInternal::All, None, None, hw_acc)
@@ -106,3 +108,67 @@ async fn distance_matrix_anonymous_vector() {
         });
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn distance_matrix_with_explanation() {
+    let collection_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let point_count = 500;
+    let ids = (0..point_count).map_into().collect();
+    let mut rng = SmallRng::seed_from_u64(SEED);
+
+    let vectors = (0..point_count)
+        .map(|_| rng.random::<[f32; 4]>().to_vec())
+        .collect_vec();
+
+    let batch = BatchPersisted {
+        ids,
+        vectors: BatchVectorStructPersisted::Single(vectors),
+        payloads: None,
+    };
+
+    let upsert_points = collection::operations::CollectionUpdateOperations::PointOperation(
+        collection::operations::point_ops::PointOperations::UpsertPoints(
+            collection::operations::point_ops::PointInsertOperationsInternal::from(batch),
+        ),
+    );
+
+    let hw_counter = HwMeasurementAcc::new();
+    collection
+        .update_from_client_simple(upsert_points, true, WriteOrdering::default(), hw_counter)
+        .await
+        .unwrap();
+
+    let hw_acc = HwMeasurementAcc::new();
+    let sample_size = 100;
+    let limit_per_sample = 10;
+    let request = CollectionSearchMatrixRequest {
+        sample_size,
+        limit_per_sample,
+        filter: None,
+        using: DEFAULT_VECTOR_NAME.to_owned(),
+        with_explanation: true,
+    };
+    let matrix = collection
+        .search_points_matrix(request, ShardSelectorInternal::All, None, None, hw_acc)
+        .await
+        .unwrap();
+
+    // only the highest-scoring cells are explained
+    let explained = matrix
+        .nearests
+        .iter()
+        .flatten()
+        .filter_map(|point| Some((point.score, point.score_explanation.as_ref()?)))
+        .collect_vec();
+    assert_eq!(
+        explained.len(),
+        CollectionSearchMatrixRequest::MAX_EXPLAINED_CELLS
+    );
+    for (score, explanation) in explained {
+        assert!(!explanation.top_dimensions.is_empty());
+        assert!((explanation.total_score - score).abs() < 1e-4);
+    }
+}
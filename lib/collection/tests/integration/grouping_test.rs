@@ -143,6 +143,7 @@ mod group_by {
         let request = GroupRequest::with_limit_from_request(
             SourceRequest::Recommend(RecommendRequestInternal {
                 strategy: Default::default(),
+                rocchio: None,
                 filter: None,
                 params: None,
                 limit: 4,
@@ -39,6 +39,10 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        point_history_versions: None,
+        soft_delete_retention_sec: None,
+        write_coalescing_ms: None,
+        write_coalescing_max_points: None,
     };
 
     let vector_params1 = VectorParamsBuilder::new(4, Distance::Dot).build();
@@ -47,6 +47,28 @@ impl HwSharedDrain {
         self.vector_io_read_counter.load(Ordering::Relaxed)
     }
 
+    fn hw_data(&self) -> HardwareData {
+        let HwSharedDrain {
+            cpu_counter,
+            payload_io_read_counter,
+            payload_io_write_counter,
+            payload_index_io_read_counter,
+            payload_index_io_write_counter,
+            vector_io_read_counter,
+            vector_io_write_counter,
+        } = self;
+
+        HardwareData {
+            cpu: cpu_counter.load(Ordering::Relaxed),
+            payload_io_read: payload_io_read_counter.load(Ordering::Relaxed),
+            payload_io_write: payload_io_write_counter.load(Ordering::Relaxed),
+            vector_io_read: vector_io_read_counter.load(Ordering::Relaxed),
+            vector_io_write: vector_io_write_counter.load(Ordering::Relaxed),
+            payload_index_io_read: payload_index_io_read_counter.load(Ordering::Relaxed),
+            payload_index_io_write: payload_index_io_write_counter.load(Ordering::Relaxed),
+        }
+    }
+
     /// Accumulates all values from `src` into this HwSharedDrain.
     fn accumulate_from_hw_data(&self, src: HardwareData) {
         let HwSharedDrain {
@@ -187,25 +209,19 @@ impl HwMeasurementAcc {
     }
 
     pub fn hw_data(&self) -> HardwareData {
-        let HwSharedDrain {
-            cpu_counter,
-            payload_io_read_counter,
-            payload_io_write_counter,
-            payload_index_io_read_counter,
-            payload_index_io_write_counter,
-            vector_io_read_counter,
-            vector_io_write_counter,
-        } = &self.request_drain;
-
-        HardwareData {
-            cpu: cpu_counter.load(Ordering::Relaxed),
-            payload_io_read: payload_io_read_counter.load(Ordering::Relaxed),
-            payload_io_write: payload_io_write_counter.load(Ordering::Relaxed),
-            vector_io_read: vector_io_read_counter.load(Ordering::Relaxed),
-            vector_io_write: vector_io_write_counter.load(Ordering::Relaxed),
-            payload_index_io_read: payload_index_io_read_counter.load(Ordering::Relaxed),
-            payload_index_io_write: payload_index_io_write_counter.load(Ordering::Relaxed),
-        }
+        self.request_drain.hw_data()
+    }
+
+    /// Accumulate a share of the measurements of `other`, `part` out of `total`
+    ///
+    /// This is useful if `other` measured a single operation made on behalf of multiple requests.
+    /// Usage which `other` accumulated for its request only, is accumulated for the request of
+    /// this accumulator only as well.
+    pub fn accumulate_share(&self, other: &HwMeasurementAcc, part: usize, total: usize) {
+        let request_share = other.request_drain.hw_data().share(part, total);
+        let metrics_share = other.metrics_drain.hw_data().share(part, total);
+        self.request_drain.accumulate_from_hw_data(request_share);
+        self.metrics_drain.accumulate_from_hw_data(metrics_share);
     }
 }
 
@@ -12,6 +12,28 @@ pub struct HardwareData {
     pub payload_index_io_write: usize,
 }
 
+impl HardwareData {
+    /// Proportional part of these measurements, `part` out of `total`
+    pub fn share(self, part: usize, total: usize) -> Self {
+        let share = |value: usize| {
+            if total == 0 {
+                return 0;
+            }
+            (value as u128 * part as u128 / total as u128) as usize
+        };
+
+        Self {
+            cpu: share(self.cpu),
+            payload_io_read: share(self.payload_io_read),
+            payload_io_write: share(self.payload_io_write),
+            vector_io_read: share(self.vector_io_read),
+            vector_io_write: share(self.vector_io_write),
+            payload_index_io_read: share(self.payload_index_io_read),
+            payload_index_io_write: share(self.payload_index_io_write),
+        }
+    }
+}
+
 impl Add for HardwareData {
     type Output = HardwareData;
 
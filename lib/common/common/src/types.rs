@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use ordered_float::OrderedFloat;
 use strum::EnumIter;
@@ -35,24 +37,481 @@ impl PartialOrd for ScoredPointOffset {
 pub struct DimensionContribution {
     pub dimension: usize,
     pub contribution: ScoreType,
+    /// Human-readable name of the dimension, e.g. the vocabulary term of a sparse index
+    pub label: Option<String>,
+}
+
+/// Kind of an example of a recommend query
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExampleKind {
+    Positive,
+    Negative,
+}
+
+/// Example of a recommend query which weighed the most on a dimension of the effective query vector
+#[derive(Clone, Debug, PartialEq)]
+pub struct DominantExample {
+    pub dimension: usize,
+    pub kind: ExampleKind,
+    /// Position of the example among the examples of its kind
+    pub index: usize,
+    /// Value the example adds to this dimension of the effective query vector
+    pub contribution: ScoreType,
+}
+
+/// Method a fusion query combined the results of its prefetches with
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FusionMethod {
+    /// Reciprocal rank fusion, with its `k` parameter
+    Rrf { k: usize },
+    /// Distribution-based score fusion
+    Dbsf,
+}
+
+/// Part of the fused score of a point coming from one prefetch of a fusion query
+#[derive(Clone, Debug, PartialEq)]
+pub struct FusionSource {
+    /// Position of the point in the results of the prefetch, `None` if it wasn't part of them
+    pub rank: Option<usize>,
+    /// Score of the point in the results of the prefetch
+    pub score: Option<ScoreType>,
+    /// Value the prefetch adds to the fused score
+    pub contribution: ScoreType,
+}
+
+/// How a fusion query combined the results of its prefetches into the score of a point
+#[derive(Clone, Debug, PartialEq)]
+pub struct FusionExplanation {
+    pub method: FusionMethod,
+    /// One source per prefetch of the query, in the order of the prefetches
+    pub sources: Vec<FusionSource>,
+}
+
+/// Space in which the contributions of an explanation are expressed
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ExplanationSpace {
+    /// Vectors as they were inserted
+    #[default]
+    Original,
+    /// Vectors as they are stored, after ingest preprocessing (e.g. L2 normalization for cosine)
+    Processed,
+}
+
+/// Storage the explained vector of a point was read from
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum VectorSource {
+    /// Original vector storage
+    #[default]
+    Original,
+    /// Dequantized from the quantized storage, contributions are approximate
+    Quantized,
+}
+
+/// Why no explanation could be computed for a point
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UnavailableReason {
+    /// Points are ordered by a payload field, not by a vector score
+    OrderByQuery,
+    /// The kind of query doesn't support explanations
+    UnsupportedQuery,
+    /// The vector of the point couldn't be retrieved
+    VectorMissing,
+    /// Explanations were skipped to finish the request within its timeout
+    Timeout,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ExplanationStatus {
+    #[default]
+    Available,
+    Unavailable(UnavailableReason),
+}
+
+/// Order of dimensions with equal-magnitude contributions in a [`ScoreExplanation`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Lower dimension index first
+    #[default]
+    Ascending,
+    /// Higher dimension index first
+    Descending,
+}
+
+/// Exact vectors an explanation was computed from,
+/// so that it can be reproduced offline
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExplanationBundle {
+    pub query: Vec<ScoreType>,
+    /// Vector of the point, as stored in the collection
+    pub stored: Vec<ScoreType>,
+}
+
+/// Amount of detail returned for a [`ScoreExplanation`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ExplanationVerbosity {
+    /// Contributions of the top dimensions, and how they were computed
+    #[default]
+    Full,
+    /// Only the total score and the indices of the top dimensions, for bandwidth-constrained clients
+    Minimal,
+}
+
+/// Upper bound on the number of dimensions kept in a [`ScoreExplanation`],
+/// to avoid huge responses on high-dimensional vectors
+pub const MAX_EXPLANATION_TOP_N: usize = 1024;
+
+/// What the contribution of a dimension in a [`ScoreExplanation`] stands for
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ExplanationMode {
+    /// Share of the score computed from the dimension
+    #[default]
+    Contribution,
+    /// Drop of the score if the dimension was removed from both vectors
+    Counterfactual,
+}
+
+/// How the explanations of a request are built
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ExplanationParams {
+    /// Number of top contributing dimensions to keep, the default one if not set
+    pub top_n: Option<usize>,
+    /// Keep the contributions of all dimensions, up to [`MAX_EXPLANATION_TOP_N`]
+    pub include_all: bool,
+    pub mode: ExplanationMode,
+    /// Also report the [`ScoreExplanation::quantization_error`] of points found by a quantized search
+    pub compare_quantized: bool,
+}
+
+impl ExplanationParams {
+    /// Number of dimensions to keep, never more than [`MAX_EXPLANATION_TOP_N`]
+    pub fn top_n(&self, default: usize) -> usize {
+        if self.include_all {
+            return MAX_EXPLANATION_TOP_N;
+        }
+        self.top_n.unwrap_or(default).min(MAX_EXPLANATION_TOP_N)
+    }
+}
+
+/// Whether larger or smaller scores mean more similar vectors
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Order {
+    LargeBetter,
+    SmallBetter,
 }
 
 /// Explanation of how a similarity score was computed
 #[derive(Clone, Debug, Default)]
 pub struct ScoreExplanation {
     pub top_dimensions: Vec<DimensionContribution>,
+    /// Score reconstructed from the contributions of all dimensions,
+    /// including the ones not kept in `top_dimensions`
+    pub total_score: ScoreType,
+    /// Whether the segment of the explained point searched it approximately, e.g. with an HNSW graph,
+    /// rather than by scanning all of its vectors
+    pub approximate_search: bool,
+    /// Space of the vectors the contributions were computed on
+    pub space: ExplanationSpace,
+    /// Storage the vector of the point was read from
+    pub vector_source: VectorSource,
+    pub status: ExplanationStatus,
+    /// Vectors used to compute the contributions, only set if requested
+    pub bundle: Option<ExplanationBundle>,
+    /// Amount of detail to return to the client
+    pub verbosity: ExplanationVerbosity,
+    /// L2 norm of the query vector, to tell direction from magnitude differences
+    pub query_norm: Option<ScoreType>,
+    /// L2 norm of the stored vector
+    pub stored_norm: Option<ScoreType>,
+    /// Explanation of the stored vector against the collection centroid,
+    /// showing what makes the point special rather than typical
+    pub baseline_explanation: Option<Box<ScoreExplanation>>,
+    /// Name of the vector the contributions were computed on, for collections with named vectors
+    pub vector_name: Option<String>,
+    /// For recommend queries, the example which weighed the most on each of the top dimensions
+    pub dominant_examples: Vec<DominantExample>,
+    /// Contribution of each top dimension minus its contribution with the quantized vector of the point,
+    /// only set if requested and the point was found by a quantized search
+    pub quantization_error: Vec<DimensionContribution>,
+    /// For fusion queries, the contribution of each prefetch to the fused score,
+    /// in place of the contributions of dimensions
+    pub fusion: Option<FusionExplanation>,
 }
 
 impl ScoreExplanation {
-    pub fn new(mut contributions: Vec<DimensionContribution>, top_n: usize) -> Self {
+    pub fn new(contributions: Vec<DimensionContribution>, top_n: usize) -> Self {
+        Self::with_tie_break(contributions, top_n, TieBreak::default())
+    }
+
+    /// Keep the `top_n` contributions of largest magnitude,
+    /// ordering equal magnitudes by dimension according to `tie_break`
+    pub fn with_tie_break(
+        mut contributions: Vec<DimensionContribution>,
+        top_n: usize,
+        tie_break: TieBreak,
+    ) -> Self {
+        let total_score = contributions.iter().map(|dim| dim.contribution).sum();
         contributions.sort_by(|a, b| {
             OrderedFloat(b.contribution.abs())
                 .cmp(&OrderedFloat(a.contribution.abs()))
+                .then_with(|| match tie_break {
+                    TieBreak::Ascending => a.dimension.cmp(&b.dimension),
+                    TieBreak::Descending => b.dimension.cmp(&a.dimension),
+                })
         });
         contributions.truncate(top_n);
         Self {
             top_dimensions: contributions,
+            total_score,
+            approximate_search: false,
+            space: ExplanationSpace::default(),
+            vector_source: VectorSource::default(),
+            status: ExplanationStatus::Available,
+            bundle: None,
+            verbosity: ExplanationVerbosity::default(),
+            query_norm: None,
+            stored_norm: None,
+            baseline_explanation: None,
+            vector_name: None,
+            dominant_examples: Vec::new(),
+            quantization_error: Vec::new(),
+            fusion: None,
+        }
+    }
+
+    /// Explanation of the fused score of a point, see [`FusionExplanation`]
+    pub fn fused(fusion: FusionExplanation) -> Self {
+        Self {
+            total_score: fusion
+                .sources
+                .iter()
+                .map(|source| source.contribution)
+                .sum(),
+            status: ExplanationStatus::Available,
+            fusion: Some(fusion),
+            ..Default::default()
+        }
+    }
+
+    /// Copy of the explanation where a higher value always means more similar vectors,
+    /// whatever the distance it was computed for.
+    ///
+    /// Distances where smaller is better (Euclid, Manhattan) report their total score as a
+    /// distance, so its sign is flipped. Their contributions are negative penalties, which are
+    /// already larger for more similar dimensions, and are kept as they are.
+    pub fn normalize_signs_for(&self, distance: impl Into<Order>) -> Self {
+        let mut normalized = self.clone();
+        match distance.into() {
+            Order::LargeBetter => {}
+            Order::SmallBetter => {
+                normalized.total_score = -normalized.total_score;
+                if let Some(baseline) = &mut normalized.baseline_explanation {
+                    baseline.total_score = -baseline.total_score;
+                }
+            }
+        }
+        normalized
+    }
+
+    /// Explanation without contributions, telling why they couldn't be computed
+    pub fn unavailable(reason: UnavailableReason) -> Self {
+        Self {
+            status: ExplanationStatus::Unavailable(reason),
+            ..Default::default()
+        }
+    }
+
+    /// Cosine similarity of the contributions of two explanations, aligned by dimension.
+    ///
+    /// Dimensions missing from one explanation count as zero contributions, so explanations
+    /// with disjoint dimensions have a similarity of 0. Also 0 if either has no contributions.
+    pub fn cosine_similarity_of_explanations(a: &Self, b: &Self) -> ScoreType {
+        let norm = |explanation: &Self| {
+            explanation
+                .top_dimensions
+                .iter()
+                .map(|dim| dim.contribution * dim.contribution)
+                .sum::<ScoreType>()
+                .sqrt()
+        };
+        let (norm_a, norm_b) = (norm(a), norm(b));
+        if norm_a == 0.0 || norm_b == 0.0 {
+            return 0.0;
+        }
+
+        let b_contributions: HashMap<_, _> = b
+            .top_dimensions
+            .iter()
+            .map(|dim| (dim.dimension, dim.contribution))
+            .collect();
+        let dot: ScoreType = a
+            .top_dimensions
+            .iter()
+            .filter_map(|dim| Some(dim.contribution * b_contributions.get(&dim.dimension)?))
+            .sum();
+
+        dot / (norm_a * norm_b)
+    }
+
+    /// Whether both explanations are equal, with all their scores, contributions and norms
+    /// differing by at most `epsilon`.
+    ///
+    /// `verbosity` is ignored, as it only affects how an explanation is returned.
+    pub fn approx_eq(&self, other: &Self, epsilon: ScoreType) -> bool {
+        let close = |a: ScoreType, b: ScoreType| (a - b).abs() <= epsilon;
+        let close_options = |a: Option<ScoreType>, b: Option<ScoreType>| match (a, b) {
+            (None, None) => true,
+            (Some(a), Some(b)) => close(a, b),
+            _ => false,
+        };
+        let close_vectors = |a: &[ScoreType], b: &[ScoreType]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(&a, &b)| close(a, b))
+        };
+        let close_dimensions = |a: &[DimensionContribution], b: &[DimensionContribution]| {
+            a.len() == b.len()
+                && a.iter().zip(b).all(|(a, b)| {
+                    a.dimension == b.dimension
+                        && a.label == b.label
+                        && close(a.contribution, b.contribution)
+                })
+        };
+
+        let same_bundle = match (&self.bundle, &other.bundle) {
+            (None, None) => true,
+            (Some(a), Some(b)) => {
+                close_vectors(&a.query, &b.query) && close_vectors(&a.stored, &b.stored)
+            }
+            _ => false,
+        };
+
+        let same_baseline = match (&self.baseline_explanation, &other.baseline_explanation) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.approx_eq(b, epsilon),
+            _ => false,
+        };
+
+        let same_dominant_examples = self.dominant_examples.len() == other.dominant_examples.len()
+            && self
+                .dominant_examples
+                .iter()
+                .zip(&other.dominant_examples)
+                .all(|(a, b)| {
+                    a.dimension == b.dimension
+                        && a.kind == b.kind
+                        && a.index == b.index
+                        && close(a.contribution, b.contribution)
+                });
+
+        let same_fusion = match (&self.fusion, &other.fusion) {
+            (None, None) => true,
+            (Some(a), Some(b)) => {
+                a.method == b.method
+                    && a.sources.len() == b.sources.len()
+                    && a.sources.iter().zip(&b.sources).all(|(a, b)| {
+                        a.rank == b.rank
+                            && close_options(a.score, b.score)
+                            && close(a.contribution, b.contribution)
+                    })
+            }
+            _ => false,
+        };
+
+        close_dimensions(&self.top_dimensions, &other.top_dimensions)
+            && close(self.total_score, other.total_score)
+            && self.approximate_search == other.approximate_search
+            && self.space == other.space
+            && self.vector_source == other.vector_source
+            && self.status == other.status
+            && same_bundle
+            && close_options(self.query_norm, other.query_norm)
+            && close_options(self.stored_norm, other.stored_norm)
+            && same_baseline
+            && self.vector_name == other.vector_name
+            && same_dominant_examples
+            && close_dimensions(&self.quantization_error, &other.quantization_error)
+            && same_fusion
+    }
+
+    /// Dimensions present in `top_dimensions`, for membership checks and set operations across explanations
+    pub fn dimension_set(&self) -> HashSet<usize> {
+        self.top_dimensions
+            .iter()
+            .map(|dim| dim.dimension)
+            .collect()
+    }
+
+    /// Copy of this explanation keeping only the dimensions matching `predicate`, in the same order.
+    ///
+    /// Shares of the contributions, as displayed or exported, are computed over the retained dimensions.
+    /// `total_score` is kept, as the score of the point doesn't change.
+    pub fn filter_dimensions(&self, predicate: impl FnMut(&DimensionContribution) -> bool) -> Self {
+        let mut filtered = self.clone();
+        filtered.top_dimensions.retain(predicate);
+        filtered
+    }
+
+    /// Compact one-line representation, e.g. `dim 3: +8.000, dim 1: +5.000`
+    pub fn summary(&self) -> String {
+        match self.status {
+            ExplanationStatus::Available => self
+                .top_dimensions
+                .iter()
+                .map(|dim| format!("dim {}: {:+.3}", dim.dimension, dim.contribution))
+                .collect::<Vec<_>>()
+                .join(", "),
+            ExplanationStatus::Unavailable(reason) => format!("unavailable: {reason:?}"),
+        }
+    }
+}
+
+/// Multi-line table of the contributions, for CLI tools and logs.
+///
+/// `share` is the share of the contribution in the total absolute contribution of the listed dimensions.
+impl fmt::Display for ScoreExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let ExplanationStatus::Unavailable(reason) = self.status {
+            return writeln!(f, "explanation unavailable: {reason:?}");
+        }
+
+        if self.top_dimensions.is_empty() {
+            return writeln!(f, "no contributions");
+        }
+
+        let total: ScoreType = self
+            .top_dimensions
+            .iter()
+            .map(|dim| dim.contribution.abs())
+            .sum();
+
+        writeln!(
+            f,
+            "{:>10}  {:>14}  {:>8}",
+            "dimension", "contribution", "share"
+        )?;
+        for dim in &self.top_dimensions {
+            let share = if total > 0.0 {
+                dim.contribution.abs() / total * 100.0
+            } else {
+                0.0
+            };
+            writeln!(
+                f,
+                "{:>10}  {:>+14.6}  {:>7.2}%",
+                dim.dimension, dim.contribution, share,
+            )?;
+        }
+
+        // Euclid and Manhattan contributions are negated differences, so they are never positive
+        writeln!(
+            f,
+            "positive contributions make vectors more similar, negative ones less similar",
+        )?;
+        if self.space == ExplanationSpace::Processed {
+            writeln!(f, "computed on stored vectors, after ingest preprocessing")?;
+        }
+        if self.approximate_search {
+            writeln!(f, "point was found with approximate search")?;
         }
+        Ok(())
     }
 }
 
@@ -349,6 +349,7 @@ impl PyOrderBy {
             key: JsonPath::from(key),
             direction: direction.map(Direction::from),
             start_from: start_from.map(StartFrom::from),
+            then_by: vec![],
         };
 
         Ok(Self(order_by))
@@ -381,6 +382,7 @@ impl PyOrderBy {
             key: _,
             direction: _,
             start_from: _,
+            then_by: _, // ordering by multiple keys is not exposed in Python
         } = self.0;
     }
 }
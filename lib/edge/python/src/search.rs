@@ -38,6 +38,11 @@ impl PySearchRequest {
             with_payload: with_payload.map(WithPayloadInterface::from),
             score_threshold,
             with_explanation: false,
+            with_explanation_bundle: false,
+            explanation_verbosity: Default::default(),
+            explanation_params: Default::default(),
+            mmr: None,
+            distinct_by: None,
         })
     }
 
@@ -123,6 +128,7 @@ impl PySearchParams {
             quantization: quantization.map(QuantizationSearchParams::from),
             indexed_only,
             acorn: acorn.map(AcornSearchParams::from),
+            explain_search_path: false,
         })
     }
 
@@ -165,6 +171,7 @@ impl PySearchParams {
             quantization: _,
             indexed_only: _,
             acorn: _,
+            explain_search_path: _, // internal, not exposed to Python
         } = self.0;
     }
 }
@@ -182,6 +189,7 @@ impl PyQuantizationSearchParams {
             ignore,
             rescore,
             oversampling,
+            compare_explanations: false,
         })
     }
 
@@ -212,6 +220,7 @@ impl PyQuantizationSearchParams {
             ignore: _,
             rescore: _,
             oversampling: _,
+            compare_explanations: _, // internal, not exposed to Python
         } = self.0;
     }
 }
@@ -437,6 +446,9 @@ impl<'py> IntoPyObject<'py> for PyPayloadSelector {
                     keys: PyJsonPath::wrap_vec(exclude),
                 }
             }
+            PayloadSelector::Transform(transform) => PyPayloadSelectorInterface::Include {
+                keys: PyJsonPath::wrap_vec(transform.source_keys()),
+            },
         };
 
         Bound::new(py, selector)
@@ -445,6 +457,7 @@ impl<'py> IntoPyObject<'py> for PyPayloadSelector {
 
 impl Repr for PyPayloadSelector {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let source_keys;
         let (repr, keys) = match &self.0 {
             PayloadSelector::Include(PayloadSelectorInclude { include }) => {
                 ("Include", PyJsonPath::wrap_slice(include))
@@ -452,6 +465,10 @@ impl Repr for PyPayloadSelector {
             PayloadSelector::Exclude(PayloadSelectorExclude { exclude }) => {
                 ("Exclude", PyJsonPath::wrap_slice(exclude))
             }
+            PayloadSelector::Transform(transform) => {
+                source_keys = transform.source_keys();
+                ("Include", PyJsonPath::wrap_slice(&source_keys))
+            }
         };
 
         f.complex_enum::<PyPayloadSelectorInterface>(repr, &[("keys", &keys)])
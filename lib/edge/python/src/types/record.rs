@@ -48,6 +48,7 @@ impl PyRecord {
             vector: _,
             shard_key: _, // not relevant for Qdrant Edge
             order_value: _,
+            then_by_values: _, // only used to merge ordered results
         } = self.0;
     }
 }
@@ -24,6 +24,7 @@ impl PyUpdateOperation {
                 point_ops::ConditionalInsertOperationInternal {
                     points_op: points,
                     condition: Filter::from(condition),
+                    precondition: None,
                 },
             ),
 
@@ -37,6 +38,8 @@ impl PyUpdateOperation {
     pub fn delete_points(point_ids: Vec<PyPointId>) -> Self {
         let operation = point_ops::PointOperations::DeletePoints {
             ids: PyPointId::peel_vec(point_ids),
+            precondition: None,
+            deleted_at: None,
         };
 
         Self(CollectionUpdateOperations::PointOperation(operation))
@@ -44,7 +47,8 @@ impl PyUpdateOperation {
 
     #[staticmethod]
     pub fn delete_points_by_filter(filter: PyFilter) -> Self {
-        let operation = point_ops::PointOperations::DeletePointsByFilter(Filter::from(filter));
+        let operation =
+            point_ops::PointOperations::DeletePointsByFilter(Filter::from(filter).into());
         Self(CollectionUpdateOperations::PointOperation(operation))
     }
 
@@ -89,6 +93,7 @@ impl PyUpdateOperation {
             points: Some(PyPointId::peel_vec(point_ids)),
             filter: None,
             key: key.map(JsonPath::from),
+            precondition: None,
         });
 
         Self(CollectionUpdateOperations::PayloadOperation(operation))
@@ -106,6 +111,7 @@ impl PyUpdateOperation {
             points: None,
             filter: Some(Filter::from(filter)),
             key: key.map(JsonPath::from),
+            precondition: None,
         });
 
         Self(CollectionUpdateOperations::PayloadOperation(operation))
@@ -160,6 +166,7 @@ impl PyUpdateOperation {
             points: Some(PyPointId::peel_vec(point_ids)),
             filter: None,
             key: key.map(JsonPath::from),
+            precondition: None,
         });
 
         Self(CollectionUpdateOperations::PayloadOperation(operation))
@@ -177,6 +184,7 @@ impl PyUpdateOperation {
             points: None,
             filter: Some(Filter::from(filter)),
             key: key.map(JsonPath::from),
+            precondition: None,
         });
 
         Self(CollectionUpdateOperations::PayloadOperation(operation))
@@ -239,6 +239,11 @@ impl Shard {
                     with_vector: None,
                     score_threshold: score_threshold.map(OrderedFloat::into_inner),
                     with_explanation: false,
+                    with_explanation_bundle: false,
+                    explanation_verbosity: Default::default(),
+                    explanation_params: Default::default(),
+                    mmr: None,
+                    distinct_by: None,
                 };
 
                 self.search(search_request)
@@ -7,7 +7,7 @@ use rand::distr::weighted::WeightedIndex;
 use rand::rngs::StdRng;
 use rand::{Rng as _, SeedableRng as _};
 use segment::common::operation_error::{OperationError, OperationResult};
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::OrderBy;
 use segment::types::*;
 use shard::query::scroll::{QueryScrollRequestInternal, ScrollOrder};
 use shard::retrieve::record_internal::RecordInternal;
@@ -150,15 +150,14 @@ impl Shard {
             })
             .collect::<Result<_, _>>()?;
 
-        let (order_values, point_ids): (Vec<_>, Vec<_>) = read_results
+        let ordered_points = read_results
             .into_iter()
-            .kmerge_by(|a, b| match order_by.direction() {
-                Direction::Asc => a <= b,
-                Direction::Desc => a >= b,
-            })
+            .kmerge_by(|a, b| order_by.compare(a, b).is_le())
             .dedup()
             .take(limit)
-            .unzip();
+            .collect_vec();
+
+        let point_ids = ordered_points.iter().map(|point| point.id).collect_vec();
 
         let points = retrieve_blocking(
             self.segments.clone(),
@@ -170,12 +169,12 @@ impl Shard {
             hw_measurement_acc,
         )?;
 
-        let ordered_points = point_ids
-            .iter()
-            .zip(order_values)
-            .filter_map(|(point_id, value)| {
-                let mut record = points.get(point_id).cloned()?;
-                record.order_value = Some(value);
+        let ordered_points = ordered_points
+            .into_iter()
+            .filter_map(|point| {
+                let mut record = points.get(&point.id).cloned()?;
+                record.order_value = Some(point.value);
+                record.then_by_values = point.then_by;
                 Some(record)
             })
             .collect();
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use chrono::Utc;
 use common::counter::hardware_counter::HardwareCounterCell;
 use segment::common::operation_error::{OperationError, OperationResult};
 use shard::operations::CollectionUpdateOperations;
@@ -8,11 +9,17 @@ use shard::update::*;
 use crate::Shard;
 
 impl Shard {
-    pub fn update(&self, operation: CollectionUpdateOperations) -> OperationResult<()> {
+    pub fn update(&self, mut operation: CollectionUpdateOperations) -> OperationResult<()> {
         let mut wal = self.wal.lock();
+        let hw_counter = HardwareCounterCell::disposable();
+
+        // Check the precondition once, the operation is written to the WAL without it
+        check_operation_precondition(&self.segments.read(), &operation, &hw_counter)?;
+        operation.take_precondition();
+        // Points are soft deleted at the same time when the operation is replayed from the WAL
+        operation.set_deleted_at(Utc::now());
 
         let operation_id = wal.write(&operation).map_err(service_error)?;
-        let hw_counter = HardwareCounterCell::disposable();
 
         let result = match operation {
             CollectionUpdateOperations::PointOperation(point_operation) => {
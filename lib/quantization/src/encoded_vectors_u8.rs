@@ -95,6 +95,11 @@ impl MetadataInt8 {
         i.clamp(0.0, 127.0).round() as u8
     }
 
+    #[inline]
+    pub fn decode_value(&self, code: u8) -> f32 {
+        f32::from(code) * self.alpha + self.offset
+    }
+
     #[inline]
     fn postprocess_score(&self, score: f32, query_offset: f32, vector_offset: f32) -> f32 {
         self.multiplier * score + query_offset + vector_offset
@@ -524,6 +529,17 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
         (offset, code)
     }
 
+    /// Approximate vector of point `i`, reconstructed from its quantized codes only
+    pub fn dequantize(&self, i: PointOffsetType) -> Vec<f32> {
+        let (_offset, code) = self.get_quantized_vector_offset_and_code(i);
+        match &self.metadata {
+            Metadata::Int8(metadata) => code[..metadata.vector_parameters.dim]
+                .iter()
+                .map(|&value| metadata.decode_value(value))
+                .collect(),
+        }
+    }
+
     pub fn get_quantized_vector_size(vector_parameters: &VectorParameters) -> usize {
         let actual_dim = Self::get_actual_dim(vector_parameters);
         actual_dim + ADDITIONAL_CONSTANT_SIZE
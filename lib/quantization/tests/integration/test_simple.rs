@@ -469,4 +469,49 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_dequantize_simple() {
+        let vectors_count = 129;
+        let vector_dim = 65;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let vector_data: Vec<Vec<f32>> = (0..vectors_count)
+            .map(|_| {
+                (0..vector_dim)
+                    .map(|_| rng.random_range(-1.0..=1.0))
+                    .collect()
+            })
+            .collect();
+
+        let vector_parameters = VectorParameters {
+            dim: vector_dim,
+            deprecated_count: None,
+            distance_type: DistanceType::Dot,
+            invert: false,
+        };
+        let quantized_vector_size =
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+        let encoded = EncodedVectorsU8::encode(
+            vector_data.iter(),
+            TestEncodedStorageBuilder::new(None, quantized_vector_size),
+            &vector_parameters,
+            vectors_count,
+            None,
+            ScalarQuantizationMethod::Int8,
+            None,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+
+        // 128 levels over the [-1, 1] range
+        let error = 2.0 / 127.0;
+        for (index, vector) in vector_data.iter().enumerate() {
+            let dequantized = encoded.dequantize(index as u32);
+            assert_eq!(dequantized.len(), vector_dim);
+            for (original, dequantized) in vector.iter().zip(&dequantized) {
+                assert!((original - dequantized).abs() <= error);
+            }
+        }
+    }
 }
@@ -76,6 +76,8 @@ pub enum OperationError {
     },
     #[error("The expression {expression} produced a non-finite number")]
     NonFiniteNumber { expression: String },
+    #[error("Update precondition not met: {description}")]
+    PreconditionConflict { description: String },
 
     // ToDo: Remove after RocksDB is deprecated
     #[error("RocksDB column family {name} not found")]
@@ -106,6 +108,12 @@ impl OperationError {
         }
     }
 
+    pub fn precondition_conflict(description: impl Into<String>) -> Self {
+        Self::PreconditionConflict {
+            description: description.into(),
+        }
+    }
+
     pub fn inconsistent_storage(description: impl Into<String>) -> Self {
         Self::InconsistentStorage {
             description: description.into(),
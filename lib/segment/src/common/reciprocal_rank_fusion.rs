@@ -12,7 +12,7 @@ use crate::types::{ExtendedPointId, ScoredPoint};
 pub const DEFAULT_RRF_K: usize = 2;
 
 /// Compute the RRF score for a given position.
-fn position_score(position: usize, k: usize) -> f32 {
+pub fn position_score(position: usize, k: usize) -> f32 {
     1.0 / (position as f32 + k as f32)
 }
 
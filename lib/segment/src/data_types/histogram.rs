@@ -0,0 +1,168 @@
+use ordered_float::OrderedFloat;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::json_path::JsonPath;
+use crate::types::{Filter, FloatPayloadType, Range};
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct HistogramParams {
+    pub key: JsonPath,
+
+    #[validate(range(min = 1, max = 1000))]
+    pub buckets: usize,
+    #[validate(nested)]
+    pub filter: Option<Filter>,
+    /// Lower bound of the first bucket, lowest value of the field if not set
+    pub min: Option<FloatPayloadType>,
+    /// Upper bound of the last bucket, highest value of the field if not set
+    pub max: Option<FloatPayloadType>,
+    #[validate(custom(function = "validate_percentiles"))]
+    pub percentiles: Vec<f64>,
+    #[serde(default)]
+    pub exact: bool,
+}
+
+impl HistogramParams {
+    pub const DEFAULT_BUCKETS: usize = 10;
+    pub const DEFAULT_EXACT: bool = false;
+}
+
+fn validate_percentiles(percentiles: &[f64]) -> Result<(), ValidationError> {
+    if percentiles
+        .iter()
+        .all(|percentile| (0.0..=100.0).contains(percentile))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new(
+            "percentiles must be between 0 and 100",
+        ))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    /// Inclusive lower bound of the bucket
+    pub from: FloatPayloadType,
+    /// Upper bound of the bucket, inclusive only for the last bucket
+    pub to: FloatPayloadType,
+    /// Number of points with a value in the bucket.
+    /// Points with several values are counted in each bucket of their values.
+    pub count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct PercentileValue {
+    pub percentile: f64,
+    /// Value estimated by interpolating within the histogram buckets
+    pub value: FloatPayloadType,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct HistogramResponse {
+    pub buckets: Vec<HistogramBucket>,
+    pub percentiles: Vec<PercentileValue>,
+}
+
+/// Split `[min, max]` into `buckets` ranges of equal width.
+///
+/// All ranges exclude their upper bound, except the last one, so each value falls into exactly one bucket.
+pub fn bucket_ranges(
+    min: FloatPayloadType,
+    max: FloatPayloadType,
+    buckets: usize,
+) -> Vec<Range<OrderedFloat<FloatPayloadType>>> {
+    if buckets == 0 || min > max {
+        return Vec::new();
+    }
+
+    // A single value can't be split
+    let buckets = if min == max { 1 } else { buckets };
+    let width = (max - min) / buckets as FloatPayloadType;
+
+    (0..buckets)
+        .map(|i| {
+            let from = min + width * i as FloatPayloadType;
+            let is_last = i + 1 == buckets;
+            Range {
+                gte: Some(OrderedFloat(from)),
+                lt: (!is_last).then(|| OrderedFloat(from + width)),
+                lte: is_last.then_some(OrderedFloat(max)),
+                gt: None,
+            }
+        })
+        .collect()
+}
+
+/// Estimate a percentile assuming values are spread uniformly within each bucket
+pub fn estimate_percentile(
+    buckets: &[HistogramBucket],
+    percentile: f64,
+) -> Option<FloatPayloadType> {
+    let total: usize = buckets.iter().map(|bucket| bucket.count).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let rank = percentile / 100.0 * total as f64;
+    let mut below = 0;
+    for bucket in buckets.iter().filter(|bucket| bucket.count > 0) {
+        if below as f64 + bucket.count as f64 >= rank {
+            let fraction = (rank - below as f64) / bucket.count as f64;
+            return Some(bucket.from + (bucket.to - bucket.from) * fraction);
+        }
+        below += bucket.count;
+    }
+
+    buckets
+        .iter()
+        .rev()
+        .find(|bucket| bucket.count > 0)
+        .map(|bucket| bucket.to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(from: f64, to: f64, count: usize) -> HistogramBucket {
+        HistogramBucket { from, to, count }
+    }
+
+    #[test]
+    fn test_bucket_ranges() {
+        let ranges = bucket_ranges(0.0, 10.0, 4);
+        assert_eq!(ranges.len(), 4);
+        assert_eq!(ranges[0].gte, Some(OrderedFloat(0.0)));
+        assert_eq!(ranges[0].lt, Some(OrderedFloat(2.5)));
+        assert_eq!(ranges[0].lte, None);
+        assert_eq!(ranges[3].gte, Some(OrderedFloat(7.5)));
+        assert_eq!(ranges[3].lt, None);
+        assert_eq!(ranges[3].lte, Some(OrderedFloat(10.0)));
+
+        let single_value = bucket_ranges(3.0, 3.0, 10);
+        assert_eq!(single_value.len(), 1);
+        assert_eq!(single_value[0].gte, Some(OrderedFloat(3.0)));
+        assert_eq!(single_value[0].lte, Some(OrderedFloat(3.0)));
+
+        assert!(bucket_ranges(5.0, 1.0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_estimate_percentile() {
+        let buckets = [
+            bucket(0.0, 10.0, 2),
+            bucket(10.0, 20.0, 0),
+            bucket(20.0, 30.0, 2),
+        ];
+        assert_eq!(estimate_percentile(&buckets, 0.0), Some(0.0));
+        assert_eq!(estimate_percentile(&buckets, 25.0), Some(5.0));
+        assert_eq!(estimate_percentile(&buckets, 50.0), Some(10.0));
+        assert_eq!(estimate_percentile(&buckets, 75.0), Some(25.0));
+        assert_eq!(estimate_percentile(&buckets, 100.0), Some(30.0));
+
+        assert_eq!(estimate_percentile(&[bucket(0.0, 1.0, 0)], 50.0), None);
+    }
+}
@@ -2,6 +2,7 @@ pub mod build_index_result;
 pub mod collection_defaults;
 pub mod facets;
 pub mod groups;
+pub mod histogram;
 pub mod index;
 pub mod manifest;
 pub mod modifier;
@@ -9,5 +10,6 @@ pub mod named_vectors;
 pub mod order_by;
 pub mod primitive;
 pub mod query_context;
+pub mod query_plan;
 pub mod tiny_map;
 pub mod vectors;
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::hash::Hash;
 
 use num_cmp::NumCmp;
@@ -8,7 +9,8 @@ use validator::Validate;
 
 use crate::json_path::JsonPath;
 use crate::types::{
-    DateTimePayloadType, FloatPayloadType, IntPayloadType, Order, Range, RangeInterface,
+    DateTimePayloadType, FloatPayloadType, IntPayloadType, Order, PointIdType, Range,
+    RangeInterface,
 };
 
 #[derive(Deserialize, Serialize, JsonSchema, Copy, Clone, Debug, Default, PartialEq, Hash)]
@@ -78,6 +80,37 @@ pub struct OrderBy {
 
     /// Which payload value to start scrolling from. Default is the lowest value for `asc` and the highest for `desc`
     pub start_from: Option<StartFrom>,
+
+    /// Additional payload keys to order points with equal `key` values by, in order of priority.
+    /// Points without a value for one of these keys come last.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub then_by: Vec<ThenBy>,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct ThenBy {
+    /// Payload key to order by
+    pub key: JsonPath,
+
+    /// Direction of ordering: `asc` or `desc`. Default is ascending.
+    pub direction: Option<Direction>,
+}
+
+impl ThenBy {
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_default()
+    }
+}
+
+/// Point read in the order of an [`OrderBy`], with the values it is ordered by
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderedPoint {
+    /// Value of the `order_by.key` field
+    pub value: OrderValue,
+    /// Values of the `order_by.then_by` fields, `None` if the point has no value for the field
+    pub then_by: Vec<Option<OrderValue>>,
+    pub id: PointIdType,
 }
 
 impl OrderBy {
@@ -105,6 +138,49 @@ impl OrderBy {
         self.direction.unwrap_or_default()
     }
 
+    pub fn is_compound(&self) -> bool {
+        !self.then_by.is_empty()
+    }
+
+    /// Compare points by `key`, then by the `then_by` keys, then by point id.
+    /// Point ids follow the direction of `key`.
+    pub fn compare(&self, a: &OrderedPoint, b: &OrderedPoint) -> Ordering {
+        self.compare_values(
+            (Some(a.value), &a.then_by, a.id),
+            (Some(b.value), &b.then_by, b.id),
+        )
+    }
+
+    /// Same as [`Self::compare`], for points which might have no value for `key`
+    pub fn compare_values(
+        &self,
+        (a_value, a_then_by, a_id): (Option<OrderValue>, &[Option<OrderValue>], PointIdType),
+        (b_value, b_then_by, b_id): (Option<OrderValue>, &[Option<OrderValue>], PointIdType),
+    ) -> Ordering {
+        let primary = match self.direction() {
+            Direction::Asc => (a_value, a_id).cmp(&(b_value, b_id)),
+            Direction::Desc => (b_value, b_id).cmp(&(a_value, a_id)),
+        };
+        if !self.is_compound() || a_value != b_value {
+            return primary;
+        }
+
+        self.then_by
+            .iter()
+            .zip(a_then_by.iter().zip(b_then_by))
+            .map(|(then_by, (a_value, b_value))| match (a_value, b_value) {
+                (Some(a_value), Some(b_value)) => match then_by.direction() {
+                    Direction::Asc => a_value.cmp(b_value),
+                    Direction::Desc => b_value.cmp(a_value),
+                },
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(primary)
+    }
+
     pub fn start_from(&self) -> OrderValue {
         self.start_from
             .as_ref()
@@ -227,7 +303,7 @@ impl Ord for OrderValue {
 mod tests {
     use proptest::proptest;
 
-    use crate::data_types::order_by::OrderValue;
+    use super::*;
 
     proptest! {
 
@@ -245,4 +321,31 @@ mod tests {
             assert!(OrderValue::MAX.cmp(&OrderValue::from(f64::NAN)).is_ge());
         }
     }
+
+    #[test]
+    fn test_compound_compare() {
+        let order_by: OrderBy = serde_json::from_str(
+            r#"{"key": "price", "direction": "desc", "then_by": [{"key": "rating", "direction": "asc"}]}"#,
+        )
+        .unwrap();
+        let point = |value: i64, then_by: Option<f64>, id: u64| OrderedPoint {
+            value: OrderValue::from(value),
+            then_by: vec![then_by.map(OrderValue::from)],
+            id: id.into(),
+        };
+
+        let mut points = vec![
+            point(10, Some(4.5), 1),
+            point(20, None, 2),
+            point(10, None, 3),
+            point(10, Some(3.0), 4),
+            point(20, Some(1.0), 5),
+            point(10, Some(3.0), 6),
+        ];
+        points.sort_by(|a, b| order_by.compare(a, b));
+
+        let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        let expected: Vec<PointIdType> = [5, 2, 6, 4, 1, 3].map(PointIdType::from).to_vec();
+        assert_eq!(ids, expected);
+    }
 }
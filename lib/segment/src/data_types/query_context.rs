@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use bitvec::prelude::BitSlice;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
@@ -153,6 +153,7 @@ impl<'a> SegmentQueryContext<'a> {
                 .copied(),
             deleted_points: self.deleted_points,
             hardware_counter: self.hardware_counter.fork(),
+            approximate: AtomicBool::new(false),
         }
     }
 
@@ -190,6 +191,9 @@ pub struct VectorQueryContext<'a> {
     deleted_points: Option<&'a BitSlice>,
 
     hardware_counter: HardwareCounterCell,
+
+    /// Set by the vector index if it searched approximately, e.g. by traversing an HNSW graph
+    approximate: AtomicBool,
 }
 
 impl VectorQueryContext<'_> {
@@ -240,6 +244,15 @@ impl VectorQueryContext<'_> {
     pub fn is_require_idf(&self) -> bool {
         self.idf.is_some() && self.indexed_vectors.is_some()
     }
+
+    pub fn mark_approximate(&self) {
+        self.approximate.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the vector index searched approximately instead of scanning all vectors
+    pub fn is_approximate(&self) -> bool {
+        self.approximate.load(Ordering::Relaxed)
+    }
 }
 
 #[cfg(feature = "testing")]
@@ -252,6 +265,7 @@ impl Default for VectorQueryContext<'_> {
             indexed_vectors: None,
             deleted_points: None,
             hardware_counter: HardwareCounterCell::new(),
+            approximate: AtomicBool::new(false),
         }
     }
 }
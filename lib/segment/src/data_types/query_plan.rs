@@ -0,0 +1,117 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::index::field_index::{CardinalityEstimation, PrimaryCondition};
+
+/// How a segment scores points against a query vector
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorSearchStrategy {
+    /// Exact search was requested, every matching vector is scored without quantization
+    Exact,
+    /// Every matching vector is scored, either because there is no index or because it is faster
+    Plain,
+    /// Search traverses the HNSW graph
+    Hnsw,
+    /// Search uses the sparse inverted index
+    Sparse,
+}
+
+#[derive(Clone, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct FilterPlan {
+    /// Conditions resolved through payload indexes to select candidate points
+    pub indexed_conditions: Vec<String>,
+    /// No condition can be resolved through an index, every point of the segment is checked
+    pub full_scan: bool,
+    /// Estimated number of points matching the filter
+    pub cardinality: EstimatedCardinality,
+}
+
+impl FilterPlan {
+    pub fn from_estimation(estimation: &CardinalityEstimation) -> Self {
+        let indexed_conditions = estimation
+            .primary_clauses
+            .iter()
+            .map(|clause| match clause {
+                PrimaryCondition::Condition(condition) => condition.key.to_string(),
+                PrimaryCondition::Ids(_) => "has_id".to_string(),
+                PrimaryCondition::HasVector(vector) => format!("has_vector({vector})"),
+            })
+            .collect();
+
+        Self {
+            indexed_conditions,
+            full_scan: estimation.primary_clauses.is_empty() && estimation.max > 0,
+            cardinality: EstimatedCardinality::from(estimation),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+pub struct EstimatedCardinality {
+    pub min: usize,
+    pub exp: usize,
+    pub max: usize,
+}
+
+impl From<&CardinalityEstimation> for EstimatedCardinality {
+    fn from(estimation: &CardinalityEstimation) -> Self {
+        Self {
+            min: estimation.min,
+            exp: estimation.exp,
+            max: estimation.max,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct SegmentSearchPlan {
+    /// Number of available points in the segment
+    pub points: usize,
+    /// Strategy used to score vectors, absent if no vector is searched
+    pub strategy: Option<VectorSearchStrategy>,
+    /// How the filter is applied, absent if there is no filter
+    pub filter: Option<FilterPlan>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json_path::JsonPath;
+    use crate::types::{FieldCondition, Match, ValueVariants};
+
+    #[test]
+    fn test_filter_plan_from_estimation() {
+        let condition = FieldCondition::new_match(
+            JsonPath::new("city"),
+            Match::new_value(ValueVariants::String("Berlin".into())),
+        );
+        let indexed = CardinalityEstimation {
+            primary_clauses: vec![PrimaryCondition::from(condition)],
+            min: 1,
+            exp: 5,
+            max: 10,
+        };
+        let plan = FilterPlan::from_estimation(&indexed);
+        assert_eq!(plan.indexed_conditions, vec!["city".to_string()]);
+        assert!(!plan.full_scan);
+        assert_eq!(
+            plan.cardinality,
+            EstimatedCardinality {
+                min: 1,
+                exp: 5,
+                max: 10
+            },
+        );
+
+        let unindexed = CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 0,
+            exp: 50,
+            max: 100,
+        };
+        let plan = FilterPlan::from_estimation(&unindexed);
+        assert!(plan.indexed_conditions.is_empty());
+        assert!(plan.full_scan);
+    }
+}
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
+use chrono::{DateTime, Utc};
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::TelemetryDetail;
 
@@ -11,10 +12,11 @@ use crate::common::operation_error::{OperationError, OperationResult, SegmentFai
 use crate::data_types::build_index_result::BuildFieldIndexResult;
 use crate::data_types::facets::{FacetParams, FacetValue};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::order_by::{OrderBy, OrderValue};
+use crate::data_types::order_by::{OrderBy, OrderedPoint};
 use crate::data_types::query_context::{FormulaContext, QueryContext, SegmentQueryContext};
 use crate::data_types::vectors::{QueryVector, VectorInternal};
 use crate::entry::snapshot_entry::SnapshotEntry;
+use crate::id_tracker::tombstones::TombstonedPoint;
 use crate::index::field_index::{CardinalityEstimation, FieldIndex};
 use crate::json_path::JsonPath;
 use crate::telemetry::SegmentTelemetry;
@@ -79,6 +81,26 @@ pub trait SegmentEntry: SnapshotEntry {
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<bool>;
 
+    /// Delete point, but keep it restorable as a tombstone until it is purged
+    ///
+    /// Falls back to a regular deletion if the segment can't keep tombstones.
+    fn delete_point_soft(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        deleted_at: DateTime<Utc>,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<bool>;
+
+    /// Read data of a soft deleted point
+    ///
+    /// Returns `None` if the point has no tombstone in this segment.
+    fn tombstoned_point(
+        &self,
+        point_id: PointIdType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<TombstonedPoint>>;
+
     fn update_vectors(
         &mut self,
         op_num: SeqNumberType,
@@ -163,7 +185,7 @@ pub trait SegmentEntry: SnapshotEntry {
     ) -> Vec<PointIdType>;
 
     /// Return points which satisfies filtering condition ordered by the `order_by.key` field,
-    /// starting with `order_by.start_from` value including.
+    /// starting with `order_by.start_from` value including, then by the `order_by.then_by` fields.
     ///
     /// Will fail if there is no index for the order_by key.
     /// Cancelled by `is_stopped` flag.
@@ -174,7 +196,7 @@ pub trait SegmentEntry: SnapshotEntry {
         order_by: &'a OrderBy,
         is_stopped: &AtomicBool,
         hw_counter: &HardwareCounterCell,
-    ) -> OperationResult<Vec<(OrderValue, PointIdType)>>;
+    ) -> OperationResult<Vec<OrderedPoint>>;
 
     /// Return random points which satisfies filtering condition.
     ///
@@ -0,0 +1,30 @@
+//! Public entry point for score explanations.
+//!
+//! Everything needed to compute and inspect an explanation is re-exported here,
+//! so external consumers don't need to know whether an item lives in
+//! `segment::spaces::explainability` or in `common::types`.
+
+pub use common::types::{
+    DimensionContribution, DominantExample, ExampleKind, ExplanationSpace, ExplanationStatus,
+    FusionExplanation, FusionMethod, FusionSource, ScoreExplanation, TieBreak, UnavailableReason,
+    VectorSource,
+};
+pub use sparse::common::sparse_vector::SparseVector;
+
+pub use crate::spaces::explainability::{
+    CompositeExplanation, DEFAULT_TOP_DIMENSIONS, DIMENSION_LABELS_METADATA_KEY, DriftReport,
+    ExplainError, ExplanationConfig, ExplanationDriftTracker, MeanVector, SelectedDimension,
+    ZeroReason, aggregate_absolute_explanations, aggregate_explanations,
+    aggregate_explanations_weighted, average_recommend_query, compute_baseline_explanation,
+    compute_contributions, compute_counterfactual_explanation, compute_delta_from_mean_explanation,
+    compute_explanation, compute_pruned_sparse_explanation, compute_quantization_error_explanation,
+    compute_quantized_explanation, compute_recommend_context_explanation,
+    compute_recommend_explanation, compute_sparse_explanation, context_contributions,
+    cosine_contributions, counterfactual_contributions, delta_from_mean_contributions,
+    dimension_labels_from_metadata, discover_contributions, dominant_recommend_examples,
+    dot_product_contributions, euclidean_contributions, explain_fusion, explain_scored_offsets,
+    explain_selected_dimensions, explanations_to_csv, explanations_to_tsv, invert_preprocessing,
+    is_preprocessed_on_ingest, label_dimensions, manhattan_contributions, prune_sparse_query,
+    rank_weight, sparse_contributions, stored_vectors_space, suggest_score_threshold,
+    try_compute_explanation, weighted_average_recommend_query,
+};
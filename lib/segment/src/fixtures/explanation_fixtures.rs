@@ -0,0 +1,59 @@
+use common::types::{ExplanationStatus, ScoreType};
+
+use crate::data_types::vectors::VectorStructInternal;
+use crate::types::ScoredPoint;
+
+const SCORE_EPSILON: ScoreType = 1e-4;
+
+/// Check that the explanation of every point agrees with the point itself:
+///
+/// * `total_score` matches the score of the point
+/// * `top_dimensions` are ordered by decreasing magnitude
+/// * explained dimensions exist in the vector of the point, if it is returned
+///
+/// Points without explanation, or with an unavailable one, are skipped.
+pub fn assert_explanations_consistent(points: &[ScoredPoint]) {
+    for point in points {
+        let Some(explanation) = &point.score_explanation else {
+            continue;
+        };
+        if explanation.status != ExplanationStatus::Available {
+            continue;
+        }
+
+        let tolerance = SCORE_EPSILON * point.score.abs().max(1.0);
+        assert!(
+            (explanation.total_score - point.score).abs() <= tolerance,
+            "point {}: explained total {} doesn't match score {}",
+            point.id,
+            explanation.total_score,
+            point.score,
+        );
+
+        for (prev, next) in explanation
+            .top_dimensions
+            .iter()
+            .zip(explanation.top_dimensions.iter().skip(1))
+        {
+            assert!(
+                prev.contribution.abs() >= next.contribution.abs(),
+                "point {}: dimension {} is ranked before dimension {} with a larger contribution",
+                point.id,
+                prev.dimension,
+                next.dimension,
+            );
+        }
+
+        if let Some(VectorStructInternal::Single(vector)) = &point.vector {
+            for dim in &explanation.top_dimensions {
+                assert!(
+                    dim.dimension < vector.len(),
+                    "point {}: dimension {} is out of the {} dimensions of the vector",
+                    point.id,
+                    dim.dimension,
+                    vector.len(),
+                );
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod explanation_fixtures;
 pub mod index_fixtures;
 pub mod payload_context_fixture;
 pub mod payload_fixtures;
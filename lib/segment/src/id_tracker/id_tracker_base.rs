@@ -1,9 +1,11 @@
 use std::fmt;
 use std::path::PathBuf;
 
+use ahash::AHashSet;
 use bitvec::prelude::BitSlice;
 use common::ext::BitSliceExt as _;
 use common::types::PointOffsetType;
+use itertools::Itertools as _;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 
@@ -14,6 +16,7 @@ use crate::common::operation_error::OperationResult;
 use crate::id_tracker::immutable_id_tracker::ImmutableIdTracker;
 #[cfg(feature = "rocksdb")]
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
+use crate::id_tracker::tombstones::Tombstones;
 use crate::types::{PointIdType, SeqNumberType};
 
 /// Sampling randomness seed
@@ -159,6 +162,15 @@ pub trait IdTracker: fmt::Debug {
         )
     }
 
+    /// Tombstones of soft deleted points, `None` if this tracker can't keep them
+    fn tombstones(&self) -> Option<&Tombstones> {
+        None
+    }
+
+    fn tombstones_mut(&mut self) -> Option<&mut Tombstones> {
+        None
+    }
+
     /// Iterate over all stored internal versions, even if they were deleted
     /// Required for cleanup on segment open
     fn iter_internal_versions(
@@ -182,8 +194,38 @@ pub trait IdTracker: fmt::Debug {
         // We return those points to the caller to clean up the storage.
         let mut to_return = Vec::new();
 
+        // Tombstones of points which were inserted again are outdated, drop them and return
+        // their old points to clean up the storage
+        let outdated = self.tombstones().map_or_else(Vec::new, |tombstones| {
+            tombstones
+                .iter()
+                .filter(|(external_id, _)| self.internal_id(*external_id).is_some())
+                .map(|(external_id, tombstone)| (external_id, tombstone.internal_id))
+                .collect_vec()
+        });
+        if let Some(tombstones) = self.tombstones_mut() {
+            for (external_id, internal_id) in outdated {
+                tombstones.remove(external_id);
+                to_return.push(internal_id);
+            }
+        }
+
+        // Soft deleted points keep their storage until their tombstone is purged
+        let tombstoned: AHashSet<_> = self
+            .tombstones()
+            .into_iter()
+            .flat_map(|tombstones| {
+                tombstones
+                    .iter()
+                    .map(|(_, tombstone)| tombstone.internal_id)
+            })
+            .collect();
+
         for (internal_id, version) in self.iter_internal_versions() {
-            if version != DELETED_POINT_VERSION && self.external_id(internal_id).is_none() {
+            if version != DELETED_POINT_VERSION
+                && self.external_id(internal_id).is_none()
+                && !tombstoned.contains(&internal_id)
+            {
                 to_return.push(internal_id);
             }
         }
@@ -443,6 +485,26 @@ impl IdTracker for IdTrackerEnum {
         }
     }
 
+    fn tombstones(&self) -> Option<&Tombstones> {
+        match self {
+            IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.tombstones(),
+            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.tombstones(),
+            IdTrackerEnum::InMemoryIdTracker(id_tracker) => id_tracker.tombstones(),
+            #[cfg(feature = "rocksdb")]
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.tombstones(),
+        }
+    }
+
+    fn tombstones_mut(&mut self) -> Option<&mut Tombstones> {
+        match self {
+            IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.tombstones_mut(),
+            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.tombstones_mut(),
+            IdTrackerEnum::InMemoryIdTracker(id_tracker) => id_tracker.tombstones_mut(),
+            #[cfg(feature = "rocksdb")]
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.tombstones_mut(),
+        }
+    }
+
     fn iter_internal_versions(
         &self,
     ) -> Box<dyn Iterator<Item = (PointOffsetType, SeqNumberType)> + '_> {
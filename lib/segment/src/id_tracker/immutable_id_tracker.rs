@@ -23,6 +23,7 @@ use crate::id_tracker::compressed::internal_to_external::CompressedInternalToExt
 use crate::id_tracker::compressed::versions_store::CompressedVersions;
 use crate::id_tracker::in_memory_id_tracker::InMemoryIdTracker;
 use crate::id_tracker::point_mappings::FileEndianess;
+use crate::id_tracker::tombstones::Tombstones;
 use crate::id_tracker::{DELETED_POINT_VERSION, IdTracker};
 use crate::types::{ExtendedPointId, PointIdType, SeqNumberType};
 
@@ -64,6 +65,9 @@ pub struct ImmutableIdTracker {
     internal_to_version_wrapper: MmapSliceBufferedUpdateWrapper<SeqNumberType>,
 
     mappings: CompressedPointMappings,
+
+    /// Soft deleted points, which can still be restored
+    tombstones: Tombstones,
 }
 
 impl ImmutableIdTracker {
@@ -71,9 +75,12 @@ impl ImmutableIdTracker {
         in_memory_tracker: InMemoryIdTracker,
         path: &Path,
     ) -> OperationResult<Self> {
-        let (internal_to_version, mappings) = in_memory_tracker.into_internal();
+        let (internal_to_version, mappings, tombstones) = in_memory_tracker.into_internal();
         let compressed_mappings = CompressedPointMappings::from_mappings(mappings);
-        let id_tracker = Self::new(path, &internal_to_version, compressed_mappings)?;
+        let mut id_tracker = Self::new(path, &internal_to_version, compressed_mappings)?;
+
+        tombstones.move_into(&mut id_tracker.tombstones);
+        id_tracker.tombstones.flusher()()?;
 
         Ok(id_tracker)
     }
@@ -269,12 +276,15 @@ impl ImmutableIdTracker {
         let reader = BufReader::new(File::open(Self::mappings_file_path(segment_path))?);
         let mappings = Self::load_mapping(reader, Some(deleted_bitvec))?;
 
+        let tombstones = Tombstones::open(segment_path)?;
+
         Ok(Self {
             path: segment_path.to_path_buf(),
             deleted_wrapper,
             internal_to_version_wrapper,
             internal_to_version,
             mappings,
+            tombstones,
         })
     }
 
@@ -347,12 +357,15 @@ impl ImmutableIdTracker {
         deleted_wrapper.flusher()()?;
         internal_to_version_wrapper.flusher()()?;
 
+        let tombstones = Tombstones::open(path)?;
+
         Ok(Self {
             path: path.to_path_buf(),
             deleted_wrapper,
             internal_to_version_wrapper,
             internal_to_version,
             mappings,
+            tombstones,
         })
     }
 
@@ -462,8 +475,14 @@ impl IdTracker for ImmutableIdTracker {
 
     /// Creates a flusher function, that writes the deleted points bitvec to disk.
     fn mapping_flusher(&self) -> Flusher {
-        // Only flush deletions because mappings are immutable
-        self.deleted_wrapper.flusher()
+        // Only flush deletions because mappings are immutable.
+        // Tombstones go first, so a soft deleted point is never persisted as dropped without them.
+        let tombstones_flusher = self.tombstones.flusher();
+        let deleted_flusher = self.deleted_wrapper.flusher();
+        Box::new(move || {
+            tombstones_flusher()?;
+            deleted_flusher()
+        })
     }
 
     /// Creates a flusher function, that writes the points versions to disk.
@@ -495,6 +514,14 @@ impl IdTracker for ImmutableIdTracker {
         "immutable id tracker"
     }
 
+    fn tombstones(&self) -> Option<&Tombstones> {
+        Some(&self.tombstones)
+    }
+
+    fn tombstones_mut(&mut self) -> Option<&mut Tombstones> {
+        Some(&mut self.tombstones)
+    }
+
     fn iter_internal_versions(
         &self,
     ) -> Box<dyn Iterator<Item = (PointOffsetType, SeqNumberType)> + '_> {
@@ -502,11 +529,13 @@ impl IdTracker for ImmutableIdTracker {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![
+        let mut files = vec![
             Self::deleted_file_path(&self.path),
             Self::mappings_file_path(&self.path),
             Self::version_mapping_file_path(&self.path),
-        ]
+        ];
+        files.extend(Tombstones::segment_files(&self.path));
+        files
     }
 
     fn immutable_files(&self) -> Vec<PathBuf> {
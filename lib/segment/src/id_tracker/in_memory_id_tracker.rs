@@ -10,6 +10,7 @@ use rand::rngs::StdRng;
 use crate::common::Flusher;
 use crate::common::operation_error::OperationResult;
 use crate::id_tracker::point_mappings::PointMappings;
+use crate::id_tracker::tombstones::Tombstones;
 use crate::id_tracker::{DELETED_POINT_VERSION, IdTracker};
 use crate::types::{PointIdType, SeqNumberType};
 
@@ -18,6 +19,7 @@ use crate::types::{PointIdType, SeqNumberType};
 pub struct InMemoryIdTracker {
     internal_to_version: Vec<SeqNumberType>,
     mappings: PointMappings,
+    tombstones: Tombstones,
 }
 
 impl InMemoryIdTracker {
@@ -25,8 +27,8 @@ impl InMemoryIdTracker {
         Self::default()
     }
 
-    pub fn into_internal(self) -> (Vec<SeqNumberType>, PointMappings) {
-        (self.internal_to_version, self.mappings)
+    pub fn into_internal(self) -> (Vec<SeqNumberType>, PointMappings, Tombstones) {
+        (self.internal_to_version, self.mappings, self.tombstones)
     }
 
     /// Generate a random [`InMemoryIdTracker`].
@@ -35,6 +37,7 @@ impl InMemoryIdTracker {
         Self {
             internal_to_version: vec![rand.random(); size as usize],
             mappings: PointMappings::random_with_params(rand, size, preserved_size, bits_in_id),
+            tombstones: Tombstones::default(),
         }
     }
 }
@@ -151,6 +154,14 @@ impl IdTracker for InMemoryIdTracker {
         "in memory id tracker"
     }
 
+    fn tombstones(&self) -> Option<&Tombstones> {
+        Some(&self.tombstones)
+    }
+
+    fn tombstones_mut(&mut self) -> Option<&mut Tombstones> {
+        Some(&mut self.tombstones)
+    }
+
     fn iter_internal_versions(
         &self,
     ) -> Box<dyn Iterator<Item = (PointOffsetType, SeqNumberType)> + '_> {
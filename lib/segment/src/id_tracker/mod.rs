@@ -6,6 +6,7 @@ pub mod mutable_id_tracker;
 pub mod point_mappings;
 #[cfg(feature = "rocksdb")]
 pub mod simple_id_tracker;
+pub mod tombstones;
 
 use common::types::PointOffsetType;
 pub use id_tracker_base::*;
@@ -17,6 +17,7 @@ use super::point_mappings::FileEndianess;
 use crate::common::Flusher;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::id_tracker::point_mappings::PointMappings;
+use crate::id_tracker::tombstones::Tombstones;
 use crate::id_tracker::{DELETED_POINT_VERSION, IdTracker};
 use crate::types::{PointIdType, SeqNumberType};
 
@@ -107,6 +108,9 @@ pub struct MutableIdTracker {
     /// List of point mappings pending to be persisted, will be persisted on flush
     pending_mappings: Arc<Mutex<Vec<MappingChange>>>,
 
+    /// Soft deleted points, which can still be restored
+    tombstones: Tombstones,
+
     is_alive_lock: IsAliveLock,
 }
 
@@ -166,12 +170,15 @@ impl MutableIdTracker {
         #[cfg(debug_assertions)]
         mappings.assert_mappings();
 
+        let tombstones = Tombstones::open(&segment_path)?;
+
         Ok(Self {
             segment_path,
             internal_to_version,
             mappings,
             pending_versions: Default::default(),
             pending_mappings: Default::default(),
+            tombstones,
             is_alive_lock: IsAliveLock::new(),
         })
     }
@@ -180,6 +187,7 @@ impl MutableIdTracker {
         [mappings_path(segment_path), versions_path(segment_path)]
             .into_iter()
             .filter(|path| path.is_file())
+            .chain(Tombstones::segment_files(segment_path))
             .collect()
     }
 }
@@ -293,10 +301,13 @@ impl IdTracker for MutableIdTracker {
     fn mapping_flusher(&self) -> Flusher {
         let mappings_path = mappings_path(&self.segment_path);
 
+        // Persist tombstones before the mappings they belong to are dropped
+        let tombstones_flusher = self.tombstones.flusher();
+
         let changes = {
             let changes_guard = self.pending_mappings.lock();
             if changes_guard.is_empty() {
-                return Box::new(|| Ok(()));
+                return tombstones_flusher;
             }
             changes_guard.clone()
         };
@@ -312,6 +323,8 @@ impl IdTracker for MutableIdTracker {
                 return Ok(());
             };
 
+            tombstones_flusher()?;
+
             store_mapping_changes(&mappings_path, &changes)?;
 
             reconcile_persisted_mapping_changes(&pending_mappings_arc, &changes);
@@ -365,6 +378,14 @@ impl IdTracker for MutableIdTracker {
         self.mappings.deleted()
     }
 
+    fn tombstones(&self) -> Option<&Tombstones> {
+        Some(&self.tombstones)
+    }
+
+    fn tombstones_mut(&mut self) -> Option<&mut Tombstones> {
+        Some(&mut self.tombstones)
+    }
+
     fn iter_internal_versions(
         &self,
     ) -> Box<dyn Iterator<Item = (PointOffsetType, SeqNumberType)> + '_> {
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ahash::AHashMap;
+use chrono::{DateTime, Utc};
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_bin, read_bin};
+use serde::{Deserialize, Serialize};
+
+use crate::common::Flusher;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::vectors::VectorInternal;
+use crate::types::{Payload, PointIdType, SeqNumberType, VectorNameBuf};
+
+const FILE_TOMBSTONES: &str = "id_tracker.tombstones";
+
+/// Soft deleted point, its vectors and payload are kept in the segment until it is purged
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tombstone {
+    /// Internal ID the point had before it was deleted
+    pub internal_id: PointOffsetType,
+    /// Version of the operation which deleted the point
+    pub version: SeqNumberType,
+    /// When the point was deleted
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Data of a soft deleted point, as read from its segment
+#[derive(Clone, Debug)]
+pub struct TombstonedPoint {
+    /// Version of the operation which deleted the point
+    pub version: SeqNumberType,
+    pub deleted_at: DateTime<Utc>,
+    pub vectors: HashMap<VectorNameBuf, VectorInternal>,
+    pub payload: Payload,
+}
+
+/// Tombstones of soft deleted points of a segment
+///
+/// Soft deleted points are dropped from the ID mappings like any deleted point, so they are
+/// excluded from search and retrieval. Their tombstone remembers where their data is, so that they
+/// can be restored until the tombstone is purged.
+///
+/// All tombstones are rewritten on flush, if any of them changed. This relies on tombstones being
+/// purged regularly, so their number stays small.
+#[derive(Debug, Default)]
+pub struct Tombstones {
+    /// File tombstones are persisted in, `None` for in-memory ID trackers
+    path: Option<PathBuf>,
+    tombstones: AHashMap<PointIdType, Tombstone>,
+    /// Whether tombstones changed since the last flush
+    is_dirty: Arc<AtomicBool>,
+}
+
+impl Tombstones {
+    /// Load tombstones of the segment, or start with none if they were never persisted
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        let path = tombstones_path(segment_path);
+
+        let tombstones = if path.is_file() {
+            let tombstones: Vec<(PointIdType, Tombstone)> = read_bin(&path).map_err(|err| {
+                OperationError::service_error(format!(
+                    "Failed to load ID tracker tombstones: {err}"
+                ))
+            })?;
+            tombstones.into_iter().collect()
+        } else {
+            AHashMap::new()
+        };
+
+        Ok(Self {
+            path: Some(path),
+            tombstones,
+            is_dirty: Default::default(),
+        })
+    }
+
+    pub fn segment_files(segment_path: &Path) -> Vec<PathBuf> {
+        let path = tombstones_path(segment_path);
+        if path.is_file() { vec![path] } else { vec![] }
+    }
+
+    pub fn get(&self, point_id: PointIdType) -> Option<&Tombstone> {
+        self.tombstones.get(&point_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (PointIdType, &Tombstone)> {
+        self.tombstones
+            .iter()
+            .map(|(point_id, tombstone)| (*point_id, tombstone))
+    }
+
+    pub fn len(&self) -> usize {
+        self.tombstones.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tombstones.is_empty()
+    }
+
+    pub fn insert(&mut self, point_id: PointIdType, tombstone: Tombstone) {
+        self.tombstones.insert(point_id, tombstone);
+        self.is_dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn remove(&mut self, point_id: PointIdType) -> Option<Tombstone> {
+        let tombstone = self.tombstones.remove(&point_id)?;
+        self.is_dirty.store(true, Ordering::Relaxed);
+        Some(tombstone)
+    }
+
+    /// Move all tombstones into `other`, e.g. when persisting an in-memory ID tracker
+    pub fn move_into(self, other: &mut Self) {
+        for (point_id, tombstone) in self.tombstones {
+            other.insert(point_id, tombstone);
+        }
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        let Some(path) = self.path.clone() else {
+            return Box::new(|| Ok(()));
+        };
+
+        // Changes made after this point are persisted by the next flusher
+        if !self.is_dirty.swap(false, Ordering::Relaxed) {
+            return Box::new(|| Ok(()));
+        }
+
+        let tombstones: Vec<_> = self.tombstones.iter().map(|(id, t)| (*id, *t)).collect();
+        let is_dirty = self.is_dirty.clone();
+
+        Box::new(move || {
+            atomic_save_bin(&path, &tombstones).map_err(|err| {
+                is_dirty.store(true, Ordering::Relaxed);
+                OperationError::service_error(format!(
+                    "Failed to persist ID tracker tombstones: {err}"
+                ))
+            })
+        })
+    }
+}
+
+fn tombstones_path(segment_path: &Path) -> PathBuf {
+    segment_path.join(FILE_TOMBSTONES)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_tombstones_persistence() {
+        let dir = Builder::new().prefix("tombstones").tempdir().unwrap();
+
+        let mut tombstones = Tombstones::open(dir.path()).unwrap();
+        assert!(tombstones.is_empty());
+
+        let deleted_at = Utc::now();
+        for (point_id, internal_id) in [(10, 0), (20, 1), (30, 2)] {
+            let tombstone = Tombstone {
+                internal_id,
+                version: 5,
+                deleted_at,
+            };
+            tombstones.insert(point_id.into(), tombstone);
+        }
+        tombstones.remove(20.into());
+        tombstones.flusher()().unwrap();
+
+        // Nothing changed, flush is a no-op
+        tombstones.flusher()().unwrap();
+
+        let tombstones = Tombstones::open(dir.path()).unwrap();
+        assert_eq!(tombstones.len(), 2);
+        assert_eq!(tombstones.get(10.into()).unwrap().internal_id, 0);
+        assert_eq!(tombstones.get(30.into()).unwrap().deleted_at, deleted_at);
+        assert!(tombstones.get(20.into()).is_none());
+    }
+}
@@ -36,6 +36,7 @@ use crate::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
 use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::query_plan::VectorSearchStrategy;
 use crate::data_types::vectors::{QueryVector, VectorInternal, VectorRef};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::hnsw_index::HnswM;
@@ -1152,6 +1153,7 @@ impl HNSWIndex {
         params: Option<&SearchParams>,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        vector_query_context.mark_approximate();
         vectors
             .iter()
             .map(|&vector| match vector {
@@ -1340,6 +1342,73 @@ impl HNSWIndex {
         )
     }
 
+    /// Decide whether a filtered search should traverse the graph or score filtered points directly
+    fn filtered_search_uses_graph(
+        &self,
+        query_filter: &Filter,
+        hw_counter: &HardwareCounterCell,
+    ) -> bool {
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let available_vector_count = vector_storage.available_vector_count();
+
+        let query_point_cardinality = payload_index.estimate_cardinality(query_filter, hw_counter);
+        let query_cardinality = adjust_to_available_vectors(
+            query_point_cardinality,
+            available_vector_count,
+            id_tracker.available_point_count(),
+        );
+
+        if query_cardinality.max < self.config.full_scan_threshold {
+            return false;
+        }
+
+        if query_cardinality.min > self.config.full_scan_threshold {
+            return true;
+        }
+
+        // Fast cardinality estimation is not enough, do sample estimation of cardinality
+        let filter_context = payload_index.filter_context(query_filter, hw_counter);
+        sample_check_cardinality(
+            id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+            |idx| filter_context.check(idx),
+            self.config.full_scan_threshold,
+            available_vector_count, // Check cardinality among available vectors
+        )
+    }
+
+    /// Strategy [`VectorIndex::search`] would pick for the given filter and params
+    pub fn search_strategy(
+        &self,
+        filter: Option<&Filter>,
+        params: Option<&SearchParams>,
+        hw_counter: &HardwareCounterCell,
+    ) -> VectorSearchStrategy {
+        if params.is_some_and(|params| params.exact) {
+            return VectorSearchStrategy::Exact;
+        }
+
+        let is_hnsw_disabled = self.config.m == 0 && self.config.payload_m.unwrap_or(0) == 0;
+        if is_hnsw_disabled {
+            return VectorSearchStrategy::Plain;
+        }
+
+        let uses_graph = match filter {
+            None => {
+                self.vector_storage.borrow().available_vector_count()
+                    >= self.config.full_scan_threshold
+            }
+            Some(query_filter) => self.filtered_search_uses_graph(query_filter, hw_counter),
+        };
+
+        if uses_graph {
+            VectorSearchStrategy::Hnsw
+        } else {
+            VectorSearchStrategy::Plain
+        }
+    }
+
     /// Read underlying data from disk into disk cache.
     pub fn populate(&self) -> OperationResult<()> {
         self.graph.populate()
@@ -1380,6 +1449,7 @@ impl VectorIndex for HNSWIndex {
                     ignore: true,
                     rescore: Some(false),
                     oversampling: None,
+                    compare_explanations: false,
                 }); // disable quantization for exact search
                 params
             })
@@ -1439,57 +1509,8 @@ impl VectorIndex for HNSWIndex {
                     );
                 }
 
-                let payload_index = self.payload_index.borrow();
-                let vector_storage = self.vector_storage.borrow();
-                let id_tracker = self.id_tracker.borrow();
-                let available_vector_count = vector_storage.available_vector_count();
-
                 let hw_counter = query_context.hardware_counter();
-
-                let query_point_cardinality =
-                    payload_index.estimate_cardinality(query_filter, &hw_counter);
-                let query_cardinality = adjust_to_available_vectors(
-                    query_point_cardinality,
-                    available_vector_count,
-                    id_tracker.available_point_count(),
-                );
-
-                if query_cardinality.max < self.config.full_scan_threshold {
-                    // if cardinality is small - use plain index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    return self.search_vectors_plain(
-                        vectors,
-                        query_filter,
-                        top,
-                        params,
-                        query_context,
-                    );
-                }
-
-                if query_cardinality.min > self.config.full_scan_threshold {
-                    // if cardinality is high enough - use HNSW index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    return self.search_vectors_with_graph(
-                        vectors,
-                        filter,
-                        top,
-                        params,
-                        query_context,
-                    );
-                }
-
-                let filter_context = payload_index.filter_context(query_filter, &hw_counter);
-
-                // Fast cardinality estimation is not enough, do sample estimation of cardinality
-                let id_tracker = self.id_tracker.borrow();
-                if sample_check_cardinality(
-                    id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
-                    |idx| filter_context.check(idx),
-                    self.config.full_scan_threshold,
-                    available_vector_count, // Check cardinality among available vectors
-                ) {
+                if self.filtered_search_uses_graph(query_filter, &hw_counter) {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
@@ -12,7 +12,7 @@ pub mod sparse_index;
 mod struct_filter_context;
 pub mod struct_payload_index;
 pub mod vector_index_base;
-mod vector_index_search_common;
+pub(crate) mod vector_index_search_common;
 mod visited_pool;
 
 pub use payload_index_base::*;
@@ -8,7 +8,8 @@ use geo::{Distance, Haversine};
 use serde_json::Value;
 
 use super::parsed_formula::{
-    DatetimeExpression, DecayKind, ParsedExpression, ParsedFormula, PreciseScore, VariableId,
+    ConditionId, DatetimeExpression, DecayKind, ParsedExpression, ParsedFormula, PreciseScore,
+    VariableId,
 };
 use super::value_retriever::VariableRetrieverFn;
 use crate::common::operation_error::{OperationError, OperationResult};
@@ -35,6 +36,21 @@ pub struct FormulaScorer<'a> {
     defaults: HashMap<VariableId, Value>,
 }
 
+/// Attribution of a formula score to the conditions which matched the point
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormulaExplanation {
+    /// Conditions which matched the point, in the order they appear in the formula
+    pub fired_conditions: Vec<ConditionContribution>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionContribution {
+    /// Index of the condition in [`ParsedFormula::conditions`]
+    pub condition: ConditionId,
+    /// How much the final score changes because this condition matched
+    pub contribution: ScoreType,
+}
+
 pub trait FriendlyName {
     fn friendly_name() -> &'static str;
 }
@@ -109,6 +125,30 @@ impl FormulaScorer<'_> {
             })
     }
 
+    /// Explain which conditions matched the given point and how much each of them
+    /// contributed to its score.
+    ///
+    /// The contribution of a condition is the difference between the score and the score
+    /// the point would get if that condition had not matched.
+    pub fn explain(&self, point_id: PointOffsetType) -> OperationResult<FormulaExplanation> {
+        let score = self.eval_expression(&self.formula, point_id)?;
+
+        let mut fired_conditions = Vec::new();
+        for (condition, checker) in self.condition_checkers.iter().enumerate() {
+            if !check_condition(checker, point_id) {
+                continue;
+            }
+            let without_condition = self.formula.with_condition_disabled(condition);
+            let score_without = self.eval_expression(&without_condition, point_id)?;
+            fired_conditions.push(ConditionContribution {
+                condition,
+                contribution: (score - score_without) as ScoreType,
+            });
+        }
+
+        Ok(FormulaExplanation { fired_conditions })
+    }
+
     /// Evaluate the expression recursively
     fn eval_expression(
         &self,
@@ -460,6 +500,14 @@ mod tests {
         ParsedExpression::Constant(PreciseScoreOrdered::from(10.0)), ParsedExpression::new_score_id(0), None
     ), 10.0 / 1.0)]
     #[case(ParsedExpression::new_neg(ParsedExpression::Constant(PreciseScoreOrdered::from(10.0))), -10.0)]
+    // Rescoring by popularity: score * ln(1 + popularity)
+    #[case(ParsedExpression::Mult(vec![
+        ParsedExpression::new_score_id(1),
+        ParsedExpression::new_ln(ParsedExpression::Sum(vec![
+            ParsedExpression::Constant(PreciseScoreOrdered::from(1.0)),
+            ParsedExpression::new_payload_id(JsonPath::new(FIELD_NAME)),
+        ])),
+    ]), 2.0 * 86.0_f64.ln())]
     // Error cases
     #[case(ParsedExpression::new_geo_distance(
         GeoPoint::new_unchecked(-100.43383200156751, 25.717877679163667), JsonPath::new(GEO_FIELD_NAME)
@@ -579,4 +627,51 @@ mod tests {
 
         assert_eq!(scorer.eval_expression(&expr, 0), expected);
     }
+
+    #[test]
+    fn test_explain_conditional_boost() {
+        let scores = vec![(0..4).map(|id| (id, id as f32)).collect()];
+
+        // score + 2 if the point matches the first condition, + 3 if it matches the second
+        let formula = ParsedExpression::Sum(vec![
+            ParsedExpression::new_score_id(0),
+            ParsedExpression::Mult(vec![
+                ParsedExpression::Constant(PreciseScoreOrdered::from(2.0)),
+                ParsedExpression::new_condition_id(0),
+            ]),
+            ParsedExpression::Mult(vec![
+                ParsedExpression::Constant(PreciseScoreOrdered::from(3.0)),
+                ParsedExpression::new_condition_id(1),
+            ]),
+        ]);
+
+        let scorer_fixture = ScorerFixture::new(scores, |prefetches_scores| FormulaScorer {
+            formula,
+            prefetches_scores,
+            payload_retrievers: HashMap::new(),
+            condition_checkers: vec![
+                OptimizedCondition::Checker(Box::new(|point_id| point_id % 2 == 0)),
+                OptimizedCondition::Checker(Box::new(|_| false)),
+            ],
+            defaults: HashMap::new(),
+        });
+        let scorer = scorer_fixture.borrow_dependent();
+
+        for point_id in 0..4 {
+            let explanation = scorer.explain(point_id).unwrap();
+            if point_id % 2 == 0 {
+                assert_eq!(
+                    explanation.fired_conditions,
+                    vec![ConditionContribution {
+                        condition: 0,
+                        contribution: 2.0,
+                    }],
+                );
+                assert_eq!(scorer.score(point_id).unwrap(), point_id as f32 + 2.0);
+            } else {
+                assert!(explanation.fired_conditions.is_empty());
+                assert_eq!(scorer.score(point_id).unwrap(), point_id as f32);
+            }
+        }
+    }
 }
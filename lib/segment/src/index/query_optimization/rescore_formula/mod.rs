@@ -1,3 +1,5 @@
 mod formula_scorer;
 pub mod parsed_formula;
 mod value_retriever;
+
+pub use formula_scorer::{ConditionContribution, FormulaExplanation};
@@ -179,6 +179,63 @@ impl ParsedExpression {
         ParsedExpression::Variable(VariableId::Condition(index))
     }
 
+    /// Returns a copy of the expression in which the given condition always evaluates to zero,
+    /// as if it never matched.
+    pub fn with_condition_disabled(&self, condition: ConditionId) -> Self {
+        let disable = |expr: &ParsedExpression| Box::new(expr.with_condition_disabled(condition));
+        match self {
+            ParsedExpression::Variable(VariableId::Condition(id)) if *id == condition => {
+                ParsedExpression::Constant(OrderedFloat(0.0))
+            }
+            ParsedExpression::Constant(_)
+            | ParsedExpression::Variable(_)
+            | ParsedExpression::GeoDistance { .. }
+            | ParsedExpression::Datetime(_) => self.clone(),
+            ParsedExpression::Mult(expressions) => ParsedExpression::Mult(
+                expressions
+                    .iter()
+                    .map(|expr| expr.with_condition_disabled(condition))
+                    .collect(),
+            ),
+            ParsedExpression::Sum(expressions) => ParsedExpression::Sum(
+                expressions
+                    .iter()
+                    .map(|expr| expr.with_condition_disabled(condition))
+                    .collect(),
+            ),
+            ParsedExpression::Div {
+                left,
+                right,
+                by_zero_default,
+            } => ParsedExpression::Div {
+                left: disable(left),
+                right: disable(right),
+                by_zero_default: *by_zero_default,
+            },
+            ParsedExpression::Neg(expr) => ParsedExpression::Neg(disable(expr)),
+            ParsedExpression::Sqrt(expr) => ParsedExpression::Sqrt(disable(expr)),
+            ParsedExpression::Pow { base, exponent } => ParsedExpression::Pow {
+                base: disable(base),
+                exponent: disable(exponent),
+            },
+            ParsedExpression::Exp(expr) => ParsedExpression::Exp(disable(expr)),
+            ParsedExpression::Log10(expr) => ParsedExpression::Log10(disable(expr)),
+            ParsedExpression::Ln(expr) => ParsedExpression::Ln(disable(expr)),
+            ParsedExpression::Abs(expr) => ParsedExpression::Abs(disable(expr)),
+            ParsedExpression::Decay {
+                kind,
+                x,
+                target,
+                lambda,
+            } => ParsedExpression::Decay {
+                kind: *kind,
+                x: disable(x),
+                target: target.as_deref().map(disable),
+                lambda: *lambda,
+            },
+        }
+    }
+
     /// Transforms the constant part of the decay function into a single `lambda` value.
     ///
     /// Graphical representation of the formulas:
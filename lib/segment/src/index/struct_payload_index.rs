@@ -465,8 +465,14 @@ impl StructPayloadIndex {
             index.init()?;
         }
 
+        let id_tracker = self.id_tracker.borrow();
         payload_storage.iter(
             |point_id, point_payload| {
+                // Payload of soft deleted points is kept in storage, but must not be indexed
+                if id_tracker.is_deleted_point(point_id) {
+                    return Ok(true);
+                }
+
                 let field_value = &point_payload.get_value(field);
                 for builder in builders.iter_mut() {
                     builder.add_point(point_id, field_value, hw_counter)?;
@@ -592,7 +598,10 @@ impl StructPayloadIndex {
         crate::rocksdb_backup::restore(snapshot_path, &segment_path.join("payload_index"))
     }
 
-    fn clear_index_for_point(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
+    pub(crate) fn clear_index_for_point(
+        &mut self,
+        point_id: PointOffsetType,
+    ) -> OperationResult<()> {
         for (_, field_indexes) in self.field_indexes.iter_mut() {
             for index in field_indexes {
                 index.remove_point(point_id)?;
@@ -1,5 +1,6 @@
 pub mod common;
 pub mod entry;
+pub mod explain;
 #[cfg(feature = "testing")]
 pub mod fixtures;
 pub mod id_tracker;
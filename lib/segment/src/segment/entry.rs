@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
+use chrono::{DateTime, Utc};
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::TelemetryDetail;
 use fs_err as fs;
@@ -15,13 +16,15 @@ use crate::common::{
 use crate::data_types::build_index_result::BuildFieldIndexResult;
 use crate::data_types::facets::{FacetParams, FacetValue};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::order_by::{OrderBy, OrderValue};
+use crate::data_types::order_by::{OrderBy, OrderedPoint};
 use crate::data_types::query_context::{
     FormulaContext, QueryContext, QueryIdfStats, SegmentQueryContext,
 };
 use crate::data_types::vectors::{QueryVector, VectorInternal};
 use crate::entry::entry_point::{SegmentEntry, SegmentFlushOrdering};
+use crate::id_tracker::tombstones::TombstonedPoint;
 use crate::index::field_index::{CardinalityEstimation, FieldIndex};
+use crate::index::vector_index_search_common::is_quantized_search;
 use crate::index::{BuildIndexResult, PayloadIndex, VectorIndex};
 use crate::json_path::JsonPath;
 use crate::payload_storage::PayloadStorage;
@@ -84,10 +87,40 @@ impl SegmentEntry for Segment {
 
         let hw_counter = vector_query_context.hardware_counter();
 
+        let compare_explanations = params
+            .and_then(|params| params.quantization)
+            .is_some_and(|quantization| quantization.compare_explanations)
+            && is_quantized_search(vector_data.quantized_vectors.borrow().as_ref(), params);
+        let explain_search_path = params.is_some_and(|params| params.explain_search_path);
+        let approximate_search = vector_query_context.is_approximate();
+
         internal_results
             .into_iter()
-            .map(|internal_result| {
-                self.process_search_result(internal_result, with_payload, with_vector, &hw_counter)
+            .zip(query_vectors)
+            .map(|(internal_result, query_vector)| {
+                let mut points = self.process_search_result(
+                    internal_result,
+                    with_payload,
+                    with_vector,
+                    &hw_counter,
+                )?;
+                if compare_explanations {
+                    self.explain_quantization_error(
+                        vector_name,
+                        query_vector,
+                        &mut points,
+                        &hw_counter,
+                    )?;
+                }
+                if explain_search_path {
+                    for point in &mut points {
+                        point
+                            .score_explanation
+                            .get_or_insert_default()
+                            .approximate_search = approximate_search;
+                    }
+                }
+                Ok(points)
             })
             .collect()
     }
@@ -160,6 +193,49 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn delete_point_soft(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        deleted_at: DateTime<Utc>,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<bool> {
+        if self.id_tracker.borrow().tombstones().is_none() {
+            return self.delete_point(op_num, point_id, hw_counter);
+        }
+
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        match internal_id {
+            // Point does already not exist anymore
+            None => Ok(false),
+            Some(internal_id) => {
+                self.handle_point_version_and_failure(op_num, Some(internal_id), |segment| {
+                    segment.tombstone_point_internal(point_id, internal_id, op_num, deleted_at)?;
+
+                    segment.version_tracker.set_payload(Some(op_num));
+
+                    Ok((true, Some(internal_id)))
+                })
+            }
+        }
+    }
+
+    fn tombstoned_point(
+        &self,
+        point_id: PointIdType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<TombstonedPoint>> {
+        let tombstone = self
+            .id_tracker
+            .borrow()
+            .tombstones()
+            .and_then(|tombstones| tombstones.get(point_id).copied());
+
+        tombstone
+            .map(|tombstone| self.read_tombstoned_point(&tombstone, hw_counter))
+            .transpose()
+    }
+
     fn update_vectors(
         &mut self,
         op_num: SeqNumberType,
@@ -386,7 +462,7 @@ impl SegmentEntry for Segment {
         order_by: &'a OrderBy,
         is_stopped: &AtomicBool,
         hw_counter: &HardwareCounterCell,
-    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+    ) -> OperationResult<Vec<OrderedPoint>> {
         match filter {
             None => {
                 self.filtered_read_by_value_stream(order_by, limit, None, is_stopped, hw_counter)
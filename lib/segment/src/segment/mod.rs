@@ -2,10 +2,12 @@ mod entry;
 mod facet;
 mod formula_rescore;
 mod order_by;
+mod query_plan;
 mod sampling;
 mod scroll;
 mod search;
 mod segment_ops;
+mod tombstones;
 mod version_tracker;
 
 pub mod snapshot;
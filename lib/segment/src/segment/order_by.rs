@@ -2,15 +2,19 @@ use std::sync::atomic::AtomicBool;
 
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::iterator_ext::IteratorExt;
+use common::types::PointOffsetType;
 use itertools::Either;
 
 use super::Segment;
 use crate::common::operation_error::{OperationError, OperationResult};
-use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
+use crate::data_types::order_by::{Direction, OrderBy, OrderValue, OrderedPoint};
 use crate::index::PayloadIndex;
+use crate::index::field_index::NumericFieldIndex;
 use crate::index::field_index::numeric_index::StreamRange;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::json_path::JsonPath;
 use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
-use crate::types::{Filter, PointIdType};
+use crate::types::Filter;
 
 impl Segment {
     pub fn filtered_read_by_index_ordered(
@@ -20,17 +24,12 @@ impl Segment {
         condition: &Filter,
         is_stopped: &AtomicBool,
         hw_counter: &HardwareCounterCell,
-    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+    ) -> OperationResult<Vec<OrderedPoint>> {
         let payload_index = self.payload_index.borrow();
         let id_tracker = self.id_tracker.borrow();
 
-        let numeric_index = payload_index
-            .field_indexes
-            .get(&order_by.key)
-            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
-            .ok_or_else(|| OperationError::MissingRangeIndexForOrderBy {
-                key: order_by.key.to_string(),
-            })?;
+        let numeric_index = numeric_index(&payload_index, &order_by.key)?;
+        let then_by = ThenByIndexes::new(&payload_index, order_by)?;
 
         let cardinality_estimation = payload_index.estimate_cardinality(condition, hw_counter);
 
@@ -58,9 +57,26 @@ impl Segment {
             .filter_map(|(value, internal_id)| {
                 id_tracker
                     .external_id(internal_id)
-                    .map(|external_id| (value, external_id))
+                    .map(|external_id| (value, internal_id, external_id))
             });
 
+        // Candidates are already narrowed down by the filter, order all of them by every key
+        if order_by.is_compound() {
+            let mut page: Vec<_> = values_ids_iterator
+                .map(|(value, internal_id, id)| OrderedPoint {
+                    value,
+                    then_by: then_by.values(internal_id),
+                    id,
+                })
+                .collect();
+            page.sort_unstable_by(|a, b| order_by.compare(a, b));
+            page.truncate(limit.unwrap_or(usize::MAX));
+            return Ok(page);
+        }
+
+        let values_ids_iterator =
+            values_ids_iterator.map(|(value, _internal_id, external_id)| (value, external_id));
+
         let page = match order_by.direction() {
             Direction::Asc => {
                 let mut page = match limit {
@@ -80,7 +96,14 @@ impl Segment {
             }
         };
 
-        Ok(page)
+        Ok(page
+            .into_iter()
+            .map(|(value, id)| OrderedPoint {
+                value,
+                then_by: vec![],
+                id,
+            })
+            .collect())
     }
 
     pub fn filtered_read_by_value_stream(
@@ -90,16 +113,11 @@ impl Segment {
         filter: Option<&Filter>,
         is_stopped: &AtomicBool,
         hw_counter: &HardwareCounterCell,
-    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+    ) -> OperationResult<Vec<OrderedPoint>> {
         let payload_index = self.payload_index.borrow();
 
-        let numeric_index = payload_index
-            .field_indexes
-            .get(&order_by.key)
-            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
-            .ok_or_else(|| OperationError::MissingRangeIndexForOrderBy {
-                key: order_by.key.to_string(),
-            })?;
+        let numeric_index = numeric_index(&payload_index, &order_by.key)?;
+        let then_by = ThenByIndexes::new(&payload_index, order_by)?;
 
         let range_iter = numeric_index.stream_range(&order_by.as_range());
 
@@ -127,10 +145,78 @@ impl Segment {
             .filter_map(|(value, internal_id)| {
                 id_tracker
                     .external_id(internal_id)
-                    .map(|external_id| (value, external_id))
+                    .map(|external_id| OrderedPoint {
+                        value,
+                        then_by: then_by.values(internal_id),
+                        id: external_id,
+                    })
+            });
+
+        if !order_by.is_compound() {
+            return Ok(reads.take(limit.unwrap_or(usize::MAX)).collect());
+        }
+
+        // The index only orders by the first key. Read until the page is full and all points
+        // sharing the value of its last point are read, then order these by the other keys.
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut page: Vec<OrderedPoint> = Vec::new();
+        for point in reads {
+            if page.len() >= limit && page.last().is_some_and(|last| last.value != point.value) {
+                break;
+            }
+            page.push(point);
+        }
+        page.sort_by(|a, b| order_by.compare(a, b));
+        page.truncate(limit);
+
+        Ok(page)
+    }
+}
+
+fn numeric_index<'a>(
+    payload_index: &'a StructPayloadIndex,
+    key: &JsonPath,
+) -> OperationResult<NumericFieldIndex<'a>> {
+    payload_index
+        .field_indexes
+        .get(key)
+        .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
+        .ok_or_else(|| OperationError::MissingRangeIndexForOrderBy {
+            key: key.to_string(),
+        })
+}
+
+/// Indexes of the `then_by` keys of an [`OrderBy`], to look up the values a point is ordered by
+struct ThenByIndexes<'a> {
+    indexes: Vec<(NumericFieldIndex<'a>, Direction)>,
+}
+
+impl<'a> ThenByIndexes<'a> {
+    fn new(payload_index: &'a StructPayloadIndex, order_by: &OrderBy) -> OperationResult<Self> {
+        let indexes = order_by
+            .then_by
+            .iter()
+            .map(|then_by| {
+                Ok((
+                    numeric_index(payload_index, &then_by.key)?,
+                    then_by.direction(),
+                ))
+            })
+            .collect::<OperationResult<_>>()?;
+        Ok(Self { indexes })
+    }
+
+    /// Value of every key for the point, the first one in the key's direction if there are several
+    fn values(&self, internal_id: PointOffsetType) -> Vec<Option<OrderValue>> {
+        self.indexes
+            .iter()
+            .map(|(index, direction)| {
+                let values = index.get_ordering_values(internal_id);
+                match direction {
+                    Direction::Asc => values.min(),
+                    Direction::Desc => values.max(),
+                }
             })
-            .take(limit.unwrap_or(usize::MAX))
-            .collect();
-        Ok(reads)
+            .collect()
     }
 }
@@ -0,0 +1,66 @@
+use common::counter::hardware_counter::HardwareCounterCell;
+
+use super::Segment;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::query_plan::{FilterPlan, SegmentSearchPlan, VectorSearchStrategy};
+use crate::entry::entry_point::SegmentEntry;
+use crate::index::{PayloadIndex, VectorIndexEnum};
+use crate::types::{Filter, SearchParams, VectorName};
+
+impl Segment {
+    /// Describe how this segment would execute a search, without running it.
+    ///
+    /// If `vector_name` is not set, only the filter is planned, as for scrolls.
+    pub fn search_plan(
+        &self,
+        vector_name: Option<&VectorName>,
+        filter: Option<&Filter>,
+        params: Option<&SearchParams>,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<SegmentSearchPlan> {
+        let strategy = vector_name
+            .map(|vector_name| {
+                let vector_data = self
+                    .vector_data
+                    .get(vector_name)
+                    .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))?;
+                let strategy = match &*vector_data.vector_index.borrow() {
+                    VectorIndexEnum::Plain(_) => {
+                        if params.is_some_and(|params| params.exact) {
+                            VectorSearchStrategy::Exact
+                        } else {
+                            VectorSearchStrategy::Plain
+                        }
+                    }
+                    VectorIndexEnum::Hnsw(index) => {
+                        index.search_strategy(filter, params, hw_counter)
+                    }
+                    VectorIndexEnum::SparseRam(_)
+                    | VectorIndexEnum::SparseImmutableRam(_)
+                    | VectorIndexEnum::SparseMmap(_)
+                    | VectorIndexEnum::SparseCompressedImmutableRamF32(_)
+                    | VectorIndexEnum::SparseCompressedImmutableRamF16(_)
+                    | VectorIndexEnum::SparseCompressedImmutableRamU8(_)
+                    | VectorIndexEnum::SparseCompressedMmapF32(_)
+                    | VectorIndexEnum::SparseCompressedMmapF16(_)
+                    | VectorIndexEnum::SparseCompressedMmapU8(_) => VectorSearchStrategy::Sparse,
+                };
+                OperationResult::Ok(strategy)
+            })
+            .transpose()?;
+
+        let filter = filter.map(|filter| {
+            let estimation = self
+                .payload_index
+                .borrow()
+                .estimate_cardinality(filter, hw_counter);
+            FilterPlan::from_estimation(&estimation)
+        });
+
+        Ok(SegmentSearchPlan {
+            points: self.available_point_count(),
+            strategy,
+            filter,
+        })
+    }
+}
@@ -6,16 +6,13 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::named_vectors::NamedVectors;
 #[cfg(feature = "testing")]
 use crate::data_types::query_context::QueryContext;
-#[cfg(feature = "testing")]
-use crate::data_types::vectors::QueryVector;
-use crate::data_types::vectors::VectorStructInternal;
+use crate::data_types::vectors::{QueryVector, VectorInternal, VectorStructInternal};
 #[cfg(feature = "testing")]
 use crate::entry::entry_point::SegmentEntry;
-#[cfg(feature = "testing")]
-use crate::types::VectorName;
+use crate::spaces::explainability::{ExplanationConfig, compute_quantization_error_explanation};
 #[cfg(feature = "testing")]
 use crate::types::{Filter, SearchParams};
-use crate::types::{ScoredPoint, WithPayload, WithVector};
+use crate::types::{ScoredPoint, VectorName, WithPayload, WithVector};
 
 impl Segment {
     /// Converts raw ScoredPointOffset search result into ScoredPoint result
@@ -94,6 +91,61 @@ impl Segment {
             .collect()
     }
 
+    /// Explain `points` found by a quantized search of `query_vector` with their original vectors,
+    /// along with how much the quantization changes the contribution of each dimension,
+    /// see [`compute_quantization_error_explanation`].
+    ///
+    /// Only nearest queries of dense vectors are explained, other points are left unexplained.
+    pub(super) fn explain_quantization_error(
+        &self,
+        vector_name: &VectorName,
+        query_vector: &QueryVector,
+        points: &mut [ScoredPoint],
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        let QueryVector::Nearest(VectorInternal::Dense(query)) = query_vector else {
+            return Ok(());
+        };
+        let Some(vector_data) = self.vector_data.get(vector_name) else {
+            return Ok(());
+        };
+        let quantized_vectors = vector_data.quantized_vectors.borrow();
+        let Some(quantized_vectors) = quantized_vectors.as_ref() else {
+            return Ok(());
+        };
+        let Some(vector_config) = self.segment_config.vector_data.get(vector_name) else {
+            return Ok(());
+        };
+        let distance = vector_config.distance;
+        // Errors of all dimensions, the explanation of the request keeps the ones of its top dimensions
+        let config = ExplanationConfig {
+            top_n: query.len(),
+            ..Default::default()
+        };
+
+        let id_tracker = self.id_tracker.borrow();
+        for point in points {
+            let Some(point_offset) = id_tracker.internal_id(point.id) else {
+                continue;
+            };
+            let Some(VectorInternal::Dense(original)) =
+                self.vector_by_offset(vector_name, point_offset, hw_counter)?
+            else {
+                continue;
+            };
+            point.score_explanation = compute_quantization_error_explanation(
+                distance,
+                query,
+                &original,
+                quantized_vectors,
+                point_offset,
+                &config,
+            )
+            .ok();
+        }
+        Ok(())
+    }
+
     /// This function is a simplified version of `search_batch` intended for testing purposes.
     #[allow(clippy::too_many_arguments)]
     #[cfg(feature = "testing")]
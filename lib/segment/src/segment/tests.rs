@@ -718,3 +718,73 @@ fn test_handle_point_version() {
         .unwrap();
     assert!(applied);
 }
+
+#[test]
+fn test_soft_delete_point() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let dim = 2;
+
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), dim, Distance::Dot).unwrap();
+    segment
+        .upsert_point(100, 1.into(), only_default_vector(&[1.0, 0.0]), &hw_counter)
+        .unwrap();
+    segment
+        .upsert_point(101, 2.into(), only_default_vector(&[0.0, 1.0]), &hw_counter)
+        .unwrap();
+
+    let payload: Payload = serde_json::from_str(r#"{"color": "red"}"#).unwrap();
+    segment
+        .set_full_payload(102, 1.into(), &payload, &hw_counter)
+        .unwrap();
+
+    let deleted_at = chrono::Utc::now();
+    assert!(
+        segment
+            .delete_point_soft(103, 1.into(), deleted_at, &hw_counter)
+            .unwrap()
+    );
+
+    // Soft deleted point is gone for readers, but its data is kept in its tombstone
+    assert_eq!(segment.available_point_count(), 1);
+    assert!(!segment.has_point(1.into()));
+    assert_eq!(segment.tombstone_count(), 1);
+
+    let tombstoned = segment
+        .tombstoned_point(1.into(), &hw_counter)
+        .unwrap()
+        .unwrap();
+    assert_eq!(tombstoned.version, 103);
+    assert_eq!(tombstoned.deleted_at, deleted_at);
+    assert_eq!(tombstoned.payload, payload);
+    assert!(tombstoned.vectors.contains_key(DEFAULT_VECTOR_NAME));
+    assert!(
+        segment
+            .tombstoned_point(2.into(), &hw_counter)
+            .unwrap()
+            .is_none()
+    );
+
+    // Tombstones survive a reload
+    segment.flush(true).unwrap();
+    let segment_path = segment.current_path.clone();
+    drop(segment);
+    let mut segment = load_segment(&segment_path, &AtomicBool::new(false))
+        .unwrap()
+        .unwrap();
+    assert_eq!(segment.tombstone_count(), 1);
+    assert!(!segment.has_point(1.into()));
+
+    // Only tombstones older than the cutoff are purged
+    assert_eq!(segment.purge_tombstones(deleted_at).unwrap(), 0);
+    let cutoff = deleted_at + chrono::Duration::seconds(1);
+    assert_eq!(segment.purge_tombstones(cutoff).unwrap(), 1);
+    assert_eq!(segment.tombstone_count(), 0);
+    assert!(
+        segment
+            .tombstoned_point(1.into(), &hw_counter)
+            .unwrap()
+            .is_none()
+    );
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::types::PointOffsetType;
+
+use super::Segment;
+use crate::common::operation_error::OperationResult;
+use crate::data_types::vectors::VectorInternal;
+use crate::id_tracker::tombstones::{Tombstone, TombstonedPoint};
+use crate::index::PayloadIndex;
+use crate::types::{PointIdType, SeqNumberType, VectorNameBuf};
+use crate::vector_storage::{Random, VectorStorage};
+
+impl Segment {
+    /// Soft delete point: drop its mapping and remove it from payload indexes, but keep its
+    /// vectors and payload in storage, so it can be restored from its tombstone
+    pub(super) fn tombstone_point_internal(
+        &mut self,
+        point_id: PointIdType,
+        internal_id: PointOffsetType,
+        op_num: SeqNumberType,
+        deleted_at: DateTime<Utc>,
+    ) -> OperationResult<()> {
+        self.payload_index
+            .borrow_mut()
+            .clear_index_for_point(internal_id)?;
+
+        let mut id_tracker = self.id_tracker.borrow_mut();
+        id_tracker.drop_internal(internal_id)?;
+        if let Some(tombstones) = id_tracker.tombstones_mut() {
+            let tombstone = Tombstone {
+                internal_id,
+                version: op_num,
+                deleted_at,
+            };
+            tombstones.insert(point_id, tombstone);
+        }
+
+        Ok(())
+    }
+
+    /// Read data of a soft deleted point from storage
+    pub(super) fn read_tombstoned_point(
+        &self,
+        tombstone: &Tombstone,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<TombstonedPoint> {
+        let internal_id = tombstone.internal_id;
+
+        // Not using `vector_by_offset`, as it refuses deleted points
+        let mut vectors: HashMap<VectorNameBuf, VectorInternal> = HashMap::new();
+        for (vector_name, vector_data) in &self.vector_data {
+            let vector_storage = vector_data.vector_storage.borrow();
+            if vector_storage.total_vector_count() <= internal_id as usize
+                || vector_storage.is_deleted_vector(internal_id)
+            {
+                continue;
+            }
+
+            let vector = vector_storage.get_vector::<Random>(internal_id);
+            if vector_storage.is_on_disk() {
+                hw_counter
+                    .vector_io_read()
+                    .incr_delta(vector.estimate_size_in_bytes());
+            }
+            vectors.insert(vector_name.clone(), vector.to_owned());
+        }
+
+        let payload = self.payload_by_offset(internal_id, hw_counter)?;
+
+        Ok(TombstonedPoint {
+            version: tombstone.version,
+            deleted_at: tombstone.deleted_at,
+            vectors,
+            payload,
+        })
+    }
+
+    /// Number of soft deleted points kept in this segment
+    pub fn tombstone_count(&self) -> usize {
+        self.id_tracker
+            .borrow()
+            .tombstones()
+            .map_or(0, |tombstones| tombstones.len())
+    }
+
+    /// Remove the tombstone of a soft deleted point and clear its payload, so it can't be
+    /// restored anymore
+    ///
+    /// Returns `false` if the point has no tombstone in this segment.
+    pub fn remove_tombstone(&mut self, point_id: PointIdType) -> OperationResult<bool> {
+        let removed = self
+            .id_tracker
+            .borrow_mut()
+            .tombstones_mut()
+            .and_then(|tombstones| tombstones.remove(point_id));
+
+        let Some(tombstone) = removed else {
+            return Ok(false);
+        };
+
+        // Vectors are left in place, like for regular deletions
+        let hw_counter = HardwareCounterCell::disposable(); // Internal operation
+        self.payload_index
+            .borrow_mut()
+            .clear_payload(tombstone.internal_id, &hw_counter)?;
+
+        Ok(true)
+    }
+
+    /// Remove all tombstones of points deleted before `deleted_before`
+    ///
+    /// Returns the number of purged tombstones.
+    pub fn purge_tombstones(&mut self, deleted_before: DateTime<Utc>) -> OperationResult<usize> {
+        let expired: Vec<_> = self
+            .id_tracker
+            .borrow()
+            .tombstones()
+            .into_iter()
+            .flat_map(|tombstones| tombstones.iter())
+            .filter(|(_, tombstone)| tombstone.deleted_at < deleted_before)
+            .map(|(point_id, _)| point_id)
+            .collect();
+
+        for &point_id in &expired {
+            self.remove_tombstone(point_id)?;
+        }
+
+        Ok(expired.len())
+    }
+}
@@ -1,12 +1,13 @@
 use std::cmp;
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
-use ahash::AHasher;
+use ahash::{AHashMap, AHasher};
 use atomic_refcell::AtomicRefCell;
 use bitvec::macros::internal::funty::Integral;
 use common::budget::ResourcePermit;
@@ -32,9 +33,9 @@ use super::{
 use crate::common::error_logging::LogError;
 use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
 use crate::entry::entry_point::SegmentEntry;
-use crate::id_tracker::compressed::compressed_point_mappings::CompressedPointMappings;
 use crate::id_tracker::immutable_id_tracker::ImmutableIdTracker;
 use crate::id_tracker::in_memory_id_tracker::InMemoryIdTracker;
+use crate::id_tracker::tombstones::Tombstone;
 use crate::id_tracker::{IdTracker, IdTrackerEnum, for_each_unique_point};
 use crate::index::field_index::FieldIndex;
 use crate::index::sparse_index::sparse_vector_index::SparseVectorIndexOpenArgs;
@@ -49,7 +50,7 @@ use crate::segment_constructor::{
 };
 use crate::types::{
     CompactExtendedPointId, ExtendedPointId, HnswGlobalConfig, PayloadFieldSchema, PayloadKeyType,
-    SegmentConfig, SegmentState, SeqNumberType, VectorNameBuf,
+    PointIdType, SegmentConfig, SegmentState, SeqNumberType, VectorNameBuf,
 };
 use crate::vector_storage::quantized::quantized_vectors::{
     QuantizedVectors, QuantizedVectorsStorageType,
@@ -306,6 +307,47 @@ impl SegmentBuilder {
                 ordering: 0,
             });
         });
+
+        // Carry over the newest tombstone of points not alive in any source segment,
+        // so soft deleted points stay restorable after optimization
+        let mut tombstones_to_insert: AHashMap<PointIdType, (usize, Tombstone)> = AHashMap::new();
+        if self.id_tracker.tombstones().is_some() {
+            for (segment_index, id_tracker) in locked_id_trackers.iter().enumerate() {
+                let Some(tombstones) = id_tracker.tombstones() else {
+                    continue;
+                };
+
+                for (point_id, tombstone) in tombstones.iter() {
+                    let is_alive = locked_id_trackers
+                        .iter()
+                        .any(|id_tracker| id_tracker.internal_id(point_id).is_some());
+                    if is_alive {
+                        continue;
+                    }
+
+                    match tombstones_to_insert.entry(point_id) {
+                        Entry::Occupied(mut entry) => {
+                            if entry.get().1.version < tombstone.version {
+                                entry.insert((segment_index, *tombstone));
+                            }
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert((segment_index, *tombstone));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (point_id, (segment_index, tombstone)) in &tombstones_to_insert {
+            points_to_insert.push(PointData {
+                external_id: CompactExtendedPointId::from(*point_id),
+                segment_index: U24::new_wrapped(*segment_index as u32),
+                internal_id: tombstone.internal_id,
+                version: tombstone.version,
+                ordering: 0,
+            });
+        }
         drop(locked_id_trackers);
 
         let payloads: Vec<_> = segments.iter().map(|i| i.payload_index.borrow()).collect();
@@ -449,6 +491,19 @@ impl SegmentBuilder {
                     &HardwareCounterCell::disposable(),
                 )?;
             }
+
+            // Soft deleted points are only linked to copy their data, drop them again
+            let external_id = ExtendedPointId::from(point_data.external_id);
+            if let Some((_, tombstone)) = tombstones_to_insert.get(&external_id) {
+                self.id_tracker.drop_internal(new_internal_id)?;
+                if let Some(tombstones) = self.id_tracker.tombstones_mut() {
+                    let tombstone = Tombstone {
+                        internal_id: new_internal_id,
+                        ..*tombstone
+                    };
+                    tombstones.insert(external_id, tombstone);
+                }
+            }
         }
 
         for payload in payloads {
@@ -502,10 +557,10 @@ impl SegmentBuilder {
 
             let id_tracker = match id_tracker {
                 IdTrackerEnum::InMemoryIdTracker(in_memory_id_tracker) => {
-                    let (versions, mappings) = in_memory_id_tracker.into_internal();
-                    let compressed_mapping = CompressedPointMappings::from_mappings(mappings);
-                    let immutable_id_tracker =
-                        ImmutableIdTracker::new(temp_dir.path(), &versions, compressed_mapping)?;
+                    let immutable_id_tracker = ImmutableIdTracker::from_in_memory_tracker(
+                        in_memory_id_tracker,
+                        temp_dir.path(),
+                    )?;
                     IdTrackerEnum::ImmutableIdTracker(immutable_id_tracker)
                 }
                 IdTrackerEnum::MutableIdTracker(_) => id_tracker,
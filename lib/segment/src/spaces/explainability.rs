@@ -1,12 +1,72 @@
+//! Per-dimension score explanations,
 //! allowing users to understand which dimensions contributed most to the similarity score.
 
-use common::types::{DimensionContribution, ScoreExplanation, ScoreType};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
-use crate::data_types::vectors::VectorElementType;
-use crate::types::Distance;
+use ahash::AHashMap;
+use common::types::{
+    DimensionContribution, DominantExample, ExampleKind, ExplanationSpace, FusionExplanation,
+    FusionMethod, FusionSource, PointOffsetType, ScoreExplanation, ScoreType, ScoredPointOffset,
+    TieBreak, UnavailableReason, VectorSource,
+};
+use sparse::common::sparse_vector::SparseVector;
+use thiserror::Error;
+
+use crate::common::reciprocal_rank_fusion::position_score;
+use crate::common::score_fusion::distr_norm;
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{
+    DenseVector, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf,
+};
+use crate::types::{
+    Distance, Payload, PointIdType, ScoredPoint, VectorName, VectorStorageDatatype,
+};
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::query::DiscoveryQuery;
 
 pub const DEFAULT_TOP_DIMENSIONS: usize = 10;
 
+/// Columns of the rows produced by [`explanations_to_csv`] and [`explanations_to_tsv`]
+const EXPORT_COLUMNS: [&str; 4] = ["point_id", "dimension", "contribution", "percentage"];
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ExplainError {
+    #[error("Vector dimension mismatch: query has {query_dim} dimensions, stored has {stored_dim}")]
+    DimensionMismatch { query_dim: usize, stored_dim: usize },
+    #[error("Explanation is not supported: {description}")]
+    Unsupported { description: String },
+    #[error("No examples to build the explained query from")]
+    NoExamples,
+    #[error("Dimension {dimension} is out of range for vectors of {dim} dimensions")]
+    DimensionOutOfRange { dimension: usize, dim: usize },
+}
+
+/// Parameters controlling how an explanation is built
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExplanationConfig {
+    /// Number of top contributing dimensions to keep
+    pub top_n: usize,
+    /// Express contributions in the space of the inserted vectors, inverting ingest preprocessing
+    pub original_space: bool,
+    /// Order of dimensions with equal-magnitude contributions
+    pub tie_break: TieBreak,
+    /// Represent both vectors in this datatype before explaining,
+    /// e.g. to explain vectors stored as bytes in the float space. `None` keeps them as given
+    pub datatype: Option<VectorStorageDatatype>,
+}
+
+impl Default for ExplanationConfig {
+    fn default() -> Self {
+        Self {
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            original_space: false,
+            tie_break: TieBreak::default(),
+            datatype: None,
+        }
+    }
+}
 
 /// For dot product, the contribution of each dimension is simply `v1[i] * v2[i]`.
 /// The total score is the sum of all contributions.
@@ -20,6 +80,7 @@ pub fn dot_product_contributions(
         .map(|(dimension, (a, b))| DimensionContribution {
             dimension,
             contribution: a * b,
+            label: None,
         })
         .collect()
 }
@@ -39,29 +100,31 @@ pub fn euclidean_contributions(
             DimensionContribution {
                 dimension,
                 contribution: -(diff * diff), // Negative because smaller distance = more similar
+                label: None,
             }
         })
         .collect()
 }
 
-
 /// For cosine similarity, we compute the contribution of each dimension to the dot product
 /// portion of the formula. The contributions are normalized by the product of the norms.
 pub fn cosine_contributions(
     v1: &[VectorElementType],
     v2: &[VectorElementType],
 ) -> Vec<DimensionContribution> {
-    let norm1: ScoreType = v1.iter().map(|x| x * x).sum::<ScoreType>().sqrt();
-    let norm2: ScoreType = v2.iter().map(|x| x * x).sum::<ScoreType>().sqrt();
-    
+    let norm1 = l2_norm(v1);
+    let norm2 = l2_norm(v2);
+
     let denominator = norm1 * norm2;
     if denominator == 0.0 {
         // If either vector has zero norm, all contributions are zero
-        return v1.iter()
+        return v1
+            .iter()
             .enumerate()
             .map(|(dimension, _)| DimensionContribution {
                 dimension,
                 contribution: 0.0,
+                label: None,
             })
             .collect();
     }
@@ -72,6 +135,7 @@ pub fn cosine_contributions(
         .map(|(dimension, (a, b))| DimensionContribution {
             dimension,
             contribution: (a * b) / denominator,
+            label: None,
         })
         .collect()
 }
@@ -88,11 +152,56 @@ pub fn manhattan_contributions(
         .map(|(dimension, (a, b))| DimensionContribution {
             dimension,
             contribution: -(a - b).abs(),
+            label: None,
+        })
+        .collect()
+}
+
+/// Contribution of each dimension relative to a baseline mean vector: `(v2[i] - mean[i]) * v1[i]`.
+///
+/// Dimensions where the stored vector `v2` is unusual compared to the mean get larger contributions,
+/// even if their absolute magnitude is small.
+pub fn delta_from_mean_contributions(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+    mean: &[VectorElementType],
+) -> Vec<DimensionContribution> {
+    v1.iter()
+        .zip(v2.iter())
+        .zip(mean.iter())
+        .enumerate()
+        .map(
+            |(dimension, ((query, stored), mean))| DimensionContribution {
+                dimension,
+                contribution: (stored - mean) * query,
+                label: None,
+            },
+        )
+        .collect()
+}
+
+/// For sparse vectors, only indices present in both vectors contribute, by `query[i] * stored[i]`.
+/// The dimension of a contribution is the sparse index.
+pub fn sparse_contributions(
+    query: &SparseVector,
+    stored: &SparseVector,
+) -> Vec<DimensionContribution> {
+    let stored: AHashMap<_, _> = stored.indices.iter().zip(&stored.values).collect();
+    query
+        .indices
+        .iter()
+        .zip(&query.values)
+        .filter_map(|(index, query_value)| {
+            let stored_value = stored.get(index)?;
+            Some(DimensionContribution {
+                dimension: *index as usize,
+                contribution: query_value * *stored_value,
+                label: None,
+            })
         })
         .collect()
 }
 
-/// Compute per-dimension contributions based on the distance metric.
 pub fn compute_contributions(
     distance: Distance,
     v1: &[VectorElementType],
@@ -106,14 +215,18 @@ pub fn compute_contributions(
     }
 }
 
+fn l2_norm(vector: &[VectorElementType]) -> ScoreType {
+    vector.iter().map(|x| x * x).sum::<ScoreType>().sqrt()
+}
+
 /// Compute a score explanation for the similarity between two vectors.
-/// 
+///
 /// # Arguments
 /// * `distance` - The distance metric used for similarity
 /// * `v1` - The first vector (typically the query vector)
 /// * `v2` - The second vector (typically the stored vector)
 /// * `top_n` - Number of top contributing dimensions to include (default: 10)
-/// 
+///
 /// # Returns
 /// A `ScoreExplanation` containing the top N dimensions that contributed most to the score.
 pub fn compute_explanation(
@@ -123,20 +236,1092 @@ pub fn compute_explanation(
     top_n: Option<usize>,
 ) -> ScoreExplanation {
     let contributions = compute_contributions(distance, v1, v2);
+    let mut explanation =
+        ScoreExplanation::new(contributions, top_n.unwrap_or(DEFAULT_TOP_DIMENSIONS));
+    explanation.total_score = distance.postprocess_score(explanation.total_score);
+    explanation.query_norm = Some(l2_norm(v1));
+    explanation.stored_norm = Some(l2_norm(v2));
+    explanation
+}
+
+/// How much the score of `v1` and `v2` drops if each dimension is removed from both vectors.
+///
+/// Scores are compared before postprocessing, so that a positive contribution always means
+/// the dimension makes the vectors more similar. For dot product, Euclidean and Manhattan
+/// distances this is the same as [`compute_contributions`], as their scores are sums over
+/// dimensions. Cosine similarity is re-normalized by the norms of the vectors without the dimension.
+pub fn counterfactual_contributions(
+    distance: Distance,
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> Vec<DimensionContribution> {
+    match distance {
+        Distance::Cosine => cosine_counterfactual_contributions(v1, v2),
+        Distance::Dot | Distance::Euclid | Distance::Manhattan => {
+            compute_contributions(distance, v1, v2)
+        }
+    }
+}
+
+fn cosine_counterfactual_contributions(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> Vec<DimensionContribution> {
+    let cosine = |dot: ScoreType, norm1_sq: ScoreType, norm2_sq: ScoreType| {
+        // Removing a dimension may leave a slightly negative squared norm from rounding
+        let denominator = (norm1_sq.max(0.0) * norm2_sq.max(0.0)).sqrt();
+        if denominator == 0.0 {
+            0.0
+        } else {
+            dot / denominator
+        }
+    };
+
+    let dot: ScoreType = v1.iter().zip(v2).map(|(a, b)| a * b).sum();
+    let norm1_sq: ScoreType = v1.iter().map(|a| a * a).sum();
+    let norm2_sq: ScoreType = v2.iter().map(|b| b * b).sum();
+    let score = cosine(dot, norm1_sq, norm2_sq);
+
+    v1.iter()
+        .zip(v2)
+        .enumerate()
+        .map(|(dimension, (a, b))| DimensionContribution {
+            dimension,
+            contribution: score - cosine(dot - a * b, norm1_sq - a * a, norm2_sq - b * b),
+            label: None,
+        })
+        .collect()
+}
+
+/// Same as [`compute_explanation`], with the [`counterfactual_contributions`] of the dimensions.
+///
+/// Counterfactual contributions of cosine similarity don't add up to the score,
+/// so the total score is computed from the vectors instead.
+pub fn compute_counterfactual_explanation(
+    distance: Distance,
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+    top_n: Option<usize>,
+) -> ScoreExplanation {
+    let score: ScoreType = compute_contributions(distance, v1, v2)
+        .iter()
+        .map(|dim| dim.contribution)
+        .sum();
+    let contributions = counterfactual_contributions(distance, v1, v2);
+    let mut explanation =
+        ScoreExplanation::new(contributions, top_n.unwrap_or(DEFAULT_TOP_DIMENSIONS));
+    explanation.total_score = distance.postprocess_score(score);
+    explanation.query_norm = Some(l2_norm(v1));
+    explanation.stored_norm = Some(l2_norm(v2));
+    explanation
+}
+
+/// Same as [`compute_explanation`], for sparse vectors, which are always scored by dot product
+pub fn compute_sparse_explanation(
+    query: &SparseVector,
+    stored: &SparseVector,
+    top_n: Option<usize>,
+) -> ScoreExplanation {
+    let contributions = sparse_contributions(query, stored);
     ScoreExplanation::new(contributions, top_n.unwrap_or(DEFAULT_TOP_DIMENSIONS))
 }
 
+/// Keep the `top_k` indices of the query with the largest absolute weights,
+/// as a query pruned before scoring would.
+pub fn prune_sparse_query(query: &SparseVector, top_k: usize) -> SparseVector {
+    let mut entries: Vec<_> = query
+        .indices
+        .iter()
+        .copied()
+        .zip(query.values.iter().copied())
+        .collect();
+    entries.sort_by(|(_, a), (_, b)| b.abs().total_cmp(&a.abs()));
+    entries.truncate(top_k);
+
+    let (indices, values) = entries.into_iter().unzip();
+    SparseVector { indices, values }
+}
+
+/// Same as [`compute_sparse_explanation`], for a query pruned to its `query_top_k` indices
+/// before scoring. Pruned out indices don't contribute, even if the stored vector has them.
+pub fn compute_pruned_sparse_explanation(
+    query: &SparseVector,
+    stored: &SparseVector,
+    query_top_k: usize,
+    top_n: Option<usize>,
+) -> ScoreExplanation {
+    compute_sparse_explanation(&prune_sparse_query(query, query_top_k), stored, top_n)
+}
+
+/// Explain a stored vector `v2` relative to the collection `mean`, see [`delta_from_mean_contributions`].
+pub fn compute_delta_from_mean_explanation(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+    mean: &[VectorElementType],
+    config: &ExplanationConfig,
+) -> Result<ScoreExplanation, ExplainError> {
+    for other in [v2, mean] {
+        if v1.len() != other.len() {
+            return Err(ExplainError::DimensionMismatch {
+                query_dim: v1.len(),
+                stored_dim: other.len(),
+            });
+        }
+    }
+    let contributions = delta_from_mean_contributions(v1, v2, mean);
+    Ok(ScoreExplanation::with_tie_break(
+        contributions,
+        config.top_n,
+        config.tie_break,
+    ))
+}
+
+/// Explain how the `stored` vector deviates from the collection `centroid`.
+///
+/// Contributions are Euclidean, whatever the distance of the collection: the dimensions
+/// where the point is furthest from a typical point get the largest (negative) contributions.
+pub fn compute_baseline_explanation(
+    stored: &[VectorElementType],
+    centroid: &[VectorElementType],
+    config: &ExplanationConfig,
+) -> Result<ScoreExplanation, ExplainError> {
+    if stored.len() != centroid.len() {
+        return Err(ExplainError::DimensionMismatch {
+            query_dim: centroid.len(),
+            stored_dim: stored.len(),
+        });
+    }
+    let contributions = euclidean_contributions(centroid, stored);
+    Ok(ScoreExplanation::with_tie_break(
+        contributions,
+        config.top_n,
+        config.tie_break,
+    ))
+}
+
+/// Per-dimension mean of a set of vectors, accumulated incrementally.
+///
+/// Meant to be computed once per collection and kept around as the baseline of
+/// [`compute_delta_from_mean_explanation`].
+#[derive(Clone, Debug, Default)]
+pub struct MeanVector {
+    sum: Vec<f64>,
+    count: usize,
+}
+
+impl MeanVector {
+    pub fn add(&mut self, vector: &[VectorElementType]) -> Result<(), ExplainError> {
+        if self.count == 0 {
+            self.sum = vec![0.0; vector.len()];
+        } else if self.sum.len() != vector.len() {
+            return Err(ExplainError::DimensionMismatch {
+                query_dim: self.sum.len(),
+                stored_dim: vector.len(),
+            });
+        }
+
+        for (acc, value) in self.sum.iter_mut().zip(vector) {
+            *acc += f64::from(*value);
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `None` if no vectors were added
+    pub fn mean(&self) -> Option<DenseVector> {
+        if self.count == 0 {
+            return None;
+        }
+        let count = self.count as f64;
+        Some(
+            self.sum
+                .iter()
+                .map(|sum| (sum / count) as VectorElementType)
+                .collect(),
+        )
+    }
+}
+
+/// Whether vectors of the given distance are preprocessed on ingest,
+/// so that stored vectors differ from the inserted ones.
+///
+/// Only cosine applies a preprocessing: vectors are L2-normalized.
+pub fn is_preprocessed_on_ingest(distance: Distance) -> bool {
+    matches!(distance, Distance::Cosine)
+}
+
+/// Space of the contributions computed on stored vectors of the given distance
+pub fn stored_vectors_space(distance: Distance) -> ExplanationSpace {
+    if is_preprocessed_on_ingest(distance) {
+        ExplanationSpace::Processed
+    } else {
+        ExplanationSpace::Original
+    }
+}
+
+/// Key of the collection metadata holding dimension labels, see [`dimension_labels_from_metadata`]
+pub const DIMENSION_LABELS_METADATA_KEY: &str = "dimension_labels";
+
+/// Labels of the dimensions of `vector_name`, from collection metadata of the form
+/// `{"dimension_labels": {"<vector name>": {"<dimension>": "<label>"}}}`.
+/// A list of labels may be given instead of an object, to label dimensions in order.
+///
+/// Malformed labels are ignored, so that invalid metadata never fails a search.
+pub fn dimension_labels_from_metadata(
+    metadata: &Payload,
+    vector_name: &VectorName,
+) -> HashMap<usize, String> {
+    let labels = metadata
+        .0
+        .get(DIMENSION_LABELS_METADATA_KEY)
+        .and_then(|labels| labels.get(vector_name));
+
+    match labels {
+        Some(serde_json::Value::Object(labels)) => labels
+            .iter()
+            .filter_map(|(dimension, label)| {
+                Some((dimension.parse().ok()?, label.as_str()?.to_owned()))
+            })
+            .collect(),
+        Some(serde_json::Value::Array(labels)) => labels
+            .iter()
+            .enumerate()
+            .filter_map(|(dimension, label)| Some((dimension, label.as_str()?.to_owned())))
+            .collect(),
+        _ => HashMap::new(),
+    }
+}
+
+/// Set the label of each contribution of `explanation`, and of its baseline explanation
+pub fn label_dimensions(explanation: &mut ScoreExplanation, labels: &HashMap<usize, String>) {
+    for dim in &mut explanation.top_dimensions {
+        dim.label = labels.get(&dim.dimension).cloned();
+    }
+    if let Some(baseline) = &mut explanation.baseline_explanation {
+        label_dimensions(baseline, labels);
+    }
+}
+
+/// Re-express an explanation computed on stored vectors in the space of the inserted vectors.
+///
+/// L2 normalization scales a vector by a positive factor, and cosine contributions are invariant
+/// to such scaling, so inverting it leaves the contributions themselves unchanged.
+pub fn invert_preprocessing(
+    distance: Distance,
+    mut explanation: ScoreExplanation,
+) -> Result<ScoreExplanation, ExplainError> {
+    match (explanation.space, distance) {
+        (ExplanationSpace::Original, _) => Ok(explanation),
+        (ExplanationSpace::Processed, Distance::Cosine) => {
+            explanation.space = ExplanationSpace::Original;
+            Ok(explanation)
+        }
+        (ExplanationSpace::Processed, Distance::Dot | Distance::Euclid | Distance::Manhattan) => {
+            Err(ExplainError::Unsupported {
+                description: format!("can't invert preprocessing of {distance:?} vectors"),
+            })
+        }
+    }
+}
+
+/// Same as [`compute_explanation`], but validates the input vectors first.
+///
+/// `v2` is expected to be a stored vector, so the explanation is flagged with the space
+/// of the stored vectors, unless [`ExplanationConfig::original_space`] is requested.
+pub fn try_compute_explanation(
+    distance: Distance,
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+    config: &ExplanationConfig,
+) -> Result<ScoreExplanation, ExplainError> {
+    if v1.len() != v2.len() {
+        return Err(ExplainError::DimensionMismatch {
+            query_dim: v1.len(),
+            stored_dim: v2.len(),
+        });
+    }
+    let (v1, v2) = match config.datatype {
+        Some(datatype) => (
+            represent_in_datatype(v1, datatype),
+            represent_in_datatype(v2, datatype),
+        ),
+        None => (Cow::Borrowed(v1), Cow::Borrowed(v2)),
+    };
+    let contributions = compute_contributions(distance, &v1, &v2);
+    let mut explanation =
+        ScoreExplanation::with_tie_break(contributions, config.top_n, config.tie_break);
+    explanation.total_score = distance.postprocess_score(explanation.total_score);
+    explanation.space = stored_vectors_space(distance);
+    explanation.query_norm = Some(l2_norm(&v1));
+    explanation.stored_norm = Some(l2_norm(&v2));
+
+    if config.original_space {
+        explanation = invert_preprocessing(distance, explanation)?;
+    }
+
+    Ok(explanation)
+}
+
+/// Values of `vector` as they would be stored with the given `datatype`
+fn represent_in_datatype(
+    vector: &[VectorElementType],
+    datatype: VectorStorageDatatype,
+) -> Cow<'_, [VectorElementType]> {
+    let vector = Cow::Borrowed(vector);
+    match datatype {
+        VectorStorageDatatype::Float32 => vector,
+        VectorStorageDatatype::Float16 => VectorElementTypeHalf::slice_to_float_cow(
+            VectorElementTypeHalf::slice_from_float_cow(vector),
+        ),
+        VectorStorageDatatype::Uint8 => VectorElementTypeByte::slice_to_float_cow(
+            VectorElementTypeByte::slice_from_float_cow(vector),
+        ),
+    }
+}
+
+/// Same as [`try_compute_explanation`], but the stored vector of the point is dequantized from
+/// `quantized_vectors`, so the original vector storage is not read at all.
+///
+/// Contributions are approximate. Quantized vectors are stored after ingest preprocessing,
+/// so `query` is expected to be preprocessed as well.
+pub fn compute_quantized_explanation(
+    distance: Distance,
+    query: &[VectorElementType],
+    quantized_vectors: &QuantizedVectors,
+    point_offset: PointOffsetType,
+    config: &ExplanationConfig,
+) -> Result<ScoreExplanation, ExplainError> {
+    let stored = quantized_vectors
+        .dequantize_vector(point_offset)
+        .ok_or_else(|| ExplainError::Unsupported {
+            description: "only scalar quantized dense vectors can be dequantized".to_string(),
+        })?;
+
+    let mut explanation = try_compute_explanation(distance, query, &stored, config)?;
+    explanation.vector_source = VectorSource::Quantized;
+    Ok(explanation)
+}
+
+/// Same as [`try_compute_explanation`] on the `original` vector of the point, with the
+/// [`ScoreExplanation::quantization_error`] of each top dimension: how much its contribution
+/// changes when the vector of the point is dequantized from `quantized_vectors` instead.
+pub fn compute_quantization_error_explanation(
+    distance: Distance,
+    query: &[VectorElementType],
+    original: &[VectorElementType],
+    quantized_vectors: &QuantizedVectors,
+    point_offset: PointOffsetType,
+    config: &ExplanationConfig,
+) -> Result<ScoreExplanation, ExplainError> {
+    let quantized = quantized_vectors
+        .dequantize_vector(point_offset)
+        .ok_or_else(|| ExplainError::Unsupported {
+            description: "only scalar quantized dense vectors can be dequantized".to_string(),
+        })?;
+
+    let mut explanation = try_compute_explanation(distance, query, original, config)?;
+    explanation.quantization_error = quantization_error(
+        &explanation.top_dimensions,
+        &compute_contributions(distance, query, &quantized),
+    );
+    Ok(explanation)
+}
+
+/// Difference between each of the `top_dimensions` and the contribution of the same dimension
+/// in `quantized`, which holds the contributions of all dimensions, indexed by dimension
+fn quantization_error(
+    top_dimensions: &[DimensionContribution],
+    quantized: &[DimensionContribution],
+) -> Vec<DimensionContribution> {
+    top_dimensions
+        .iter()
+        .map(|dim| DimensionContribution {
+            dimension: dim.dimension,
+            contribution: dim.contribution
+                - quantized
+                    .get(dim.dimension)
+                    .map_or(0.0, |quantized| quantized.contribution),
+            label: None,
+        })
+        .collect()
+}
+
+/// Explain the final top-K of an internal search, by point offset.
+///
+/// Top-K selection only carries [`ScoredPointOffset`]s, so explanations are deferred until it is done:
+/// `fetch_vector` is only called for the offsets of the final results, no other point is materialized.
+/// Results whose vector can't be fetched are explained as [`UnavailableReason::VectorMissing`].
+pub fn explain_scored_offsets<'a>(
+    distance: Distance,
+    query: &[VectorElementType],
+    top: &[ScoredPointOffset],
+    mut fetch_vector: impl FnMut(PointOffsetType) -> Option<Cow<'a, [VectorElementType]>>,
+    config: &ExplanationConfig,
+) -> Vec<(PointOffsetType, Result<ScoreExplanation, ExplainError>)> {
+    top.iter()
+        .map(|scored| {
+            let explanation = match fetch_vector(scored.idx) {
+                Some(stored) => try_compute_explanation(distance, query, &stored, config),
+                None => Ok(ScoreExplanation::unavailable(
+                    UnavailableReason::VectorMissing,
+                )),
+            };
+            (scored.idx, explanation)
+        })
+        .collect()
+}
+
+/// Why a dimension doesn't contribute to the score
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ZeroReason {
+    /// The query is zero in this dimension, it doesn't care about the feature
+    QueryZero,
+    /// The stored vector is zero in this dimension, the point lacks the feature
+    StoredZero,
+    /// Both vectors are zero in this dimension
+    Both,
+}
+
+/// Contribution of a dimension explicitly asked for, whether it is among the top ones or not
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectedDimension {
+    pub dimension: usize,
+    pub contribution: ScoreType,
+    /// Set if the dimension contributes nothing because one of the vectors is zero in it
+    pub zero_reason: Option<ZeroReason>,
+}
+
+/// Explain the given `dimensions` only, telling apart dimensions which contribute nothing
+/// because of the query from the ones which contribute nothing because of the stored vector.
+pub fn explain_selected_dimensions(
+    distance: Distance,
+    query: &[VectorElementType],
+    stored: &[VectorElementType],
+    dimensions: &[usize],
+) -> Result<Vec<SelectedDimension>, ExplainError> {
+    if query.len() != stored.len() {
+        return Err(ExplainError::DimensionMismatch {
+            query_dim: query.len(),
+            stored_dim: stored.len(),
+        });
+    }
+    let contributions = compute_contributions(distance, query, stored);
+
+    dimensions
+        .iter()
+        .map(|&dimension| {
+            let contribution = contributions
+                .get(dimension)
+                .ok_or(ExplainError::DimensionOutOfRange {
+                    dimension,
+                    dim: query.len(),
+                })?
+                .contribution;
+
+            let zero_reason = if contribution != 0.0 {
+                None
+            } else {
+                match (query[dimension] == 0.0, stored[dimension] == 0.0) {
+                    (true, true) => Some(ZeroReason::Both),
+                    (true, false) => Some(ZeroReason::QueryZero),
+                    (false, true) => Some(ZeroReason::StoredZero),
+                    // e.g. equal values for euclidean distance
+                    (false, false) => None,
+                }
+            };
+
+            Ok(SelectedDimension {
+                dimension,
+                contribution,
+                zero_reason,
+            })
+        })
+        .collect()
+}
+
+fn average_dense(
+    vectors: &[(&[VectorElementType], VectorElementType)],
+) -> Result<Option<DenseVector>, ExplainError> {
+    let Some((first, _)) = vectors.first() else {
+        return Ok(None);
+    };
+
+    let mut avg = vec![0.0; first.len()];
+    let mut total_weight = 0.0;
+    for (vector, weight) in vectors {
+        if vector.len() != avg.len() {
+            return Err(ExplainError::DimensionMismatch {
+                query_dim: avg.len(),
+                stored_dim: vector.len(),
+            });
+        }
+        for (acc, value) in avg.iter_mut().zip(vector.iter()) {
+            *acc += value * weight;
+        }
+        total_weight += weight;
+    }
+
+    if total_weight == 0.0 {
+        return Err(ExplainError::Unsupported {
+            description: "weights of the examples sum to zero".to_string(),
+        });
+    }
+    avg.iter_mut().for_each(|value| *value /= total_weight);
+    Ok(Some(avg))
+}
+
+/// Effective query of the `average_vector` recommend strategy: `avg(pos) + avg(pos) - avg(neg)`.
+///
+/// The search itself requires positives, but for explanation purposes negatives alone are
+/// accepted: the effective query becomes `-avg(neg)`, so contributions show the dimensions
+/// where the result most avoids the negative examples.
+pub fn average_recommend_query(
+    positives: &[&[VectorElementType]],
+    negatives: &[&[VectorElementType]],
+) -> Result<DenseVector, ExplainError> {
+    let unweighted = |examples: &[&[VectorElementType]]| {
+        examples
+            .iter()
+            .map(|&example| (example, 1.0))
+            .collect::<Vec<_>>()
+    };
+    weighted_average_recommend_query(&unweighted(positives), &unweighted(negatives))
+}
+
+/// Same as [`average_recommend_query`], but each example is weighted in the average of its kind
+pub fn weighted_average_recommend_query(
+    positives: &[(&[VectorElementType], VectorElementType)],
+    negatives: &[(&[VectorElementType], VectorElementType)],
+) -> Result<DenseVector, ExplainError> {
+    match (average_dense(positives)?, average_dense(negatives)?) {
+        (Some(avg_positive), None) => Ok(avg_positive),
+        (Some(avg_positive), Some(avg_negative)) => {
+            if avg_positive.len() != avg_negative.len() {
+                return Err(ExplainError::DimensionMismatch {
+                    query_dim: avg_positive.len(),
+                    stored_dim: avg_negative.len(),
+                });
+            }
+            Ok(avg_positive
+                .iter()
+                .zip(avg_negative.iter())
+                .map(|(pos, neg)| pos + pos - neg)
+                .collect())
+        }
+        (None, Some(avg_negative)) => Ok(avg_negative.into_iter().map(|neg| -neg).collect()),
+        (None, None) => Err(ExplainError::NoExamples),
+    }
+}
+
+/// For each of `dimensions`, the example which adds the value of largest magnitude to the
+/// effective query of [`weighted_average_recommend_query`]. Ties go to the first example,
+/// positives first.
+pub fn dominant_recommend_examples(
+    positives: &[(&[VectorElementType], VectorElementType)],
+    negatives: &[(&[VectorElementType], VectorElementType)],
+    dimensions: &[usize],
+) -> Result<Vec<DominantExample>, ExplainError> {
+    let dim = weighted_average_recommend_query(positives, negatives)?.len();
+
+    let total_weight = |examples: &[(&[VectorElementType], VectorElementType)]| {
+        examples
+            .iter()
+            .map(|(_, weight)| weight)
+            .sum::<VectorElementType>()
+    };
+    // Positives are counted twice in the effective query, unless there are no negatives
+    let positive_scale = if negatives.is_empty() { 1.0 } else { 2.0 } / total_weight(positives);
+    let negative_scale = -1.0 / total_weight(negatives);
+
+    dimensions
+        .iter()
+        .map(|&dimension| {
+            if dimension >= dim {
+                return Err(ExplainError::DimensionOutOfRange { dimension, dim });
+            }
+            let examples = positives
+                .iter()
+                .map(move |example| (ExampleKind::Positive, example, positive_scale))
+                .enumerate()
+                .chain(
+                    negatives
+                        .iter()
+                        .map(move |example| (ExampleKind::Negative, example, negative_scale))
+                        .enumerate(),
+                );
+            let dominant = examples
+                .map(
+                    |(index, (kind, (example, weight), scale))| DominantExample {
+                        dimension,
+                        kind,
+                        index,
+                        contribution: example[dimension] * weight * scale,
+                    },
+                )
+                .min_by(|a, b| b.contribution.abs().total_cmp(&a.contribution.abs()))
+                .ok_or(ExplainError::NoExamples)?;
+            Ok(dominant)
+        })
+        .collect()
+}
+
+/// Explain a result of a recommend request with the `average_vector` strategy.
+pub fn compute_recommend_explanation(
+    distance: Distance,
+    positives: &[&[VectorElementType]],
+    negatives: &[&[VectorElementType]],
+    stored: &[VectorElementType],
+    config: &ExplanationConfig,
+) -> Result<ScoreExplanation, ExplainError> {
+    let query = average_recommend_query(positives, negatives)?;
+    try_compute_explanation(distance, &query, stored, config)
+}
+
+/// Attribution of the discover context score: for each `(positive, negative)` pair,
+/// contributions towards the positive minus contributions towards the negative.
+pub fn context_contributions(
+    distance: Distance,
+    pairs: &[(&[VectorElementType], &[VectorElementType])],
+    stored: &[VectorElementType],
+) -> Result<Vec<DimensionContribution>, ExplainError> {
+    let mut totals = vec![0.0; stored.len()];
+
+    for (positive, negative) in pairs {
+        for example in [positive, negative] {
+            if example.len() != stored.len() {
+                return Err(ExplainError::DimensionMismatch {
+                    query_dim: example.len(),
+                    stored_dim: stored.len(),
+                });
+            }
+        }
+
+        let towards_positive = compute_contributions(distance, positive, stored);
+        let towards_negative = compute_contributions(distance, negative, stored);
+        for ((total, pos), neg) in totals
+            .iter_mut()
+            .zip(&towards_positive)
+            .zip(&towards_negative)
+        {
+            *total += pos.contribution - neg.contribution;
+        }
+    }
+
+    Ok(totals
+        .into_iter()
+        .enumerate()
+        .map(|(dimension, contribution)| DimensionContribution {
+            dimension,
+            contribution,
+            label: None,
+        })
+        .collect())
+}
+
+/// Attribution of a discover score: contributions towards the target,
+/// plus the [`context_contributions`] of its pairs.
+///
+/// `query` must be the context actually used for scoring, i.e. after its pairs were resolved,
+/// so that the explanation doesn't drift from the score when the context is expanded.
+pub fn discover_contributions(
+    distance: Distance,
+    query: &DiscoveryQuery<DenseVector>,
+    stored: &[VectorElementType],
+) -> Result<Vec<DimensionContribution>, ExplainError> {
+    if query.target.len() != stored.len() {
+        return Err(ExplainError::DimensionMismatch {
+            query_dim: query.target.len(),
+            stored_dim: stored.len(),
+        });
+    }
+
+    let pairs: Vec<_> = query
+        .pairs
+        .iter()
+        .map(|pair| (pair.positive.as_slice(), pair.negative.as_slice()))
+        .collect();
+    let context = context_contributions(distance, &pairs, stored)?;
+    let target = compute_contributions(distance, &query.target, stored);
+
+    Ok(target
+        .into_iter()
+        .zip(context)
+        .map(|(target, ctx)| DimensionContribution {
+            dimension: target.dimension,
+            contribution: target.contribution + ctx.contribution,
+            label: None,
+        })
+        .collect())
+}
+
+/// Explanation of a query combining recommend examples with discover context pairs
+#[derive(Clone, Debug, Default)]
+pub struct CompositeExplanation {
+    /// Sum of both components
+    pub total: ScoreExplanation,
+    /// Contributions of the effective recommend vector, see [`average_recommend_query`]
+    pub recommend: ScoreExplanation,
+    /// Contributions of the context pairs, see [`context_contributions`]
+    pub context: ScoreExplanation,
+}
+
+/// Explain a result of a query using both recommend examples and discover context pairs.
+pub fn compute_recommend_context_explanation(
+    distance: Distance,
+    positives: &[&[VectorElementType]],
+    negatives: &[&[VectorElementType]],
+    pairs: &[(&[VectorElementType], &[VectorElementType])],
+    stored: &[VectorElementType],
+    config: &ExplanationConfig,
+) -> Result<CompositeExplanation, ExplainError> {
+    let query = average_recommend_query(positives, negatives)?;
+    if query.len() != stored.len() {
+        return Err(ExplainError::DimensionMismatch {
+            query_dim: query.len(),
+            stored_dim: stored.len(),
+        });
+    }
+
+    let recommend = compute_contributions(distance, &query, stored);
+    let context = context_contributions(distance, pairs, stored)?;
+
+    let total = recommend
+        .iter()
+        .zip(&context)
+        .map(|(reco, ctx)| DimensionContribution {
+            dimension: reco.dimension,
+            contribution: reco.contribution + ctx.contribution,
+            label: None,
+        })
+        .collect();
+
+    Ok(CompositeExplanation {
+        total: ScoreExplanation::with_tie_break(total, config.top_n, config.tie_break),
+        recommend: ScoreExplanation::with_tie_break(recommend, config.top_n, config.tie_break),
+        context: ScoreExplanation::with_tie_break(context, config.top_n, config.tie_break),
+    })
+}
+
+/// Explain the fused scores of a fusion query, given the results of each of its prefetches.
+///
+/// Every explained point gets one [`FusionSource`] per prefetch, in the order of `sources`.
+/// Prefetches which didn't return the point contribute 0.
+pub fn explain_fusion(
+    method: FusionMethod,
+    sources: Vec<Vec<ScoredPoint>>,
+) -> HashMap<PointIdType, FusionExplanation> {
+    let num_sources = sources.len();
+    let mut explanations: HashMap<PointIdType, FusionExplanation> = HashMap::new();
+
+    for (source_idx, points) in sources.into_iter().enumerate() {
+        let contributions: Vec<ScoreType> = match method {
+            FusionMethod::Rrf { k } => (0..points.len())
+                .map(|pos| position_score(pos, k))
+                .collect(),
+            FusionMethod::Dbsf => distr_norm(points.clone())
+                .into_iter()
+                .map(|point| point.score)
+                .collect(),
+        };
+
+        for (rank, (point, contribution)) in points.into_iter().zip(contributions).enumerate() {
+            let explanation = explanations
+                .entry(point.id)
+                .or_insert_with(|| FusionExplanation {
+                    method,
+                    sources: vec![
+                        FusionSource {
+                            rank: None,
+                            score: None,
+                            contribution: 0.0,
+                        };
+                        num_sources
+                    ],
+                });
+            explanation.sources[source_idx] = FusionSource {
+                rank: Some(rank),
+                score: Some(point.score),
+                contribution,
+            };
+        }
+    }
+
+    explanations
+}
+
+/// Aggregate the explanations of a result set into the dimensions that influenced it the most.
+///
+/// Contributions of each dimension are averaged over all explained points,
+/// points without an explanation are ignored.
+pub fn aggregate_explanations(points: &[ScoredPoint], top_n: usize) -> ScoreExplanation {
+    aggregate_explanations_weighted(points, top_n, |_rank| 1.0)
+}
+
+/// Same as [`aggregate_explanations`], but averages the magnitude of the contributions,
+/// so that a dimension pushing some points up and others down doesn't cancel out.
+pub fn aggregate_absolute_explanations(points: &[ScoredPoint], top_n: usize) -> ScoreExplanation {
+    let mut accumulator = ContributionAccumulator::default();
+    for explanation in points
+        .iter()
+        .filter_map(|point| point.score_explanation.as_ref())
+    {
+        accumulator.add_absolute(explanation, 1.0);
+    }
+    accumulator.aggregate(top_n)
+}
+
+/// Weight of a result in [`aggregate_explanations_weighted`], so that top results count more.
+pub fn rank_weight(rank: usize) -> ScoreType {
+    1.0 / (rank + 1) as ScoreType
+}
+
+/// Same as [`aggregate_explanations`], but the contributions of each point are weighted by
+/// `weight(rank)`, where `rank` is the position of the point in `points`.
+pub fn aggregate_explanations_weighted(
+    points: &[ScoredPoint],
+    top_n: usize,
+    weight: impl Fn(usize) -> ScoreType,
+) -> ScoreExplanation {
+    let mut accumulator = ContributionAccumulator::default();
+    for (rank, point) in points.iter().enumerate() {
+        if let Some(explanation) = &point.score_explanation {
+            accumulator.add(explanation, weight(rank));
+        }
+    }
+    accumulator.aggregate(top_n)
+}
+
+/// Weighted sums of the contributions of many explanations, per dimension
+#[derive(Clone, Debug, Default)]
+struct ContributionAccumulator {
+    sums: AHashMap<usize, ScoreType>,
+    total_weight: ScoreType,
+}
+
+impl ContributionAccumulator {
+    /// Explanations without contributions are ignored
+    fn add(&mut self, explanation: &ScoreExplanation, weight: ScoreType) {
+        self.add_mapped(explanation, weight, |contribution| contribution);
+    }
+
+    /// Same as [`ContributionAccumulator::add`], summing the magnitude of the contributions
+    fn add_absolute(&mut self, explanation: &ScoreExplanation, weight: ScoreType) {
+        self.add_mapped(explanation, weight, ScoreType::abs);
+    }
+
+    fn add_mapped(
+        &mut self,
+        explanation: &ScoreExplanation,
+        weight: ScoreType,
+        map: impl Fn(ScoreType) -> ScoreType,
+    ) {
+        if explanation.top_dimensions.is_empty() {
+            return;
+        }
+
+        self.total_weight += weight;
+        for dim in &explanation.top_dimensions {
+            *self.sums.entry(dim.dimension).or_default() += weight * map(dim.contribution);
+        }
+    }
+
+    /// Weighted average contribution of each dimension
+    fn aggregate(&self, top_n: usize) -> ScoreExplanation {
+        if self.total_weight == 0.0 {
+            return ScoreExplanation::default();
+        }
+
+        let contributions = self
+            .sums
+            .iter()
+            .map(|(&dimension, &sum)| DimensionContribution {
+                dimension,
+                contribution: sum / self.total_weight,
+                label: None,
+            })
+            .collect();
+
+        ScoreExplanation::new(contributions, top_n)
+    }
+}
+
+/// Comparison of the two latest windows of an [`ExplanationDriftTracker`]
+#[derive(Clone, Debug)]
+pub struct DriftReport {
+    /// Aggregated explanations of the previous window
+    pub previous: ScoreExplanation,
+    /// Aggregated explanations of the current window
+    pub current: ScoreExplanation,
+    /// The dimension contributing the most is not the same in both windows
+    pub drifted: bool,
+}
+
+/// Aggregates the explanations of a fixed query over rolling time windows,
+/// to detect when the dimensions driving its matches change over time, e.g. due to data drift.
+#[derive(Clone, Debug)]
+pub struct ExplanationDriftTracker {
+    window: Duration,
+    top_n: usize,
+    current_start: Option<Instant>,
+    current: ContributionAccumulator,
+    previous: ContributionAccumulator,
+}
+
+impl ExplanationDriftTracker {
+    pub fn new(window: Duration, top_n: usize) -> Self {
+        Self {
+            window,
+            top_n,
+            current_start: None,
+            current: ContributionAccumulator::default(),
+            previous: ContributionAccumulator::default(),
+        }
+    }
+
+    pub fn record(&mut self, explanation: &ScoreExplanation) {
+        self.record_at(explanation, Instant::now());
+    }
+
+    /// Same as [`ExplanationDriftTracker::record`], with an explicit recording time
+    pub fn record_at(&mut self, explanation: &ScoreExplanation, at: Instant) {
+        let start = *self.current_start.get_or_insert(at);
+        let elapsed = at.saturating_duration_since(start);
+        if elapsed >= self.window {
+            // Windows without any recording in between leave no previous window to compare to
+            self.previous = if elapsed < self.window * 2 {
+                std::mem::take(&mut self.current)
+            } else {
+                ContributionAccumulator::default()
+            };
+            self.current = ContributionAccumulator::default();
+            self.current_start = Some(at);
+        }
+
+        self.current.add(explanation, 1.0);
+    }
+
+    /// Compare the aggregated explanations of the current window with the previous one.
+    ///
+    /// Drift is only reported if both windows have explanations to compare.
+    pub fn compare_windows(&self) -> DriftReport {
+        let previous = self.previous.aggregate(self.top_n);
+        let current = self.current.aggregate(self.top_n);
+
+        let drifted = match (
+            previous.top_dimensions.first(),
+            current.top_dimensions.first(),
+        ) {
+            (Some(previous_top), Some(current_top)) => {
+                previous_top.dimension != current_top.dimension
+            }
+            _ => false,
+        };
+
+        DriftReport {
+            previous,
+            current,
+            drifted,
+        }
+    }
+}
+
+/// Share of the top dimension in the total absolute contribution of the explained dimensions.
+///
+/// Close to 1 for focused matches driven by a single dimension, lower for diffuse ones.
+fn top_dimension_dominance(explanation: &ScoreExplanation) -> Option<ScoreType> {
+    let top = explanation.top_dimensions.first()?;
+    let total: ScoreType = explanation
+        .top_dimensions
+        .iter()
+        .map(|dim| dim.contribution.abs())
+        .sum();
+    (total > 0.0).then(|| top.contribution.abs() / total)
+}
+
+/// Suggest a `score_threshold` separating focused matches from diffuse ones.
+///
+/// Explained points are sorted by score, and the threshold is the score of the last point
+/// before the sharpest drop of top dimension dominance (the "elbow").
+/// Returns `None` if there are less than 2 explained points to compare.
+pub fn suggest_score_threshold(points: &[ScoredPoint]) -> Option<ScoreType> {
+    let mut scored_dominance: Vec<_> = points
+        .iter()
+        .filter_map(|point| {
+            let dominance = top_dimension_dominance(point.score_explanation.as_ref()?)?;
+            Some((point.score, dominance))
+        })
+        .collect();
+    scored_dominance.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+    scored_dominance
+        .windows(2)
+        .map(|pair| (pair[0].0, pair[0].1 - pair[1].1))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(score, _drop)| score)
+}
+
+/// Export explanations of a batch of points as CSV, one row per explained dimension.
+///
+/// `percentage` is the signed share of the contribution in the total absolute contribution
+/// of the point's explained dimensions. Points without an explanation are skipped.
+pub fn explanations_to_csv(points: &[ScoredPoint]) -> String {
+    explanations_to_delimited(points, ',')
+}
+
+/// Same as [`explanations_to_csv`], but tab-separated.
+pub fn explanations_to_tsv(points: &[ScoredPoint]) -> String {
+    explanations_to_delimited(points, '\t')
+}
+
+fn explanations_to_delimited(points: &[ScoredPoint], delimiter: char) -> String {
+    let mut output = EXPORT_COLUMNS.join(&delimiter.to_string());
+    output.push('\n');
+
+    for point in points {
+        let Some(explanation) = &point.score_explanation else {
+            continue;
+        };
+
+        let total: ScoreType = explanation
+            .top_dimensions
+            .iter()
+            .map(|dim| dim.contribution.abs())
+            .sum();
+
+        for dim in &explanation.top_dimensions {
+            let percentage = if total > 0.0 {
+                dim.contribution / total * 100.0
+            } else {
+                0.0
+            };
+            output.push_str(&format!(
+                "{}{delimiter}{}{delimiter}{}{delimiter}{percentage}\n",
+                point.id, dim.dimension, dim.contribution,
+            ));
+        }
+    }
+
+    output
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use super::*;
+    use crate::vector_storage::query::ContextPair;
 
     #[test]
     fn test_dot_product_contributions() {
         let v1 = vec![1.0, 2.0, 3.0];
         let v2 = vec![4.0, 5.0, 6.0];
-        
+
         let contributions = dot_product_contributions(&v1, &v2);
-        
+
         assert_eq!(contributions.len(), 3);
         assert_eq!(contributions[0].dimension, 0);
         assert_eq!(contributions[0].contribution, 4.0); // 1*4
@@ -144,18 +1329,36 @@ mod tests {
         assert_eq!(contributions[1].contribution, 10.0); // 2*5
         assert_eq!(contributions[2].dimension, 2);
         assert_eq!(contributions[2].contribution, 18.0); // 3*6
-        
+
         let total: ScoreType = contributions.iter().map(|c| c.contribution).sum();
         assert_eq!(total, 32.0); // 4+10+18
     }
 
+    #[test]
+    fn test_explanation_dimension_set() {
+        let explanation =
+            compute_explanation(Distance::Dot, &[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], None);
+        assert_eq!(explanation.dimension_set(), HashSet::from([0, 1, 2]));
+
+        let top_two =
+            compute_explanation(Distance::Dot, &[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], Some(2));
+        assert_eq!(top_two.dimension_set(), HashSet::from([1, 2]));
+        assert_eq!(
+            explanation
+                .dimension_set()
+                .intersection(&top_two.dimension_set())
+                .count(),
+            2,
+        );
+    }
+
     #[test]
     fn test_euclidean_contributions() {
         let v1 = vec![1.0, 2.0, 3.0];
         let v2 = vec![4.0, 5.0, 6.0];
-        
+
         let contributions = euclidean_contributions(&v1, &v2);
-        
+
         assert_eq!(contributions.len(), 3);
         // Each contribution should be negative (since we're looking at distance)
         assert!(contributions[0].contribution < 0.0);
@@ -168,21 +1371,100 @@ mod tests {
     fn test_cosine_contributions() {
         let v1 = vec![1.0, 0.0];
         let v2 = vec![1.0, 0.0];
-        
+
         let contributions = cosine_contributions(&v1, &v2);
-        
+
         assert_eq!(contributions.len(), 2);
         assert_eq!(contributions[0].contribution, 1.0); // perfectly aligned
         assert_eq!(contributions[1].contribution, 0.0); // no contribution from dimension 1
     }
 
+    #[test]
+    fn test_counterfactual_contributions() {
+        let v1 = vec![1.0, 1.0, 0.0];
+        let v2 = vec![1.0, 0.0, 1.0];
+
+        // cos = 0.5; without dimension 0 the vectors are orthogonal,
+        // without dimension 1 or 2 the similarity rises to 1 / sqrt(2)
+        let contributions = counterfactual_contributions(Distance::Cosine, &v1, &v2);
+        let expected = [0.5, 0.5 - 1.0 / 2.0f32.sqrt(), 0.5 - 1.0 / 2.0f32.sqrt()];
+        for (contribution, expected) in contributions.iter().zip(expected) {
+            assert!((contribution.contribution - expected).abs() < 1e-6);
+        }
+
+        // Additive scores lose exactly the contribution of the removed dimension
+        assert_eq!(
+            counterfactual_contributions(Distance::Euclid, &v1, &v2),
+            euclidean_contributions(&v1, &v2),
+        );
+
+        let explanation = compute_counterfactual_explanation(Distance::Cosine, &v1, &v2, Some(1));
+        assert_eq!(explanation.top_dimensions.len(), 1);
+        assert_eq!(explanation.top_dimensions[0].dimension, 0);
+        assert!((explanation.total_score - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_quantization_error() {
+        let query = vec![1.0, 2.0, 3.0];
+        let original = vec![0.5, 0.5, 1.0];
+        let quantized = vec![0.5, 0.25, 1.5];
+
+        let explanation = compute_explanation(Distance::Dot, &query, &original, Some(2));
+        let error = quantization_error(
+            &explanation.top_dimensions,
+            &dot_product_contributions(&query, &quantized),
+        );
+
+        // One error per top dimension, in the same order
+        let dimensions: Vec<_> = error.iter().map(|dim| dim.dimension).collect();
+        assert_eq!(dimensions, vec![2, 1]);
+        let errors: Vec<_> = error.iter().map(|dim| dim.contribution).collect();
+        assert_eq!(errors, vec![3.0 - 4.5, 1.0 - 0.5]);
+    }
+
+    #[test]
+    fn test_explain_fusion() {
+        let point = |id: u64, score: ScoreType| ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: None,
+        };
+        let sources = vec![
+            vec![point(1, 0.9), point(2, 0.8)],
+            vec![point(2, 0.7), point(3, 0.6)],
+        ];
+
+        let explanations = explain_fusion(FusionMethod::Rrf { k: 2 }, sources.clone());
+        assert_eq!(explanations.len(), 3);
+        let fused = crate::common::reciprocal_rank_fusion::rrf_scoring(sources, 2);
+        for fused_point in &fused {
+            let explanation = &explanations[&fused_point.id];
+            assert_eq!(explanation.sources.len(), 2);
+            let total: ScoreType = explanation.sources.iter().map(|s| s.contribution).sum();
+            assert!((total - fused_point.score).abs() < 1e-6);
+        }
+
+        let only_second = &explanations[&3.into()];
+        assert_eq!(only_second.sources[0].rank, None);
+        assert_eq!(only_second.sources[0].contribution, 0.0);
+        assert_eq!(only_second.sources[1].rank, Some(1));
+        assert_eq!(only_second.sources[1].score, Some(0.6));
+        assert_eq!(only_second.sources[1].contribution, 1.0 / 3.0);
+    }
+
     #[test]
     fn test_explanation_top_n() {
         let v1 = vec![1.0, 5.0, 2.0, 8.0, 3.0];
         let v2 = vec![1.0, 1.0, 1.0, 1.0, 1.0];
-        
+
         let explanation = compute_explanation(Distance::Dot, &v1, &v2, Some(3));
-        
+
         assert_eq!(explanation.top_dimensions.len(), 3);
         // Should be sorted by absolute contribution (descending)
         // Contributions: [1, 5, 2, 8, 3]
@@ -194,4 +1476,918 @@ mod tests {
         assert_eq!(explanation.top_dimensions[2].dimension, 4);
         assert_eq!(explanation.top_dimensions[2].contribution, 3.0);
     }
+
+    #[test]
+    fn test_try_compute_explanation_dimension_mismatch() {
+        let v1 = vec![1.0, 2.0, 3.0];
+        let v2 = vec![1.0, 2.0];
+
+        let result =
+            try_compute_explanation(Distance::Dot, &v1, &v2, &ExplanationConfig::default());
+
+        assert_eq!(
+            result.unwrap_err(),
+            ExplainError::DimensionMismatch {
+                query_dim: 3,
+                stored_dim: 2,
+            },
+        );
+    }
+
+    #[test]
+    fn test_explanations_to_csv() {
+        let explained_point = |id: u64, query: &[VectorElementType]| ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score: 0.0,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: Some(compute_explanation(
+                Distance::Dot,
+                query,
+                &[1.0, 1.0, 1.0],
+                None,
+            )),
+        };
+
+        let mut unexplained = explained_point(3, &[1.0, 1.0, 1.0]);
+        unexplained.score_explanation = None;
+
+        let points = vec![
+            explained_point(1, &[1.0, 2.0, 1.0]),
+            explained_point(2, &[3.0, 0.0, 1.0]),
+            unexplained,
+        ];
+
+        let csv = explanations_to_csv(&points);
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines[0], "point_id,dimension,contribution,percentage");
+        // 3 dimensions for each of the 2 explained points
+        assert_eq!(lines.len(), 1 + 3 + 3);
+        assert_eq!(lines[1], "1,1,2,50");
+        assert!(lines.iter().all(|line| !line.starts_with("3,")));
+
+        let tsv = explanations_to_tsv(&points);
+        assert!(tsv.starts_with("point_id\tdimension\tcontribution\tpercentage\n"));
+    }
+
+    #[test]
+    fn test_dominant_recommend_examples() {
+        let positive_1 = [1.0, 0.0, 0.5];
+        let positive_2 = [0.0, 0.2, 0.5];
+        let negative = [0.0, -3.0, 0.5];
+
+        // Query is `2 * [0.75, 0.05, 0.5] - [0, -3, 0.5] = [1.5, 3.1, 0.5]`
+        let dominant = dominant_recommend_examples(
+            &[(&positive_1, 3.0), (&positive_2, 1.0)],
+            &[(&negative, 1.0)],
+            &[0, 1, 2],
+        )
+        .unwrap();
+
+        assert_eq!(dominant[0].kind, ExampleKind::Positive);
+        assert_eq!(dominant[0].index, 0);
+        assert_eq!(dominant[0].contribution, 1.5);
+
+        assert_eq!(dominant[1].kind, ExampleKind::Negative);
+        assert_eq!(dominant[1].index, 0);
+        assert_eq!(dominant[1].contribution, 3.0);
+
+        // Positive 1 weighs more than positive 2, their values are the same
+        assert_eq!(dominant[2].kind, ExampleKind::Positive);
+        assert_eq!(dominant[2].index, 0);
+        assert_eq!(dominant[2].contribution, 0.75);
+
+        assert!(matches!(
+            dominant_recommend_examples(&[(&positive_1, 1.0)], &[], &[3]),
+            Err(ExplainError::DimensionOutOfRange {
+                dimension: 3,
+                dim: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_recommend_explanation_negative_only() {
+        let negative_1 = [1.0, 0.0, 0.2];
+        let negative_2 = [1.0, 0.0, -0.2];
+        let stored = [-1.0, 0.5, 0.0];
+
+        let explanation = compute_recommend_explanation(
+            Distance::Dot,
+            &[],
+            &[&negative_1, &negative_2],
+            &stored,
+            &ExplanationConfig::default(),
+        )
+        .unwrap();
+
+        // Effective query is `-avg(neg) = [-1, 0, 0]`: the result is explained by dimension 0,
+        // where it points away from the negatives.
+        assert!(!explanation.top_dimensions.is_empty());
+        assert_eq!(explanation.top_dimensions[0].dimension, 0);
+        assert_eq!(explanation.top_dimensions[0].contribution, 1.0);
+
+        let no_examples = compute_recommend_explanation(
+            Distance::Dot,
+            &[],
+            &[],
+            &stored,
+            &ExplanationConfig::default(),
+        );
+        assert_eq!(no_examples.unwrap_err(), ExplainError::NoExamples);
+    }
+
+    #[test]
+    fn test_explanation_space_of_normalized_vectors() {
+        let query = vec![1.0, 2.0, 2.0];
+        let inserted = vec![3.0, 0.0, 4.0];
+        let stored = Distance::Cosine.preprocess_vector::<VectorElementType>(inserted.clone());
+
+        let processed = try_compute_explanation(
+            Distance::Cosine,
+            &query,
+            &stored,
+            &ExplanationConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(processed.space, ExplanationSpace::Processed);
+
+        let config = ExplanationConfig {
+            original_space: true,
+            ..Default::default()
+        };
+        let original = try_compute_explanation(Distance::Cosine, &query, &stored, &config).unwrap();
+        assert_eq!(original.space, ExplanationSpace::Original);
+
+        // Same contributions as if computed on the vector before normalization
+        let expected = compute_explanation(Distance::Cosine, &query, &inserted, None);
+        for (actual, expected) in original.top_dimensions.iter().zip(&expected.top_dimensions) {
+            assert_eq!(actual.dimension, expected.dimension);
+            assert!((actual.contribution - expected.contribution).abs() < 1e-6);
+        }
+
+        let dot = try_compute_explanation(Distance::Dot, &query, &inserted, &config).unwrap();
+        assert_eq!(dot.space, ExplanationSpace::Original);
+    }
+
+    #[test]
+    fn test_delta_from_mean_explanation() {
+        let query = [1.0, 1.0, 1.0];
+        let stored = [10.0, 5.0, 2.0];
+
+        let mut mean = MeanVector::default();
+        mean.add(&[12.0, 1.0, 2.0]).unwrap();
+        mean.add(&[8.0, 1.0, 2.0]).unwrap();
+        assert_eq!(mean.count(), 2);
+        let mean = mean.mean().unwrap();
+        assert_eq!(mean, vec![10.0, 1.0, 2.0]);
+
+        // Largest raw contribution comes from dimension 0
+        let raw = compute_explanation(Distance::Dot, &query, &stored, None);
+        assert_eq!(raw.top_dimensions[0].dimension, 0);
+
+        // But dimension 1 is the one that is distinctive compared to the rest of the collection
+        let explanation = compute_delta_from_mean_explanation(
+            &query,
+            &stored,
+            &mean,
+            &ExplanationConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(explanation.top_dimensions[0].dimension, 1);
+        assert_eq!(explanation.top_dimensions[0].contribution, 4.0);
+    }
+
+    #[test]
+    fn test_rank_weighted_aggregation() {
+        let explained_point = |id: u64, dimension: usize, contribution: ScoreType| ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score: contribution,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: Some(ScoreExplanation::new(
+                vec![DimensionContribution {
+                    dimension,
+                    contribution,
+                    label: None,
+                }],
+                DEFAULT_TOP_DIMENSIONS,
+            )),
+        };
+
+        // Top result is driven by dimension 0, the tail by dimension 1
+        let points = vec![
+            explained_point(1, 0, 10.0),
+            explained_point(2, 1, 5.0),
+            explained_point(3, 1, 5.0),
+            explained_point(4, 1, 5.0),
+        ];
+
+        let uniform = aggregate_explanations(&points, DEFAULT_TOP_DIMENSIONS);
+        assert_eq!(uniform.top_dimensions[0].dimension, 1);
+        assert_eq!(uniform.top_dimensions[0].contribution, 3.75);
+
+        let weighted =
+            aggregate_explanations_weighted(&points, DEFAULT_TOP_DIMENSIONS, rank_weight);
+        assert_eq!(weighted.top_dimensions[0].dimension, 0);
+        assert_eq!(weighted.top_dimensions[1].dimension, 1);
+    }
+
+    #[test]
+    fn test_absolute_aggregation() {
+        let explained_point = |id: u64, dimension: usize, contribution: ScoreType| ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score: contribution,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: Some(ScoreExplanation::new(
+                vec![DimensionContribution {
+                    dimension,
+                    contribution,
+                    label: None,
+                }],
+                DEFAULT_TOP_DIMENSIONS,
+            )),
+        };
+
+        // Dimension 0 pushes half of the points up and the other half down
+        let points = vec![
+            explained_point(1, 0, 4.0),
+            explained_point(2, 0, -4.0),
+            explained_point(3, 1, 1.0),
+            explained_point(4, 1, 1.0),
+        ];
+
+        let signed = aggregate_explanations(&points, DEFAULT_TOP_DIMENSIONS);
+        assert_eq!(signed.top_dimensions[0].dimension, 1);
+
+        let absolute = aggregate_absolute_explanations(&points, DEFAULT_TOP_DIMENSIONS);
+        assert_eq!(absolute.top_dimensions[0].dimension, 0);
+        assert_eq!(absolute.top_dimensions[0].contribution, 2.0);
+        assert_eq!(absolute.top_dimensions[1].contribution, 0.5);
+    }
+
+    #[test]
+    fn test_dimension_labels_from_metadata() {
+        let serde_json::Value::Object(metadata) = serde_json::json!({
+            "dimension_labels": {
+                "sparse": {"3": "cat", "7": "dog", "x": "ignored", "9": 1},
+                "dense": ["red", "green"],
+            },
+            "other": "unrelated",
+        }) else {
+            unreachable!()
+        };
+        let metadata = Payload::from(metadata);
+
+        let sparse = dimension_labels_from_metadata(&metadata, "sparse");
+        assert_eq!(
+            sparse,
+            HashMap::from([(3, "cat".to_string()), (7, "dog".to_string())]),
+        );
+        let dense = dimension_labels_from_metadata(&metadata, "dense");
+        assert_eq!(dense[&1], "green");
+        assert!(dimension_labels_from_metadata(&metadata, "missing").is_empty());
+
+        let mut explanation = compute_sparse_explanation(
+            &SparseVector {
+                indices: vec![3, 5],
+                values: vec![1.0, 2.0],
+            },
+            &SparseVector {
+                indices: vec![3, 5],
+                values: vec![1.0, 1.0],
+            },
+            None,
+        );
+        label_dimensions(&mut explanation, &sparse);
+        let labels: Vec<_> = explanation
+            .top_dimensions
+            .iter()
+            .map(|dim| (dim.dimension, dim.label.as_deref()))
+            .collect();
+        assert_eq!(labels, vec![(5, None), (3, Some("cat"))]);
+    }
+
+    #[test]
+    fn test_recommend_context_explanation() {
+        let positive = [1.0, 0.0, 0.0];
+        let context_positive = [0.0, 1.0, 0.0];
+        let context_negative = [0.0, 0.0, 1.0];
+        let stored = [2.0, 3.0, 1.0];
+
+        let explanation = compute_recommend_context_explanation(
+            Distance::Dot,
+            &[&positive],
+            &[],
+            &[(&context_positive, &context_negative)],
+            &stored,
+            &ExplanationConfig::default(),
+        )
+        .unwrap();
+
+        // Recommend component is driven by the positive example
+        assert_eq!(explanation.recommend.top_dimensions[0].dimension, 0);
+        assert_eq!(explanation.recommend.top_dimensions[0].contribution, 2.0);
+
+        // Context component pulls towards the context positive, away from the context negative
+        let context_contribution = |dimension| {
+            explanation
+                .context
+                .top_dimensions
+                .iter()
+                .find(|dim| dim.dimension == dimension)
+                .unwrap()
+                .contribution
+        };
+        assert_eq!(context_contribution(1), 3.0);
+        assert_eq!(context_contribution(2), -1.0);
+
+        // Total is the sum of both components
+        assert_eq!(explanation.total.top_dimensions[0].dimension, 1);
+        assert_eq!(explanation.total.top_dimensions[0].contribution, 3.0);
+        assert_eq!(explanation.total.top_dimensions[1].dimension, 0);
+        assert_eq!(explanation.total.top_dimensions[1].contribution, 2.0);
+    }
+
+    #[test]
+    fn test_normalize_signs_for() {
+        let query = [1.0, 1.0];
+        let closer = [1.0, 0.5];
+        let further = [1.0, -2.0];
+
+        for distance in [
+            Distance::Dot,
+            Distance::Cosine,
+            Distance::Euclid,
+            Distance::Manhattan,
+        ] {
+            let normalized = |stored: &[VectorElementType]| {
+                compute_explanation(distance, &query, stored, None).normalize_signs_for(distance)
+            };
+            let contribution_of_dim_1 = |explanation: &ScoreExplanation| {
+                explanation
+                    .top_dimensions
+                    .iter()
+                    .find(|dim| dim.dimension == 1)
+                    .unwrap()
+                    .contribution
+            };
+
+            let closer_explanation = normalized(&closer);
+            let further_explanation = normalized(&further);
+            assert!(
+                contribution_of_dim_1(&closer_explanation)
+                    > contribution_of_dim_1(&further_explanation),
+                "{distance:?}",
+            );
+            assert!(
+                closer_explanation.total_score > further_explanation.total_score,
+                "{distance:?}"
+            );
+        }
+
+        // Reported distance is flipped, the original is kept
+        let original = compute_explanation(Distance::Euclid, &query, &further, None);
+        let normalized = original.normalize_signs_for(Distance::Euclid);
+        assert!(original.total_score > 0.0);
+        assert_eq!(normalized.total_score, -original.total_score);
+        assert_eq!(original.top_dimensions, normalized.top_dimensions);
+    }
+
+    #[test]
+    fn test_sparse_contributions() {
+        let query = SparseVector::new(vec![1, 5, 66], vec![1.0, 2.0, 3.0]).unwrap();
+        let stored = SparseVector::new(vec![66, 1, 7], vec![0.5, 4.0, 9.0]).unwrap();
+
+        let explanation = compute_sparse_explanation(&query, &stored, None);
+
+        // Only shared indices contribute: 1 and 66
+        assert_eq!(
+            explanation.top_dimensions,
+            vec![
+                DimensionContribution {
+                    dimension: 1,
+                    contribution: 4.0,
+                    label: None,
+                },
+                DimensionContribution {
+                    dimension: 66,
+                    contribution: 1.5,
+                    label: None,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_explanation_display() {
+        let v1 = vec![1.0, 5.0, 2.0, 8.0, 3.0];
+        let v2 = vec![1.0, 1.0, 1.0, 1.0, 1.0];
+
+        let explanation = compute_explanation(Distance::Dot, &v1, &v2, Some(3));
+        let output = explanation.to_string();
+        let lines: Vec<_> = output.lines().collect();
+
+        assert!(lines[0].contains("dimension"));
+        assert!(lines[0].contains("contribution"));
+        assert_eq!(
+            lines[1].split_whitespace().collect::<Vec<_>>(),
+            ["3", "+8.000000", "50.00%"]
+        );
+        assert_eq!(
+            lines[2].split_whitespace().collect::<Vec<_>>(),
+            ["1", "+5.000000", "31.25%"]
+        );
+        assert_eq!(
+            lines[3].split_whitespace().collect::<Vec<_>>(),
+            ["4", "+3.000000", "18.75%"]
+        );
+
+        assert_eq!(
+            explanation.summary(),
+            "dim 3: +8.000, dim 1: +5.000, dim 4: +3.000"
+        );
+
+        let empty = compute_explanation(Distance::Dot, &[], &[], None);
+        assert_eq!(empty.to_string(), "no contributions\n");
+    }
+
+    #[test]
+    fn test_explanation_tie_break() {
+        // Dimensions 1 and 3 contribute the same magnitude, with opposite signs
+        let v1 = vec![1.0, 4.0, 2.0, -4.0];
+        let v2 = vec![1.0, 1.0, 1.0, 1.0];
+
+        let dimensions = |tie_break| {
+            let config = ExplanationConfig {
+                top_n: 2,
+                tie_break,
+                ..Default::default()
+            };
+            try_compute_explanation(Distance::Dot, &v1, &v2, &config)
+                .unwrap()
+                .top_dimensions
+                .iter()
+                .map(|dim| dim.dimension)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(dimensions(TieBreak::Ascending), [1, 3]);
+        assert_eq!(dimensions(TieBreak::Descending), [3, 1]);
+        assert_eq!(ExplanationConfig::default().tie_break, TieBreak::Ascending);
+    }
+
+    #[test]
+    fn test_explanation_total_score() {
+        use crate::fixtures::explanation_fixtures::assert_explanations_consistent;
+
+        let query = [1.0, 2.0, 3.0];
+        let stored = [2.0, 0.0, 1.0];
+
+        let points: Vec<_> = [
+            (Distance::Dot, 5.0),
+            (Distance::Euclid, 3.0),
+            (Distance::Manhattan, 5.0),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, (distance, score))| ScoredPoint {
+            id: (id as u64).into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: Some(stored.to_vec().into()),
+            shard_key: None,
+            order_value: None,
+            // Only keep a part of the dimensions, total must still account for all of them
+            score_explanation: Some(compute_explanation(distance, &query, &stored, Some(1))),
+        })
+        .collect();
+
+        assert_explanations_consistent(&points);
+    }
+
+    #[test]
+    fn test_suggest_score_threshold() {
+        let explained_point = |id: u64, score: ScoreType, contributions: &[ScoreType]| {
+            let contributions = contributions
+                .iter()
+                .enumerate()
+                .map(|(dimension, &contribution)| DimensionContribution {
+                    dimension,
+                    contribution,
+                    label: None,
+                })
+                .collect();
+            ScoredPoint {
+                id: id.into(),
+                version: 0,
+                score,
+                payload: None,
+                vector: None,
+                shard_key: None,
+                order_value: None,
+                score_explanation: Some(ScoreExplanation::new(
+                    contributions,
+                    DEFAULT_TOP_DIMENSIONS,
+                )),
+            }
+        };
+
+        // Focused matches, driven by a single dimension, followed by diffuse ones
+        let points = vec![
+            explained_point(4, 0.6, &[0.2, 0.2, 0.1, 0.1]),
+            explained_point(1, 0.95, &[0.9, 0.05]),
+            explained_point(2, 0.9, &[0.8, 0.05, 0.05]),
+            explained_point(3, 0.85, &[0.75, 0.1]),
+            explained_point(5, 0.55, &[0.15, 0.15, 0.15, 0.1]),
+            explained_point(6, 0.5, &[0.1, 0.1, 0.1, 0.1, 0.1]),
+        ];
+
+        assert_eq!(suggest_score_threshold(&points), Some(0.85));
+
+        assert_eq!(suggest_score_threshold(&points[..1]), None);
+        assert_eq!(suggest_score_threshold(&[]), None);
+    }
+
+    #[test]
+    fn test_explanation_drift_tracker() {
+        let explanation = |contributions: &[ScoreType]| {
+            let contributions = contributions
+                .iter()
+                .enumerate()
+                .map(|(dimension, &contribution)| DimensionContribution {
+                    dimension,
+                    contribution,
+                    label: None,
+                })
+                .collect();
+            ScoreExplanation::new(contributions, DEFAULT_TOP_DIMENSIONS)
+        };
+
+        let window = Duration::from_secs(60);
+        let start = Instant::now();
+        let mut tracker = ExplanationDriftTracker::new(window, DEFAULT_TOP_DIMENSIONS);
+
+        // First window: matches are driven by dimension 0
+        tracker.record_at(&explanation(&[0.9, 0.1, 0.0]), start);
+        tracker.record_at(&explanation(&[0.8, 0.2, 0.1]), start + window / 2);
+        // Nothing to compare to yet
+        assert!(!tracker.compare_windows().drifted);
+
+        // Second window: with the same dominant dimension, there is no drift
+        tracker.record_at(&explanation(&[0.7, 0.2, 0.1]), start + window);
+        let report = tracker.compare_windows();
+        assert!(!report.drifted);
+        assert_eq!(report.previous.top_dimensions[0].dimension, 0);
+
+        // Third window: matches are now driven by dimension 2
+        tracker.record_at(&explanation(&[0.1, 0.1, 0.9]), start + window * 2);
+        tracker.record_at(
+            &explanation(&[0.2, 0.0, 0.7]),
+            start + window * 2 + window / 2,
+        );
+        let report = tracker.compare_windows();
+        assert!(report.drifted);
+        assert_eq!(report.previous.top_dimensions[0].dimension, 0);
+        assert_eq!(report.current.top_dimensions[0].dimension, 2);
+
+        // After a silent window, the previous window is too old to compare to
+        tracker.record_at(&explanation(&[0.9, 0.0, 0.0]), start + window * 5);
+        let report = tracker.compare_windows();
+        assert!(!report.drifted);
+        assert!(report.previous.top_dimensions.is_empty());
+    }
+
+    #[test]
+    fn test_explain_selected_dimensions_zero_reason() {
+        let query = [1.0, 0.0, 2.0, 0.0];
+        let stored = [0.0, 3.0, 4.0, 0.0];
+
+        let selected =
+            explain_selected_dimensions(Distance::Dot, &query, &stored, &[0, 1, 2, 3]).unwrap();
+        let reasons: Vec<_> = selected.iter().map(|dim| dim.zero_reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                Some(ZeroReason::StoredZero),
+                Some(ZeroReason::QueryZero),
+                None,
+                Some(ZeroReason::Both),
+            ],
+        );
+        assert_eq!(selected[2].contribution, 8.0);
+
+        // Zero vector components don't make a zero contribution for euclidean distance
+        let selected =
+            explain_selected_dimensions(Distance::Euclid, &query, &stored, &[0]).unwrap();
+        assert_eq!(selected[0].contribution, -1.0);
+        assert_eq!(selected[0].zero_reason, None);
+
+        let err = explain_selected_dimensions(Distance::Dot, &query, &stored, &[4]).unwrap_err();
+        assert_eq!(
+            err,
+            ExplainError::DimensionOutOfRange {
+                dimension: 4,
+                dim: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_pruned_sparse_explanation() {
+        let query = SparseVector::new(vec![1, 5, 66, 8], vec![0.1, 2.0, -3.0, 0.5]).unwrap();
+        let stored = SparseVector::new(vec![66, 1, 5, 8], vec![0.5, 4.0, 1.0, 2.0]).unwrap();
+
+        // Only indices 66 and 5 are kept, 1 and 8 are pruned out despite matching the stored vector
+        let explanation = compute_pruned_sparse_explanation(&query, &stored, 2, None);
+        assert_eq!(
+            explanation.top_dimensions,
+            vec![
+                DimensionContribution {
+                    dimension: 5,
+                    contribution: 2.0,
+                    label: None,
+                },
+                DimensionContribution {
+                    dimension: 66,
+                    contribution: -1.5,
+                    label: None,
+                },
+            ],
+        );
+        assert_eq!(explanation.total_score, 0.5);
+
+        // Without pruning, all shared indices contribute
+        let explanation = compute_pruned_sparse_explanation(&query, &stored, 4, None);
+        assert_eq!(explanation.top_dimensions.len(), 4);
+    }
+
+    #[test]
+    fn test_cosine_similarity_of_explanations() {
+        let explanation = |contributions: &[(usize, ScoreType)]| {
+            let contributions = contributions
+                .iter()
+                .map(|&(dimension, contribution)| DimensionContribution {
+                    dimension,
+                    contribution,
+                    label: None,
+                })
+                .collect();
+            ScoreExplanation::new(contributions, DEFAULT_TOP_DIMENSIONS)
+        };
+
+        let a = explanation(&[(0, 4.0), (3, 2.0)]);
+        let b = explanation(&[(3, 1.1), (0, 2.0), (7, 0.01)]);
+        let disjoint = explanation(&[(1, 5.0), (2, 1.0)]);
+
+        let similar = ScoreExplanation::cosine_similarity_of_explanations(&a, &b);
+        assert!(similar > 0.99, "{similar}");
+        assert_eq!(
+            similar,
+            ScoreExplanation::cosine_similarity_of_explanations(&b, &a),
+        );
+
+        assert_eq!(
+            ScoreExplanation::cosine_similarity_of_explanations(&a, &disjoint),
+            0.0,
+        );
+        assert_eq!(
+            ScoreExplanation::cosine_similarity_of_explanations(&a, &ScoreExplanation::default()),
+            0.0,
+        );
+    }
+
+    #[test]
+    fn test_discover_contributions_use_expanded_context() {
+        let target = vec![1.0, 0.0, 0.0];
+        let stored = [2.0, 3.0, 1.0];
+
+        let mut query = DiscoveryQuery::new(
+            target,
+            vec![ContextPair::from((
+                vec![0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0],
+            ))],
+        );
+        let initial = discover_contributions(Distance::Dot, &query, &stored).unwrap();
+
+        // Expanding the context adds a pair pulling away from the last dimension
+        query.pairs.push(ContextPair::from((
+            vec![0.0, 0.0, 0.0],
+            vec![0.0, 0.0, 2.0],
+        )));
+        let expanded = discover_contributions(Distance::Dot, &query, &stored).unwrap();
+
+        let contribution =
+            |contributions: &[DimensionContribution], dim: usize| contributions[dim].contribution;
+        assert_eq!(contribution(&initial, 0), 2.0);
+        assert_eq!(contribution(&initial, 1), 3.0);
+        assert_eq!(contribution(&initial, 2), -1.0);
+
+        // Only the expanded context is reflected
+        assert_eq!(contribution(&expanded, 0), 2.0);
+        assert_eq!(contribution(&expanded, 1), 3.0);
+        assert_eq!(contribution(&expanded, 2), -3.0);
+    }
+
+    #[test]
+    fn test_filter_dimensions() {
+        let explanation = compute_explanation(
+            Distance::Dot,
+            &[1.0, -2.0, 3.0, -4.0],
+            &[1.0, 1.0, 1.0, 1.0],
+            None,
+        );
+        assert_eq!(explanation.total_score, -2.0);
+
+        let positive = explanation.filter_dimensions(|dim| dim.contribution > 0.0);
+
+        let dimensions: Vec<_> = positive
+            .top_dimensions
+            .iter()
+            .map(|dim| dim.dimension)
+            .collect();
+        assert_eq!(dimensions, vec![2, 0]);
+        assert_eq!(positive.total_score, explanation.total_score);
+
+        // Shares are computed over the retained dimensions only
+        let csv = explanations_to_csv(&[ScoredPoint {
+            id: 1.into(),
+            version: 0,
+            score: 0.0,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: Some(positive),
+        }]);
+        assert!(csv.contains("1,2,3,75"), "{csv}");
+        assert!(csv.contains("1,0,1,25"), "{csv}");
+    }
+
+    #[test]
+    fn test_explain_in_datatype() {
+        // Vector stored as bytes, queried with fractional values
+        let query = [0.5, 1.75, 2.5];
+        let stored = [3.0, 7.0, 200.0];
+
+        let explain_in = |datatype| {
+            let config = ExplanationConfig {
+                top_n: 3,
+                datatype: Some(datatype),
+                ..Default::default()
+            };
+            try_compute_explanation(Distance::Dot, &query, &stored, &config).unwrap()
+        };
+        let contribution = |explanation: &ScoreExplanation, dimension| {
+            explanation
+                .top_dimensions
+                .iter()
+                .find(|dim| dim.dimension == dimension)
+                .unwrap()
+                .contribution
+        };
+
+        let float = explain_in(VectorStorageDatatype::Float32);
+        assert_eq!(contribution(&float, 0), 1.5);
+        assert_eq!(contribution(&float, 2), 500.0);
+
+        // In the byte space, the query is truncated to integers as well
+        let byte = explain_in(VectorStorageDatatype::Uint8);
+        assert_eq!(contribution(&byte, 0), 0.0);
+        assert_eq!(contribution(&byte, 1), 7.0);
+        assert_eq!(contribution(&byte, 2), 400.0);
+        assert_eq!(byte.total_score, 407.0);
+
+        let default = try_compute_explanation(
+            Distance::Dot,
+            &query,
+            &stored,
+            &ExplanationConfig {
+                top_n: 3,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(default.top_dimensions, float.top_dimensions);
+    }
+
+    #[test]
+    fn test_explanation_norms() {
+        let query = [1.0, 2.0, 3.0];
+        let stored = [4.0, 5.0, 6.0];
+
+        for distance in [Distance::Cosine, Distance::Dot] {
+            let explanation = compute_explanation(distance, &query, &stored, None);
+            assert_eq!(explanation.query_norm, Some(14.0_f32.sqrt()));
+            assert_eq!(explanation.stored_norm, Some(77.0_f32.sqrt()));
+        }
+
+        let sparse = compute_sparse_explanation(
+            &SparseVector::new(vec![0], vec![1.0]).unwrap(),
+            &SparseVector::new(vec![0], vec![2.0]).unwrap(),
+            None,
+        );
+        assert_eq!(sparse.query_norm, None);
+    }
+
+    #[test]
+    fn test_baseline_explanation_highlights_deviations() {
+        let centroid = [1.0, 1.0, 1.0, 1.0];
+        let stored = [1.0, 4.0, 0.5, 1.0];
+
+        let baseline =
+            compute_baseline_explanation(&stored, &centroid, &ExplanationConfig::default())
+                .unwrap();
+
+        // Largest deviation first, typical dimensions don't contribute
+        assert_eq!(baseline.top_dimensions[0].dimension, 1);
+        assert_eq!(baseline.top_dimensions[0].contribution, -9.0);
+        assert_eq!(baseline.top_dimensions[1].dimension, 2);
+        assert_eq!(baseline.top_dimensions[1].contribution, -0.25);
+        assert!(
+            baseline.top_dimensions[2..]
+                .iter()
+                .all(|dim| dim.contribution == 0.0)
+        );
+
+        assert!(
+            compute_baseline_explanation(&stored, &centroid[..2], &Default::default()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_deferred_offset_explanations_match_eager_ones() {
+        let query = [1.0, -1.0, 2.0];
+        let storage: Vec<DenseVector> = vec![
+            vec![0.0, 1.0, 0.0],
+            vec![3.0, 0.0, 1.0],
+            vec![1.0, 1.0, 1.0],
+            vec![0.5, -2.0, 4.0],
+            vec![2.0, 2.0, 2.0],
+        ];
+        let config = ExplanationConfig::default();
+
+        // Eager: every point is explained before the top-K is chosen
+        let eager: Vec<_> = storage
+            .iter()
+            .map(|stored| try_compute_explanation(Distance::Dot, &query, stored, &config).unwrap())
+            .collect();
+
+        let mut scored: Vec<_> = eager
+            .iter()
+            .enumerate()
+            .map(|(idx, explanation)| ScoredPointOffset {
+                idx: idx as PointOffsetType,
+                score: explanation.total_score,
+            })
+            .collect();
+        scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scored.truncate(2);
+
+        // Deferred: only the final top-K is fetched and explained
+        let mut fetched = Vec::new();
+        let deferred = explain_scored_offsets(
+            Distance::Dot,
+            &query,
+            &scored,
+            |offset| {
+                fetched.push(offset);
+                storage
+                    .get(offset as usize)
+                    .map(|v| Cow::Borrowed(v.as_slice()))
+            },
+            &config,
+        );
+
+        assert_eq!(fetched, vec![3, 1]);
+        for (offset, explanation) in deferred {
+            let explanation = explanation.unwrap();
+            let expected = &eager[offset as usize];
+            assert_eq!(explanation.top_dimensions, expected.top_dimensions);
+            assert_eq!(explanation.total_score, expected.total_score);
+        }
+
+        let missing = ScoredPointOffset {
+            idx: 42,
+            score: 0.0,
+        };
+        let deferred = explain_scored_offsets(Distance::Dot, &query, &[missing], |_| None, &config);
+        assert_eq!(
+            deferred[0].1.as_ref().unwrap().status,
+            common::types::ExplanationStatus::Unavailable(UnavailableReason::VectorMissing),
+        );
+    }
 }
@@ -11,6 +11,7 @@ use std::sync::Arc;
 
 use ahash::AHashSet;
 use common::stable_hash::StableHash;
+pub use common::types::Order;
 use common::types::{ScoreExplanation, ScoreType};
 use ecow::EcoString;
 use fnv::FnvBuildHasher;
@@ -348,10 +349,10 @@ impl Distance {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub enum Order {
-    LargeBetter,
-    SmallBetter,
+impl From<Distance> for Order {
+    fn from(distance: Distance) -> Self {
+        distance.distance_order()
+    }
 }
 
 /// Search result
@@ -505,6 +506,12 @@ pub struct QuantizationSearchParams {
     #[validate(range(min = 1.0))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oversampling: Option<f64>,
+
+    /// Explain the results with their original vectors, comparing the contributions
+    /// with the ones of their quantized vectors. Set from the explanation params of the request.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub compare_explanations: bool,
 }
 
 impl Hash for QuantizationSearchParams {
@@ -513,10 +520,12 @@ impl Hash for QuantizationSearchParams {
             ignore,
             rescore,
             oversampling,
+            compare_explanations,
         } = self;
         ignore.hash(state);
         rescore.hash(state);
         oversampling.map(OrderedFloat).hash(state);
+        compare_explanations.hash(state);
     }
 }
 
@@ -592,6 +601,12 @@ pub struct SearchParams {
     #[validate(nested)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub acorn: Option<AcornSearchParams>,
+
+    /// Explain the results with whether the segment they were found in searched them approximately.
+    /// Set for requests with explanations.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub explain_search_path: bool,
 }
 
 /// Configuration for vectors.
@@ -3332,7 +3347,7 @@ pub trait CustomIdCheckerCondition: fmt::Debug {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash)]
 #[serde(untagged, rename_all = "snake_case")]
 #[serde(
-    expecting = "Expected a boolean, an array of strings, or an object with an include/exclude field"
+    expecting = "Expected a boolean, an array of strings, or an object with an include/exclude/transform field"
 )]
 pub enum WithPayloadInterface {
     /// If `true` - return all payload,
@@ -3423,6 +3438,20 @@ impl WithPayloadInterface {
             _ => true,
         }
     }
+
+    /// Split off a transformation, to fetch only its source fields and transform them afterwards.
+    ///
+    /// Shards return the source fields as they are, so transformations are applied once on
+    /// the final results instead, regardless of where the points come from.
+    pub fn take_transform(&mut self) -> Option<PayloadSelectorTransform> {
+        let WithPayloadInterface::Selector(PayloadSelector::Transform(transform)) = self else {
+            return None;
+        };
+        let source_keys = transform.source_keys();
+        let transform = mem::replace(transform, PayloadSelectorTransform { transform: vec![] });
+        *self = WithPayloadInterface::Fields(source_keys);
+        Some(transform)
+    }
 }
 
 impl From<bool> for WithPayload {
@@ -3485,6 +3514,101 @@ impl PayloadSelectorExclude {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PayloadSelectorTransform {
+    /// Only include these payload fields, transformed as specified
+    pub transform: Vec<PayloadFieldTransform>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PayloadFieldTransform {
+    /// Payload field to include, may point into nested objects
+    pub key: PayloadKeyType,
+    /// Top-level key to return the value under, instead of its original path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename: Option<String>,
+    /// Cut strings of the value longer than this number of characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+}
+
+impl PayloadSelectorTransform {
+    /// Payload fields the transformation is computed from
+    pub fn source_keys(&self) -> Vec<PayloadKeyType> {
+        self.transform
+            .iter()
+            .map(|field| field.key.clone())
+            .unique()
+            .collect()
+    }
+
+    pub fn apply(&self, payload: &Payload) -> Payload {
+        let mut transformed = Map::new();
+        for field in &self.transform {
+            match &field.rename {
+                Some(rename) => {
+                    let mut values = payload.get_value(&field.key);
+                    let mut value = match values.len() {
+                        0 => continue,
+                        1 => values.swap_remove(0).clone(),
+                        _ => Value::Array(values.into_iter().cloned().collect()),
+                    };
+                    if let Some(max_length) = field.max_length {
+                        truncate_strings(&mut value, max_length);
+                    }
+                    transformed.insert(rename.clone(), value);
+                }
+                None => {
+                    let mut included =
+                        Value::Object(JsonPath::value_filter(&payload.0, |key, _| {
+                            key.check_include_pattern(&field.key)
+                        }));
+                    if let Some(max_length) = field.max_length {
+                        truncate_strings(&mut included, max_length);
+                    }
+                    if let Value::Object(included) = included {
+                        merge_json_objects(&mut transformed, included);
+                    }
+                }
+            }
+        }
+        Payload(transformed)
+    }
+}
+
+fn truncate_strings(value: &mut Value, max_length: usize) {
+    match value {
+        Value::String(string) => {
+            if let Some((end, _)) = string.char_indices().nth(max_length) {
+                string.truncate(end);
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| truncate_strings(value, max_length)),
+        Value::Object(object) => object
+            .values_mut()
+            .for_each(|value| truncate_strings(value, max_length)),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+/// Recursively merge `other` into `target`, values of `other` win over non-object values
+fn merge_json_objects(target: &mut Map<String, Value>, other: Map<String, Value>) {
+    for (key, value) in other {
+        match (target.get_mut(&key), value) {
+            (Some(Value::Object(target)), Value::Object(other)) => {
+                merge_json_objects(target, other)
+            }
+            (_, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
 /// Specifies how to treat payload selector
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
 #[serde(untagged, rename_all = "snake_case")]
@@ -3493,6 +3617,8 @@ pub enum PayloadSelector {
     Include(PayloadSelectorInclude),
     /// Exclude this fields from result payload. Keep all other fields.
     Exclude(PayloadSelectorExclude),
+    /// Include only this fields into response payload, renamed or truncated
+    Transform(PayloadSelectorTransform),
 }
 
 impl From<PayloadSelectorExclude> for WithPayloadInterface {
@@ -3537,6 +3663,7 @@ impl PayloadSelector {
                     .all(|pattern| !pattern.check_exclude_pattern(key))
             })
             .into(),
+            PayloadSelector::Transform(selector) => selector.apply(&x),
         }
     }
 }
@@ -4984,6 +5111,65 @@ mod tests {
         };
         assert_eq!(payload, expected);
     }
+
+    #[test]
+    fn test_payload_selector_transform() {
+        let payload = payload_json! {
+            "title": "A rather long title",
+            "meta": {
+                "author": {
+                    "name": "Jane",
+                    "age": 42
+                },
+                "tags": ["first", "second"]
+            },
+            "items": [
+                { "name": "apple" },
+                { "name": "banana" }
+            ]
+        };
+
+        let selector: PayloadSelector = serde_json::from_str(
+            r#"{"transform": [
+                {"key": "title", "max_length": 8},
+                {"key": "meta.author.name", "rename": "author"},
+                {"key": "meta.tags"},
+                {"key": "items[].name", "rename": "names", "max_length": 3}
+            ]}"#,
+        )
+        .unwrap();
+        let payload = selector.process(payload);
+
+        let expected = payload_json! {
+            "title": "A rather",
+            "author": "Jane",
+            "meta": {
+                "tags": ["first", "second"]
+            },
+            "names": ["app", "ban"]
+        };
+        assert_eq!(payload, expected);
+    }
+
+    #[test]
+    fn test_with_payload_take_transform() {
+        let mut with_payload: WithPayloadInterface = serde_json::from_str(
+            r#"{"transform": [{"key": "a.b", "rename": "b"}, {"key": "c"}, {"key": "a.b"}]}"#,
+        )
+        .unwrap();
+
+        let transform = with_payload.take_transform().unwrap();
+        assert_eq!(transform.transform.len(), 3);
+        assert_eq!(
+            with_payload,
+            WithPayloadInterface::Fields(vec![JsonPath::new("a.b"), JsonPath::new("c")]),
+        );
+        assert!(with_payload.take_transform().is_none());
+
+        let payload = payload_json! { "a": { "b": 1, "x": 2 }, "c": 3, "d": 4 };
+        let payload = transform.apply(&payload);
+        assert_eq!(payload, payload_json! { "b": 1, "c": 3, "a": { "b": 1 } });
+    }
 }
 
 fn shard_key_string_example() -> String {
@@ -19,7 +19,7 @@ use super::quantized_scorer_builder::QuantizedScorerBuilder;
 use crate::common::Flusher;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::primitive::PrimitiveVectorElement;
-use crate::data_types::vectors::{QueryVector, VectorElementType, VectorRef};
+use crate::data_types::vectors::{DenseVector, QueryVector, VectorElementType, VectorRef};
 use crate::types::{
     BinaryQuantization, BinaryQuantizationConfig, BinaryQuantizationEncoding,
     BinaryQuantizationQueryEncoding, CompressionRatio, Distance, MultiVectorConfig,
@@ -292,6 +292,33 @@ impl QuantizedVectors {
         }
     }
 
+    /// Approximate vector of point `id`, reconstructed from the quantized storage only,
+    /// without reading the original vector.
+    ///
+    /// Only scalar quantization of single dense vectors can be reconstructed, `None` otherwise.
+    pub fn dequantize_vector(&self, id: PointOffsetType) -> Option<DenseVector> {
+        match &self.storage_impl {
+            QuantizedVectorStorage::ScalarRam(storage) => Some(storage.dequantize(id)),
+            QuantizedVectorStorage::ScalarMmap(storage) => Some(storage.dequantize(id)),
+            QuantizedVectorStorage::ScalarChunkedMmap(storage) => Some(storage.dequantize(id)),
+            QuantizedVectorStorage::PQRam(_)
+            | QuantizedVectorStorage::PQMmap(_)
+            | QuantizedVectorStorage::PQChunkedMmap(_)
+            | QuantizedVectorStorage::BinaryRam(_)
+            | QuantizedVectorStorage::BinaryMmap(_)
+            | QuantizedVectorStorage::BinaryChunkedMmap(_)
+            | QuantizedVectorStorage::ScalarRamMulti(_)
+            | QuantizedVectorStorage::ScalarMmapMulti(_)
+            | QuantizedVectorStorage::ScalarChunkedMmapMulti(_)
+            | QuantizedVectorStorage::PQRamMulti(_)
+            | QuantizedVectorStorage::PQMmapMulti(_)
+            | QuantizedVectorStorage::PQChunkedMmapMulti(_)
+            | QuantizedVectorStorage::BinaryRamMulti(_)
+            | QuantizedVectorStorage::BinaryMmapMulti(_)
+            | QuantizedVectorStorage::BinaryChunkedMmapMulti(_) => None,
+        }
+    }
+
     pub fn raw_scorer<'a>(
         &'a self,
         query: QueryVector,
@@ -0,0 +1,34 @@
+use segment::explain::{
+    DEFAULT_TOP_DIMENSIONS, DimensionContribution, ExplainError, ExplanationConfig,
+    ScoreExplanation, compute_contributions, compute_explanation, dot_product_contributions,
+    try_compute_explanation,
+};
+use segment::types::Distance;
+
+#[test]
+fn explain_facade_test() {
+    let query = [1.0, 5.0, 2.0, 8.0];
+    let stored = [1.0, 1.0, 1.0, 1.0];
+
+    let contributions: Vec<DimensionContribution> =
+        compute_contributions(Distance::Dot, &query, &stored);
+    assert_eq!(contributions, dot_product_contributions(&query, &stored));
+
+    let explanation: ScoreExplanation = compute_explanation(Distance::Dot, &query, &stored, None);
+    assert_eq!(
+        explanation.top_dimensions.len(),
+        4.min(DEFAULT_TOP_DIMENSIONS)
+    );
+    assert_eq!(explanation.top_dimensions[0].dimension, 3);
+    assert_eq!(explanation.top_dimensions[0].contribution, 8.0);
+
+    let config = ExplanationConfig {
+        top_n: 2,
+        ..Default::default()
+    };
+    let explanation = try_compute_explanation(Distance::Dot, &query, &stored, &config).unwrap();
+    assert_eq!(explanation.top_dimensions.len(), 2);
+
+    let err = try_compute_explanation(Distance::Dot, &query, &stored[..2], &config).unwrap_err();
+    assert!(matches!(err, ExplainError::DimensionMismatch { .. }));
+}
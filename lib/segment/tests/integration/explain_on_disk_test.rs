@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use common::counter::hardware_counter::HardwareCounterCell;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, DenseVector, only_default_vector};
+use segment::entry::entry_point::SegmentEntry;
+use segment::explain::{ScoreExplanation, compute_explanation};
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::segment::Segment;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, Indexes, SegmentConfig, VectorDataConfig, VectorStorageType, WithPayload,
+};
+use tempfile::Builder;
+
+const DIM: usize = 8;
+const DISTANCE: Distance = Distance::Cosine;
+
+fn build_segment_with_storage(
+    path: &Path,
+    storage_type: VectorStorageType,
+    vectors: &[DenseVector],
+) -> Segment {
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance: DISTANCE,
+                storage_type,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let hw_counter = HardwareCounterCell::new();
+    let mut segment = build_segment(path, &config, true).unwrap();
+    for (n, vector) in vectors.iter().enumerate() {
+        segment
+            .upsert_point(
+                n as u64,
+                (n as u64).into(),
+                only_default_vector(vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+    segment
+}
+
+/// Search the segment, and explain each result from the vector it returned
+fn search_and_explain(segment: &Segment, query: &[f32]) -> Vec<ScoreExplanation> {
+    let query_vector = query.to_vec().into();
+    let results = segment
+        .search(
+            DEFAULT_VECTOR_NAME,
+            &query_vector,
+            &WithPayload::default(),
+            &true.into(),
+            None,
+            10,
+            None,
+        )
+        .unwrap();
+
+    results
+        .iter()
+        .map(|point| {
+            let vector = point.vector.as_ref().unwrap();
+            let stored: &[f32] = vector.get(DEFAULT_VECTOR_NAME).unwrap().try_into().unwrap();
+            compute_explanation(DISTANCE, query, stored, Some(DIM))
+        })
+        .collect()
+}
+
+#[test]
+fn explain_on_disk_test() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let vectors: Vec<_> = (0..50).map(|_| random_vector(&mut rng, DIM)).collect();
+    let query = random_vector(&mut rng, DIM);
+
+    let ram_dir = Builder::new().prefix("ram_segment").tempdir().unwrap();
+    let ram_storage = if cfg!(feature = "rocksdb") {
+        VectorStorageType::Memory
+    } else {
+        VectorStorageType::InRamChunkedMmap
+    };
+    let ram_segment = build_segment_with_storage(ram_dir.path(), ram_storage, &vectors);
+
+    let disk_dir = Builder::new().prefix("disk_segment").tempdir().unwrap();
+    let disk_segment =
+        build_segment_with_storage(disk_dir.path(), VectorStorageType::ChunkedMmap, &vectors);
+
+    let ram_explanations = search_and_explain(&ram_segment, &query);
+    let disk_explanations = search_and_explain(&disk_segment, &query);
+
+    assert_eq!(ram_explanations.len(), 10);
+    assert_eq!(ram_explanations.len(), disk_explanations.len());
+    for (ram, disk) in ram_explanations.iter().zip(&disk_explanations) {
+        assert_eq!(ram.top_dimensions.len(), DIM);
+        assert_eq!(ram.top_dimensions, disk.top_dimensions);
+        assert_eq!(ram.total_score, disk.total_score);
+    }
+}
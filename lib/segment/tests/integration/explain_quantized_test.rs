@@ -0,0 +1,113 @@
+use std::sync::atomic::AtomicBool;
+
+use common::counter::hardware_counter::HardwareCounterCell;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use segment::data_types::vectors::only_default_vector;
+use segment::entry::entry_point::SegmentEntry;
+use segment::explain::{
+    ExplanationConfig, VectorSource, compute_explanation, compute_quantization_error_explanation,
+    compute_quantized_explanation,
+};
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
+use segment::types::{Distance, ScalarQuantizationConfig};
+use segment::vector_storage::quantized::quantized_vectors::{
+    QuantizedVectors, QuantizedVectorsStorageType,
+};
+use tempfile::Builder;
+
+#[test]
+fn explain_quantized_test() {
+    let dim = 16;
+    let num_vectors = 50;
+    let stopped = AtomicBool::new(false);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let quantized_dir = Builder::new().prefix("quantized_dir").tempdir().unwrap();
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), dim, Distance::Dot).unwrap();
+    let vectors: Vec<_> = (0..num_vectors)
+        .map(|_| random_vector(&mut rng, dim))
+        .collect();
+    for (n, vector) in vectors.iter().enumerate() {
+        segment
+            .upsert_point(
+                n as u64,
+                (n as u64).into(),
+                only_default_vector(vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+
+    let quantized_vectors = {
+        let vector_data = segment.vector_data.values().next().unwrap();
+        QuantizedVectors::create(
+            &vector_data.vector_storage.borrow(),
+            &ScalarQuantizationConfig {
+                r#type: Default::default(),
+                quantile: None,
+                always_ram: Some(true),
+            }
+            .into(),
+            QuantizedVectorsStorageType::Immutable,
+            quantized_dir.path(),
+            4,
+            &stopped,
+        )
+        .unwrap()
+    };
+
+    // Original vectors can't be read anymore, explanations only rely on quantized storage
+    drop(segment);
+    dir.close().unwrap();
+
+    let query = random_vector(&mut rng, dim);
+    let config = ExplanationConfig {
+        top_n: dim,
+        ..Default::default()
+    };
+
+    for (offset, vector) in vectors.iter().enumerate() {
+        let explanation = compute_quantized_explanation(
+            Distance::Dot,
+            &query,
+            &quantized_vectors,
+            offset as u32,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(explanation.vector_source, VectorSource::Quantized);
+        assert_eq!(explanation.top_dimensions.len(), dim);
+
+        let exact = compute_explanation(Distance::Dot, &query, vector, Some(dim));
+        assert_eq!(exact.vector_source, VectorSource::Original);
+        assert!((explanation.total_score - exact.total_score).abs() < dim as f32 * 0.05);
+
+        // Compared explanations stay exact, and report a small error per dimension
+        let compared = compute_quantization_error_explanation(
+            Distance::Dot,
+            &query,
+            vector,
+            &quantized_vectors,
+            offset as u32,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(compared.vector_source, VectorSource::Original);
+        assert_eq!(compared.top_dimensions, exact.top_dimensions);
+        assert_eq!(compared.quantization_error.len(), dim);
+        for (error, dimension) in compared
+            .quantization_error
+            .iter()
+            .zip(&compared.top_dimensions)
+        {
+            assert_eq!(error.dimension, dimension.dimension);
+            assert!(error.contribution.abs() < 0.05);
+        }
+    }
+}
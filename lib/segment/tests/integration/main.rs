@@ -3,6 +3,9 @@ mod byte_storage_hnsw_test;
 mod byte_storage_quantization_test;
 mod disbalanced_vectors_test;
 mod exact_search_test;
+mod explain_facade_test;
+mod explain_on_disk_test;
+mod explain_quantized_test;
 mod fail_recovery_test;
 mod filtering_context_check;
 mod filtrable_hnsw_test;
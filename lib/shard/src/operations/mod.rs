@@ -4,8 +4,9 @@ pub mod point_ops;
 pub mod staging;
 pub mod vector_ops;
 
+use chrono::{DateTime, Utc};
 use segment::json_path::JsonPath;
-use segment::types::{PayloadFieldSchema, PointIdType};
+use segment::types::{Filter, PayloadFieldSchema, PointIdType, SeqNumberType};
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 
@@ -58,6 +59,7 @@ impl CollectionUpdateOperations {
                 PointOperations::SyncPoints(op) => {
                     Some(op.points.iter().map(|point| point.id).collect())
                 }
+                PointOperations::RestorePoints { ids } => Some(ids.clone()),
                 #[cfg(feature = "staging")]
                 PointOperations::TestDelay(_) => None,
             },
@@ -78,6 +80,68 @@ impl CollectionUpdateOperations {
             Self::FieldIndexOperation(_) => (),
         }
     }
+
+    pub fn precondition(&self) -> Option<&UpdatePrecondition> {
+        match self {
+            Self::PointOperation(op) => match op {
+                PointOperations::UpsertPointsConditional(op) => op.precondition.as_ref(),
+                PointOperations::DeletePoints { precondition, .. } => precondition.as_ref(),
+                PointOperations::UpsertPoints(_)
+                | PointOperations::DeletePointsByFilter(_)
+                | PointOperations::SyncPoints(_)
+                | PointOperations::RestorePoints { .. } => None,
+                #[cfg(feature = "staging")]
+                PointOperations::TestDelay(_) => None,
+            },
+            Self::PayloadOperation(op) => match op {
+                payload_ops::PayloadOps::SetPayload(op)
+                | payload_ops::PayloadOps::OverwritePayload(op) => op.precondition.as_ref(),
+                payload_ops::PayloadOps::DeletePayload(_)
+                | payload_ops::PayloadOps::ClearPayload { .. }
+                | payload_ops::PayloadOps::ClearPayloadByFilter(_) => None,
+            },
+            Self::VectorOperation(_) => None,
+            Self::FieldIndexOperation(_) => None,
+        }
+    }
+
+    /// Remove the precondition of the operation, if any, so that it is applied unconditionally
+    pub fn take_precondition(&mut self) -> Option<UpdatePrecondition> {
+        match self {
+            Self::PointOperation(op) => match op {
+                PointOperations::UpsertPointsConditional(op) => op.precondition.take(),
+                PointOperations::DeletePoints { precondition, .. } => precondition.take(),
+                PointOperations::UpsertPoints(_)
+                | PointOperations::DeletePointsByFilter(_)
+                | PointOperations::SyncPoints(_)
+                | PointOperations::RestorePoints { .. } => None,
+                #[cfg(feature = "staging")]
+                PointOperations::TestDelay(_) => None,
+            },
+            Self::PayloadOperation(op) => match op {
+                payload_ops::PayloadOps::SetPayload(op)
+                | payload_ops::PayloadOps::OverwritePayload(op) => op.precondition.take(),
+                payload_ops::PayloadOps::DeletePayload(_)
+                | payload_ops::PayloadOps::ClearPayload { .. }
+                | payload_ops::PayloadOps::ClearPayloadByFilter(_) => None,
+            },
+            Self::VectorOperation(_) => None,
+            Self::FieldIndexOperation(_) => None,
+        }
+    }
+
+    /// Set the time points are deleted at, if the operation deletes points and it isn't set yet
+    ///
+    /// Set once when the operation is accepted, so that points are soft deleted at the same time
+    /// on all replicas, on WAL replay and in shard transfers.
+    pub fn set_deleted_at(&mut self, deleted_at: DateTime<Utc>) {
+        match self {
+            Self::PointOperation(op) => op.set_deleted_at(deleted_at),
+            Self::VectorOperation(_) => (),
+            Self::PayloadOperation(_) => (),
+            Self::FieldIndexOperation(_) => (),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants, Hash)]
@@ -97,6 +161,48 @@ pub struct CreateIndex {
     pub field_schema: Option<PayloadFieldSchema>,
 }
 
+/// Preconditions of an update, checked on every listed point before any of them is changed.
+///
+/// If a point doesn't meet them, the whole operation is rejected with a conflict error.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, Hash)]
+pub struct UpdatePrecondition {
+    /// Points must exist and have exactly this version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<SeqNumberType>,
+    /// Points must exist and have payload matching this filter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_payload_matches: Option<Filter>,
+    /// Points must not exist yet, so that the update only inserts new points
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub if_not_exists: bool,
+}
+
+impl UpdatePrecondition {
+    /// Precondition from the optional parameters of a request, `None` if none is set
+    pub fn new(
+        if_version: Option<SeqNumberType>,
+        if_payload_matches: Option<Filter>,
+    ) -> Option<Self> {
+        if if_version.is_none() && if_payload_matches.is_none() {
+            return None;
+        }
+        Some(Self {
+            if_version,
+            if_payload_matches,
+            if_not_exists: false,
+        })
+    }
+
+    /// Precondition of an update which only inserts new points
+    pub fn not_exists() -> Self {
+        Self {
+            if_version: None,
+            if_payload_matches: None,
+            if_not_exists: true,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct OperationWithClockTag {
     #[serde(flatten)]
@@ -291,13 +397,24 @@ mod tests {
 
         fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
             let upsert = Self::UpsertPoints(PointInsertOperationsInternal::PointsList(Vec::new()));
-            let delete = Self::DeletePoints { ids: Vec::new() };
+            let delete = Self::DeletePoints {
+                ids: Vec::new(),
+                precondition: None,
+                deleted_at: None,
+            };
 
-            let delete_by_filter = Self::DeletePointsByFilter(Filter {
+            let filter = Filter {
                 should: None,
                 min_should: None,
                 must: None,
                 must_not: None,
+            };
+
+            let delete_by_filter = Self::DeletePointsByFilter(filter.clone().into());
+
+            let delete_by_filter_at = Self::DeletePointsByFilter(DeleteByFilterOperation {
+                filter,
+                deleted_at: DateTime::from_timestamp(1_700_000_000, 0),
             });
 
             let sync = Self::SyncPoints(PointSyncOperation {
@@ -310,6 +427,7 @@ mod tests {
                 Just(upsert),
                 Just(delete),
                 Just(delete_by_filter),
+                Just(delete_by_filter_at),
                 Just(sync),
             ]
             .boxed()
@@ -330,6 +448,8 @@ mod tests {
                 PointIdsList {
                     points: Vec::new(),
                     shard_key: None,
+                    if_version: None,
+                    if_payload_matches: None,
                 },
                 Vec::new(),
             );
@@ -358,6 +478,7 @@ mod tests {
                 points: None,
                 filter: None,
                 key: None,
+                precondition: None,
             });
 
             let overwrite = Self::OverwritePayload(SetPayloadOp {
@@ -365,6 +486,7 @@ mod tests {
                 points: None,
                 filter: None,
                 key: None,
+                precondition: None,
             });
 
             let delete = Self::DeletePayload(DeletePayloadOp {
@@ -3,12 +3,14 @@ use std::fmt;
 use api::rest::ShardKeySelector;
 use schemars::JsonSchema;
 use segment::json_path::JsonPath;
-use segment::types::{Filter, Payload, PayloadKeyType, PointIdType};
+use segment::types::{Filter, Payload, PayloadKeyType, PointIdType, SeqNumberType};
 use serde;
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
 
+use super::UpdatePrecondition;
+
 /// Define operations description for point payloads manipulation
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants, Hash)]
 #[strum_discriminants(derive(EnumIter))]
@@ -74,6 +76,13 @@ pub struct SetPayload {
     pub shard_key: Option<ShardKeySelector>,
     /// Assigns payload to each point that satisfy this path of property
     pub key: Option<JsonPath>,
+    /// If specified, all listed points must already exist with this version on the shard's leader replica, otherwise the request fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<SeqNumberType>,
+    /// If specified, all listed points must already exist and match this filter, otherwise the request fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub if_payload_matches: Option<Filter>,
 }
 
 /// This data structure is used inside shard operations queue
@@ -90,6 +99,9 @@ pub struct SetPayloadOp {
     pub filter: Option<Filter>,
     /// Payload selector to indicate property of payload, e.g. `a.b.c`
     pub key: Option<JsonPath>,
+    /// Precondition to check on listed points, rejects the whole operation if not met
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precondition: Option<UpdatePrecondition>,
 }
 
 /// This data structure is used in API interface and applied across multiple shards
@@ -129,6 +141,10 @@ struct SetPayloadShadow {
     pub filter: Option<Filter>,
     pub shard_key: Option<ShardKeySelector>,
     pub key: Option<JsonPath>,
+    #[serde(default)]
+    pub if_version: Option<SeqNumberType>,
+    #[serde(default)]
+    pub if_payload_matches: Option<Filter>,
 }
 
 impl TryFrom<SetPayloadShadow> for SetPayload {
@@ -141,6 +157,8 @@ impl TryFrom<SetPayloadShadow> for SetPayload {
             filter,
             shard_key,
             key,
+            if_version,
+            if_payload_matches,
         } = value;
 
         if points.is_some() || filter.is_some() {
@@ -150,6 +168,8 @@ impl TryFrom<SetPayloadShadow> for SetPayload {
                 filter,
                 shard_key,
                 key,
+                if_version,
+                if_payload_matches,
             })
         } else {
             Err(PointsSelectorValidationError)
@@ -7,6 +7,7 @@ use api::conversions::json::payload_to_proto;
 use api::rest::{
     DenseVector, MultiDenseVector, ShardKeySelector, VectorOutput, VectorStructOutput,
 };
+use chrono::{DateTime, Utc};
 use common::validation::validate_multi_vector;
 use itertools::Itertools as _;
 use ordered_float::OrderedFloat;
@@ -18,7 +19,7 @@ use segment::data_types::vectors::{
     BatchVectorStructInternal, DEFAULT_VECTOR_NAME, MultiDenseVectorInternal, VectorInternal,
     VectorStructInternal,
 };
-use segment::types::{Filter, Payload, PointIdType, VectorNameBuf};
+use segment::types::{Filter, Payload, PointIdType, SeqNumberType, VectorNameBuf};
 use serde::{Deserialize, Serialize};
 use sparse::common::types::{DimId, DimWeight};
 use strum::{EnumDiscriminants, EnumIter};
@@ -35,6 +36,13 @@ pub struct PointIdsList {
     pub points: Vec<PointIdType>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// If specified, all points must already exist with this version on the shard's leader replica, otherwise deletion fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<SeqNumberType>,
+    /// If specified, all points must already exist and match this filter, otherwise deletion fails with a conflict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub if_payload_matches: Option<Filter>,
 }
 
 impl From<Vec<PointIdType>> for PointIdsList {
@@ -42,10 +50,22 @@ impl From<Vec<PointIdType>> for PointIdsList {
         Self {
             points,
             shard_key: None,
+            if_version: None,
+            if_payload_matches: None,
         }
     }
 }
 
+/// Deleted points to restore, if soft delete is enabled for the collection
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct RestorePoints {
+    #[validate(length(min = 1))]
+    pub points: Vec<PointIdType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
 // General idea of having an extra layer of data structures after REST and gRPC
 // is to ensure that all vectors are inferenced and validated before they are persisted.
 //
@@ -100,11 +120,20 @@ pub enum PointOperations {
     /// Insert points, or update existing points if condition matches
     UpsertPointsConditional(ConditionalInsertOperationInternal),
     /// Delete point if exists
-    DeletePoints { ids: Vec<PointIdType> },
+    DeletePoints {
+        ids: Vec<PointIdType>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        precondition: Option<UpdatePrecondition>,
+        /// When the deletion was accepted, to soft delete points at the same time on all replicas
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        deleted_at: Option<DateTime<Utc>>,
+    },
     /// Delete points by given filter criteria
-    DeletePointsByFilter(Filter),
+    DeletePointsByFilter(DeleteByFilterOperation),
     /// Points Sync
     SyncPoints(PointSyncOperation),
+    /// Restore soft deleted points from their tombstones
+    RestorePoints { ids: Vec<PointIdType> },
     /// Introduce artificial delay for testing purposes
     #[cfg(feature = "staging")]
     TestDelay(super::staging::TestDelayOperation),
@@ -115,9 +144,10 @@ impl PointOperations {
         match self {
             Self::UpsertPoints(op) => Some(op.point_ids()),
             Self::UpsertPointsConditional(op) => Some(op.points_op.point_ids()),
-            Self::DeletePoints { ids } => Some(ids.clone()),
+            Self::DeletePoints { ids, .. } => Some(ids.clone()),
             Self::DeletePointsByFilter(_) => None,
             Self::SyncPoints(op) => Some(op.points.iter().map(|point| point.id).collect()),
+            Self::RestorePoints { ids } => Some(ids.clone()),
             #[cfg(feature = "staging")]
             Self::TestDelay(_) => None,
         }
@@ -132,9 +162,26 @@ impl PointOperations {
             Self::UpsertPointsConditional(op) => {
                 op.points_op.retain_point_ids(filter);
             }
-            Self::DeletePoints { ids } => ids.retain(filter),
+            Self::DeletePoints { ids, .. } => ids.retain(filter),
             Self::DeletePointsByFilter(_) => (),
             Self::SyncPoints(op) => op.points.retain(|point| filter(&point.id)),
+            Self::RestorePoints { ids } => ids.retain(filter),
+            #[cfg(feature = "staging")]
+            Self::TestDelay(_) => (),
+        }
+    }
+
+    /// Set the time points are deleted at, if the operation deletes points and it isn't set yet
+    pub fn set_deleted_at(&mut self, time: DateTime<Utc>) {
+        match self {
+            Self::DeletePoints { deleted_at, .. }
+            | Self::DeletePointsByFilter(DeleteByFilterOperation { deleted_at, .. }) => {
+                deleted_at.get_or_insert(time);
+            }
+            Self::UpsertPoints(_)
+            | Self::UpsertPointsConditional(_)
+            | Self::SyncPoints(_)
+            | Self::RestorePoints { .. } => (),
             #[cfg(feature = "staging")]
             Self::TestDelay(_) => (),
         }
@@ -307,6 +354,7 @@ impl PointInsertOperationsInternal {
                                     payload,
                                     filter: Some(update_filter.with_point_ids(vec![id])),
                                     key: None,
+                                    precondition: None,
                                 }
                             } else {
                                 SetPayloadOp {
@@ -314,6 +362,7 @@ impl PointInsertOperationsInternal {
                                     payload,
                                     filter: None,
                                     key: None,
+                                    precondition: None,
                                 }
                             };
 
@@ -347,6 +396,7 @@ impl PointInsertOperationsInternal {
                                 payload,
                                 filter: Some(update_filter.with_point_ids(vec![point.id])),
                                 key: None,
+                                precondition: None,
                             }
                         } else {
                             SetPayloadOp {
@@ -354,6 +404,7 @@ impl PointInsertOperationsInternal {
                                 payload,
                                 filter: None,
                                 key: None,
+                                precondition: None,
                             }
                         };
 
@@ -392,6 +443,64 @@ pub struct ConditionalInsertOperationInternal {
     pub points_op: PointInsertOperationsInternal,
     /// Condition to check, if the point already exists
     pub condition: Filter,
+    /// Precondition to check before updating any point, rejects the whole operation if not met
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precondition: Option<UpdatePrecondition>,
+}
+
+/// Deletion of the points matching the filter
+///
+/// Serialized as a plain filter if `deleted_at` is not set, as it was before it was added.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Hash)]
+#[serde(
+    from = "DeleteByFilterOperationSerde",
+    into = "DeleteByFilterOperationSerde"
+)]
+pub struct DeleteByFilterOperation {
+    pub filter: Filter,
+    /// When the deletion was accepted, to soft delete points at the same time on all replicas
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl From<Filter> for DeleteByFilterOperation {
+    fn from(filter: Filter) -> Self {
+        Self {
+            filter,
+            deleted_at: None,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+enum DeleteByFilterOperationSerde {
+    WithDeletedAt {
+        filter: Filter,
+        deleted_at: DateTime<Utc>,
+    },
+    Filter(Filter),
+}
+
+impl From<DeleteByFilterOperationSerde> for DeleteByFilterOperation {
+    fn from(operation: DeleteByFilterOperationSerde) -> Self {
+        match operation {
+            DeleteByFilterOperationSerde::WithDeletedAt { filter, deleted_at } => Self {
+                filter,
+                deleted_at: Some(deleted_at),
+            },
+            DeleteByFilterOperationSerde::Filter(filter) => Self::from(filter),
+        }
+    }
+}
+
+impl From<DeleteByFilterOperation> for DeleteByFilterOperationSerde {
+    fn from(operation: DeleteByFilterOperation) -> Self {
+        let DeleteByFilterOperation { filter, deleted_at } = operation;
+        match deleted_at {
+            Some(deleted_at) => Self::WithDeletedAt { filter, deleted_at },
+            None => Self::Filter(filter),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Hash)]
@@ -4,12 +4,16 @@ pub mod snapshot_entry;
 #[cfg(test)]
 mod tests;
 
+use std::cmp;
+
 use ahash::AHashMap;
 use bitvec::prelude::BitVec;
+use chrono::{DateTime, Utc};
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::PointOffsetType;
 use itertools::Itertools as _;
 use segment::common::operation_error::OperationResult;
+use segment::entry::entry_point::SegmentEntry;
 use segment::types::*;
 
 use crate::locked_segment::LockedSegment;
@@ -221,8 +225,8 @@ impl ProxySegment {
                         // wrapped segment is considered to be newer. This is possible because
                         // different proxy segments can share state through a common write segment.
                         // See: <https://github.com/qdrant/qdrant/pull/7208>
-                        wrapped_segment.delete_point(
-                            versions.operation_version,
+                        versions.delete_from(
+                            wrapped_segment,
                             *point_id,
                             &HardwareCounterCell::disposable(), // Internal operation: no need to measure.
                         )?;
@@ -240,6 +244,60 @@ impl ProxySegment {
         Ok(())
     }
 
+    /// Schedule deletion of a point from the wrapped segment
+    ///
+    /// If `deleted_at` is set, the point is soft deleted once the deletion is propagated.
+    fn schedule_delete(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        deleted_at: Option<DateTime<Utc>>,
+    ) -> OperationResult<bool> {
+        let mut was_deleted = false;
+
+        self.version = cmp::max(self.version, op_num);
+
+        let deleted_point = ProxyDeletedPoint {
+            local_version: op_num,
+            operation_version: op_num,
+            deleted_at,
+        };
+
+        let point_offset = match &self.wrapped_segment {
+            LockedSegment::Original(raw_segment) => {
+                let point_offset = raw_segment.read().get_internal_id(point_id);
+                if point_offset.is_some() {
+                    let prev = self.deleted_points.insert(point_id, deleted_point);
+                    was_deleted = prev.is_none();
+                    if let Some(prev) = prev {
+                        debug_assert!(
+                            prev.operation_version < op_num,
+                            "Overriding deleted flag {prev:?} with older op_num:{op_num}",
+                        )
+                    }
+                }
+                point_offset
+            }
+            LockedSegment::Proxy(proxy) => {
+                if proxy.read().has_point(point_id) {
+                    let prev = self.deleted_points.insert(point_id, deleted_point);
+                    was_deleted = prev.is_none();
+                    if let Some(prev) = prev {
+                        debug_assert!(
+                            prev.operation_version < op_num,
+                            "Overriding deleted flag {prev:?} with older op_num:{op_num}",
+                        )
+                    }
+                }
+                None
+            }
+        };
+
+        self.set_deleted_offset(point_offset);
+
+        Ok(was_deleted)
+    }
+
     pub fn get_deleted_points(&self) -> &DeletedPoints {
         &self.deleted_points
     }
@@ -260,6 +318,26 @@ pub struct ProxyDeletedPoint {
     /// We use it for the delete operations when propagating them to the wrapped or optimized
     /// segment.
     pub operation_version: SeqNumberType,
+    /// Set if the point is soft deleted, to keep it restorable as a tombstone once the delete is
+    /// propagated.
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl ProxyDeletedPoint {
+    /// Apply the scheduled delete to the given segment
+    pub fn delete_from<S: SegmentEntry + ?Sized>(
+        &self,
+        segment: &mut S,
+        point_id: PointIdType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<bool> {
+        match self.deleted_at {
+            Some(deleted_at) => {
+                segment.delete_point_soft(self.operation_version, point_id, deleted_at, hw_counter)
+            }
+            None => segment.delete_point(self.operation_version, point_id, hw_counter),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
+use chrono::{DateTime, Utc};
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::TelemetryDetail;
 use segment::common::Flusher;
@@ -11,10 +12,11 @@ use segment::common::operation_error::{OperationError, OperationResult, SegmentF
 use segment::data_types::build_index_result::BuildFieldIndexResult;
 use segment::data_types::facets::{FacetParams, FacetValue};
 use segment::data_types::named_vectors::NamedVectors;
-use segment::data_types::order_by::OrderValue;
+use segment::data_types::order_by::OrderedPoint;
 use segment::data_types::query_context::{FormulaContext, QueryContext, SegmentQueryContext};
 use segment::data_types::vectors::{QueryVector, VectorInternal};
 use segment::entry::entry_point::{SegmentEntry, SegmentFlushOrdering};
+use segment::id_tracker::tombstones::TombstonedPoint;
 use segment::index::field_index::{CardinalityEstimation, FieldIndex};
 use segment::json_path::JsonPath;
 use segment::telemetry::SegmentTelemetry;
@@ -165,55 +167,45 @@ impl SegmentEntry for ProxySegment {
         point_id: PointIdType,
         _hw_counter: &HardwareCounterCell,
     ) -> OperationResult<bool> {
-        let mut was_deleted = false;
+        self.schedule_delete(op_num, point_id, None)
+    }
 
-        self.version = cmp::max(self.version, op_num);
+    fn delete_point_soft(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        deleted_at: DateTime<Utc>,
+        _hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<bool> {
+        self.schedule_delete(op_num, point_id, Some(deleted_at))
+    }
 
-        let point_offset = match &self.wrapped_segment {
-            LockedSegment::Original(raw_segment) => {
-                let point_offset = raw_segment.read().get_internal_id(point_id);
-                if point_offset.is_some() {
-                    let prev = self.deleted_points.insert(
-                        point_id,
-                        ProxyDeletedPoint {
-                            local_version: op_num,
-                            operation_version: op_num,
-                        },
-                    );
-                    was_deleted = prev.is_none();
-                    if let Some(prev) = prev {
-                        debug_assert!(
-                            prev.operation_version < op_num,
-                            "Overriding deleted flag {prev:?} with older op_num:{op_num}",
-                        )
-                    }
-                }
-                point_offset
-            }
-            LockedSegment::Proxy(proxy) => {
-                if proxy.read().has_point(point_id) {
-                    let prev = self.deleted_points.insert(
-                        point_id,
-                        ProxyDeletedPoint {
-                            local_version: op_num,
-                            operation_version: op_num,
-                        },
-                    );
-                    was_deleted = prev.is_none();
-                    if let Some(prev) = prev {
-                        debug_assert!(
-                            prev.operation_version < op_num,
-                            "Overriding deleted flag {prev:?} with older op_num:{op_num}",
-                        )
-                    }
-                }
-                None
-            }
-        };
+    fn tombstoned_point(
+        &self,
+        point_id: PointIdType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<TombstonedPoint>> {
+        let wrapped = self.wrapped_segment.get();
+        let wrapped_guard = wrapped.read();
 
-        self.set_deleted_offset(point_offset);
+        // Soft delete is not propagated yet, the point is still alive in the wrapped segment
+        if let Some(ProxyDeletedPoint {
+            operation_version,
+            deleted_at: Some(deleted_at),
+            ..
+        }) = self.deleted_points.get(&point_id)
+        {
+            let vectors = wrapped_guard.all_vectors(point_id, hw_counter)?;
+            let payload = wrapped_guard.payload(point_id, hw_counter)?;
+            return Ok(Some(TombstonedPoint {
+                version: *operation_version,
+                deleted_at: *deleted_at,
+                vectors: vectors.into_owned_map(),
+                payload,
+            }));
+        }
 
-        Ok(was_deleted)
+        wrapped_guard.tombstoned_point(point_id, hw_counter)
     }
 
     fn update_vectors(
@@ -388,7 +380,7 @@ impl SegmentEntry for ProxySegment {
         order_by: &'a segment::data_types::order_by::OrderBy,
         is_stopped: &AtomicBool,
         hw_counter: &HardwareCounterCell,
-    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+    ) -> OperationResult<Vec<OrderedPoint>> {
         let read_points = if self.deleted_points.is_empty() {
             self.wrapped_segment
                 .get()
@@ -20,6 +20,9 @@ use segment::vector_storage::{VectorStorage as _, VectorStorageEnum, new_raw_sco
 use self::lazy_matrix::LazyMatrix;
 use super::MmrInternal;
 
+/// Lambda used when the request does not specify a diversity
+pub const DEFAULT_MMR_LAMBDA: f32 = 0.5;
+
 /// Calculate the MMR (Maximal Marginal Relevance) score for a set of points with vectors.
 ///
 /// Assumes the points have vectors attached. If not, the entire point will be discarded.
@@ -205,6 +205,11 @@ impl From<CoreSearchRequest> for ShardQueryRequest {
             with_vector,
             with_payload,
             with_explanation: _, // ShardQueryRequest doesn't support explanation yet
+            with_explanation_bundle: _,
+            explanation_verbosity: _,
+            explanation_params: _,
+            mmr: _, // reranking happens at the request level
+            distinct_by: _,
         } = value;
 
         Self {
@@ -233,6 +238,12 @@ impl From<rest::schema::SearchRequestInternal> for ShardQueryRequest {
             with_vector,
             with_payload,
             with_explanation: _, // ShardQueryRequest doesn't support explanation yet
+            with_explanation_bundle: _,
+            explanation_verbosity: _,
+            explanation_params: _,
+            explanation_mode: _,
+            mmr: _, // reranking happens at the request level
+            distinct_by: _,
         } = value;
 
         Self {
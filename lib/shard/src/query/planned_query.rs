@@ -397,6 +397,11 @@ fn leaf_source_from_scoring_query(
                 with_payload: Some(WithPayloadInterface::from(false)),
                 score_threshold,
                 with_explanation: false,
+                with_explanation_bundle: false,
+                explanation_verbosity: Default::default(),
+                explanation_params: Default::default(),
+                mmr: None,
+                distinct_by: None,
             };
 
             let idx = core_searches.len();
@@ -460,6 +465,11 @@ fn leaf_source_from_scoring_query(
                 params,
                 limit: candidates_limit,
                 with_explanation: false,
+                with_explanation_bundle: false,
+                explanation_verbosity: Default::default(),
+                explanation_params: Default::default(),
+                mmr: None,
+                distinct_by: None,
             };
 
             let idx = core_searches.len();
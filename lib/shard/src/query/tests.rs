@@ -85,6 +85,11 @@ fn test_try_from_double_rescore() {
             with_vector: Some(WithVector::Bool(false)),
             score_threshold: None,
             with_explanation: false,
+            with_explanation_bundle: false,
+            explanation_verbosity: Default::default(),
+            explanation_params: Default::default(),
+            mmr: None,
+            distinct_by: None,
         }]
     );
 
@@ -125,6 +130,93 @@ fn test_try_from_double_rescore() {
     );
 }
 
+#[test]
+fn test_try_from_quantized_rescore_pipeline() {
+    // Binary quantized ANN -> scalar quantized rescore -> full precision rescore
+    let dummy_vector = vec![1.0, 2.0, 3.0];
+    let quantized = |rescore| {
+        Some(SearchParams {
+            quantization: Some(QuantizationSearchParams {
+                ignore: false,
+                rescore: Some(rescore),
+                oversampling: None,
+            }),
+            ..Default::default()
+        })
+    };
+    let full_precision = Some(SearchParams {
+        quantization: Some(QuantizationSearchParams {
+            ignore: true,
+            rescore: None,
+            oversampling: None,
+        }),
+        ..Default::default()
+    });
+    let nearest = |using| {
+        ScoringQuery::Vector(QueryEnum::Nearest(NamedQuery::new(
+            VectorInternal::Dense(dummy_vector.clone()),
+            using,
+        )))
+    };
+
+    let request = ShardQueryRequest {
+        prefetches: vec![ShardPrefetch {
+            prefetches: vec![ShardPrefetch {
+                prefetches: Vec::new(),
+                query: Some(nearest("binary")),
+                limit: 1000,
+                params: quantized(false),
+                filter: None,
+                score_threshold: None,
+            }],
+            query: Some(nearest("scalar")),
+            limit: 100,
+            params: quantized(false),
+            filter: None,
+            score_threshold: None,
+        }],
+        query: Some(nearest("full")),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: full_precision,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+    assert_eq!(request.prefetches_depth(), 2);
+
+    let planned_query = PlannedQuery::try_from(vec![request]).unwrap();
+
+    // Only the deepest stage is searched, with its own quantization params
+    assert_eq!(planned_query.searches.len(), 1);
+    assert_eq!(planned_query.searches[0].query.get_vector_name(), "binary");
+    assert_eq!(planned_query.searches[0].limit, 1000);
+    assert_eq!(planned_query.searches[0].params, quantized(false));
+
+    // Each rescore stage keeps its own quantization params
+    assert_eq!(
+        planned_query.root_plans[0].merge_plan,
+        MergePlan {
+            sources: vec![Source::Prefetch(Box::from(MergePlan {
+                sources: vec![Source::SearchesIdx(0)],
+                rescore_stages: Some(RescoreStages::shard_level(RescoreParams {
+                    rescore: nearest("scalar"),
+                    limit: 100,
+                    score_threshold: None,
+                    params: quantized(false),
+                })),
+            }))],
+            rescore_stages: Some(RescoreStages::shard_level(RescoreParams {
+                rescore: nearest("full"),
+                limit: 10,
+                score_threshold: None,
+                params: full_precision,
+            })),
+        },
+    );
+}
+
 #[test]
 fn test_try_from_no_prefetch() {
     let dummy_vector = vec![1.0, 2.0, 3.0];
@@ -159,6 +251,11 @@ fn test_try_from_no_prefetch() {
             with_payload: Some(WithPayloadInterface::Bool(false)),
             score_threshold: Some(0.5),
             with_explanation: false,
+            with_explanation_bundle: false,
+            explanation_verbosity: Default::default(),
+            explanation_params: Default::default(),
+            mmr: None,
+            distinct_by: None,
         }]
     );
 
@@ -246,6 +343,11 @@ fn test_try_from_hybrid_query() {
                 with_vector: Some(WithVector::Bool(false)),
                 score_threshold: None,
                 with_explanation: false,
+                with_explanation_bundle: false,
+                explanation_verbosity: Default::default(),
+                explanation_params: Default::default(),
+                mmr: None,
+                distinct_by: None,
             },
             CoreSearchRequest {
                 query: QueryEnum::Nearest(NamedQuery::new(
@@ -260,6 +362,11 @@ fn test_try_from_hybrid_query() {
                 with_vector: Some(WithVector::Bool(false)),
                 score_threshold: None,
                 with_explanation: false,
+                with_explanation_bundle: false,
+                explanation_verbosity: Default::default(),
+                explanation_params: Default::default(),
+                mmr: None,
+                distinct_by: None,
             }
         ]
     );
@@ -378,6 +485,11 @@ fn test_base_params_mapping_in_try_from() {
             with_vector: Some(WithVector::Bool(false)),
             score_threshold: Some(0.1),
             with_explanation: false,
+            with_explanation_bundle: false,
+            explanation_verbosity: Default::default(),
+            explanation_params: Default::default(),
+            mmr: None,
+            distinct_by: None,
         }]
     )
 }
@@ -19,6 +19,8 @@ pub struct RecordInternal {
     pub shard_key: Option<ShardKey>,
     /// Order value, if used for order_by
     pub order_value: Option<OrderValue>,
+    /// Values of the `order_by.then_by` fields, if ordered by multiple fields
+    pub then_by_values: Vec<Option<OrderValue>>,
 }
 
 impl RecordInternal {
@@ -47,6 +49,7 @@ impl TryFrom<RecordInternal> for PointStructPersisted {
             vector,
             shard_key: _,
             order_value: _,
+            then_by_values: _,
         } = record;
 
         if vector.is_none() {
@@ -69,6 +72,7 @@ impl From<RecordInternal> for api::grpc::qdrant::RetrievedPoint {
             vector,
             shard_key,
             order_value,
+            then_by_values: _,
         } = record;
         Self {
             id: Some(id.into()),
@@ -88,6 +92,7 @@ impl From<RecordInternal> for api::rest::Record {
             vector,
             shard_key,
             order_value,
+            then_by_values: _,
         } = value;
         Self {
             id,
@@ -21,6 +21,32 @@ pub fn retrieve_blocking(
     is_stopped: &AtomicBool,
     hw_measurement_acc: HwMeasurementAcc,
 ) -> OperationResult<AHashMap<PointIdType, RecordInternal>> {
+    let records = retrieve_versioned_blocking(
+        segments,
+        points,
+        with_payload,
+        with_vector,
+        timeout,
+        is_stopped,
+        hw_measurement_acc,
+    )?;
+
+    Ok(records
+        .into_iter()
+        .map(|(id, (_version, record))| (id, record))
+        .collect())
+}
+
+/// Same as [`retrieve_blocking`], but also returns the version of each point
+pub fn retrieve_versioned_blocking(
+    segments: LockedSegmentHolder,
+    points: &[PointIdType],
+    with_payload: &WithPayload,
+    with_vector: &WithVector,
+    timeout: Duration,
+    is_stopped: &AtomicBool,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> OperationResult<AHashMap<PointIdType, (SeqNumberType, RecordInternal)>> {
     let mut point_version: AHashMap<PointIdType, SeqNumberType> = Default::default();
     let mut point_records: AHashMap<PointIdType, RecordInternal> = Default::default();
 
@@ -76,6 +102,7 @@ pub fn retrieve_blocking(
                 },
                 shard_key: None,
                 order_value: None,
+                then_by_values: vec![],
             },
         );
         *version_entry.or_default() = version;
@@ -83,5 +110,8 @@ pub fn retrieve_blocking(
         Ok(true)
     })?;
 
-    Ok(point_records)
+    Ok(point_records
+        .into_iter()
+        .map(|(id, record)| (id, (point_version[&id], record)))
+        .collect())
 }
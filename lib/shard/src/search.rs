@@ -1,11 +1,13 @@
 use api::rest::SearchRequestInternal;
-use common::types::ScoreType;
+use common::types::{ExplanationParams, ExplanationVerbosity, ScoreType};
 use itertools::Itertools as _;
 use segment::data_types::vectors::{NamedQuery, NamedVectorStruct, VectorInternal};
+use segment::json_path::JsonPath;
 use segment::types::{Filter, SearchParams, WithPayloadInterface, WithVector};
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 use sparse::common::sparse_vector::validate_sparse_vector_impl;
 
+use crate::query::mmr::DEFAULT_MMR_LAMBDA;
 use crate::query::query_enum::QueryEnum;
 
 /// DEPRECATED: Search method should be removed and replaced with `ShardQueryRequest`
@@ -30,6 +32,34 @@ pub struct CoreSearchRequest {
     pub score_threshold: Option<ScoreType>,
     /// If true, return an explanation of which dimensions contributed most to each result's score.
     pub with_explanation: bool,
+    /// If true, explanations also contain the vectors they were computed from
+    pub with_explanation_bundle: bool,
+    /// Amount of detail returned in explanations
+    pub explanation_verbosity: ExplanationVerbosity,
+    /// Number of dimensions returned in explanations
+    pub explanation_params: ExplanationParams,
+    /// Rerank the results with MMR before truncating to `limit`
+    pub mmr: Option<SearchMmr>,
+    /// Keep only the best scored point of each value of this payload field
+    pub distinct_by: Option<JsonPath>,
+}
+
+/// MMR reranking of search results
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SearchMmr {
+    /// Lambda parameter controlling diversity vs relevance trade-off (0.0 = full diversity, 1.0 = full relevance)
+    pub lambda: f32,
+    /// Number of nearest candidates to rerank
+    pub candidates_limit: usize,
+}
+
+impl SearchMmr {
+    pub fn new(diversity: Option<f32>, candidates_limit: Option<usize>, limit: usize) -> Self {
+        Self {
+            lambda: diversity.map(|x| 1.0 - x).unwrap_or(DEFAULT_MMR_LAMBDA),
+            candidates_limit: candidates_limit.unwrap_or(limit),
+        }
+    }
 }
 
 impl CoreSearchRequest {
@@ -56,6 +86,12 @@ impl From<SearchRequestInternal> for CoreSearchRequest {
             with_vector,
             with_payload,
             with_explanation,
+            with_explanation_bundle,
+            explanation_verbosity,
+            explanation_params,
+            explanation_mode,
+            mmr,
+            distinct_by,
         } = request;
         Self {
             query: QueryEnum::Nearest(NamedQuery::from(NamedVectorStruct::from(vector))),
@@ -67,6 +103,14 @@ impl From<SearchRequestInternal> for CoreSearchRequest {
             with_vector,
             score_threshold,
             with_explanation: with_explanation.unwrap_or(false),
+            with_explanation_bundle: with_explanation_bundle.unwrap_or(false),
+            explanation_verbosity: explanation_verbosity.unwrap_or_default().into(),
+            explanation_params: ExplanationParams {
+                mode: explanation_mode.unwrap_or_default().into(),
+                ..explanation_params.map(Into::into).unwrap_or_default()
+            },
+            mmr: mmr.map(|mmr| SearchMmr::new(mmr.diversity, mmr.candidates_limit, limit)),
+            distinct_by,
         }
     }
 }
@@ -149,6 +193,11 @@ impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
             ),
             score_threshold: value.score_threshold,
             with_explanation: false, // no gTPC
+            with_explanation_bundle: false,
+            explanation_verbosity: ExplanationVerbosity::default(),
+            explanation_params: Default::default(),
+            mmr: None, // reranked by the caller, not sent to remote shards
+            distinct_by: None,
         })
     }
 }
@@ -187,6 +236,8 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
             timeout: _,
             shard_key_selector: _,
             sparse_indices,
+            with_explanation,
+            mmr,
         } = value;
 
         if let Some(sparse_indices) = &sparse_indices {
@@ -215,7 +266,18 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
                 .transpose()?,
             with_vector: with_vectors.map(WithVector::from),
             score_threshold: score_threshold.map(|s| s as ScoreType),
-            with_explanation: false, // no gRPC
+            with_explanation: with_explanation.unwrap_or(false),
+            with_explanation_bundle: false,
+            explanation_verbosity: ExplanationVerbosity::default(),
+            explanation_params: Default::default(),
+            mmr: mmr.map(|mmr| {
+                SearchMmr::new(
+                    mmr.diversity,
+                    mmr.candidates_limit.map(|limit| limit as usize),
+                    limit as usize,
+                )
+            }),
+            distinct_by: None, // not exposed over gRPC
         })
     }
 }
@@ -14,6 +14,7 @@ use std::thread::JoinHandle;
 use std::time::Duration;
 
 use ahash::{AHashMap, AHashSet};
+use chrono::{DateTime, Utc};
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::iterator_ext::IteratorExt;
 use common::save_on_disk::SaveOnDisk;
@@ -74,6 +75,9 @@ pub struct SegmentHolder {
     /// Holder for a thread, which does flushing of all segments sequentially.
     /// This is used to avoid multiple concurrent flushes.
     pub flush_thread: Mutex<Option<JoinHandle<OperationResult<()>>>>,
+
+    /// If set, deleted points are kept restorable as tombstones for this long
+    pub soft_delete_retention: Option<Duration>,
 }
 
 impl Drop for SegmentHolder {
@@ -870,6 +874,29 @@ impl SegmentHolder {
         }
     }
 
+    /// Purge tombstones of points deleted before `deleted_before`, so they can't be restored
+    /// anymore
+    ///
+    /// Segments under optimization are skipped, their tombstones are carried over to the
+    /// optimized segment and purged on the next call.
+    ///
+    /// Returns the number of purged tombstones.
+    pub fn purge_tombstones(&self, deleted_before: DateTime<Utc>) -> OperationResult<usize> {
+        let mut purged = 0;
+        for (_, segment) in self.iter() {
+            let LockedSegment::Original(segment) = segment else {
+                continue;
+            };
+
+            if segment.read().tombstone_count() == 0 {
+                continue;
+            }
+
+            purged += segment.write().purge_tombstones(deleted_before)?;
+        }
+        Ok(purged)
+    }
+
     pub fn report_optimizer_error<E: ToString>(&mut self, error: E) {
         // Save only the first error
         // If is more likely to be the real cause of all further problems
@@ -3,6 +3,7 @@
 use std::sync::atomic::AtomicBool;
 
 use ahash::{AHashMap, AHashSet};
+use chrono::{DateTime, Utc};
 use common::counter::hardware_counter::HardwareCounterCell;
 use itertools::iproduct;
 use parking_lot::{RwLock, RwLockWriteGuard};
@@ -10,18 +11,21 @@ use segment::common::operation_error::{OperationError, OperationResult};
 use segment::data_types::build_index_result::BuildFieldIndexResult;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::entry::entry_point::SegmentEntry;
+use segment::id_tracker::tombstones::TombstonedPoint;
 use segment::json_path::JsonPath;
 use segment::types::{
     Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
     SeqNumberType, VectorNameBuf,
 };
 
-use crate::operations::FieldIndexOperations;
+use crate::locked_segment::LockedSegment;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{
-    ConditionalInsertOperationInternal, PointOperations, PointStructPersisted,
+    ConditionalInsertOperationInternal, DeleteByFilterOperation, PointOperations,
+    PointStructPersisted,
 };
 use crate::operations::vector_ops::{PointVectorsPersisted, UpdateVectorsOp, VectorOperations};
+use crate::operations::{CollectionUpdateOperations, FieldIndexOperations, UpdatePrecondition};
 use crate::segment_holder::SegmentHolder;
 
 pub fn process_point_operation(
@@ -39,11 +43,15 @@ pub fn process_point_operation(
         PointOperations::UpsertPointsConditional(operation) => {
             conditional_upsert(&segments.read(), op_num, operation, hw_counter)
         }
-        PointOperations::DeletePoints { ids } => {
-            delete_points(&segments.read(), op_num, &ids, hw_counter)
-        }
-        PointOperations::DeletePointsByFilter(filter) => {
-            delete_points_by_filter(&segments.read(), op_num, &filter, hw_counter)
+        // Preconditions are checked before operations are written to the WAL,
+        // see `check_operation_precondition`
+        PointOperations::DeletePoints {
+            ids,
+            precondition: _,
+            deleted_at,
+        } => delete_points(&segments.read(), op_num, &ids, deleted_at, hw_counter),
+        PointOperations::DeletePointsByFilter(DeleteByFilterOperation { filter, deleted_at }) => {
+            delete_points_by_filter(&segments.read(), op_num, &filter, deleted_at, hw_counter)
         }
         PointOperations::SyncPoints(operation) => {
             let (deleted, new, updated) = sync_points(
@@ -56,6 +64,9 @@ pub fn process_point_operation(
             )?;
             Ok(deleted + new + updated)
         }
+        PointOperations::RestorePoints { ids } => {
+            restore_points(&segments.read(), op_num, &ids, hw_counter)
+        }
         #[cfg(feature = "staging")]
         PointOperations::TestDelay(operation) => {
             operation.execute();
@@ -277,6 +288,7 @@ pub fn conditional_upsert(
     let ConditionalInsertOperationInternal {
         mut points_op,
         condition,
+        precondition: _, // checked before the operation is written to the WAL
     } = operation;
 
     let point_ids = points_op.point_ids();
@@ -330,19 +342,31 @@ fn upsert_with_payload(
 const DELETION_BATCH_SIZE: usize = 512;
 
 /// Tries to delete points from all segments, returns number of actually deleted points
+///
+/// If soft delete is enabled, points are soft deleted at `deleted_at`, or now if not set.
 pub fn delete_points(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
     ids: &[PointIdType],
+    deleted_at: Option<DateTime<Utc>>,
     hw_counter: &HardwareCounterCell,
 ) -> OperationResult<usize> {
     let mut total_deleted_points = 0;
 
+    let deleted_at = segments
+        .soft_delete_retention
+        .map(|_| deleted_at.unwrap_or_else(Utc::now));
+
     for batch in ids.chunks(DELETION_BATCH_SIZE) {
         let deleted_points = segments.apply_points(
             batch,
             |_| (),
-            |id, _idx, write_segment, ()| write_segment.delete_point(op_num, id, hw_counter),
+            |id, _idx, write_segment, ()| match deleted_at {
+                Some(deleted_at) => {
+                    write_segment.delete_point_soft(op_num, id, deleted_at, hw_counter)
+                }
+                None => write_segment.delete_point(op_num, id, hw_counter),
+            },
         )?;
 
         total_deleted_points += deleted_points;
@@ -352,13 +376,19 @@ pub fn delete_points(
 }
 
 /// Deletes points from all segments matching the given filter
+///
+/// If soft delete is enabled, points are soft deleted at `deleted_at`, or now if not set.
 pub fn delete_points_by_filter(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
     filter: &Filter,
+    deleted_at: Option<DateTime<Utc>>,
     hw_counter: &HardwareCounterCell,
 ) -> OperationResult<usize> {
     let mut total_deleted = 0;
+    let deleted_at = segments
+        .soft_delete_retention
+        .map(|_| deleted_at.unwrap_or_else(Utc::now));
     // we don’t want to cancel this filtered read
     let is_stopped = AtomicBool::new(false);
     let mut points_to_delete: AHashMap<_, _> = segments
@@ -387,7 +417,13 @@ pub fn delete_points_by_filter(
 
         let mut deleted_in_batch = 0;
         while let Some(point_id) = curr_points.pop() {
-            if s.delete_point(op_num, point_id, hw_counter)? {
+            let deleted = match deleted_at {
+                Some(deleted_at) => {
+                    s.delete_point_soft(op_num, point_id, deleted_at, hw_counter)?
+                }
+                None => s.delete_point(op_num, point_id, hw_counter)?,
+            };
+            if deleted {
                 total_deleted += 1;
                 deleted_in_batch += 1;
             }
@@ -409,6 +445,86 @@ pub fn delete_points_by_filter(
     Ok(total_deleted)
 }
 
+/// Restore soft deleted points from their newest tombstone, returns number of restored points
+///
+/// Points which are alive, or have no tombstone older than this operation, are skipped.
+/// This keeps the operation idempotent when it is replayed from WAL.
+pub fn restore_points(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    ids: &[PointIdType],
+    hw_counter: &HardwareCounterCell,
+) -> OperationResult<usize> {
+    let mut restored = 0;
+
+    for &point_id in ids {
+        let mut newest: Option<TombstonedPoint> = None;
+        let mut tombstoned_in = Vec::new();
+        let mut is_alive = false;
+
+        for (_, segment) in segments.iter() {
+            let segment_arc = segment.get();
+            let read_segment = segment_arc.read();
+            if read_segment.has_point(point_id) {
+                is_alive = true;
+                break;
+            }
+
+            let Some(point) = read_segment.tombstoned_point(point_id, hw_counter)? else {
+                continue;
+            };
+            if point.version >= op_num {
+                continue;
+            }
+
+            tombstoned_in.push(segment.clone());
+            if newest
+                .as_ref()
+                .is_none_or(|newest| newest.version < point.version)
+            {
+                newest = Some(point);
+            }
+        }
+
+        let Some(point) = newest.filter(|_| !is_alive) else {
+            continue;
+        };
+
+        let default_write_segment = segments.smallest_appendable_segment().ok_or_else(|| {
+            OperationError::service_error("No appendable segments exist, expected at least one")
+        })?;
+
+        let segment_arc = default_write_segment.get();
+        let mut write_segment = segment_arc.write();
+        upsert_with_payload(
+            &mut write_segment,
+            op_num,
+            point_id,
+            NamedVectors::from_map(point.vectors),
+            Some(&point.payload),
+            hw_counter,
+        )?;
+        RwLockWriteGuard::unlock_fair(write_segment);
+
+        // Tombstones of segments under optimization are carried over, and purged later
+        for segment in tombstoned_in {
+            if let LockedSegment::Original(segment) = segment {
+                segment.write().remove_tombstone(point_id)?;
+            }
+        }
+
+        restored += 1;
+    }
+
+    if restored == 0 {
+        // In case we didn't hit any points, we suggest this op_num to the segment-holder to make WAL acknowledge this operation.
+        // If we don't do this, startup might take up a lot of time in some scenarios because of recovering these no-op operations.
+        segments.bump_max_segment_version_overwrite(op_num);
+    }
+
+    Ok(restored)
+}
+
 /// Sync points within a given [from_id; to_id) range.
 ///
 /// 1. Retrieve existing points for a range
@@ -893,6 +1009,98 @@ fn select_excluded_by_filter_ids(
         .collect())
 }
 
+/// Check the precondition of `operation` against the current state of `segments`, if it has any.
+///
+/// Preconditions are checked once, before the operation is written to the WAL, and the operation
+/// is written without it. So applying the operation, e.g. on WAL replay, never checks it again.
+pub fn check_operation_precondition(
+    segments: &SegmentHolder,
+    operation: &CollectionUpdateOperations,
+    hw_counter: &HardwareCounterCell,
+) -> OperationResult<()> {
+    let Some(precondition) = operation.precondition() else {
+        return Ok(());
+    };
+
+    let Some(point_ids) = operation.point_ids() else {
+        return Err(OperationError::ValidationError {
+            description: "Preconditions require selecting points by ID".to_string(),
+        });
+    };
+
+    check_update_precondition(segments, &point_ids, precondition, hw_counter)
+}
+
+/// Checks that all given points exist and meet the precondition, or don't exist if the
+/// precondition requires so, returns a conflict otherwise.
+fn check_update_precondition(
+    segments: &SegmentHolder,
+    point_ids: &[PointIdType],
+    precondition: &UpdatePrecondition,
+    hw_counter: &HardwareCounterCell,
+) -> OperationResult<()> {
+    let UpdatePrecondition {
+        if_version,
+        if_payload_matches,
+        if_not_exists,
+    } = precondition;
+
+    // A point may temporarily be present in multiple segments, take its latest version
+    let mut point_versions: AHashMap<PointIdType, SeqNumberType> = AHashMap::new();
+    // we don’t want to cancel this read
+    let is_stopped = AtomicBool::new(false);
+    segments.read_points(point_ids, &is_stopped, |point_id, segment| {
+        if let Some(version) = segment.point_version(point_id) {
+            let latest_version = point_versions.entry(point_id).or_insert(version);
+            *latest_version = (*latest_version).max(version);
+        }
+        Ok(true)
+    })?;
+
+    let mismatched_points = match if_payload_matches {
+        Some(filter) => select_excluded_by_filter_ids(
+            segments,
+            point_ids.iter().copied(),
+            filter.clone(),
+            hw_counter,
+        )?,
+        None => AHashSet::new(),
+    };
+
+    for &point_id in point_ids {
+        let Some(&version) = point_versions.get(&point_id) else {
+            if *if_not_exists {
+                continue;
+            }
+            return Err(OperationError::precondition_conflict(format!(
+                "point {point_id} does not exist"
+            )));
+        };
+
+        if *if_not_exists {
+            return Err(OperationError::precondition_conflict(format!(
+                "point {point_id} already exists"
+            )));
+        }
+
+        if let Some(expected_version) = *if_version
+            && version != expected_version
+        {
+            return Err(OperationError::precondition_conflict(format!(
+                "point {point_id} has version {version}, expected {expected_version}"
+            )));
+        }
+
+        if mismatched_points.contains(&point_id) {
+            return Err(OperationError::precondition_conflict(format!(
+                "payload of point {point_id} does not match the condition"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 fn points_by_filter(
     segments: &SegmentHolder,
     filter: &Filter,
@@ -927,10 +1135,17 @@ mod test {
 
     use common::counter::hardware_counter::HardwareCounterCell;
     use parking_lot::RwLock;
+    use segment::common::operation_error::OperationError;
     use segment::types::{Condition, FieldCondition, Filter, Match, MatchValue, ValueVariants};
     use tempfile::Builder;
 
+    use super::process_point_operation;
     use crate::fixtures::{build_segment_1, build_segment_2};
+    use crate::operations::UpdatePrecondition;
+    use crate::operations::point_ops::{
+        ConditionalInsertOperationInternal, PointInsertOperationsInternal, PointOperations,
+        PointStructPersisted, VectorStructPersisted,
+    };
     use crate::segment_holder::SegmentHolder;
     use crate::update::delete_points_by_filter;
 
@@ -984,4 +1199,160 @@ mod test {
         assert_eq!(old_version + 1, new_version);
         assert_eq!(new_version, DELETE_OP_NUM);
     }
+
+    #[test]
+    fn test_delete_with_precondition() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut holder = SegmentHolder::default();
+        let sid1 = holder.add_new(build_segment_1(dir.path()));
+
+        let version = holder
+            .get(sid1)
+            .unwrap()
+            .get()
+            .read()
+            .point_version(1.into())
+            .unwrap();
+
+        let segments = RwLock::new(holder);
+
+        let color_filter = |color: &str| {
+            Filter::new_must(Condition::Field(FieldCondition::new_match(
+                "color".parse().unwrap(),
+                Match::Value(MatchValue {
+                    value: ValueVariants::String(color.to_string()),
+                }),
+            )))
+        };
+
+        let delete_op =
+            |ids: Vec<u64>, precondition: UpdatePrecondition| PointOperations::DeletePoints {
+                ids: ids.into_iter().map(Into::into).collect(),
+                precondition: Some(precondition),
+                deleted_at: None,
+            };
+
+        let conflicting = [
+            // Stale version
+            delete_op(
+                vec![1],
+                UpdatePrecondition {
+                    if_version: Some(version - 1),
+                    if_payload_matches: None,
+                    if_not_exists: false,
+                },
+            ),
+            // Payload doesn't match
+            delete_op(
+                vec![1],
+                UpdatePrecondition {
+                    if_version: None,
+                    if_payload_matches: Some(color_filter("blue")),
+                    if_not_exists: false,
+                },
+            ),
+            // Point doesn't exist
+            delete_op(
+                vec![1, 100],
+                UpdatePrecondition {
+                    if_version: None,
+                    if_payload_matches: Some(color_filter("red")),
+                    if_not_exists: false,
+                },
+            ),
+        ];
+
+        for operation in conflicting {
+            let operation = CollectionUpdateOperations::PointOperation(operation);
+            let result = check_operation_precondition(&segments.read(), &operation, &hw_counter);
+            assert!(
+                matches!(result, Err(OperationError::PreconditionConflict { .. })),
+                "expected conflict, got {result:?}",
+            );
+        }
+
+        // Nothing was deleted by the rejected operations
+        assert!(
+            segments
+                .read()
+                .get(sid1)
+                .unwrap()
+                .get()
+                .read()
+                .has_point(1.into())
+        );
+
+        let operation = delete_op(
+            vec![1],
+            UpdatePrecondition {
+                if_version: Some(version),
+                if_payload_matches: Some(color_filter("red")),
+                if_not_exists: false,
+            },
+        );
+        check_operation_precondition(
+            &segments.read(),
+            &CollectionUpdateOperations::PointOperation(operation.clone()),
+            &hw_counter,
+        )
+        .unwrap();
+        let deleted = process_point_operation(&segments, 100, operation, &hw_counter).unwrap();
+        assert_eq!(deleted, 1);
+    }
+
+    #[test]
+    fn test_insert_if_not_exists() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut holder = SegmentHolder::default();
+        let sid1 = holder.add_new(build_segment_1(dir.path()));
+
+        let segments = RwLock::new(holder);
+
+        let insert_op = |ids: Vec<u64>| {
+            let points = ids
+                .into_iter()
+                .map(|id| PointStructPersisted {
+                    id: id.into(),
+                    vector: VectorStructPersisted::Single(vec![1.0, 0.0, 0.0, 1.0]),
+                    payload: None,
+                })
+                .collect();
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op: PointInsertOperationsInternal::PointsList(points),
+                condition: Filter::default(),
+                precondition: Some(UpdatePrecondition::not_exists()),
+            })
+        };
+        let check = |operation: &PointOperations| {
+            let operation = CollectionUpdateOperations::PointOperation(operation.clone());
+            check_operation_precondition(&segments.read(), &operation, &hw_counter)
+        };
+
+        // Point 1 already exists
+        let result = check(&insert_op(vec![1, 100]));
+        assert!(
+            matches!(result, Err(OperationError::PreconditionConflict { .. })),
+            "expected conflict, got {result:?}",
+        );
+
+        let operation = insert_op(vec![100, 101]);
+        check(&operation).unwrap();
+        let inserted = process_point_operation(&segments, 101, operation, &hw_counter).unwrap();
+        assert_eq!(inserted, 2);
+
+        // Applying the operation doesn't check its precondition, e.g. on WAL replay
+        process_point_operation(&segments, 101, insert_op(vec![100, 101]), &hw_counter).unwrap();
+        assert!(check(&insert_op(vec![100])).is_err());
+
+        let segments = segments.read();
+        let segment = segments.get(sid1).unwrap().get();
+        assert!(segment.read().has_point(100.into()));
+        assert!(segment.read().has_point(101.into()));
+    }
 }
@@ -23,6 +23,55 @@ impl AliasMapping {
     pub fn save(&self, path: &Path) -> Result<(), StorageError> {
         Ok(atomic_save_json(path, self)?)
     }
+
+    pub fn get(&self, alias: &str) -> Option<&CollectionId> {
+        self.0.get(alias)
+    }
+
+    pub fn insert(&mut self, alias: Alias, collection_name: CollectionId) {
+        self.0.insert(alias, collection_name);
+    }
+
+    pub fn remove(&mut self, alias: &str) -> Option<CollectionId> {
+        self.0.remove(alias)
+    }
+
+    pub fn rename(
+        &mut self,
+        old_alias_name: &str,
+        new_alias_name: Alias,
+    ) -> Result<(), StorageError> {
+        let collection_name = self.0.remove(old_alias_name).ok_or_else(|| {
+            StorageError::not_found(format!("Alias {old_alias_name} does not exists!"))
+        })?;
+        self.0.insert(new_alias_name, collection_name);
+        Ok(())
+    }
+
+    /// Exchange collections of two existing aliases
+    pub fn swap(
+        &mut self,
+        first_alias_name: &str,
+        second_alias_name: &str,
+    ) -> Result<(), StorageError> {
+        for alias in [first_alias_name, second_alias_name] {
+            if !self.0.contains_key(alias) {
+                return Err(StorageError::not_found(format!(
+                    "Alias {alias} does not exists!"
+                )));
+            }
+        }
+
+        if first_alias_name != second_alias_name {
+            let first_collection = self.0[first_alias_name].clone();
+            let second_collection = self.0[second_alias_name].clone();
+            self.0
+                .insert(first_alias_name.to_string(), second_collection);
+            self.0
+                .insert(second_alias_name.to_string(), first_collection);
+        }
+        Ok(())
+    }
 }
 
 /// Persists mapping between alias and collection name. The data is assumed to be relatively small.
@@ -97,18 +146,10 @@ impl AliasPersistence {
         old_alias_name: &str,
         new_alias_name: String,
     ) -> Result<(), StorageError> {
-        match self.get(old_alias_name) {
-            None => Err(StorageError::NotFound {
-                description: format!("Alias {old_alias_name} does not exists!"),
-            }),
-            Some(collection_name) => {
-                self.alias_mapping.0.remove(old_alias_name);
-                self.alias_mapping.0.insert(new_alias_name, collection_name);
-                // 'remove' & 'insert' saved atomically
-                self.alias_mapping.save(&self.data_path)?;
-                Ok(())
-            }
-        }
+        self.alias_mapping.rename(old_alias_name, new_alias_name)?;
+        // 'remove' & 'insert' saved atomically
+        self.alias_mapping.save(&self.data_path)?;
+        Ok(())
     }
 
     pub fn collection_aliases(&self, collection_name: &str) -> Vec<String> {
@@ -135,3 +176,29 @@ impl AliasPersistence {
         self.alias_mapping.0.contains_key(alias)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_aliases() {
+        let mut mapping = AliasMapping::default();
+        mapping.insert("prod".to_string(), "collection_v1".to_string());
+        mapping.insert("staging".to_string(), "collection_v2".to_string());
+
+        mapping.swap("prod", "staging").unwrap();
+        assert_eq!(mapping.get("prod").unwrap(), "collection_v2");
+        assert_eq!(mapping.get("staging").unwrap(), "collection_v1");
+
+        // Swapping with a missing alias leaves the mapping untouched
+        let before = mapping.clone();
+        assert!(mapping.swap("prod", "missing").is_err());
+        assert!(mapping.swap("missing", "prod").is_err());
+        assert_eq!(mapping, before);
+
+        // Alias swapped with itself stays in place
+        mapping.swap("prod", "prod").unwrap();
+        assert_eq!(mapping.get("prod").unwrap(), "collection_v2");
+    }
+}
@@ -0,0 +1,357 @@
+//! Server-side copy of a collection into a new one.
+//!
+//! The new collection is created with the configuration and payload indexes of the source one,
+//! after that points are copied shard by shard in the background. Progress is tracked in memory
+//! by the name of the new collection, it is not preserved across restarts.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::rest::VectorStructOutput;
+use chrono::{DateTime, Utc};
+use collection::config::ShardingMethod;
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStructPersisted, UpdateAck, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{ScrollRequestInternal, VectorsConfig};
+use collection::operations::verification::new_unchecked_verification_pass;
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::shards::shard::ShardId;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::types::{WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation, CreatePayloadIndex,
+};
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::toc::TableOfContent;
+use crate::dispatcher::Dispatcher;
+use crate::rbac::{Access, AccessRequirements};
+
+/// Number of points copied in a single update operation, if not specified
+const DEFAULT_CLONE_BATCH_SIZE: usize = 1000;
+
+/// Number of finished tasks kept around for progress reporting
+const MAX_FINISHED_CLONE_TASKS: usize = 64;
+
+/// Operation for copying an existing collection into a new one
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CloneCollection {
+    /// Name of the new collection
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name")
+    )]
+    pub collection_name: String,
+    /// Number of shards in the new collection. Default: same as in the source collection
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub shard_number: Option<u32>,
+    /// If true - payload of the new collection is stored on disk.
+    /// Default: same as in the source collection
+    #[serde(default)]
+    pub on_disk_payload: Option<bool>,
+    /// If true - dense vectors of the new collection are stored on disk.
+    /// Default: same as in the source collection
+    #[serde(default)]
+    pub on_disk_vectors: Option<bool>,
+    /// Number of points copied in a single update operation
+    #[serde(default)]
+    #[validate(range(min = 1, max = 100_000))]
+    pub batch_size: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CloneTaskStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct CloneTaskInfo {
+    pub source_collection: String,
+    pub collection_name: String,
+    pub status: CloneTaskStatus,
+    /// Number of shards of the source collection
+    pub shards_total: usize,
+    /// Number of shards copied completely
+    pub shards_copied: usize,
+    /// Number of points copied so far
+    pub points_copied: usize,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Reason of the failure, if status is `failed`
+    pub error: Option<String>,
+}
+
+/// Registry of clone tasks started on this peer, by the name of the new collection.
+#[derive(Default)]
+pub struct CloneTasks {
+    tasks: Arc<Mutex<HashMap<String, CloneTaskInfo>>>,
+}
+
+impl CloneTasks {
+    pub fn get(&self, collection_name: &str) -> Option<CloneTaskInfo> {
+        self.tasks.lock().get(collection_name).cloned()
+    }
+
+    fn update_progress(&self, collection_name: &str, update: impl FnOnce(&mut CloneTaskInfo)) {
+        if let Some(info) = self.tasks.lock().get_mut(collection_name) {
+            update(info);
+        }
+    }
+
+    fn finish(&self, collection_name: &str, result: Result<(), StorageError>) {
+        let mut tasks = self.tasks.lock();
+
+        if let Some(info) = tasks.get_mut(collection_name) {
+            info.finished_at = Some(Utc::now());
+            match result {
+                Ok(()) => {
+                    log::info!(
+                        "Copied {} points of collection {} into {}",
+                        info.points_copied,
+                        info.source_collection,
+                        info.collection_name,
+                    );
+                    info.status = CloneTaskStatus::Completed;
+                }
+                Err(err) => {
+                    log::error!(
+                        "Copying collection {} into {} failed: {err}",
+                        info.source_collection,
+                        info.collection_name,
+                    );
+                    info.status = CloneTaskStatus::Failed;
+                    info.error = Some(err.to_string());
+                }
+            }
+        }
+
+        // Forget the oldest finished tasks
+        let mut finished: Vec<_> = tasks
+            .values()
+            .filter_map(|info| Some((info.finished_at?, info.collection_name.clone())))
+            .collect();
+
+        if finished.len() > MAX_FINISHED_CLONE_TASKS {
+            finished.sort();
+            let excess = finished.len() - MAX_FINISHED_CLONE_TASKS;
+            for (_, collection_name) in finished.into_iter().take(excess) {
+                tasks.remove(&collection_name);
+            }
+        }
+    }
+}
+
+/// Create a copy of the `source_collection`, and start copying its points in the background.
+///
+/// Returns once the new collection and its payload indexes are created. Points written into the
+/// source collection while copying is in progress may not be copied.
+pub async fn clone_collection(
+    dispatcher: Arc<Dispatcher>,
+    source_collection: String,
+    request: CloneCollection,
+    access: Access,
+    wait_timeout: Option<Duration>,
+) -> Result<CloneTaskInfo, StorageError> {
+    let toc = dispatcher
+        .toc(&access, &new_unchecked_verification_pass())
+        .clone();
+
+    let source_pass =
+        access.check_collection_access(&source_collection, AccessRequirements::new())?;
+    let state = toc.get_collection(&source_pass).await?.state().await;
+
+    if state.config.params.sharding_method.unwrap_or_default() == ShardingMethod::Custom {
+        return Err(StorageError::bad_request(
+            "Cloning collections with custom sharding is not supported",
+        ));
+    }
+
+    let mut shard_ids: Vec<ShardId> = state.shards.keys().copied().collect();
+    shard_ids.sort_unstable();
+
+    let CloneCollection {
+        collection_name,
+        shard_number,
+        on_disk_payload,
+        on_disk_vectors,
+        batch_size,
+    } = request;
+
+    let mut create_collection = CreateCollection::from(state.config);
+    create_collection.uuid = None;
+
+    if let Some(shard_number) = shard_number {
+        create_collection.shard_number = Some(shard_number);
+    }
+
+    if let Some(on_disk_payload) = on_disk_payload {
+        create_collection.on_disk_payload = Some(on_disk_payload);
+    }
+
+    if let Some(on_disk_vectors) = on_disk_vectors {
+        match &mut create_collection.vectors {
+            VectorsConfig::Single(params) => params.on_disk = Some(on_disk_vectors),
+            VectorsConfig::Multi(params) => params
+                .values_mut()
+                .for_each(|params| params.on_disk = Some(on_disk_vectors)),
+        }
+    }
+
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                collection_name.clone(),
+                create_collection,
+            )?),
+            access.clone(),
+            wait_timeout,
+        )
+        .await?;
+
+    // Create indexes before copying points, so they are built along the way
+    for (field_name, field_schema) in state.payload_index_schema.schema {
+        dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::CreatePayloadIndex(CreatePayloadIndex {
+                    collection_name: collection_name.clone(),
+                    field_name,
+                    field_schema,
+                }),
+                access.clone(),
+                wait_timeout,
+            )
+            .await?;
+    }
+
+    let info = CloneTaskInfo {
+        source_collection: source_collection.clone(),
+        collection_name: collection_name.clone(),
+        status: CloneTaskStatus::Running,
+        shards_total: shard_ids.len(),
+        shards_copied: 0,
+        points_copied: 0,
+        started_at: Utc::now(),
+        finished_at: None,
+        error: None,
+    };
+
+    toc.clone_tasks()
+        .tasks
+        .lock()
+        .insert(collection_name.clone(), info.clone());
+
+    let runtime = toc.general_runtime_handle().clone();
+    runtime.spawn(async move {
+        let result = copy_points(
+            &toc,
+            &source_collection,
+            &collection_name,
+            &shard_ids,
+            batch_size.unwrap_or(DEFAULT_CLONE_BATCH_SIZE),
+            &access,
+        )
+        .await;
+
+        toc.clone_tasks().finish(&collection_name, result);
+    });
+
+    Ok(info)
+}
+
+async fn copy_points(
+    toc: &TableOfContent,
+    source_collection: &str,
+    collection_name: &str,
+    shard_ids: &[ShardId],
+    batch_size: usize,
+    access: &Access,
+) -> Result<(), StorageError> {
+    for &shard_id in shard_ids {
+        let mut offset = None;
+
+        loop {
+            let request = ScrollRequestInternal {
+                offset,
+                limit: Some(batch_size),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: WithVector::Bool(true),
+                order_by: None,
+            };
+
+            let page = toc
+                .scroll(
+                    source_collection,
+                    request,
+                    None,
+                    None,
+                    ShardSelectorInternal::ShardId(shard_id),
+                    access.clone(),
+                    HwMeasurementAcc::new_with_metrics_drain(
+                        toc.get_collection_hw_metrics(source_collection.to_string()),
+                    ),
+                )
+                .await?;
+
+            let points = page
+                .points
+                .into_iter()
+                .map(|mut record| {
+                    // Points without vectors are still points
+                    record
+                        .vector
+                        .get_or_insert_with(|| VectorStructOutput::Named(Default::default()));
+                    PointStructPersisted::try_from(record).map_err(StorageError::service_error)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let points_count = points.len();
+
+            if points_count > 0 {
+                let operation =
+                    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                        PointInsertOperationsInternal::PointsList(points),
+                    ));
+
+                toc.update(
+                    collection_name,
+                    OperationWithClockTag::from(operation),
+                    UpdateAck::Applied,
+                    WriteOrdering::default(),
+                    ShardSelectorInternal::Empty,
+                    access.clone(),
+                    HwMeasurementAcc::new_with_metrics_drain(
+                        toc.get_collection_hw_metrics(collection_name.to_string()),
+                    ),
+                )
+                .await?;
+            }
+
+            toc.clone_tasks().update_progress(collection_name, |info| {
+                info.points_copied += points_count;
+            });
+
+            offset = page.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+
+        toc.clone_tasks()
+            .update_progress(collection_name, |info| info.shards_copied += 1);
+    }
+
+    Ok(())
+}
@@ -74,6 +74,21 @@ pub struct RenameAliasOperation {
     pub rename_alias: RenameAlias,
 }
 
+/// Exchange collections of two existing aliases
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SwapAliases {
+    pub first_alias_name: String,
+    pub second_alias_name: String,
+}
+
+/// Exchange collections of two existing aliases
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SwapAliasesOperation {
+    pub swap_aliases: SwapAliases,
+}
+
 /// Group of all the possible operations related to collection aliases
 #[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -82,6 +97,7 @@ pub enum AliasOperations {
     CreateAlias(CreateAliasOperation),
     DeleteAlias(DeleteAliasOperation),
     RenameAlias(RenameAliasOperation),
+    SwapAliases(SwapAliasesOperation),
 }
 
 impl From<CreateAlias> for AliasOperations {
@@ -102,6 +118,12 @@ impl From<RenameAlias> for AliasOperations {
     }
 }
 
+impl From<SwapAliases> for AliasOperations {
+    fn from(swap_aliases: SwapAliases) -> Self {
+        AliasOperations::SwapAliases(SwapAliasesOperation { swap_aliases })
+    }
+}
+
 /// Operation for creating new collection and (optionally) specify index params
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "snake_case")]
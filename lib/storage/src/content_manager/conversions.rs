@@ -15,7 +15,7 @@ use crate::content_manager::collection_meta_ops::{
     AliasOperations, ChangeAliasesOperation, CollectionMetaOperations, CreateAlias,
     CreateAliasOperation, CreateCollection, CreateCollectionOperation, DeleteAlias,
     DeleteAliasOperation, DeleteCollectionOperation, RenameAlias, RenameAliasOperation,
-    UpdateCollection, UpdateCollectionOperation,
+    SwapAliases, SwapAliasesOperation, UpdateCollection, UpdateCollectionOperation,
 };
 use crate::content_manager::errors::StorageError;
 
@@ -48,6 +48,7 @@ impl From<StorageError> for Status {
             }
             StorageError::ShardUnavailable { .. } => tonic::Code::Unavailable,
             StorageError::EmptyPartialSnapshot { .. } => tonic::Code::FailedPrecondition,
+            StorageError::Conflict { .. } => tonic::Code::Aborted,
         };
         let mut status = Status::new(error_code, format!("{error}"));
         // add metadata headers
@@ -261,6 +262,21 @@ impl From<api::grpc::qdrant::RenameAlias> for AliasOperations {
     }
 }
 
+impl From<api::grpc::qdrant::SwapAliases> for AliasOperations {
+    fn from(value: api::grpc::qdrant::SwapAliases) -> Self {
+        let api::grpc::qdrant::SwapAliases {
+            first_alias_name,
+            second_alias_name,
+        } = value;
+        Self::SwapAliases(SwapAliasesOperation {
+            swap_aliases: SwapAliases {
+                first_alias_name,
+                second_alias_name,
+            },
+        })
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::AliasOperations> for AliasOperations {
     type Error = Status;
 
@@ -276,6 +292,7 @@ impl TryFrom<api::grpc::qdrant::AliasOperations> for AliasOperations {
             Some(api::grpc::qdrant::alias_operations::Action::RenameAlias(rename)) => {
                 Ok(rename.into())
             }
+            Some(api::grpc::qdrant::alias_operations::Action::SwapAliases(swap)) => Ok(swap.into()),
             _ => Err(Status::invalid_argument("Malformed AliasOperation type")),
         }
     }
@@ -0,0 +1,475 @@
+//! Background import of points from object storage.
+//!
+//! An import reads JSON Lines datasets from `s3://`, `gs://` or `file://` locations, and hands
+//! decoded points over to the update path in batches. Progress of running and recently finished
+//! imports is tracked in memory by task id, it is not preserved across restarts.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use object_store::ObjectStore;
+use object_store::aws::AmazonS3Builder;
+use object_store::gcp::GoogleCloudStorageBuilder;
+use object_store::local::LocalFileSystem;
+use object_store::path::Path as ObjectPath;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, Lines};
+use tokio::runtime::Handle;
+use tokio::task::JoinSet;
+use tokio_util::io::StreamReader;
+use url::Url;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::content_manager::errors::StorageError;
+
+/// Number of points upserted in a single update operation, if not specified
+const DEFAULT_IMPORT_BATCH_SIZE: usize = 1000;
+
+/// Number of update operations running concurrently, if not specified
+const DEFAULT_IMPORT_PARALLELISM: usize = 4;
+
+/// Number of finished tasks kept around for progress reporting
+const MAX_FINISHED_IMPORT_TASKS: usize = 64;
+
+pub type ImportTaskId = Uuid;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct ImportFromUrlRequest {
+    /// Location of a JSON Lines dataset, one point per line.
+    /// If the location ends with `/`, all objects under this prefix are imported.
+    ///
+    /// Examples:
+    /// - `s3://my-bucket/datasets/points.jsonl`
+    /// - `gs://my-bucket/datasets/points/`
+    /// - `file:///qdrant/import/points.jsonl`
+    pub location: Url,
+
+    /// Number of points upserted in a single update operation
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+
+    /// Number of update operations running concurrently
+    #[serde(default)]
+    #[validate(range(min = 1, max = 64))]
+    pub parallelism: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportTaskStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct ImportTaskInfo {
+    pub task_id: ImportTaskId,
+    pub collection_name: String,
+    pub location: Url,
+    pub status: ImportTaskStatus,
+    /// Number of dataset files found at the location, known once listing is done
+    pub files_total: Option<usize>,
+    /// Number of dataset files read completely
+    pub files_read: usize,
+    /// Number of points successfully upserted so far
+    pub points_imported: usize,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Reason of the failure, if status is `failed`
+    pub error: Option<String>,
+}
+
+/// Object store location resolved from an import URL.
+pub struct ImportSource {
+    store: Arc<dyn ObjectStore>,
+    path: ObjectPath,
+    is_prefix: bool,
+}
+
+impl ImportSource {
+    /// Resolve the object store for the given URL.
+    ///
+    /// Credentials for `s3://` and `gs://` are taken from the environment, the same way as for
+    /// snapshots stored in S3, so callers must require manage access for any location.
+    pub fn open(location: &Url) -> Result<Self, StorageError> {
+        let is_prefix = location.path().ends_with('/');
+
+        let (store, path): (Arc<dyn ObjectStore>, _) = match location.scheme() {
+            "s3" => {
+                let store = AmazonS3Builder::from_env()
+                    .with_url(location.as_str())
+                    .build()
+                    .map_err(|err| invalid_location(location, err))?;
+                (Arc::new(store), object_path(location)?)
+            }
+            "gs" => {
+                let store = GoogleCloudStorageBuilder::from_env()
+                    .with_url(location.as_str())
+                    .build()
+                    .map_err(|err| invalid_location(location, err))?;
+                (Arc::new(store), object_path(location)?)
+            }
+            "file" => {
+                let local_path = location.to_file_path().map_err(|_| {
+                    invalid_location(location, "file path must be absolute or on localhost")
+                })?;
+                let path = ObjectPath::from_absolute_path(&local_path)
+                    .map_err(|err| invalid_location(location, err))?;
+                (Arc::new(LocalFileSystem::new()), path)
+            }
+            scheme => {
+                return Err(StorageError::bad_input(format!(
+                    "Invalid import location {location}: URLs with {scheme} scheme are not supported",
+                )));
+            }
+        };
+
+        Ok(Self {
+            store,
+            path,
+            is_prefix,
+        })
+    }
+
+    /// All dataset files of this source, in lexicographical order
+    async fn list_files(&self) -> Result<Vec<ObjectPath>, StorageError> {
+        if !self.is_prefix {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files: Vec<_> = self
+            .store
+            .list(Some(&self.path))
+            .map_ok(|meta| meta.location)
+            .try_collect()
+            .await
+            .map_err(|err| {
+                StorageError::service_error(format!("Failed to list {}: {err}", self.path))
+            })?;
+
+        files.sort();
+        Ok(files)
+    }
+
+    async fn read_lines(
+        &self,
+        file: &ObjectPath,
+    ) -> Result<Lines<impl AsyncBufRead + Unpin>, StorageError> {
+        let object = self.store.get(file).await.map_err(|err| match err {
+            object_store::Error::NotFound { .. } => {
+                StorageError::not_found(format!("Import file {file} does not exist"))
+            }
+            _ => StorageError::service_error(format!("Failed to get {file}: {err}")),
+        })?;
+
+        let stream = object.into_stream().map_err(std::io::Error::other);
+        Ok(StreamReader::new(stream).lines())
+    }
+}
+
+fn object_path(location: &Url) -> Result<ObjectPath, StorageError> {
+    ObjectPath::from_url_path(location.path()).map_err(|err| invalid_location(location, err))
+}
+
+fn invalid_location(location: &Url, err: impl std::fmt::Display) -> StorageError {
+    StorageError::bad_input(format!("Invalid import location {location}: {err}"))
+}
+
+/// Registry of import tasks started on this peer.
+#[derive(Default)]
+pub struct ImportTasks {
+    tasks: Arc<Mutex<HashMap<ImportTaskId, ImportTaskInfo>>>,
+}
+
+impl ImportTasks {
+    /// Start importing points from `source` in the background.
+    ///
+    /// Every line of the dataset is deserialized into `T`, and each batch of points is passed to
+    /// `upsert`, which returns the number of upserted points. Up to `parallelism` batches are in
+    /// flight at the same time. The import stops at the first error, points upserted before it
+    /// stay in the collection.
+    pub fn start<T, F, Fut>(
+        &self,
+        runtime: &Handle,
+        collection_name: String,
+        request: &ImportFromUrlRequest,
+        source: ImportSource,
+        upsert: F,
+    ) -> ImportTaskInfo
+    where
+        T: DeserializeOwned + Send + 'static,
+        F: Fn(Vec<T>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<usize, StorageError>> + Send + 'static,
+    {
+        let task_id = Uuid::new_v4();
+
+        let info = ImportTaskInfo {
+            task_id,
+            collection_name,
+            location: request.location.clone(),
+            status: ImportTaskStatus::Running,
+            files_total: None,
+            files_read: 0,
+            points_imported: 0,
+            started_at: Utc::now(),
+            finished_at: None,
+            error: None,
+        };
+
+        self.tasks.lock().insert(task_id, info.clone());
+
+        let runner = ImportRunner {
+            tasks: self.tasks.clone(),
+            task_id,
+            source,
+            batch_size: request.batch_size.unwrap_or(DEFAULT_IMPORT_BATCH_SIZE),
+            parallelism: request.parallelism.unwrap_or(DEFAULT_IMPORT_PARALLELISM),
+        };
+
+        runtime.spawn(async move {
+            let result = runner.run(upsert).await;
+            runner.finish(result);
+        });
+
+        info
+    }
+
+    pub fn get(&self, collection_name: &str, task_id: ImportTaskId) -> Option<ImportTaskInfo> {
+        self.tasks
+            .lock()
+            .get(&task_id)
+            .filter(|info| info.collection_name == collection_name)
+            .cloned()
+    }
+
+    /// All known tasks of the collection, most recent first
+    pub fn list(&self, collection_name: &str) -> Vec<ImportTaskInfo> {
+        let mut tasks: Vec<_> = self
+            .tasks
+            .lock()
+            .values()
+            .filter(|info| info.collection_name == collection_name)
+            .cloned()
+            .collect();
+
+        tasks.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        tasks
+    }
+}
+
+struct ImportRunner {
+    tasks: Arc<Mutex<HashMap<ImportTaskId, ImportTaskInfo>>>,
+    task_id: ImportTaskId,
+    source: ImportSource,
+    batch_size: usize,
+    parallelism: usize,
+}
+
+impl ImportRunner {
+    async fn run<T, F, Fut>(&self, upsert: F) -> Result<(), StorageError>
+    where
+        T: DeserializeOwned + Send + 'static,
+        F: Fn(Vec<T>) -> Fut,
+        Fut: Future<Output = Result<usize, StorageError>> + Send + 'static,
+    {
+        let files = self.source.list_files().await?;
+        self.update_progress(|info| info.files_total = Some(files.len()));
+
+        // Dropping the set on error aborts all batches in flight
+        let mut in_flight = JoinSet::new();
+
+        for file in files {
+            let mut lines = self.source.read_lines(&file).await?;
+            let mut batch = Vec::with_capacity(self.batch_size);
+            let mut line_number = 0;
+
+            loop {
+                let line = lines.next_line().await.map_err(|err| {
+                    StorageError::service_error(format!("Failed to read {file}: {err}"))
+                })?;
+
+                let Some(line) = line else {
+                    break;
+                };
+
+                line_number += 1;
+
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let point = serde_json::from_str(&line).map_err(|err| {
+                    StorageError::bad_input(format!("Invalid point at {file}:{line_number}: {err}"))
+                })?;
+
+                batch.push(point);
+
+                if batch.len() >= self.batch_size {
+                    let points = std::mem::replace(&mut batch, Vec::with_capacity(self.batch_size));
+                    self.wait_for_capacity(&mut in_flight).await?;
+                    in_flight.spawn(upsert(points));
+                }
+            }
+
+            if !batch.is_empty() {
+                self.wait_for_capacity(&mut in_flight).await?;
+                in_flight.spawn(upsert(batch));
+            }
+
+            self.update_progress(|info| info.files_read += 1);
+        }
+
+        while let Some(result) = in_flight.join_next().await {
+            self.handle_batch_result(result)?;
+        }
+
+        Ok(())
+    }
+
+    async fn wait_for_capacity(
+        &self,
+        in_flight: &mut JoinSet<Result<usize, StorageError>>,
+    ) -> Result<(), StorageError> {
+        while in_flight.len() >= self.parallelism {
+            let Some(result) = in_flight.join_next().await else {
+                break;
+            };
+            self.handle_batch_result(result)?;
+        }
+        Ok(())
+    }
+
+    fn handle_batch_result(
+        &self,
+        result: Result<Result<usize, StorageError>, tokio::task::JoinError>,
+    ) -> Result<(), StorageError> {
+        let points_count = result??;
+        self.update_progress(|info| info.points_imported += points_count);
+        Ok(())
+    }
+
+    fn update_progress(&self, update: impl FnOnce(&mut ImportTaskInfo)) {
+        if let Some(info) = self.tasks.lock().get_mut(&self.task_id) {
+            update(info);
+        }
+    }
+
+    fn finish(&self, result: Result<(), StorageError>) {
+        let mut tasks = self.tasks.lock();
+
+        if let Some(info) = tasks.get_mut(&self.task_id) {
+            info.finished_at = Some(Utc::now());
+            match result {
+                Ok(()) => {
+                    log::info!(
+                        "Imported {} points from {} into collection {}",
+                        info.points_imported,
+                        info.location,
+                        info.collection_name,
+                    );
+                    info.status = ImportTaskStatus::Completed;
+                }
+                Err(err) => {
+                    log::error!(
+                        "Import {} into collection {} failed: {err}",
+                        info.location,
+                        info.collection_name,
+                    );
+                    info.status = ImportTaskStatus::Failed;
+                    info.error = Some(err.to_string());
+                }
+            }
+        }
+
+        // Forget the oldest finished tasks
+        let mut finished: Vec<_> = tasks
+            .values()
+            .filter_map(|info| Some((info.finished_at?, info.task_id)))
+            .collect();
+
+        if finished.len() > MAX_FINISHED_IMPORT_TASKS {
+            finished.sort();
+            let excess = finished.len() - MAX_FINISHED_IMPORT_TASKS;
+            for (_, task_id) in finished.into_iter().take(excess) {
+                tasks.remove(&task_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_import_jsonl_prefix() {
+        let dir = tempfile::Builder::new().prefix("import").tempdir().unwrap();
+
+        for part in 0..3 {
+            let mut file =
+                fs_err::File::create(dir.path().join(format!("part-{part}.jsonl"))).unwrap();
+            for i in 0..10 {
+                let point = json!({"id": part * 10 + i, "vector": [1.0, 2.0]});
+                writeln!(file, "{point}").unwrap();
+            }
+        }
+
+        let location = Url::from_directory_path(dir.path()).unwrap();
+        let request = ImportFromUrlRequest {
+            location: location.clone(),
+            batch_size: Some(4),
+            parallelism: Some(2),
+        };
+
+        let source = ImportSource::open(&location).unwrap();
+        let tasks = ImportTasks::default();
+        let imported_ids = Arc::new(Mutex::new(Vec::new()));
+
+        let info = tasks.start(&Handle::current(), "test".to_string(), &request, source, {
+            let imported_ids = imported_ids.clone();
+            move |points: Vec<serde_json::Value>| {
+                let imported_ids = imported_ids.clone();
+                async move {
+                    assert!(points.len() <= 4);
+                    let count = points.len();
+                    imported_ids
+                        .lock()
+                        .extend(points.iter().map(|point| point["id"].as_u64().unwrap()));
+                    Ok(count)
+                }
+            }
+        });
+
+        let info = loop {
+            let info = tasks.get("test", info.task_id).unwrap();
+            if info.status != ImportTaskStatus::Running {
+                break info;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+
+        assert_eq!(info.status, ImportTaskStatus::Completed, "{:?}", info.error);
+        assert_eq!(info.files_total, Some(3));
+        assert_eq!(info.files_read, 3);
+        assert_eq!(info.points_imported, 30);
+        assert!(tasks.get("other", info.task_id).is_none());
+
+        let mut imported_ids = imported_ids.lock().clone();
+        imported_ids.sort_unstable();
+        assert_eq!(imported_ids, (0..30).collect::<Vec<_>>());
+    }
+}
@@ -5,6 +5,7 @@ use self::consensus_manager::CollectionsSnapshot;
 use self::errors::StorageError;
 
 pub mod alias_mapping;
+pub mod clone_collection;
 pub mod collection_meta_ops;
 pub mod collection_verification;
 mod collections_ops;
@@ -12,6 +13,8 @@ pub mod consensus;
 pub mod consensus_manager;
 pub mod conversions;
 pub mod errors;
+pub mod import;
+pub mod point_id_generator;
 pub mod shard_distribution;
 pub mod snapshots;
 #[cfg(feature = "staging")]
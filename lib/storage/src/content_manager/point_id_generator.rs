@@ -0,0 +1,140 @@
+//! Assignment of ids to points inserted without them.
+//!
+//! Sequential ids are composed of the current time in milliseconds, the lower bits of the peer id
+//! and a sequence number within the millisecond, so they grow monotonically on a single peer.
+//! Ids are never taken from a millisecond which didn't start yet, so they don't repeat after a
+//! restart unless the clock went backwards.
+//!
+//! Peers whose ids share the lower bits, or a clock going backwards, can still produce an id
+//! which is already taken. Points with assigned ids are therefore only inserted, never overwriting
+//! an existing point.
+
+use std::thread;
+
+use api::rest::PointIdGeneration;
+use chrono::Utc;
+use collection::shards::shard::PeerId;
+use parking_lot::Mutex;
+use segment::types::PointIdType;
+use uuid::Uuid;
+
+const PEER_BITS: u32 = 10;
+const SEQUENCE_BITS: u32 = 12;
+const MAX_SEQUENCE: u64 = (1 << SEQUENCE_BITS) - 1;
+const TIMESTAMP_MASK: u64 = (1 << (u64::BITS - PEER_BITS - SEQUENCE_BITS)) - 1;
+
+pub struct PointIdGenerator {
+    peer_bits: u64,
+    /// Timestamp and sequence number of the last assigned sequential id
+    last: Mutex<(u64, u64)>,
+}
+
+impl PointIdGenerator {
+    pub fn new(this_peer_id: PeerId) -> Self {
+        Self {
+            peer_bits: this_peer_id & ((1 << PEER_BITS) - 1),
+            last: Mutex::new((0, 0)),
+        }
+    }
+
+    /// Generate `count` new point ids of the given type
+    pub fn generate(&self, id_generation: PointIdGeneration, count: usize) -> Vec<PointIdType> {
+        match id_generation {
+            PointIdGeneration::Uuid => (0..count)
+                .map(|_| PointIdType::Uuid(Uuid::new_v4()))
+                .collect(),
+            PointIdGeneration::Sequential => {
+                let mut last = self.last.lock();
+                (0..count)
+                    .map(|_| PointIdType::NumId(self.next_sequential(&mut last)))
+                    .collect()
+            }
+        }
+    }
+
+    fn next_sequential(&self, last: &mut (u64, u64)) -> u64 {
+        let (timestamp, sequence) = last;
+        let mut now = current_timestamp();
+
+        if now > *timestamp {
+            *timestamp = now;
+            *sequence = 0;
+        } else if *sequence < MAX_SEQUENCE {
+            *sequence += 1;
+        } else {
+            // Sequence is exhausted within this millisecond, wait for the next one
+            while now <= *timestamp {
+                thread::yield_now();
+                now = current_timestamp();
+            }
+            *timestamp = now;
+            *sequence = 0;
+        }
+
+        (*timestamp << (PEER_BITS + SEQUENCE_BITS)) | (self.peer_bits << SEQUENCE_BITS) | *sequence
+    }
+}
+
+fn current_timestamp() -> u64 {
+    Utc::now().timestamp_millis().max(0) as u64 & TIMESTAMP_MASK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequential_ids_increase() {
+        let generator = PointIdGenerator::new(12345);
+
+        let ids: Vec<u64> = generator
+            .generate(PointIdGeneration::Sequential, 10_000)
+            .into_iter()
+            .chain(generator.generate(PointIdGeneration::Sequential, 10))
+            .map(|id| match id {
+                PointIdType::NumId(id) => id,
+                PointIdType::Uuid(_) => panic!("Unexpected UUID"),
+            })
+            .collect();
+
+        assert_eq!(ids.len(), 10_010);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(
+            ids.iter()
+                .all(|id| (id >> SEQUENCE_BITS) & ((1 << PEER_BITS) - 1) == 12345 % 1024)
+        );
+    }
+
+    #[test]
+    fn test_sequential_ids_after_restart() {
+        let to_num = |id| match id {
+            PointIdType::NumId(id) => id,
+            PointIdType::Uuid(_) => panic!("Unexpected UUID"),
+        };
+
+        // Exhaust the sequence of several milliseconds
+        let generator = PointIdGenerator::new(1);
+        let last_id = generator
+            .generate(PointIdGeneration::Sequential, 20_000)
+            .into_iter()
+            .map(to_num)
+            .max()
+            .unwrap();
+
+        thread::sleep(std::time::Duration::from_millis(2));
+
+        let restarted = PointIdGenerator::new(1);
+        let ids = restarted.generate(PointIdGeneration::Sequential, 1);
+        assert!(to_num(ids[0]) > last_id);
+    }
+
+    #[test]
+    fn test_uuid_ids() {
+        let generator = PointIdGenerator::new(1);
+        let ids = generator.generate(PointIdGeneration::Uuid, 3);
+
+        assert_eq!(ids.len(), 3);
+        assert!(ids.iter().all(|id| matches!(id, PointIdType::Uuid(_))));
+        assert_ne!(ids[0], ids[1]);
+    }
+}
@@ -221,6 +221,8 @@ impl TableOfContent {
                 );
             }
             removed.stop_gracefully().await;
+            // A collection created with the same name must not serve results of the removed one
+            self.invalidate_search_cache(collection_name);
 
             // Move collection to ".deleted" folder to prevent accidental reuse
             // the original collection path will be moved atomically within this
@@ -273,6 +275,10 @@ impl TableOfContent {
     }
 
     /// performs several alias changes in an atomic fashion
+    ///
+    /// All actions are applied to a copy of the alias mapping, which replaces the current one
+    /// only if every action succeeds. So readers never observe an intermediate state, and a
+    /// failed action doesn't leave previous actions applied.
     async fn update_aliases(
         &self,
         operation: ChangeAliasesOperation,
@@ -281,6 +287,7 @@ impl TableOfContent {
         // Prevent search on partially switched collections
         let collection_lock = self.collections.write().await;
         let mut alias_lock = self.alias_persistence.write().await;
+        let mut alias_mapping = alias_lock.state().clone();
         for action in operation.actions {
             match action {
                 AliasOperations::CreateAlias(CreateAliasOperation {
@@ -293,12 +300,12 @@ impl TableOfContent {
                     collection_lock.validate_collection_exists(&collection_name)?;
                     collection_lock.validate_collection_not_exists(&alias_name)?;
 
-                    alias_lock.insert(alias_name, collection_name)?;
+                    alias_mapping.insert(alias_name, collection_name);
                 }
                 AliasOperations::DeleteAlias(DeleteAliasOperation {
                     delete_alias: DeleteAlias { alias_name },
                 }) => {
-                    alias_lock.remove(&alias_name)?;
+                    alias_mapping.remove(&alias_name);
                 }
                 AliasOperations::RenameAlias(RenameAliasOperation {
                     rename_alias:
@@ -307,10 +314,20 @@ impl TableOfContent {
                             new_alias_name,
                         },
                 }) => {
-                    alias_lock.rename_alias(&old_alias_name, new_alias_name)?;
+                    alias_mapping.rename(&old_alias_name, new_alias_name)?;
+                }
+                AliasOperations::SwapAliases(SwapAliasesOperation {
+                    swap_aliases:
+                        SwapAliases {
+                            first_alias_name,
+                            second_alias_name,
+                        },
+                }) => {
+                    alias_mapping.swap(&first_alias_name, &second_alias_name)?;
                 }
             };
         }
+        alias_lock.apply_state(alias_mapping)?;
         Ok(true)
     }
 
@@ -5,6 +5,7 @@ pub mod dispatcher;
 mod point_ops;
 mod point_ops_internal;
 pub mod request_hw_counter;
+mod search_cache;
 mod snapshots;
 mod telemetry;
 mod temp_directories;
@@ -38,12 +39,16 @@ use tokio::runtime::{Handle, Runtime};
 use tokio::sync::{Mutex, RwLock, RwLockReadGuard, Semaphore};
 
 use self::dispatcher::TocDispatcher;
+use self::search_cache::CollectionSearchCache;
 use crate::ConsensusOperations;
 use crate::content_manager::alias_mapping::AliasPersistence;
+use crate::content_manager::clone_collection::CloneTasks;
 use crate::content_manager::collection_meta_ops::CreateCollectionOperation;
 use crate::content_manager::collections_ops::{Checker, Collections};
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::errors::StorageError;
+use crate::content_manager::import::ImportTasks;
+use crate::content_manager::point_id_generator::PointIdGenerator;
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
 use crate::content_manager::toc::telemetry::TocTelemetryCollector;
 use crate::rbac::{Access, AccessRequirements, CollectionPass};
@@ -84,8 +89,16 @@ pub struct TableOfContent {
     collection_create_lock: Mutex<()>,
     /// Aggregation of all hardware measurements for each alias or collection config.
     collection_hw_metrics: DashMap<CollectionId, HwSharedDrain>,
+    /// Cached search results of each collection.
+    search_caches: DashMap<CollectionId, Arc<CollectionSearchCache>>,
     /// Collector for various telemetry/metrics.
     telemetry: TocTelemetryCollector,
+    /// Background imports of points from object storage, started on this peer.
+    import_tasks: ImportTasks,
+    /// Background copies of points into cloned collections, started on this peer.
+    clone_tasks: CloneTasks,
+    /// Ids assigned to points inserted without them through this peer.
+    point_id_generator: PointIdGenerator,
 }
 
 impl TableOfContent {
@@ -209,7 +222,11 @@ impl TableOfContent {
             update_rate_limiter: rate_limiter,
             collection_create_lock: Default::default(),
             collection_hw_metrics: DashMap::new(),
+            search_caches: DashMap::new(),
             telemetry,
+            import_tasks: ImportTasks::default(),
+            clone_tasks: CloneTasks::default(),
+            point_id_generator: PointIdGenerator::new(this_peer_id),
         }
     }
 
@@ -700,4 +717,16 @@ impl TableOfContent {
     pub fn general_runtime_handle(&self) -> &Handle {
         self.general_runtime.handle()
     }
+
+    pub fn import_tasks(&self) -> &ImportTasks {
+        &self.import_tasks
+    }
+
+    pub fn clone_tasks(&self) -> &CloneTasks {
+        &self.clone_tasks
+    }
+
+    pub fn point_id_generator(&self) -> &PointIdGenerator {
+        &self.point_id_generator
+    }
 }
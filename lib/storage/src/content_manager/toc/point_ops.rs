@@ -7,23 +7,27 @@ use collection::collection::distance_matrix::{
 use collection::grouping::GroupBy;
 use collection::grouping::group_by::GroupRequest;
 use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::point_ops::WriteOrdering;
+use collection::operations::point_ops::{UpdateAck, WriteOrdering};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
+use collection::operations::universal_query::query_plan::QueryPlan;
 use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::shards::local_shard::point_history::PointVersion;
 use collection::{discovery, recommendations};
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use futures::TryStreamExt as _;
 use futures::stream::FuturesUnordered;
 use segment::data_types::facets::{FacetParams, FacetResponse};
-use segment::types::{ScoredPoint, ShardKey};
+use segment::types::{
+    Payload, PayloadSelectorTransform, PointIdType, ScoredPoint, ShardKey, WithPayloadInterface,
+};
 use shard::retrieve::record_internal::RecordInternal;
 use shard::search::CoreSearchRequestBatch;
 
 use super::TableOfContent;
 use crate::content_manager::errors::{StorageError, StorageResult};
-use crate::rbac::Access;
+use crate::rbac::{Access, AccessRequirements};
 
 impl TableOfContent {
     /// Recommend points using positive and negative example from the request
@@ -136,8 +140,14 @@ impl TableOfContent {
             return Ok(vec![]);
         };
 
+        let transforms: Vec<_> = request
+            .searches
+            .iter_mut()
+            .map(|search| take_payload_transform(&mut search.with_payload))
+            .collect();
+
         let collection = self.get_collection(&collection_pass).await?;
-        collection
+        let mut results = collection
             .core_search_batch(
                 request,
                 read_consistency,
@@ -145,8 +155,52 @@ impl TableOfContent {
                 timeout,
                 hw_measurement_acc,
             )
-            .await
-            .map_err(|err| err.into())
+            .await?;
+
+        apply_scored_payload_transforms(&mut results, &transforms);
+        Ok(results)
+    }
+
+    /// Same as [`Self::core_search_batch`], but shards which don't answer within the timeout
+    /// are left out of the results instead of failing the whole batch.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn core_search_batch_partial(
+        &self,
+        collection_name: &str,
+        mut request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> StorageResult<PartialSearchResults> {
+        let mut collection_pass = None;
+        for request in &mut request.searches {
+            collection_pass = Some(access.check_point_op(collection_name, request)?);
+        }
+        let Some(collection_pass) = collection_pass else {
+            return Ok(PartialSearchResults::default());
+        };
+
+        let transforms: Vec<_> = request
+            .searches
+            .iter_mut()
+            .map(|search| take_payload_transform(&mut search.with_payload))
+            .collect();
+
+        let collection = self.get_collection(&collection_pass).await?;
+        let mut partial = collection
+            .core_search_batch_partial(
+                request,
+                read_consistency,
+                shard_selection,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await?;
+
+        apply_scored_payload_transforms(&mut partial.results, &transforms);
+        Ok(partial)
     }
 
     /// Count points in the collection.
@@ -202,7 +256,7 @@ impl TableOfContent {
     pub async fn retrieve(
         &self,
         collection_name: &str,
-        request: PointRequestInternal,
+        mut request: PointRequestInternal,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
         shard_selection: ShardSelectorInternal,
@@ -210,9 +264,10 @@ impl TableOfContent {
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<Vec<RecordInternal>> {
         let collection_pass = access.check_point_op(collection_name, &request)?;
+        let transform = take_payload_transform(&mut request.with_payload);
 
         let collection = self.get_collection(&collection_pass).await?;
-        collection
+        let mut records = collection
             .retrieve(
                 request,
                 read_consistency,
@@ -220,8 +275,14 @@ impl TableOfContent {
                 timeout,
                 hw_measurement_acc,
             )
-            .await
-            .map_err(|err| err.into())
+            .await?;
+
+        if let Some(transform) = &transform {
+            for record in &mut records {
+                apply_payload_transform(&mut record.payload, transform);
+            }
+        }
+        Ok(records)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -326,7 +387,7 @@ impl TableOfContent {
     pub async fn scroll(
         &self,
         collection_name: &str,
-        request: ScrollRequestInternal,
+        mut request: ScrollRequestInternal,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
         shard_selection: ShardSelectorInternal,
@@ -334,9 +395,10 @@ impl TableOfContent {
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<ScrollResult> {
         let collection_pass = access.check_point_op(collection_name, &request)?;
+        let transform = take_payload_transform(&mut request.with_payload);
 
         let collection = self.get_collection(&collection_pass).await?;
-        collection
+        let mut result = collection
             .scroll_by(
                 request,
                 read_consistency,
@@ -344,8 +406,14 @@ impl TableOfContent {
                 timeout,
                 hw_measurement_acc,
             )
-            .await
-            .map_err(|err| err.into())
+            .await?;
+
+        if let Some(transform) = &transform {
+            for point in &mut result.points {
+                apply_payload_transform(&mut point.payload, transform);
+            }
+        }
+        Ok(result)
     }
 
     pub async fn query_batch(
@@ -366,9 +434,14 @@ impl TableOfContent {
             return Ok(vec![]);
         };
 
+        let transforms: Vec<_> = requests
+            .iter_mut()
+            .map(|(request, _shard_selector)| request.with_payload.take_transform())
+            .collect();
+
         let collection = self.get_collection(&collection_pass).await?;
 
-        collection
+        let mut results = collection
             .query_batch(
                 requests,
                 |name| self.get_collection_opt(name),
@@ -376,6 +449,56 @@ impl TableOfContent {
                 timeout,
                 hw_measurement_acc,
             )
+            .await?;
+
+        apply_scored_payload_transforms(&mut results, &transforms);
+        Ok(results)
+    }
+
+    /// Describe how a query would be executed, without running it
+    #[allow(clippy::too_many_arguments)]
+    pub async fn query_plan(
+        &self,
+        collection_name: &str,
+        mut request: CollectionQueryRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> StorageResult<QueryPlan> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .query_plan(
+                request,
+                shard_selection,
+                |name| self.get_collection_opt(name),
+                read_consistency,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Previous versions of a point, latest first
+    pub async fn point_history(
+        &self,
+        collection_name: &str,
+        point_id: PointIdType,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+    ) -> StorageResult<Vec<PointVersion>> {
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new())?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .point_history(point_id, shard_selection)
             .await
             .map_err(|err| err.into())
     }
@@ -445,7 +568,7 @@ impl TableOfContent {
         collection: &Collection,
         shard_keys: Vec<ShardKey>,
         operation: CollectionUpdateOperations,
-        wait: bool,
+        ack: UpdateAck,
         ordering: WriteOrdering,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<UpdateResult> {
@@ -456,7 +579,7 @@ impl TableOfContent {
             .map(|shard_key| {
                 collection.update_from_client(
                     operation.clone(),
-                    wait,
+                    ack,
                     ordering,
                     Some(shard_key),
                     hw_measurement_acc.clone(),
@@ -481,7 +604,7 @@ impl TableOfContent {
         &self,
         collection_name: &str,
         operation: OperationWithClockTag,
-        wait: bool,
+        ack: UpdateAck,
         ordering: WriteOrdering,
         shard_selector: ShardSelectorInternal,
         access: Access,
@@ -536,101 +659,135 @@ impl TableOfContent {
 
         // TODO: `debug_assert(operation.clock_tag.is_none())` for `_update_shard_keys`/`update_from_client`!?
 
-        let res = match shard_selector {
-            ShardSelectorInternal::Empty => {
-                collection
-                    .update_from_client(
-                        operation.operation,
-                        wait,
-                        ordering,
-                        None,
-                        hw_measurement_acc.clone(),
-                    )
-                    .await?
-            }
-
-            ShardSelectorInternal::All => {
-                let shard_keys = collection.get_shard_keys().await;
-                if shard_keys.is_empty() {
+        let res: StorageResult<UpdateResult> = async {
+            Ok(match shard_selector {
+                ShardSelectorInternal::Empty => {
                     collection
                         .update_from_client(
                             operation.operation,
-                            wait,
+                            ack,
                             ordering,
                             None,
                             hw_measurement_acc.clone(),
                         )
                         .await?
-                } else {
+                }
+
+                ShardSelectorInternal::All => {
+                    let shard_keys = collection.get_shard_keys().await;
+                    if shard_keys.is_empty() {
+                        collection
+                            .update_from_client(
+                                operation.operation,
+                                ack,
+                                ordering,
+                                None,
+                                hw_measurement_acc.clone(),
+                            )
+                            .await?
+                    } else {
+                        Self::_update_shard_keys(
+                            &collection,
+                            shard_keys,
+                            operation.operation,
+                            ack,
+                            ordering,
+                            hw_measurement_acc.clone(),
+                        )
+                        .await?
+                    }
+                }
+
+                ShardSelectorInternal::ShardKey(shard_key) => {
+                    collection
+                        .update_from_client(
+                            operation.operation,
+                            ack,
+                            ordering,
+                            Some(shard_key),
+                            hw_measurement_acc.clone(),
+                        )
+                        .await?
+                }
+
+                ShardSelectorInternal::ShardKeys(shard_keys) => {
                     Self::_update_shard_keys(
                         &collection,
                         shard_keys,
                         operation.operation,
-                        wait,
+                        ack,
                         ordering,
                         hw_measurement_acc.clone(),
                     )
                     .await?
                 }
-            }
 
-            ShardSelectorInternal::ShardKey(shard_key) => {
-                collection
-                    .update_from_client(
+                ShardSelectorInternal::ShardKeyWithFallback(key) => {
+                    let shard_keys: Vec<_> = collection
+                        .shards_holder()
+                        .read()
+                        .await
+                        .route_with_fallback_for_write(key)?
+                        .into_iter()
+                        .map(|(_shard_ids, shard_key)| shard_key)
+                        .collect();
+
+                    Self::_update_shard_keys(
+                        &collection,
+                        shard_keys,
                         operation.operation,
-                        wait,
+                        ack,
                         ordering,
-                        Some(shard_key),
                         hw_measurement_acc.clone(),
                     )
                     .await?
-            }
+                }
+                ShardSelectorInternal::ShardId(shard_selection) => {
+                    collection
+                        .update_from_peer(
+                            operation,
+                            shard_selection,
+                            ack,
+                            ordering,
+                            hw_measurement_acc.clone(),
+                        )
+                        .await?
+                }
+            })
+        }
+        .await;
 
-            ShardSelectorInternal::ShardKeys(shard_keys) => {
-                Self::_update_shard_keys(
-                    &collection,
-                    shard_keys,
-                    operation.operation,
-                    wait,
-                    ordering,
-                    hw_measurement_acc.clone(),
-                )
-                .await?
-            }
+        // Invalidate after the update is applied (or failed), so that searches racing with it
+        // are never cached as up to date
+        self.invalidate_search_cache(collection.name());
 
-            ShardSelectorInternal::ShardKeyWithFallback(key) => {
-                let shard_keys: Vec<_> = collection
-                    .shards_holder()
-                    .read()
-                    .await
-                    .route_with_fallback_for_write(key)?
-                    .into_iter()
-                    .map(|(_shard_ids, shard_key)| shard_key)
-                    .collect();
-
-                Self::_update_shard_keys(
-                    &collection,
-                    shard_keys,
-                    operation.operation,
-                    wait,
-                    ordering,
-                    hw_measurement_acc.clone(),
-                )
-                .await?
-            }
-            ShardSelectorInternal::ShardId(shard_selection) => {
-                collection
-                    .update_from_peer(
-                        operation,
-                        shard_selection,
-                        wait,
-                        ordering,
-                        hw_measurement_acc.clone(),
-                    )
-                    .await?
-            }
-        };
+        res
+    }
+}
 
-        Ok(res)
+/// Split off a payload transformation, so that shards only return its source fields
+fn take_payload_transform(
+    with_payload: &mut Option<WithPayloadInterface>,
+) -> Option<PayloadSelectorTransform> {
+    with_payload.as_mut()?.take_transform()
+}
+
+fn apply_payload_transform(payload: &mut Option<Payload>, transform: &PayloadSelectorTransform) {
+    if let Some(payload) = payload {
+        *payload = transform.apply(payload);
+    }
+}
+
+fn apply_scored_payload_transforms(
+    batches: &mut [Vec<ScoredPoint>],
+    transforms: &[Option<PayloadSelectorTransform>],
+) {
+    for (points, transform) in batches.iter_mut().zip(transforms) {
+        let Some(transform) = transform else {
+            continue;
+        };
+        for point in points {
+            apply_payload_transform(&mut point.payload, transform);
+        }
     }
 }
@@ -0,0 +1,359 @@
+//! Cache of search results, placed in front of [`TableOfContent::core_search_batch`].
+//!
+//! Intended to absorb repeated identical queries, e.g. from RAG frontends.
+//! Each collection has its own cache, which is cleared as soon as the collection is updated
+//! through this peer. Updates which reach the collection differently (e.g. applied only on other
+//! peers, shard transfers or snapshot recovery) are not tracked, staleness is then bounded by the
+//! TTL.
+
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use lru::LruCache;
+use parking_lot::Mutex;
+use segment::types::ScoredPoint;
+use shard::search::{CoreSearchRequest, CoreSearchRequestBatch};
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageResult;
+use crate::rbac::Access;
+use crate::types::CollectionSearchCacheConfig;
+
+/// Cached search results of a single collection
+pub(super) struct CollectionSearchCache {
+    inner: Mutex<CollectionSearchCacheInner>,
+}
+
+struct CollectionSearchCacheInner {
+    /// Number of times the cache was invalidated.
+    /// Results of searches which started before an invalidation are not cached.
+    generation: u64,
+    /// Cached results, if caching is enabled for the collection
+    entries: Option<LruCache<SearchCacheKey, CacheEntry>>,
+    ttl: Duration,
+}
+
+/// Batch request the results are cached for
+///
+/// Hashed only by the parts which usually differ between requests, but compared in full.
+#[derive(PartialEq)]
+struct SearchCacheKey {
+    searches: Vec<CoreSearchRequest>,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+}
+
+// Requests containing NaN never match a cached entry, so they are effectively not cached
+impl Eq for SearchCacheKey {}
+
+impl Hash for SearchCacheKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for search in &self.searches {
+            search.query.hash(state);
+            search.filter.hash(state);
+            search.limit.hash(state);
+            search.offset.hash(state);
+        }
+    }
+}
+
+struct CacheEntry {
+    results: Vec<Vec<ScoredPoint>>,
+    inserted_at: Instant,
+}
+
+impl CollectionSearchCache {
+    fn new(config: Option<&CollectionSearchCacheConfig>) -> Self {
+        let entries = config
+            .and_then(|config| NonZeroUsize::new(config.max_entries))
+            .map(LruCache::new);
+        let ttl = config.map_or(Duration::ZERO, |config| Duration::from_secs(config.ttl_sec));
+
+        Self {
+            inner: Mutex::new(CollectionSearchCacheInner {
+                generation: 0,
+                entries,
+                ttl,
+            }),
+        }
+    }
+
+    /// Returns cached results of the request if there are any, or the current generation to
+    /// insert the results with otherwise
+    fn get(&self, key: &SearchCacheKey) -> Result<Vec<Vec<ScoredPoint>>, u64> {
+        let mut inner = self.inner.lock();
+        let CollectionSearchCacheInner {
+            generation,
+            entries,
+            ttl,
+        } = &mut *inner;
+
+        let Some(entries) = entries else {
+            return Err(*generation);
+        };
+
+        if let Some(entry) = entries.get(key) {
+            if entry.inserted_at.elapsed() < *ttl {
+                return Ok(entry.results.clone());
+            }
+            entries.pop(key);
+        }
+
+        Err(*generation)
+    }
+
+    fn insert(&self, key: SearchCacheKey, generation: u64, results: Vec<Vec<ScoredPoint>>) {
+        let mut inner = self.inner.lock();
+        if inner.generation != generation {
+            return;
+        }
+
+        if let Some(entries) = &mut inner.entries {
+            let entry = CacheEntry {
+                results,
+                inserted_at: Instant::now(),
+            };
+            entries.put(key, entry);
+        }
+    }
+
+    fn invalidate(&self) {
+        let mut inner = self.inner.lock();
+        inner.generation += 1;
+        if let Some(entries) = &mut inner.entries {
+            entries.clear();
+        }
+    }
+}
+
+impl TableOfContent {
+    /// Same as [`Self::core_search_batch`], but serves results from the search cache of the
+    /// collection, if one is configured.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn core_search_batch_cached(
+        &self,
+        collection_name: &str,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> StorageResult<Vec<Vec<ScoredPoint>>> {
+        let Some(config) = &self.storage_config.search_cache else {
+            return self
+                .core_search_batch(
+                    collection_name,
+                    request,
+                    read_consistency,
+                    shard_selection,
+                    access,
+                    timeout,
+                    hw_measurement_acc,
+                )
+                .await;
+        };
+
+        // Results must never be served to someone who couldn't run the search
+        let mut collection_pass = None;
+        for search in &request.searches {
+            collection_pass = Some(access.check_point_op(collection_name, search)?);
+        }
+        let Some(collection_pass) = collection_pass else {
+            return Ok(vec![]);
+        };
+
+        let real_collection_name = {
+            let collections = self.collections.read().await;
+            let alias_persistence = self.alias_persistence.read().await;
+            Self::resolve_name(collection_pass.name(), &collections, &alias_persistence)?
+        };
+
+        let collection_config = config.collection_config(&real_collection_name);
+        let cacheable = collection_config.is_some_and(|collection_config| {
+            !collection_config.only_without_consistency || read_consistency.is_none()
+        });
+
+        let cached = cacheable.then(|| {
+            let cache = self
+                .search_caches
+                .entry(real_collection_name)
+                .or_insert_with(|| Arc::new(CollectionSearchCache::new(collection_config)))
+                .clone();
+            let key = SearchCacheKey {
+                searches: request.searches.clone(),
+                read_consistency,
+                shard_selection: shard_selection.clone(),
+            };
+            (cache, key)
+        });
+
+        let mut slot = None;
+        if let Some((cache, key)) = cached {
+            match cache.get(&key) {
+                Ok(results) => return Ok(results),
+                Err(generation) => slot = Some((cache, key, generation)),
+            }
+        }
+
+        let results = self
+            .core_search_batch(
+                collection_name,
+                request,
+                read_consistency,
+                shard_selection,
+                access,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await?;
+
+        if let Some((cache, key, generation)) = slot {
+            cache.insert(key, generation, results.clone());
+        }
+
+        Ok(results)
+    }
+
+    /// Drop cached search results of the collection
+    pub(super) fn invalidate_search_cache(&self, collection_name: &str) {
+        let cache = self
+            .search_caches
+            .entry(collection_name.to_string())
+            .or_insert_with(|| {
+                let config = self.storage_config.search_cache.as_ref();
+                let collection_config =
+                    config.and_then(|config| config.collection_config(collection_name));
+                Arc::new(CollectionSearchCache::new(collection_config))
+            })
+            .clone();
+        cache.invalidate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use segment::data_types::vectors::NamedQuery;
+    use segment::types::ExtendedPointId;
+    use shard::query::query_enum::QueryEnum;
+
+    use super::*;
+    use crate::types::SearchCacheConfig;
+
+    fn config(ttl_sec: u64, max_entries: usize) -> CollectionSearchCacheConfig {
+        CollectionSearchCacheConfig {
+            ttl_sec,
+            max_entries,
+            only_without_consistency: true,
+        }
+    }
+
+    fn key(vector: f32) -> SearchCacheKey {
+        SearchCacheKey {
+            searches: vec![CoreSearchRequest {
+                query: QueryEnum::Nearest(NamedQuery::default_dense(vec![vector, 1.0])),
+                filter: None,
+                params: None,
+                limit: 10,
+                offset: 0,
+                with_payload: None,
+                with_vector: None,
+                score_threshold: None,
+                with_explanation: false,
+                with_explanation_bundle: false,
+                explanation_verbosity: Default::default(),
+                explanation_params: Default::default(),
+                mmr: None,
+                distinct_by: None,
+            }],
+            read_consistency: None,
+            shard_selection: ShardSelectorInternal::All,
+        }
+    }
+
+    fn results(id: u64) -> Vec<Vec<ScoredPoint>> {
+        vec![vec![ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score: 1.0,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: None,
+        }]]
+    }
+
+    fn cached_id(cache: &CollectionSearchCache, key: &SearchCacheKey) -> Option<u64> {
+        let results = cache.get(key).ok()?;
+        match results[0][0].id {
+            ExtendedPointId::NumId(id) => Some(id),
+            ExtendedPointId::Uuid(_) => None,
+        }
+    }
+
+    #[test]
+    fn test_search_cache_evicts_least_recently_used() {
+        let cache = CollectionSearchCache::new(Some(&config(60, 2)));
+
+        cache.insert(key(1.0), 0, results(1));
+        cache.insert(key(2.0), 0, results(2));
+        // Touch the first entry, so the second one becomes the least recently used
+        assert_eq!(cached_id(&cache, &key(1.0)), Some(1));
+
+        cache.insert(key(3.0), 0, results(3));
+        assert_eq!(cached_id(&cache, &key(1.0)), Some(1));
+        assert_eq!(cached_id(&cache, &key(2.0)), None);
+        assert_eq!(cached_id(&cache, &key(3.0)), Some(3));
+    }
+
+    #[test]
+    fn test_search_cache_invalidated_by_update_and_ttl() {
+        let cache = CollectionSearchCache::new(Some(&config(60, 10)));
+
+        cache.insert(key(1.0), 0, results(1));
+        assert_eq!(cached_id(&cache, &key(1.0)), Some(1));
+
+        cache.invalidate();
+        assert_eq!(cached_id(&cache, &key(1.0)), None);
+        // Search started before the collection was updated
+        cache.insert(key(1.0), 0, results(1));
+        assert_eq!(cached_id(&cache, &key(1.0)), None);
+        assert_eq!(cache.get(&key(1.0)).err(), Some(1));
+
+        let expired = CollectionSearchCache::new(Some(&config(0, 10)));
+        expired.insert(key(2.0), 0, results(2));
+        assert_eq!(cached_id(&expired, &key(2.0)), None);
+
+        let disabled = CollectionSearchCache::new(None);
+        disabled.insert(key(3.0), 0, results(3));
+        assert_eq!(cached_id(&disabled, &key(3.0)), None);
+    }
+
+    #[test]
+    fn test_search_cache_collection_config() {
+        let listed = config(10, 10);
+        let default = config(60, 100);
+
+        let cache_config = SearchCacheConfig {
+            default: None,
+            collections: HashMap::from([("listed".to_string(), listed.clone())]),
+        };
+        assert_eq!(cache_config.collection_config("listed"), Some(&listed));
+        assert_eq!(cache_config.collection_config("other"), None);
+
+        let cache_config = SearchCacheConfig {
+            default: Some(default.clone()),
+            collections: HashMap::from([("listed".to_string(), listed.clone())]),
+        };
+        assert_eq!(cache_config.collection_config("listed"), Some(&listed));
+        assert_eq!(cache_config.collection_config("other"), Some(&default));
+    }
+}
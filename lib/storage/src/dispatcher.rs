@@ -166,7 +166,9 @@ impl Dispatcher {
                 // Sync nodes when creating or renaming collection aliases
                 CollectionMetaOperations::ChangeAliases(changes) => {
                     changes.actions.iter().any(|change| match change {
-                        AliasOperations::CreateAlias(_) | AliasOperations::RenameAlias(_) => true,
+                        AliasOperations::CreateAlias(_)
+                        | AliasOperations::RenameAlias(_)
+                        | AliasOperations::SwapAliases(_) => true,
                         AliasOperations::DeleteAlias(_) => false,
                     })
                 }
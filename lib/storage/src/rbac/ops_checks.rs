@@ -388,6 +388,7 @@ mod tests_ops {
             positive: vec![RecommendExample::Dense(vec![0.0, 1.0, 2.0])],
             negative: vec![RecommendExample::Sparse(vec![(0, 0.0)].try_into().unwrap())],
             strategy: Some(RecommendStrategy::AverageVector),
+            rocchio: None,
             filter: None,
             params: Some(SearchParams::default()),
             limit: 100,
@@ -460,6 +461,11 @@ mod tests_ops {
             with_vector: Some(WithVector::Bool(true)),
             score_threshold: Some(42.0),
             with_explanation: false,
+            with_explanation_bundle: false,
+            explanation_verbosity: Default::default(),
+            explanation_params: Default::default(),
+            mmr: None,
+            distinct_by: None,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -509,6 +515,7 @@ mod tests_ops {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vectors: Some(WithVector::Bool(true)),
             }),
+            aggregations: None,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -693,6 +700,7 @@ mod tests_ops {
                     PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
                         points_op: inner,
                         condition: filter,
+                        precondition: None,
                     }),
                 );
 
@@ -703,19 +711,29 @@ mod tests_ops {
                 let op =
                     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
                         ids: vec![ExtendedPointId::NumId(12345)],
+                        precondition: None,
+                        deleted_at: None,
                     });
                 check_collection_update_operations_delete_points(&op);
             }
 
             PointOperationsDiscriminants::DeletePointsByFilter => {
                 let op = CollectionUpdateOperations::PointOperation(
-                    PointOperations::DeletePointsByFilter(make_filter_from_ids(vec![
-                        ExtendedPointId::NumId(12345),
-                    ])),
+                    PointOperations::DeletePointsByFilter(
+                        make_filter_from_ids(vec![ExtendedPointId::NumId(12345)]).into(),
+                    ),
                 );
                 check_collection_update_operations_delete_points(&op);
             }
 
+            PointOperationsDiscriminants::RestorePoints => {
+                let op =
+                    CollectionUpdateOperations::PointOperation(PointOperations::RestorePoints {
+                        ids: vec![ExtendedPointId::NumId(12345)],
+                    });
+                check_collection_update_operations_delete_points(&op);
+            }
+
             PointOperationsDiscriminants::SyncPoints => {
                 let op = CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(
                     PointSyncOperation {
@@ -738,7 +756,8 @@ mod tests_ops {
     }
 
     /// Tests for [`CollectionUpdateOperations::PointOperation`] with
-    /// [`PointOperations::DeletePoints`] and [`PointOperations::DeletePointsByFilter`].
+    /// [`PointOperations::DeletePoints`], [`PointOperations::DeletePointsByFilter`] and
+    /// [`PointOperations::RestorePoints`].
     fn check_collection_update_operations_delete_points(op: &CollectionUpdateOperations) {
         assert_allowed(op, &Access::Global(GlobalAccessMode::Manage));
         assert_forbidden(op, &Access::Global(GlobalAccessMode::Read));
@@ -768,6 +787,8 @@ mod tests_ops {
                         PointIdsList {
                             points: vec![ExtendedPointId::NumId(12345)],
                             shard_key: None,
+                            if_version: None,
+                            if_payload_matches: None,
                         },
                         vec!["vector".into()],
                     ));
@@ -804,6 +825,7 @@ mod tests_ops {
                     points: Some(vec![ExtendedPointId::NumId(12345)]),
                     filter: None,
                     key: None,
+                    precondition: None,
                 }),
                 PayloadOpsDiscriminants::DeletePayload => {
                     PayloadOps::DeletePayload(DeletePayloadOp {
@@ -826,6 +848,7 @@ mod tests_ops {
                         points: Some(vec![ExtendedPointId::NumId(12345)]),
                         filter: None,
                         key: None,
+                        precondition: None,
                     })
                 }
             };
@@ -111,6 +111,9 @@ pub struct StorageConfig {
     /// Maximum number of collections to allow in the cluster.
     #[serde(default)]
     pub max_collections: Option<usize>,
+    /// Cache of search results. If not set, results are not cached.
+    #[serde(default)]
+    pub search_cache: Option<SearchCacheConfig>,
 }
 
 impl StorageConfig {
@@ -144,6 +147,50 @@ const fn default_mmap_advice() -> madvise::Advice {
     madvise::Advice::Random
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SearchCacheConfig {
+    /// Cache settings for collections not listed in `collections`.
+    /// If not set, only listed collections are cached.
+    #[serde(default)]
+    pub default: Option<CollectionSearchCacheConfig>,
+    /// Cache settings by collection name
+    #[serde(default)]
+    pub collections: HashMap<String, CollectionSearchCacheConfig>,
+}
+
+impl SearchCacheConfig {
+    pub fn collection_config(&self, collection_name: &str) -> Option<&CollectionSearchCacheConfig> {
+        self.collections
+            .get(collection_name)
+            .or(self.default.as_ref())
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CollectionSearchCacheConfig {
+    /// For how long cached results can be served, in seconds
+    #[serde(default = "default_search_cache_ttl_sec")]
+    pub ttl_sec: u64,
+    /// Maximum number of cached requests, least recently used ones are evicted first
+    #[serde(default = "default_search_cache_max_entries")]
+    pub max_entries: usize,
+    /// Do not cache requests with explicit read consistency
+    #[serde(default = "default_search_cache_only_without_consistency")]
+    pub only_without_consistency: bool,
+}
+
+const fn default_search_cache_ttl_sec() -> u64 {
+    60
+}
+
+const fn default_search_cache_max_entries() -> usize {
+    1000
+}
+
+const fn default_search_cache_only_without_consistency() -> bool {
+    true
+}
+
 /// Information of a peer in the cluster
 #[derive(Anonymize, Debug, Serialize, JsonSchema, Clone)]
 pub struct PeerInfo {
@@ -73,6 +73,7 @@ fn test_alias_operation() {
         shard_transfer_method: None,
         collection: None,
         max_collections: None,
+        search_cache: None,
     };
 
     let search_runtime = Runtime::new().unwrap();
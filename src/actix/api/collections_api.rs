@@ -6,10 +6,12 @@ use actix_web_validator::{Json, Path, Query};
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::verification::new_unchecked_verification_pass;
 use serde::Deserialize;
+use storage::content_manager::clone_collection::{CloneCollection, clone_collection};
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
     DeleteCollectionOperation, UpdateCollection, UpdateCollectionOperation,
 };
+use storage::content_manager::errors::StorageError;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::AccessRequirements;
 use validator::Validate;
@@ -265,6 +267,54 @@ fn get_optimizations(
     })
 }
 
+#[post("/collections/{name}/clone")]
+async fn clone_collection_api(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<CloneCollection>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let timing = Instant::now();
+
+    let response = clone_collection(
+        dispatcher.into_inner(),
+        collection.into_inner().name,
+        operation.into_inner(),
+        access,
+        query.timeout(),
+    )
+    .await;
+
+    process_response(response, timing, None)
+}
+
+#[get("/collections/{name}/clone")]
+async fn get_clone_status(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let timing = Instant::now();
+
+    let response = access
+        .check_collection_access(&collection.name, AccessRequirements::new())
+        .and_then(|_| {
+            dispatcher
+                .toc(&access, &new_unchecked_verification_pass())
+                .clone_tasks()
+                .get(&collection.name)
+                .ok_or_else(|| {
+                    StorageError::not_found(format!(
+                        "Collection `{}` was not cloned on this peer",
+                        collection.name,
+                    ))
+                })
+        });
+
+    process_response(response, timing, None)
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     // Ordering of services is important for correct path pattern matching
@@ -280,7 +330,9 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_collection_aliases)
         .service(get_cluster_info)
         .service(get_optimizations)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(clone_collection_api)
+        .service(get_clone_status);
 }
 
 #[cfg(test)]
@@ -48,7 +48,7 @@ async fn count_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
 
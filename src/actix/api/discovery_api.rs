@@ -51,7 +51,7 @@ async fn discover_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
 
@@ -106,7 +106,7 @@ async fn discover_batch_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
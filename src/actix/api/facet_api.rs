@@ -1,8 +1,11 @@
 use actix_web::{Responder, post, web};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::{FacetRequest, FacetResponse};
+use api::rest::{FacetRequest, FacetRequestBatch, FacetResponse, HistogramRequest};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use storage::content_manager::collection_verification::check_strict_mode;
+use itertools::Itertools;
+use storage::content_manager::collection_verification::{
+    check_strict_mode, check_strict_mode_batch,
+};
 use storage::dispatcher::Dispatcher;
 use tokio::time::Instant;
 
@@ -12,6 +15,8 @@ use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{
     get_request_hardware_counter, process_response, process_response_error,
 };
+use crate::common::histogram::do_histogram_points;
+use crate::common::query::do_facet_points_batch;
 use crate::settings::ServiceConfig;
 
 #[post("/collections/{name}/facet")]
@@ -53,7 +58,7 @@ async fn facet(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
 
@@ -74,6 +79,129 @@ async fn facet(
     process_response(response, timing, request_hw_counter.to_rest_api())
 }
 
+#[post("/collections/{name}/facet/batch")]
+async fn facet_batch(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<FacetRequestBatch>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let FacetRequestBatch { facets } = request.into_inner();
+
+    let pass = match check_strict_mode_batch(
+        facets.iter().map(|facet| &facet.facet_request),
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, timing, None),
+    };
+
+    let requests = facets
+        .into_iter()
+        .map(|facet| {
+            let FacetRequest {
+                facet_request,
+                shard_key,
+            } = facet;
+            let shard_selection = match shard_key {
+                None => ShardSelectorInternal::All,
+                Some(shard_keys) => shard_keys.into(),
+            };
+            (From::from(facet_request), shard_selection)
+        })
+        .collect();
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting() || params.with_usage,
+        None,
+    );
+
+    let response = do_facet_points_batch(
+        dispatcher.toc(&access, &pass),
+        &collection.name,
+        requests,
+        params.consistency,
+        access,
+        params.timeout(),
+        request_hw_counter.get_counter(),
+    )
+    .await
+    .map(|responses| responses.into_iter().map(FacetResponse::from).collect_vec());
+
+    process_response(response, timing, request_hw_counter.to_rest_api())
+}
+
+#[post("/collections/{name}/histogram")]
+async fn histogram(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<HistogramRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let HistogramRequest {
+        histogram_request,
+        shard_key,
+    } = request.into_inner();
+
+    let pass = match check_strict_mode(
+        &histogram_request,
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, timing, None),
+    };
+
+    let histogram_params = From::from(histogram_request);
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting() || params.with_usage,
+        None,
+    );
+
+    let response = do_histogram_points(
+        dispatcher.toc(&access, &pass),
+        &collection.name,
+        histogram_params,
+        shard_selection,
+        params.consistency,
+        access,
+        params.timeout(),
+        request_hw_counter.get_counter(),
+    )
+    .await;
+
+    process_response(response, timing, request_hw_counter.to_rest_api())
+}
+
 pub fn config_facet_api(cfg: &mut web::ServiceConfig) {
     cfg.service(facet);
+    cfg.service(facet_batch);
+    cfg.service(histogram);
 }
@@ -49,7 +49,7 @@ async fn get_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         path.collection.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -107,7 +107,7 @@ async fn scroll_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         path.collection.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -181,7 +181,7 @@ async fn count_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         path.collection.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -1,13 +1,14 @@
 use actix_web::{Responder, post, web};
 use actix_web_validator::{Json, Path, Query};
 use api::rest::models::InferenceUsage;
-use api::rest::{QueryGroupsRequest, QueryRequest, QueryRequestBatch, QueryResponse};
+use api::rest::{
+    ExplanationMatrixResponse, QueryGroupsRequest, QueryRequest, QueryRequestBatch, QueryResponse,
+};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use itertools::Itertools;
 use storage::content_manager::collection_verification::{
     check_strict_mode, check_strict_mode_batch,
 };
-use storage::content_manager::errors::StorageError;
 use storage::dispatcher::Dispatcher;
 use tokio::time::Instant;
 
@@ -15,13 +16,14 @@ use super::CollectionPath;
 use super::read_params::ReadParams;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, get_request_hardware_counter};
+use crate::common::explain::do_query_points_explain_matrix;
 use crate::common::inference::params::InferenceParams;
 use crate::common::inference::query_requests_rest::{
     CollectionQueryGroupsRequestWithUsage, CollectionQueryRequestWithUsage,
     convert_query_groups_request_from_rest, convert_query_request_from_rest,
 };
 use crate::common::inference::token::InferenceToken;
-use crate::common::query::do_query_point_groups;
+use crate::common::query::{do_query_batch_points, do_query_point_groups, do_query_points};
 use crate::settings::ServiceConfig;
 
 #[post("/collections/{name}/points/query")]
@@ -42,7 +44,7 @@ async fn query_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -71,26 +73,166 @@ async fn query_points(
         )
         .await?;
 
-        let points = dispatcher
+        let points = do_query_points(
+            dispatcher.toc(&access, &pass),
+            &collection.name,
+            request,
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+            hw_measurement_acc,
+        )
+        .await?
+        .into_iter()
+        .map(api::rest::ScoredPoint::from)
+        .collect_vec();
+
+        Ok(QueryResponse { points })
+    }
+    .await;
+
+    helpers::process_response_with_inference_usage(
+        result,
+        timing,
+        request_hw_counter.to_rest_api(),
+        inference_usage.into_non_empty(),
+    )
+}
+
+/// Same as `query_points`, but always explains the results, and returns the explanations
+/// as a matrix of results by top dimensions, e.g. to render a heatmap.
+#[post("/collections/{name}/points/query/explain-matrix")]
+async fn query_points_explain_matrix(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+    inference_token: InferenceToken,
+) -> impl Responder {
+    let QueryRequest {
+        internal: query_request,
+        shard_key,
+    } = request.into_inner();
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting() || params.with_usage,
+        None,
+    );
+    let timing = Instant::now();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+    let hw_measurement_acc = request_hw_counter.get_counter();
+    let mut inference_usage = InferenceUsage::default();
+
+    let inference_params = InferenceParams::new(inference_token, params.timeout());
+
+    let result = async {
+        let CollectionQueryRequestWithUsage { request, usage } =
+            convert_query_request_from_rest(query_request, &inference_params).await?;
+
+        inference_usage.merge_opt(usage);
+
+        let pass = check_strict_mode(
+            &request,
+            params.timeout_as_secs(),
+            &collection.name,
+            &dispatcher,
+            &access,
+        )
+        .await?;
+
+        let matrix = do_query_points_explain_matrix(
+            dispatcher.toc(&access, &pass),
+            &collection.name,
+            request,
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+            hw_measurement_acc,
+        )
+        .await?;
+
+        Ok(ExplanationMatrixResponse::from(matrix))
+    }
+    .await;
+
+    helpers::process_response_with_inference_usage(
+        result,
+        timing,
+        request_hw_counter.to_rest_api(),
+        inference_usage.into_non_empty(),
+    )
+}
+
+/// Describe how `query_points` would execute the request, without running it
+#[post("/collections/{name}/points/query/plan")]
+async fn query_points_plan(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+    inference_token: InferenceToken,
+) -> impl Responder {
+    let QueryRequest {
+        internal: query_request,
+        shard_key,
+    } = request.into_inner();
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting() || params.with_usage,
+        None,
+    );
+    let timing = Instant::now();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+    let hw_measurement_acc = request_hw_counter.get_counter();
+    let mut inference_usage = InferenceUsage::default();
+
+    let inference_params = InferenceParams::new(inference_token, params.timeout());
+
+    let result = async {
+        let CollectionQueryRequestWithUsage { request, usage } =
+            convert_query_request_from_rest(query_request, &inference_params).await?;
+
+        inference_usage.merge_opt(usage);
+
+        let pass = check_strict_mode(
+            &request,
+            params.timeout_as_secs(),
+            &collection.name,
+            &dispatcher,
+            &access,
+        )
+        .await?;
+
+        dispatcher
             .toc(&access, &pass)
-            .query_batch(
+            .query_plan(
                 &collection.name,
-                vec![(request, shard_selection)],
+                request,
+                shard_selection,
                 params.consistency,
                 access,
                 params.timeout(),
                 hw_measurement_acc,
             )
-            .await?
-            .pop()
-            .ok_or_else(|| {
-                StorageError::service_error("Expected at least one response for one query")
-            })?
-            .into_iter()
-            .map(api::rest::ScoredPoint::from)
-            .collect_vec();
-
-        Ok(QueryResponse { points })
+            .await
     }
     .await;
 
@@ -117,7 +259,7 @@ async fn query_points_batch(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -158,25 +300,24 @@ async fn query_points_batch(
         )
         .await?;
 
-        let res = dispatcher
-            .toc(&access, &pass)
-            .query_batch(
-                &collection.name,
-                batch,
-                params.consistency,
-                access,
-                params.timeout(),
-                hw_measurement_acc,
-            )
-            .await?
-            .into_iter()
-            .map(|response| QueryResponse {
-                points: response
-                    .into_iter()
-                    .map(api::rest::ScoredPoint::from)
-                    .collect_vec(),
-            })
-            .collect_vec();
+        let res = do_query_batch_points(
+            dispatcher.toc(&access, &pass),
+            &collection.name,
+            batch,
+            params.consistency,
+            access,
+            params.timeout(),
+            hw_measurement_acc,
+        )
+        .await?
+        .into_iter()
+        .map(|response| QueryResponse {
+            points: response
+                .into_iter()
+                .map(api::rest::ScoredPoint::from)
+                .collect_vec(),
+        })
+        .collect_vec();
         Ok(res)
     }
     .await;
@@ -207,7 +348,7 @@ async fn query_points_groups(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -260,6 +401,8 @@ async fn query_points_groups(
 
 pub fn config_query_api(cfg: &mut web::ServiceConfig) {
     cfg.service(query_points);
+    cfg.service(query_points_explain_matrix);
+    cfg.service(query_points_plan);
     cfg.service(query_points_batch);
     cfg.service(query_points_groups);
 }
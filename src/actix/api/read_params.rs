@@ -13,6 +13,10 @@ pub struct ReadParams {
     pub consistency: Option<ReadConsistency>,
     /// If set, overrides global timeout for this request. Unit is seconds.
     pub timeout: Option<NonZeroU64>,
+    /// Return hardware usage of this request in the response,
+    /// even if hardware reporting is disabled in the service config.
+    #[serde(default)]
+    pub with_usage: bool,
 }
 
 impl ReadParams {
@@ -77,6 +81,21 @@ mod test {
         }
     }
 
+    #[test]
+    fn deserialize_with_usage() {
+        test_str(
+            "with_usage=true",
+            ReadParams {
+                with_usage: true,
+                ..Default::default()
+            },
+        );
+        test_str(
+            &format!("{}&with_usage=false", str("all")),
+            from_type(ReadConsistencyType::All),
+        );
+    }
+
     #[test]
     fn try_deserialize_factor_0() {
         assert!(try_deserialize(&str("0")).is_err());
@@ -60,7 +60,7 @@ async fn recommend_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
 
@@ -146,7 +146,7 @@ async fn recommend_batch_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -211,7 +211,7 @@ async fn recommend_point_groups(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -1,15 +1,17 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
-use actix_web::{Responder, get, post, web};
+use actix_web::{HttpResponse, Responder, get, post, web};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{PointRequest, PointRequestInternal, ScrollRequest};
+use collection::operations::verification::new_unchecked_verification_pass;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
-use futures::TryFutureExt;
+use futures::{StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
 use segment::types::{PointIdType, WithPayloadInterface};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use shard::retrieve::record_internal::RecordInternal;
 use storage::content_manager::collection_verification::{
     check_strict_mode, check_strict_mode_timeout,
@@ -27,9 +29,19 @@ use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{
     get_request_hardware_counter, process_response, process_response_error,
 };
-use crate::common::query::do_get_points;
+use crate::common::export::{ExportParams, ExportRequest, do_export_points};
+use crate::common::query::{
+    DEFAULT_SCROLL_STREAM_BATCH_SIZE, do_get_points, do_get_points_multi, do_scroll_points_stream,
+};
 use crate::settings::ServiceConfig;
 
+#[derive(Deserialize, Validate)]
+struct ScrollStreamParams {
+    /// Number of points fetched per page. Default: 1000
+    #[validate(range(min = 1, max = 100_000))]
+    batch_size: Option<usize>,
+}
+
 #[derive(Deserialize, Validate)]
 struct PointPath {
     #[validate(length(min = 1))]
@@ -98,7 +110,7 @@ async fn get_point(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -123,6 +135,37 @@ async fn get_point(
     process_response(res, timing, request_hw_counter.to_rest_api())
 }
 
+#[get("/collections/{name}/points/{id}/history")]
+async fn get_point_history(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    point: Path<PointPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let Ok(point_id) = point.id.parse::<PointIdType>() else {
+        let err = StorageError::BadInput {
+            description: format!("Can not recognize \"{}\" as point id", point.id),
+        };
+        return process_response_error(err, Instant::now(), None);
+    };
+
+    // Point history is read from local data only, strict mode limits do not apply
+    let pass = new_unchecked_verification_pass();
+
+    let timing = Instant::now();
+    let res = dispatcher
+        .toc(&access, &pass)
+        .point_history(
+            &collection.name,
+            point_id,
+            ShardSelectorInternal::All,
+            access,
+        )
+        .await;
+
+    process_response(res, timing, None)
+}
+
 #[post("/collections/{name}/points")]
 async fn get_points(
     dispatcher: web::Data<Dispatcher>,
@@ -157,7 +200,7 @@ async fn get_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -183,6 +226,83 @@ async fn get_points(
     process_response(res, timing, request_hw_counter.to_rest_api())
 }
 
+#[derive(Deserialize, Validate)]
+struct MultiPointRequest {
+    /// Points to retrieve, by collection name
+    #[validate(length(min = 1))]
+    collections: HashMap<String, PointRequestInternal>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CollectionRecords {
+    Result(Vec<api::rest::Record>),
+    Error(String),
+}
+
+/// Retrieve points from multiple collections in a single call.
+/// Results are returned by collection name, failed collections report their own error.
+#[post("/points/multi")]
+async fn get_points_multi(
+    dispatcher: web::Data<Dispatcher>,
+    request: Json<MultiPointRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let MultiPointRequest { collections } = request.into_inner();
+
+    let mut pass = None;
+    let mut requests = HashMap::with_capacity(collections.len());
+    let mut response = HashMap::with_capacity(collections.len());
+    for (collection_name, point_request) in collections {
+        match check_strict_mode_timeout(
+            params.timeout_as_secs(),
+            &collection_name,
+            &dispatcher,
+            &access,
+        )
+        .await
+        {
+            Ok(collection_pass) => {
+                pass = Some(collection_pass);
+                requests.insert(collection_name, point_request);
+            }
+            Err(err) => {
+                response.insert(collection_name, CollectionRecords::Error(err.to_string()));
+            }
+        }
+    }
+
+    if let Some(pass) = pass {
+        let records = do_get_points_multi(
+            dispatcher.toc(&access, &pass),
+            requests,
+            params.consistency,
+            params.timeout(),
+            access.clone(),
+            // Usage is only accounted in collection metrics, it is not reported per collection
+            |collection_name| {
+                get_request_hardware_counter(&dispatcher, collection_name.to_string(), false, None)
+                    .get_counter()
+            },
+        )
+        .await;
+
+        response.extend(records.into_iter().map(|(collection_name, records)| {
+            let records = match records {
+                Ok(records) => CollectionRecords::Result(
+                    records.into_iter().map(api::rest::Record::from).collect(),
+                ),
+                Err(err) => CollectionRecords::Error(err.to_string()),
+            };
+            (collection_name, records)
+        }));
+    }
+
+    process_response(Ok::<_, StorageError>(response), timing, None)
+}
+
 #[post("/collections/{name}/points/scroll")]
 async fn scroll_points(
     dispatcher: web::Data<Dispatcher>,
@@ -218,7 +338,7 @@ async fn scroll_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -238,3 +358,139 @@ async fn scroll_points(
 
     process_response(res, timing, request_hw_counter.to_rest_api())
 }
+
+/// Same as `scroll_points`, but streams every matching point as server-sent events,
+/// fetching them page by page as the client consumes the response.
+/// The `limit` of the request bounds the total number of streamed points, if set.
+#[post("/collections/{name}/points/scroll/stream")]
+async fn scroll_points_stream(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ScrollRequest>,
+    params: Query<ReadParams>,
+    stream_params: Query<ScrollStreamParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let ScrollRequest {
+        scroll_request,
+        shard_key,
+    } = request.into_inner();
+
+    let pass = match check_strict_mode(
+        &scroll_request,
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now(), None),
+    };
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting() || params.with_usage,
+        None,
+    );
+    let timing = Instant::now();
+
+    let result = do_scroll_points_stream(
+        dispatcher.toc(&access, &pass).clone(),
+        collection.name.clone(),
+        scroll_request,
+        stream_params
+            .batch_size
+            .unwrap_or(DEFAULT_SCROLL_STREAM_BATCH_SIZE),
+        params.consistency,
+        params.timeout(),
+        shard_selection,
+        access,
+        request_hw_counter.get_counter(),
+    );
+
+    match result {
+        Ok(pages) => HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .streaming(
+                pages
+                    .map_ok(|page| futures::stream::iter(page.points.into_iter().map(Ok)))
+                    .try_flatten()
+                    .map(|record| {
+                        let record = record.map_err(actix_web::error::ErrorInternalServerError)?;
+                        serde_json::to_string(&record)
+                            .map(|json| web::Bytes::from(format!("data: {json}\n\n")))
+                            .map_err(actix_web::error::ErrorInternalServerError)
+                    }),
+            ),
+        Err(err) => process_response_error(err, timing, request_hw_counter.to_rest_api()),
+    }
+}
+
+/// Stream all points matching the filter as a JSON Lines or Parquet file,
+/// in the same format as accepted by the point import.
+#[post("/collections/{name}/points/export")]
+async fn export_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ExportRequest>,
+    params: Query<ReadParams>,
+    export_params: Query<ExportParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let request = request.into_inner();
+    let export_params = export_params.into_inner();
+
+    let pass = match check_strict_mode(
+        &request.scroll_request(),
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now(), None),
+    };
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting() || params.with_usage,
+        None,
+    );
+    let timing = Instant::now();
+
+    let result = do_export_points(
+        dispatcher.toc(&access, &pass).clone(),
+        collection.name.clone(),
+        request,
+        export_params,
+        params.consistency,
+        params.timeout(),
+        access,
+        request_hw_counter.get_counter(),
+    )
+    .await;
+
+    match result {
+        Ok(chunks) => HttpResponse::Ok()
+            .content_type(export_params.format.content_type())
+            .streaming(chunks.map(|chunk| {
+                chunk
+                    .map(web::Bytes::from)
+                    .map_err(actix_web::error::ErrorInternalServerError)
+            })),
+        Err(err) => process_response_error(err, timing, request_hw_counter.to_rest_api()),
+    }
+}
@@ -1,17 +1,26 @@
+use std::collections::HashMap;
+
 use actix_web::{HttpResponse, Responder, post, web};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::{SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest};
+use api::rest::{
+    SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest,
+    SearchRequestInternal,
+};
 use collection::collection::distance_matrix::CollectionSearchMatrixRequest;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
 };
+use collection::shards::shard::ShardId;
+use futures::StreamExt as _;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_verification::{
     check_strict_mode, check_strict_mode_batch,
 };
 use storage::dispatcher::Dispatcher;
 use tokio::time::Instant;
+use validator::Validate;
 
 use super::CollectionPath;
 use super::read_params::ReadParams;
@@ -19,9 +28,12 @@ use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{
     get_request_hardware_counter, process_response, process_response_error,
 };
+use crate::common::federated_search::do_search_points_federated;
 use crate::common::query::{
-    do_core_search_points, do_search_batch_points, do_search_point_groups, do_search_points_matrix,
+    do_core_search_points, do_core_search_points_stream, do_search_batch_points,
+    do_search_point_groups, do_search_points_matrix,
 };
+use crate::common::recall::{do_search_recall, ground_truth_params};
 use crate::settings::ServiceConfig;
 
 #[post("/collections/{name}/points/search")]
@@ -59,7 +71,7 @@ async fn search_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
 
@@ -86,6 +98,77 @@ async fn search_points(
     process_response(result, timing, request_hw_counter.to_rest_api())
 }
 
+/// Same as `search_points`, but always explains the results, and sends them one by one
+/// as server-sent events while explanations are computed.
+#[post("/collections/{name}/points/search/explain/stream")]
+async fn search_points_explain_stream(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let SearchRequest {
+        search_request,
+        shard_key,
+    } = request.into_inner();
+
+    let pass = match check_strict_mode(
+        &search_request,
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now(), None),
+    };
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting() || params.with_usage,
+        None,
+    );
+
+    let timing = Instant::now();
+
+    let mut core_request = CoreSearchRequest::from(search_request);
+    core_request.with_explanation = true;
+
+    let result = do_core_search_points_stream(
+        dispatcher.toc(&access, &pass),
+        &collection.name,
+        core_request,
+        params.consistency,
+        shard_selection,
+        access,
+        params.timeout(),
+        request_hw_counter.get_counter(),
+    )
+    .await;
+
+    match result {
+        Ok(points) => HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .streaming(points.map(|point| {
+                let point = point.map_err(actix_web::error::ErrorInternalServerError)?;
+                serde_json::to_string(&api::rest::ScoredPoint::from(point))
+                    .map(|json| web::Bytes::from(format!("data: {json}\n\n")))
+                    .map_err(actix_web::error::ErrorInternalServerError)
+            })),
+        Err(err) => process_response_error(err, timing, request_hw_counter.to_rest_api()),
+    }
+}
+
 #[post("/collections/{name}/points/search/batch")]
 async fn batch_search_points(
     dispatcher: web::Data<Dispatcher>,
@@ -95,9 +178,12 @@ async fn batch_search_points(
     service_config: web::Data<ServiceConfig>,
     ActixAccess(access): ActixAccess,
 ) -> HttpResponse {
-    let requests = request
-        .into_inner()
-        .searches
+    let SearchRequestBatch {
+        searches,
+        partial_ok,
+    } = request.into_inner();
+
+    let requests = searches
         .into_iter()
         .map(|req| {
             let SearchRequest {
@@ -130,7 +216,7 @@ async fn batch_search_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
 
@@ -144,10 +230,12 @@ async fn batch_search_points(
         access,
         params.timeout(),
         request_hw_counter.get_counter(),
+        partial_ok,
     )
     .await
-    .map(|batch_scored_points| {
-        batch_scored_points
+    .map(|found| {
+        let batches = found
+            .results
             .into_iter()
             .map(|scored_points| {
                 scored_points
@@ -155,12 +243,31 @@ async fn batch_search_points(
                     .map(api::rest::ScoredPoint::from)
                     .collect_vec()
             })
-            .collect_vec()
+            .collect_vec();
+        (batches, found.timed_out_shards)
     });
 
+    if !partial_ok {
+        let result = result.map(|(batches, _timed_out_shards)| batches);
+        return process_response(result, timing, request_hw_counter.to_rest_api());
+    }
+
+    let result = result.map(|(batches, timed_out_shards)| PartialSearchBatchResponse {
+        batches,
+        partial: !timed_out_shards.is_empty(),
+        timed_out_shards,
+    });
     process_response(result, timing, request_hw_counter.to_rest_api())
 }
 
+#[derive(Serialize)]
+struct PartialSearchBatchResponse {
+    batches: Vec<Vec<api::rest::ScoredPoint>>,
+    /// Whether some shards timed out, so their points are missing from the results
+    partial: bool,
+    timed_out_shards: Vec<ShardId>,
+}
+
 #[post("/collections/{name}/points/search/groups")]
 async fn search_point_groups(
     dispatcher: web::Data<Dispatcher>,
@@ -196,7 +303,7 @@ async fn search_point_groups(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -251,7 +358,7 @@ async fn search_points_matrix_pairs(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -307,7 +414,7 @@ async fn search_points_matrix_offsets(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.name.clone(),
-        service_config.hardware_reporting(),
+        service_config.hardware_reporting() || params.with_usage,
         None,
     );
     let timing = Instant::now();
@@ -328,11 +435,187 @@ async fn search_points_matrix_offsets(
     process_response(response, timing, request_hw_counter.to_rest_api())
 }
 
+#[derive(Deserialize, Validate)]
+struct FederatedSearchRequest {
+    /// Collections to search in, must use the same distance for the searched vector
+    #[validate(length(min = 1))]
+    collections: Vec<String>,
+    /// Maximum number of results taken from a collection, by collection name
+    #[serde(default)]
+    quotas: HashMap<String, usize>,
+    #[serde(flatten)]
+    #[validate(nested)]
+    search: SearchRequestInternal,
+}
+
+#[derive(Serialize)]
+struct FederatedScoredPoint {
+    /// Collection the point was found in
+    collection: String,
+    #[serde(flatten)]
+    point: api::rest::ScoredPoint,
+}
+
+/// Run the same search in multiple collections, and merge the results by score.
+#[post("/points/search/federated")]
+async fn search_points_federated(
+    dispatcher: web::Data<Dispatcher>,
+    request: Json<FederatedSearchRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let FederatedSearchRequest {
+        collections,
+        quotas,
+        search,
+    } = request.into_inner();
+
+    let mut pass = None;
+    for collection_name in &collections {
+        match check_strict_mode(
+            &search,
+            params.timeout_as_secs(),
+            collection_name,
+            &dispatcher,
+            &access,
+        )
+        .await
+        {
+            Ok(collection_pass) => pass = Some(collection_pass),
+            Err(err) => return process_response_error(err, Instant::now(), None),
+        }
+    }
+    let Some(pass) = pass else {
+        return process_response(Ok(Vec::<FederatedScoredPoint>::new()), Instant::now(), None);
+    };
+
+    let timing = Instant::now();
+
+    let result = do_search_points_federated(
+        dispatcher.toc(&access, &pass),
+        collections,
+        search.into(),
+        quotas,
+        params.consistency,
+        access.clone(),
+        params.timeout(),
+        // Usage is only accounted in collection metrics, it is not reported per collection
+        |collection_name| {
+            get_request_hardware_counter(&dispatcher, collection_name.to_string(), false, None)
+                .get_counter()
+        },
+    )
+    .await
+    .map(|found| {
+        found
+            .into_iter()
+            .map(|found| FederatedScoredPoint {
+                collection: found.collection_name,
+                point: api::rest::ScoredPoint::from(found.point),
+            })
+            .collect_vec()
+    });
+
+    process_response(result, timing, None)
+}
+
+#[derive(Serialize)]
+struct SearchRecallResponse {
+    /// Share of the exact results also found by the approximate search
+    recall: f32,
+    /// Ids of the exact results missed by the approximate search, best first
+    missed: Vec<segment::types::PointIdType>,
+    /// Results of the search using the index and quantization as configured
+    approximate: Vec<api::rest::ScoredPoint>,
+    /// Results of the exact kNN search
+    exact: Vec<api::rest::ScoredPoint>,
+}
+
+/// Run the search both via the index and as exact kNN, and report the recall of the index.
+/// Useful to tune `hnsw_ef` and quantization params.
+#[post("/collections/{name}/points/search/recall")]
+async fn search_points_recall(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let SearchRequest {
+        search_request,
+        shard_key,
+    } = request.into_inner();
+
+    // Exact search must be allowed by strict mode, as it is run as well
+    let exact_search_request = SearchRequestInternal {
+        params: Some(ground_truth_params(search_request.params)),
+        ..search_request.clone()
+    };
+    let pass = match check_strict_mode(
+        &exact_search_request,
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now(), None),
+    };
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting() || params.with_usage,
+        None,
+    );
+
+    let timing = Instant::now();
+
+    let result = do_search_recall(
+        dispatcher.toc(&access, &pass),
+        &collection.name,
+        search_request.into(),
+        params.consistency,
+        shard_selection,
+        access,
+        params.timeout(),
+        request_hw_counter.get_counter(),
+    )
+    .await
+    .map(|search_recall| SearchRecallResponse {
+        recall: search_recall.recall,
+        missed: search_recall.missed,
+        approximate: search_recall
+            .approximate
+            .into_iter()
+            .map(api::rest::ScoredPoint::from)
+            .collect(),
+        exact: search_recall
+            .exact
+            .into_iter()
+            .map(api::rest::ScoredPoint::from)
+            .collect(),
+    });
+
+    process_response(result, timing, request_hw_counter.to_rest_api())
+}
+
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
+        .service(search_points_explain_stream)
         .service(batch_search_points)
         .service(search_point_groups)
         .service(search_points_matrix_pairs)
-        .service(search_points_matrix_offsets);
+        .service(search_points_matrix_offsets)
+        .service(search_points_federated)
+        .service(search_points_recall);
 }
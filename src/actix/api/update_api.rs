@@ -1,15 +1,19 @@
 use actix_web::rt::time::Instant;
-use actix_web::{Responder, delete, post, put, web};
+use actix_web::{Responder, delete, get, post, put, web};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::UpdateVectors;
 use api::rest::schema::PointInsertOperations;
+use api::rest::{InsertPoints, UpdateVectors};
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
-use collection::operations::point_ops::PointsSelector;
+use collection::operations::point_ops::{PointsSelector, RestorePoints};
 use collection::operations::vector_ops::DeleteVectors;
+use collection::operations::verification::new_unchecked_verification_pass;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use segment::json_path::JsonPath;
 use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::import::{ImportFromUrlRequest, ImportTaskId};
 use storage::dispatcher::Dispatcher;
+use storage::rbac::AccessRequirements;
 use validator::Validate;
 
 use super::CollectionPath;
@@ -17,6 +21,9 @@ use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{
     get_request_hardware_counter, process_response, process_response_with_inference_usage,
 };
+use crate::common::import::{
+    ImportParams, do_import_points, do_import_points_from_url, receive_import_file,
+};
 use crate::common::inference::params::InferenceParams;
 use crate::common::inference::token::InferenceToken;
 use crate::common::strict_mode::*;
@@ -45,7 +52,7 @@ async fn upsert_points(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
 
     let timing = Instant::now();
@@ -76,6 +83,53 @@ async fn upsert_points(
     )
 }
 
+#[post("/collections/{name}/points/insert")]
+async fn insert_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<InsertPoints>,
+    params: Query<UpdateParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+    inference_token: InferenceToken,
+) -> impl Responder {
+    let request = request.into_inner();
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting(),
+        Some(params.ack().wait()),
+    );
+
+    let timing = Instant::now();
+    let inference_params = InferenceParams::new(inference_token, params.timeout);
+
+    let result_with_usage = do_insert_points(
+        StrictModeCheckedTocProvider::new(&dispatcher),
+        collection.into_inner().name,
+        request,
+        InternalUpdateParams::default(),
+        params.into_inner(),
+        access,
+        inference_params,
+        request_hw_counter.get_counter(),
+    )
+    .await;
+
+    let (res, inference_usage) = match result_with_usage {
+        Ok((insert_result, usage)) => (Ok(insert_result), usage),
+        Err(err) => (Err(err), None),
+    };
+
+    process_response_with_inference_usage(
+        res,
+        timing,
+        request_hw_counter.to_rest_api(),
+        inference_usage,
+    )
+}
+
 #[post("/collections/{name}/points/delete")]
 async fn delete_points(
     dispatcher: web::Data<Dispatcher>,
@@ -91,7 +145,7 @@ async fn delete_points(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
     let timing = Instant::now();
 
@@ -109,6 +163,39 @@ async fn delete_points(
     process_response(res, timing, request_hw_counter.to_rest_api())
 }
 
+#[post("/collections/{name}/points/restore")]
+async fn restore_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<RestorePoints>,
+    params: Query<UpdateParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let operation = operation.into_inner();
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting(),
+        Some(params.ack().wait()),
+    );
+    let timing = Instant::now();
+
+    let res = do_restore_points(
+        StrictModeCheckedTocProvider::new(&dispatcher),
+        collection.into_inner().name,
+        operation,
+        InternalUpdateParams::default(),
+        params.into_inner(),
+        access,
+        request_hw_counter.get_counter(),
+    )
+    .await;
+
+    process_response(res, timing, request_hw_counter.to_rest_api())
+}
+
 #[put("/collections/{name}/points/vectors")]
 async fn update_vectors(
     dispatcher: web::Data<Dispatcher>,
@@ -125,7 +212,7 @@ async fn update_vectors(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
     let timing = Instant::now();
 
@@ -171,7 +258,7 @@ async fn delete_vectors(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
     let timing = Instant::now();
 
@@ -204,7 +291,7 @@ async fn set_payload(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
     let timing = Instant::now();
 
@@ -237,7 +324,7 @@ async fn overwrite_payload(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
     let timing = Instant::now();
 
@@ -270,7 +357,7 @@ async fn delete_payload(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
     let timing = Instant::now();
 
@@ -303,7 +390,7 @@ async fn clear_payload(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
     let timing = Instant::now();
 
@@ -337,7 +424,7 @@ async fn update_batch(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
 
     let inference_params = InferenceParams::new(inference_token.clone(), params.timeout);
@@ -384,7 +471,7 @@ async fn create_field_index(
         &dispatcher,
         collection.name.clone(),
         service_config.hardware_reporting(),
-        Some(params.wait),
+        Some(params.ack().wait()),
     );
 
     let response = do_create_index(
@@ -427,6 +514,105 @@ async fn delete_field_index(
     process_response(response, timing, None)
 }
 
+#[put("/collections/{name}/points/import")]
+#[expect(clippy::too_many_arguments)]
+async fn import_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    body: web::Payload,
+    import_params: Query<ImportParams>,
+    params: Query<UpdateParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+    inference_token: InferenceToken,
+) -> impl Responder {
+    let timing = Instant::now();
+    let collection_name = collection.into_inner().name;
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection_name.clone(),
+        service_config.hardware_reporting(),
+        Some(params.ack().wait()),
+    );
+
+    let inference_params = InferenceParams::new(inference_token, params.timeout);
+
+    let result = async {
+        // Check access before accepting the file, every batch is checked again on upsert
+        access.check_collection_access(&collection_name, AccessRequirements::new().write())?;
+
+        let temp_dir = dispatcher
+            .toc(&access, &new_unchecked_verification_pass())
+            .optional_temp_or_storage_temp_path()?;
+        let file = receive_import_file(body, &temp_dir).await?;
+
+        do_import_points(
+            StrictModeCheckedTocProvider::new(&dispatcher),
+            collection_name,
+            file,
+            import_params.into_inner(),
+            params.into_inner(),
+            access,
+            inference_params,
+            request_hw_counter.get_counter(),
+        )
+        .await
+    }
+    .await;
+
+    process_response(result, timing, request_hw_counter.to_rest_api())
+}
+
+#[post("/collections/{name}/points/import")]
+async fn import_points_from_url(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ImportFromUrlRequest>,
+    ActixAccess(access): ActixAccess,
+    inference_token: InferenceToken,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    // Strict mode is checked for every imported batch separately
+    let toc = dispatcher
+        .toc(&access, &new_unchecked_verification_pass())
+        .clone();
+
+    let response = do_import_points_from_url(
+        toc,
+        collection.into_inner().name,
+        request.into_inner(),
+        access,
+        InferenceParams::new(inference_token, None),
+    )
+    .await;
+
+    process_response(response, timing, None)
+}
+
+#[get("/collections/{name}/points/import/{task_id}")]
+async fn get_import_task(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, ImportTaskId)>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let (collection_name, task_id) = path.into_inner();
+
+    let response = access
+        .check_collection_access(&collection_name, AccessRequirements::new())
+        .and_then(|_| {
+            dispatcher
+                .toc(&access, &new_unchecked_verification_pass())
+                .import_tasks()
+                .get(&collection_name, task_id)
+                .ok_or_else(|| StorageError::not_found(format!("Import task {task_id} not found")))
+        });
+
+    process_response(response, timing, None)
+}
+
 /// Request body for the staging test delay endpoint.
 /// Only available when the `staging` feature is enabled.
 #[cfg(feature = "staging")]
@@ -455,7 +641,6 @@ async fn staging_test_delay(
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     use collection::operations::point_ops::PointOperations;
-    use collection::operations::verification::new_unchecked_verification_pass;
     use shard::operations::CollectionUpdateOperations;
     use shard::operations::staging::TestDelayOperation;
 
@@ -489,7 +674,9 @@ async fn staging_test_delay(
 // Configure services
 pub fn config_update_api(cfg: &mut web::ServiceConfig) {
     cfg.service(upsert_points)
+        .service(insert_points)
         .service(delete_points)
+        .service(restore_points)
         .service(update_vectors)
         .service(delete_vectors)
         .service(set_payload)
@@ -498,7 +685,10 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(clear_payload)
         .service(create_field_index)
         .service(delete_field_index)
-        .service(update_batch);
+        .service(update_batch)
+        .service(import_points)
+        .service(import_points_from_url)
+        .service(get_import_task);
 
     #[cfg(feature = "staging")]
     cfg.service(staging_test_delay);
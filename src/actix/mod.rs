@@ -31,7 +31,10 @@ use crate::actix::api::local_shard_api::config_local_shard_api;
 use crate::actix::api::profiler_api::config_profiler_api;
 use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
-use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
+use crate::actix::api::retrieve_api::{
+    export_points, get_point, get_point_history, get_points, get_points_multi, scroll_points,
+    scroll_points_stream,
+};
 use crate::actix::api::search_api::config_search_api;
 use crate::actix::api::service_api::config_service_api;
 use crate::actix::api::shards_api::config_shards_api;
@@ -163,9 +166,13 @@ pub fn init(
                 // Ordering of services is important for correct path pattern matching
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
                 .service(scroll_points)
+                .service(scroll_points_stream)
+                .service(export_points)
                 .service(count_points)
+                .service(get_point_history)
                 .service(get_point)
-                .service(get_points);
+                .service(get_points)
+                .service(get_points_multi);
 
             if let Some(static_folder) = web_ui_available.as_deref() {
                 app = app.service(web_ui_factory(static_folder));
@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+
+use collection::operations::types::CoreSearchRequest;
+use collection::operations::universal_query::collection_query::CollectionQueryRequest;
+use itertools::Itertools as _;
+use segment::data_types::groups::GroupId;
+use segment::json_path::JsonPath;
+use segment::types::{
+    PayloadSelector, PayloadSelectorInclude, ScoredPoint, WithPayload, WithPayloadInterface,
+};
+
+/// Distinct requests fetch this many times more candidates than requested,
+/// as some of them share a value of the distinct field
+const DISTINCT_BY_OVERSAMPLING: usize = 4;
+
+/// Deduplicate the results of each request of a batch with its own [`DistinctBy`], if any
+pub(super) fn distinct_batch(distincts: Vec<Option<DistinctBy>>, results: &mut [Vec<ScoredPoint>]) {
+    for (distinct, points) in distincts.into_iter().zip(results) {
+        if let Some(distinct) = distinct {
+            *points = distinct.apply(std::mem::take(points));
+        }
+    }
+}
+
+/// Deduplication of the results of a request by a payload field,
+/// keeping the best scored point of each of its values, as `group_by` with a `group_size` of 1.
+///
+/// The request is widened to more candidates, as some of them share a value,
+/// so fewer than `limit` results are only returned when the candidates run out of distinct values.
+#[derive(Debug)]
+pub(super) struct DistinctBy {
+    key: JsonPath,
+    limit: usize,
+    offset: usize,
+    /// Payload requested by the client. The distinct field is fetched on top of it,
+    /// and is removed from the results if it wasn't requested.
+    with_payload: WithPayloadInterface,
+}
+
+impl DistinctBy {
+    /// Returns `None` if the request isn't distinct.
+    ///
+    /// Otherwise, widens the request and forces the distinct field into the payload of its results.
+    pub(super) fn prepare(request: &mut CoreSearchRequest) -> Option<Self> {
+        let key = request.distinct_by.take()?;
+        let with_payload = request
+            .with_payload
+            .clone()
+            .unwrap_or(WithPayloadInterface::Bool(false));
+        request.with_payload = Some(with_distinct_payload(&with_payload, &key));
+
+        let distinct = Self::new(key, request.limit, request.offset, with_payload);
+        request.limit = distinct.candidates_limit();
+        request.offset = 0;
+        Some(distinct)
+    }
+
+    /// Same as [`DistinctBy::prepare`], for universal query requests
+    pub(super) fn prepare_query(request: &mut CollectionQueryRequest) -> Option<Self> {
+        let key = request.distinct_by.take()?;
+        let with_payload = request.with_payload.clone();
+        request.with_payload = with_distinct_payload(&with_payload, &key);
+
+        let distinct = Self::new(key, request.limit, request.offset, with_payload);
+        request.limit = distinct.candidates_limit();
+        request.offset = 0;
+        Some(distinct)
+    }
+
+    fn new(key: JsonPath, limit: usize, offset: usize, with_payload: WithPayloadInterface) -> Self {
+        Self {
+            key,
+            limit,
+            offset,
+            with_payload,
+        }
+    }
+
+    fn candidates_limit(&self) -> usize {
+        (self.limit + self.offset).saturating_mul(DISTINCT_BY_OVERSAMPLING)
+    }
+
+    /// Keep the first point of each value of the distinct field, out of points sorted best first
+    pub(super) fn apply(&self, points: Vec<ScoredPoint>) -> Vec<ScoredPoint> {
+        let WithPayload {
+            enable,
+            payload_selector,
+        } = WithPayload::from(&self.with_payload);
+
+        let mut seen = HashSet::new();
+        points
+            .into_iter()
+            .filter(|point| {
+                // A point with several values represents all of its values not seen yet
+                let values = distinct_values(point, &self.key);
+                let is_new = values.iter().any(|value| !seen.contains(value));
+                seen.extend(values);
+                is_new
+            })
+            .skip(self.offset)
+            .take(self.limit)
+            .map(|mut point| {
+                if !enable {
+                    point.payload = None;
+                } else if let Some(selector) = &payload_selector {
+                    point.payload = point.payload.map(|payload| selector.process(payload));
+                }
+                point
+            })
+            .collect()
+    }
+}
+
+/// Payload to fetch for a distinct request: the requested one, and the distinct field
+fn with_distinct_payload(
+    with_payload: &WithPayloadInterface,
+    key: &JsonPath,
+) -> WithPayloadInterface {
+    let with_key = |fields: &[JsonPath]| {
+        WithPayloadInterface::Fields(fields.iter().chain([key]).unique().cloned().collect())
+    };
+    match with_payload {
+        WithPayloadInterface::Bool(true) => WithPayloadInterface::Bool(true),
+        WithPayloadInterface::Bool(false) => with_key(&[]),
+        WithPayloadInterface::Fields(fields) => with_key(fields),
+        WithPayloadInterface::Selector(PayloadSelector::Include(PayloadSelectorInclude {
+            include,
+        })) => with_key(include),
+        // Excluded fields may contain the distinct one
+        WithPayloadInterface::Selector(PayloadSelector::Exclude(_)) => {
+            WithPayloadInterface::Bool(true)
+        }
+        WithPayloadInterface::Selector(PayloadSelector::Transform(transform)) => {
+            with_key(&transform.source_keys())
+        }
+    }
+}
+
+/// Values of the distinct field of a point, each value of an array counting on its own
+fn distinct_values(point: &ScoredPoint, key: &JsonPath) -> Vec<GroupId> {
+    point
+        .payload
+        .iter()
+        .flat_map(|payload| payload.get_value(key))
+        .flat_map(|value| match value {
+            serde_json::Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        })
+        .filter_map(|value| GroupId::try_from(value).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::types::ExplanationParams;
+    use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+    use segment::payload_json;
+    use segment::types::{Payload, WithVector};
+
+    use super::*;
+
+    fn scored_point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: None,
+        }
+    }
+
+    #[test]
+    fn test_distinct_by_keeps_best_point_per_value() {
+        let key: JsonPath = "brand".parse().unwrap();
+        let point = |id: u64, score: f32, payload: Payload| ScoredPoint {
+            payload: Some(payload),
+            ..scored_point(id, score)
+        };
+        let points = vec![
+            point(1, 0.9, payload_json! {"brand": "a", "name": "one"}),
+            point(2, 0.8, payload_json! {"brand": "a", "name": "two"}),
+            point(3, 0.7, payload_json! {"brand": ["a", "b"], "name": "three"}),
+            point(4, 0.6, payload_json! {"name": "four"}),
+            point(5, 0.5, payload_json! {"brand": 7, "name": "five"}),
+            point(6, 0.4, payload_json! {"brand": ["b", 7], "name": "six"}),
+        ];
+
+        let mut request = CollectionQueryRequest {
+            prefetch: vec![],
+            query: None,
+            using: DEFAULT_VECTOR_NAME.to_owned(),
+            filter: None,
+            score_threshold: None,
+            limit: 2,
+            offset: 1,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Fields(vec!["name".parse().unwrap()]),
+            lookup_from: None,
+            with_explanation: false,
+            explanation_params: ExplanationParams::default(),
+            distinct_by: Some(key.clone()),
+        };
+        let distinct = DistinctBy::prepare_query(&mut request).unwrap();
+
+        // Candidates are searched in place of the requested page, with the distinct field
+        assert_eq!(request.limit, 3 * DISTINCT_BY_OVERSAMPLING);
+        assert_eq!(request.offset, 0);
+        assert_eq!(request.distinct_by, None);
+        assert_eq!(
+            request.with_payload,
+            WithPayloadInterface::Fields(vec!["name".parse().unwrap(), key.clone()]),
+        );
+
+        // Distinct points are 1, 3 (for "b"), and 5, the page skips the first one
+        let results = distinct.apply(points.clone());
+        assert_eq!(
+            results.iter().map(|point| point.id).collect_vec(),
+            vec![3.into(), 5.into()],
+        );
+        // Only the requested payload is returned
+        assert_eq!(results[0].payload, Some(payload_json! {"name": "three"}));
+
+        let distinct = DistinctBy::new(key, 10, 0, WithPayloadInterface::Bool(false));
+        let results = distinct.apply(points);
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|point| point.payload.is_none()));
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::CoreSearchRequest;
+use collection::operations::universal_query::collection_query::CollectionQueryRequest;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::types::{ExplanationVerbosity, ScoreExplanation};
+use itertools::Itertools as _;
+use segment::explain::{DEFAULT_TOP_DIMENSIONS, aggregate_absolute_explanations};
+use segment::types::{PointIdType, ScoreType, ScoredPoint};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+use crate::common::query::{do_core_search_points, do_query_points};
+
+/// Search, and aggregate the explanations of all results into the mean absolute contribution
+/// of each dimension, showing which dimensions drive the whole result page.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_explain_results(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: CoreSearchRequest,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<ScoreExplanation, StorageError> {
+    request.with_explanation = true;
+    request.explanation_verbosity = ExplanationVerbosity::Full;
+    let top_n = request.explanation_params.top_n(DEFAULT_TOP_DIMENSIONS);
+
+    let results = do_core_search_points(
+        toc,
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+        timeout,
+        hw_measurement_acc,
+    )
+    .await?;
+
+    Ok(aggregate_absolute_explanations(&results, top_n))
+}
+
+/// Explanations of query results as a dense matrix,
+/// with one row per result and one column per explained dimension.
+#[derive(Clone, Debug)]
+pub struct ExplanationMatrix {
+    pub ids: Vec<PointIdType>,
+    pub scores: Vec<ScoreType>,
+    /// Dimensions which contributed most over all results, most important first
+    pub dimensions: Vec<usize>,
+    /// `contributions[row][column]` is the contribution of `dimensions[column]`
+    /// to the score of `ids[row]`, 0 if the result has no explanation
+    pub contributions: Vec<Vec<ScoreType>>,
+}
+
+impl ExplanationMatrix {
+    /// Matrix of the `top_n` dimensions with the largest average absolute contribution,
+    /// see [`aggregate_absolute_explanations`].
+    ///
+    /// The explanations of `points` are expected to contain all dimensions,
+    /// so that every cell of the matrix is known.
+    fn new(points: &[ScoredPoint], top_n: usize) -> Self {
+        let dimensions = aggregate_absolute_explanations(points, top_n)
+            .top_dimensions
+            .into_iter()
+            .map(|dim| dim.dimension)
+            .collect_vec();
+
+        let contributions = points
+            .iter()
+            .map(|point| {
+                let by_dimension: HashMap<usize, ScoreType> = point
+                    .score_explanation
+                    .iter()
+                    .flat_map(|explanation| &explanation.top_dimensions)
+                    .map(|dim| (dim.dimension, dim.contribution))
+                    .collect();
+                dimensions
+                    .iter()
+                    .map(|dimension| by_dimension.get(dimension).copied().unwrap_or(0.0))
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            ids: points.iter().map(|point| point.id).collect(),
+            scores: points.iter().map(|point| point.score).collect(),
+            dimensions,
+            contributions,
+        }
+    }
+}
+
+impl From<ExplanationMatrix> for api::rest::ExplanationMatrixResponse {
+    fn from(matrix: ExplanationMatrix) -> Self {
+        let ExplanationMatrix {
+            ids,
+            scores,
+            dimensions,
+            contributions,
+        } = matrix;
+        Self {
+            ids,
+            scores,
+            dimensions,
+            contributions,
+        }
+    }
+}
+
+/// Query points, and explain the results as an [`ExplanationMatrix`]
+/// of the dimensions which contributed most over all of them.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_query_points_explain_matrix(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: CollectionQueryRequest,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<ExplanationMatrix, StorageError> {
+    let top_n = request.explanation_params.top_n(DEFAULT_TOP_DIMENSIONS);
+    request.with_explanation = true;
+    // Columns are chosen over all results, so each row needs the contributions of every dimension
+    request.explanation_params.include_all = true;
+
+    let points = do_query_points(
+        toc,
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+        timeout,
+        hw_measurement_acc,
+    )
+    .await?;
+
+    Ok(ExplanationMatrix::new(&points, top_n))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::types::DimensionContribution;
+
+    use super::*;
+    use crate::common::explain::scored_point;
+
+    #[test]
+    fn test_explanation_matrix() {
+        let explained = |id: u64, contributions: &[ScoreType]| ScoredPoint {
+            score_explanation: Some(ScoreExplanation::new(
+                contributions
+                    .iter()
+                    .enumerate()
+                    .map(|(dimension, &contribution)| DimensionContribution {
+                        dimension,
+                        contribution,
+                        label: None,
+                    })
+                    .collect(),
+                contributions.len(),
+            )),
+            ..scored_point(id, contributions.iter().sum(), Vec::new())
+        };
+        let points = vec![
+            explained(1, &[0.1, 0.8, -0.6]),
+            explained(2, &[0.2, 0.4, -0.4]),
+            scored_point(3, 0.1, Vec::new()),
+        ];
+
+        let matrix = ExplanationMatrix::new(&points, 2);
+        assert_eq!(matrix.ids, vec![1.into(), 2.into(), 3.into()]);
+        assert_eq!(matrix.dimensions, vec![1, 2]);
+        assert_eq!(
+            matrix.contributions,
+            vec![vec![0.8, -0.6], vec![0.4, -0.4], vec![0.0, 0.0]],
+        );
+    }
+}
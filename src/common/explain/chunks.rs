@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use futures::stream::BoxStream;
+use futures::{StreamExt as _, TryStreamExt as _};
+use itertools::Itertools as _;
+use segment::types::{PointIdType, ScoredPoint};
+use shard::retrieve::record_internal::RecordInternal;
+use storage::content_manager::errors::StorageError;
+
+use super::explainer::SearchExplainer;
+
+/// Above this many results, vectors used for explanations are fetched in chunks of this size
+pub(crate) const EXPLANATION_FETCH_CHUNK_SIZE: usize = 100;
+
+/// Explain `points` fetching their vectors `chunk_size` at a time,
+/// so that the vectors of at most one chunk are held in memory.
+///
+/// Points without the explained vector are explained as [`UnavailableReason::VectorMissing`](common::types::UnavailableReason::VectorMissing).
+pub(crate) async fn explain_in_chunks<F, Fut>(
+    explainer: &SearchExplainer,
+    points: &mut [ScoredPoint],
+    chunk_size: usize,
+    mut fetch_vectors: F,
+) -> Result<(), StorageError>
+where
+    F: FnMut(Vec<PointIdType>) -> Fut,
+    Fut: Future<Output = Result<Vec<RecordInternal>, StorageError>>,
+{
+    for chunk in points.chunks_mut(chunk_size) {
+        let ids = chunk.iter().map(|point| point.id).collect();
+        let mut vectors: HashMap<_, _> = fetch_vectors(ids)
+            .await?
+            .into_iter()
+            .filter_map(|record| Some((record.id, record.vector?)))
+            .collect();
+
+        for point in chunk {
+            point.vector = vectors.remove(&point.id);
+            explainer.explain(point);
+        }
+    }
+
+    Ok(())
+}
+
+/// Explain `points` one chunk at a time, only fetching the vectors of the next chunk
+/// once the previous one is consumed
+pub(crate) fn explained_points_stream<F, Fut>(
+    points: Vec<ScoredPoint>,
+    explainer: SearchExplainer,
+    fetch_vectors: F,
+) -> BoxStream<'static, Result<ScoredPoint, StorageError>>
+where
+    F: FnMut(Vec<PointIdType>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<Vec<RecordInternal>, StorageError>> + Send,
+{
+    let chunks: Vec<Vec<ScoredPoint>> = points
+        .into_iter()
+        .chunks(EXPLANATION_FETCH_CHUNK_SIZE)
+        .into_iter()
+        .map(Iterator::collect)
+        .collect();
+
+    futures::stream::try_unfold(
+        (chunks.into_iter(), explainer, fetch_vectors),
+        |(mut chunks, explainer, mut fetch_vectors)| async move {
+            let Some(mut chunk) = chunks.next() else {
+                return Ok(None);
+            };
+            let chunk_size = chunk.len();
+            explain_in_chunks(&explainer, &mut chunk, chunk_size, &mut fetch_vectors).await?;
+            Ok(Some((chunk, (chunks, explainer, fetch_vectors))))
+        },
+    )
+    .map_ok(|chunk| futures::stream::iter(chunk).map(Ok))
+    .try_flatten()
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use common::types::{ExplanationMode, ExplanationStatus, ExplanationVerbosity};
+    use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, DenseVector, VectorStructInternal};
+    use segment::explain::DEFAULT_TOP_DIMENSIONS;
+    use segment::types::{Distance, WithVector};
+
+    use super::*;
+    use crate::common::explain::explainer::ExplainedVector;
+    use crate::common::explain::scored_point;
+
+    #[tokio::test]
+    async fn test_explained_points_stream() {
+        let num_points = 250;
+        let vectors: HashMap<PointIdType, DenseVector> = (0..num_points)
+            .map(|id| (id.into(), vec![1.0, id as f32, 0.5]))
+            .collect();
+
+        // Search results come without vectors
+        let points: Vec<_> = (0..num_points)
+            .map(|id| ScoredPoint {
+                vector: None,
+                ..scored_point(id, (num_points - id) as f32, vec![])
+            })
+            .collect();
+
+        let explainer = SearchExplainer {
+            query_vector: Ok(ExplainedVector::Dense(vec![1.0, 1.0, 2.0])),
+            vector_name: DEFAULT_VECTOR_NAME.to_owned(),
+            distance: Distance::Dot,
+            approximate_search: false,
+            with_vector: WithVector::Bool(false),
+            with_bundle: false,
+            verbosity: ExplanationVerbosity::default(),
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            mode: ExplanationMode::default(),
+            chunked_fetch: Some(DEFAULT_VECTOR_NAME.to_owned()),
+            centroid: None,
+            recommend_examples: None,
+            dimension_labels: HashMap::new(),
+        };
+
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let fetch_vectors = {
+            let fetches = fetches.clone();
+            move |ids: Vec<PointIdType>| {
+                fetches.fetch_add(1, Ordering::Relaxed);
+                let records = ids
+                    .into_iter()
+                    .map(|id| RecordInternal {
+                        id,
+                        payload: None,
+                        vector: Some(VectorStructInternal::Single(vectors[&id].clone())),
+                        shard_key: None,
+                        order_value: None,
+                        then_by_values: vec![],
+                    })
+                    .collect();
+                futures::future::ready(Ok(records))
+            }
+        };
+        let fetched = || fetches.load(Ordering::Relaxed);
+
+        let mut stream = explained_points_stream(points, explainer, fetch_vectors);
+        assert_eq!(fetched(), 0);
+
+        // Vectors are fetched chunk by chunk, as the stream is consumed
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.id, 0.into());
+        assert_eq!(fetched(), 1);
+
+        let mut streamed = vec![first];
+        while let Some(point) = stream.next().await {
+            streamed.push(point.unwrap());
+        }
+        assert_eq!(fetched(), 3);
+
+        let ids: Vec<_> = streamed.iter().map(|point| point.id).collect();
+        let expected_ids: Vec<PointIdType> = (0..num_points).map(PointIdType::from).collect();
+        assert_eq!(ids, expected_ids);
+
+        for point in &streamed {
+            let explanation = point.score_explanation.as_ref().unwrap();
+            assert_eq!(explanation.status, ExplanationStatus::Available);
+            assert!(point.vector.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_explain_in_chunks_bounds_fetched_vectors() {
+        let num_points = 1_000;
+        let chunk_size = 100;
+
+        let explainer = SearchExplainer {
+            query_vector: Ok(ExplainedVector::Dense(vec![1.0, 2.0])),
+            vector_name: DEFAULT_VECTOR_NAME.to_owned(),
+            distance: Distance::Dot,
+            approximate_search: false,
+            with_vector: WithVector::Bool(false),
+            with_bundle: false,
+            verbosity: ExplanationVerbosity::default(),
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            mode: ExplanationMode::default(),
+            chunked_fetch: Some("".to_owned()),
+            centroid: None,
+            recommend_examples: None,
+            dimension_labels: HashMap::new(),
+        };
+
+        // Search results come without vectors
+        let mut points: Vec<_> = (0..num_points)
+            .map(|id| ScoredPoint {
+                vector: None,
+                ..scored_point(id, 1.0, vec![])
+            })
+            .collect();
+
+        // Number of vectors returned by each fetch, all of them are released before the next one
+        let mut fetched = Vec::new();
+        let fetch_vectors = |ids: Vec<PointIdType>| {
+            fetched.push(ids.len());
+            let records = ids
+                .into_iter()
+                .map(|id| RecordInternal {
+                    id,
+                    payload: None,
+                    vector: Some(VectorStructInternal::Single(vec![1.0, 1.0])),
+                    shard_key: None,
+                    order_value: None,
+                    then_by_values: vec![],
+                })
+                .collect();
+            futures::future::ready(Ok(records))
+        };
+
+        explain_in_chunks(&explainer, &mut points, chunk_size, fetch_vectors)
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.len(), num_points as usize / chunk_size);
+        assert!(fetched.iter().all(|&count| count <= chunk_size));
+
+        for point in &points {
+            let explanation = point.score_explanation.as_ref().unwrap();
+            assert_eq!(explanation.status, ExplanationStatus::Available);
+            assert_eq!(explanation.total_score, 3.0);
+            assert!(point.vector.is_none());
+        }
+
+        // Failed fetches are reported, instead of explaining points as missing their vector
+        let result = explain_in_chunks(&explainer, &mut points, chunk_size, |_| {
+            futures::future::ready(Err(StorageError::service_error("Fetch failed")))
+        })
+        .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use segment::types::{Distance, VectorName, VectorNameBuf};
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+
+/// Distance metrics of explained vectors, looked up once per collection and vector name.
+///
+/// Keyed by collection, so that a batch spanning several collections
+/// explains each of its requests with the metric of its own collection.
+#[derive(Debug, Default)]
+pub(crate) struct ExplanationDistances {
+    distances: HashMap<(String, VectorNameBuf), Distance>,
+}
+
+impl ExplanationDistances {
+    pub(crate) async fn distance(
+        &mut self,
+        toc: &TableOfContent,
+        collection_name: &str,
+        vector_name: &VectorName,
+        access: &Access,
+    ) -> Distance {
+        self.get_or_lookup(collection_name, vector_name, || {
+            get_collection_distance(toc, collection_name, vector_name, access)
+        })
+        .await
+    }
+
+    /// Falls back to [`Distance::Cosine`] if `lookup` fails, without caching the fallback
+    async fn get_or_lookup<F, Fut>(
+        &mut self,
+        collection_name: &str,
+        vector_name: &VectorName,
+        lookup: F,
+    ) -> Distance
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<Distance>>,
+    {
+        let key = (collection_name.to_owned(), vector_name.to_owned());
+        if let Some(distance) = self.distances.get(&key) {
+            return *distance;
+        }
+
+        match lookup().await {
+            Some(distance) => {
+                self.distances.insert(key, distance);
+                distance
+            }
+            None => Distance::Cosine,
+        }
+    }
+}
+
+/// Get the distance metric of a vector from the collection config
+async fn get_collection_distance(
+    toc: &TableOfContent,
+    collection_name: &str,
+    vector_name: &VectorName,
+    access: &Access,
+) -> Option<Distance> {
+    let collection_pass = access
+        .check_collection_access(collection_name, AccessRequirements::new())
+        .ok()?;
+    let collection = toc.get_collection(&collection_pass).await.ok()?;
+    collection.vector_distance(vector_name).await.ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::types::ExplanationStatus;
+    use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, VectorInternal, VectorStructInternal};
+    use segment::explain::compute_explanation;
+
+    use super::*;
+    use crate::common::explain::explainer::{ExplainedVector, SearchExplainer, explain_batch};
+    use crate::common::explain::scored_point;
+
+    #[tokio::test]
+    async fn test_batch_across_collections_uses_distance_per_collection() {
+        let collection_distances =
+            HashMap::from([("dot", Distance::Dot), ("euclid", Distance::Euclid)]);
+        let mut lookups = Vec::new();
+
+        let query = vec![1.0, 2.0, 0.0];
+        let stored = vec![0.5, 1.0, 3.0];
+
+        let mut distances = ExplanationDistances::default();
+        let mut explained = Vec::new();
+        for collection_name in ["dot", "euclid", "dot", "euclid"] {
+            let distance = distances
+                .get_or_lookup(collection_name, "", || {
+                    lookups.push(collection_name);
+                    futures::future::ready(collection_distances.get(collection_name).copied())
+                })
+                .await;
+            let explainer = SearchExplainer::new(
+                distance,
+                DEFAULT_VECTOR_NAME,
+                Ok(ExplainedVector::Dense(query.clone())),
+                false,
+                false.into(),
+            );
+
+            let mut results = vec![vec![scored_point(1, 1.0, stored.clone())]];
+            explain_batch(&[Some(explainer)], &mut results);
+            explained.push((collection_name, results.remove(0).remove(0)));
+        }
+
+        // Each collection is looked up once, and its distance is not reused for the other one
+        assert_eq!(lookups, vec!["dot", "euclid"]);
+
+        for (collection_name, point) in explained {
+            let distance = collection_distances[collection_name];
+            let expected = compute_explanation(distance, &query, &stored, None);
+            let explanation = point.score_explanation.unwrap();
+            assert_eq!(explanation.top_dimensions, expected.top_dimensions);
+            assert_eq!(explanation.total_score, expected.total_score);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_distance_lookup_failure_is_not_cached() {
+        let mut distances = ExplanationDistances::default();
+        let mut lookups = 0;
+
+        // A failed lookup falls back to cosine, but is retried on the next request
+        for expected in [Distance::Cosine, Distance::Dot, Distance::Dot] {
+            let distance = distances
+                .get_or_lookup("collection", "", || {
+                    lookups += 1;
+                    futures::future::ready((lookups > 1).then_some(Distance::Dot))
+                })
+                .await;
+            assert_eq!(distance, expected);
+        }
+
+        assert_eq!(lookups, 2);
+    }
+
+    #[tokio::test]
+    async fn test_explain_query_using_named_vector() {
+        let vector_distances =
+            HashMap::from([("text", Distance::Dot), ("image", Distance::Euclid)]);
+
+        let query = vec![1.0, 0.0, 2.0];
+        let text = vec![3.0, 1.0];
+        let image = vec![0.5, 1.0, 1.0];
+
+        let mut point = scored_point(1, 1.0, vec![]);
+        point.vector = Some(VectorStructInternal::Named(HashMap::from([
+            ("text".to_owned(), VectorInternal::Dense(text)),
+            ("image".to_owned(), VectorInternal::Dense(image.clone())),
+        ])));
+
+        // The distance is looked up for the vector in `using`, not the collection's first one
+        let mut distances = ExplanationDistances::default();
+        let distance = distances
+            .get_or_lookup("collection", "image", || {
+                futures::future::ready(vector_distances.get("image").copied())
+            })
+            .await;
+        assert_eq!(distance, Distance::Euclid);
+
+        let explainer = SearchExplainer::new(
+            distance,
+            "image",
+            Ok(ExplainedVector::Dense(query.clone())),
+            false,
+            false.into(),
+        );
+        explainer.explain(&mut point);
+
+        let expected = compute_explanation(Distance::Euclid, &query, &image, None);
+        let explanation = point.score_explanation.unwrap();
+        assert_eq!(explanation.status, ExplanationStatus::Available);
+        assert_eq!(explanation.vector_name.as_deref(), Some("image"));
+        assert_eq!(explanation.top_dimensions, expected.top_dimensions);
+        assert_eq!(explanation.total_score, expected.total_score);
+    }
+}
@@ -0,0 +1,1228 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use api::rest::SearchGroupsRequestInternal;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::*;
+use collection::operations::universal_query::collection_query::*;
+use common::types::{
+    DimensionContribution, ExplanationBundle, ExplanationMode, ExplanationVerbosity,
+    ScoreExplanation, UnavailableReason,
+};
+use segment::data_types::vectors::{
+    DEFAULT_VECTOR_NAME, DenseVector, NamedQuery, NamedVectorStruct, VectorInternal,
+    VectorStructInternal,
+};
+use segment::explain::{
+    DEFAULT_TOP_DIMENSIONS, ExplanationConfig, SparseVector, compute_baseline_explanation,
+    compute_counterfactual_explanation, compute_explanation, compute_sparse_explanation,
+    label_dimensions, stored_vectors_space,
+};
+use segment::types::{
+    Distance, ScoredPoint, SearchParams, VectorName, VectorNameBuf, WithPayloadInterface,
+    WithVector,
+};
+use shard::query::query_enum::QueryEnum;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+
+use super::chunks::EXPLANATION_FETCH_CHUNK_SIZE;
+use super::distances::ExplanationDistances;
+use super::recommend::{ExampleWeights, RecommendExamples, resolve_recommend_examples};
+
+/// Vectors to fetch for an explained request: the requested ones, and the explained one
+pub(crate) fn with_explained_vector(
+    with_vector: &WithVector,
+    vector_name: &VectorName,
+) -> WithVector {
+    with_vector.merge(&WithVector::from(vector_name.to_owned()))
+}
+
+/// State required to explain the results of a single search request
+#[derive(Clone, Debug)]
+pub(crate) struct SearchExplainer {
+    /// Vector to explain the results against, or why the request can't be explained
+    pub(super) query_vector: Result<ExplainedVector, UnavailableReason>,
+    /// Named vector searched by the request, results are explained with their vector of this name
+    pub(super) vector_name: VectorNameBuf,
+    pub(super) distance: Distance,
+    /// Whether points were found approximately, if their segment didn't report how it searched them.
+    /// Segments of remote shards, and results of rescoring or fusion, don't report it.
+    pub(super) approximate_search: bool,
+    /// Vectors requested by the client. The explained vector is fetched on top of them
+    /// to compute explanations, and is removed from the results if it wasn't requested.
+    pub(super) with_vector: WithVector,
+    /// Attach the vectors used to compute each explanation
+    pub(super) with_bundle: bool,
+    pub(super) verbosity: ExplanationVerbosity,
+    /// Number of top contributing dimensions to keep
+    pub(super) top_n: usize,
+    pub(super) mode: ExplanationMode,
+    /// Vector to fetch in chunks after the search, instead of forcing vectors into its results
+    pub(crate) chunked_fetch: Option<VectorNameBuf>,
+    /// Centroid of the collection, to also explain dense results against, see [`compute_baseline_explanation`]
+    pub(super) centroid: Option<DenseVector>,
+    /// Examples of a recommend query, to tell which of them weighed the most on each dimension
+    pub(super) recommend_examples: Option<RecommendExamples>,
+    /// Labels of the dimensions of the explained vector, from the collection metadata
+    pub(super) dimension_labels: HashMap<usize, String>,
+}
+
+impl SearchExplainer {
+    /// Returns `None` if the request doesn't ask for explanations.
+    ///
+    /// Otherwise, forces vectors into the results of the request, as explanations are computed from them.
+    pub(crate) async fn prepare(
+        toc: &TableOfContent,
+        collection_name: &str,
+        request: &mut CoreSearchRequest,
+        access: &Access,
+        distances: &mut ExplanationDistances,
+    ) -> Option<Self> {
+        if !request.with_explanation {
+            return None;
+        }
+
+        let with_vector = request
+            .with_vector
+            .clone()
+            .unwrap_or(WithVector::Bool(false));
+        request.with_vector = Some(with_explained_vector(
+            &with_vector,
+            request.query.get_vector_name(),
+        ));
+
+        let query_vector =
+            extract_query_vector(&request.query).ok_or(UnavailableReason::UnsupportedQuery);
+        let distance = distances
+            .distance(
+                toc,
+                collection_name,
+                request.query.get_vector_name(),
+                access,
+            )
+            .await;
+
+        let mut explainer = Self::new(
+            distance,
+            request.query.get_vector_name(),
+            query_vector,
+            !request.params.is_some_and(|params| params.exact),
+            with_vector,
+        );
+        explainer.with_bundle = request.with_explanation_bundle;
+        explainer.verbosity = request.explanation_verbosity;
+        explainer.top_n = request.explanation_params.top_n(DEFAULT_TOP_DIMENSIONS);
+        explainer.mode = request.explanation_params.mode;
+        if request.explanation_params.compare_quantized {
+            compare_quantized_explanations(&mut request.params);
+        }
+        explain_search_path(&mut request.params);
+        explainer.dimension_labels = get_dimension_labels(
+            toc,
+            collection_name,
+            request.query.get_vector_name(),
+            access,
+        )
+        .await;
+
+        Some(explainer)
+    }
+
+    /// Don't force vectors into the results of a request with many results,
+    /// as holding all of them at once can spike memory.
+    /// They are fetched in chunks after the search instead, see [`explain_in_chunks`](super::explain_in_chunks).
+    pub(crate) fn fetch_vectors_in_chunks(&mut self, request: &mut CoreSearchRequest) {
+        if request.limit + request.offset > EXPLANATION_FETCH_CHUNK_SIZE {
+            self.fetch_vectors_after_search(request);
+        }
+    }
+
+    /// Don't return the explained vector from the search, unless vectors were requested,
+    /// to fetch it in chunks after the search instead
+    pub(crate) fn fetch_vectors_after_search(&mut self, request: &mut CoreSearchRequest) {
+        if self.with_vector.is_enabled() {
+            return;
+        }
+        request.with_vector = Some(WithVector::Bool(false));
+        self.chunked_fetch = Some(request.query.get_vector_name().to_owned());
+    }
+
+    /// Same as [`SearchExplainer::prepare`], for universal query requests
+    pub(crate) async fn prepare_query(
+        toc: &TableOfContent,
+        collection_name: &str,
+        request: &mut CollectionQueryRequest,
+        access: &Access,
+        shard_selection: &ShardSelectorInternal,
+        distances: &mut ExplanationDistances,
+    ) -> Option<Self> {
+        if !request.with_explanation {
+            return None;
+        }
+
+        let with_vector = request.with_vector.clone();
+        request.with_vector = with_explained_vector(&with_vector, &request.using);
+
+        let mut recommend_examples = None;
+        let query_vector = match &request.query {
+            Some(Query::Vector(VectorQuery::RecommendAverageVector(reco))) => {
+                // Recommend requests don't carry example weights, all examples weigh the same
+                let examples = resolve_recommend_examples(
+                    toc,
+                    collection_name,
+                    reco,
+                    &ExampleWeights::default(),
+                    &request.using,
+                    request.lookup_from.as_ref(),
+                    access,
+                    shard_selection,
+                )
+                .await;
+                examples.and_then(|examples| {
+                    let query_vector = examples.query_vector();
+                    recommend_examples = Some(examples);
+                    query_vector
+                })
+            }
+            _ => extract_universal_query_vector(request),
+        };
+        let distance = distances
+            .distance(toc, collection_name, &request.using, access)
+            .await;
+
+        let mut explainer = Self::new(
+            distance,
+            &request.using,
+            query_vector,
+            !request.params.is_some_and(|params| params.exact),
+            with_vector,
+        );
+        explainer.recommend_examples = recommend_examples;
+        explainer.top_n = request.explanation_params.top_n(DEFAULT_TOP_DIMENSIONS);
+        explainer.mode = request.explanation_params.mode;
+        if request.explanation_params.compare_quantized {
+            compare_quantized_explanations(&mut request.params);
+        }
+        explain_search_path(&mut request.params);
+        explainer.dimension_labels =
+            get_dimension_labels(toc, collection_name, &request.using, access).await;
+
+        Some(explainer)
+    }
+
+    /// Same as [`SearchExplainer::prepare_query`], for universal query groups requests
+    pub(crate) async fn prepare_query_groups(
+        toc: &TableOfContent,
+        collection_name: &str,
+        request: &mut CollectionQueryGroupsRequest,
+        access: &Access,
+        shard_selection: &ShardSelectorInternal,
+    ) -> Option<Self> {
+        if !request.with_explanation {
+            return None;
+        }
+
+        // Group hits are scored by the same query as the hits of a plain query request
+        let mut query_request = CollectionQueryRequest {
+            prefetch: request.prefetch.clone(),
+            query: request.query.clone(),
+            using: request.using.clone(),
+            filter: None,
+            score_threshold: None,
+            limit: request.limit,
+            offset: 0,
+            params: request.params,
+            with_vector: request.with_vector.clone(),
+            with_payload: WithPayloadInterface::Bool(false),
+            lookup_from: request.lookup_from.clone(),
+            with_explanation: true,
+            explanation_params: request.explanation_params,
+            distinct_by: None,
+        };
+        let explainer = Self::prepare_query(
+            toc,
+            collection_name,
+            &mut query_request,
+            access,
+            shard_selection,
+            &mut ExplanationDistances::default(),
+        )
+        .await;
+        request.with_vector = query_request.with_vector;
+        request.params = query_request.params;
+
+        explainer
+    }
+
+    /// Same as [`SearchExplainer::prepare`], for search groups requests
+    pub(crate) async fn prepare_groups(
+        toc: &TableOfContent,
+        collection_name: &str,
+        request: &mut SearchGroupsRequestInternal,
+        access: &Access,
+    ) -> Option<Self> {
+        if !request.with_explanation.unwrap_or(false) {
+            return None;
+        }
+
+        let query = QueryEnum::Nearest(NamedQuery::from(NamedVectorStruct::from(
+            request.vector.clone(),
+        )));
+        let with_vector = request
+            .with_vector
+            .clone()
+            .unwrap_or(WithVector::Bool(false));
+        request.with_vector = Some(with_explained_vector(&with_vector, query.get_vector_name()));
+        let query_vector = extract_query_vector(&query).ok_or(UnavailableReason::UnsupportedQuery);
+        let distance = ExplanationDistances::default()
+            .distance(toc, collection_name, query.get_vector_name(), access)
+            .await;
+
+        let mut explainer = Self::new(
+            distance,
+            query.get_vector_name(),
+            query_vector,
+            !request.params.is_some_and(|params| params.exact),
+            with_vector,
+        );
+        explain_search_path(&mut request.params);
+        explainer.dimension_labels =
+            get_dimension_labels(toc, collection_name, query.get_vector_name(), access).await;
+
+        Some(explainer)
+    }
+
+    pub(super) fn new(
+        distance: Distance,
+        vector_name: &VectorName,
+        query_vector: Result<ExplainedVector, UnavailableReason>,
+        approximate_search: bool,
+        with_vector: WithVector,
+    ) -> Self {
+        Self {
+            query_vector,
+            vector_name: vector_name.to_owned(),
+            distance,
+            approximate_search,
+            with_vector,
+            with_bundle: false,
+            verbosity: ExplanationVerbosity::default(),
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            mode: ExplanationMode::default(),
+            chunked_fetch: None,
+            centroid: None,
+            recommend_examples: None,
+            dimension_labels: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn explain(&self, point: &mut ScoredPoint) {
+        let result_vec = match (&self.query_vector, &point.vector) {
+            (Err(reason), _) => Err(*reason),
+            // Vectors are forced into the results, but may still fail to be fetched
+            (Ok(_), None) => Err(UnavailableReason::VectorMissing),
+            (Ok(query_vec), Some(vector_struct)) => {
+                ExplainedVector::from_struct(vector_struct, &self.vector_name, query_vec)
+                    .ok_or(UnavailableReason::UnsupportedQuery)
+            }
+        };
+        let mut explanation = self.explain_vector(result_vec);
+        // Segments only explain points to report how they searched them, and their quantization error
+        if let Some(segment_explanation) = point.score_explanation.take() {
+            explanation.approximate_search = segment_explanation.approximate_search;
+            explanation.quantization_error =
+                top_dimension_errors(&explanation, &segment_explanation.quantization_error);
+        }
+        point.score_explanation = Some(explanation);
+
+        if self.strips_explained_vector() {
+            VectorStructInternal::take_opt(&mut point.vector, &self.vector_name);
+        }
+    }
+
+    /// The explained vector was only fetched to compute explanations, and must not be returned
+    fn strips_explained_vector(&self) -> bool {
+        match &self.with_vector {
+            WithVector::Bool(requested) => !requested,
+            WithVector::Selector(names) => !names.contains(&self.vector_name),
+        }
+    }
+
+    /// Same as [`VectorStructInternal::take_opt`], on the REST output of the vectors of a point
+    fn strip_output_vector(&self, vector: &mut Option<api::rest::VectorStructOutput>) {
+        if !self.strips_explained_vector() {
+            return;
+        }
+        *vector = vector.take().and_then(|vector| match vector {
+            api::rest::VectorStructOutput::Single(_)
+            | api::rest::VectorStructOutput::MultiDense(_) => {
+                (self.vector_name != DEFAULT_VECTOR_NAME).then_some(vector)
+            }
+            api::rest::VectorStructOutput::Named(mut vectors) => {
+                vectors.remove(&self.vector_name);
+                (!vectors.is_empty()).then_some(api::rest::VectorStructOutput::Named(vectors))
+            }
+        });
+    }
+
+    pub(super) fn explain_record(&self, record: &mut api::rest::Record) -> ScoreExplanation {
+        self.explain_output(&mut record.vector)
+    }
+
+    /// Don't explain a point, but still strip its vector if it was only fetched for explanation
+    pub(super) fn skip_output(
+        &self,
+        vector: &mut Option<api::rest::VectorStructOutput>,
+        reason: UnavailableReason,
+    ) -> ScoreExplanation {
+        self.strip_output_vector(vector);
+        ScoreExplanation {
+            verbosity: self.verbosity,
+            ..ScoreExplanation::unavailable(reason)
+        }
+    }
+
+    /// Explain a point from the vector of its REST output
+    pub(super) fn explain_output(
+        &self,
+        vector: &mut Option<api::rest::VectorStructOutput>,
+    ) -> ScoreExplanation {
+        let result_vec = match (&self.query_vector, &*vector) {
+            (Err(reason), _) => Err(*reason),
+            (Ok(_), None) => Err(UnavailableReason::VectorMissing),
+            (Ok(query_vec), Some(vector_struct)) => {
+                ExplainedVector::from_output(vector_struct, &self.vector_name, query_vec)
+                    .ok_or(UnavailableReason::UnsupportedQuery)
+            }
+        };
+        let explanation = self.explain_vector(result_vec);
+
+        self.strip_output_vector(vector);
+
+        explanation
+    }
+
+    pub(super) fn explain_vector(
+        &self,
+        result_vec: Result<ExplainedVector, UnavailableReason>,
+    ) -> ScoreExplanation {
+        let mut explanation = ScoreExplanation {
+            verbosity: self.verbosity,
+            vector_name: Some(self.vector_name.clone()),
+            ..self.compute_vector_explanation(result_vec)
+        };
+        if !self.dimension_labels.is_empty() {
+            label_dimensions(&mut explanation, &self.dimension_labels);
+        }
+        explanation
+    }
+
+    fn compute_vector_explanation(
+        &self,
+        result_vec: Result<ExplainedVector, UnavailableReason>,
+    ) -> ScoreExplanation {
+        let query_vec = match &self.query_vector {
+            Ok(query_vec) => query_vec,
+            Err(reason) => return ScoreExplanation::unavailable(*reason),
+        };
+        let result_vec = match result_vec {
+            Ok(result_vec) => result_vec,
+            Err(reason) => return ScoreExplanation::unavailable(reason),
+        };
+
+        let mut explanation = match (query_vec, &result_vec) {
+            (ExplainedVector::Dense(query_vec), ExplainedVector::Dense(result_vec)) => {
+                let mut explanation = compute_explanation_for_distance(
+                    query_vec,
+                    result_vec,
+                    self.distance,
+                    self.top_n,
+                    self.mode,
+                );
+                explanation.space = stored_vectors_space(self.distance);
+                if self.with_bundle {
+                    explanation.bundle = Some(ExplanationBundle {
+                        query: query_vec.clone(),
+                        stored: result_vec.clone(),
+                    });
+                }
+                if let Some(centroid) = &self.centroid {
+                    explanation.baseline_explanation = compute_baseline_explanation(
+                        result_vec,
+                        centroid,
+                        &ExplanationConfig::default(),
+                    )
+                    .ok()
+                    .map(Box::new);
+                }
+                if let Some(examples) = &self.recommend_examples {
+                    let dimensions: Vec<_> = explanation
+                        .top_dimensions
+                        .iter()
+                        .map(|dim| dim.dimension)
+                        .collect();
+                    explanation.dominant_examples =
+                        examples.dominant_examples(&dimensions).unwrap_or_default();
+                }
+                explanation
+            }
+            (ExplainedVector::Sparse(query_vec), ExplainedVector::Sparse(result_vec)) => {
+                compute_sparse_explanation(query_vec, result_vec, Some(self.top_n))
+            }
+            (ExplainedVector::Dense(_), ExplainedVector::Sparse(_))
+            | (ExplainedVector::Sparse(_), ExplainedVector::Dense(_)) => {
+                return ScoreExplanation::unavailable(UnavailableReason::UnsupportedQuery);
+            }
+        };
+        explanation.approximate_search = self.approximate_search;
+        explanation
+    }
+}
+
+/// Kinds of vectors explanations can be computed for
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum ExplainedVector {
+    Dense(DenseVector),
+    Sparse(SparseVector),
+}
+
+impl ExplainedVector {
+    pub(super) fn from_internal(vector: &VectorInternal) -> Option<Self> {
+        match vector {
+            VectorInternal::Dense(dense) => Some(Self::Dense(dense.clone())),
+            VectorInternal::Sparse(sparse) => Some(Self::Sparse(sparse.clone())),
+            VectorInternal::MultiDense(_) => None, // Multi-dense not supported yet
+        }
+    }
+
+    fn from_vector_output(vector: &api::rest::VectorOutput) -> Option<Self> {
+        match vector {
+            api::rest::VectorOutput::Dense(dense) => Some(Self::Dense(dense.clone())),
+            api::rest::VectorOutput::Sparse(sparse) => Some(Self::Sparse(sparse.clone())),
+            api::rest::VectorOutput::MultiDense(_) => None,
+        }
+    }
+
+    fn is_same_kind(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Dense(_), Self::Dense(_)) | (Self::Sparse(_), Self::Sparse(_))
+        )
+    }
+
+    /// Extract the stored vector named `vector_name`, if it is of the same kind as the query
+    fn from_struct(
+        vector_struct: &VectorStructInternal,
+        vector_name: &VectorName,
+        query: &Self,
+    ) -> Option<Self> {
+        match vector_struct {
+            // Single is already a DenseVector
+            VectorStructInternal::Single(dense) => Some(Self::Dense(dense.clone())),
+            VectorStructInternal::MultiDense(_) => None, // Multi-dense not supported yet
+            VectorStructInternal::Named(named_map) => named_map
+                .get(vector_name)
+                .and_then(Self::from_internal)
+                .filter(|vector| vector.is_same_kind(query)),
+        }
+    }
+
+    /// Same as [`ExplainedVector::from_struct`], for REST vector outputs
+    fn from_output(
+        vector_struct: &api::rest::VectorStructOutput,
+        vector_name: &VectorName,
+        query: &Self,
+    ) -> Option<Self> {
+        match vector_struct {
+            api::rest::VectorStructOutput::Single(dense) => Some(Self::Dense(dense.clone())),
+            api::rest::VectorStructOutput::MultiDense(_) => None,
+            api::rest::VectorStructOutput::Named(named_map) => named_map
+                .get(vector_name)
+                .and_then(Self::from_vector_output)
+                .filter(|vector| vector.is_same_kind(query)),
+        }
+    }
+}
+
+/// Extract the vector to explain the results of a universal query against
+fn extract_universal_query_vector(
+    request: &CollectionQueryRequest,
+) -> Result<ExplainedVector, UnavailableReason> {
+    match &request.query {
+        Some(Query::Vector(VectorQuery::Nearest(VectorInputInternal::Vector(vector)))) => {
+            ExplainedVector::from_internal(vector).ok_or(UnavailableReason::UnsupportedQuery)
+        }
+        // Points are ordered by payload, but a nearest prefetch may still have selected them by vector
+        Some(Query::OrderBy(_)) => request
+            .prefetch
+            .iter()
+            .find_map(extract_prefetch_query_vector)
+            .ok_or(UnavailableReason::OrderByQuery),
+        _ => Err(UnavailableReason::UnsupportedQuery),
+    }
+}
+
+fn extract_prefetch_query_vector(prefetch: &CollectionPrefetch) -> Option<ExplainedVector> {
+    match &prefetch.query {
+        Some(Query::Vector(VectorQuery::Nearest(VectorInputInternal::Vector(vector)))) => {
+            ExplainedVector::from_internal(vector)
+        }
+        _ => None,
+    }
+}
+
+/// Extract the query vector from a QueryEnum (for Nearest queries with dense or sparse vectors)
+fn extract_query_vector(query: &QueryEnum) -> Option<ExplainedVector> {
+    match query {
+        QueryEnum::Nearest(named_query) => ExplainedVector::from_internal(&named_query.query),
+        _ => None, // Only Nearest queries have vectors
+    }
+}
+
+/// Ask segments to explain the results of a quantized search,
+/// so that the error of quantized vectors can be reported, see [`ScoreExplanation::quantization_error`]
+fn compare_quantized_explanations(params: &mut Option<SearchParams>) {
+    params
+        .get_or_insert_default()
+        .quantization
+        .get_or_insert_default()
+        .compare_explanations = true;
+}
+
+/// Let segments report whether they searched the results approximately,
+/// instead of guessing it from the params of the request, see [`ScoreExplanation::approximate_search`]
+fn explain_search_path(params: &mut Option<SearchParams>) {
+    params.get_or_insert_default().explain_search_path = true;
+}
+
+/// Quantization `errors` of the top dimensions of `explanation`, in the same order
+fn top_dimension_errors(
+    explanation: &ScoreExplanation,
+    errors: &[DimensionContribution],
+) -> Vec<DimensionContribution> {
+    explanation
+        .top_dimensions
+        .iter()
+        .filter_map(|dim| {
+            errors
+                .iter()
+                .find(|error| error.dimension == dim.dimension)
+                .cloned()
+        })
+        .collect()
+}
+
+/// Get the labels of the dimensions of a vector from the collection metadata
+pub(super) async fn get_dimension_labels(
+    toc: &TableOfContent,
+    collection_name: &str,
+    vector_name: &VectorName,
+    access: &Access,
+) -> HashMap<usize, String> {
+    let Ok(collection_pass) =
+        access.check_collection_access(collection_name, AccessRequirements::new())
+    else {
+        return HashMap::new();
+    };
+    match toc.get_collection(&collection_pass).await {
+        Ok(collection) => collection.dimension_labels(vector_name).await,
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Compute explanation based on the distance metric
+fn compute_explanation_for_distance(
+    query: &[f32],
+    result: &[f32],
+    distance: Distance,
+    top_n: usize,
+    mode: ExplanationMode,
+) -> ScoreExplanation {
+    match mode {
+        ExplanationMode::Contribution => compute_explanation(distance, query, result, Some(top_n)),
+        ExplanationMode::Counterfactual => {
+            compute_counterfactual_explanation(distance, query, result, Some(top_n))
+        }
+    }
+}
+
+/// Explain the results of each request of a batch with its own explainer, if any
+pub(crate) fn explain_batch(
+    explainers: &[Option<SearchExplainer>],
+    results: &mut [Vec<ScoredPoint>],
+) {
+    for (explainer, points) in explainers.iter().zip(results) {
+        let Some(explainer) = explainer else {
+            continue;
+        };
+        for point in points {
+            explainer.explain(point);
+        }
+    }
+}
+
+/// Explain every member of every group, each against its own vector.
+///
+/// Explanations must not push the request over its `deadline`: once it is reached,
+/// members of the remaining groups are reported as unavailable instead.
+pub(crate) fn explain_groups(
+    explainer: &SearchExplainer,
+    result: &mut GroupsResult,
+    deadline: Option<Instant>,
+) {
+    for group in &mut result.groups {
+        let timed_out = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        for hit in &mut group.hits {
+            let explanation = if timed_out {
+                explainer.skip_output(&mut hit.vector, UnavailableReason::Timeout)
+            } else {
+                explainer.explain_output(&mut hit.vector)
+            };
+            hit.score_explanation = Some(explanation.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use api::rest::{ExplanationStatusOutput, ScoreExplanationOutput, UnavailableReasonOutput};
+    use common::types::{ExplanationParams, ExplanationStatus, MAX_EXPLANATION_TOP_N};
+    use itertools::Itertools as _;
+
+    use super::*;
+    use crate::common::explain::scored_point;
+
+    #[test]
+    fn test_explain_keeps_selected_vectors() {
+        let mut explainer = SearchExplainer {
+            query_vector: Ok(ExplainedVector::Dense(vec![1.0, 1.0, 2.0])),
+            vector_name: "image".to_owned(),
+            distance: Distance::Dot,
+            approximate_search: false,
+            with_vector: WithVector::Selector(vec!["text".to_owned()]),
+            with_bundle: false,
+            verbosity: ExplanationVerbosity::default(),
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            mode: ExplanationMode::default(),
+            chunked_fetch: None,
+            centroid: None,
+            recommend_examples: None,
+            dimension_labels: HashMap::new(),
+        };
+        let named_point = || ScoredPoint {
+            vector: Some(VectorStructInternal::Named(
+                [
+                    ("image".into(), VectorInternal::Dense(vec![1.0, 0.0, 1.0])),
+                    ("text".into(), VectorInternal::Dense(vec![0.5, 0.5])),
+                ]
+                .into(),
+            )),
+            ..scored_point(1, 3.0, Vec::new())
+        };
+
+        // The explained vector was only fetched for the explanation
+        let mut point = named_point();
+        explainer.explain(&mut point);
+        assert_eq!(
+            point.score_explanation.unwrap().status,
+            ExplanationStatus::Available,
+        );
+        let Some(VectorStructInternal::Named(vectors)) = point.vector else {
+            panic!("Selected vectors should be kept");
+        };
+        assert_eq!(vectors.keys().collect_vec(), vec!["text"]);
+
+        // The explained vector was selected as well
+        explainer.with_vector = WithVector::Selector(vec!["text".to_owned(), "image".to_owned()]);
+        let mut point = named_point();
+        explainer.explain(&mut point);
+        let Some(VectorStructInternal::Named(vectors)) = point.vector else {
+            panic!("Selected vectors should be kept");
+        };
+        assert_eq!(vectors.len(), 2);
+    }
+
+    #[test]
+    fn test_explain_point_without_vector() {
+        let explainer = SearchExplainer {
+            query_vector: Ok(ExplainedVector::Dense(vec![1.0, 1.0, 2.0])),
+            vector_name: DEFAULT_VECTOR_NAME.to_owned(),
+            distance: Distance::Dot,
+            approximate_search: false,
+            with_vector: WithVector::Bool(true),
+            with_bundle: false,
+            verbosity: ExplanationVerbosity::default(),
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            mode: ExplanationMode::default(),
+            chunked_fetch: None,
+            centroid: None,
+            recommend_examples: None,
+            dimension_labels: HashMap::new(),
+        };
+
+        let mut with_vector = scored_point(1, 3.0, vec![1.0, 0.0, 1.0]);
+        let mut without_vector = scored_point(2, 2.0, vec![0.0, 1.0, 0.5]);
+        without_vector.vector = None;
+
+        explainer.explain(&mut with_vector);
+        explainer.explain(&mut without_vector);
+
+        let explanation = with_vector.score_explanation.unwrap();
+        assert_eq!(explanation.status, ExplanationStatus::Available);
+        assert!(!explanation.top_dimensions.is_empty());
+
+        let explanation = without_vector.score_explanation.unwrap();
+        assert_eq!(
+            explanation.status,
+            ExplanationStatus::Unavailable(UnavailableReason::VectorMissing),
+        );
+        assert!(explanation.top_dimensions.is_empty());
+    }
+
+    #[test]
+    fn test_explanation_bundle_reproduces_explanation() {
+        let query = vec![0.2, 0.1, 0.9, 0.7];
+        let explainer = SearchExplainer {
+            query_vector: Ok(ExplainedVector::Dense(query.clone())),
+            vector_name: DEFAULT_VECTOR_NAME.to_owned(),
+            distance: Distance::Euclid,
+            approximate_search: false,
+            with_vector: WithVector::Bool(false),
+            with_bundle: true,
+            verbosity: ExplanationVerbosity::default(),
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            mode: ExplanationMode::default(),
+            chunked_fetch: None,
+            centroid: None,
+            recommend_examples: None,
+            dimension_labels: HashMap::new(),
+        };
+
+        let mut point = scored_point(1, 1.0, vec![0.05, 0.61, 0.76, 0.74]);
+        explainer.explain(&mut point);
+
+        let explanation = point.score_explanation.unwrap();
+        let bundle = explanation.bundle.unwrap();
+        assert_eq!(bundle.query, query);
+        assert_eq!(bundle.stored, vec![0.05, 0.61, 0.76, 0.74]);
+
+        let reproduced = compute_explanation(Distance::Euclid, &bundle.query, &bundle.stored, None);
+        assert_eq!(explanation.top_dimensions, reproduced.top_dimensions);
+
+        // Bundle is opt-in, due to its size
+        let explainer = SearchExplainer {
+            with_bundle: false,
+            ..explainer
+        };
+        let mut point = scored_point(1, 1.0, vec![0.05, 0.61, 0.76, 0.74]);
+        explainer.explain(&mut point);
+        assert!(point.score_explanation.unwrap().bundle.is_none());
+    }
+
+    #[test]
+    fn test_explain_groups_explains_every_member() {
+        let explainer = SearchExplainer {
+            query_vector: Ok(ExplainedVector::Dense(vec![1.0, 2.0, 0.0])),
+            vector_name: DEFAULT_VECTOR_NAME.to_owned(),
+            distance: Distance::Dot,
+            approximate_search: false,
+            with_vector: WithVector::Bool(false),
+            with_bundle: false,
+            verbosity: ExplanationVerbosity::default(),
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            mode: ExplanationMode::default(),
+            chunked_fetch: None,
+            centroid: None,
+            recommend_examples: None,
+            dimension_labels: HashMap::new(),
+        };
+
+        let hit = |id: u64, vector: DenseVector| api::rest::ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score: 0.0,
+            payload: None,
+            vector: Some(api::rest::VectorStructOutput::Single(vector)),
+            shard_key: None,
+            order_value: None,
+            score_explanation: None,
+        };
+
+        let members = [
+            vec![3.0, 0.0, 1.0],
+            vec![0.0, 1.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+        ];
+        let mut result = GroupsResult {
+            groups: vec![PointGroup {
+                hits: members
+                    .iter()
+                    .enumerate()
+                    .map(|(id, vector)| hit(id as u64, vector.clone()))
+                    .collect(),
+                id: "group".into(),
+                lookup: None,
+                aggregations: None,
+            }],
+        };
+
+        explain_groups(&explainer, &mut result, None);
+
+        let hits = &result.groups[0].hits;
+        assert_eq!(hits.len(), 3);
+        for (hit, vector) in hits.iter().zip(&members) {
+            let Some(ScoreExplanationOutput::Full(explanation)) = &hit.score_explanation else {
+                panic!("expected a full explanation");
+            };
+            let expected = compute_explanation(Distance::Dot, &[1.0, 2.0, 0.0], vector, None);
+            let dimensions: Vec<_> = explanation
+                .top_dimensions
+                .iter()
+                .map(|dim| (dim.dimension, dim.contribution))
+                .collect();
+            let expected: Vec<_> = expected
+                .top_dimensions
+                .iter()
+                .map(|dim| (dim.dimension, dim.contribution))
+                .collect();
+            assert_eq!(dimensions, expected);
+            assert!(hit.vector.is_none());
+        }
+    }
+
+    #[test]
+    fn test_explanation_top_n_from_params() {
+        let params = |top_n, include_all| ExplanationParams {
+            top_n,
+            include_all,
+            ..Default::default()
+        };
+        assert_eq!(params(None, false).top_n(DEFAULT_TOP_DIMENSIONS), 10);
+        assert_eq!(params(Some(2), false).top_n(DEFAULT_TOP_DIMENSIONS), 2);
+        assert_eq!(params(Some(2), true).top_n(DEFAULT_TOP_DIMENSIONS), 1024);
+        // Never more than the upper bound, even if the request bypassed validation
+        assert_eq!(
+            params(Some(5000), false).top_n(DEFAULT_TOP_DIMENSIONS),
+            1024
+        );
+
+        let mut explainer = SearchExplainer::new(
+            Distance::Dot,
+            DEFAULT_VECTOR_NAME,
+            Ok(ExplainedVector::Dense(vec![1.0; 2000])),
+            false,
+            false.into(),
+        );
+
+        explainer.top_n = params(Some(2), false).top_n(DEFAULT_TOP_DIMENSIONS);
+        let mut point = scored_point(1, 2000.0, vec![1.0; 2000]);
+        explainer.explain(&mut point);
+        let explanation = point.score_explanation.unwrap();
+        assert_eq!(explanation.top_dimensions.len(), 2);
+        assert_eq!(explanation.total_score, 2000.0);
+
+        explainer.top_n = params(None, true).top_n(DEFAULT_TOP_DIMENSIONS);
+        let mut point = scored_point(1, 2000.0, vec![1.0; 2000]);
+        explainer.explain(&mut point);
+        let explanation = point.score_explanation.unwrap();
+        assert_eq!(explanation.top_dimensions.len(), MAX_EXPLANATION_TOP_N);
+    }
+
+    #[test]
+    fn test_explain_with_dimension_labels() {
+        let mut explainer = SearchExplainer::new(
+            Distance::Dot,
+            DEFAULT_VECTOR_NAME,
+            Ok(ExplainedVector::Dense(vec![1.0, 2.0, 3.0])),
+            false,
+            false.into(),
+        );
+        explainer.dimension_labels = HashMap::from([(2, "blue".to_string())]);
+
+        let mut point = scored_point(1, 6.0, vec![1.0, 1.0, 1.0]);
+        explainer.explain(&mut point);
+        let explanation = point.score_explanation.unwrap();
+
+        // Only labelled dimensions get a label
+        let labels: Vec<_> = explanation
+            .top_dimensions
+            .iter()
+            .map(|dim| (dim.dimension, dim.label.as_deref()))
+            .collect();
+        assert_eq!(labels, vec![(2, Some("blue")), (1, None), (0, None)]);
+    }
+
+    #[test]
+    fn test_explain_groups_respects_deadline() {
+        let explainer = SearchExplainer {
+            query_vector: Ok(ExplainedVector::Dense(vec![1.0, 2.0])),
+            vector_name: DEFAULT_VECTOR_NAME.to_owned(),
+            distance: Distance::Dot,
+            approximate_search: false,
+            with_vector: WithVector::Bool(false),
+            with_bundle: false,
+            verbosity: ExplanationVerbosity::default(),
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            mode: ExplanationMode::default(),
+            chunked_fetch: None,
+            centroid: None,
+            recommend_examples: None,
+            dimension_labels: HashMap::new(),
+        };
+
+        let groups_result = || GroupsResult {
+            groups: (0..100)
+                .map(|group_id: u64| PointGroup {
+                    hits: vec![api::rest::ScoredPoint {
+                        id: group_id.into(),
+                        version: 0,
+                        score: 0.0,
+                        payload: None,
+                        vector: Some(api::rest::VectorStructOutput::Single(vec![1.0, 1.0])),
+                        shard_key: None,
+                        order_value: None,
+                        score_explanation: None,
+                    }],
+                    id: group_id.into(),
+                    lookup: None,
+                    aggregations: None,
+                })
+                .collect(),
+        };
+        let statuses = |result: &GroupsResult| -> Vec<_> {
+            result
+                .groups
+                .iter()
+                .flat_map(|group| &group.hits)
+                .map(|hit| match hit.score_explanation.as_ref().unwrap() {
+                    ScoreExplanationOutput::Full(explanation) => explanation.status,
+                    ScoreExplanationOutput::Minimal(_) => panic!("expected a full explanation"),
+                })
+                .collect()
+        };
+
+        // Deadline already reached: all groups are still returned, without explanations
+        let mut result = groups_result();
+        explain_groups(&explainer, &mut result, Some(Instant::now()));
+        assert_eq!(result.groups.len(), 100);
+        assert!(statuses(&result).iter().all(|status| {
+            *status == ExplanationStatusOutput::Unavailable(UnavailableReasonOutput::Timeout)
+        }));
+        // Vectors were only fetched for explanations
+        assert!(
+            result
+                .groups
+                .iter()
+                .flat_map(|group| &group.hits)
+                .all(|hit| hit.vector.is_none())
+        );
+
+        let mut result = groups_result();
+        explain_groups(
+            &explainer,
+            &mut result,
+            Some(Instant::now() + Duration::from_secs(60)),
+        );
+        assert!(
+            statuses(&result)
+                .iter()
+                .all(|status| *status == ExplanationStatusOutput::Available)
+        );
+    }
+
+    #[test]
+    fn test_explain_against_centroid() {
+        let explainer = SearchExplainer {
+            centroid: Some(vec![1.0, 1.0, 1.0]),
+            ..SearchExplainer::new(
+                Distance::Dot,
+                DEFAULT_VECTOR_NAME,
+                Ok(ExplainedVector::Dense(vec![1.0, 1.0, 1.0])),
+                false,
+                false.into(),
+            )
+        };
+
+        let mut point = scored_point(1, 6.0, vec![1.0, 4.0, 1.0]);
+        explainer.explain(&mut point);
+        let explanation = point.score_explanation.unwrap();
+
+        // The query explanation is unchanged, the baseline one shows the deviation from the centroid
+        assert_eq!(explanation.top_dimensions[0].dimension, 1);
+        assert_eq!(explanation.top_dimensions[0].contribution, 4.0);
+        let baseline = explanation.baseline_explanation.unwrap();
+        assert_eq!(baseline.top_dimensions[0].dimension, 1);
+        assert_eq!(baseline.top_dimensions[0].contribution, -9.0);
+        assert_eq!(baseline.top_dimensions[1].contribution, 0.0);
+
+        // Without a centroid, there is no baseline
+        let mut point = scored_point(1, 6.0, vec![1.0, 4.0, 1.0]);
+        SearchExplainer {
+            centroid: None,
+            ..explainer
+        }
+        .explain(&mut point);
+        assert!(
+            point
+                .score_explanation
+                .unwrap()
+                .baseline_explanation
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_explain_sparse_query() {
+        let query = SparseVector {
+            indices: vec![1, 5, 9],
+            values: vec![2.0, 1.0, -1.0],
+        };
+        let stored = SparseVector {
+            indices: vec![0, 5, 9],
+            values: vec![4.0, 3.0, 2.0],
+        };
+
+        let named_query = NamedQuery {
+            query: VectorInternal::Sparse(query.clone()),
+            using: Some("sparse".to_owned()),
+        };
+        let query_enum = QueryEnum::Nearest(named_query);
+        let query_vector = extract_query_vector(&query_enum).unwrap();
+        assert_eq!(query_vector, ExplainedVector::Sparse(query.clone()));
+
+        let mut point = scored_point(1, 1.0, vec![]);
+        point.vector = Some(VectorStructInternal::Named(HashMap::from([
+            ("dense".to_owned(), VectorInternal::Dense(vec![1.0, 1.0])),
+            ("sparse".to_owned(), VectorInternal::Sparse(stored.clone())),
+        ])));
+
+        let explainer = SearchExplainer::new(
+            Distance::Dot,
+            query_enum.get_vector_name(),
+            Ok(query_vector),
+            false,
+            false.into(),
+        );
+        explainer.explain(&mut point);
+
+        // Only indices present in both vectors contribute
+        let explanation = point.score_explanation.unwrap();
+        let expected = compute_sparse_explanation(&query, &stored, None);
+        assert_eq!(explanation.top_dimensions, expected.top_dimensions);
+        let dimensions: Vec<_> = explanation
+            .top_dimensions
+            .iter()
+            .map(|contribution| contribution.dimension)
+            .collect();
+        assert_eq!(dimensions, vec![5, 9]);
+        assert_eq!(explanation.total_score, 1.0);
+    }
+
+    #[test]
+    fn test_counterfactual_explanation_mode() {
+        let query = vec![1.0, 1.0, 0.0];
+        let stored = vec![1.0, 0.0, 1.0];
+
+        let mut explainer = SearchExplainer::new(
+            Distance::Cosine,
+            DEFAULT_VECTOR_NAME,
+            Ok(ExplainedVector::Dense(query.clone())),
+            false,
+            false.into(),
+        );
+        explainer.mode = ExplanationMode::Counterfactual;
+
+        let mut point = scored_point(1, 0.5, stored.clone());
+        explainer.explain(&mut point);
+
+        let explanation = point.score_explanation.unwrap();
+        let expected = compute_counterfactual_explanation(
+            Distance::Cosine,
+            &query,
+            &stored,
+            Some(DEFAULT_TOP_DIMENSIONS),
+        );
+        assert_eq!(explanation.top_dimensions, expected.top_dimensions);
+        assert_ne!(
+            explanation.top_dimensions,
+            compute_explanation(Distance::Cosine, &query, &stored, None).top_dimensions,
+        );
+    }
+
+    #[test]
+    fn test_explanation_keeps_quantization_error_of_segments() {
+        let explainer = SearchExplainer::new(
+            Distance::Dot,
+            DEFAULT_VECTOR_NAME,
+            Ok(ExplainedVector::Dense(vec![1.0, 2.0, 3.0])),
+            false,
+            false.into(),
+        );
+
+        // Segments report the error of all dimensions
+        let error = |dimension, contribution| DimensionContribution {
+            dimension,
+            contribution,
+            label: None,
+        };
+        let mut point = scored_point(1, 4.5, vec![0.5, 0.5, 1.0]);
+        point.score_explanation = Some(ScoreExplanation {
+            quantization_error: vec![error(0, 0.1), error(1, -0.2), error(2, 0.3)],
+            ..Default::default()
+        });
+        explainer.explain(&mut point);
+
+        let explanation = point.score_explanation.unwrap();
+        assert_eq!(explanation.total_score, 4.5);
+        // Contributions are 0.5, 1.0 and 3.0, errors follow the order of the top dimensions
+        assert_eq!(
+            explanation.quantization_error,
+            vec![error(2, 0.3), error(1, -0.2), error(0, 0.1)],
+        );
+
+        // Without quantization error from segments, there is nothing to report
+        let mut point = scored_point(2, 4.5, vec![0.5, 0.5, 1.0]);
+        explainer.explain(&mut point);
+        assert!(
+            point
+                .score_explanation
+                .unwrap()
+                .quantization_error
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_explanation_takes_approximate_search_from_segments() {
+        // Requests without `exact` may still be searched exactly, e.g. in small segments
+        let explainer = SearchExplainer::new(
+            Distance::Dot,
+            DEFAULT_VECTOR_NAME,
+            Ok(ExplainedVector::Dense(vec![1.0, 2.0, 3.0])),
+            true,
+            false.into(),
+        );
+
+        let segment_explanation = |approximate_search| ScoreExplanation {
+            approximate_search,
+            ..Default::default()
+        };
+        let mut exact = scored_point(1, 4.5, vec![0.5, 0.5, 1.0]);
+        exact.score_explanation = Some(segment_explanation(false));
+        explainer.explain(&mut exact);
+        assert!(!exact.score_explanation.unwrap().approximate_search);
+
+        let mut approximate = scored_point(2, 4.5, vec![0.5, 0.5, 1.0]);
+        approximate.score_explanation = Some(segment_explanation(true));
+        explainer.explain(&mut approximate);
+        assert!(approximate.score_explanation.unwrap().approximate_search);
+
+        // Points not reported by their segment fall back to the params of the request
+        let mut unreported = scored_point(3, 4.5, vec![0.5, 0.5, 1.0]);
+        explainer.explain(&mut unreported);
+        assert!(unreported.score_explanation.unwrap().approximate_search);
+    }
+}
@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::universal_query::collection_query::{CollectionQueryRequest, Query};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::types::{ExplanationParams, FusionMethod, ScoreExplanation};
+use segment::explain::explain_fusion;
+use segment::types::{Filter, ScoredPoint};
+use shard::query::FusionInternal;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+/// Prefetches of an explained fusion query, replayed on their own after the query
+/// to report how much each of them contributed to the fused scores.
+pub(crate) struct FusionReplay {
+    method: FusionMethod,
+    prefetches: Vec<CollectionQueryRequest>,
+}
+
+impl FusionReplay {
+    pub(crate) fn prepare(request: &CollectionQueryRequest) -> Option<Self> {
+        if !request.with_explanation {
+            return None;
+        }
+
+        let method = match request.query {
+            Some(Query::Fusion(FusionInternal::RrfK(k))) => FusionMethod::Rrf { k },
+            Some(Query::Fusion(FusionInternal::Dbsf)) => FusionMethod::Dbsf,
+            _ => return None,
+        };
+
+        let prefetches = request
+            .prefetch
+            .iter()
+            .map(|prefetch| CollectionQueryRequest {
+                prefetch: prefetch.prefetch.clone(),
+                query: prefetch.query.clone(),
+                using: prefetch.using.clone(),
+                // Prefetches are filtered by the filter of the query as well
+                filter: Filter::merge_opts(request.filter.clone(), prefetch.filter.clone()),
+                score_threshold: prefetch
+                    .score_threshold
+                    .map(|threshold| threshold.into_inner()),
+                limit: prefetch.limit,
+                offset: 0,
+                params: prefetch.params,
+                with_vector: CollectionQueryRequest::DEFAULT_WITH_VECTOR,
+                with_payload: CollectionQueryRequest::DEFAULT_WITH_PAYLOAD,
+                lookup_from: prefetch.lookup_from.clone(),
+                with_explanation: false,
+                explanation_params: ExplanationParams::default(),
+                distinct_by: None,
+            })
+            .collect();
+
+        Some(Self { method, prefetches })
+    }
+
+    /// Replace the explanations of `points` with the contribution of each prefetch,
+    /// given the results of the replayed prefetches.
+    fn explain(&self, sources: Vec<Vec<ScoredPoint>>, points: &mut [ScoredPoint]) {
+        let mut explanations = explain_fusion(self.method, sources);
+        for point in points {
+            let Some(fusion) = explanations.remove(&point.id) else {
+                continue;
+            };
+            let verbosity = point
+                .score_explanation
+                .as_ref()
+                .map(|explanation| explanation.verbosity)
+                .unwrap_or_default();
+            point.score_explanation = Some(ScoreExplanation {
+                verbosity,
+                ..ScoreExplanation::fused(fusion)
+            });
+        }
+    }
+}
+
+/// Explain the results of the fusion queries of a batch, see [`FusionReplay`].
+///
+/// The prefetches of all queries are replayed in a single batch.
+/// If the replay fails, the results keep the explanations they already have.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn explain_fusion_batch(
+    toc: &TableOfContent,
+    collection_name: &str,
+    replays: Vec<(Option<FusionReplay>, ShardSelectorInternal)>,
+    results: &mut [Vec<ScoredPoint>],
+    read_consistency: Option<ReadConsistency>,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: HwMeasurementAcc,
+) {
+    let requests: Vec<_> = replays
+        .iter()
+        .filter_map(|(replay, shard_selection)| Some((replay.as_ref()?, shard_selection)))
+        .flat_map(|(replay, shard_selection)| {
+            replay
+                .prefetches
+                .iter()
+                .map(|prefetch| (prefetch.clone(), shard_selection.clone()))
+        })
+        .collect();
+    if requests.is_empty() {
+        return;
+    }
+
+    let Ok(sources) = toc
+        .query_batch(
+            collection_name,
+            requests,
+            read_consistency,
+            access,
+            timeout,
+            hw_measurement_acc,
+        )
+        .await
+    else {
+        return;
+    };
+
+    let mut sources = sources.into_iter();
+    for ((replay, _), points) in replays.iter().zip(results) {
+        let Some(replay) = replay else {
+            continue;
+        };
+        let replay_sources = sources.by_ref().take(replay.prefetches.len()).collect();
+        replay.explain(replay_sources, points);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use collection::operations::universal_query::collection_query::CollectionPrefetch;
+    use common::types::{ExplanationStatus, ExplanationVerbosity, UnavailableReason};
+    use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+    use segment::types::{WithPayloadInterface, WithVector};
+
+    use super::*;
+    use crate::common::explain::scored_point;
+
+    #[test]
+    fn test_fusion_replay() {
+        let prefetch = |limit: usize| CollectionPrefetch {
+            prefetch: Vec::new(),
+            query: None,
+            using: DEFAULT_VECTOR_NAME.to_owned(),
+            filter: None,
+            score_threshold: None,
+            limit,
+            params: None,
+            lookup_from: None,
+        };
+        let mut request = CollectionQueryRequest {
+            prefetch: vec![prefetch(2), prefetch(3)],
+            query: Some(Query::Fusion(FusionInternal::RrfK(2))),
+            using: DEFAULT_VECTOR_NAME.to_owned(),
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 5,
+            params: None,
+            with_vector: WithVector::Bool(true),
+            with_payload: WithPayloadInterface::Bool(true),
+            lookup_from: None,
+            with_explanation: false,
+            explanation_params: ExplanationParams::default(),
+            distinct_by: None,
+        };
+        assert!(FusionReplay::prepare(&request).is_none());
+
+        request.with_explanation = true;
+        let replay = FusionReplay::prepare(&request).unwrap();
+        assert_eq!(replay.method, FusionMethod::Rrf { k: 2 });
+        assert_eq!(replay.prefetches.len(), 2);
+        assert_eq!(replay.prefetches[1].limit, 3);
+        assert_eq!(replay.prefetches[1].offset, 0);
+        assert!(!replay.prefetches[1].with_explanation);
+
+        let point = |id: u64, score: f32| ScoredPoint {
+            vector: None,
+            ..scored_point(id, score, Vec::new())
+        };
+        let mut results = vec![point(2, 0.8), point(1, 0.5), point(3, 0.3)];
+        results[0].score_explanation = Some(ScoreExplanation {
+            verbosity: ExplanationVerbosity::Minimal,
+            ..ScoreExplanation::unavailable(UnavailableReason::UnsupportedQuery)
+        });
+        replay.explain(
+            vec![vec![point(1, 0.9), point(2, 0.7)], vec![point(2, 0.6)]],
+            &mut results,
+        );
+
+        let explanation = results[0].score_explanation.as_ref().unwrap();
+        assert_eq!(explanation.verbosity, ExplanationVerbosity::Minimal);
+        assert_eq!(explanation.status, ExplanationStatus::Available);
+        let fusion = explanation.fusion.as_ref().unwrap();
+        assert_eq!(fusion.sources[0].rank, Some(1));
+        assert_eq!(fusion.sources[1].rank, Some(0));
+        assert!((explanation.total_score - (1.0 / 3.0 + 1.0 / 2.0)).abs() < 1e-6);
+
+        // Not part of any replayed prefetch, explanation left untouched
+        assert!(results[2].score_explanation.is_none());
+    }
+}
@@ -0,0 +1,41 @@
+//! Explanations of the scores of search and query results.
+//!
+//! Request handlers of [`crate::common::query`] prepare a [`SearchExplainer`] along with their
+//! request, and use it to explain the results once the request is done.
+
+mod aggregate;
+mod chunks;
+mod distances;
+mod explainer;
+mod fusion;
+mod pair;
+mod recommend;
+mod scroll;
+
+pub use aggregate::{ExplanationMatrix, do_explain_results, do_query_points_explain_matrix};
+pub(crate) use chunks::{EXPLANATION_FETCH_CHUNK_SIZE, explain_in_chunks, explained_points_stream};
+pub(crate) use distances::ExplanationDistances;
+pub(crate) use explainer::{SearchExplainer, explain_batch, explain_groups, with_explained_vector};
+pub(crate) use fusion::{FusionReplay, explain_fusion_batch};
+pub use pair::{PairExplanation, do_explain_pair};
+pub use scroll::{SampledExplanations, do_count_points_explained, do_scroll_points_explained};
+
+#[cfg(test)]
+fn scored_point(
+    id: u64,
+    score: f32,
+    vector: segment::data_types::vectors::DenseVector,
+) -> segment::types::ScoredPoint {
+    segment::types::ScoredPoint {
+        id: id.into(),
+        version: 0,
+        score,
+        payload: None,
+        vector: Some(segment::data_types::vectors::VectorStructInternal::Single(
+            vector,
+        )),
+        shard_key: None,
+        order_value: None,
+        score_explanation: None,
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::PointRequestInternal;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::types::ScoreExplanation;
+use segment::types::{
+    Distance, PointIdType, ScoreType, VectorName, WithPayloadInterface, WithVector,
+};
+use shard::retrieve::record_internal::RecordInternal;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+use super::distances::ExplanationDistances;
+use super::explainer::{ExplainedVector, SearchExplainer, get_dimension_labels};
+
+/// Explanation of the similarity of two stored points
+#[derive(Clone, Debug)]
+pub struct PairExplanation {
+    pub explanation: ScoreExplanation,
+    /// Score of point `b` for a query with the vector of point `a`
+    pub score: ScoreType,
+}
+
+/// Explain the similarity of two stored points under the `using` vector,
+/// as if the vector of `point_id_a` was used as a query and `point_id_b` was found.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_explain_pair(
+    toc: &TableOfContent,
+    collection_name: &str,
+    point_id_a: PointIdType,
+    point_id_b: PointIdType,
+    using: &VectorName,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<PairExplanation, StorageError> {
+    let distance = ExplanationDistances::default()
+        .distance(toc, collection_name, using, &access)
+        .await;
+    let dimension_labels = get_dimension_labels(toc, collection_name, using, &access).await;
+
+    let records = toc
+        .retrieve(
+            collection_name,
+            PointRequestInternal {
+                ids: vec![point_id_a, point_id_b],
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: WithVector::Selector(vec![using.to_owned()]),
+            },
+            read_consistency,
+            timeout,
+            shard_selection,
+            access,
+            hw_measurement_acc,
+        )
+        .await?;
+
+    explain_pair_records(
+        &records,
+        point_id_a,
+        point_id_b,
+        using,
+        distance,
+        dimension_labels,
+    )
+}
+
+fn explain_pair_records(
+    records: &[RecordInternal],
+    point_id_a: PointIdType,
+    point_id_b: PointIdType,
+    using: &VectorName,
+    distance: Distance,
+    dimension_labels: HashMap<usize, String>,
+) -> Result<PairExplanation, StorageError> {
+    let vector_a = pair_vector(records, point_id_a, using)?;
+    let vector_b = pair_vector(records, point_id_b, using)?;
+
+    let mut explainer = SearchExplainer::new(distance, using, Ok(vector_a), false, false.into());
+    explainer.dimension_labels = dimension_labels;
+
+    let explanation = explainer.explain_vector(Ok(vector_b));
+    Ok(PairExplanation {
+        score: explanation.total_score,
+        explanation,
+    })
+}
+
+fn pair_vector(
+    records: &[RecordInternal],
+    point_id: PointIdType,
+    using: &VectorName,
+) -> Result<ExplainedVector, StorageError> {
+    let record = records
+        .iter()
+        .find(|record| record.id == point_id)
+        .ok_or_else(|| StorageError::not_found(format!("No point with id {point_id} found")))?;
+    record
+        .get_vector_by_name(using)
+        .and_then(|vector| ExplainedVector::from_internal(&vector.to_owned()))
+        .ok_or_else(|| {
+            StorageError::bad_input(format!(
+                "Point {point_id} has no dense or sparse vector named `{using}`"
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::{DenseVector, VectorInternal, VectorStructInternal};
+
+    use super::*;
+
+    #[test]
+    fn test_explain_pair_records() {
+        let record = |id: u64, vector: DenseVector| RecordInternal {
+            id: id.into(),
+            payload: None,
+            vector: Some(VectorStructInternal::Named(
+                [("image".into(), VectorInternal::Dense(vector))].into(),
+            )),
+            shard_key: None,
+            order_value: None,
+            then_by_values: vec![],
+        };
+        let records = vec![
+            record(1, vec![1.0, 2.0, 0.0]),
+            record(2, vec![3.0, 1.0, 5.0]),
+        ];
+        let labels = HashMap::from([(0, "red".to_owned())]);
+
+        let pair =
+            explain_pair_records(&records, 1.into(), 2.into(), "image", Distance::Dot, labels)
+                .unwrap();
+        assert_eq!(pair.score, 5.0);
+        assert_eq!(pair.score, pair.explanation.total_score);
+        assert_eq!(pair.explanation.vector_name.as_deref(), Some("image"));
+        let top = &pair.explanation.top_dimensions[0];
+        assert_eq!(top.dimension, 0);
+        assert_eq!(top.label.as_deref(), Some("red"));
+
+        // Missing points and vectors are reported instead of explained
+        let missing_point = explain_pair_records(
+            &records,
+            1.into(),
+            3.into(),
+            "image",
+            Distance::Dot,
+            HashMap::new(),
+        );
+        assert!(matches!(missing_point, Err(StorageError::NotFound { .. })));
+        let missing_vector = explain_pair_records(
+            &records,
+            1.into(),
+            2.into(),
+            "text",
+            Distance::Dot,
+            HashMap::new(),
+        );
+        assert!(matches!(missing_vector, Err(StorageError::BadInput { .. })));
+    }
+}
@@ -0,0 +1,301 @@
+use api::rest::LookupLocation;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::PointRequestInternal;
+use collection::operations::universal_query::collection_query::VectorInputInternal;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::types::UnavailableReason;
+use itertools::Itertools as _;
+use segment::data_types::vectors::{DenseVector, VectorElementType, VectorInternal};
+use segment::explain::{
+    DominantExample, ExplainError, dominant_recommend_examples, weighted_average_recommend_query,
+};
+use segment::types::{VectorName, WithPayloadInterface, WithVector};
+use segment::vector_storage::query::RecoQuery;
+use shard::retrieve::record_internal::RecordInternal;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+use super::explainer::ExplainedVector;
+
+/// Resolve the examples of an `average_vector` recommend query to their vectors.
+///
+/// Examples given by id are fetched the same way the query itself fetches them:
+/// from the `lookup_from` collection and vector if set, from the searched collection otherwise.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn resolve_recommend_examples(
+    toc: &TableOfContent,
+    collection_name: &str,
+    reco: &RecoQuery<VectorInputInternal>,
+    weights: &ExampleWeights,
+    using: &VectorName,
+    lookup_from: Option<&LookupLocation>,
+    access: &Access,
+    shard_selection: &ShardSelectorInternal,
+) -> Result<RecommendExamples, UnavailableReason> {
+    let (lookup_collection, lookup_vector, lookup_shards) = match lookup_from {
+        Some(lookup) => (
+            lookup.collection.as_str(),
+            lookup.vector.as_deref().unwrap_or(using),
+            lookup
+                .shard_key
+                .clone()
+                .map_or(ShardSelectorInternal::All, ShardSelectorInternal::from),
+        ),
+        None => (collection_name, using, shard_selection.clone()),
+    };
+
+    let ids: Vec<_> = reco
+        .flat_iter()
+        .filter_map(|input| match input {
+            VectorInputInternal::Id(id) => Some(*id),
+            VectorInputInternal::Vector(_) => None,
+        })
+        .collect();
+
+    let records = if ids.is_empty() {
+        Vec::new()
+    } else {
+        toc.retrieve(
+            lookup_collection,
+            PointRequestInternal {
+                ids,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: WithVector::Selector(vec![lookup_vector.to_owned()]),
+            },
+            None,
+            None,
+            lookup_shards,
+            access.clone(),
+            HwMeasurementAcc::disposable(),
+        )
+        .await
+        .map_err(|_| UnavailableReason::VectorMissing)?
+    };
+
+    RecommendExamples::resolve(reco, weights, &records, lookup_vector)
+}
+
+/// Weights of the examples of a recommend query, in the order of its examples.
+/// Examples without a weight weigh 1.
+#[derive(Clone, Debug, Default)]
+pub(super) struct ExampleWeights {
+    positives: Vec<VectorElementType>,
+    negatives: Vec<VectorElementType>,
+}
+
+impl ExampleWeights {
+    fn weigh(
+        examples: Vec<DenseVector>,
+        weights: &[VectorElementType],
+    ) -> Vec<(DenseVector, VectorElementType)> {
+        examples
+            .into_iter()
+            .enumerate()
+            .map(|(i, example)| (example, weights.get(i).copied().unwrap_or(1.0)))
+            .collect()
+    }
+}
+
+/// Weighted vectors of the examples of a recommend query
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct RecommendExamples {
+    positives: Vec<(DenseVector, VectorElementType)>,
+    negatives: Vec<(DenseVector, VectorElementType)>,
+}
+
+type WeightedExamples<'a> = Vec<(&'a [VectorElementType], VectorElementType)>;
+
+impl RecommendExamples {
+    /// Resolve the examples of a recommend query, looking ids up in the vectors of `records`
+    fn resolve(
+        reco: &RecoQuery<VectorInputInternal>,
+        weights: &ExampleWeights,
+        records: &[RecordInternal],
+        vector_name: &VectorName,
+    ) -> Result<Self, UnavailableReason> {
+        let resolve = |input: &VectorInputInternal| {
+            let vector = match input {
+                VectorInputInternal::Vector(vector) => Some(vector.clone()),
+                VectorInputInternal::Id(id) => records
+                    .iter()
+                    .find(|record| record.id == *id)
+                    .and_then(|record| record.get_vector_by_name(vector_name))
+                    .map(|vector| vector.to_owned()),
+            };
+            match vector {
+                Some(VectorInternal::Dense(dense)) => Ok(dense),
+                Some(VectorInternal::Sparse(_) | VectorInternal::MultiDense(_)) => {
+                    Err(UnavailableReason::UnsupportedQuery)
+                }
+                None => Err(UnavailableReason::VectorMissing),
+            }
+        };
+
+        let positives: Vec<DenseVector> = reco.positives.iter().map(resolve).try_collect()?;
+        let negatives: Vec<DenseVector> = reco.negatives.iter().map(resolve).try_collect()?;
+
+        Ok(Self {
+            positives: ExampleWeights::weigh(positives, &weights.positives),
+            negatives: ExampleWeights::weigh(negatives, &weights.negatives),
+        })
+    }
+
+    fn borrowed(&self) -> (WeightedExamples<'_>, WeightedExamples<'_>) {
+        fn borrow(examples: &[(DenseVector, VectorElementType)]) -> WeightedExamples<'_> {
+            examples
+                .iter()
+                .map(|(example, weight)| (example.as_slice(), *weight))
+                .collect()
+        }
+        (borrow(&self.positives), borrow(&self.negatives))
+    }
+
+    /// Effective query vector: weighted average of the examples
+    pub(super) fn query_vector(&self) -> Result<ExplainedVector, UnavailableReason> {
+        let (positives, negatives) = self.borrowed();
+        weighted_average_recommend_query(&positives, &negatives)
+            .map(ExplainedVector::Dense)
+            .map_err(|_| UnavailableReason::UnsupportedQuery)
+    }
+
+    pub(super) fn dominant_examples(
+        &self,
+        dimensions: &[usize],
+    ) -> Result<Vec<DominantExample>, ExplainError> {
+        let (positives, negatives) = self.borrowed();
+        dominant_recommend_examples(&positives, &negatives, dimensions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::types::ExampleKind;
+    use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, VectorStructInternal};
+    use segment::types::Distance;
+
+    use super::*;
+    use crate::common::explain::explainer::SearchExplainer;
+    use crate::common::explain::scored_point;
+
+    #[test]
+    fn test_recommend_query_vector_uses_lookup_vectors() {
+        // Examples as stored in the `lookup_from` collection, under its `image` vector
+        let lookup_record = |id: u64, vector: DenseVector| RecordInternal {
+            id: id.into(),
+            payload: None,
+            vector: Some(VectorStructInternal::Named(
+                [("image".into(), VectorInternal::Dense(vector))].into(),
+            )),
+            shard_key: None,
+            order_value: None,
+            then_by_values: vec![],
+        };
+        let records = vec![
+            lookup_record(1, vec![1.0, 0.0, 2.0]),
+            lookup_record(2, vec![0.0, 1.0, 0.0]),
+        ];
+
+        let reco = RecoQuery::new(
+            vec![
+                VectorInputInternal::Id(1.into()),
+                VectorInputInternal::Vector(VectorInternal::Dense(vec![3.0, 0.0, 0.0])),
+            ],
+            vec![VectorInputInternal::Id(2.into())],
+        );
+
+        let query_vector =
+            RecommendExamples::resolve(&reco, &ExampleWeights::default(), &records, "image")
+                .and_then(|examples| examples.query_vector())
+                .unwrap();
+        // avg(pos) = [2, 0, 1], avg(neg) = [0, 1, 0]
+        assert_eq!(query_vector, ExplainedVector::Dense(vec![4.0, -1.0, 2.0]),);
+
+        // Ids are looked up under the lookup vector name only
+        assert_eq!(
+            RecommendExamples::resolve(&reco, &ExampleWeights::default(), &records, "text"),
+            Err(UnavailableReason::VectorMissing),
+        );
+    }
+
+    #[test]
+    fn test_recommend_query_vector_with_weighted_examples() {
+        let reco = RecoQuery::new(
+            vec![
+                VectorInputInternal::Vector(VectorInternal::Dense(vec![1.0, 0.0])),
+                VectorInputInternal::Vector(VectorInternal::Dense(vec![0.0, 1.0])),
+            ],
+            vec![],
+        );
+        let weights = ExampleWeights {
+            positives: vec![3.0, 1.0],
+            negatives: vec![],
+        };
+
+        let examples = RecommendExamples::resolve(&reco, &weights, &[], "").unwrap();
+        let query_vector = examples.query_vector().unwrap();
+        assert_eq!(query_vector, ExplainedVector::Dense(vec![0.75, 0.25]));
+
+        // The explanation leans towards the heavier example
+        let explainer = SearchExplainer::new(
+            Distance::Dot,
+            DEFAULT_VECTOR_NAME,
+            Ok(query_vector),
+            false,
+            false.into(),
+        );
+        let mut point = scored_point(1, 1.0, vec![1.0, 1.0]);
+        explainer.explain(&mut point);
+        let explanation = point.score_explanation.unwrap();
+        assert_eq!(explanation.top_dimensions[0].dimension, 0);
+        assert_eq!(explanation.top_dimensions[0].contribution, 0.75);
+        assert_eq!(explanation.top_dimensions[1].contribution, 0.25);
+    }
+
+    #[test]
+    fn test_explain_recommend_dominant_examples() {
+        let reco = RecoQuery::new(
+            vec![
+                VectorInputInternal::Vector(VectorInternal::Dense(vec![1.0, 0.0])),
+                VectorInputInternal::Vector(VectorInternal::Dense(vec![0.0, 1.0])),
+            ],
+            vec![VectorInputInternal::Vector(VectorInternal::Dense(vec![
+                0.0, -2.0,
+            ]))],
+        );
+        let examples =
+            RecommendExamples::resolve(&reco, &ExampleWeights::default(), &[], "").unwrap();
+
+        // Query is `2 * [0.5, 0.5] - [0, -2] = [1, 3]`
+        let mut explainer = SearchExplainer::new(
+            Distance::Dot,
+            DEFAULT_VECTOR_NAME,
+            examples.query_vector(),
+            false,
+            false.into(),
+        );
+        explainer.recommend_examples = Some(examples);
+
+        let mut point = scored_point(1, 4.0, vec![1.0, 1.0]);
+        explainer.explain(&mut point);
+        let explanation = point.score_explanation.unwrap();
+
+        // Dimension 1 is pushed the most by moving away from the negative example
+        assert_eq!(
+            explanation.dominant_examples,
+            vec![
+                DominantExample {
+                    dimension: 1,
+                    kind: ExampleKind::Negative,
+                    index: 0,
+                    contribution: 2.0,
+                },
+                DominantExample {
+                    dimension: 0,
+                    kind: ExampleKind::Positive,
+                    index: 0,
+                    contribution: 1.0,
+                },
+            ],
+        );
+    }
+}
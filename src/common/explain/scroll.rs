@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{
+    CountRequestInternal, CountResult, ScrollRequestInternal, ScrollResult,
+};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::types::ScoreExplanation;
+use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, DenseVector};
+use segment::types::{WithPayloadInterface, WithVector};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+use super::distances::ExplanationDistances;
+use super::explainer::{ExplainedVector, SearchExplainer, with_explained_vector};
+use crate::common::query::do_count_points;
+
+/// Explanations of a sample of counted points
+#[derive(Clone, Debug)]
+pub struct SampledExplanations {
+    pub explanations: Vec<ScoreExplanation>,
+    /// The count was estimated, so the sampled points may not be representative of the counted set
+    pub estimated: bool,
+}
+
+impl SampledExplanations {
+    fn new(request: &CountRequestInternal, explanations: Vec<ScoreExplanation>) -> Self {
+        Self {
+            explanations,
+            estimated: !request.exact,
+        }
+    }
+}
+
+/// Count points, explaining a sample of up to `sample_size` of them against `reference_vector`.
+///
+/// The sample is the first points matching the filter of the count request, see [`do_scroll_points_explained`].
+#[allow(clippy::too_many_arguments)]
+pub async fn do_count_points_explained(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CountRequestInternal,
+    reference_vector: DenseVector,
+    sample_size: usize,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<(CountResult, SampledExplanations), StorageError> {
+    let sample_request = ScrollRequestInternal {
+        limit: Some(sample_size),
+        filter: request.filter.clone(),
+        with_payload: Some(WithPayloadInterface::Bool(false)),
+        with_vector: WithVector::Bool(false),
+        ..Default::default()
+    };
+    let (_, explanations) = do_scroll_points_explained(
+        toc,
+        collection_name,
+        sample_request,
+        reference_vector,
+        read_consistency,
+        timeout,
+        shard_selection.clone(),
+        access.clone(),
+        hw_measurement_acc.clone(),
+    )
+    .await?;
+    let sample = SampledExplanations::new(&request, explanations);
+
+    let count = do_count_points(
+        toc,
+        collection_name,
+        request,
+        read_consistency,
+        timeout,
+        shard_selection,
+        access,
+        hw_measurement_acc,
+    )
+    .await?;
+
+    Ok((count, sample))
+}
+
+/// Scroll points, explaining each of them against `reference_vector`.
+///
+/// Explanations are computed from the vectors of the scrolled records themselves, so they come
+/// from the same `read_consistency` view as the scroll result, without fetching vectors twice.
+/// Returns one explanation per scrolled point, in the same order.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_scroll_points_explained(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: ScrollRequestInternal,
+    reference_vector: DenseVector,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<(ScrollResult, Vec<ScoreExplanation>), StorageError> {
+    let with_vector = request.with_vector.clone();
+    request.with_vector = with_explained_vector(&with_vector, DEFAULT_VECTOR_NAME);
+
+    let distance = ExplanationDistances::default()
+        .distance(toc, collection_name, DEFAULT_VECTOR_NAME, &access)
+        .await;
+    let explainer = SearchExplainer::new(
+        distance,
+        DEFAULT_VECTOR_NAME,
+        Ok(ExplainedVector::Dense(reference_vector)),
+        false,
+        with_vector,
+    );
+
+    let mut result = toc
+        .scroll(
+            collection_name,
+            request,
+            read_consistency,
+            timeout,
+            shard_selection,
+            access,
+            hw_measurement_acc,
+        )
+        .await?;
+
+    let explanations = explain_records(&explainer, &mut result.points);
+
+    Ok((result, explanations))
+}
+
+fn explain_records(
+    explainer: &SearchExplainer,
+    records: &mut [api::rest::Record],
+) -> Vec<ScoreExplanation> {
+    records
+        .iter_mut()
+        .map(|record| explainer.explain_record(record))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common::types::{ExplanationMode, ExplanationVerbosity};
+    use segment::explain::{DEFAULT_TOP_DIMENSIONS, compute_explanation};
+    use segment::types::Distance;
+
+    use super::*;
+
+    #[test]
+    fn test_explain_records_uses_scrolled_vectors() {
+        let explainer = SearchExplainer {
+            query_vector: Ok(ExplainedVector::Dense(vec![1.0, 2.0, 0.0])),
+            vector_name: DEFAULT_VECTOR_NAME.to_owned(),
+            distance: Distance::Dot,
+            approximate_search: false,
+            with_vector: WithVector::Bool(false),
+            with_bundle: false,
+            verbosity: ExplanationVerbosity::default(),
+            top_n: DEFAULT_TOP_DIMENSIONS,
+            mode: ExplanationMode::default(),
+            chunked_fetch: None,
+            centroid: None,
+            recommend_examples: None,
+            dimension_labels: HashMap::new(),
+        };
+
+        let record = |id: u64, vector: DenseVector| api::rest::Record {
+            id: id.into(),
+            payload: None,
+            vector: Some(api::rest::VectorStructOutput::Single(vector)),
+            shard_key: None,
+            order_value: None,
+        };
+
+        // As returned by a scroll with `ReadConsistency::Majority`: explanations must be built
+        // from exactly these vectors, not from a separate fetch which could see other versions
+        let mut records = vec![
+            record(1, vec![3.0, 0.0, 1.0]),
+            record(2, vec![0.0, 1.0, 1.0]),
+        ];
+        let scrolled: Vec<_> = records.iter().map(|record| record.vector.clone()).collect();
+
+        let explanations = explain_records(&explainer, &mut records);
+        assert_eq!(explanations.len(), 2);
+
+        for (explanation, vector) in explanations.iter().zip(scrolled) {
+            let Some(api::rest::VectorStructOutput::Single(vector)) = vector else {
+                panic!("single dense vector expected");
+            };
+            let expected = compute_explanation(Distance::Dot, &[1.0, 2.0, 0.0], &vector, None);
+            assert_eq!(explanation.top_dimensions, expected.top_dimensions);
+            assert!(!explanation.approximate_search);
+        }
+
+        // Vectors were not requested, so they are stripped after explaining
+        assert!(records.iter().all(|record| record.vector.is_none()));
+    }
+
+    #[test]
+    fn test_sampled_explanations_of_estimated_count() {
+        let explanations = vec![compute_explanation(
+            Distance::Dot,
+            &[1.0, 2.0],
+            &[1.0, 1.0],
+            None,
+        )];
+
+        let estimated = SampledExplanations::new(
+            &CountRequestInternal {
+                filter: None,
+                exact: false,
+            },
+            explanations.clone(),
+        );
+        assert!(estimated.estimated);
+        assert_eq!(estimated.explanations.len(), 1);
+
+        let exact = SampledExplanations::new(
+            &CountRequestInternal {
+                filter: None,
+                exact: true,
+            },
+            explanations,
+        );
+        assert!(!exact.estimated);
+    }
+}
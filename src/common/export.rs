@@ -0,0 +1,434 @@
+//! Streaming export of points, in the formats accepted by the point import
+//!
+//! JSON Lines export writes one point per line, in the same shape as points of an upsert request.
+//! Parquet export writes the columns expected by the Parquet import:
+//! - `id` - point id, as string
+//! - `payload` - JSON object encoded as string
+//! - `vector` - default dense vector, other dense vectors under their own names
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::rest::{ShardKeySelector, VectorOutput, VectorStructOutput};
+use arrow::array::{ArrayRef, Float32Builder, ListBuilder, StringBuilder};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::ScrollRequestInternal;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use schemars::JsonSchema;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::types::{
+    Filter, Payload, PointIdType, VectorNameBuf, WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+use validator::Validate;
+
+use crate::common::import::{DEFAULT_VECTOR_COLUMN, ID_COLUMN, PAYLOAD_COLUMN};
+use crate::common::query::{DEFAULT_SCROLL_STREAM_BATCH_SIZE, do_scroll_points_stream};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// JSON Lines, one point per line
+    #[default]
+    Jsonl,
+    /// Apache Parquet
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Jsonl => "application/x-ndjson",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, Copy)]
+pub struct ExportParams {
+    /// Format of the exported file. Default: jsonl
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Number of points fetched per page. Default: 1000
+    #[serde(default)]
+    #[validate(range(min = 1, max = 100_000))]
+    pub batch_size: Option<usize>,
+}
+
+/// Export request - dump all points which match given condition
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+pub struct ExportRequest {
+    /// Export only points which satisfy this conditions. If not provided - all points.
+    #[validate(nested)]
+    pub filter: Option<Filter>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+impl ExportRequest {
+    /// Scroll request reading all data of the exported points
+    pub fn scroll_request(&self) -> ScrollRequestInternal {
+        ScrollRequestInternal {
+            offset: None,
+            limit: None,
+            filter: self.filter.clone(),
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: WithVector::Bool(true),
+            order_by: None,
+        }
+    }
+}
+
+/// Point in the shape of an upsert request
+#[derive(Serialize)]
+struct ExportedPoint<'a> {
+    id: PointIdType,
+    vector: &'a VectorStructOutput,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<&'a Payload>,
+}
+
+/// Stream all points matching the request, encoded in the requested format.
+///
+/// Points are read page by page in the order of their ids, each page being fetched only when the
+/// previous chunk was consumed. Every point is exported at most once, but the export is not a
+/// point-in-time view: points updated while the export is running may appear in either state.
+#[expect(clippy::too_many_arguments)]
+pub async fn do_export_points(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: ExportRequest,
+    params: ExportParams,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+    access: Access,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<BoxStream<'static, Result<Vec<u8>, StorageError>>, StorageError> {
+    let ExportParams { format, batch_size } = params;
+
+    let parquet_writer = match format {
+        ExportFormat::Jsonl => None,
+        ExportFormat::Parquet => {
+            let vector_names = dense_vector_names(&toc, &collection_name, &access).await?;
+            Some(ParquetExportWriter::new(vector_names)?)
+        }
+    };
+
+    let shard_selection = match &request.shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys.clone()),
+    };
+
+    let pages = do_scroll_points_stream(
+        toc,
+        collection_name,
+        request.scroll_request(),
+        batch_size.unwrap_or(DEFAULT_SCROLL_STREAM_BATCH_SIZE),
+        read_consistency,
+        timeout,
+        shard_selection,
+        access,
+        hw_measurement_acc,
+    )?;
+
+    let Some(writer) = parquet_writer else {
+        let chunks = pages.and_then(|page| async move { jsonl_chunk(&page.points) });
+        return Ok(chunks.boxed());
+    };
+
+    // Writer is taken out of the state once the footer is written
+    let chunks =
+        futures::stream::try_unfold((pages, Some(writer)), |(mut pages, writer)| async move {
+            let Some(mut writer) = writer else {
+                return Ok(None);
+            };
+
+            match pages.try_next().await? {
+                Some(page) => {
+                    let chunk = writer.write_page(&page.points)?;
+                    Ok(Some((chunk, (pages, Some(writer)))))
+                }
+                None => {
+                    let chunk = writer.finish()?;
+                    Ok(Some((chunk, (pages, None))))
+                }
+            }
+        });
+
+    Ok(chunks.boxed())
+}
+
+fn jsonl_chunk(points: &[api::rest::Record]) -> Result<Vec<u8>, StorageError> {
+    let empty_vector = VectorStructOutput::Named(Default::default());
+
+    let mut chunk = Vec::new();
+    for point in points {
+        let point = ExportedPoint {
+            id: point.id,
+            vector: point.vector.as_ref().unwrap_or(&empty_vector),
+            payload: point.payload.as_ref(),
+        };
+        serde_json::to_writer(&mut chunk, &point).map_err(export_error)?;
+        chunk.push(b'\n');
+    }
+
+    Ok(chunk)
+}
+
+/// Names of dense vectors of the collection, every other vector type can't be exported as Parquet
+async fn dense_vector_names(
+    toc: &TableOfContent,
+    collection_name: &str,
+    access: &Access,
+) -> Result<Vec<VectorNameBuf>, StorageError> {
+    let collection_pass =
+        access.check_collection_access(collection_name, AccessRequirements::new())?;
+    let params = toc
+        .get_collection(&collection_pass)
+        .await?
+        .state()
+        .await
+        .config
+        .params;
+
+    let has_sparse_vectors = params
+        .sparse_vectors
+        .as_ref()
+        .is_some_and(|sparse_vectors| !sparse_vectors.is_empty());
+    let has_multivectors = params
+        .vectors
+        .params_iter()
+        .any(|(_, params)| params.multivector_config.is_some());
+
+    if has_sparse_vectors || has_multivectors {
+        return Err(StorageError::bad_request(
+            "Parquet export supports only dense vectors, use JSON Lines format instead",
+        ));
+    }
+
+    Ok(params
+        .vectors
+        .params_iter()
+        .map(|(name, _)| name.to_owned())
+        .collect())
+}
+
+/// Writes record batches into an in-memory buffer, and hands out the encoded bytes after each page.
+struct ParquetExportWriter {
+    writer: ArrowWriter<Vec<u8>>,
+    schema: SchemaRef,
+    vector_names: Vec<VectorNameBuf>,
+}
+
+impl ParquetExportWriter {
+    fn new(vector_names: Vec<VectorNameBuf>) -> Result<Self, StorageError> {
+        let mut fields = vec![
+            Field::new(ID_COLUMN, DataType::Utf8, false),
+            Field::new(PAYLOAD_COLUMN, DataType::Utf8, true),
+        ];
+
+        for name in &vector_names {
+            let column = if name == DEFAULT_VECTOR_NAME {
+                DEFAULT_VECTOR_COLUMN
+            } else if matches!(
+                name.as_str(),
+                ID_COLUMN | PAYLOAD_COLUMN | DEFAULT_VECTOR_COLUMN
+            ) {
+                return Err(StorageError::bad_request(format!(
+                    "Vector `{name}` can't be exported as Parquet, its name is reserved for another column",
+                )));
+            } else {
+                name.as_str()
+            };
+
+            fields.push(Field::new_list(
+                column,
+                Field::new_list_field(DataType::Float32, true),
+                true,
+            ));
+        }
+
+        let schema = Arc::new(Schema::new(fields));
+
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+
+        let writer = ArrowWriter::try_new(Vec::new(), schema.clone(), Some(properties))
+            .map_err(export_error)?;
+
+        Ok(Self {
+            writer,
+            schema,
+            vector_names,
+        })
+    }
+
+    /// Encode the page as a row group, and return all bytes written so far
+    fn write_page(&mut self, points: &[api::rest::Record]) -> Result<Vec<u8>, StorageError> {
+        if points.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut ids = StringBuilder::new();
+        let mut payloads = StringBuilder::new();
+        let mut vectors: Vec<_> = self
+            .vector_names
+            .iter()
+            .map(|_| ListBuilder::new(Float32Builder::new()))
+            .collect();
+
+        for point in points {
+            ids.append_value(point.id.to_string());
+
+            match &point.payload {
+                Some(payload) => {
+                    payloads.append_value(serde_json::to_string(payload).map_err(export_error)?)
+                }
+                None => payloads.append_null(),
+            }
+
+            for (name, builder) in self.vector_names.iter().zip(&mut vectors) {
+                match dense_vector(point.vector.as_ref(), name) {
+                    Some(vector) => builder.append_value(vector.iter().copied().map(Some)),
+                    None => builder.append_null(),
+                }
+            }
+        }
+
+        let mut columns: Vec<ArrayRef> = vec![Arc::new(ids.finish()), Arc::new(payloads.finish())];
+        columns.extend(
+            vectors
+                .iter_mut()
+                .map(|builder| Arc::new(builder.finish()) as ArrayRef),
+        );
+
+        let batch = RecordBatch::try_new(self.schema.clone(), columns).map_err(export_error)?;
+
+        self.writer.write(&batch).map_err(export_error)?;
+        self.writer.flush().map_err(export_error)?;
+
+        // Writer tracks offsets on its own, so it is fine to drain the buffer
+        Ok(std::mem::take(self.writer.inner_mut()))
+    }
+
+    /// Write the file footer, and return the remaining bytes
+    fn finish(self) -> Result<Vec<u8>, StorageError> {
+        self.writer.into_inner().map_err(export_error)
+    }
+}
+
+fn dense_vector<'a>(vector: Option<&'a VectorStructOutput>, name: &str) -> Option<&'a [f32]> {
+    match vector? {
+        VectorStructOutput::Single(vector) => {
+            (name == DEFAULT_VECTOR_NAME).then_some(vector.as_slice())
+        }
+        VectorStructOutput::MultiDense(_) => None,
+        VectorStructOutput::Named(vectors) => match vectors.get(name)? {
+            VectorOutput::Dense(vector) => Some(vector.as_slice()),
+            VectorOutput::Sparse(_) | VectorOutput::MultiDense(_) => None,
+        },
+    }
+}
+
+fn export_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::service_error(format!("Failed to encode exported points: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use std::io::{Seek, Write};
+
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use serde_json::json;
+
+    use super::*;
+    use crate::common::import::points_from_record_batch;
+
+    fn record(id: u64, vector: Option<Vec<f32>>) -> api::rest::Record {
+        api::rest::Record {
+            id: PointIdType::NumId(id),
+            payload: Some(serde_json::from_value(json!({"n": id})).unwrap()),
+            vector: vector.map(|vector| {
+                VectorStructOutput::Named(HashMap::from([(
+                    "image".to_string(),
+                    VectorOutput::Dense(vector),
+                )]))
+            }),
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_parquet_export_roundtrip() {
+        let mut writer = ParquetExportWriter::new(vec!["image".to_string()]).unwrap();
+
+        let mut file = writer
+            .write_page(&[record(1, Some(vec![1.0, 2.0])), record(2, None)])
+            .unwrap();
+        file.extend(
+            writer
+                .write_page(&[record(3, Some(vec![3.0, 4.0]))])
+                .unwrap(),
+        );
+        file.extend(writer.finish().unwrap());
+
+        let mut tmp_file = tempfile::tempfile().unwrap();
+        tmp_file.write_all(&file).unwrap();
+        tmp_file.rewind().unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(tmp_file)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let points: Vec<_> = reader
+            .flat_map(|batch| points_from_record_batch(&batch.unwrap()).unwrap())
+            .collect();
+
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0].id, PointIdType::NumId(1));
+        assert_eq!(
+            points[0].payload,
+            Some(serde_json::from_value(json!({"n": 1})).unwrap()),
+        );
+        assert_eq!(
+            serde_json::to_value(&points[2].vector).unwrap(),
+            json!({"image": [3.0, 4.0]}),
+        );
+        assert_eq!(serde_json::to_value(&points[1].vector).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn test_jsonl_export() {
+        let chunk = jsonl_chunk(&[record(1, Some(vec![1.0])), record(2, None)]).unwrap();
+        let lines: Vec<serde_json::Value> = String::from_utf8(chunk)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                json!({"id": 1, "vector": {"image": [1.0]}, "payload": {"n": 1}}),
+                json!({"id": 2, "vector": {}, "payload": {"n": 2}}),
+            ],
+        );
+    }
+}
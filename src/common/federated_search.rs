@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::CoreSearchRequest;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use itertools::Itertools as _;
+use segment::types::{Order, ScoredPoint};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+
+use crate::common::query::do_core_search_points;
+
+/// Point found by [`do_search_points_federated`], along with the collection it comes from
+#[derive(Debug, Clone, PartialEq)]
+pub struct FederatedScoredPoint {
+    pub collection_name: String,
+    pub point: ScoredPoint,
+}
+
+/// Run the same search in multiple collections, and merge their results by score.
+///
+/// All collections must use the same distance for the searched vector, so that scores are
+/// comparable. `quotas` limit the number of results taken from a collection, by its name.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_search_points_federated(
+    toc: &TableOfContent,
+    collection_names: Vec<String>,
+    request: CoreSearchRequest,
+    quotas: HashMap<String, usize>,
+    read_consistency: Option<ReadConsistency>,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: impl Fn(&str) -> HwMeasurementAcc,
+) -> Result<Vec<FederatedScoredPoint>, StorageError> {
+    let collection_names = collection_names.into_iter().unique().collect_vec();
+    let order = federated_score_order(toc, &collection_names, &request, &access).await?;
+
+    let offset = request.offset;
+    let limit = request.limit;
+    let searches = collection_names.into_iter().map(|collection_name| {
+        let mut request = request.clone();
+        request.offset = 0;
+        request.limit = quotas
+            .get(&collection_name)
+            .map_or(offset + limit, |&quota| quota.min(offset + limit));
+        let hw_measurement_acc = hw_measurement_acc(&collection_name);
+        let access = access.clone();
+        async move {
+            let points = do_core_search_points(
+                toc,
+                &collection_name,
+                request,
+                read_consistency,
+                ShardSelectorInternal::All,
+                access,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await?;
+            Ok::<_, StorageError>((collection_name, points))
+        }
+    });
+
+    let results = futures::future::try_join_all(searches).await?;
+    Ok(merge_federated_results(results, order, offset, limit))
+}
+
+/// Check that scores of all collections are comparable, and find in which order to merge them
+async fn federated_score_order(
+    toc: &TableOfContent,
+    collection_names: &[String],
+    request: &CoreSearchRequest,
+    access: &Access,
+) -> Result<Order, StorageError> {
+    let vector_name = request.query.get_vector_name();
+
+    let mut distance = None;
+    for collection_name in collection_names {
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new())?;
+        let collection = toc.get_collection(&collection_pass).await?;
+        let collection_distance = collection.vector_distance(vector_name).await?;
+        match distance {
+            None => distance = Some(collection_distance),
+            Some(distance) if distance != collection_distance => {
+                return Err(StorageError::bad_input(format!(
+                    "Can't merge results of collections with different distances for vector \
+                     {vector_name:?}: {distance:?} and {collection_distance:?}",
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+
+    // Only nearest search scores by distance, other queries score larger better
+    match distance {
+        Some(distance) if request.query.is_distance_scored() => Ok(distance.distance_order()),
+        _ => Ok(Order::LargeBetter),
+    }
+}
+
+/// Merge results of each collection, sorted by score already, and apply `offset` and `limit`
+fn merge_federated_results(
+    results: Vec<(String, Vec<ScoredPoint>)>,
+    order: Order,
+    offset: usize,
+    limit: usize,
+) -> Vec<FederatedScoredPoint> {
+    results
+        .into_iter()
+        .map(|(collection_name, points)| {
+            points.into_iter().map(move |point| FederatedScoredPoint {
+                collection_name: collection_name.clone(),
+                point,
+            })
+        })
+        .kmerge_by(|a, b| match order {
+            Order::LargeBetter => a.point.score > b.point.score,
+            Order::SmallBetter => a.point.score < b.point.score,
+        })
+        .skip(offset)
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored_point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_federated_results_by_score_order() {
+        let results = || {
+            vec![
+                (
+                    "docs".to_string(),
+                    vec![scored_point(1, 0.9), scored_point(2, 0.5)],
+                ),
+                (
+                    "chunks".to_string(),
+                    vec![
+                        scored_point(1, 0.7),
+                        scored_point(3, 0.6),
+                        scored_point(4, 0.1),
+                    ],
+                ),
+            ]
+        };
+        let found = |merged: Vec<FederatedScoredPoint>| {
+            merged
+                .into_iter()
+                .map(|found| (found.collection_name, found.point.score))
+                .collect_vec()
+        };
+
+        assert_eq!(
+            found(merge_federated_results(results(), Order::LargeBetter, 1, 3)),
+            vec![
+                ("chunks".to_string(), 0.7),
+                ("chunks".to_string(), 0.6),
+                ("docs".to_string(), 0.5),
+            ],
+        );
+
+        // Distances, smaller is better, so every collection is sorted in reverse
+        let distances = results()
+            .into_iter()
+            .map(|(name, mut points)| {
+                points.reverse();
+                (name, points)
+            })
+            .collect();
+        assert_eq!(
+            found(merge_federated_results(distances, Order::SmallBetter, 0, 2)),
+            vec![("chunks".to_string(), 0.1), ("docs".to_string(), 0.5)],
+        );
+    }
+}
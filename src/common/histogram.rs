@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use api::rest::OrderByInterface;
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CountRequestInternal, ScrollRequestInternal};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use segment::data_types::histogram::{
+    HistogramBucket, HistogramParams, HistogramResponse, PercentileValue, bucket_ranges,
+    estimate_percentile,
+};
+use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
+use segment::types::{Condition, FieldCondition, Filter, WithPayloadInterface, WithVector};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+/// Histogram and percentiles of a numeric payload field.
+///
+/// Missing bounds are read from the ends of the field's range index, and each bucket is counted
+/// with a range condition, so points are not scanned unless exact counts are requested.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_histogram_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: HistogramParams,
+    shard_selection: ShardSelectorInternal,
+    read_consistency: Option<ReadConsistency>,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<HistogramResponse, StorageError> {
+    let HistogramParams {
+        key,
+        buckets,
+        filter,
+        min,
+        max,
+        percentiles,
+        exact,
+    } = request;
+
+    let field_bound = |direction: Direction| {
+        let request = ScrollRequestInternal {
+            offset: None,
+            limit: Some(1),
+            filter: filter.clone(),
+            with_payload: Some(WithPayloadInterface::Bool(false)),
+            with_vector: WithVector::Bool(false),
+            order_by: Some(OrderByInterface::Struct(OrderBy {
+                key: key.clone(),
+                direction: Some(direction),
+                start_from: None,
+                then_by: vec![],
+            })),
+        };
+        let scroll = toc.scroll(
+            collection_name,
+            request,
+            read_consistency,
+            timeout,
+            shard_selection.clone(),
+            access.clone(),
+            hw_measurement_acc.clone(),
+        );
+        async move {
+            let result = scroll.await?;
+            let bound = result
+                .points
+                .first()
+                .and_then(|point| point.order_value)
+                .map(|value| match value {
+                    OrderValue::Int(value) => value as f64,
+                    OrderValue::Float(value) => value,
+                });
+            Ok::<_, StorageError>(bound)
+        }
+    };
+
+    let min = match min {
+        Some(min) => Some(min),
+        None => field_bound(Direction::Asc).await?,
+    };
+    let max = match max {
+        Some(max) => Some(max),
+        None => field_bound(Direction::Desc).await?,
+    };
+    let (Some(min), Some(max)) = (min, max) else {
+        // No point has a value in the field
+        return Ok(HistogramResponse::default());
+    };
+
+    let ranges = bucket_ranges(min, max, buckets);
+    let counts = ranges.iter().map(|range| {
+        let in_bucket = Filter::new_must(Condition::Field(FieldCondition::new_range(
+            key.clone(),
+            *range,
+        )));
+        let filter = filter
+            .as_ref()
+            .map(|filter| filter.merge(&in_bucket))
+            .unwrap_or(in_bucket);
+        toc.count(
+            collection_name,
+            CountRequestInternal {
+                filter: Some(filter),
+                exact,
+            },
+            read_consistency,
+            timeout,
+            shard_selection.clone(),
+            access.clone(),
+            hw_measurement_acc.clone(),
+        )
+    });
+    let counts = futures::future::try_join_all(counts).await?;
+
+    let buckets: Vec<_> = ranges
+        .into_iter()
+        .zip(counts)
+        .map(|(range, count)| HistogramBucket {
+            from: range.gte.map_or(min, |from| from.0),
+            to: range.lt.or(range.lte).map_or(max, |to| to.0),
+            count: count.count,
+        })
+        .collect();
+
+    let percentiles = percentiles
+        .into_iter()
+        .filter_map(|percentile| {
+            let value = estimate_percentile(&buckets, percentile)?;
+            Some(PercentileValue { percentile, value })
+        })
+        .collect();
+
+    Ok(HistogramResponse {
+        buckets,
+        percentiles,
+    })
+}
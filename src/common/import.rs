@@ -0,0 +1,529 @@
+//! Server-side upsert of points from Arrow IPC stream and Parquet files, and from JSON Lines
+//! datasets in object storage
+//!
+//! Expected columns:
+//! - `id` - point id, unsigned integer, or string with an unsigned integer or UUID
+//! - `payload` - optional, JSON object encoded as string
+//! - any other column - dense vector, stored as list of floats;
+//!   `vector` column is stored as the default vector, others as named vectors
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+
+use api::rest::schema::{PointInsertOperations, PointsList};
+use api::rest::{DenseVector, PointStruct, Vector, VectorStruct};
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::datatypes::{DataType, Float32Type, Float64Type, Int64Type, UInt64Type};
+use arrow::ipc::reader::StreamReader;
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use futures::{Stream, StreamExt};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use schemars::JsonSchema;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::types::{Payload, PointIdType, VectorNameBuf};
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::import::{ImportFromUrlRequest, ImportSource, ImportTaskInfo};
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+use tempfile::TempPath;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use validator::Validate;
+
+use crate::common::inference::params::InferenceParams;
+use crate::common::strict_mode::{CheckedTocProvider, StrictModeCheckedInternalTocProvider};
+use crate::common::update::{InternalUpdateParams, UpdateParams, do_upsert_points};
+
+/// Number of points upserted in a single update operation, if not specified
+const DEFAULT_IMPORT_BATCH_SIZE: usize = 1000;
+
+/// Number of decoded batches buffered ahead of the update path
+const IMPORT_BATCHES_IN_FLIGHT: usize = 2;
+
+pub(crate) const ID_COLUMN: &str = "id";
+pub(crate) const PAYLOAD_COLUMN: &str = "payload";
+pub(crate) const DEFAULT_VECTOR_COLUMN: &str = "vector";
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// Arrow IPC streaming format
+    Arrow,
+    /// Apache Parquet
+    Parquet,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, Copy)]
+pub struct ImportParams {
+    /// Format of the imported file
+    pub format: ImportFormat,
+    /// Number of points upserted in a single update operation
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy)]
+pub struct ImportResult {
+    /// Number of points upserted from the file
+    pub points_count: usize,
+}
+
+/// Write an incoming byte stream into a temporary file in `temp_dir`
+pub async fn receive_import_file<S, B, E>(
+    stream: S,
+    temp_dir: &Path,
+) -> Result<TempPath, StorageError>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: Display,
+{
+    let (file, temp_path) = tempfile::Builder::new()
+        .prefix("import")
+        .suffix(".tmp")
+        .tempfile_in(temp_dir)?
+        .into_parts();
+
+    let mut file = tokio::io::BufWriter::new(tokio::fs::File::from_std(file));
+
+    let mut stream = std::pin::pin!(stream);
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|err| {
+            StorageError::bad_request(format!("Failed to receive import file: {err}"))
+        })?;
+        file.write_all(chunk.as_ref()).await?;
+    }
+
+    file.flush().await?;
+
+    Ok(temp_path)
+}
+
+/// Upsert all points from the given file into the collection.
+///
+/// The file is decoded on a blocking thread, and points are handed over to the regular upsert
+/// path in batches. Batches are applied one by one, so if one of them fails, previously
+/// upserted points stay in the collection.
+#[expect(clippy::too_many_arguments)]
+pub async fn do_import_points(
+    toc_provider: impl CheckedTocProvider + Clone,
+    collection_name: String,
+    file: TempPath,
+    import_params: ImportParams,
+    params: UpdateParams,
+    access: Access,
+    inference_params: InferenceParams,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<ImportResult, StorageError> {
+    let ImportParams { format, batch_size } = import_params;
+    let batch_size = batch_size.unwrap_or(DEFAULT_IMPORT_BATCH_SIZE);
+
+    let (sender, mut receiver) = mpsc::channel(IMPORT_BATCHES_IN_FLIGHT);
+
+    // Temporary file is removed once the decoder is done with it
+    let decoder = tokio::task::spawn_blocking(move || {
+        let result = read_points(&file, format, batch_size, |points| {
+            // Receiver is dropped if the update path failed, stop reading in that case
+            sender.blocking_send(Ok(points)).is_ok()
+        });
+
+        if let Err(err) = result {
+            let _ = sender.blocking_send(Err(err));
+        }
+    });
+
+    let mut points_count = 0;
+
+    while let Some(points) = receiver.recv().await {
+        let points = points?;
+        let batch_points_count = points.len();
+
+        let operation = PointInsertOperations::PointsList(PointsList {
+            points,
+            shard_key: None,
+            update_filter: None,
+            if_version: None,
+            if_payload_matches: None,
+            if_not_exists: None,
+        });
+
+        do_upsert_points(
+            toc_provider.clone(),
+            collection_name.clone(),
+            operation,
+            InternalUpdateParams::default(),
+            params,
+            access.clone(),
+            inference_params.clone(),
+            hw_measurement_acc.clone(),
+        )
+        .await?;
+
+        points_count += batch_points_count;
+    }
+
+    decoder.await?;
+
+    Ok(ImportResult { points_count })
+}
+
+/// Start a background import of points from object storage into the collection.
+///
+/// Returns as soon as the location is resolved, progress can be polled by the returned task id.
+/// Each batch goes through the regular upsert path, including strict mode checks and inference.
+pub async fn do_import_points_from_url(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: ImportFromUrlRequest,
+    access: Access,
+    inference_params: InferenceParams,
+) -> Result<ImportTaskInfo, StorageError> {
+    let collection_pass =
+        access.check_collection_access(&collection_name, AccessRequirements::new().write())?;
+
+    // Every location is read with the server's own filesystem or cloud credentials, so require
+    // the same access as for recovering from a snapshot on the local filesystem
+    access.check_global_access(AccessRequirements::new().manage())?;
+
+    // Fail early if the collection does not exist
+    toc.get_collection(&collection_pass).await?;
+
+    let source = ImportSource::open(&request.location)?;
+
+    let upsert = {
+        let toc = toc.clone();
+        let collection_name = collection_name.clone();
+
+        move |points: Vec<PointStruct>| {
+            let toc = toc.clone();
+            let collection_name = collection_name.clone();
+            let access = access.clone();
+            let inference_params = inference_params.clone();
+
+            async move {
+                let points_count = points.len();
+
+                let operation = PointInsertOperations::PointsList(PointsList {
+                    points,
+                    shard_key: None,
+                    update_filter: None,
+                    if_version: None,
+                    if_payload_matches: None,
+                    if_not_exists: None,
+                });
+
+                let params = UpdateParams {
+                    wait: true,
+                    ordering: Default::default(),
+                    ack: None,
+                    timeout: None,
+                };
+
+                let hw_measurement_acc = HwMeasurementAcc::new_with_metrics_drain(
+                    toc.get_collection_hw_metrics(collection_name.clone()),
+                );
+
+                do_upsert_points(
+                    StrictModeCheckedInternalTocProvider::new(&toc),
+                    collection_name,
+                    operation,
+                    InternalUpdateParams::default(),
+                    params,
+                    access,
+                    inference_params,
+                    hw_measurement_acc,
+                )
+                .await?;
+
+                Ok(points_count)
+            }
+        }
+    };
+
+    Ok(toc.import_tasks().start(
+        toc.general_runtime_handle(),
+        collection_name,
+        &request,
+        source,
+        upsert,
+    ))
+}
+
+/// Decode points from the file, and pass them to `handle_points` in chunks of `batch_size`.
+///
+/// Stops early if `handle_points` returns false.
+fn read_points(
+    path: &Path,
+    format: ImportFormat,
+    batch_size: usize,
+    mut handle_points: impl FnMut(Vec<PointStruct>) -> bool,
+) -> Result<(), StorageError> {
+    let file = fs_err::File::open(path)?;
+
+    let reader: Box<dyn RecordBatchReader + Send> = match format {
+        ImportFormat::Arrow => {
+            Box::new(StreamReader::try_new(BufReader::new(file), None).map_err(import_error)?)
+        }
+        ImportFormat::Parquet => Box::new(
+            ParquetRecordBatchReaderBuilder::try_new(file.into_parts().0)
+                .and_then(|builder| builder.with_batch_size(batch_size).build())
+                .map_err(import_error)?,
+        ),
+    };
+
+    for record_batch in reader {
+        let record_batch = record_batch.map_err(import_error)?;
+        let mut points = points_from_record_batch(&record_batch)?;
+
+        while !points.is_empty() {
+            let rest = points.split_off(points.len().min(batch_size));
+            if !handle_points(points) {
+                return Ok(());
+            }
+            points = rest;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn points_from_record_batch(
+    record_batch: &RecordBatch,
+) -> Result<Vec<PointStruct>, StorageError> {
+    let schema = record_batch.schema();
+
+    let mut ids = None;
+    let mut payloads = None;
+    let mut vectors: Vec<(VectorNameBuf, &ArrayRef)> = Vec::new();
+
+    for (field, column) in schema.fields().iter().zip(record_batch.columns()) {
+        match field.name().as_str() {
+            ID_COLUMN => ids = Some(column),
+            PAYLOAD_COLUMN => payloads = Some(column),
+            DEFAULT_VECTOR_COLUMN => vectors.push((DEFAULT_VECTOR_NAME.into(), column)),
+            name => vectors.push((name.into(), column)),
+        }
+    }
+
+    let ids = ids.ok_or_else(|| import_error(format!("missing `{ID_COLUMN}` column")))?;
+
+    (0..record_batch.num_rows())
+        .map(|row| {
+            let id = point_id_at(ids, row)?;
+
+            let payload = payloads
+                .map(|payloads| payload_at(payloads, row))
+                .transpose()?
+                .flatten();
+
+            let mut named_vectors = HashMap::with_capacity(vectors.len());
+            for (name, column) in &vectors {
+                if let Some(vector) = dense_vector_at(column, row).map_err(|err| {
+                    import_error(format!("invalid vector `{name}` of point {id}: {err}"))
+                })? {
+                    named_vectors.insert(name.clone(), Vector::Dense(vector));
+                }
+            }
+
+            let vector = match named_vectors.remove(DEFAULT_VECTOR_NAME) {
+                Some(Vector::Dense(vector)) if named_vectors.is_empty() => {
+                    VectorStruct::Single(vector)
+                }
+                Some(vector) => {
+                    named_vectors.insert(DEFAULT_VECTOR_NAME.into(), vector);
+                    VectorStruct::Named(named_vectors)
+                }
+                None => VectorStruct::Named(named_vectors),
+            };
+
+            Ok(PointStruct {
+                id,
+                vector,
+                payload,
+            })
+        })
+        .collect()
+}
+
+fn point_id_at(column: &ArrayRef, row: usize) -> Result<PointIdType, StorageError> {
+    if column.is_null(row) {
+        return Err(import_error(format!("`{ID_COLUMN}` must not be null")));
+    }
+
+    match column.data_type() {
+        DataType::UInt64 => Ok(PointIdType::NumId(
+            column.as_primitive::<UInt64Type>().value(row),
+        )),
+        DataType::Int64 => {
+            let id = column.as_primitive::<Int64Type>().value(row);
+            u64::try_from(id)
+                .map(PointIdType::NumId)
+                .map_err(|_| import_error(format!("point id {id} must not be negative")))
+        }
+        DataType::Utf8 => parse_point_id(column.as_string::<i32>().value(row)),
+        DataType::LargeUtf8 => parse_point_id(column.as_string::<i64>().value(row)),
+        other => Err(import_error(format!(
+            "unsupported type {other} of `{ID_COLUMN}` column, expected unsigned integer or string"
+        ))),
+    }
+}
+
+fn parse_point_id(id: &str) -> Result<PointIdType, StorageError> {
+    if let Ok(num_id) = id.parse() {
+        return Ok(PointIdType::NumId(num_id));
+    }
+
+    uuid::Uuid::parse_str(id)
+        .map(PointIdType::Uuid)
+        .map_err(|err| import_error(format!("invalid point id {id}: {err}")))
+}
+
+fn payload_at(column: &ArrayRef, row: usize) -> Result<Option<Payload>, StorageError> {
+    if column.is_null(row) {
+        return Ok(None);
+    }
+
+    let payload = match column.data_type() {
+        DataType::Utf8 => column.as_string::<i32>().value(row),
+        DataType::LargeUtf8 => column.as_string::<i64>().value(row),
+        other => {
+            return Err(import_error(format!(
+                "unsupported type {other} of `{PAYLOAD_COLUMN}` column, expected JSON string"
+            )));
+        }
+    };
+
+    serde_json::from_str(payload)
+        .map(Some)
+        .map_err(|err| import_error(format!("invalid payload: {err}")))
+}
+
+fn dense_vector_at(column: &ArrayRef, row: usize) -> Result<Option<DenseVector>, String> {
+    if column.is_null(row) {
+        return Ok(None);
+    }
+
+    let values = match column.data_type() {
+        DataType::List(_) => column.as_list::<i32>().value(row),
+        DataType::LargeList(_) => column.as_list::<i64>().value(row),
+        DataType::FixedSizeList(_, _) => column.as_fixed_size_list().value(row),
+        other => return Err(format!("unsupported type {other}, expected list of floats")),
+    };
+
+    if values.null_count() > 0 {
+        return Err("vector must not contain nulls".to_string());
+    }
+
+    match values.data_type() {
+        DataType::Float32 => Ok(Some(values.as_primitive::<Float32Type>().values().to_vec())),
+        DataType::Float64 => Ok(Some(
+            values
+                .as_primitive::<Float64Type>()
+                .values()
+                .iter()
+                .map(|&value| value as f32)
+                .collect(),
+        )),
+        other => Err(format!("unsupported element type {other}, expected float")),
+    }
+}
+
+fn import_error(err: impl Display) -> StorageError {
+    StorageError::bad_request(format!("Failed to read import file: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{FixedSizeListArray, Float32Array, StringArray, UInt64Array};
+    use arrow::datatypes::{Field, Schema};
+    use arrow::ipc::writer::StreamWriter;
+    use serde_json::json;
+
+    use super::*;
+
+    fn record_batch(ids: Vec<u64>) -> RecordBatch {
+        let rows = ids.len();
+
+        let vectors = FixedSizeListArray::try_new(
+            Arc::new(Field::new("item", DataType::Float32, false)),
+            2,
+            Arc::new(Float32Array::from_iter_values(
+                (0..rows * 2).map(|value| value as f32),
+            )),
+            None,
+        )
+        .unwrap();
+
+        let payloads = StringArray::from_iter(
+            (0..rows).map(|row| (row % 2 == 0).then(|| json!({ "row": row }).to_string())),
+        );
+
+        let schema = Schema::new(vec![
+            Field::new(ID_COLUMN, DataType::UInt64, false),
+            Field::new(DEFAULT_VECTOR_COLUMN, vectors.data_type().clone(), false),
+            Field::new(PAYLOAD_COLUMN, DataType::Utf8, true),
+        ]);
+
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(UInt64Array::from(ids)),
+                Arc::new(vectors),
+                Arc::new(payloads),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_points_from_record_batch() {
+        let points = points_from_record_batch(&record_batch(vec![10, 20])).unwrap();
+
+        assert_eq!(points.len(), 2);
+
+        assert_eq!(points[0].id, PointIdType::NumId(10));
+        assert_eq!(points[0].vector, VectorStruct::Single(vec![0.0, 1.0]));
+        assert_eq!(
+            points[0].payload,
+            Some(serde_json::from_value(json!({ "row": 0 })).unwrap()),
+        );
+
+        assert_eq!(points[1].id, PointIdType::NumId(20));
+        assert_eq!(points[1].vector, VectorStruct::Single(vec![2.0, 3.0]));
+        assert_eq!(points[1].payload, None);
+    }
+
+    #[test]
+    fn test_read_arrow_stream_in_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("points.arrow");
+
+        let batch = record_batch((0..5).collect());
+        let mut writer =
+            StreamWriter::try_new(fs_err::File::create(&path).unwrap(), &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        let mut batches = Vec::new();
+        read_points(&path, ImportFormat::Arrow, 2, |points| {
+            batches.push(points.iter().map(|point| point.id).collect::<Vec<_>>());
+            true
+        })
+        .unwrap();
+
+        let expected_batches: Vec<Vec<PointIdType>> = vec![
+            vec![0.into(), 1.into()],
+            vec![2.into(), 3.into()],
+            vec![4.into()],
+        ];
+        assert_eq!(batches, expected_batches);
+    }
+}
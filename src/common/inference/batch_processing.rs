@@ -1,8 +1,8 @@
 use std::collections::HashSet;
 
 use api::rest::{
-    ContextInput, ContextPair, DiscoverInput, Prefetch, Query, QueryGroupsRequestInternal,
-    QueryInterface, QueryRequestInternal, RecommendInput, VectorInput,
+    ArithmeticInput, ContextInput, ContextPair, DiscoverInput, Prefetch, Query,
+    QueryGroupsRequestInternal, QueryInterface, QueryRequestInternal, RecommendInput, VectorInput,
 };
 
 use super::service::{InferenceData, InferenceInput, InferenceRequest};
@@ -86,6 +86,12 @@ fn collect_recommend_input(recommend: &RecommendInput, batch: &mut BatchAccum) {
     }
 }
 
+fn collect_arithmetic_input(arithmetic: &ArithmeticInput, batch: &mut BatchAccum) {
+    for vector in arithmetic.iter() {
+        collect_vector_input(vector, batch);
+    }
+}
+
 fn collect_query(query: &Query, batch: &mut BatchAccum) {
     match query {
         Query::Nearest(nearest) => collect_vector_input(&nearest.nearest, batch),
@@ -98,6 +104,7 @@ fn collect_query(query: &Query, batch: &mut BatchAccum) {
                 }
             }
         }
+        Query::Arithmetic(arithmetic) => collect_arithmetic_input(&arithmetic.arithmetic, batch),
         Query::OrderBy(_)
         | Query::Fusion(_)
         | Query::Rrf(_)
@@ -150,6 +157,9 @@ pub fn collect_query_groups_request(request: &QueryGroupsRequestInternal) -> Bat
         with_payload: _,
         lookup_from: _,
         group_request: _,
+        with_explanation: _,
+        explanation_params: _,
+        explanation_mode: _,
     } = request;
 
     if let Some(query) = query {
@@ -180,6 +190,10 @@ pub fn collect_query_request(request: &QueryRequestInternal) -> BatchAccum {
         with_vector: _,
         with_payload: _,
         lookup_from: _,
+        with_explanation: _,
+        explanation_params: _,
+        explanation_mode: _,
+        distinct_by: _,
     } = request;
 
     if let Some(query) = query {
@@ -351,7 +365,11 @@ mod tests {
                 group_size: None,
                 limit: None,
                 with_lookup: None,
+                aggregations: None,
             },
+            with_explanation: None,
+            explanation_params: None,
+            explanation_mode: None,
         };
 
         let batch = collect_query_groups_request(&request);
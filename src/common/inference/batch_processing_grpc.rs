@@ -135,6 +135,7 @@ pub(crate) fn collect_recommend_input(
         positive,
         negative,
         strategy: _,
+        rocchio: _,
     } = recommend;
 
     for vector in positive {
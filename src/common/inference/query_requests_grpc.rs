@@ -4,8 +4,8 @@ use api::grpc::qdrant::query::Variant;
 use api::grpc::{InferenceUsage, qdrant as grpc};
 use api::rest::{self, LookupLocation, RecommendStrategy};
 use collection::operations::universal_query::collection_query::{
-    CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, Mmr, NearestWithMmr,
-    Query, VectorInputInternal, VectorQuery,
+    ArithmeticQuery, CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, Mmr,
+    NearestWithMmr, Query, VectorInputInternal, VectorQuery,
 };
 use collection::operations::universal_query::formula::FormulaInternal;
 use collection::operations::universal_query::shard_query::{FusionInternal, SampleInternal};
@@ -99,6 +99,9 @@ pub async fn convert_query_point_groups_from_grpc(
             .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
         params: params.map(From::from),
         with_lookup: with_lookup.map(TryFrom::try_from).transpose()?,
+        aggregations: None,                     // no gRPC
+        with_explanation: false,                // no gRPC
+        explanation_params: Default::default(), // no gRPC
     };
 
     Ok((request, usage.unwrap_or_default().into()))
@@ -125,6 +128,7 @@ pub async fn convert_query_points_from_grpc(
         shard_key_selector: _,
         lookup_from,
         timeout: _,
+        with_explanation,
     } = query;
 
     let mut batch = BatchAccumGrpc::new();
@@ -175,6 +179,9 @@ pub async fn convert_query_points_from_grpc(
                 .transpose()?
                 .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
             lookup_from: lookup_from.map(LookupLocation::try_from).transpose()?,
+            with_explanation: with_explanation.unwrap_or(false),
+            explanation_params: Default::default(), // no gRPC
+            distinct_by: None,                      // no gRPC
         },
         usage.unwrap_or_default().into(),
     ))
@@ -236,6 +243,7 @@ fn convert_query_with_inferred(
                 positive,
                 negative,
                 strategy,
+                rocchio,
             } = recommend;
 
             let positives = positive
@@ -265,6 +273,12 @@ fn convert_query_with_inferred(
                 RecommendStrategy::SumScores => {
                     Query::Vector(VectorQuery::RecommendSumScores(reco_query))
                 }
+                RecommendStrategy::Rocchio => {
+                    let rocchio = rocchio.map(rest::RocchioParams::from).unwrap_or_default();
+                    Query::Vector(VectorQuery::Arithmetic(ArithmeticQuery::rocchio(
+                        reco_query, rocchio,
+                    )))
+                }
             }
         }
         Variant::Discover(discover) => {
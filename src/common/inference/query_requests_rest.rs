@@ -2,11 +2,12 @@ use api::rest::models::InferenceUsage;
 use api::rest::schema as rest;
 use collection::lookup::WithLookup;
 use collection::operations::universal_query::collection_query::{
-    CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, Mmr, NearestWithMmr,
-    Query, VectorInputInternal, VectorQuery,
+    ArithmeticQuery, CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, Mmr,
+    NearestWithMmr, Query, VectorInputInternal, VectorQuery,
 };
 use collection::operations::universal_query::formula::FormulaInternal;
 use collection::operations::universal_query::shard_query::{FusionInternal, SampleInternal};
+use common::types::ExplanationParams;
 use ordered_float::OrderedFloat;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, MultiDenseVectorInternal, VectorInternal};
@@ -46,6 +47,9 @@ pub async fn convert_query_groups_request_from_rest(
         with_payload,
         lookup_from,
         group_request,
+        with_explanation,
+        explanation_params,
+        explanation_mode,
     } = request;
 
     let (inferred, usage) =
@@ -83,6 +87,12 @@ pub async fn convert_query_groups_request_from_rest(
             .group_size
             .unwrap_or(CollectionQueryRequest::DEFAULT_GROUP_SIZE),
         with_lookup: group_request.with_lookup.map(WithLookup::from),
+        aggregations: group_request.aggregations,
+        with_explanation: with_explanation.unwrap_or(false),
+        explanation_params: ExplanationParams {
+            mode: explanation_mode.unwrap_or_default().into(),
+            ..explanation_params.map(Into::into).unwrap_or_default()
+        },
     };
 
     Ok(CollectionQueryGroupsRequestWithUsage {
@@ -112,6 +122,10 @@ pub async fn convert_query_request_from_rest(
         with_vector,
         with_payload,
         lookup_from,
+        with_explanation,
+        explanation_params,
+        explanation_mode,
+        distinct_by,
     } = request;
 
     let prefetch = prefetch
@@ -140,6 +154,12 @@ pub async fn convert_query_request_from_rest(
         with_vector: with_vector.unwrap_or(CollectionQueryRequest::DEFAULT_WITH_VECTOR),
         with_payload: with_payload.unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
         lookup_from,
+        with_explanation: with_explanation.unwrap_or(false),
+        explanation_params: ExplanationParams {
+            mode: explanation_mode.unwrap_or_default().into(),
+            ..explanation_params.map(Into::into).unwrap_or_default()
+        },
+        distinct_by,
     };
     Ok(CollectionQueryRequestWithUsage {
         request: collection_query_request,
@@ -219,6 +239,7 @@ fn convert_query_with_inferred(
                 positive,
                 negative,
                 strategy,
+                rocchio,
             } = recommend.recommend;
             let positives = positive
                 .into_iter()
@@ -241,6 +262,9 @@ fn convert_query_with_inferred(
                 rest::RecommendStrategy::SumScores => {
                     Ok(Query::Vector(VectorQuery::RecommendSumScores(reco_query)))
                 }
+                rest::RecommendStrategy::Rocchio => Ok(Query::Vector(VectorQuery::Arithmetic(
+                    ArithmeticQuery::rocchio(reco_query, rocchio.unwrap_or_default()),
+                ))),
             }
         }
         rest::Query::Discover(discover) => {
@@ -266,6 +290,28 @@ fn convert_query_with_inferred(
                 context,
             ))))
         }
+        rest::Query::Arithmetic(arithmetic) => {
+            let rest::ArithmeticInput {
+                add,
+                subtract,
+                weights,
+            } = arithmetic.arithmetic;
+            let add = add
+                .into_iter()
+                .flatten()
+                .map(|v| convert_vector_input_with_inferred(v, inferred))
+                .collect::<Result<Vec<_>, _>>()?;
+            let subtract = subtract
+                .into_iter()
+                .flatten()
+                .map(|v| convert_vector_input_with_inferred(v, inferred))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Query::Vector(VectorQuery::Arithmetic(ArithmeticQuery {
+                add,
+                subtract,
+                weights,
+            })))
+        }
         rest::Query::OrderBy(order_by) => Ok(Query::OrderBy(OrderBy::from(order_by.order_by))),
         rest::Query::Fusion(fusion) => Ok(Query::Fusion(FusionInternal::from(fusion.fusion))),
         rest::Query::Rrf(rrf) => Ok(Query::Fusion(FusionInternal::from(rrf.rrf))),
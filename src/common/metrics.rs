@@ -211,6 +211,9 @@ impl CollectionsTelemetry {
         // Points per collection
         let mut points_per_collection = Vec::with_capacity(num_collections);
 
+        // Expired points per collection
+        let mut expired_points_per_collection = Vec::with_capacity(num_collections);
+
         // Vectors excluded from index-only requests.
         let mut indexed_only_excluded = Vec::with_capacity(num_collections);
 
@@ -301,6 +304,11 @@ impl CollectionsTelemetry {
                 &[("id", &collection.id)],
             ));
 
+            expired_points_per_collection.push(counter(
+                collection.count_expired_points() as f64,
+                &[("id", &collection.id)],
+            ));
+
             for (vec_name, count) in collection.count_points_per_vector() {
                 vector_count_by_name.push(gauge(
                     count as f64,
@@ -455,6 +463,14 @@ impl CollectionsTelemetry {
             prefix,
         ));
 
+        metrics.push_metric(metric_family(
+            "collection_expired_points",
+            "total amount of points deleted due to expiration since the last start",
+            MetricType::COUNTER,
+            expired_points_per_collection,
+            prefix,
+        ));
+
         metrics.push_metric(metric_family(
             "collection_dead_replicas",
             "total amount of shard replicas in non-active state",
@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use collection::operations::types::CoreSearchRequest;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use ordered_float::OrderedFloat;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{ScoredPoint, WithVector};
+use shard::query::MmrInternal;
+use shard::query::query_enum::QueryEnum;
+use shard::search::SearchMmr;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+
+use crate::common::explain::with_explained_vector;
+
+/// MMR reranking of the results of a search request.
+///
+/// The request is widened to the MMR candidates, which are then reranked
+/// with the vectors they were searched with, and truncated to the requested limit.
+#[derive(Debug)]
+pub(super) struct MmrRerank {
+    mmr: MmrInternal,
+    limit: usize,
+    offset: usize,
+    /// Remove the vector used for reranking from the results, as it wasn't requested
+    strip_vector: bool,
+}
+
+impl MmrRerank {
+    /// Returns `None` if the request doesn't ask for MMR.
+    ///
+    /// Otherwise, widens the request to the MMR candidates and forces their vectors into the results.
+    pub(super) fn prepare(request: &mut CoreSearchRequest) -> Result<Option<Self>, StorageError> {
+        let Some(SearchMmr {
+            lambda,
+            candidates_limit,
+        }) = request.mmr.take()
+        else {
+            return Ok(None);
+        };
+
+        let QueryEnum::Nearest(named) = &request.query else {
+            return Err(StorageError::bad_input(
+                "MMR is only supported for nearest neighbors search",
+            ));
+        };
+
+        let using = request.query.get_vector_name().to_owned();
+        let with_vector = request
+            .with_vector
+            .clone()
+            .unwrap_or(WithVector::Bool(false));
+        let strip_vector = match &with_vector {
+            WithVector::Bool(requested) => !requested,
+            WithVector::Selector(names) => !names.contains(&using),
+        };
+        request.with_vector = Some(with_explained_vector(&with_vector, &using));
+
+        let rerank = Self {
+            mmr: MmrInternal {
+                vector: named.query.clone(),
+                using,
+                lambda: OrderedFloat(lambda),
+                candidates_limit,
+            },
+            limit: request.limit,
+            offset: request.offset,
+            strip_vector,
+        };
+
+        request.limit = candidates_limit.max(request.limit + request.offset);
+        request.offset = 0;
+
+        Ok(Some(rerank))
+    }
+
+    pub(super) async fn rerank(
+        self,
+        toc: &TableOfContent,
+        collection_name: &str,
+        points: Vec<ScoredPoint>,
+        access: &Access,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        let Self {
+            mmr,
+            limit,
+            offset,
+            strip_vector,
+        } = self;
+
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new())?;
+        let collection = toc.get_collection(&collection_pass).await?;
+
+        let vector_name = mmr.using.clone();
+        let reranked = collection
+            .mmr_rerank(points, mmr, limit + offset, timeout, hw_measurement_acc)
+            .await?;
+
+        Ok(reranked
+            .into_iter()
+            .skip(offset)
+            .map(|mut point| {
+                if strip_vector {
+                    VectorStructInternal::take_opt(&mut point.vector, &vector_name);
+                }
+                point
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::{NamedQuery, VectorInternal};
+    use segment::vector_storage::query::RecoQuery;
+    use shard::query::mmr::DEFAULT_MMR_LAMBDA;
+
+    use super::*;
+
+    #[test]
+    fn test_mmr_rerank_widens_request() {
+        let mut request = CoreSearchRequest {
+            query: QueryEnum::Nearest(NamedQuery {
+                query: VectorInternal::Dense(vec![1.0, 0.0]),
+                using: Some("image".to_owned()),
+            }),
+            filter: None,
+            params: None,
+            limit: 5,
+            offset: 2,
+            with_payload: None,
+            with_vector: Some(WithVector::Selector(vec!["text".to_owned()])),
+            score_threshold: None,
+            with_explanation: false,
+            with_explanation_bundle: false,
+            explanation_verbosity: Default::default(),
+            explanation_params: Default::default(),
+            mmr: Some(SearchMmr::new(Some(0.8), Some(20), 5)),
+            distinct_by: None,
+        };
+
+        let rerank = MmrRerank::prepare(&mut request).unwrap().unwrap();
+        assert_eq!(rerank.limit, 5);
+        assert_eq!(rerank.offset, 2);
+        assert!(rerank.strip_vector);
+        assert!((rerank.mmr.lambda.0 - 0.2).abs() < 1e-6);
+        assert_eq!(rerank.mmr.using, "image");
+
+        // Candidates are searched in place of the requested page, with their vectors
+        assert_eq!(request.limit, 20);
+        assert_eq!(request.offset, 0);
+        assert_eq!(request.mmr, None);
+        assert_eq!(
+            request.with_vector,
+            Some(WithVector::Selector(vec![
+                "text".to_owned(),
+                "image".to_owned()
+            ])),
+        );
+
+        // Never search fewer candidates than the requested page
+        request.limit = 30;
+        request.mmr = Some(SearchMmr::new(None, None, 30));
+        let rerank = MmrRerank::prepare(&mut request).unwrap().unwrap();
+        assert_eq!(rerank.mmr.lambda.0, DEFAULT_MMR_LAMBDA);
+        assert!(!rerank.strip_vector);
+        assert_eq!(request.limit, 30);
+
+        // Only nearest neighbors search can be reranked
+        let mut request = CoreSearchRequest {
+            query: QueryEnum::RecommendBestScore(NamedQuery {
+                query: RecoQuery::new(vec![VectorInternal::Dense(vec![1.0, 0.0])], vec![]),
+                using: None,
+            }),
+            mmr: Some(SearchMmr::new(None, None, 5)),
+            ..request
+        };
+        assert!(MmrRerank::prepare(&mut request).is_err());
+    }
+}
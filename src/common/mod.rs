@@ -1,14 +1,22 @@
 pub mod auth;
 pub mod collections;
 pub mod debugger;
+pub mod distinct;
 pub mod error_reporting;
+pub mod explain;
+pub mod export;
+pub mod federated_search;
 pub mod health;
 pub mod helpers;
+pub mod histogram;
 pub mod http_client;
+pub mod import;
 pub mod inference;
 pub mod metrics;
+pub mod mmr;
 pub mod pyroscope_state;
 pub mod query;
+pub mod recall;
 pub mod snapshots;
 pub mod stacktrace;
 pub mod strict_mode;
@@ -1,4 +1,6 @@
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use api::rest::SearchGroupsRequestInternal;
 use collection::collection::distance_matrix::*;
@@ -9,17 +11,24 @@ use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::operations::universal_query::collection_query::*;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
-use common::types::ScoreExplanation;
-use segment::data_types::vectors::{DenseVector, VectorInternal};
-use segment::spaces::explainability::compute_explanation;
-use segment::types::{Distance, ScoredPoint, WithVector};
-use shard::query::query_enum::QueryEnum;
+use futures::StreamExt as _;
+use futures::stream::BoxStream;
+use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::types::{PointIdType, ScoredPoint, WithPayloadInterface, WithVector};
 use shard::retrieve::record_internal::RecordInternal;
 use shard::search::CoreSearchRequestBatch;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::Access;
 
+use crate::common::distinct::{DistinctBy, distinct_batch};
+use crate::common::explain::{
+    EXPLANATION_FETCH_CHUNK_SIZE, ExplanationDistances, FusionReplay, SearchExplainer,
+    explain_batch, explain_fusion_batch, explain_groups, explain_in_chunks,
+    explained_points_stream,
+};
+use crate::common::mmr::MmrRerank;
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_core_search_points(
     toc: &TableOfContent,
@@ -31,137 +40,223 @@ pub async fn do_core_search_points(
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
-    let with_explanation = request.with_explanation;
-    let original_with_vector = request.with_vector.clone();
-    
-    // if explanation is requested, we need vectors to compute it
-    if with_explanation {
-        request.with_vector = Some(WithVector::Bool(true));
-    }
-    
-    let query_vector: Option<DenseVector> = if with_explanation {
-        extract_query_vector(&request.query)
-    } else {
-        None
-    };
-    
-    let batch_res = do_core_search_batch_points(
+    let mut explainer = SearchExplainer::prepare(
         toc,
         collection_name,
-        CoreSearchRequestBatch {
-            searches: vec![request],
-        },
-        read_consistency,
-        shard_selection.clone(),
-        access.clone(),
-        timeout,
-        hw_measurement_acc,
+        &mut request,
+        &access,
+        &mut ExplanationDistances::default(),
     )
-    .await?;
-    
+    .await;
+    if let Some(explainer) = &mut explainer {
+        explainer.fetch_vectors_in_chunks(&mut request);
+    }
+    let distinct = DistinctBy::prepare(&mut request);
+    let mmr = MmrRerank::prepare(&mut request)?;
+
+    let batch_res = toc
+        .core_search_batch_cached(
+            collection_name,
+            CoreSearchRequestBatch {
+                searches: vec![request],
+            },
+            read_consistency,
+            shard_selection.clone(),
+            access.clone(),
+            timeout,
+            hw_measurement_acc.clone(),
+        )
+        .await?;
+
     let mut results = batch_res
         .into_iter()
         .next()
         .ok_or_else(|| StorageError::service_error("Empty search result"))?;
-    
-    // Compute explanations if requested
-    if with_explanation {
-        if let Some(ref query_vec) = query_vector {
-            // Get the distance metric from collection config
-            let distance = get_collection_distance(toc, collection_name, &access, &shard_selection).await
-                .unwrap_or(Distance::Cosine);
-            
+
+    if let Some(mmr) = mmr {
+        results = mmr
+            .rerank(
+                toc,
+                collection_name,
+                results,
+                &access,
+                timeout,
+                hw_measurement_acc.clone(),
+            )
+            .await?;
+    }
+    if let Some(distinct) = distinct {
+        results = distinct.apply(results);
+    }
+
+    let Some(explainer) = explainer else {
+        return Ok(results);
+    };
+
+    match &explainer.chunked_fetch {
+        None => {
             for point in &mut results {
-                if let Some(ref vector_struct) = point.vector {
-                    // Try to get the default vector or first named vector
-                    if let Some(result_vec) = extract_dense_vector_from_struct(vector_struct) {
-                        let explanation = compute_explanation_for_distance(
-                            query_vec,
-                            &result_vec,
-                            distance,
-                            10, // top 10
-                        );
-                        point.score_explanation = Some(explanation);
-                    }
-                }
+                explainer.explain(point);
             }
         }
-        
-        if original_with_vector.is_none() || matches!(original_with_vector, Some(WithVector::Bool(false))) {
-            for point in &mut results {
-                point.vector = None;
-            }
+        Some(vector_name) => {
+            let fetch_vectors = |ids| {
+                toc.retrieve(
+                    collection_name,
+                    PointRequestInternal {
+                        ids,
+                        with_payload: Some(WithPayloadInterface::Bool(false)),
+                        with_vector: WithVector::Selector(vec![vector_name.clone()]),
+                    },
+                    read_consistency,
+                    timeout,
+                    shard_selection.clone(),
+                    access.clone(),
+                    hw_measurement_acc.clone(),
+                )
+            };
+            explain_in_chunks(
+                &explainer,
+                &mut results,
+                EXPLANATION_FETCH_CHUNK_SIZE,
+                fetch_vectors,
+            )
+            .await?;
         }
     }
-    
+
     Ok(results)
 }
 
-/// Extract the query vector from a QueryEnum (for Nearest queries with dense vectors)
-fn extract_query_vector(query: &QueryEnum) -> Option<DenseVector> {
-    match query {
-        QueryEnum::Nearest(named_query) => {
-            match &named_query.query {
-                VectorInternal::Dense(dense) => Some(dense.clone()),
-                VectorInternal::Sparse(_) | VectorInternal::MultiDense(_) => None,
-            }
-        }
-        _ => None, // Only Nearest queries have vectors
+/// Same as [`do_core_search_points`], but explanations are computed as the returned stream is
+/// consumed. Unless requested, vectors are not returned by the search, they are fetched
+/// [`EXPLANATION_FETCH_CHUNK_SIZE`] at a time to explain the next chunk of results.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_core_search_points_stream(
+    toc: &Arc<TableOfContent>,
+    collection_name: &str,
+    mut request: CoreSearchRequest,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<BoxStream<'static, Result<ScoredPoint, StorageError>>, StorageError> {
+    let mut explainer = SearchExplainer::prepare(
+        toc,
+        collection_name,
+        &mut request,
+        &access,
+        &mut ExplanationDistances::default(),
+    )
+    .await;
+    if let Some(explainer) = &mut explainer {
+        explainer.fetch_vectors_after_search(&mut request);
     }
-}
+    let distinct = DistinctBy::prepare(&mut request);
+    let mmr = MmrRerank::prepare(&mut request)?;
 
-/// Extract a dense vector from a VectorStruct
-fn extract_dense_vector_from_struct(vector_struct: &segment::data_types::vectors::VectorStructInternal) -> Option<DenseVector> {
-    use segment::data_types::vectors::VectorStructInternal;
-    match vector_struct {
-        VectorStructInternal::Single(dense) => Some(dense.clone()), // Single is already a DenseVector
-        VectorStructInternal::MultiDense(_) => None, // Multi-dense not supported yet
-        VectorStructInternal::Named(named_map) => {
-            // Get the first dense vector from named vectors
-            for vec in named_map.values() {
-                if let VectorInternal::Dense(dense) = vec {
-                    return Some(dense.clone());
-                }
-            }
-            None
-        }
+    let batch_res = toc
+        .core_search_batch_cached(
+            collection_name,
+            CoreSearchRequestBatch {
+                searches: vec![request],
+            },
+            read_consistency,
+            shard_selection.clone(),
+            access.clone(),
+            timeout,
+            hw_measurement_acc.clone(),
+        )
+        .await?;
+
+    let mut results = batch_res
+        .into_iter()
+        .next()
+        .ok_or_else(|| StorageError::service_error("Empty search result"))?;
+
+    if let Some(mmr) = mmr {
+        results = mmr
+            .rerank(
+                toc,
+                collection_name,
+                results,
+                &access,
+                timeout,
+                hw_measurement_acc.clone(),
+            )
+            .await?;
+    }
+    if let Some(distinct) = distinct {
+        results = distinct.apply(results);
     }
-}
 
-/// Get the distance metric for a collection
-async fn get_collection_distance(
-    toc: &TableOfContent,
-    collection_name: &str,
-    access: &Access,
-    shard_selection: &ShardSelectorInternal,
-) -> Option<Distance> {
-    // Try to get collection info to determine the distance metric
-    // This is a simplified approach - in a real implementation you might cache this
-    let _ = (toc, collection_name, access, shard_selection);
-    // For now, return None and let the caller use a default
-    // A full implementation would query the collection config
-    None
-}
+    let Some(explainer) = explainer else {
+        return Ok(futures::stream::iter(results).map(Ok).boxed());
+    };
+
+    let Some(vector_name) = explainer.chunked_fetch.clone() else {
+        // Requested vectors are returned by the search, no need to fetch them
+        let stream = futures::stream::iter(results).map(move |mut point| {
+            explainer.explain(&mut point);
+            Ok(point)
+        });
+        return Ok(stream.boxed());
+    };
 
-/// Compute explanation based on the distance metric
-fn compute_explanation_for_distance(
-    query: &[f32],
-    result: &[f32],
-    distance: Distance,
-    top_n: usize,
-) -> ScoreExplanation {
-    compute_explanation(distance, query, result, Some(top_n))
+    let toc = toc.clone();
+    let collection_name = collection_name.to_owned();
+    let fetch_vectors = move |ids| {
+        let toc = toc.clone();
+        let collection_name = collection_name.clone();
+        let request = PointRequestInternal {
+            ids,
+            with_payload: Some(WithPayloadInterface::Bool(false)),
+            with_vector: WithVector::Selector(vec![vector_name.clone()]),
+        };
+        let shard_selection = shard_selection.clone();
+        let access = access.clone();
+        let hw_measurement_acc = hw_measurement_acc.clone();
+        async move {
+            toc.retrieve(
+                &collection_name,
+                request,
+                read_consistency,
+                timeout,
+                shard_selection,
+                access,
+                hw_measurement_acc,
+            )
+            .await
+        }
+    };
+
+    Ok(explained_points_stream(results, explainer, fetch_vectors))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_search_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
-    requests: Vec<(CoreSearchRequest, ShardSelectorInternal)>,
+    mut requests: Vec<(CoreSearchRequest, ShardSelectorInternal)>,
     read_consistency: Option<ReadConsistency>,
     access: Access,
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
-) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+    partial_ok: bool,
+) -> Result<PartialSearchResults, StorageError> {
+    let mut distances = ExplanationDistances::default();
+    let mut explainers = Vec::with_capacity(requests.len());
+    let mut distincts = Vec::with_capacity(requests.len());
+    let mut mmrs = Vec::with_capacity(requests.len());
+    for (request, _) in &mut requests {
+        explainers.push(
+            SearchExplainer::prepare(toc, collection_name, request, &access, &mut distances).await,
+        );
+        distincts.push(DistinctBy::prepare(request));
+        mmrs.push(MmrRerank::prepare(request)?);
+    }
+
     let requests = batch_requests::<
         (CoreSearchRequest, ShardSelectorInternal),
         ShardSelectorInternal,
@@ -183,7 +278,8 @@ pub async fn do_search_batch_points(
                 searches: core_requests,
             };
 
-            let req = toc.core_search_batch(
+            let req = core_search_batch_with_partial(
+                toc,
                 collection_name,
                 core_batch,
                 read_consistency,
@@ -191,6 +287,7 @@ pub async fn do_search_batch_points(
                 access.clone(),
                 timeout,
                 hw_measurement_acc.clone(),
+                partial_ok,
             );
             res.push(req);
             Ok(())
@@ -198,22 +295,65 @@ pub async fn do_search_batch_points(
     )?;
 
     let results = futures::future::try_join_all(requests).await?;
-    let flatten_results: Vec<Vec<_>> = results.into_iter().flatten().collect();
-    Ok(flatten_results)
+    let mut timed_out_shards = Vec::new();
+    let mut flatten_results: Vec<Vec<_>> = Vec::new();
+    for result in results {
+        flatten_results.extend(result.results);
+        timed_out_shards.extend(result.timed_out_shards);
+    }
+    timed_out_shards.sort_unstable();
+    timed_out_shards.dedup();
+
+    for (mmr, points) in mmrs.into_iter().zip(&mut flatten_results) {
+        let Some(mmr) = mmr else {
+            continue;
+        };
+        *points = mmr
+            .rerank(
+                toc,
+                collection_name,
+                std::mem::take(points),
+                &access,
+                timeout,
+                hw_measurement_acc.clone(),
+            )
+            .await?;
+    }
+    distinct_batch(distincts, &mut flatten_results);
+    explain_batch(&explainers, &mut flatten_results);
+    Ok(PartialSearchResults {
+        results: flatten_results,
+        timed_out_shards,
+    })
+}
+
+/// Time left until `deadline`, if any
+fn remaining_time(deadline: Option<Instant>) -> Option<Duration> {
+    deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()))
 }
 
 #[allow(clippy::too_many_arguments)]
 pub async fn do_core_search_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
-    request: CoreSearchRequestBatch,
+    mut request: CoreSearchRequestBatch,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
-) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
-    toc.core_search_batch(
+    partial_ok: bool,
+) -> Result<PartialSearchResults, StorageError> {
+    let mut distances = ExplanationDistances::default();
+    let mut explainers = Vec::with_capacity(request.searches.len());
+    for search in &mut request.searches {
+        explainers.push(
+            SearchExplainer::prepare(toc, collection_name, search, &access, &mut distances).await,
+        );
+    }
+
+    let mut results = core_search_batch_with_partial(
+        toc,
         collection_name,
         request,
         read_consistency,
@@ -221,31 +361,86 @@ pub async fn do_core_search_batch_points(
         access,
         timeout,
         hw_measurement_acc,
+        partial_ok,
     )
-    .await
+    .await?;
+    explain_batch(&explainers, &mut results.results);
+    Ok(results)
+}
+
+/// Search through the search cache, or bypass it if results of timed out shards may be left out,
+/// so incomplete results are never cached.
+#[allow(clippy::too_many_arguments)]
+async fn core_search_batch_with_partial(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CoreSearchRequestBatch,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: HwMeasurementAcc,
+    partial_ok: bool,
+) -> Result<PartialSearchResults, StorageError> {
+    if partial_ok {
+        toc.core_search_batch_partial(
+            collection_name,
+            request,
+            read_consistency,
+            shard_selection,
+            access,
+            timeout,
+            hw_measurement_acc,
+        )
+        .await
+    } else {
+        toc.core_search_batch_cached(
+            collection_name,
+            request,
+            read_consistency,
+            shard_selection,
+            access,
+            timeout,
+            hw_measurement_acc,
+        )
+        .await
+        .map(PartialSearchResults::complete)
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
 pub async fn do_search_point_groups(
     toc: &TableOfContent,
     collection_name: &str,
-    request: SearchGroupsRequestInternal,
+    mut request: SearchGroupsRequestInternal,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
 ) -> Result<GroupsResult, StorageError> {
-    toc.group(
-        collection_name,
-        GroupRequest::from(request),
-        read_consistency,
-        shard_selection,
-        access,
-        timeout,
-        hw_measurement_acc,
-    )
-    .await
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    let explainer =
+        SearchExplainer::prepare_groups(toc, collection_name, &mut request, &access).await;
+
+    let mut result = toc
+        .group(
+            collection_name,
+            GroupRequest::from(request),
+            read_consistency,
+            shard_selection,
+            access,
+            timeout,
+            hw_measurement_acc,
+        )
+        .await?;
+
+    if let Some(explainer) = explainer {
+        explain_groups(&explainer, &mut result, deadline);
+    }
+
+    Ok(result)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -280,8 +475,10 @@ pub async fn do_discover_batch_points(
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
-    let requests = request
-        .searches
+    let DiscoverRequestBatch { searches, context } = request;
+    let shared_context = context.unwrap_or_default();
+
+    let requests = searches
         .into_iter()
         .map(|req| {
             let shard_selector = match req.shard_key {
@@ -289,7 +486,10 @@ pub async fn do_discover_batch_points(
                 Some(shard_key) => ShardSelectorInternal::from(shard_key),
             };
 
-            (req.discover_request, shard_selector)
+            let mut discover_request = req.discover_request;
+            discover_request.extend_context(&shared_context);
+
+            (discover_request, shard_selector)
         })
         .collect();
 
@@ -304,6 +504,31 @@ pub async fn do_discover_batch_points(
     .await
 }
 
+/// Compute several facets at once, e.g. over different payload keys.
+pub async fn do_facet_points_batch(
+    toc: &TableOfContent,
+    collection_name: &str,
+    requests: Vec<(FacetParams, ShardSelectorInternal)>,
+    read_consistency: Option<ReadConsistency>,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<Vec<FacetResponse>, StorageError> {
+    let facets = requests.into_iter().map(|(request, shard_selection)| {
+        toc.facet(
+            collection_name,
+            request,
+            shard_selection,
+            read_consistency,
+            access.clone(),
+            timeout,
+            hw_measurement_acc.clone(),
+        )
+    });
+
+    futures::future::try_join_all(facets).await
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_count_points(
     toc: &TableOfContent,
@@ -350,6 +575,43 @@ pub async fn do_get_points(
     .await
 }
 
+/// Retrieve points from multiple collections at once.
+///
+/// Collections are queried concurrently, a failure in one of them does not affect the others.
+/// Hardware usage is accounted per collection, with counters made by `hw_measurement_acc`.
+pub async fn do_get_points_multi(
+    toc: &TableOfContent,
+    requests: HashMap<String, PointRequestInternal>,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+    access: Access,
+    hw_measurement_acc: impl Fn(&str) -> HwMeasurementAcc,
+) -> HashMap<String, Result<Vec<RecordInternal>, StorageError>> {
+    let retrievals = requests.into_iter().map(|(collection_name, request)| {
+        let hw_measurement_acc = hw_measurement_acc(&collection_name);
+        let access = access.clone();
+        async move {
+            let records = do_get_points(
+                toc,
+                &collection_name,
+                request,
+                read_consistency,
+                timeout,
+                ShardSelectorInternal::All,
+                access,
+                hw_measurement_acc,
+            )
+            .await;
+            (collection_name, records)
+        }
+    });
+
+    futures::future::join_all(retrievals)
+        .await
+        .into_iter()
+        .collect()
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_scroll_points(
     toc: &TableOfContent,
@@ -373,76 +635,279 @@ pub async fn do_scroll_points(
     .await
 }
 
+/// Default number of points fetched per page by [`do_scroll_points_stream`]
+pub const DEFAULT_SCROLL_STREAM_BATCH_SIZE: usize = 1000;
+
+/// Position of a streamed scroll, see [`do_scroll_points_stream`]
+#[derive(Clone, Debug)]
+struct ScrollCursor {
+    /// Offset of the next page
+    offset: Option<PointIdType>,
+    /// Number of points left to stream, if the request is limited
+    remaining: Option<usize>,
+    batch_size: usize,
+    /// The last page had no next page offset
+    exhausted: bool,
+}
+
+impl ScrollCursor {
+    fn new(request: &ScrollRequestInternal, batch_size: usize) -> Self {
+        Self {
+            offset: request.offset,
+            remaining: request.limit,
+            batch_size: batch_size.max(1),
+            exhausted: false,
+        }
+    }
+
+    /// Limit of the next page, or `None` once the scroll is over
+    fn next_limit(&self) -> Option<usize> {
+        if self.exhausted {
+            return None;
+        }
+        match self.remaining {
+            Some(0) => None,
+            Some(remaining) => Some(remaining.min(self.batch_size)),
+            None => Some(self.batch_size),
+        }
+    }
+
+    fn advance(&mut self, page: &ScrollResult) {
+        self.offset = page.next_page_offset;
+        self.exhausted = page.next_page_offset.is_none();
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_sub(page.points.len());
+        }
+    }
+}
+
+/// Scroll all points matching the request, as a stream of pages of at most `batch_size` points.
+///
+/// Pages are only fetched as the stream is polled, each one starting from the `next_page_offset`
+/// of the previous one, so a slow consumer never holds more than a single page in memory.
+/// The `limit` of the request bounds the total number of streamed points, instead of a single page.
+/// The `timeout` applies to each page.
+#[allow(clippy::too_many_arguments)]
+pub fn do_scroll_points_stream(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: ScrollRequestInternal,
+    batch_size: usize,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<BoxStream<'static, Result<ScrollResult, StorageError>>, StorageError> {
+    if request.order_by.is_some() {
+        return Err(StorageError::bad_input(
+            "Streamed scroll doesn't support order_by, as ordered pages can't be resumed from an offset",
+        ));
+    }
+
+    let cursor = ScrollCursor::new(&request, batch_size);
+
+    let pages = futures::stream::try_unfold(cursor, move |mut cursor| {
+        let toc = toc.clone();
+        let collection_name = collection_name.clone();
+        let shard_selection = shard_selection.clone();
+        let access = access.clone();
+        let hw_measurement_acc = hw_measurement_acc.clone();
+        let mut request = request.clone();
+
+        async move {
+            let Some(limit) = cursor.next_limit() else {
+                return Ok(None);
+            };
+            request.offset = cursor.offset;
+            request.limit = Some(limit);
+
+            let page = toc
+                .scroll(
+                    &collection_name,
+                    request,
+                    read_consistency,
+                    timeout,
+                    shard_selection,
+                    access,
+                    hw_measurement_acc,
+                )
+                .await?;
+
+            cursor.advance(&page);
+            Ok(Some((page, cursor)))
+        }
+    });
+
+    Ok(pages.boxed())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_query_points(
     toc: &TableOfContent,
     collection_name: &str,
-    request: CollectionQueryRequest,
+    mut request: CollectionQueryRequest,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
-    let requests = vec![(request, shard_selection)];
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    let fusion_replay = FusionReplay::prepare(&request);
+    let explainer = SearchExplainer::prepare_query(
+        toc,
+        collection_name,
+        &mut request,
+        &access,
+        &shard_selection,
+        &mut ExplanationDistances::default(),
+    )
+    .await;
+    let distinct = DistinctBy::prepare_query(&mut request);
+
+    let requests = vec![(request, shard_selection.clone())];
     let batch_res = toc
         .query_batch(
             collection_name,
             requests,
             read_consistency,
-            access,
+            access.clone(),
             timeout,
-            hw_measurement_acc,
+            hw_measurement_acc.clone(),
         )
         .await?;
-    batch_res
+    let mut results = batch_res
         .into_iter()
         .next()
-        .ok_or_else(|| StorageError::service_error("Empty query result"))
+        .ok_or_else(|| StorageError::service_error("Empty query result"))?;
+    if let Some(distinct) = distinct {
+        results = distinct.apply(results);
+    }
+
+    if let Some(explainer) = explainer {
+        for point in &mut results {
+            explainer.explain(point);
+        }
+    }
+
+    explain_fusion_batch(
+        toc,
+        collection_name,
+        vec![(fusion_replay, shard_selection)],
+        std::slice::from_mut(&mut results),
+        read_consistency,
+        access,
+        remaining_time(deadline),
+        hw_measurement_acc,
+    )
+    .await;
+
+    Ok(results)
 }
 
 #[allow(clippy::too_many_arguments)]
 pub async fn do_query_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
-    requests: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+    mut requests: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
     read_consistency: Option<ReadConsistency>,
     access: Access,
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
-    toc.query_batch(
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    let fusion_replays: Vec<_> = requests
+        .iter()
+        .map(|(request, shard_selection)| (FusionReplay::prepare(request), shard_selection.clone()))
+        .collect();
+    let mut distances = ExplanationDistances::default();
+    let mut explainers = Vec::with_capacity(requests.len());
+    let mut distincts = Vec::with_capacity(requests.len());
+    for (request, shard_selection) in &mut requests {
+        explainers.push(
+            SearchExplainer::prepare_query(
+                toc,
+                collection_name,
+                request,
+                &access,
+                shard_selection,
+                &mut distances,
+            )
+            .await,
+        );
+        distincts.push(DistinctBy::prepare_query(request));
+    }
+
+    let mut results = toc
+        .query_batch(
+            collection_name,
+            requests,
+            read_consistency,
+            access.clone(),
+            timeout,
+            hw_measurement_acc.clone(),
+        )
+        .await?;
+    distinct_batch(distincts, &mut results);
+    explain_batch(&explainers, &mut results);
+
+    explain_fusion_batch(
+        toc,
         collection_name,
-        requests,
+        fusion_replays,
+        &mut results,
         read_consistency,
         access,
-        timeout,
+        remaining_time(deadline),
         hw_measurement_acc,
     )
-    .await
+    .await;
+
+    Ok(results)
 }
 
 #[allow(clippy::too_many_arguments)]
 pub async fn do_query_point_groups(
     toc: &TableOfContent,
     collection_name: &str,
-    request: CollectionQueryGroupsRequest,
+    mut request: CollectionQueryGroupsRequest,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
 ) -> Result<GroupsResult, StorageError> {
-    toc.group(
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    let explainer = SearchExplainer::prepare_query_groups(
+        toc,
         collection_name,
-        GroupRequest::from(request),
-        read_consistency,
-        shard_selection,
-        access,
-        timeout,
-        hw_measurement_acc,
+        &mut request,
+        &access,
+        &shard_selection,
     )
-    .await
+    .await;
+
+    let mut result = toc
+        .group(
+            collection_name,
+            GroupRequest::from(request),
+            read_consistency,
+            shard_selection,
+            access,
+            timeout,
+            hw_measurement_acc,
+        )
+        .await?;
+
+    if let Some(explainer) = explainer {
+        explain_groups(&explainer, &mut result, deadline);
+    }
+
+    Ok(result)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -467,3 +932,53 @@ pub async fn do_search_points_matrix(
     )
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_cursor_pages() {
+        let page = |ids: std::ops::Range<u64>, next: Option<u64>| ScrollResult {
+            points: ids
+                .map(|id| api::rest::Record {
+                    id: id.into(),
+                    payload: None,
+                    vector: None,
+                    shard_key: None,
+                    order_value: None,
+                })
+                .collect(),
+            next_page_offset: next.map(PointIdType::from),
+        };
+
+        // Limited scroll: pages of `batch_size`, the last one truncated to the limit
+        let request = ScrollRequestInternal {
+            offset: Some(10.into()),
+            limit: Some(5),
+            ..Default::default()
+        };
+        let mut cursor = ScrollCursor::new(&request, 2);
+        assert_eq!(cursor.offset, Some(10.into()));
+        assert_eq!(cursor.next_limit(), Some(2));
+        cursor.advance(&page(10..12, Some(12)));
+        assert_eq!(cursor.offset, Some(12.into()));
+        assert_eq!(cursor.next_limit(), Some(2));
+        cursor.advance(&page(12..14, Some(14)));
+        assert_eq!(cursor.next_limit(), Some(1));
+        cursor.advance(&page(14..15, Some(15)));
+        assert_eq!(cursor.next_limit(), None);
+
+        // Unlimited scroll: ends with the last page of the collection
+        let request = ScrollRequestInternal {
+            limit: None,
+            ..Default::default()
+        };
+        let mut cursor = ScrollCursor::new(&request, 3);
+        assert_eq!(cursor.next_limit(), Some(3));
+        cursor.advance(&page(0..3, Some(3)));
+        assert_eq!(cursor.next_limit(), Some(3));
+        cursor.advance(&page(3..4, None));
+        assert_eq!(cursor.next_limit(), None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::CoreSearchRequest;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use segment::types::{PointIdType, QuantizationSearchParams, ScoredPoint, SearchParams};
+use shard::search::CoreSearchRequestBatch;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+use crate::common::query::do_core_search_batch_points;
+
+/// Results of the same search done with the index and as exact kNN
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchRecall {
+    /// Share of the exact results also found by the approximate search
+    pub recall: f32,
+    /// Ids of the exact results missed by the approximate search, best first
+    pub missed: Vec<PointIdType>,
+    pub approximate: Vec<ScoredPoint>,
+    pub exact: Vec<ScoredPoint>,
+}
+
+/// Run the search both via the index and as exact kNN, ignoring quantization,
+/// to report how many of the true nearest neighbors the index finds.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_search_recall(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CoreSearchRequest,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<SearchRecall, StorageError> {
+    let exact_request = CoreSearchRequest {
+        params: Some(ground_truth_params(request.params)),
+        ..request.clone()
+    };
+
+    let mut results = do_core_search_batch_points(
+        toc,
+        collection_name,
+        CoreSearchRequestBatch {
+            searches: vec![request, exact_request],
+        },
+        read_consistency,
+        shard_selection,
+        access,
+        timeout,
+        hw_measurement_acc,
+        false,
+    )
+    .await?
+    .results
+    .into_iter();
+
+    let (Some(approximate), Some(exact)) = (results.next(), results.next()) else {
+        return Err(StorageError::service_error(
+            "Expected results for both approximate and exact search",
+        ));
+    };
+    let (recall, missed) = recall_of(&approximate, &exact);
+
+    Ok(SearchRecall {
+        recall,
+        missed,
+        approximate,
+        exact,
+    })
+}
+
+/// Search params which make a search return the true nearest neighbors
+pub fn ground_truth_params(params: Option<SearchParams>) -> SearchParams {
+    SearchParams {
+        exact: true,
+        indexed_only: false,
+        quantization: Some(QuantizationSearchParams {
+            ignore: true,
+            rescore: None,
+            oversampling: None,
+            compare_explanations: false,
+        }),
+        ..params.unwrap_or_default()
+    }
+}
+
+/// Share of `exact` points found in `approximate`, and the ones which are missing
+fn recall_of(approximate: &[ScoredPoint], exact: &[ScoredPoint]) -> (f32, Vec<PointIdType>) {
+    if exact.is_empty() {
+        return (1.0, Vec::new());
+    }
+
+    let found: HashSet<_> = approximate.iter().map(|point| point.id).collect();
+    let missed: Vec<_> = exact
+        .iter()
+        .map(|point| point.id)
+        .filter(|id| !found.contains(id))
+        .collect();
+    let recall = (exact.len() - missed.len()) as f32 / exact.len() as f32;
+
+    (recall, missed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored_point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            score_explanation: None,
+        }
+    }
+
+    #[test]
+    fn test_search_recall() {
+        let exact = vec![
+            scored_point(1, 0.9),
+            scored_point(2, 0.8),
+            scored_point(3, 0.7),
+            scored_point(4, 0.6),
+        ];
+        let approximate = vec![
+            scored_point(1, 0.9),
+            scored_point(3, 0.7),
+            scored_point(5, 0.65),
+            scored_point(4, 0.6),
+        ];
+
+        let (recall, missed) = recall_of(&approximate, &exact);
+        assert_eq!(recall, 0.75);
+        assert_eq!(missed, vec![2.into()]);
+
+        assert_eq!(recall_of(&approximate, &[]), (1.0, Vec::new()));
+
+        let params = ground_truth_params(Some(SearchParams {
+            hnsw_ef: Some(16),
+            indexed_only: true,
+            ..Default::default()
+        }));
+        assert!(params.exact);
+        assert!(!params.indexed_only);
+        assert_eq!(params.hnsw_ef, Some(16));
+        assert!(
+            params
+                .quantization
+                .is_some_and(|quantization| quantization.ignore)
+        );
+    }
+}
@@ -3,18 +3,23 @@ use std::time::Duration;
 
 use api::rest::models::InferenceUsage;
 use api::rest::*;
+use chrono::{DateTime, Utc};
 use collection::collection::Collection;
-use collection::operations::conversions::write_ordering_from_proto;
+use collection::operations::conversions::{update_ack_from_proto, write_ordering_from_proto};
 use collection::operations::point_ops::*;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::types::{CollectionError, CollectionResult, UpdateResult};
+use collection::operations::types::{
+    CollectionError, CollectionResult, InsertPointsResult, UpdateResult,
+};
 use collection::operations::vector_ops::*;
 use collection::operations::verification::*;
 use collection::shards::shard::ShardId;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use schemars::JsonSchema;
 use segment::json_path::JsonPath;
-use segment::types::{Filter, PayloadFieldSchema, PayloadKeyType, StrictModeConfig};
+use segment::types::{
+    Filter, PayloadFieldSchema, PayloadKeyType, PointIdType, SeqNumberType, StrictModeConfig,
+};
 use serde::{Deserialize, Serialize};
 use shard::operations::payload_ops::*;
 use shard::operations::*;
@@ -37,6 +42,9 @@ pub struct UpdateParams {
     pub wait: bool,
     #[serde(default)]
     pub ordering: WriteOrdering,
+    /// When to acknowledge the update, takes precedence over `wait`
+    #[serde(default)]
+    pub ack: Option<UpdateAck>,
     #[serde(default)]
     pub timeout: Option<Duration>,
 }
@@ -45,17 +53,23 @@ impl UpdateParams {
     pub fn from_grpc(
         wait: Option<bool>,
         ordering: Option<api::grpc::qdrant::WriteOrdering>,
+        ack: Option<i32>,
         timeout: Option<Duration>,
     ) -> tonic::Result<Self> {
         let params = Self {
             wait: wait.unwrap_or(false),
             ordering: write_ordering_from_proto(ordering)?,
+            ack: update_ack_from_proto(ack)?,
             timeout,
         };
 
         Ok(params)
     }
 
+    pub fn ack(&self) -> UpdateAck {
+        self.ack.unwrap_or(UpdateAck::from_wait(self.wait))
+    }
+
     pub(crate) fn timeout_as_secs(&self) -> Option<usize> {
         self.timeout.map(|timeout| timeout.as_secs() as usize)
     }
@@ -65,6 +79,8 @@ impl UpdateParams {
 pub struct InternalUpdateParams {
     pub shard_id: Option<ShardId>,
     pub clock_tag: Option<ClockTag>,
+    /// Time points are soft deleted at, as set by the leader
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl InternalUpdateParams {
@@ -75,6 +91,7 @@ impl InternalUpdateParams {
         Self {
             shard_id,
             clock_tag: clock_tag.map(ClockTag::from),
+            deleted_at: None,
         }
     }
 }
@@ -302,35 +319,45 @@ pub async fn do_upsert_points(
         )
         .await?;
 
-    let (operation, shard_key, usage, update_filter) = match operation {
+    let (operation, shard_key, usage, update_filter, precondition) = match operation {
         PointInsertOperations::PointsBatch(batch) => {
             let PointsBatch {
                 batch,
                 shard_key,
                 update_filter,
+                if_version,
+                if_payload_matches,
+                if_not_exists,
             } = batch;
+            let precondition = upsert_precondition(if_version, if_payload_matches, if_not_exists)?;
             let (batch, usage) = convert_batch(batch, inference_params).await?;
             let operation = PointInsertOperationsInternal::PointsBatch(batch);
-            (operation, shard_key, usage, update_filter)
+            (operation, shard_key, usage, update_filter, precondition)
         }
         PointInsertOperations::PointsList(list) => {
             let PointsList {
                 points,
                 shard_key,
                 update_filter,
+                if_version,
+                if_payload_matches,
+                if_not_exists,
             } = list;
+            let precondition = upsert_precondition(if_version, if_payload_matches, if_not_exists)?;
             let (list, usage) =
                 convert_point_struct(points, InferenceType::Update, inference_params).await?;
             let operation = PointInsertOperationsInternal::PointsList(list);
-            (operation, shard_key, usage, update_filter)
+            (operation, shard_key, usage, update_filter, precondition)
         }
     };
 
-    let operation = if let Some(condition) = update_filter {
+    let operation = if update_filter.is_some() || precondition.is_some() {
         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsConditional(
             ConditionalInsertOperationInternal {
                 points_op: operation,
-                condition,
+                // Empty filter matches all points, so no point is excluded from the update
+                condition: update_filter.unwrap_or_default(),
+                precondition,
             },
         ))
     } else {
@@ -352,6 +379,104 @@ pub async fn do_upsert_points(
     Ok((result, usage))
 }
 
+/// Precondition of an upsert from the optional parameters of a request, `None` if none is set
+fn upsert_precondition(
+    if_version: Option<SeqNumberType>,
+    if_payload_matches: Option<Filter>,
+    if_not_exists: Option<bool>,
+) -> Result<Option<UpdatePrecondition>, StorageError> {
+    let precondition = UpdatePrecondition::new(if_version, if_payload_matches);
+
+    if !if_not_exists.unwrap_or_default() {
+        return Ok(precondition);
+    }
+
+    if precondition.is_some() {
+        return Err(StorageError::bad_request(
+            "Preconditions on existing points can't be combined with if_not_exists",
+        ));
+    }
+
+    Ok(Some(UpdatePrecondition::not_exists()))
+}
+
+/// Upsert points with ids assigned by the server.
+///
+/// Returns the assigned ids in the order of the points in the request.
+#[expect(clippy::too_many_arguments)]
+pub async fn do_insert_points(
+    toc_provider: impl CheckedTocProvider,
+    collection_name: String,
+    request: InsertPoints,
+    internal_params: InternalUpdateParams,
+    params: UpdateParams,
+    access: Access,
+    inference_params: InferenceParams,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<(InsertPointsResult, Option<models::InferenceUsage>), StorageError> {
+    let toc = toc_provider
+        .check_strict_mode(
+            &request,
+            &collection_name,
+            params.timeout_as_secs(),
+            &access,
+        )
+        .await?;
+
+    let InsertPoints {
+        points,
+        id_generation,
+        shard_key,
+    } = request;
+
+    let assigned_ids = toc
+        .point_id_generator()
+        .generate(id_generation, points.len());
+
+    let points = assigned_ids
+        .iter()
+        .zip(points)
+        .map(|(&id, point)| {
+            let InsertPointStruct { vector, payload } = point;
+            PointStruct {
+                id,
+                vector,
+                payload,
+            }
+        })
+        .collect();
+
+    let operation = PointInsertOperations::PointsList(PointsList {
+        points,
+        shard_key,
+        update_filter: None,
+        if_version: None,
+        if_payload_matches: None,
+        // Assigned ids may collide with existing points, which must not be overwritten
+        if_not_exists: Some(true),
+    });
+
+    // Strict mode is already checked above
+    let (result, usage) = do_upsert_points(
+        UncheckedTocProvider::new_unchecked(toc),
+        collection_name,
+        operation,
+        internal_params,
+        params,
+        access,
+        inference_params,
+        hw_measurement_acc,
+    )
+    .await?;
+
+    let result = InsertPointsResult {
+        result,
+        assigned_ids,
+    };
+
+    Ok((result, usage))
+}
+
 pub async fn do_delete_points(
     toc_provider: impl CheckedTocProvider,
     collection_name: String,
@@ -366,11 +491,34 @@ pub async fn do_delete_points(
         .await?;
 
     let (operation, shard_key) = match points {
-        PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
-            (PointOperations::DeletePoints { ids: points }, shard_key)
+        PointsSelector::PointIdsSelector(PointIdsList {
+            points,
+            shard_key,
+            if_version,
+            if_payload_matches,
+        }) => {
+            let operation = PointOperations::DeletePoints {
+                ids: points,
+                precondition: UpdatePrecondition::new(if_version, if_payload_matches),
+                deleted_at: None,
+            };
+            (operation, shard_key)
         }
-        PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
-            (PointOperations::DeletePointsByFilter(filter), shard_key)
+        PointsSelector::FilterSelector(FilterSelector {
+            filter,
+            shard_key,
+            if_version,
+            if_payload_matches,
+        }) => {
+            if if_version.is_some() || if_payload_matches.is_some() {
+                return Err(StorageError::bad_request(
+                    "Preconditions require selecting points by ID",
+                ));
+            }
+            (
+                PointOperations::DeletePointsByFilter(filter.into()),
+                shard_key,
+            )
         }
     };
 
@@ -389,6 +537,45 @@ pub async fn do_delete_points(
     .await
 }
 
+/// Restore soft deleted points of the collection.
+///
+/// Points which were not deleted, or whose tombstones were already purged, are skipped.
+pub async fn do_restore_points(
+    toc_provider: impl CheckedTocProvider,
+    collection_name: String,
+    request: RestorePoints,
+    internal_params: InternalUpdateParams,
+    params: UpdateParams,
+    access: Access,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<UpdateResult, StorageError> {
+    let toc = toc_provider
+        .check_strict_mode(
+            &request,
+            &collection_name,
+            params.timeout_as_secs(),
+            &access,
+        )
+        .await?;
+
+    let RestorePoints { points, shard_key } = request;
+
+    let operation =
+        CollectionUpdateOperations::PointOperation(PointOperations::RestorePoints { ids: points });
+
+    update(
+        toc,
+        &collection_name,
+        operation,
+        internal_params,
+        params,
+        shard_key,
+        access,
+        hw_measurement_acc,
+    )
+    .await
+}
+
 #[expect(clippy::too_many_arguments)]
 pub async fn do_update_vectors(
     toc_provider: impl CheckedTocProvider,
@@ -538,14 +725,19 @@ pub async fn do_set_payload(
         filter,
         shard_key,
         key,
+        if_version,
+        if_payload_matches,
     } = operation;
 
+    let precondition = payload_precondition(points.as_ref(), if_version, if_payload_matches)?;
+
     let operation =
         CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
             payload,
             points,
             filter,
             key,
+            precondition,
         }));
 
     update(
@@ -585,8 +777,12 @@ pub async fn do_overwrite_payload(
         filter,
         shard_key,
         key: _,
+        if_version,
+        if_payload_matches,
     } = operation;
 
+    let precondition = payload_precondition(points.as_ref(), if_version, if_payload_matches)?;
+
     let operation =
         CollectionUpdateOperations::PayloadOperation(PayloadOps::OverwritePayload(SetPayloadOp {
             payload,
@@ -594,6 +790,7 @@ pub async fn do_overwrite_payload(
             filter,
             // overwrite operation doesn't support payload selector
             key: None,
+            precondition,
         }));
 
     update(
@@ -668,10 +865,30 @@ pub async fn do_clear_payload(
         .await?;
 
     let (point_operation, shard_key) = match points {
-        PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
+        PointsSelector::PointIdsSelector(PointIdsList {
+            points,
+            shard_key,
+            if_version,
+            if_payload_matches,
+        }) => {
+            if if_version.is_some() || if_payload_matches.is_some() {
+                return Err(StorageError::bad_request(
+                    "Preconditions are not supported for clearing payload",
+                ));
+            }
             (PayloadOps::ClearPayload { points }, shard_key)
         }
-        PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
+        PointsSelector::FilterSelector(FilterSelector {
+            filter,
+            shard_key,
+            if_version,
+            if_payload_matches,
+        }) => {
+            if if_version.is_some() || if_payload_matches.is_some() {
+                return Err(StorageError::bad_request(
+                    "Preconditions are not supported for clearing payload",
+                ));
+            }
             (PayloadOps::ClearPayloadByFilter(filter), shard_key)
         }
     };
@@ -999,7 +1216,7 @@ pub async fn do_delete_index_internal(
 pub async fn update(
     toc: &TableOfContent,
     collection_name: &str,
-    operation: CollectionUpdateOperations,
+    mut operation: CollectionUpdateOperations,
     internal_params: InternalUpdateParams,
     params: UpdateParams,
     shard_key: Option<ShardKeySelector>,
@@ -1009,11 +1226,18 @@ pub async fn update(
     let InternalUpdateParams {
         shard_id,
         clock_tag,
+        deleted_at,
     } = internal_params;
 
+    if let Some(deleted_at) = deleted_at {
+        operation.set_deleted_at(deleted_at);
+    }
+
+    let ack = params.ack();
     let UpdateParams {
-        wait,
+        wait: _,
         ordering,
+        ack: _,
         timeout: _,
     } = params;
 
@@ -1051,7 +1275,7 @@ pub async fn update(
     toc.update(
         collection_name,
         OperationWithClockTag::new(operation, clock_tag),
-        wait,
+        ack,
         ordering,
         shard_selector,
         access,
@@ -1090,3 +1314,23 @@ fn get_shard_selector_for_update(
         (None, None) => ShardSelectorInternal::Empty,
     }
 }
+
+/// Precondition of a payload update.
+///
+/// Preconditions are checked against the listed points, so they can't be used
+/// when points are selected by filter.
+fn payload_precondition(
+    points: Option<&Vec<PointIdType>>,
+    if_version: Option<SeqNumberType>,
+    if_payload_matches: Option<Filter>,
+) -> Result<Option<UpdatePrecondition>, StorageError> {
+    let precondition = UpdatePrecondition::new(if_version, if_payload_matches);
+
+    if precondition.is_some() && points.is_none() {
+        return Err(StorageError::bad_request(
+            "Preconditions require selecting points by ID",
+        ));
+    }
+
+    Ok(precondition)
+}
@@ -10,7 +10,7 @@ use api::rest::{
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
-use collection::operations::point_ops::{PointsSelector, WriteOrdering};
+use collection::operations::point_ops::{PointsSelector, UpdateAck, WriteOrdering};
 use collection::operations::snapshot_ops::{
     ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
 };
@@ -98,6 +98,7 @@ struct AllDefinitions {
     bn: Usage,
     bo: ShardKeysResponse,
     bp: OptimizationsResponse,
+    bq: UpdateAck,
 }
 
 fn save_schema<T: JsonSchema>() {
@@ -13,8 +13,8 @@ use api::grpc::qdrant::{
     RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints,
     SearchBatchResponse, SearchGroupsResponse, SearchMatrixOffsets, SearchMatrixOffsetsResponse,
     SearchMatrixPairs, SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups,
-    SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse,
-    UpdatePointVectors, UpsertPoints,
+    SearchPoints, SearchResponse, SetPayloadPoints, UpdateAck, UpdateBatchPoints,
+    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
 use collection::operations::types::CoreSearchRequest;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
@@ -59,6 +59,15 @@ impl PointsService {
     }
 }
 
+/// Whether an update request waits for the changes to be applied, `ack` takes precedence
+fn update_waits(wait: Option<bool>, ack: Option<i32>) -> bool {
+    match ack.map(UpdateAck::try_from) {
+        Some(Ok(UpdateAck::None | UpdateAck::Queued)) => false,
+        Some(Ok(UpdateAck::Applied | UpdateAck::Replicated)) => true,
+        Some(Err(_)) | None => wait.unwrap_or(false),
+    }
+}
+
 #[tonic::async_trait]
 impl Points for PointsService {
     async fn upsert(
@@ -73,7 +82,7 @@ impl Points for PointsService {
         let inference_params = InferenceParams::new(inference_token, timeout);
 
         let collection_name = request.get_ref().collection_name.clone();
-        let wait = Some(request.get_ref().wait.unwrap_or(false));
+        let wait = Some(update_waits(request.get_ref().wait, request.get_ref().ack));
         let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
 
         upsert(
@@ -96,7 +105,7 @@ impl Points for PointsService {
 
         let access = extract_access(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
-        let wait = Some(request.get_ref().wait.unwrap_or(false));
+        let wait = Some(update_waits(request.get_ref().wait, request.get_ref().ack));
         let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
 
         delete(
@@ -142,7 +151,7 @@ impl Points for PointsService {
         let inference_params = InferenceParams::new(inference_token, timeout);
 
         let collection_name = request.get_ref().collection_name.clone();
-        let wait = Some(request.get_ref().wait.unwrap_or(false));
+        let wait = Some(update_waits(request.get_ref().wait, request.get_ref().ack));
         let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
 
         update_vectors(
@@ -190,7 +199,7 @@ impl Points for PointsService {
         let access = extract_access(&mut request);
 
         let collection_name = request.get_ref().collection_name.clone();
-        let wait = Some(request.get_ref().wait.unwrap_or(false));
+        let wait = Some(update_waits(request.get_ref().wait, request.get_ref().ack));
         let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
 
         set_payload(
@@ -213,7 +222,7 @@ impl Points for PointsService {
         let access = extract_access(&mut request);
 
         let collection_name = request.get_ref().collection_name.clone();
-        let wait = Some(request.get_ref().wait.unwrap_or(false));
+        let wait = Some(update_waits(request.get_ref().wait, request.get_ref().ack));
         let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
 
         overwrite_payload(
@@ -236,7 +245,7 @@ impl Points for PointsService {
         let access = extract_access(&mut request);
 
         let collection_name = request.get_ref().collection_name.clone();
-        let wait = Some(request.get_ref().wait.unwrap_or(false));
+        let wait = Some(update_waits(request.get_ref().wait, request.get_ref().ack));
         let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
 
         delete_payload(
@@ -259,7 +268,7 @@ impl Points for PointsService {
         let access = extract_access(&mut request);
 
         let collection_name = request.get_ref().collection_name.clone();
-        let wait = Some(request.get_ref().wait.unwrap_or(false));
+        let wait = Some(update_waits(request.get_ref().wait, request.get_ref().ack));
         let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
 
         clear_payload(
@@ -285,7 +294,7 @@ impl Points for PointsService {
         let inference_params = InferenceParams::new(inference_token, timeout);
 
         let collection_name = request.get_ref().collection_name.clone();
-        let wait = Some(request.get_ref().wait.unwrap_or(false));
+        let wait = Some(update_waits(request.get_ref().wait, request.get_ref().ack));
         let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
 
         update_batch(
@@ -307,7 +316,7 @@ impl Points for PointsService {
 
         let access = extract_access(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
-        let wait = Some(request.get_ref().wait.unwrap_or(false));
+        let wait = Some(update_waits(request.get_ref().wait, request.get_ref().ack));
         let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
 
         create_field_index(
@@ -549,6 +558,7 @@ impl Points for PointsService {
             discover_points,
             read_consistency,
             timeout,
+            context,
         } = request.into_inner();
 
         let hw_metrics =
@@ -557,6 +567,7 @@ impl Points for PointsService {
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             &collection_name,
             discover_points,
+            context,
             read_consistency,
             access,
             timeout.map(Duration::from_secs),
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use api::grpc::HardwareUsage;
+use api::grpc::conversions::try_date_time_from_proto;
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
@@ -12,8 +13,9 @@ use api::grpc::qdrant::{
     FacetResponseInternal, GetPointsInternal, GetResponse, IntermediateResult,
     PointsOperationResponseInternal, QueryBatchPointsInternal, QueryBatchResponseInternal,
     QueryResultInternal, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
-    ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
-    SyncPointsInternal, UpdateBatchInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    RestorePointsInternal, ScrollPointsInternal, ScrollResponse, SearchBatchResponse,
+    SetPayloadPointsInternal, SyncPointsInternal, UpdateBatchInternal, UpdateVectorsInternal,
+    UpsertPointsInternal,
 };
 use api::grpc::update_operation::Update;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
@@ -117,10 +119,19 @@ impl PointsInternalService {
             delete_points,
             shard_id,
             clock_tag,
+            deleted_at,
         } = delete_points_internal;
 
         let delete_points = extract_internal_request(delete_points)?;
 
+        let internal_params = InternalUpdateParams {
+            deleted_at: deleted_at
+                .map(try_date_time_from_proto)
+                .transpose()?
+                .map(|deleted_at| deleted_at.0),
+            ..InternalUpdateParams::from_grpc(shard_id, clock_tag)
+        };
+
         let hw_metrics = self.get_request_collection_hw_usage_counter_for_internal(
             delete_points.collection_name.clone(),
         );
@@ -128,6 +139,32 @@ impl PointsInternalService {
         delete(
             UncheckedTocProvider::new_unchecked(&self.toc),
             delete_points,
+            internal_params,
+            FULL_ACCESS.clone(),
+            hw_metrics,
+        )
+        .await
+    }
+
+    async fn restore_internal(
+        &self,
+        restore_points_internal: RestorePointsInternal,
+    ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+        let RestorePointsInternal {
+            restore_points,
+            shard_id,
+            clock_tag,
+        } = restore_points_internal;
+
+        let restore_points = extract_internal_request(restore_points)?;
+
+        let hw_metrics = self.get_request_collection_hw_usage_counter_for_internal(
+            restore_points.collection_name.clone(),
+        );
+
+        restore(
+            UncheckedTocProvider::new_unchecked(&self.toc),
+            restore_points,
             InternalUpdateParams::from_grpc(shard_id, clock_tag),
             FULL_ACCESS.clone(),
             hw_metrics,
@@ -472,6 +509,15 @@ impl PointsInternal for PointsInternalService {
         self.delete_internal(request.into_inner()).await
     }
 
+    async fn restore(
+        &self,
+        request: Request<RestorePointsInternal>,
+    ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        self.restore_internal(request.into_inner()).await
+    }
+
     async fn update_vectors(
         &self,
         request: Request<UpdateVectorsInternal>,
@@ -583,6 +629,7 @@ impl PointsInternal for PointsInternalService {
                             .await?
                     }
                     Update::Delete(delete) => self.delete_internal(delete).await?,
+                    Update::Restore(restore) => self.restore_internal(restore).await?,
                     Update::UpdateVectors(update_vectors) => {
                         self.update_vectors_internal(update_vectors, inference_params.clone())
                             .await?
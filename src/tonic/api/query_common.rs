@@ -2,13 +2,13 @@ use std::time::{Duration, Instant};
 
 use api::conversions::json::json_path_from_proto;
 use api::grpc::qdrant::{
-    BatchResult, CoreSearchPoints, CountPoints, CountResponse, DiscoverBatchResponse,
-    DiscoverPoints, DiscoverResponse, FacetCounts, FacetResponse, GetPoints, GetResponse,
-    GroupsResult, QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints,
-    QueryResponse, ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse, SearchMatrixPoints,
-    SearchPointGroups, SearchPoints, SearchResponse,
+    BatchResult, ContextExamplePair as ContextExamplePairGrpc, CoreSearchPoints, CountPoints,
+    CountResponse, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts,
+    FacetResponse, GetPoints, GetResponse, GroupsResult, QueryBatchResponse, QueryGroupsResponse,
+    QueryPointGroups, QueryPoints, QueryResponse, ReadConsistency as ReadConsistencyGrpc,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse,
+    SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse,
 };
 use api::grpc::{InferenceUsage, Usage};
 use api::rest::OrderByInterface;
@@ -19,7 +19,8 @@ use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::try_discover_request_from_grpc;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, PointRequestInternal, ScrollRequestInternal, default_exact_count,
+    ContextExamplePair, CoreSearchRequest, PointRequestInternal, ScrollRequestInternal,
+    default_exact_count,
 };
 use collection::shards::shard::ShardId;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
@@ -27,7 +28,7 @@ use segment::data_types::facets::FacetParams;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, NamedQuery, VectorInternal};
 use shard::query::query_enum::QueryEnum;
-use shard::search::CoreSearchRequestBatch;
+use shard::search::{CoreSearchRequestBatch, SearchMmr};
 use storage::content_manager::toc::TableOfContent;
 use storage::content_manager::toc::request_hw_counter::RequestHwCounter;
 use storage::rbac::Access;
@@ -81,6 +82,8 @@ pub async fn search(
         timeout,
         shard_key_selector,
         sparse_indices,
+        with_explanation,
+        mmr,
     } = search_points;
 
     let vector_internal =
@@ -104,7 +107,18 @@ pub async fn search(
                 .unwrap_or_default(),
         ),
         score_threshold,
-        with_explanation: false, // no gRPC
+        with_explanation: with_explanation.unwrap_or(false),
+        with_explanation_bundle: false,
+        explanation_verbosity: Default::default(),
+        explanation_params: Default::default(),
+        mmr: mmr.map(|mmr| {
+            SearchMmr::new(
+                mmr.diversity,
+                mmr.candidates_limit.map(|limit| limit as usize),
+                limit as usize,
+            )
+        }),
+        distinct_by: None, // no gRPC
     };
 
     let toc = toc_provider
@@ -174,8 +188,10 @@ pub async fn core_search_batch(
         access,
         timeout,
         request_hw_counter.get_counter(),
+        false,
     )
-    .await?;
+    .await?
+    .results;
 
     let response = SearchBatchResponse {
         result: scored_points
@@ -520,16 +536,23 @@ pub async fn discover_batch(
     toc_provider: impl CheckedTocProvider,
     collection_name: &str,
     discover_points: Vec<DiscoverPoints>,
+    shared_context: Vec<ContextExamplePairGrpc>,
     read_consistency: Option<ReadConsistencyGrpc>,
     access: Access,
     timeout: Option<Duration>,
     request_hw_counter: RequestHwCounter,
 ) -> Result<Response<DiscoverBatchResponse>, Status> {
+    let shared_context = shared_context
+        .into_iter()
+        .map(ContextExamplePair::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
     let mut requests = Vec::with_capacity(discover_points.len());
 
     for discovery_request in discover_points {
-        let (internal_request, _collection_name, _consistency, _timeout, shard_key_selector) =
+        let (mut internal_request, _collection_name, _consistency, _timeout, shard_key_selector) =
             try_discover_request_from_grpc(discovery_request)?;
+        internal_request.extend_context(&shared_context);
         let shard_selector = convert_shard_selector_for_read(None, shard_key_selector)?;
         requests.push((internal_request, shard_selector));
     }
@@ -1046,6 +1069,7 @@ pub async fn search_points_matrix(
             .map_err(|_| Status::invalid_argument("could not parse 'limit' param into usize"))?
             .unwrap_or(CollectionSearchMatrixRequest::DEFAULT_LIMIT_PER_SAMPLE),
         using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned()),
+        with_explanation: false, // no gRPC
     };
 
     let toc = toc_provider
@@ -8,13 +8,16 @@ use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointS
 use api::grpc::qdrant::{
     ClearPayloadPoints, CreateFieldIndexCollection, DeleteFieldIndexCollection,
     DeletePayloadPoints, DeletePointVectors, DeletePoints, FieldType, PayloadIndexParams,
-    PointsOperationResponseInternal, PointsSelector, SetPayloadPoints, SyncPoints,
+    PointsOperationResponseInternal, PointsSelector, RestorePoints, SetPayloadPoints, SyncPoints,
     UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
     points_update_operation,
 };
 use api::grpc::{HardwareUsage, InferenceUsage, Usage};
 use api::rest::schema::{PointInsertOperations, PointsList};
-use api::rest::{PointStruct, PointVectors, ShardKeySelector, UpdateVectors, VectorStruct};
+use api::rest::{
+    InsertPointStruct, InsertPoints, PointStruct, PointVectors, ShardKeySelector, UpdateVectors,
+    VectorStruct,
+};
 use collection::operations::CollectionUpdateOperations;
 use collection::operations::conversions::try_points_selector_from_grpc;
 use collection::operations::payload_ops::DeletePayload;
@@ -53,8 +56,71 @@ pub async fn upsert(
         shard_key_selector,
         update_filter,
         timeout,
+        if_version,
+        if_payload_matches,
+        id_generation,
+        if_not_exists,
+        ack,
     } = upsert_points;
 
+    if let Some(id_generation) = id_generation {
+        if update_filter.is_some() || if_version.is_some() || if_payload_matches.is_some() {
+            return Err(Status::invalid_argument(
+                "Update filter and preconditions can't be used if ids are assigned by the server",
+            ));
+        }
+
+        let id_generation = grpc::PointIdGeneration::try_from(id_generation).map_err(|_| {
+            Status::invalid_argument(format!("Unknown id generation: {id_generation}"))
+        })?;
+
+        let points: Result<_, _> = points
+            .into_iter()
+            .map(InsertPointStruct::try_from)
+            .collect();
+
+        let request = InsertPoints {
+            points: points?,
+            id_generation: id_generation.into(),
+            shard_key: shard_key_selector
+                .map(ShardKeySelector::try_from)
+                .transpose()?,
+        };
+
+        let timing = Instant::now();
+        let (result, inference_usage) = do_insert_points(
+            toc_provider,
+            collection_name,
+            request,
+            internal_params,
+            UpdateParams::from_grpc(
+                wait,
+                ordering,
+                ack,
+                timeout.map(std::time::Duration::from_secs),
+            )?,
+            access,
+            inference_params,
+            request_hw_counter.get_counter(),
+        )
+        .await?;
+
+        let mut response = points_operation_response_internal_with_inference_usage(
+            timing,
+            result.result,
+            request_hw_counter.to_grpc_api(),
+            inference_usage.map(grpc::InferenceUsage::from),
+        );
+        if let Some(update_result) = &mut response.result {
+            update_result.assigned_ids = result
+                .assigned_ids
+                .into_iter()
+                .map(grpc::PointId::from)
+                .collect();
+        }
+        return Ok(Response::new(response));
+    }
+
     let points: Result<_, _> = points.into_iter().map(PointStruct::try_from).collect();
 
     let operation = PointInsertOperations::PointsList(PointsList {
@@ -65,6 +131,9 @@ pub async fn upsert(
         update_filter: update_filter
             .map(segment::types::Filter::try_from)
             .transpose()?,
+        if_version,
+        if_payload_matches: if_payload_matches.map(Filter::try_from).transpose()?,
+        if_not_exists,
     });
 
     let timing = Instant::now();
@@ -73,7 +142,12 @@ pub async fn upsert(
         collection_name,
         operation,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         inference_params,
         request_hw_counter.get_counter(),
@@ -103,20 +177,88 @@ pub async fn delete(
         ordering,
         shard_key_selector,
         timeout,
+        if_version,
+        if_payload_matches,
+        ack,
     } = delete_points;
 
-    let points_selector = match points {
+    let mut points_selector = match points {
         None => return Err(Status::invalid_argument("PointSelector is missing")),
         Some(p) => try_points_selector_from_grpc(p, shard_key_selector)?,
     };
 
+    match &mut points_selector {
+        point_ops::PointsSelector::PointIdsSelector(ids) => {
+            ids.if_version = if_version;
+            ids.if_payload_matches = if_payload_matches.map(Filter::try_from).transpose()?;
+        }
+        point_ops::PointsSelector::FilterSelector(_) => {
+            if if_version.is_some() || if_payload_matches.is_some() {
+                return Err(Status::invalid_argument(
+                    "Preconditions require selecting points by ID",
+                ));
+            }
+        }
+    }
+
     let timing = Instant::now();
     let result = do_delete_points(
         toc_provider,
         collection_name,
         points_selector,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
+        access,
+        request_hw_counter.get_counter(),
+    )
+    .await?;
+
+    let response =
+        points_operation_response_internal(timing, result, request_hw_counter.to_grpc_api());
+    Ok(Response::new(response))
+}
+
+pub async fn restore(
+    toc_provider: impl CheckedTocProvider,
+    restore_points: RestorePoints,
+    internal_params: InternalUpdateParams,
+    access: Access,
+    request_hw_counter: RequestHwCounter,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let RestorePoints {
+        collection_name,
+        wait,
+        ids,
+        ordering,
+        timeout,
+        ack,
+    } = restore_points;
+
+    let points = ids
+        .into_iter()
+        .map(|id| id.try_into())
+        .collect::<Result<_, _>>()?;
+
+    let timing = Instant::now();
+    let result = do_restore_points(
+        toc_provider,
+        collection_name,
+        point_ops::RestorePoints {
+            points,
+            shard_key: None,
+        },
+        internal_params,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         request_hw_counter.get_counter(),
     )
@@ -143,6 +285,7 @@ pub async fn update_vectors(
         shard_key_selector,
         update_filter,
         timeout,
+        ack,
     } = update_point_vectors;
 
     // Build list of operation points
@@ -175,7 +318,12 @@ pub async fn update_vectors(
         collection_name,
         operation,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         inference_params,
         request_hw_counter.get_counter(),
@@ -206,6 +354,7 @@ pub async fn delete_vectors(
         ordering,
         shard_key_selector,
         timeout,
+        ack,
     } = delete_point_vectors;
 
     let (points, filter) = extract_points_selector(points_selector)?;
@@ -229,7 +378,12 @@ pub async fn delete_vectors(
         collection_name,
         operation,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         request_hw_counter.get_counter(),
     )
@@ -256,6 +410,9 @@ pub async fn set_payload(
         shard_key_selector,
         key,
         timeout,
+        if_version,
+        if_payload_matches,
+        ack,
     } = set_payload_points;
     let key = key.map(|k| json_path_from_proto(&k)).transpose()?;
 
@@ -268,6 +425,8 @@ pub async fn set_payload(
             .map(ShardKeySelector::try_from)
             .transpose()?,
         key,
+        if_version,
+        if_payload_matches: if_payload_matches.map(Filter::try_from).transpose()?,
     };
 
     let timing = Instant::now();
@@ -276,7 +435,12 @@ pub async fn set_payload(
         collection_name,
         operation,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         request_hw_counter.get_counter(),
     )
@@ -302,6 +466,9 @@ pub async fn overwrite_payload(
         ordering,
         shard_key_selector,
         timeout,
+        if_version,
+        if_payload_matches,
+        ack,
         ..
     } = set_payload_points;
 
@@ -315,6 +482,8 @@ pub async fn overwrite_payload(
             .transpose()?,
         // overwrite operation don't support indicate path of property
         key: None,
+        if_version,
+        if_payload_matches: if_payload_matches.map(Filter::try_from).transpose()?,
     };
 
     let timing = Instant::now();
@@ -323,7 +492,12 @@ pub async fn overwrite_payload(
         collection_name,
         operation,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         request_hw_counter.get_counter(),
     )
@@ -349,6 +523,7 @@ pub async fn delete_payload(
         ordering,
         shard_key_selector,
         timeout,
+        ack,
     } = delete_payload_points;
     let keys = keys.iter().map(|k| json_path_from_proto(k)).try_collect()?;
 
@@ -368,7 +543,12 @@ pub async fn delete_payload(
         collection_name,
         operation,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         request_hw_counter.get_counter(),
     )
@@ -393,6 +573,7 @@ pub async fn clear_payload(
         ordering,
         shard_key_selector,
         timeout,
+        ack,
     } = clear_payload_points;
 
     let points_selector = match points {
@@ -406,7 +587,12 @@ pub async fn clear_payload(
         collection_name,
         points_selector,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         request_hw_counter.get_counter(),
     )
@@ -431,6 +617,7 @@ pub async fn update_batch(
         operations,
         ordering,
         timeout,
+        ack,
     } = update_batch_points;
 
     let timing = Instant::now();
@@ -459,6 +646,11 @@ pub async fn update_batch(
                         shard_key_selector,
                         update_filter,
                         timeout,
+                        ack,
+                        if_version: None,
+                        if_payload_matches: None,
+                        id_generation: None,
+                        if_not_exists: None,
                     },
                     internal_params,
                     access.clone(),
@@ -477,6 +669,9 @@ pub async fn update_batch(
                         ordering,
                         shard_key_selector: None,
                         timeout,
+                        ack,
+                        if_version: None,
+                        if_payload_matches: None,
                     },
                     internal_params,
                     access.clone(),
@@ -503,6 +698,9 @@ pub async fn update_batch(
                         shard_key_selector,
                         key,
                         timeout,
+                        ack,
+                        if_version: None,
+                        if_payload_matches: None,
                     },
                     internal_params,
                     access.clone(),
@@ -530,6 +728,9 @@ pub async fn update_batch(
                         // overwrite operation doesn't support it
                         key: None,
                         timeout,
+                        ack,
+                        if_version: None,
+                        if_payload_matches: None,
                     },
                     internal_params,
                     access.clone(),
@@ -554,6 +755,7 @@ pub async fn update_batch(
                         ordering,
                         shard_key_selector,
                         timeout,
+                        ack,
                     },
                     internal_params,
                     access.clone(),
@@ -574,6 +776,7 @@ pub async fn update_batch(
                         ordering,
                         shard_key_selector,
                         timeout,
+                        ack,
                     },
                     internal_params,
                     access.clone(),
@@ -598,6 +801,7 @@ pub async fn update_batch(
                         shard_key_selector,
                         update_filter,
                         timeout,
+                        ack,
                     },
                     internal_params,
                     access.clone(),
@@ -623,6 +827,7 @@ pub async fn update_batch(
                         ordering,
                         shard_key_selector,
                         timeout,
+                        ack,
                     },
                     internal_params,
                     access.clone(),
@@ -640,6 +845,7 @@ pub async fn update_batch(
                         ordering,
                         shard_key_selector: None,
                         timeout,
+                        ack,
                     },
                     internal_params,
                     access.clone(),
@@ -660,6 +866,9 @@ pub async fn update_batch(
                         ordering,
                         shard_key_selector,
                         timeout,
+                        ack,
+                        if_version: None,
+                        if_payload_matches: None,
                     },
                     internal_params,
                     access.clone(),
@@ -701,6 +910,7 @@ pub async fn create_field_index(
         field_index_params,
         ordering,
         timeout,
+        ack,
     } = create_field_index_collection;
 
     let field_name = json_path_from_proto(&field_name)?;
@@ -717,7 +927,12 @@ pub async fn create_field_index(
         collection_name,
         operation,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         request_hw_counter.get_counter(),
     )
@@ -743,6 +958,7 @@ pub async fn create_field_index_internal(
         field_index_params,
         ordering,
         timeout,
+        ack,
     } = create_field_index_collection;
 
     let field_name = json_path_from_proto(&field_name)?;
@@ -755,7 +971,12 @@ pub async fn create_field_index_internal(
         field_name,
         field_schema,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         HwMeasurementAcc::disposable(), // API unmeasured
     )
     .await?;
@@ -776,6 +997,7 @@ pub async fn delete_field_index(
         field_name,
         ordering,
         timeout,
+        ack,
     } = delete_field_index_collection;
 
     let field_name = json_path_from_proto(&field_name)?;
@@ -786,7 +1008,12 @@ pub async fn delete_field_index(
         collection_name,
         field_name,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         access,
         HwMeasurementAcc::disposable(), // API unmeasured
     )
@@ -807,6 +1034,7 @@ pub async fn delete_field_index_internal(
         field_name,
         ordering,
         timeout,
+        ack,
     } = delete_field_index_collection;
 
     let field_name = json_path_from_proto(&field_name)?;
@@ -817,7 +1045,12 @@ pub async fn delete_field_index_internal(
         collection_name,
         field_name,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         HwMeasurementAcc::disposable(), // API unmeasured
     )
     .await?;
@@ -841,6 +1074,7 @@ pub async fn sync(
         to_id,
         ordering,
         timeout,
+        ack,
     } = sync_points;
 
     let timing = Instant::now();
@@ -866,7 +1100,12 @@ pub async fn sync(
         &collection_name,
         operation,
         internal_params,
-        UpdateParams::from_grpc(wait, ordering, timeout.map(std::time::Duration::from_secs))?,
+        UpdateParams::from_grpc(
+            wait,
+            ordering,
+            ack,
+            timeout.map(std::time::Duration::from_secs),
+        )?,
         None,
         access,
         HwMeasurementAcc::disposable(), // API unmeasured
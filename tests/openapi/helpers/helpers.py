@@ -177,4 +177,25 @@ def distribution_based_score_fusion(responses: List[List[Any]], limit: int = 10)
 @lru_cache
 def qdrant_host_headers():
     headers = json.loads(QDRANT_HOST_HEADERS)
-    return headers
\ No newline at end of file
+    return headers
+
+
+def assert_explanations_consistent(points: List[Any], epsilon: float = 1e-4):
+    """Check that the explanation of every point agrees with the point itself:
+    `total_score` matches the score, `top_dimensions` are ordered by decreasing magnitude,
+    and explained dimensions exist in the returned vector, if any.
+    Points without an available explanation are skipped."""
+    for point in points:
+        explanation = point.get("score_explanation")
+        if explanation is None or explanation["status"] != "available":
+            continue
+
+        tolerance = epsilon * max(1.0, abs(point["score"]))
+        assert abs(explanation["total_score"] - point["score"]) <= tolerance, point
+
+        magnitudes = [abs(dim["contribution"]) for dim in explanation["top_dimensions"]]
+        assert magnitudes == sorted(magnitudes, reverse=True), point
+
+        vector = point.get("vector")
+        if isinstance(vector, list):
+            assert all(dim["dimension"] < len(vector) for dim in explanation["top_dimensions"]), point
@@ -0,0 +1,470 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import assert_explanations_consistent, request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search_with_explanation(collection_name, params=None, **kwargs):
+    body = {
+        "vector": [0.2, 0.1, 0.9, 0.7],
+        "limit": 3,
+        "with_explanation": True,
+        **kwargs,
+    }
+    if params is not None:
+        body["params"] = params
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_explanation_approximate_search_flag(collection_name):
+    # The collection is too small to be indexed, its segments scan all vectors even without `exact`
+    result = search_with_explanation(collection_name, params={"hnsw_ef": 1, "exact": False})
+    assert len(result) > 0
+    assert_explanations_consistent(result)
+    for point in result:
+        assert point['score_explanation']['approximate_search'] is False
+
+    result = search_with_explanation(collection_name, params={"exact": True})
+    assert len(result) > 0
+    assert_explanations_consistent(result)
+    for point in result:
+        assert point['score_explanation']['approximate_search'] is False
+
+
+def search_points(collection_name, **kwargs):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 10,
+            **kwargs,
+        },
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_explanation_does_not_change_score_threshold_results(collection_name):
+    plain = search_points(collection_name, score_threshold=1.0)
+    explained = search_points(collection_name, score_threshold=1.0, with_explanation=True)
+
+    assert [point['id'] for point in plain] == [4, 1, 3]
+    assert [(point['id'], point['score']) for point in explained] == \
+        [(point['id'], point['score']) for point in plain]
+
+    for point in explained:
+        assert point['score_explanation']['status'] == "available"
+        # Vectors are only fetched to compute explanations, they are not returned
+        assert 'vector' not in point
+
+
+def test_explanation_bundle(collection_name):
+    result = search_points(collection_name, limit=3, with_explanation=True)
+    for point in result:
+        assert 'bundle' not in point['score_explanation']
+
+    result = search_points(
+        collection_name,
+        limit=3,
+        with_explanation=True,
+        with_explanation_bundle=True,
+    )
+    assert len(result) == 3
+    for point in result:
+        bundle = point['score_explanation']['bundle']
+        assert bundle['query'] == pytest.approx([0.2, 0.1, 0.9, 0.7])
+        # Dot product: the contributions are reproduced from the bundle vectors
+        for dim in point['score_explanation']['top_dimensions']:
+            i = dim['dimension']
+            assert dim['contribution'] == pytest.approx(bundle['query'][i] * bundle['stored'][i])
+
+
+def test_minimal_explanation(collection_name):
+    full = search_points(collection_name, limit=3, with_explanation=True)
+    minimal = search_points(
+        collection_name,
+        limit=3,
+        with_explanation=True,
+        explanation_verbosity="minimal",
+    )
+    assert len(minimal) == 3
+    for full_point, minimal_point in zip(full, minimal):
+        explanation = minimal_point['score_explanation']
+        assert set(explanation) == {'total_score', 'top_dimensions'}
+        assert explanation['total_score'] == full_point['score_explanation']['total_score']
+        assert explanation['top_dimensions'] == [
+            dim['dimension'] for dim in full_point['score_explanation']['top_dimensions']
+        ]
+
+
+def test_counterfactual_explanation_of_dot_product(collection_name):
+    contribution = search_points(collection_name, limit=3, with_explanation=True)
+    counterfactual = search_points(
+        collection_name,
+        limit=3,
+        with_explanation=True,
+        explanation_mode="counterfactual",
+    )
+    # Dot product is a sum over dimensions, removing one loses exactly its contribution
+    for contribution_point, counterfactual_point in zip(contribution, counterfactual):
+        assert counterfactual_point['id'] == contribution_point['id']
+        assert (
+            counterfactual_point['score_explanation']['top_dimensions']
+            == contribution_point['score_explanation']['top_dimensions']
+        )
+
+
+
+def test_explanation_top_n(collection_name):
+    points = search_points(
+        collection_name,
+        limit=3,
+        with_explanation=True,
+        explanation_params={"top_n": 2},
+    )
+    for point in points:
+        assert len(point['score_explanation']['top_dimensions']) == 2
+
+    points = search_points(
+        collection_name,
+        limit=3,
+        with_explanation=True,
+        explanation_params={"include_all": True},
+    )
+    for point in points:
+        # All 4 dimensions of the collection vectors
+        assert len(point['score_explanation']['top_dimensions']) == 4
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3,
+            "with_explanation": True,
+            "explanation_params": {"top_n": 5000},
+        },
+    )
+    assert response.status_code == 422
+
+def test_explanation_of_every_group_member(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/groups',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "group_by": "city",
+            "group_size": 3,
+            "limit": 3,
+            "with_explanation": True,
+        },
+    )
+    assert response.ok
+
+    groups = response.json()['result']['groups']
+    berlin = next(group for group in groups if group['id'] == "Berlin")
+    assert len(berlin['hits']) == 3
+
+    for group in groups:
+        # Each member is explained against its own vector
+        assert_explanations_consistent(group['hits'])
+        for hit in group['hits']:
+            assert hit['score_explanation']['status'] == "available"
+            assert 'vector' not in hit
+
+    contributions = [
+        tuple(dim['contribution'] for dim in hit['score_explanation']['top_dimensions'])
+        for hit in berlin['hits']
+    ]
+    assert len(set(contributions)) == 3
+
+
+def test_explanation_of_every_query_group_member(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/query/groups',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "query": [0.2, 0.1, 0.9, 0.7],
+            "group_by": "city",
+            "group_size": 3,
+            "limit": 3,
+            "with_explanation": True,
+        },
+    )
+    assert response.ok
+
+    groups = response.json()['result']['groups']
+    assert len(groups) > 0
+    for group in groups:
+        assert_explanations_consistent(group['hits'])
+        for hit in group['hits']:
+            assert hit['score_explanation']['status'] == "available"
+            assert 'vector' not in hit
+
+
+def test_explanation_space_of_normalized_collection(collection_name):
+    cosine_collection_name = f"{collection_name}_cosine"
+    drop_collection(cosine_collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': cosine_collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Cosine",
+            },
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': cosine_collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [3.0, 0.0, 4.0, 0.0]},
+                {"id": 2, "vector": [0.0, 1.0, 0.0, 1.0]},
+            ]
+        }
+    )
+    assert response.ok
+
+    result = search_with_explanation(cosine_collection_name, with_vector=True)
+    assert len(result) == 2
+    for point in result:
+        # Vectors are L2-normalized on ingest, contributions are computed on stored vectors
+        assert point['score_explanation']['space'] == "processed"
+
+    stored = next(point['vector'] for point in result if point['id'] == 1)
+    assert stored == pytest.approx([0.6, 0.0, 0.8, 0.0])
+
+    drop_collection(cosine_collection_name)
+
+
+def query_with_explanation(collection_name, **kwargs):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/query',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "limit": 3,
+            "with_explanation": True,
+            **kwargs,
+        },
+    )
+    assert response.ok
+    return response.json()['result']['points']
+
+
+def create_price_index(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"field_name": "price", "field_schema": "float"},
+    )
+    assert response.ok
+
+
+def test_explanation_order_by_query_is_unavailable(collection_name):
+    create_price_index(collection_name)
+
+    points = query_with_explanation(collection_name, query={"order_by": "price"})
+    assert len(points) > 0
+    for point in points:
+        explanation = point['score_explanation']
+        assert explanation['status'] == {"unavailable": "order_by_query"}
+        assert explanation['top_dimensions'] == []
+
+
+def test_explanation_order_by_query_with_nearest_prefetch(collection_name):
+    create_price_index(collection_name)
+
+    points = query_with_explanation(
+        collection_name,
+        prefetch={"query": [0.2, 0.1, 0.9, 0.7], "limit": 5},
+        query={"order_by": "price"},
+    )
+    assert len(points) > 0
+    for point in points:
+        explanation = point['score_explanation']
+        assert explanation['status'] == "available"
+        assert len(explanation['top_dimensions']) > 0
+
+
+def test_explanation_sparse_nearest_query(collection_name):
+    points = query_with_explanation(
+        collection_name,
+        query={"indices": [1, 3, 66], "values": [1.0, 2.0, 0.5]},
+        using="sparse-text",
+    )
+    assert len(points) == 2
+
+    for point in points:
+        explanation = point['score_explanation']
+        assert explanation['status'] == "available"
+        # Sparse dimensions are the indices shared by the query and the stored vector
+        dimensions = {dim['dimension'] for dim in explanation['top_dimensions']}
+        assert len(dimensions) > 0
+        assert dimensions <= {1, 3, 66}
+
+    assert_explanations_consistent(points)
+
+
+def test_explanation_of_recommend_with_lookup_from(collection_name):
+    lookup_collection_name = f"{collection_name}_lookup"
+    drop_collection(lookup_collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': lookup_collection_name},
+        body={"vectors": {"size": 4, "distance": "Dot"}},
+    )
+    assert response.ok
+
+    # Same ids as in the searched collection, but different vectors
+    positive = [1.0, 0.0, 0.5, 0.0]
+    negative = [0.0, 0.5, 0.0, 0.5]
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': lookup_collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": positive},
+                {"id": 2, "vector": negative},
+            ]
+        },
+    )
+    assert response.ok
+
+    points = query_with_explanation(
+        collection_name,
+        query={"recommend": {"positive": [1], "negative": [2], "strategy": "average_vector"}},
+        lookup_from={"collection": lookup_collection_name},
+        with_vector=True,
+    )
+    assert len(points) > 0
+    assert_explanations_consistent(points)
+
+    # Effective query is built from the looked-up examples: avg(pos) + avg(pos) - avg(neg)
+    query = [2 * pos - neg for pos, neg in zip(positive, negative)]
+    for point in points:
+        explanation = point['score_explanation']
+        assert explanation['status'] == "available"
+        for dim in explanation['top_dimensions']:
+            i = dim['dimension']
+            assert dim['contribution'] == pytest.approx(query[i] * point['vector'][i], abs=1e-5)
+
+    drop_collection(lookup_collection_name)
+
+
+
+def test_explanation_in_query_batch(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/query/batch',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "searches": [
+                {"query": [0.2, 0.1, 0.9, 0.7], "limit": 3, "with_explanation": True},
+                {"query": [0.2, 0.1, 0.9, 0.7], "limit": 3},
+            ]
+        },
+    )
+    assert response.ok
+    explained, plain = response.json()['result']
+
+    assert len(explained['points']) == 3
+    for point in explained['points']:
+        assert point['score_explanation']['status'] == "available"
+        # Vectors are only fetched for the explanation
+        assert point.get('vector') is None
+    assert_explanations_consistent(explained['points'])
+
+    for point in plain['points']:
+        assert point.get('score_explanation') is None
+
+
+def test_explanation_in_search_batch(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/batch',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "searches": [
+                {"vector": [0.2, 0.1, 0.9, 0.7], "limit": 3, "with_explanation": True},
+                {"vector": [0.5, 0.5, 0.1, 0.1], "limit": 3},
+                {"vector": [0.9, 0.1, 0.1, 0.3], "limit": 3, "with_explanation": True},
+            ]
+        },
+    )
+    assert response.ok
+    first, plain, last = response.json()['result']
+
+    # Each explanation is computed from the query vector of its own search
+    for points in (first, last):
+        assert len(points) == 3
+        for point in points:
+            assert point['score_explanation']['status'] == "available"
+        assert_explanations_consistent(points)
+
+    for point in plain:
+        assert point.get('score_explanation') is None
+
+
+def test_explanation_of_rrf_fusion(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/query',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "prefetch": [
+                {"query": [0.2, 0.1, 0.9, 0.7], "limit": 3},
+                {"query": [0.9, 0.1, 0.1, 0.3], "limit": 3},
+            ],
+            "query": {"fusion": "rrf"},
+            "limit": 4,
+            "with_explanation": True,
+        },
+    )
+    assert response.ok
+    points = response.json()['result']['points']
+
+    assert len(points) > 0
+    for point in points:
+        explanation = point['score_explanation']
+        assert explanation['status'] == "available"
+        # One source per prefetch, adding up to the fused score
+        sources = explanation['fusion']['sources']
+        assert len(sources) == 2
+        assert any('rank' in source for source in sources)
+        assert sum(source['contribution'] for source in sources) == pytest.approx(point['score'], rel=1e-4)
+        assert explanation['total_score'] == pytest.approx(point['score'], rel=1e-4)
@@ -543,6 +543,35 @@ def test_nearest_with_mmr(collection_name):
     assert mmr_result == mmr_default_result, "MMR with explicit vs implicit defaults should produce the same output"
 
 
+def test_prefetch_score_threshold_before_fusion(collection_name):
+    def query(body):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/query",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body=body,
+        )
+        assert response.ok, response.json()
+        return response.json()["result"]["points"]
+
+    queries = [[0.1, 0.2, 0.3, 0.4], [0.5, 0.6, 0.7, 0.8]]
+
+    # Threshold each prefetch at the score of its 3rd result
+    prefetches = []
+    kept_ids = set()
+    for vector in queries:
+        points = query({"query": vector})
+        threshold = points[2]["score"]
+        prefetches.append({"query": vector, "score_threshold": threshold})
+        kept_ids.update(point["id"] for point in points if point["score"] >= threshold)
+
+    points = query({"prefetch": prefetches, "query": {"fusion": "rrf"}})
+
+    # Candidates below the threshold of their prefetch never reach the fusion
+    assert 0 < len(points) < 8
+    assert {point["id"] for point in points} == kept_ids
+
+
 @pytest.mark.parametrize("body", [
     {
         "prefetch": [
@@ -1,5 +1,5 @@
 import pytest
-from math import isclose
+from math import isclose, log
 
 from .helpers.collection_setup import basic_collection_setup, drop_collection
 from .helpers.helpers import request_with_validation
@@ -45,6 +45,11 @@ def setup(on_disk_vectors, collection_name):
             },
             lambda score, price: score + (price / (1.0 + abs(price))),
         ),
+        (
+            # boost by popularity
+            {"mult": ["$score", {"ln": {"sum": [1.0, "price"]}}]},
+            lambda score, price: score * log(1.0 + price),
+        ),
     ],
 )
 def test_formula(collection_name, formula, expecting):